@@ -6,7 +6,7 @@
 
 use std::path::{Path, PathBuf};
 
-use argus_core::ArgusError;
+use argus_core::{ArgusError, ChunkStrategy};
 use argus_repomap::walker::Language;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -138,6 +138,111 @@ pub fn chunk_file(
     Ok(chunks)
 }
 
+/// Extract chunks from a source file with the given [`ChunkStrategy`].
+///
+/// [`ChunkStrategy::ByFunction`] is [`chunk_file`]. [`ChunkStrategy::ByClass`]
+/// replaces the method chunks inside each class, `impl` block, trait, or
+/// interface with one chunk for the whole container; languages without such
+/// containers (Go, C) chunk by function. [`ChunkStrategy::ByBlock`] emits one
+/// chunk per top-level block of the file, where items on consecutive lines
+/// (imports, attributes, doc comments and the item they annotate) form one
+/// block. Every chunk keeps the line range and name of what it covers.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Parse`] if the language grammar cannot be loaded.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_core::ChunkStrategy;
+/// use argus_repomap::walker::Language;
+/// use argus_codelens::chunker::chunk_file_with;
+///
+/// let code = "struct Cache;\n\nimpl Cache {\n    fn get(&self) {}\n    fn put(&self) {}\n}\n";
+/// let chunks = chunk_file_with(Path::new("cache.rs"), code, Language::Rust, ChunkStrategy::ByClass).unwrap();
+/// let names: Vec<_> = chunks.iter().map(|c| (c.entity_type.as_str(), c.entity_name.as_str())).collect();
+/// assert_eq!(names, [("struct", "Cache"), ("impl", "Cache")]);
+/// assert_eq!((chunks[1].start_line, chunks[1].end_line), (3, 6));
+/// ```
+pub fn chunk_file_with(
+    path: &Path,
+    content: &str,
+    language: Language,
+    strategy: ChunkStrategy,
+) -> Result<Vec<CodeChunk>, ArgusError> {
+    if strategy == ChunkStrategy::ByFunction {
+        return chunk_file(path, content, language);
+    }
+    let Some(ts_language) = language.tree_sitter_language() else {
+        return Ok(Vec::new());
+    };
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&ts_language)
+        .map_err(|e| ArgusError::Parse(format!("failed to set language: {e}")))?;
+    let Some(tree) = parser.parse(content, None) else {
+        return Ok(Vec::new());
+    };
+    let source = content.as_bytes();
+    let lang_str = language_str(language);
+
+    if strategy == ChunkStrategy::ByBlock {
+        return Ok(collect_top_level_blocks(
+            tree.root_node(),
+            source,
+            path,
+            lang_str,
+        ));
+    }
+
+    let mut containers = Vec::new();
+    collect_containers(
+        tree.root_node(),
+        source,
+        path,
+        language,
+        lang_str,
+        &mut containers,
+    );
+    let mut chunks: Vec<CodeChunk> = chunk_file(path, content, language)?
+        .into_iter()
+        .filter(|chunk| {
+            !containers
+                .iter()
+                .any(|c| c.start_line <= chunk.start_line && chunk.end_line <= c.end_line)
+        })
+        .collect();
+    chunks.extend(containers);
+    chunks.sort_by_key(|chunk| (chunk.start_line, chunk.end_line));
+    Ok(chunks)
+}
+
+/// Key used to pick a file's [`ChunkStrategy`]: the language name for
+/// files with a grammar, otherwise the lowercased extension.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_repomap::walker::Language;
+/// use argus_codelens::chunker::language_key;
+///
+/// assert_eq!(language_key(Path::new("src/lib.rs"), Language::Rust), "rust");
+/// assert_eq!(language_key(Path::new("README.MD"), Language::Unknown), "md");
+/// ```
+pub fn language_key(path: &Path, language: Language) -> String {
+    match language {
+        Language::Unknown => path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map_or_else(|| "text".to_string(), str::to_lowercase),
+        _ => language_str(language).to_string(),
+    }
+}
+
 /// Chunk all files in a repository.
 ///
 /// # Errors
@@ -165,6 +270,270 @@ pub fn chunk_repo(root: &Path) -> Result<Vec<CodeChunk>, ArgusError> {
     Ok(all_chunks)
 }
 
+/// Sliding-window settings for the fallback chunker.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::chunker::FixedWindow;
+///
+/// let window = FixedWindow::default();
+/// assert_eq!(window.lines, 40);
+/// assert_eq!(window.overlap, 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedWindow {
+    /// Number of lines per chunk.
+    pub lines: usize,
+    /// Number of lines shared between consecutive chunks.
+    pub overlap: usize,
+}
+
+impl Default for FixedWindow {
+    fn default() -> Self {
+        Self {
+            lines: 40,
+            overlap: 10,
+        }
+    }
+}
+
+/// Split a file into overlapping fixed-size line windows.
+///
+/// Used for files without a tree-sitter grammar (docs, YAML, SQL, ...) or
+/// whose AST yields no chunks. Chunks are tagged with `entity_type = "block"`
+/// and named after the file and line range. An overlap greater than or equal
+/// to the window size is clamped so every window advances at least one line.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_codelens::chunker::{chunk_fixed_window, FixedWindow};
+///
+/// let content = (1..=25).map(|i| format!("line {i}")).collect::<Vec<_>>().join("\n");
+/// let window = FixedWindow { lines: 10, overlap: 2 };
+/// let chunks = chunk_fixed_window(Path::new("notes.md"), &content, window);
+/// assert_eq!(chunks.len(), 3);
+/// assert_eq!(chunks[0].entity_type, "block");
+/// assert_eq!((chunks[1].start_line, chunks[1].end_line), (9, 18));
+/// assert_eq!(chunks[0].language, "md");
+/// ```
+pub fn chunk_fixed_window(path: &Path, content: &str, window: FixedWindow) -> Vec<CodeChunk> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.iter().all(|l| l.trim().is_empty()) {
+        return Vec::new();
+    }
+
+    let size = window.lines.max(1);
+    let step = size - window.overlap.min(size - 1);
+    let language = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map_or_else(|| "text".to_string(), str::to_lowercase);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + size).min(lines.len());
+        let body = lines[start..end].join("\n");
+        if !body.trim().is_empty() {
+            let entity_name = format!("{}:{}-{}", path.display(), start + 1, end);
+            let context_header = build_context_header(path, "block", &entity_name, None, "");
+            let content_hash = compute_hash(&body);
+            chunks.push(CodeChunk {
+                file_path: path.to_path_buf(),
+                start_line: start as u32 + 1,
+                end_line: end as u32,
+                entity_name,
+                entity_type: "block".into(),
+                language: language.clone(),
+                content: body,
+                context_header,
+                content_hash,
+            });
+        }
+        if end == lines.len() {
+            break;
+        }
+        start += step;
+    }
+
+    chunks
+}
+
+/// Split a file without a grammar into sections at its headings.
+///
+/// Markdown files split at `#` headings (outside fenced code blocks); other
+/// files split at `[table]` headers (TOML, INI) or unindented `key:` lines
+/// (YAML). Each section runs up to the next heading and is named after its
+/// heading; text before the first heading is a section of its own. Chunks
+/// are tagged with `entity_type = "section"`. Returns nothing when the file
+/// has no headings, so callers can fall back to [`chunk_fixed_window`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_codelens::chunker::chunk_sections;
+///
+/// let toml = "[package]\nname = \"demo\"\n\n[dependencies]\nserde = \"1\"\n";
+/// let chunks = chunk_sections(Path::new("Cargo.toml"), toml);
+/// assert_eq!(chunks.len(), 2);
+/// assert_eq!(chunks[1].entity_name, "dependencies");
+/// assert_eq!((chunks[1].start_line, chunks[1].end_line), (4, 5));
+/// ```
+pub fn chunk_sections(path: &Path, content: &str) -> Vec<CodeChunk> {
+    let language = language_key(path, Language::Unknown);
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate() {
+        let heading = match language.as_str() {
+            "md" | "markdown" => {
+                if line.trim_start().starts_with("```") {
+                    in_fence = !in_fence;
+                }
+                line.strip_prefix('#')
+                    .filter(|_| !in_fence)
+                    .map(|rest| rest.trim_start_matches('#'))
+                    .filter(|rest| rest.starts_with(' '))
+                    .map(str::trim)
+            }
+            "yaml" | "yml" => line
+                .strip_suffix(':')
+                .or_else(|| line.split_once(": ").map(|(key, _)| key))
+                .filter(|key| !key.is_empty() && !key.starts_with([' ', '\t', '#', '-'])),
+            _ => line
+                .trim_end()
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|name| name.trim_matches(['[', ']'])),
+        };
+        if let Some(heading) = heading {
+            headings.push((i, heading.to_string()));
+        }
+    }
+    if headings.is_empty() {
+        return Vec::new();
+    }
+    if headings[0].0 > 0 {
+        // Untitled preamble, named after its line range below
+        headings.insert(0, (0, String::new()));
+    }
+
+    let mut chunks = Vec::new();
+    for (n, (start, name)) in headings.iter().enumerate() {
+        let end = headings.get(n + 1).map_or(lines.len(), |(next, _)| *next);
+        let body = lines[*start..end].join("\n");
+        let body = body.trim_end();
+        if body.trim().is_empty() {
+            continue;
+        }
+        let end_line = start + body.lines().count();
+        let name = if name.is_empty() {
+            format!("{}:{}-{end_line}", path.display(), start + 1)
+        } else {
+            name.clone()
+        };
+        chunks.push(CodeChunk {
+            file_path: path.to_path_buf(),
+            start_line: *start as u32 + 1,
+            end_line: end_line as u32,
+            context_header: build_context_header(path, "section", &name, None, ""),
+            entity_name: name,
+            entity_type: "section".into(),
+            language: language.clone(),
+            content: body.to_string(),
+            content_hash: compute_hash(body),
+        });
+    }
+    chunks
+}
+
+/// Remove comments (and Python docstrings) from source text.
+///
+/// Uses the language's tree-sitter grammar to find comment nodes, so
+/// comment markers inside string literals are left alone. Lines left empty
+/// by the removal are dropped. Content in a language without a grammar is
+/// returned unchanged.
+///
+/// Used to keep comment boilerplate such as license headers from dominating
+/// embeddings; the original content is still what gets stored and shown.
+///
+/// # Examples
+///
+/// ```
+/// use argus_repomap::walker::Language;
+/// use argus_codelens::chunker::strip_comments;
+///
+/// let code = "// Copyright Acme Corp\nfn main() {\n    run(); // go\n}";
+/// assert_eq!(strip_comments(code, Language::Rust), "fn main() {\n    run();\n}");
+/// ```
+pub fn strip_comments(content: &str, language: Language) -> String {
+    let Some(ts_language) = language.tree_sitter_language() else {
+        return content.to_string();
+    };
+    let mut parser = Parser::new();
+    if parser.set_language(&ts_language).is_err() {
+        return content.to_string();
+    }
+    let Some(tree) = parser.parse(content, None) else {
+        return content.to_string();
+    };
+
+    let mut ranges = Vec::new();
+    collect_comment_ranges(tree.root_node(), language, &mut ranges);
+    if ranges.is_empty() {
+        return content.to_string();
+    }
+
+    let mut stripped = String::with_capacity(content.len());
+    let mut pos = 0;
+    for (start, end) in ranges {
+        if start < pos {
+            continue;
+        }
+        stripped.push_str(&content[pos..start]);
+        pos = end;
+    }
+    stripped.push_str(&content[pos..]);
+
+    stripped
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collect_comment_ranges(node: Node, language: Language, ranges: &mut Vec<(usize, usize)>) {
+    if node.kind().contains("comment") || is_docstring(&node, language) {
+        ranges.push((node.start_byte(), node.end_byte()));
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comment_ranges(child, language, ranges);
+    }
+}
+
+/// A Python docstring: a bare string that is the first statement of a
+/// module, class, or function body.
+fn is_docstring(node: &Node, language: Language) -> bool {
+    if language != Language::Python || node.kind() != "expression_statement" {
+        return false;
+    }
+    let is_lone_string =
+        node.named_child_count() == 1 && node.named_child(0).is_some_and(|c| c.kind() == "string");
+    let is_first_statement = node
+        .parent()
+        .and_then(|p| p.named_child(0))
+        .is_some_and(|first| first.id() == node.id());
+    is_lone_string && is_first_statement
+}
+
 fn language_str(lang: Language) -> &'static str {
     match lang {
         Language::Rust => "rust",
@@ -283,6 +652,158 @@ fn make_chunk(
     }
 }
 
+/// Entity type for a node that groups methods in `language`, if it is one.
+fn container_type(language: Language, kind: &str) -> Option<&'static str> {
+    let entity_type = match (language, kind) {
+        (Language::Rust, "impl_item") => "impl",
+        (Language::Rust, "trait_item") => "trait",
+        (Language::Python, "class_definition") => "class",
+        (
+            Language::TypeScript | Language::JavaScript,
+            "class_declaration" | "abstract_class_declaration",
+        ) => "class",
+        (Language::TypeScript, "interface_declaration") => "interface",
+        (Language::Java, "class_declaration" | "record_declaration" | "enum_declaration") => {
+            "class"
+        }
+        (Language::Java, "interface_declaration") => "interface",
+        (Language::Cpp, "class_specifier" | "struct_specifier") => "class",
+        (Language::Ruby, "class") => "class",
+        (Language::Ruby, "module") => "module",
+        (Language::Php, "class_declaration" | "trait_declaration") => "class",
+        (Language::Php, "interface_declaration") => "interface",
+        (Language::Kotlin, "class_declaration" | "object_declaration") => "class",
+        (Language::Swift, "class_declaration") => "class",
+        (Language::Swift, "protocol_declaration") => "interface",
+        _ => return None,
+    };
+    Some(entity_type)
+}
+
+/// Name of a container node: the implemented type for a Rust `impl`, the
+/// declared name otherwise.
+fn container_name(node: &Node, source: &[u8]) -> Option<String> {
+    let field = if node.kind() == "impl_item" {
+        "type"
+    } else {
+        "name"
+    };
+    node.child_by_field_name(field)
+        .map(|name| node_text(&name, source))
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            [
+                "type_identifier",
+                "identifier",
+                "constant",
+                "name",
+                "simple_identifier",
+            ]
+            .iter()
+            .find_map(|kind| find_child_text(node, kind, source))
+        })
+}
+
+/// Collect the outermost containers (classes, `impl` blocks, ...) under
+/// `node` as whole chunks.
+fn collect_containers(
+    node: Node,
+    source: &[u8],
+    file_path: &Path,
+    language: Language,
+    lang_str: &str,
+    chunks: &mut Vec<CodeChunk>,
+) {
+    if let Some(entity_type) = container_type(language, node.kind()) {
+        // Forward declarations such as `class Foo;` have no body to group
+        if node.start_position().row < node.end_position().row {
+            if let Some(name) = container_name(&node, source) {
+                chunks.push(make_chunk(
+                    file_path,
+                    &node,
+                    source,
+                    &name,
+                    entity_type,
+                    lang_str,
+                    None,
+                ));
+                return;
+            }
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_containers(child, source, file_path, language, lang_str, chunks);
+    }
+}
+
+/// One chunk per run of top-level nodes on consecutive lines, named after
+/// the first declaration in the run. Runs of only comments are dropped.
+fn collect_top_level_blocks(
+    root: Node,
+    source: &[u8],
+    file_path: &Path,
+    language: &str,
+) -> Vec<CodeChunk> {
+    let mut runs: Vec<Vec<Node>> = Vec::new();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        match runs.last_mut() {
+            Some(run)
+                if run
+                    .last()
+                    .is_some_and(|prev| child.start_position().row <= last_row(prev) + 1) =>
+            {
+                run.push(child)
+            }
+            _ => runs.push(vec![child]),
+        }
+    }
+
+    runs.into_iter()
+        .filter(|run| run.iter().any(|node| !node.kind().contains("comment")))
+        .map(|run| {
+            let (first, last) = (run[0], run[run.len() - 1]);
+            let start_line = first.start_position().row as u32 + 1;
+            let end_line = last_row(&last) as u32 + 1;
+            let content = String::from_utf8_lossy(&source[first.start_byte()..last.end_byte()])
+                .trim_end()
+                .to_string();
+            let entity_name = run
+                .iter()
+                .find_map(|node| {
+                    node.child_by_field_name("name")
+                        .map(|name| node_text(&name, source))
+                })
+                .unwrap_or_else(|| format!("{}:{start_line}-{end_line}", file_path.display()));
+            let context_header = build_context_header(file_path, "block", &entity_name, None, "");
+            let content_hash = compute_hash(&content);
+            CodeChunk {
+                file_path: file_path.to_path_buf(),
+                start_line,
+                end_line,
+                entity_name,
+                entity_type: "block".into(),
+                language: language.to_string(),
+                content,
+                context_header,
+                content_hash,
+            }
+        })
+        .collect()
+}
+
+/// Last row a node has text on. Some nodes, such as Rust line comments,
+/// include their newline and so end at column 0 of the following row.
+fn last_row(node: &Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
 fn collect_rust_chunks(
     node: Node,
     source: &[u8],
@@ -1086,4 +1607,197 @@ impl AuthService {
             .unwrap();
         assert!(method.context_header.contains("# Scope: impl AuthService"));
     }
+
+    #[test]
+    fn fixed_window_covers_every_line_with_overlap() {
+        let content = (1..=100)
+            .map(|i| format!("key_{i}: value"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let window = FixedWindow {
+            lines: 40,
+            overlap: 10,
+        };
+        let chunks = chunk_fixed_window(Path::new("config.yaml"), &content, window);
+
+        let ranges: Vec<(u32, u32)> = chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, vec![(1, 40), (31, 70), (61, 100)]);
+        assert!(chunks.iter().all(|c| c.entity_type == "block"));
+        assert!(chunks.iter().all(|c| c.language == "yaml"));
+        assert_eq!(chunks[0].entity_name, "config.yaml:1-40");
+        assert!(chunks[0].context_header.contains("# Type: block"));
+    }
+
+    #[test]
+    fn fixed_window_hashes_are_content_based() {
+        let content = "SELECT 1;\nSELECT 2;\nSELECT 3;";
+        let window = FixedWindow {
+            lines: 2,
+            overlap: 0,
+        };
+        let a = chunk_fixed_window(Path::new("a.sql"), content, window);
+        let b = chunk_fixed_window(Path::new("b.sql"), content, window);
+        assert_eq!(a.len(), 2);
+        assert_ne!(a[0].content_hash, a[1].content_hash);
+        assert_eq!(a[0].content_hash, b[0].content_hash);
+        assert_eq!(a[0].content_hash, compute_hash("SELECT 1;\nSELECT 2;"));
+    }
+
+    #[test]
+    fn fixed_window_handles_degenerate_input() {
+        assert!(
+            chunk_fixed_window(Path::new("empty.md"), "\n  \n", FixedWindow::default()).is_empty()
+        );
+
+        let window = FixedWindow {
+            lines: 3,
+            overlap: 5,
+        };
+        let chunks = chunk_fixed_window(
+            Path::new("Dockerfile"),
+            "FROM a\nRUN b\nRUN c\nCMD d",
+            window,
+        );
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].language, "text");
+    }
+
+    #[test]
+    fn strip_comments_removes_comments_and_docstrings() {
+        let py = "# License: MIT\ndef f():\n    \"\"\"Docstring.\"\"\"\n    return \"# not a comment\"  # trailing\n";
+        assert_eq!(
+            strip_comments(py, Language::Python),
+            "def f():\n    return \"# not a comment\""
+        );
+
+        let js = "/* Copyright */\nfunction f() { return 1; }";
+        assert_eq!(
+            strip_comments(js, Language::JavaScript),
+            "function f() { return 1; }"
+        );
+
+        let text = "# heading\nbody";
+        assert_eq!(strip_comments(text, Language::Unknown), text);
+    }
+
+    #[test]
+    fn by_class_keeps_each_class_whole_with_its_methods() {
+        let content = r#"import os
+
+
+def helper():
+    return 1
+
+
+class Repository:
+    def __init__(self, root):
+        self.root = root
+
+    def load(self, name):
+        return os.path.join(self.root, name)
+
+    def save(self, name, data):
+        pass
+
+
+def main():
+    Repository(".").load("x")
+"#;
+        let path = Path::new("repo.py");
+        let chunks =
+            chunk_file_with(path, content, Language::Python, ChunkStrategy::ByClass).unwrap();
+
+        let names: Vec<(&str, &str)> = chunks
+            .iter()
+            .map(|c| (c.entity_type.as_str(), c.entity_name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("function", "helper"),
+                ("class", "Repository"),
+                ("function", "main")
+            ]
+        );
+
+        let class = &chunks[1];
+        assert_eq!((class.start_line, class.end_line), (8, 16));
+        assert!(class.content.contains("def load(self, name):"));
+        assert!(class.content.contains("def save(self, name, data):"));
+        assert!(class.context_header.contains("# Name: Repository"));
+
+        let by_function = chunk_file(path, content, Language::Python).unwrap();
+        assert!(by_function.iter().any(|c| c.entity_name == "load"));
+    }
+
+    #[test]
+    fn by_class_groups_rust_impl_blocks_by_type() {
+        let content = "pub struct Config;\n\nimpl Config {\n    pub fn new() -> Self {\n        Config\n    }\n\n    pub fn reset(&mut self) {}\n}\n\nimpl Default for Config {\n    fn default() -> Self {\n        Config\n    }\n}\n";
+        let chunks = chunk_file_with(
+            Path::new("config.rs"),
+            content,
+            Language::Rust,
+            ChunkStrategy::ByClass,
+        )
+        .unwrap();
+
+        let spans: Vec<(&str, &str, u32, u32)> = chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.entity_type.as_str(),
+                    c.entity_name.as_str(),
+                    c.start_line,
+                    c.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("struct", "Config", 1, 1),
+                ("impl", "Config", 3, 9),
+                ("impl", "Config", 11, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn by_block_groups_adjacent_top_level_items() {
+        let content = "use std::io;\nuse std::fs;\n\n/// Reads it.\n#[inline]\nfn read() {}\n\nfn write() {}\n";
+        let chunks = chunk_file_with(
+            Path::new("io.rs"),
+            content,
+            Language::Rust,
+            ChunkStrategy::ByBlock,
+        )
+        .unwrap();
+
+        let spans: Vec<(&str, u32, u32)> = chunks
+            .iter()
+            .map(|c| (c.entity_name.as_str(), c.start_line, c.end_line))
+            .collect();
+        assert_eq!(
+            spans,
+            [("io.rs:1-2", 1, 2), ("read", 4, 6), ("write", 8, 8)]
+        );
+        assert!(chunks.iter().all(|c| c.entity_type == "block"));
+    }
+
+    #[test]
+    fn markdown_sections_split_at_headings_outside_code_fences() {
+        let content =
+            "Intro text.\n\n# Install\n\n```sh\n# not a heading\n```\n\n## Usage\nRun it.\n";
+        let chunks = chunk_sections(Path::new("README.md"), content);
+
+        let spans: Vec<(&str, u32, u32)> = chunks
+            .iter()
+            .map(|c| (c.entity_name.as_str(), c.start_line, c.end_line))
+            .collect();
+        assert_eq!(
+            spans,
+            [("README.md:1-1", 1, 1), ("Install", 3, 7), ("Usage", 9, 10)]
+        );
+        assert!(chunk_sections(Path::new("notes.md"), "no headings here\n").is_empty());
+    }
 }
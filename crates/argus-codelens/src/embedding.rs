@@ -2,8 +2,16 @@
 //!
 //! Supports Voyage, Gemini, and OpenAI embedding APIs. The provider is
 //! selected via [`EmbeddingConfig`]. Same interface, different API calls.
+//!
+//! Indexed code and search queries are embedded as different
+//! [`InputType`]s, which asymmetric-retrieval models use to place short
+//! queries near the documents that answer them.
+
+use std::future::Future;
+use std::time::Duration;
 
-use argus_core::{ArgusError, EmbeddingConfig};
+use argus_core::{ArgusError, EmbeddingConfig, LlmError, LlmErrorKind, NetworkConfig};
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
 /// Embedding provider variant.
@@ -14,6 +22,42 @@ enum Provider {
     OpenAi,
 }
 
+/// Which side of a retrieval an embedded text is on.
+///
+/// Voyage sends it as `input_type` and Gemini as `taskType`; OpenAI has no
+/// such parameter and embeds both the same way.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::embedding::InputType;
+///
+/// assert_ne!(InputType::Query, InputType::Document);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputType {
+    /// A search query, embedded by [`EmbeddingClient::embed_query`].
+    Query,
+    /// Indexed content, embedded by [`EmbeddingClient::embed_document`].
+    Document,
+}
+
+impl InputType {
+    fn voyage_input_type(self) -> &'static str {
+        match self {
+            InputType::Query => "query",
+            InputType::Document => "document",
+        }
+    }
+
+    fn gemini_task_type(self) -> &'static str {
+        match self {
+            InputType::Query => "RETRIEVAL_QUERY",
+            InputType::Document => "RETRIEVAL_DOCUMENT",
+        }
+    }
+}
+
 /// Client for embedding code via Voyage, Gemini, or OpenAI APIs.
 ///
 /// # Examples
@@ -29,6 +73,7 @@ pub struct EmbeddingClient {
     api_key: String,
     model: String,
     provider: Provider,
+    concurrency: usize,
 }
 
 impl std::fmt::Debug for EmbeddingClient {
@@ -36,6 +81,7 @@ impl std::fmt::Debug for EmbeddingClient {
         f.debug_struct("EmbeddingClient")
             .field("provider", &self.provider)
             .field("model", &self.model)
+            .field("concurrency", &self.concurrency)
             .finish_non_exhaustive()
     }
 }
@@ -51,6 +97,18 @@ const GEMINI_DELAY_MS: u64 = 100;
 const OPENAI_BATCH_SIZE: usize = 64;
 const OPENAI_DELAY_MS: u64 = 200;
 
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const INITIAL_BACKOFF_MS: u64 = 1000;
+
+/// Request body for whichever provider the client uses.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum EmbedRequest {
+    Voyage(VoyageRequest),
+    Gemini(GeminiBatchRequest),
+    OpenAi(OpenAiRequest),
+}
+
 // --- Voyage request/response ---
 
 #[derive(Serialize)]
@@ -139,6 +197,7 @@ impl EmbeddingClient {
             api_key: api_key.to_string(),
             model: "voyage-code-3".to_string(),
             provider: Provider::Voyage,
+            concurrency: 1,
         }
     }
 
@@ -204,13 +263,59 @@ impl EmbeddingClient {
         };
 
         Ok(Self {
-            client: reqwest::Client::new(),
+            client: http_client(&NetworkConfig::default())?,
             api_key,
             model,
             provider,
+            concurrency: config.index_concurrency.max(1),
         })
     }
 
+    /// Apply `[network]` connection pool settings.
+    ///
+    /// The client keeps one connection pool for its lifetime, so every
+    /// batch sent while indexing reuses open keep-alive connections instead
+    /// of repeating the TLS handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Embedding`] if the HTTP client cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::NetworkConfig;
+    /// use argus_codelens::embedding::EmbeddingClient;
+    ///
+    /// let network = NetworkConfig { max_connections: 16 };
+    /// let client = EmbeddingClient::new("key").with_network(&network).unwrap();
+    /// ```
+    pub fn with_network(mut self, network: &NetworkConfig) -> Result<Self, ArgusError> {
+        self.client = http_client(network)?;
+        Ok(self)
+    }
+
+    /// Set how many sub-batches [`embed_document`](Self::embed_document) keeps
+    /// in flight at once. Values below 1 are treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::embedding::EmbeddingClient;
+    ///
+    /// let client = EmbeddingClient::new("key").with_concurrency(8);
+    /// assert_eq!(client.concurrency(), 8);
+    /// ```
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Get the number of sub-batches sent concurrently.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
     /// Get the model name.
     pub fn model(&self) -> &str {
         &self.model
@@ -233,11 +338,14 @@ impl EmbeddingClient {
     /// Embed a batch of texts. Returns vectors in the same order.
     ///
     /// Splits into sub-batches with rate-limiting delays between batches.
-    /// Batch sizes vary by provider: Voyage/OpenAI=64, Gemini=100.
+    /// Batch sizes vary by provider: Voyage/OpenAI=64, Gemini=100. Up to
+    /// [`concurrency`](Self::concurrency) sub-batches are in flight at once,
+    /// and a sub-batch rejected with 429 is retried with exponential backoff.
     ///
     /// # Errors
     ///
-    /// Returns [`ArgusError::Embedding`] if the API call fails.
+    /// Returns [`ArgusError::Embedding`] if the API call fails, or is still
+    /// rate limited after the retries.
     ///
     /// # Examples
     ///
@@ -247,11 +355,11 @@ impl EmbeddingClient {
     /// # async fn example() {
     /// let client = EmbeddingClient::new("key");
     /// let texts = vec!["fn main() {}".to_string()];
-    /// let embeddings = client.embed_batch(&texts).await.unwrap();
+    /// let embeddings = client.embed_document(&texts).await.unwrap();
     /// assert_eq!(embeddings.len(), 1);
     /// # }
     /// ```
-    pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ArgusError> {
+    pub async fn embed_document(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ArgusError> {
         if texts.is_empty() {
             return Ok(Vec::new());
         }
@@ -262,23 +370,47 @@ impl EmbeddingClient {
             Provider::OpenAi => (OPENAI_BATCH_SIZE, OPENAI_DELAY_MS),
         };
 
-        let mut all_embeddings = Vec::with_capacity(texts.len());
+        let batches: Vec<&[String]> = texts.chunks(batch_size).collect();
+        embed_concurrently(
+            batches,
+            self.concurrency,
+            Duration::from_millis(delay_ms),
+            |batch| self.embed_with_retries(batch, InputType::Document),
+        )
+        .await
+    }
 
-        for (i, batch) in texts.chunks(batch_size).enumerate() {
-            if i > 0 {
-                tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-            }
+    /// Embed a batch of texts as documents.
+    #[deprecated(note = "use `embed_document` instead")]
+    pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ArgusError> {
+        self.embed_document(texts).await
+    }
 
-            let batch_result = match self.provider {
-                Provider::Voyage => self.embed_batch_voyage(batch, "document").await?,
-                Provider::Gemini => self.embed_batch_gemini(batch, "RETRIEVAL_DOCUMENT").await?,
-                Provider::OpenAi => self.embed_batch_openai(batch).await?,
+    async fn embed_with_retries(
+        &self,
+        batch: &[String],
+        input_type: InputType,
+    ) -> Result<Vec<Vec<f32>>, ArgusError> {
+        let mut attempt: u32 = 0;
+        loop {
+            let request = self.request(batch, input_type);
+            let result = match request {
+                EmbedRequest::Voyage(_) => self.embed_batch_voyage(&request).await,
+                EmbedRequest::Gemini(_) => self.embed_batch_gemini(&request).await,
+                EmbedRequest::OpenAi(_) => self.embed_batch_openai(&request).await,
             };
-
-            all_embeddings.extend(batch_result);
+            match result {
+                Err(err) if is_rate_limit_error(&err) && attempt < MAX_RATE_LIMIT_RETRIES => {
+                    let backoff_ms = INITIAL_BACKOFF_MS * (1_u64 << attempt);
+                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                    attempt += 1;
+                }
+                Err(ArgusError::LlmRequest(err)) => {
+                    return Err(ArgusError::Embedding(err.message));
+                }
+                other => return other,
+            }
         }
-
-        Ok(all_embeddings)
     }
 
     /// Embed a single query text.
@@ -288,6 +420,8 @@ impl EmbeddingClient {
     /// - Gemini: `taskType: "RETRIEVAL_QUERY"`
     /// - OpenAI: same as document embedding
     ///
+    /// Retries on rate limits like [`embed_document`](Self::embed_document).
+    ///
     /// # Errors
     ///
     /// Returns [`ArgusError::Embedding`] if the API call fails.
@@ -304,42 +438,52 @@ impl EmbeddingClient {
     /// # }
     /// ```
     pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ArgusError> {
-        let result = match self.provider {
-            Provider::Voyage => {
-                self.embed_batch_voyage(&[query.to_string()], "query")
-                    .await?
-            }
-            Provider::Gemini => {
-                self.embed_batch_gemini(&[query.to_string()], "RETRIEVAL_QUERY")
-                    .await?
-            }
-            Provider::OpenAi => self.embed_batch_openai(&[query.to_string()]).await?,
-        };
-
-        result
+        self.embed_with_retries(&[query.to_string()], InputType::Query)
+            .await?
             .into_iter()
             .next()
             .ok_or_else(|| ArgusError::Embedding("empty response from embedding API".into()))
     }
 
+    /// The request body embedding `texts` as `input_type` with this
+    /// client's provider and model.
+    fn request(&self, texts: &[String], input_type: InputType) -> EmbedRequest {
+        match self.provider {
+            Provider::Voyage => EmbedRequest::Voyage(VoyageRequest {
+                model: self.model.clone(),
+                input: texts.to_vec(),
+                input_type: input_type.voyage_input_type().to_string(),
+            }),
+            Provider::Gemini => EmbedRequest::Gemini(GeminiBatchRequest {
+                requests: texts
+                    .iter()
+                    .map(|text| GeminiEmbedRequest {
+                        model: format!("models/{}", self.model),
+                        content: GeminiContent {
+                            parts: vec![GeminiPart { text: text.clone() }],
+                        },
+                        task_type: input_type.gemini_task_type().to_string(),
+                    })
+                    .collect(),
+            }),
+            Provider::OpenAi => EmbedRequest::OpenAi(OpenAiRequest {
+                model: self.model.clone(),
+                input: texts.to_vec(),
+            }),
+        }
+    }
+
     // --- Voyage ---
 
     async fn embed_batch_voyage(
         &self,
-        texts: &[String],
-        input_type: &str,
+        request: &EmbedRequest,
     ) -> Result<Vec<Vec<f32>>, ArgusError> {
-        let request = VoyageRequest {
-            model: self.model.clone(),
-            input: texts.to_vec(),
-            input_type: input_type.to_string(),
-        };
-
         let response = self
             .client
             .post("https://api.voyageai.com/v1/embeddings")
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| ArgusError::Embedding(format!("HTTP request failed: {e}")))?;
@@ -350,9 +494,7 @@ impl EmbeddingClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "unable to read response body".into());
-            return Err(ArgusError::Embedding(format!(
-                "Voyage API returned {status}: {body}"
-            )));
+            return Err(api_error("Voyage", status, &body));
         }
 
         let parsed: VoyageResponse = response
@@ -367,22 +509,8 @@ impl EmbeddingClient {
 
     async fn embed_batch_gemini(
         &self,
-        texts: &[String],
-        task_type: &str,
+        request: &EmbedRequest,
     ) -> Result<Vec<Vec<f32>>, ArgusError> {
-        let requests: Vec<GeminiEmbedRequest> = texts
-            .iter()
-            .map(|text| GeminiEmbedRequest {
-                model: format!("models/{}", self.model),
-                content: GeminiContent {
-                    parts: vec![GeminiPart { text: text.clone() }],
-                },
-                task_type: task_type.to_string(),
-            })
-            .collect();
-
-        let request = GeminiBatchRequest { requests };
-
         let url = format!(
             "https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents?key={}",
             self.model, self.api_key,
@@ -391,7 +519,7 @@ impl EmbeddingClient {
         let response = self
             .client
             .post(&url)
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| ArgusError::Embedding(format!("HTTP request failed: {e}")))?;
@@ -402,9 +530,7 @@ impl EmbeddingClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "unable to read response body".into());
-            return Err(ArgusError::Embedding(format!(
-                "Gemini API returned {status}: {body}"
-            )));
+            return Err(api_error("Gemini", status, &body));
         }
 
         let parsed: GeminiBatchResponse = response
@@ -417,17 +543,15 @@ impl EmbeddingClient {
 
     // --- OpenAI ---
 
-    async fn embed_batch_openai(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ArgusError> {
-        let request = OpenAiRequest {
-            model: self.model.clone(),
-            input: texts.to_vec(),
-        };
-
+    async fn embed_batch_openai(
+        &self,
+        request: &EmbedRequest,
+    ) -> Result<Vec<Vec<f32>>, ArgusError> {
         let response = self
             .client
             .post("https://api.openai.com/v1/embeddings")
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| ArgusError::Embedding(format!("HTTP request failed: {e}")))?;
@@ -438,9 +562,7 @@ impl EmbeddingClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "unable to read response body".into());
-            return Err(ArgusError::Embedding(format!(
-                "OpenAI API returned {status}: {body}"
-            )));
+            return Err(api_error("OpenAI", status, &body));
         }
 
         let parsed: OpenAiResponse = response
@@ -452,6 +574,63 @@ impl EmbeddingClient {
     }
 }
 
+/// Run `embed` over `batches` with at most `concurrency` calls in flight,
+/// concatenating the results in batch order. Stops at the first error.
+///
+/// Calls are started at least `pace` apart, however many slots are free.
+async fn embed_concurrently<'a, F, Fut>(
+    batches: Vec<&'a [String]>,
+    concurrency: usize,
+    pace: Duration,
+    embed: F,
+) -> Result<Vec<Vec<f32>>, ArgusError>
+where
+    F: Fn(&'a [String]) -> Fut,
+    Fut: Future<Output = Result<Vec<Vec<f32>>, ArgusError>>,
+{
+    let results: Vec<Vec<Vec<f32>>> = futures::stream::iter(batches.into_iter().enumerate())
+        .then(|(i, batch)| async move {
+            if i > 0 {
+                tokio::time::sleep(pace).await;
+            }
+            batch
+        })
+        .map(embed)
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// The error for a failed embedding API response. Rate limits are
+/// classified as [`LlmErrorKind::RateLimit`] so they can be retried.
+fn api_error(provider: &str, status: reqwest::StatusCode, body: &str) -> ArgusError {
+    let message = format!("{provider} API returned {status}: {body}");
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        LlmError::new(LlmErrorKind::RateLimit, message)
+            .with_status(status.as_u16())
+            .into()
+    } else {
+        ArgusError::Embedding(message)
+    }
+}
+
+fn is_rate_limit_error(err: &ArgusError) -> bool {
+    matches!(err, ArgusError::LlmRequest(e) if e.kind == LlmErrorKind::RateLimit)
+}
+
+/// Build an HTTP client with a keep-alive connection pool sized by `network`.
+fn http_client(network: &NetworkConfig) -> Result<reqwest::Client, ArgusError> {
+    reqwest::Client::builder()
+        .pool_max_idle_per_host(network.max_connections)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(60))
+        .http2_keep_alive_interval(Duration::from_secs(30))
+        .http2_keep_alive_while_idle(true)
+        .build()
+        .map_err(|e| ArgusError::Embedding(format!("failed to create HTTP client: {e}")))
+}
+
 fn default_model(provider: Provider) -> &'static str {
     match provider {
         Provider::Voyage => "voyage-code-3",
@@ -484,6 +663,45 @@ fn is_model_compatible(model: &str, provider: Provider) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn input_type_is_sent_as_each_providers_parameter() {
+        let texts = vec!["fn login() {}".to_string()];
+        let client = |provider: &str, model: &str| {
+            EmbeddingClient::with_config(&EmbeddingConfig {
+                provider: provider.into(),
+                api_key: Some("key".into()),
+                model: model.into(),
+                ..EmbeddingConfig::default()
+            })
+            .unwrap()
+        };
+        let body = |client: &EmbeddingClient, input_type| {
+            serde_json::to_value(client.request(&texts, input_type)).unwrap()
+        };
+
+        let voyage = client("voyage", "voyage-code-3");
+        assert_eq!(body(&voyage, InputType::Query)["input_type"], "query");
+        assert_eq!(body(&voyage, InputType::Document)["input_type"], "document");
+
+        let gemini = client("gemini", "text-embedding-004");
+        assert_eq!(
+            body(&gemini, InputType::Query)["requests"][0]["taskType"],
+            "RETRIEVAL_QUERY"
+        );
+        assert_eq!(
+            body(&gemini, InputType::Document)["requests"][0]["taskType"],
+            "RETRIEVAL_DOCUMENT"
+        );
+
+        // No distinction for OpenAI: both calls send the same body
+        let openai = client("openai", "text-embedding-3-small");
+        assert_eq!(
+            body(&openai, InputType::Query),
+            body(&openai, InputType::Document)
+        );
+        assert!(body(&openai, InputType::Query).get("input_type").is_none());
+    }
+
     // --- Voyage tests ---
 
     #[test]
@@ -668,6 +886,7 @@ mod tests {
             api_key: None,
             model: "text-embedding-004".into(),
             dimensions: 768,
+            ..EmbeddingConfig::default()
         };
         let result = EmbeddingClient::with_config(&config);
         assert!(result.is_err());
@@ -683,6 +902,7 @@ mod tests {
             api_key: None,
             model: "text-embedding-3-small".into(),
             dimensions: 1536,
+            ..EmbeddingConfig::default()
         };
         let result = EmbeddingClient::with_config(&config);
         assert!(result.is_err());
@@ -733,6 +953,7 @@ mod tests {
             api_key: Some("test-key".into()),
             model: "text-embedding-3-large".into(),
             dimensions: 3072,
+            ..EmbeddingConfig::default()
         };
         let client = EmbeddingClient::with_config(&config).unwrap();
         assert_eq!(client.model(), "text-embedding-3-large");
@@ -759,6 +980,7 @@ mod tests {
             api_key: Some("test-key".into()),
             model: "text-embedding-004".into(),
             dimensions: 768,
+            ..EmbeddingConfig::default()
         };
         let client = EmbeddingClient::with_config(&config).unwrap();
         assert_eq!(client.model(), "voyage-code-3");
@@ -771,6 +993,7 @@ mod tests {
             api_key: Some("test-key".into()),
             model: "text-embedding-3-small".into(),
             dimensions: 1536,
+            ..EmbeddingConfig::default()
         };
         let client = EmbeddingClient::with_config(&config).unwrap();
         assert_eq!(client.model(), "voyage-code-3");
@@ -791,4 +1014,87 @@ mod tests {
         ));
         assert!(!is_model_compatible("voyage-code-3", Provider::OpenAi));
     }
+
+    #[test]
+    fn config_sets_index_concurrency() {
+        let config = EmbeddingConfig {
+            api_key: Some("key".into()),
+            index_concurrency: 0,
+            ..EmbeddingConfig::default()
+        };
+        let client = EmbeddingClient::with_config(&config).unwrap();
+        assert_eq!(client.concurrency(), 1);
+        assert_eq!(
+            EmbeddingClient::new("key")
+                .with_concurrency(6)
+                .concurrency(),
+            6
+        );
+    }
+
+    #[test]
+    fn rate_limit_errors_are_detected() {
+        let err = api_error(
+            "Voyage",
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            "slow down",
+        );
+        assert!(is_rate_limit_error(&err));
+        assert!(!is_rate_limit_error(&api_error(
+            "OpenAI",
+            reqwest::StatusCode::BAD_REQUEST,
+            "429 requests left"
+        )));
+    }
+
+    #[tokio::test]
+    async fn concurrent_embedding_respects_limit_and_order() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let texts: Vec<String> = (0..12).map(|i| i.to_string()).collect();
+        let batches: Vec<&[String]> = texts.chunks(2).collect();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let embeddings = embed_concurrently(batches, 3, Duration::ZERO, |batch| {
+            let in_flight = &in_flight;
+            let peak = &peak;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(batch
+                    .iter()
+                    .map(|text| vec![text.parse::<f32>().unwrap()])
+                    .collect())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        let order: Vec<f32> = embeddings.iter().map(|v| v[0]).collect();
+        assert_eq!(order, (0..12).map(|i| i as f32).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn concurrent_embedding_paces_dispatch() {
+        let texts: Vec<String> = (0..4).map(|i| i.to_string()).collect();
+        let batches: Vec<&[String]> = texts.chunks(1).collect();
+        let starts = std::sync::Mutex::new(Vec::new());
+
+        embed_concurrently(batches, 4, Duration::from_millis(30), |batch| {
+            starts.lock().unwrap().push(std::time::Instant::now());
+            async move { Ok(batch.iter().map(|_| vec![0.0]).collect()) }
+        })
+        .await
+        .unwrap();
+
+        // Free slots don't let the calls start together
+        let starts = starts.into_inner().unwrap();
+        for pair in starts.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(30));
+        }
+    }
 }
@@ -1,18 +1,77 @@
 //! Hybrid search with Reciprocal Rank Fusion (RRF).
 //!
 //! Combines vector similarity and keyword search results using RRF scoring
-//! for better retrieval quality than either method alone.
+//! for better retrieval quality than either method alone. Queries can be
+//! expanded with related terms (see [`synonym_terms`]) so a search for
+//! "auth" also finds code that only says "login" or "session".
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use argus_core::{ArgusError, SearchResult};
+use argus_core::{ArgusError, ChunkStrategy, SearchResult};
+use argus_repomap::walker::SourceFile;
 use sha2::{Digest, Sha256};
 
-use crate::chunker::{chunk_file, CodeChunk};
+use crate::chunker::{
+    chunk_file_with, chunk_fixed_window, chunk_sections, language_key, strip_comments, CodeChunk,
+    FixedWindow,
+};
 use crate::embedding::EmbeddingClient;
 use crate::store::{CodeIndex, IndexStats, SearchHit};
 
+/// What [`HybridSearch::prune_repo`] removed from the index.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::search::PruneStats;
+///
+/// let stats = PruneStats::default();
+/// assert_eq!((stats.files, stats.chunks), (0, 0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    /// Indexed files that are no longer part of the walk.
+    pub files: usize,
+    /// Chunks removed with those files.
+    pub chunks: usize,
+}
+
+/// Chunks embedded and stored together while indexing.
+///
+/// Each group is written before the next is embedded, so an interrupted
+/// run loses at most one group of embedding work.
+const INDEX_GROUP_SIZE: usize = 256;
+
+/// How much of an index run was already done by an earlier, interrupted run.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::search::ResumeStats;
+///
+/// let stats = ResumeStats { present: 120, remaining: 30 };
+/// assert_eq!(stats.present + stats.remaining, 150);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResumeStats {
+    /// Chunks already in the index, which are not embedded again.
+    pub present: usize,
+    /// Chunks this run still had to embed.
+    pub remaining: usize,
+}
+
+/// A walked file's chunks that are not in the index yet, paired with the
+/// text to embed for each.
+struct PendingFile {
+    path: PathBuf,
+    file_hash: String,
+    chunks: Vec<(CodeChunk, String)>,
+}
+
 /// Hybrid search engine combining vector and keyword search with RRF fusion.
 ///
 /// # Examples
@@ -29,6 +88,16 @@ use crate::store::{CodeIndex, IndexStats, SearchHit};
 pub struct HybridSearch {
     index: CodeIndex,
     embedding_client: EmbeddingClient,
+    fallback_window: Option<FixedWindow>,
+    max_files: Option<usize>,
+    max_file_bytes: u64,
+    files_capped: Cell<usize>,
+    embedded: Cell<(usize, Duration)>,
+    resumed: Cell<Option<ResumeStats>>,
+    non_utf8_files: RefCell<Vec<PathBuf>>,
+    oversized_files: RefCell<Vec<PathBuf>>,
+    strip_comments: bool,
+    chunking: BTreeMap<String, ChunkStrategy>,
 }
 
 impl HybridSearch {
@@ -37,9 +106,112 @@ impl HybridSearch {
         Self {
             index,
             embedding_client,
+            fallback_window: None,
+            max_files: None,
+            max_file_bytes: argus_repomap::walker::DEFAULT_MAX_FILE_BYTES,
+            files_capped: Cell::new(0),
+            embedded: Cell::new((0, Duration::ZERO)),
+            resumed: Cell::new(None),
+            non_utf8_files: RefCell::new(Vec::new()),
+            oversized_files: RefCell::new(Vec::new()),
+            strip_comments: false,
+            chunking: BTreeMap::new(),
         }
     }
 
+    /// Strip comments and docstrings from chunk text before embedding.
+    ///
+    /// Keeps boilerplate such as license headers from driving similarity.
+    /// The stored chunk content, used for display and keyword search, keeps
+    /// its comments.
+    pub fn with_comment_stripping(mut self) -> Self {
+        self.strip_comments = true;
+        self
+    }
+
+    /// Chunk each language with its own [`ChunkStrategy`], keyed by
+    /// [`language_key`]. Languages not in `chunking` chunk by function.
+    ///
+    /// Changing strategies changes every chunk of the affected files, so
+    /// rebuild the index with `index_repo` afterwards.
+    pub fn with_chunking(mut self, chunking: BTreeMap<String, ChunkStrategy>) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// Index at most `max_files` files per run.
+    ///
+    /// Files are kept deterministically (first `max_files` by path), so
+    /// repeated re-indexing sees the same subset. Check
+    /// [`files_capped`](Self::files_capped) after indexing to tell whether
+    /// the index is partial.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files);
+        self
+    }
+
+    /// Skip files larger than `max_file_bytes` (default 1 MB) instead of
+    /// chunking and embedding them.
+    ///
+    /// Check [`oversized_files`](Self::oversized_files) after indexing to
+    /// see which files were left out.
+    pub fn with_max_file_bytes(mut self, max_file_bytes: u64) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Number of files dropped by the `max_files` cap in the last index run.
+    pub fn files_capped(&self) -> usize {
+        self.files_capped.get()
+    }
+
+    /// Chunks embedded per second in the last index run, or `None` if it
+    /// embedded nothing.
+    pub fn throughput(&self) -> Option<f64> {
+        let (chunks, elapsed) = self.embedded.get();
+        (chunks > 0 && !elapsed.is_zero()).then(|| chunks as f64 / elapsed.as_secs_f64())
+    }
+
+    /// Progress the last index run picked up from an earlier one, or `None`
+    /// if no chunk it walked was already indexed.
+    pub fn resume_stats(&self) -> Option<ResumeStats> {
+        self.resumed.get()
+    }
+
+    /// Files skipped in the last index run because they are not valid UTF-8.
+    pub fn non_utf8_files(&self) -> Vec<PathBuf> {
+        self.non_utf8_files.borrow().clone()
+    }
+
+    /// Files skipped in the last index run for exceeding the size limit.
+    pub fn oversized_files(&self) -> Vec<PathBuf> {
+        self.oversized_files.borrow().clone()
+    }
+
+    /// Also index files the AST chunker cannot handle, as fixed line windows.
+    ///
+    /// Covers docs, YAML, SQL, and other files without a tree-sitter grammar,
+    /// plus source files whose AST yields no chunks. Off by default because it
+    /// increases index size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argus_codelens::chunker::FixedWindow;
+    /// use argus_codelens::search::HybridSearch;
+    /// use argus_codelens::store::CodeIndex;
+    /// use argus_codelens::embedding::EmbeddingClient;
+    ///
+    /// let index = CodeIndex::in_memory().unwrap();
+    /// let client = EmbeddingClient::new("key");
+    /// let search = HybridSearch::new(index, client)
+    ///     .with_fallback_chunking(FixedWindow::default());
+    /// ```
+    pub fn with_fallback_chunking(mut self, window: FixedWindow) -> Self {
+        self.fallback_window = Some(window);
+        self
+    }
+
     /// Access the underlying index.
     pub fn index(&self) -> &CodeIndex {
         &self.index
@@ -49,7 +221,9 @@ impl HybridSearch {
     ///
     /// # Errors
     ///
-    /// Returns [`ArgusError`] if embedding or database queries fail.
+    /// Returns [`ArgusError::DimensionMismatch`] if the index was built with
+    /// an embedding model of a different size, and [`ArgusError`] if
+    /// embedding or database queries fail.
     ///
     /// # Examples
     ///
@@ -66,17 +240,37 @@ impl HybridSearch {
     /// # }
     /// ```
     pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>, ArgusError> {
+        self.search_expanded(query, &[], limit).await
+    }
+
+    /// Search like [`search`](Self::search), plus a keyword search for each
+    /// of `terms`, all fused with RRF.
+    ///
+    /// Only `query` is embedded, so expansion costs no extra embedding
+    /// calls. Chunks matching several terms rank higher, as with any result
+    /// found by more than one retriever.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`search`](Self::search).
+    pub async fn search_expanded(
+        &self,
+        query: &str,
+        terms: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, ArgusError> {
         let fetch_count = limit * 2;
 
         // Run vector search
         let query_embedding = self.embedding_client.embed_query(query).await?;
         let vector_results = self.index.vector_search(&query_embedding, fetch_count)?;
 
-        // Run keyword search
-        let keyword_results = self.index.keyword_search(query, fetch_count)?;
+        // Run keyword search for the query and each expansion term
+        let mut lists = vec![vector_results];
+        lists.extend(self.keyword_lists(query, terms, fetch_count)?);
 
         // Fuse results with RRF
-        let fused = reciprocal_rank_fusion(&vector_results, &keyword_results, 60);
+        let fused = reciprocal_rank_fusion_lists(&lists, 60);
 
         // Take top `limit` and convert to SearchResult
         let results: Vec<SearchResult> = fused
@@ -95,8 +289,30 @@ impl HybridSearch {
         Ok(results)
     }
 
+    /// Keyword results for `query` and then for each of `terms`.
+    fn keyword_lists(
+        &self,
+        query: &str,
+        terms: &[String],
+        fetch_count: usize,
+    ) -> Result<Vec<Vec<SearchHit>>, ArgusError> {
+        std::iter::once(query)
+            .chain(terms.iter().map(String::as_str))
+            .map(|q| self.index.keyword_search(q, fetch_count))
+            .collect()
+    }
+
     /// Index a repository (chunk + embed + store).
     ///
+    /// Resumable: chunks already in the index are not embedded again, and
+    /// chunks are stored in groups as they are embedded, so re-running after
+    /// a failure or Ctrl-C continues where the last run stopped. A file is
+    /// recorded as indexed only once all its chunks are stored. Check
+    /// [`resume_stats`](Self::resume_stats) to see how much was skipped.
+    ///
+    /// If the index was built with embeddings of a different size than the
+    /// configured provider produces, it is cleared and rebuilt from scratch.
+    ///
     /// # Errors
     ///
     /// Returns [`ArgusError`] if chunking, embedding, or storage fails.
@@ -121,46 +337,27 @@ impl HybridSearch {
         // Check or set embedding dimensions for consistency
         let expected_dims = self.embedding_client.default_dimensions();
         match self.index.get_dimensions()? {
-            Some(stored) => {
-                if stored != expected_dims {
-                    return Err(ArgusError::Config(format!(
-                        "Index has {stored} dimensions but embedding config specifies {expected_dims}. \
-                         Re-index with --index to rebuild."
-                    )));
-                }
+            Some(stored) if stored == expected_dims => {}
+            Some(_) => {
+                self.index.clear()?;
+                self.index.set_dimensions(expected_dims)?;
             }
             None => {
                 self.index.set_dimensions(expected_dims)?;
             }
         }
 
-        let files = argus_repomap::walker::walk_repo(root)?;
-        let mut all_chunks = Vec::new();
-
-        for file in &files {
-            let chunks = chunk_file(&file.path, &file.content, file.language)?;
-            let file_hash = compute_file_hash(&file.content);
-            self.index.record_file(&file.path, &file_hash)?;
-            all_chunks.extend(chunks);
-        }
-
-        if all_chunks.is_empty() {
-            return self.index.stats();
+        // A full index rebuilds keyword search if the tokenizer changed
+        if self.index.stored_tokenizer()? != self.index.tokenizer() {
+            self.index.rebuild_fts()?;
         }
 
-        // Build texts for embedding (context_header + content)
-        let texts: Vec<String> = all_chunks
-            .iter()
-            .map(|c| format!("{}\n\n{}", c.context_header, c.content))
-            .collect();
-
-        // Embed in batches
-        let embeddings = self.embedding_client.embed_batch(&texts).await?;
-
-        // Store chunks with embeddings
-        let pairs: Vec<(CodeChunk, Vec<f32>)> = all_chunks.into_iter().zip(embeddings).collect();
-
-        self.index.insert_chunks(&pairs)?;
+        let files = self.walk(root)?;
+        let pending = self.pending_files(&files)?;
+        self.embed_and_store(pending, INDEX_GROUP_SIZE, |texts| async move {
+            self.embed_timed(&texts).await
+        })
+        .await?;
 
         self.index.stats()
     }
@@ -202,8 +399,9 @@ impl HybridSearch {
                 self.index.set_dimensions(expected_dims)?;
             }
         }
+        self.index.check_tokenizer()?;
 
-        let files = argus_repomap::walker::walk_repo(root)?;
+        let files = self.walk(root)?;
         let existing_paths = self.index.indexed_files()?;
 
         // Track which files are still present
@@ -218,10 +416,12 @@ impl HybridSearch {
             let stored_hash = self.index.file_hash(&file.path)?;
 
             if stored_hash.as_deref() != Some(&file_hash) {
-                // File is new or changed
-                self.index.remove_file(&file.path)?;
+                // File is new or changed. A file without a record may hold
+                // chunks from an interrupted run, which are kept and reused.
+                if stored_hash.is_some() {
+                    self.index.remove_file(&file.path)?;
+                }
                 changed_files.push(file);
-                self.index.record_file(&file.path, &file_hash)?;
             }
         }
 
@@ -236,31 +436,193 @@ impl HybridSearch {
             return self.index.stats();
         }
 
-        // Chunk changed files
-        let mut all_chunks = Vec::new();
-        for file in &changed_files {
-            let chunks = chunk_file(&file.path, &file.content, file.language)?;
-            all_chunks.extend(chunks);
-        }
+        let pending = self.pending_files(changed_files)?;
+        self.embed_and_store(pending, INDEX_GROUP_SIZE, |texts| async move {
+            self.embed_timed(&texts).await
+        })
+        .await?;
 
-        if all_chunks.is_empty() {
-            return self.index.stats();
-        }
+        self.index.stats()
+    }
+}
 
-        // Embed
-        let texts: Vec<String> = all_chunks
+impl HybridSearch {
+    /// Remove chunks for indexed files that the walk now excludes.
+    ///
+    /// Recomputes the ignore-respecting file set (`.gitignore`,
+    /// `.argusignore`) and drops every indexed file outside it, such as
+    /// files matched by a newly added ignore pattern. Nothing is embedded,
+    /// and the `max_files` cap is not applied, so capped files are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError`] if walking the repository or the database fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use argus_codelens::search::HybridSearch;
+    /// use argus_codelens::store::CodeIndex;
+    /// use argus_codelens::embedding::EmbeddingClient;
+    ///
+    /// let index = CodeIndex::open(Path::new(".argus/index.db")).unwrap();
+    /// let search = HybridSearch::new(index, EmbeddingClient::new("key"));
+    /// let pruned = search.prune_repo(Path::new(".")).unwrap();
+    /// println!("Pruned {} chunks from {} files", pruned.chunks, pruned.files);
+    /// ```
+    pub fn prune_repo(&self, root: &Path) -> Result<PruneStats, ArgusError> {
+        let walk = argus_repomap::walker::walk_repo_detailed(
+            root,
+            self.fallback_window.is_some(),
+            self.max_file_bytes,
+        )?;
+        let current: std::collections::HashSet<String> = walk
+            .files
             .iter()
-            .map(|c| format!("{}\n\n{}", c.context_header, c.content))
+            .map(|f| f.path.to_string_lossy().into_owned())
             .collect();
 
-        let embeddings = self.embedding_client.embed_batch(&texts).await?;
+        let chunks_before = self.index.stats()?.total_chunks;
+        let mut files = 0;
+        for path in self.index.indexed_files()? {
+            if !current.contains(&path) {
+                self.index.remove_file(Path::new(&path))?;
+                files += 1;
+            }
+        }
+        let chunks = chunks_before - self.index.stats()?.total_chunks;
 
-        // Store
-        let pairs: Vec<(CodeChunk, Vec<f32>)> = all_chunks.into_iter().zip(embeddings).collect();
+        Ok(PruneStats { files, chunks })
+    }
 
-        self.index.insert_chunks(&pairs)?;
+    fn walk(&self, root: &Path) -> Result<Vec<SourceFile>, ArgusError> {
+        let walk = argus_repomap::walker::walk_repo_detailed(
+            root,
+            self.fallback_window.is_some(),
+            self.max_file_bytes,
+        )?;
+        *self.non_utf8_files.borrow_mut() = walk.non_utf8;
+        *self.oversized_files.borrow_mut() = walk.oversized;
+        let mut files = walk.files;
+        let capped = self
+            .max_files
+            .map_or(0, |max| argus_repomap::walker::cap_files(&mut files, max));
+        self.files_capped.set(capped);
+        self.embedded.set((0, Duration::ZERO));
+        self.resumed.set(None);
+        Ok(files)
+    }
 
-        self.index.stats()
+    /// Chunk `files` and keep the chunks the index does not have yet.
+    ///
+    /// Records a [`ResumeStats`] when some chunks are already present.
+    fn pending_files<'a>(
+        &self,
+        files: impl IntoIterator<Item = &'a SourceFile>,
+    ) -> Result<Vec<PendingFile>, ArgusError> {
+        let mut stats = ResumeStats::default();
+        let mut pending = Vec::new();
+        for file in files {
+            let mut chunks = Vec::new();
+            for chunk in self.chunk(file)? {
+                if self.index.has_chunk(&file.path, &chunk.content_hash)? {
+                    stats.present += 1;
+                } else {
+                    let text = self.embedding_text(&chunk, file);
+                    chunks.push((chunk, text));
+                }
+            }
+            stats.remaining += chunks.len();
+            pending.push(PendingFile {
+                path: file.path.clone(),
+                file_hash: compute_file_hash(&file.content),
+                chunks,
+            });
+        }
+        if stats.present > 0 {
+            self.resumed.set(Some(stats));
+        }
+        Ok(pending)
+    }
+
+    /// Embed and store pending chunks a group at a time, recording the
+    /// group's files in the same transaction as their chunks.
+    async fn embed_and_store<F, Fut>(
+        &self,
+        files: Vec<PendingFile>,
+        group_size: usize,
+        embed: F,
+    ) -> Result<(), ArgusError>
+    where
+        F: Fn(Vec<String>) -> Fut,
+        Fut: Future<Output = Result<Vec<Vec<f32>>, ArgusError>>,
+    {
+        let mut group: Vec<(CodeChunk, String)> = Vec::new();
+        let mut group_files: Vec<(PathBuf, String)> = Vec::new();
+        let last = files.len().saturating_sub(1);
+
+        for (i, file) in files.into_iter().enumerate() {
+            group.extend(file.chunks);
+            group_files.push((file.path, file.file_hash));
+            if group.len() < group_size && i < last {
+                continue;
+            }
+
+            let mut pairs: Vec<(CodeChunk, Vec<f32>)> = Vec::new();
+            if !group.is_empty() {
+                let (chunks, texts): (Vec<CodeChunk>, Vec<String>) = group.drain(..).unzip();
+                let embeddings = embed(texts).await?;
+                pairs = chunks.into_iter().zip(embeddings).collect();
+            }
+            self.index.insert_files(&group_files, &pairs)?;
+            group_files.clear();
+        }
+        Ok(())
+    }
+
+    async fn embed_timed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ArgusError> {
+        let started = Instant::now();
+        let embeddings = self.embedding_client.embed_document(texts).await?;
+        let (chunks, elapsed) = self.embedded.get();
+        self.embedded
+            .set((chunks + texts.len(), elapsed + started.elapsed()));
+        Ok(embeddings)
+    }
+
+    /// Text sent to the embedding model: context header + (optionally
+    /// comment-stripped) content.
+    fn embedding_text(&self, chunk: &CodeChunk, file: &SourceFile) -> String {
+        if self.strip_comments {
+            let content = strip_comments(&chunk.content, file.language);
+            format!("{}\n\n{}", chunk.context_header, content)
+        } else {
+            format!("{}\n\n{}", chunk.context_header, chunk.content)
+        }
+    }
+
+    fn chunk(&self, file: &SourceFile) -> Result<Vec<CodeChunk>, ArgusError> {
+        let strategy = self
+            .chunking
+            .get(&language_key(&file.path, file.language))
+            .copied()
+            .unwrap_or_default();
+        let chunks = chunk_file_with(&file.path, &file.content, file.language, strategy)?;
+        match self.fallback_window {
+            Some(window) if chunks.is_empty() => {
+                let sections = if strategy == ChunkStrategy::ByBlock {
+                    chunk_sections(&file.path, &file.content)
+                } else {
+                    Vec::new()
+                };
+                if sections.is_empty() {
+                    Ok(chunk_fixed_window(&file.path, &file.content, window))
+                } else {
+                    Ok(sections)
+                }
+            }
+            _ => Ok(chunks),
+        }
     }
 }
 
@@ -288,23 +650,32 @@ pub fn reciprocal_rank_fusion(
     keyword_results: &[SearchHit],
     k: usize,
 ) -> Vec<RrfResult> {
+    reciprocal_rank_fusion_lists(&[vector_results, keyword_results], k)
+}
+
+/// Combine any number of ranked result lists using Reciprocal Rank Fusion.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::search::reciprocal_rank_fusion_lists;
+/// use argus_codelens::store::SearchHit;
+///
+/// let lists: [Vec<SearchHit>; 3] = [vec![], vec![], vec![]];
+/// assert!(reciprocal_rank_fusion_lists(&lists, 60).is_empty());
+/// ```
+pub fn reciprocal_rank_fusion_lists(lists: &[impl AsRef<[SearchHit]>], k: usize) -> Vec<RrfResult> {
     let mut scores: HashMap<String, f64> = HashMap::new();
     let mut chunks: HashMap<String, CodeChunk> = HashMap::new();
 
-    for (rank, hit) in vector_results.iter().enumerate() {
-        let hash = &hit.chunk.content_hash;
-        *scores.entry(hash.clone()).or_default() += 1.0 / (k as f64 + rank as f64 + 1.0);
-        chunks
-            .entry(hash.clone())
-            .or_insert_with(|| hit.chunk.clone());
-    }
-
-    for (rank, hit) in keyword_results.iter().enumerate() {
-        let hash = &hit.chunk.content_hash;
-        *scores.entry(hash.clone()).or_default() += 1.0 / (k as f64 + rank as f64 + 1.0);
-        chunks
-            .entry(hash.clone())
-            .or_insert_with(|| hit.chunk.clone());
+    for list in lists {
+        for (rank, hit) in list.as_ref().iter().enumerate() {
+            let hash = &hit.chunk.content_hash;
+            *scores.entry(hash.clone()).or_default() += 1.0 / (k as f64 + rank as f64 + 1.0);
+            chunks
+                .entry(hash.clone())
+                .or_insert_with(|| hit.chunk.clone());
+        }
     }
 
     let mut results: Vec<RrfResult> = scores
@@ -321,6 +692,69 @@ pub fn reciprocal_rank_fusion(
     results
 }
 
+/// Most expansion terms a query gets.
+pub const MAX_EXPANSION_TERMS: usize = 3;
+
+/// Groups of words code commonly uses for the same idea, most useful first.
+const SYNONYMS: &[&[&str]] = &[
+    &[
+        "auth",
+        "login",
+        "credentials",
+        "session",
+        "authentication",
+        "token",
+    ],
+    &["error", "failure", "exception", "err", "panic"],
+    &["config", "settings", "options", "configuration"],
+    &["delete", "remove", "drop", "destroy"],
+    &["create", "insert", "add", "new"],
+    &["fetch", "load", "get", "retrieve"],
+    &["save", "store", "persist", "write"],
+    &["cache", "memoize", "memo"],
+    &["user", "account", "profile"],
+    &["log", "logger", "trace"],
+    &["parse", "decode", "deserialize"],
+    &["serialize", "encode", "dump"],
+    &["database", "db", "sql", "query"],
+    &["retry", "backoff", "attempt"],
+    &["permission", "role", "authorize", "acl"],
+];
+
+/// Related terms for `query` from a built-in synonym map, without an LLM.
+///
+/// Each word of the query (case-insensitive) pulls in the other words of
+/// its synonym group; words already in the query are left out. Returns at
+/// most [`MAX_EXPANSION_TERMS`] terms, in the order the groups list them.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::search::synonym_terms;
+///
+/// assert_eq!(synonym_terms("auth"), ["login", "credentials", "session"]);
+/// assert!(synonym_terms("frobnicate").is_empty());
+/// ```
+pub fn synonym_terms(query: &str) -> Vec<String> {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut terms: Vec<String> = Vec::new();
+    for word in &words {
+        for group in SYNONYMS.iter().filter(|g| g.contains(&word.as_str())) {
+            for &related in *group {
+                if !words.iter().any(|w| w == related) && !terms.iter().any(|t| t == related) {
+                    terms.push(related.to_string());
+                }
+            }
+        }
+    }
+    terms.truncate(MAX_EXPANSION_TERMS);
+    terms
+}
+
 fn compute_file_hash(content: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
@@ -394,9 +828,213 @@ mod tests {
         assert!(shared.score > unique_v.score);
     }
 
+    #[test]
+    fn synonym_expansion_finds_code_the_query_misses() {
+        let index = CodeIndex::in_memory().unwrap();
+        let mut hit = make_hit("login", "hash_login", SearchSource::Keyword);
+        hit.chunk.content = "fn login(credentials: &Credentials) -> Session {}".into();
+        index.record_file(&hit.chunk.file_path, "hash").unwrap();
+        index.insert_chunks(&[(hit.chunk, vec![0.0; 4])]).unwrap();
+        let search = HybridSearch::new(index, EmbeddingClient::new("key"));
+
+        let terms = synonym_terms("Auth check");
+        assert_eq!(terms, ["login", "credentials", "session"]);
+        let lists = search.keyword_lists("auth", &[], 10).unwrap();
+        assert!(lists.iter().all(Vec::is_empty));
+
+        let lists = search.keyword_lists("auth", &terms, 10).unwrap();
+        assert_eq!(lists.len(), 4);
+        let fused = reciprocal_rank_fusion_lists(&lists, 60);
+        assert_eq!(fused.len(), 1);
+        assert_eq!(fused[0].chunk.entity_name, "login");
+    }
+
     #[test]
     fn rrf_empty_inputs() {
         let fused = reciprocal_rank_fusion(&[], &[], 60);
         assert!(fused.is_empty());
     }
+
+    /// Deterministic bag-of-words embedding, so similarity tracks shared tokens.
+    fn bag_of_words(text: &str) -> Vec<f32> {
+        let mut v = vec![0.0f32; 64];
+        for token in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+        {
+            let bucket = token.bytes().fold(7usize, |h, b| h * 31 + b as usize) % 64;
+            v[bucket] += 1.0;
+        }
+        v
+    }
+
+    /// Rank `candidates` against `query` by cosine similarity of their
+    /// embedding texts, returning file names best-first.
+    fn rank_by_similarity(
+        search: &HybridSearch,
+        query: &SourceFile,
+        candidates: &[SourceFile],
+    ) -> Vec<String> {
+        let index = CodeIndex::in_memory().unwrap();
+        for file in candidates {
+            index.record_file(&file.path, "hash").unwrap();
+            for chunk in search.chunk(file).unwrap() {
+                let embedding = bag_of_words(&search.embedding_text(&chunk, file));
+                index.insert_chunks(&[(chunk, embedding)]).unwrap();
+            }
+        }
+        let query_chunk = search.chunk(query).unwrap().remove(0);
+        let query_embedding = bag_of_words(&search.embedding_text(&query_chunk, query));
+        index
+            .vector_search(&query_embedding, 10)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.chunk.file_path.display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn prune_removes_chunks_for_newly_ignored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("gen")).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn kept() {}\n").unwrap();
+        std::fs::write(dir.path().join("gen/out.rs"), "fn generated() {}\n").unwrap();
+
+        let search = HybridSearch::new(CodeIndex::in_memory().unwrap(), EmbeddingClient::new("k"));
+        for file in argus_repomap::walker::walk_repo(dir.path()).unwrap() {
+            search.index.record_file(&file.path, "hash").unwrap();
+            for chunk in search.chunk(&file).unwrap() {
+                search
+                    .index
+                    .insert_chunks(&[(chunk, vec![1.0; 4])])
+                    .unwrap();
+            }
+        }
+        assert_eq!(search.index.stats().unwrap().total_files, 2);
+
+        // Nothing is excluded yet
+        assert_eq!(
+            search.prune_repo(dir.path()).unwrap(),
+            PruneStats::default()
+        );
+
+        std::fs::write(dir.path().join(".argusignore"), "gen/\n").unwrap();
+        let pruned = search.prune_repo(dir.path()).unwrap();
+        assert_eq!(
+            pruned,
+            PruneStats {
+                files: 1,
+                chunks: 1
+            }
+        );
+        assert_eq!(search.index.indexed_files().unwrap(), ["lib.rs"]);
+        assert!(search
+            .index
+            .keyword_search("generated", 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn interrupted_index_resumes_without_re_embedding() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["alpha", "beta", "gamma"] {
+            std::fs::write(
+                dir.path().join(format!("{name}.rs")),
+                format!("fn {name}() {{}}\n"),
+            )
+            .unwrap();
+        }
+        let files = argus_repomap::walker::walk_repo(dir.path()).unwrap();
+        let search = HybridSearch::new(CodeIndex::in_memory().unwrap(), EmbeddingClient::new("k"));
+
+        // The second group fails, as if the network dropped mid-run
+        let calls = Cell::new(0);
+        let pending = search.pending_files(&files).unwrap();
+        let result = search
+            .embed_and_store(pending, 1, |texts| {
+                calls.set(calls.get() + 1);
+                let call = calls.get();
+                async move {
+                    if call == 2 {
+                        Err(ArgusError::Embedding("connection reset".into()))
+                    } else {
+                        Ok(vec![vec![1.0; 4]; texts.len()])
+                    }
+                }
+            })
+            .await;
+        assert!(result.is_err());
+        let done = search.index.indexed_files().unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(search.index.stats().unwrap().total_chunks, 1);
+
+        let embedded = RefCell::new(Vec::new());
+        let pending = search.pending_files(&files).unwrap();
+        assert_eq!(
+            search.resume_stats(),
+            Some(ResumeStats {
+                present: 1,
+                remaining: 2
+            })
+        );
+        search
+            .embed_and_store(pending, 1, |texts| {
+                embedded.borrow_mut().extend(texts.iter().cloned());
+                async move { Ok(vec![vec![1.0; 4]; texts.len()]) }
+            })
+            .await
+            .unwrap();
+
+        let embedded = embedded.into_inner();
+        let done_name = done[0].trim_end_matches(".rs");
+        assert_eq!(embedded.len(), 2);
+        assert!(!embedded
+            .iter()
+            .any(|text| text.contains(&format!("fn {done_name}"))));
+        let stats = search.index.stats().unwrap();
+        assert_eq!((stats.total_chunks, stats.total_files), (3, 3));
+    }
+
+    #[test]
+    fn license_header_does_not_drive_similarity_when_stripped() {
+        let header = "# Copyright (c) Example Corporation. All rights reserved.\n\
+                      # Licensed under the Apache License, Version 2.0 (the \"License\");\n\
+                      # you may not use this file except in compliance with the License.\n\
+                      # Unless required by applicable law or agreed to in writing, software\n\
+                      # distributed under the License is distributed on an \"AS IS\" BASIS,\n\
+                      # WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.\n";
+        let py = |path: &str, content: String| SourceFile {
+            path: PathBuf::from(path),
+            language: argus_repomap::walker::Language::Python,
+            content,
+        };
+        let query = py(
+            "billing.py",
+            format!("{header}total = sum(prices) * tax_rate\n"),
+        );
+        let licensed = py(
+            "network.py",
+            format!("{header}conn = connect(host, port)\n"),
+        );
+        let related = py(
+            "invoice.py",
+            "subtotal = sum(prices)\ntotal = subtotal * tax_rate\n".into(),
+        );
+        let candidates = [licensed, related];
+
+        let plain = HybridSearch::new(CodeIndex::in_memory().unwrap(), EmbeddingClient::new("k"))
+            .with_fallback_chunking(FixedWindow::default());
+        assert_eq!(
+            rank_by_similarity(&plain, &query, &candidates)[0],
+            "network.py",
+            "without stripping the shared header dominates"
+        );
+
+        let stripped = plain.with_comment_stripping();
+        assert_eq!(
+            rank_by_similarity(&stripped, &query, &candidates)[0],
+            "invoice.py"
+        );
+    }
 }
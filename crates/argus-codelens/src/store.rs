@@ -5,11 +5,13 @@
 
 use std::{
     cmp::{Ordering, Reverse},
-    collections::BinaryHeap,
+    collections::{BTreeMap, BinaryHeap, HashSet},
+    fmt,
+    fs::{File, TryLockError},
     path::{Path, PathBuf},
 };
 
-use argus_core::ArgusError;
+use argus_core::{ArgusError, FtsTokenizer};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +43,7 @@ use crate::chunker::CodeChunk;
 /// };
 /// assert!(hit.score > 0.9);
 /// ```
+#[derive(Debug)]
 pub struct SearchHit {
     /// The matched chunk (without embedding).
     pub chunk: CodeChunk,
@@ -97,6 +100,94 @@ pub struct IndexStats {
     pub total_feedback: usize,
 }
 
+/// Number of chunks stored under one language or file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkCount {
+    /// Language name, or file path relative to the repository root.
+    pub name: String,
+    /// Chunks stored for it.
+    pub chunks: usize,
+}
+
+/// Detailed breakdown of an index, for `argus search --stats`.
+///
+/// Built by [`CodeIndex::breakdown`]. Its `Display` output lists the ten
+/// files with the most chunks and up to ten missing and orphaned files;
+/// the JSON form has every entry.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexBreakdown {
+    /// Totals, as [`CodeIndex::stats`] reports them.
+    #[serde(flatten)]
+    pub stats: IndexStats,
+    /// Mean lines per chunk.
+    pub average_chunk_lines: f64,
+    /// Mean characters per chunk.
+    pub average_chunk_chars: f64,
+    /// Chunks per language, most first.
+    pub languages: Vec<ChunkCount>,
+    /// Chunks per file, most first.
+    pub files: Vec<ChunkCount>,
+    /// Repository files that should be indexed but never were.
+    pub missing_files: Vec<String>,
+    /// Indexed files that no longer exist on disk.
+    pub orphaned_files: Vec<String>,
+    /// Chunks belonging to `orphaned_files`.
+    pub orphaned_chunks: usize,
+}
+
+impl fmt::Display for IndexBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const LISTED: usize = 10;
+        writeln!(
+            f,
+            "Index: {} chunks in {} files, {:.1} MB",
+            self.stats.total_chunks,
+            self.stats.total_files,
+            self.stats.index_size_bytes as f64 / 1_048_576.0
+        )?;
+        writeln!(
+            f,
+            "Average chunk: {:.1} lines, {:.0} characters",
+            self.average_chunk_lines, self.average_chunk_chars
+        )?;
+
+        writeln!(f, "\nBy language:")?;
+        for language in &self.languages {
+            writeln!(f, "  {:<12} {:>7}", language.name, language.chunks)?;
+        }
+        writeln!(f, "\nMost chunks:")?;
+        for file in self.files.iter().take(LISTED) {
+            writeln!(f, "  {:>7}  {}", file.chunks, file.name)?;
+        }
+
+        let listed = |f: &mut fmt::Formatter<'_>, title: &str, paths: &[String]| -> fmt::Result {
+            writeln!(f, "\n{title} ({}):", paths.len())?;
+            for path in paths.iter().take(LISTED) {
+                writeln!(f, "  {path}")?;
+            }
+            if paths.len() > LISTED {
+                writeln!(f, "  ... and {} more", paths.len() - LISTED)?;
+            }
+            Ok(())
+        };
+        listed(
+            f,
+            "Not indexed (run `argus search --reindex`)",
+            &self.missing_files,
+        )?;
+        listed(
+            f,
+            &format!(
+                "Orphaned, {} chunks (run `argus search --prune` or `--reindex`)",
+                self.orphaned_chunks
+            ),
+            &self.orphaned_files,
+        )
+    }
+}
+
 /// User feedback on a review comment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feedback {
@@ -127,8 +218,39 @@ pub struct Feedback {
 /// ```
 pub struct CodeIndex {
     conn: Connection,
+    tokenizer: FtsTokenizer,
+    stop_words: Vec<String>,
 }
 
+/// Version of the on-disk schema, stored in the metadata table.
+///
+/// Version 2 records the FTS tokenizer; indexes without a version were
+/// built with SQLite's default `unicode61` tokenizer. Version 3 keys chunks
+/// by file and content hash, so identical chunks in different files are
+/// each stored. Version 4 adds the split identifier `terms` of each chunk to
+/// keyword search.
+const SCHEMA_VERSION: u32 = 4;
+
+/// The `chunks` table, shared by new indexes and the version 3 migration.
+const CREATE_CHUNKS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS chunks (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        file_path TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        start_line INTEGER NOT NULL,
+        end_line INTEGER NOT NULL,
+        entity_name TEXT NOT NULL,
+        entity_type TEXT NOT NULL,
+        language TEXT NOT NULL,
+        content TEXT NOT NULL,
+        context_header TEXT NOT NULL,
+        embedding BLOB,
+        terms TEXT NOT NULL DEFAULT '',
+        UNIQUE (file_path, content_hash),
+        FOREIGN KEY (file_path) REFERENCES files(path)
+    );
+";
+
 #[derive(Debug)]
 struct ScoredChunk {
     score: f64,
@@ -194,6 +316,31 @@ impl CodeIndex {
     /// let index = CodeIndex::open(Path::new(".argus/index.db")).unwrap();
     /// ```
     pub fn open(path: &Path) -> Result<Self, ArgusError> {
+        Self::open_with_tokenizer(path, FtsTokenizer::default())
+    }
+
+    /// Open or create an index whose keyword search uses `tokenizer`.
+    ///
+    /// A new index is created with `tokenizer`. An existing index keeps the
+    /// tokenizer it was built with; [`check_tokenizer`](Self::check_tokenizer)
+    /// reports the mismatch and [`rebuild_fts`](Self::rebuild_fts) fixes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] if the database cannot be opened.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use argus_codelens::store::CodeIndex;
+    /// use argus_core::FtsTokenizer;
+    ///
+    /// let index =
+    ///     CodeIndex::open_with_tokenizer(Path::new(".argus/index.db"), FtsTokenizer::Trigram)
+    ///         .unwrap();
+    /// ```
+    pub fn open_with_tokenizer(path: &Path, tokenizer: FtsTokenizer) -> Result<Self, ArgusError> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
                 ArgusError::Database(format!("failed to create index directory: {e}"))
@@ -202,7 +349,11 @@ impl CodeIndex {
         let conn = Connection::open(path)
             .map_err(|e| ArgusError::Database(format!("failed to open database: {e}")))?;
 
-        let index = Self { conn };
+        let index = Self {
+            conn,
+            tokenizer,
+            stop_words: Vec::new(),
+        };
         index.init_schema()?;
         Ok(index)
     }
@@ -221,15 +372,56 @@ impl CodeIndex {
     /// let index = CodeIndex::in_memory().unwrap();
     /// ```
     pub fn in_memory() -> Result<Self, ArgusError> {
+        Self::in_memory_with_tokenizer(FtsTokenizer::default())
+    }
+
+    /// Create an in-memory index whose keyword search uses `tokenizer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] if schema creation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::store::CodeIndex;
+    /// use argus_core::FtsTokenizer;
+    ///
+    /// let index = CodeIndex::in_memory_with_tokenizer(FtsTokenizer::Trigram).unwrap();
+    /// assert_eq!(index.stored_tokenizer().unwrap(), FtsTokenizer::Trigram);
+    /// ```
+    pub fn in_memory_with_tokenizer(tokenizer: FtsTokenizer) -> Result<Self, ArgusError> {
         let conn = Connection::open_in_memory().map_err(|e| {
             ArgusError::Database(format!("failed to create in-memory database: {e}"))
         })?;
 
-        let index = Self { conn };
+        let index = Self {
+            conn,
+            tokenizer,
+            stop_words: Vec::new(),
+        };
         index.init_schema()?;
         Ok(index)
     }
 
+    /// Leave `stop_words` out of keyword queries and of the split
+    /// identifier terms of chunks inserted from now on (case-insensitive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::store::CodeIndex;
+    ///
+    /// let index = CodeIndex::in_memory()
+    ///     .unwrap()
+    ///     .with_stop_words(vec!["get".into(), "set".into()]);
+    /// assert!(index.keyword_search("get", 5).unwrap().is_empty());
+    /// ```
+    pub fn with_stop_words(mut self, stop_words: Vec<String>) -> Self {
+        self.stop_words = stop_words;
+        self
+    }
+
     fn init_schema(&self) -> Result<(), ArgusError> {
         self.conn
             .execute_batch(
@@ -245,58 +437,257 @@ impl CodeIndex {
                     indexed_at TEXT NOT NULL
                 );
 
-                CREATE TABLE IF NOT EXISTS chunks (
+                CREATE TABLE IF NOT EXISTS feedback (
                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    comment_id TEXT NOT NULL,
                     file_path TEXT NOT NULL,
-                    content_hash TEXT NOT NULL UNIQUE,
-                    start_line INTEGER NOT NULL,
-                    end_line INTEGER NOT NULL,
-                    entity_name TEXT NOT NULL,
-                    entity_type TEXT NOT NULL,
-                    language TEXT NOT NULL,
-                    content TEXT NOT NULL,
-                    context_header TEXT NOT NULL,
-                    embedding BLOB,
-                    FOREIGN KEY (file_path) REFERENCES files(path)
+                    line_number INTEGER,
+                    comment_text TEXT NOT NULL,
+                    rating INTEGER NOT NULL,
+                    timestamp TEXT NOT NULL,
+                    UNIQUE(comment_id)
                 );
+                ",
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to create schema: {e}")))?;
+        self.conn
+            .execute_batch(CREATE_CHUNKS_TABLE)
+            .map_err(|e| ArgusError::Database(format!("failed to create schema: {e}")))?;
+        let migrated = self.add_terms_column()? | self.key_chunks_by_file()?;
+
+        let fts_exists: bool = self
+            .conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'chunks_fts')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to inspect schema: {e}")))?;
+        if !fts_exists {
+            self.create_fts(self.tokenizer)?;
+        } else if migrated {
+            // Keep the tokenizer the index was built with; switching is up to `--index`
+            self.recreate_fts(self.stored_tokenizer()?)?;
+        }
+
+        Ok(())
+    }
 
-                CREATE VIRTUAL TABLE IF NOT EXISTS chunks_fts USING fts5(
-                    entity_name, content, context_header,
-                    content='chunks', content_rowid='id'
+    /// Rebuild the `chunks` table of an index from before schema version 3,
+    /// where a content hash was unique across the whole index, so that it is
+    /// unique per file. Returns whether the table was rebuilt, in which case
+    /// the FTS table has to be rebuilt too.
+    fn key_chunks_by_file(&self) -> Result<bool, ArgusError> {
+        let sql: String = self
+            .conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'chunks'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to inspect schema: {e}")))?;
+        if !sql.contains("content_hash TEXT NOT NULL UNIQUE") {
+            return Ok(false);
+        }
+
+        self.conn
+            .execute_batch(&format!(
+                "DROP TRIGGER IF EXISTS chunks_ai;
+                 DROP TRIGGER IF EXISTS chunks_ad;
+                 DROP TRIGGER IF EXISTS chunks_au;
+                 ALTER TABLE chunks RENAME TO chunks_v2;
+                 {CREATE_CHUNKS_TABLE}
+                 INSERT INTO chunks SELECT
+                     id, file_path, content_hash, start_line, end_line, entity_name,
+                     entity_type, language, content, context_header, embedding, terms
+                 FROM chunks_v2;
+                 DROP TABLE chunks_v2;"
+            ))
+            .map_err(|e| ArgusError::Database(format!("failed to re-key index chunks: {e}")))?;
+        Ok(true)
+    }
+
+    /// Add and fill the `terms` column of an index from before schema
+    /// version 4. Returns whether it was missing, in which case the FTS
+    /// table has to be rebuilt to search it.
+    fn add_terms_column(&self) -> Result<bool, ArgusError> {
+        let has_terms: bool = self
+            .conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('chunks') WHERE name = 'terms')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to inspect schema: {e}")))?;
+        if has_terms {
+            return Ok(false);
+        }
+
+        let db_err = |e: rusqlite::Error| {
+            ArgusError::Database(format!("failed to add identifier terms to index: {e}"))
+        };
+        // One transaction, so an interrupted backfill leaves no half-filled
+        // column behind to be taken for a finished migration on the next open
+        let tx = self.conn.unchecked_transaction().map_err(db_err)?;
+        // The old update trigger would re-index every chunk while filling the column
+        self.conn
+            .execute_batch(
+                "ALTER TABLE chunks ADD COLUMN terms TEXT NOT NULL DEFAULT '';
+                 DROP TRIGGER IF EXISTS chunks_au;",
+            )
+            .map_err(db_err)?;
+        let chunks: Vec<(i64, String, String)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id, entity_name, content FROM chunks")
+                .map_err(db_err)?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                .map_err(db_err)?;
+            rows.collect::<Result<_, _>>().map_err(db_err)?
+        };
+        for (id, entity_name, content) in chunks {
+            self.conn
+                .execute(
+                    "UPDATE chunks SET terms = ?1 WHERE id = ?2",
+                    params![
+                        identifier_terms(&format!("{entity_name} {content}"), &[]),
+                        id
+                    ],
+                )
+                .map_err(db_err)?;
+        }
+        tx.commit().map_err(db_err)?;
+        Ok(true)
+    }
+
+    /// Create the FTS table and its sync triggers with `tokenizer`.
+    fn create_fts(&self, tokenizer: FtsTokenizer) -> Result<(), ArgusError> {
+        let tokenize = match tokenizer {
+            FtsTokenizer::Unicode61 => "unicode61",
+            FtsTokenizer::Porter => "porter unicode61",
+            FtsTokenizer::Trigram => "trigram",
+        };
+        self.conn
+            .execute_batch(&format!(
+                "
+                CREATE VIRTUAL TABLE chunks_fts USING fts5(
+                    entity_name, content, context_header, terms,
+                    content='chunks', content_rowid='id',
+                    tokenize = '{tokenize}'
                 );
 
                 -- Triggers to keep FTS in sync
-                CREATE TRIGGER IF NOT EXISTS chunks_ai AFTER INSERT ON chunks BEGIN
-                    INSERT INTO chunks_fts(rowid, entity_name, content, context_header)
-                    VALUES (new.id, new.entity_name, new.content, new.context_header);
+                CREATE TRIGGER chunks_ai AFTER INSERT ON chunks BEGIN
+                    INSERT INTO chunks_fts(rowid, entity_name, content, context_header, terms)
+                    VALUES (new.id, new.entity_name, new.content, new.context_header, new.terms);
                 END;
 
-                CREATE TRIGGER IF NOT EXISTS chunks_ad AFTER DELETE ON chunks BEGIN
-                    INSERT INTO chunks_fts(chunks_fts, rowid, entity_name, content, context_header)
-                    VALUES ('delete', old.id, old.entity_name, old.content, old.context_header);
+                CREATE TRIGGER chunks_ad AFTER DELETE ON chunks BEGIN
+                    INSERT INTO chunks_fts(chunks_fts, rowid, entity_name, content, context_header, terms)
+                    VALUES ('delete', old.id, old.entity_name, old.content, old.context_header, old.terms);
                 END;
 
-                CREATE TRIGGER IF NOT EXISTS chunks_au AFTER UPDATE ON chunks BEGIN
-                    INSERT INTO chunks_fts(chunks_fts, rowid, entity_name, content, context_header)
-                    VALUES ('delete', old.id, old.entity_name, old.content, old.context_header);
-                    INSERT INTO chunks_fts(rowid, entity_name, content, context_header)
-                    VALUES (new.id, new.entity_name, new.content, new.context_header);
+                CREATE TRIGGER chunks_au AFTER UPDATE ON chunks BEGIN
+                    INSERT INTO chunks_fts(chunks_fts, rowid, entity_name, content, context_header, terms)
+                    VALUES ('delete', old.id, old.entity_name, old.content, old.context_header, old.terms);
+                    INSERT INTO chunks_fts(rowid, entity_name, content, context_header, terms)
+                    VALUES (new.id, new.entity_name, new.content, new.context_header, new.terms);
                 END;
+                "
+            ))
+            .map_err(|e| ArgusError::Database(format!("failed to create FTS index: {e}")))?;
 
-                CREATE TABLE IF NOT EXISTS feedback (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    comment_id TEXT NOT NULL,
-                    file_path TEXT NOT NULL,
-                    line_number INTEGER,
-                    comment_text TEXT NOT NULL,
-                    rating INTEGER NOT NULL,
-                    timestamp TEXT NOT NULL,
-                    UNIQUE(comment_id)
-                );
+        self.set_metadata("schema_version", &SCHEMA_VERSION.to_string())?;
+        self.set_metadata("fts_tokenizer", &tokenizer.to_string())
+    }
+
+    /// The tokenizer this index was opened with (and builds new FTS tables with).
+    pub fn tokenizer(&self) -> FtsTokenizer {
+        self.tokenizer
+    }
+
+    /// The tokenizer the existing FTS table was built with.
+    ///
+    /// Indexes from before schema version 2 report
+    /// [`FtsTokenizer::Unicode61`], SQLite's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on query failure or unknown metadata.
+    pub fn stored_tokenizer(&self) -> Result<FtsTokenizer, ArgusError> {
+        match self.get_metadata("fts_tokenizer")? {
+            Some(name) => FtsTokenizer::from_name(&name).ok_or_else(|| {
+                ArgusError::Database(format!("Corrupted tokenizer metadata in index: '{name}'"))
+            }),
+            None => Ok(FtsTokenizer::Unicode61),
+        }
+    }
+
+    /// Check that the FTS table was built with the configured tokenizer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Config`] if the index was built with a
+    /// different tokenizer, or [`ArgusError::Database`] on query failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::store::CodeIndex;
+    ///
+    /// let index = CodeIndex::in_memory().unwrap();
+    /// assert!(index.check_tokenizer().is_ok());
+    /// ```
+    pub fn check_tokenizer(&self) -> Result<(), ArgusError> {
+        let stored = self.stored_tokenizer()?;
+        if stored != self.tokenizer {
+            return Err(ArgusError::Config(format!(
+                "Index keyword search uses the {stored} tokenizer but config specifies {}. \
+                 Re-index with --index to rebuild.",
+                self.tokenizer
+            )));
+        }
+        Ok(())
+    }
+
+    /// Recreate the FTS table with the configured tokenizer.
+    ///
+    /// Chunks and embeddings are kept; only the keyword index is rebuilt
+    /// from the stored chunk text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] if the table cannot be rebuilt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::store::CodeIndex;
+    ///
+    /// let index = CodeIndex::in_memory().unwrap();
+    /// index.rebuild_fts().unwrap();
+    /// ```
+    pub fn rebuild_fts(&self) -> Result<(), ArgusError> {
+        self.recreate_fts(self.tokenizer)
+    }
+
+    /// Recreate the FTS table with `tokenizer` and refill it from the chunks.
+    fn recreate_fts(&self, tokenizer: FtsTokenizer) -> Result<(), ArgusError> {
+        self.conn
+            .execute_batch(
+                "
+                DROP TRIGGER IF EXISTS chunks_ai;
+                DROP TRIGGER IF EXISTS chunks_ad;
+                DROP TRIGGER IF EXISTS chunks_au;
+                DROP TABLE IF EXISTS chunks_fts;
                 ",
             )
-            .map_err(|e| ArgusError::Database(format!("failed to create schema: {e}")))?;
-
+            .map_err(|e| ArgusError::Database(format!("failed to drop FTS index: {e}")))?;
+        self.create_fts(tokenizer)?;
+        self.conn
+            .execute("INSERT INTO chunks_fts(chunks_fts) VALUES ('rebuild')", [])
+            .map_err(|e| ArgusError::Database(format!("failed to rebuild FTS index: {e}")))?;
         Ok(())
     }
 
@@ -346,6 +737,33 @@ impl CodeIndex {
         }
     }
 
+    /// Remove every chunk and file record, and forget the stored embedding
+    /// dimensions, ready for a full rebuild with a different model.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on delete failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_codelens::store::CodeIndex;
+    ///
+    /// let index = CodeIndex::in_memory().unwrap();
+    /// index.set_dimensions(1024).unwrap();
+    /// index.clear().unwrap();
+    /// assert_eq!(index.get_dimensions().unwrap(), None);
+    /// ```
+    pub fn clear(&self) -> Result<(), ArgusError> {
+        self.conn
+            .execute_batch(
+                "DELETE FROM chunks;
+                 DELETE FROM files;
+                 DELETE FROM metadata WHERE key = 'embedding_dimensions';",
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to clear index: {e}")))
+    }
+
     fn get_metadata(&self, key: &str) -> Result<Option<String>, ArgusError> {
         let result = self.conn.query_row(
             "SELECT value FROM metadata WHERE key = ?1",
@@ -404,8 +822,8 @@ impl CodeIndex {
             .execute(
                 "INSERT OR REPLACE INTO chunks
                  (file_path, content_hash, start_line, end_line, entity_name, entity_type,
-                  language, content, context_header, embedding)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                  language, content, context_header, embedding, terms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
                 params![
                     chunk.file_path.to_string_lossy().to_string(),
                     chunk.content_hash,
@@ -417,6 +835,10 @@ impl CodeIndex {
                     chunk.content,
                     chunk.context_header,
                     embedding_bytes,
+                    identifier_terms(
+                        &format!("{} {}", chunk.entity_name, chunk.content),
+                        &self.stop_words
+                    ),
                 ],
             )
             .map_err(|e| ArgusError::Database(format!("failed to insert chunk: {e}")))?;
@@ -456,6 +878,55 @@ impl CodeIndex {
         Ok(())
     }
 
+    /// Record `files` (path and content hash) as indexed and insert their
+    /// chunks, all in one transaction.
+    ///
+    /// An interrupted run thus leaves each file either fully indexed or
+    /// not recorded at all.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on insert failure, after rolling
+    /// back the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use argus_codelens::store::CodeIndex;
+    /// use argus_codelens::chunker::CodeChunk;
+    ///
+    /// let index = CodeIndex::in_memory().unwrap();
+    /// let chunk = CodeChunk {
+    ///     file_path: PathBuf::from("src/main.rs"),
+    ///     start_line: 1, end_line: 3,
+    ///     entity_name: "main".into(), entity_type: "function".into(),
+    ///     language: "rust".into(), content: "fn main() {}".into(),
+    ///     context_header: "# File: src/main.rs".into(),
+    ///     content_hash: "abc123".into(),
+    /// };
+    /// let files = [(PathBuf::from("src/main.rs"), "file_hash".to_string())];
+    /// index.insert_files(&files, &[(chunk, vec![0.1, 0.2, 0.3])]).unwrap();
+    /// assert!(index.has_chunk(Path::new("src/main.rs"), "abc123").unwrap());
+    /// ```
+    pub fn insert_files(
+        &self,
+        files: &[(PathBuf, String)],
+        chunks: &[(CodeChunk, Vec<f32>)],
+    ) -> Result<(), ArgusError> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| ArgusError::Database(format!("failed to start transaction: {e}")))?;
+        // Files first: chunks reference them
+        for (path, file_hash) in files {
+            self.record_file(path, file_hash)?;
+        }
+        self.insert_chunks(chunks)?;
+        tx.commit()
+            .map_err(|e| ArgusError::Database(format!("failed to commit index batch: {e}")))
+    }
+
     /// Vector similarity search (cosine similarity computed in Rust).
     ///
     /// Loads all embeddings from the database and computes cosine similarity
@@ -463,7 +934,9 @@ impl CodeIndex {
     ///
     /// # Errors
     ///
-    /// Returns [`ArgusError::Database`] on query failure.
+    /// Returns [`ArgusError::DimensionMismatch`] if the index stores
+    /// embeddings of a different size than `query_embedding`, and
+    /// [`ArgusError::Database`] on query failure.
     ///
     /// # Examples
     ///
@@ -482,6 +955,15 @@ impl CodeIndex {
         if limit == 0 {
             return Ok(Vec::new());
         }
+        // Mismatched vectors would all score 0 and return nothing useful
+        if let Some(stored) = self.get_dimensions()? {
+            if stored != query_embedding.len() {
+                return Err(ArgusError::DimensionMismatch {
+                    index: stored,
+                    query: query_embedding.len(),
+                });
+            }
+        }
 
         let mut stmt = self
             .conn
@@ -570,7 +1052,7 @@ impl CodeIndex {
     /// ```
     pub fn keyword_search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>, ArgusError> {
         // Escape FTS5 special characters for safety
-        let safe_query = sanitize_fts_query(query);
+        let safe_query = sanitize_fts_query(&self.without_stop_words(query));
         if safe_query.is_empty() {
             return Ok(Vec::new());
         }
@@ -622,7 +1104,20 @@ impl CodeIndex {
         Ok(hits)
     }
 
-    /// Check if a chunk with this `content_hash` already exists.
+    /// `query` without its stop words, or unchanged if nothing else is left.
+    fn without_stop_words(&self, query: &str) -> String {
+        let kept: Vec<&str> = query
+            .split_whitespace()
+            .filter(|word| !self.stop_words.iter().any(|s| s.eq_ignore_ascii_case(word)))
+            .collect();
+        if kept.is_empty() {
+            query.to_string()
+        } else {
+            kept.join(" ")
+        }
+    }
+
+    /// Check if `file_path` already has a chunk with this `content_hash`.
     ///
     /// # Errors
     ///
@@ -634,14 +1129,14 @@ impl CodeIndex {
     /// use argus_codelens::store::CodeIndex;
     ///
     /// let index = CodeIndex::in_memory().unwrap();
-    /// assert!(!index.has_chunk("nonexistent").unwrap());
+    /// assert!(!index.has_chunk(std::path::Path::new("src/main.rs"), "nonexistent").unwrap());
     /// ```
-    pub fn has_chunk(&self, content_hash: &str) -> Result<bool, ArgusError> {
+    pub fn has_chunk(&self, file_path: &Path, content_hash: &str) -> Result<bool, ArgusError> {
         let count: i64 = self
             .conn
             .query_row(
-                "SELECT COUNT(*) FROM chunks WHERE content_hash = ?1",
-                params![content_hash],
+                "SELECT COUNT(*) FROM chunks WHERE file_path = ?1 AND content_hash = ?2",
+                params![file_path.to_string_lossy().to_string(), content_hash],
                 |row| row.get(0),
             )
             .map_err(|e| ArgusError::Database(format!("failed to check chunk: {e}")))?;
@@ -796,6 +1291,95 @@ impl CodeIndex {
         })
     }
 
+    /// Break the index down by language and file, and compare it with the
+    /// repository.
+    ///
+    /// `repo_files` are the files under `root` that indexing would chunk,
+    /// relative to `root`; those the index has never recorded are reported
+    /// as missing.
+    /// Chunks whose file no longer exists under `root` are orphaned. Only
+    /// reads the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on query failure.
+    pub fn breakdown(
+        &self,
+        root: &Path,
+        repo_files: &[PathBuf],
+    ) -> Result<IndexBreakdown, ArgusError> {
+        let stats = self.stats()?;
+        let (average_chunk_lines, average_chunk_chars): (f64, f64) = self
+            .conn
+            .query_row(
+                "SELECT COALESCE(AVG(end_line - start_line + 1), 0), \
+                        COALESCE(AVG(LENGTH(content)), 0) FROM chunks",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| ArgusError::Database(format!("failed to average chunks: {e}")))?;
+
+        let languages = self.chunk_counts("language")?;
+        let files = self.chunk_counts("file_path")?;
+
+        let indexed: HashSet<String> = self.indexed_files()?.into_iter().collect();
+        let mut missing_files: Vec<String> = repo_files
+            .iter()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .filter(|p| !indexed.contains(p))
+            .collect();
+        missing_files.sort();
+
+        let mut orphaned_files = Vec::new();
+        let mut orphaned_chunks = 0;
+        for file in &files {
+            if !root.join(&file.name).exists() {
+                orphaned_files.push(file.name.clone());
+                orphaned_chunks += file.chunks;
+            }
+        }
+        orphaned_files.sort();
+
+        Ok(IndexBreakdown {
+            stats,
+            average_chunk_lines,
+            average_chunk_chars,
+            languages,
+            files,
+            missing_files,
+            orphaned_files,
+            orphaned_chunks,
+        })
+    }
+
+    /// Chunks per distinct value of `column`, most first, then by name.
+    fn chunk_counts(&self, column: &str) -> Result<Vec<ChunkCount>, ArgusError> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {column}, COUNT(*) FROM chunks GROUP BY {column}"
+            ))
+            .map_err(|e| ArgusError::Database(format!("failed to prepare query: {e}")))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .map_err(|e| ArgusError::Database(format!("failed to count chunks: {e}")))?;
+
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for row in rows {
+            let (name, chunks) =
+                row.map_err(|e| ArgusError::Database(format!("failed to read row: {e}")))?;
+            counts.insert(name, chunks as usize);
+        }
+        let mut counts: Vec<ChunkCount> = counts
+            .into_iter()
+            .map(|(name, chunks)| ChunkCount { name, chunks })
+            .collect();
+        counts.sort_by_key(|c| std::cmp::Reverse(c.chunks));
+        Ok(counts)
+    }
+
     /// Record user feedback for a review comment.
     ///
     /// # Errors
@@ -914,6 +1498,69 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
     dot / denom
 }
 
+/// The parts of the compound identifiers in `text`, lowercased and
+/// space-separated, for keyword search.
+///
+/// Identifiers are split at underscores and at camelCase boundaries, so
+/// `getUserById` yields `get user by id` and a search for `user` finds it.
+/// Identifiers with a single part, parts of one character, repeats, and
+/// `stop_words` (case-insensitive) are left out; the original text is
+/// indexed separately.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::store::identifier_terms;
+///
+/// assert_eq!(identifier_terms("getUserById(user_id)", &[]), "get user by id");
+/// assert_eq!(identifier_terms("HTTPServer::new", &["http".into()]), "server");
+/// ```
+pub fn identifier_terms(text: &str, stop_words: &[String]) -> String {
+    let mut seen = HashSet::new();
+    let mut terms = Vec::new();
+    for identifier in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let parts = split_identifier(identifier);
+        if parts.len() < 2 {
+            continue;
+        }
+        for part in parts {
+            let part = part.to_lowercase();
+            if part.chars().count() > 1
+                && !stop_words.iter().any(|w| w.eq_ignore_ascii_case(&part))
+                && seen.insert(part.clone())
+            {
+                terms.push(part);
+            }
+        }
+    }
+    terms.join(" ")
+}
+
+/// Split one identifier at underscores and camelCase boundaries, keeping
+/// acronyms together (`HTTPServer` is `HTTP`, `Server`).
+fn split_identifier(identifier: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    for word in identifier.split('_').filter(|w| !w.is_empty()) {
+        let chars: Vec<(usize, char)> = word.char_indices().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (at, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next_is_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            if c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next_is_lower))
+            {
+                parts.push(&word[start..at]);
+                start = at;
+            }
+        }
+        parts.push(&word[start..]);
+    }
+    parts
+}
+
 fn sanitize_fts_query(query: &str) -> String {
     // Split into words, wrap each in quotes for exact matching
     let words: Vec<String> = query
@@ -941,6 +1588,64 @@ fn chrono_now() -> String {
     format!("{}", duration.as_secs())
 }
 
+/// Exclusive advisory lock on an index, held while it is being written.
+///
+/// The lock lives in a sibling file (`index.db.lock` next to `index.db`)
+/// and is released when the guard is dropped or the process exits, so a
+/// crashed indexer never leaves a stale lock behind.
+///
+/// # Examples
+///
+/// ```
+/// use argus_codelens::store::IndexLock;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let index_path = dir.path().join("index.db");
+/// let lock = IndexLock::acquire(&index_path).unwrap();
+/// assert!(IndexLock::acquire(&index_path).is_err());
+/// drop(lock);
+/// assert!(IndexLock::acquire(&index_path).is_ok());
+/// ```
+#[derive(Debug)]
+pub struct IndexLock {
+    _file: File,
+}
+
+impl IndexLock {
+    /// Take the write lock for the index at `index_path` without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::IndexLocked`] if another process (or another
+    /// guard in this one) already holds the lock, or [`ArgusError::Database`]
+    /// if the lock file cannot be created.
+    pub fn acquire(index_path: &Path) -> Result<Self, ArgusError> {
+        let mut lock_path = index_path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let lock_path = PathBuf::from(lock_path);
+
+        if let Some(parent) = lock_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ArgusError::Database(format!("failed to create index directory: {e}"))
+            })?;
+        }
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| ArgusError::Database(format!("failed to open index lock: {e}")))?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Self { _file: file }),
+            Err(TryLockError::WouldBlock) => Err(ArgusError::IndexLocked(lock_path)),
+            Err(TryLockError::Error(e)) => {
+                Err(ArgusError::Database(format!("failed to lock index: {e}")))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -972,6 +1677,57 @@ mod tests {
         assert_eq!(stats.total_chunks, 1);
     }
 
+    #[test]
+    fn breakdown_counts_languages_files_missing_and_orphans() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let index = CodeIndex::in_memory().unwrap();
+        index
+            .record_file(Path::new("src/main.rs"), "file_hash")
+            .unwrap();
+        index
+            .record_file(Path::new("src/gone.py"), "gone_hash")
+            .unwrap();
+        let mut chunks = vec![
+            sample_chunk("main", "fn main() {}"),
+            sample_chunk("run", "fn run() {}"),
+        ];
+        let mut gone = sample_chunk("gone", "def gone(): pass");
+        gone.file_path = PathBuf::from("src/gone.py");
+        gone.language = "python".into();
+        chunks.push(gone);
+        for chunk in &chunks {
+            index.insert_chunk(chunk, &[0.1, 0.2]).unwrap();
+        }
+
+        let repo_files = [PathBuf::from("src/main.rs"), PathBuf::from("src/lib.rs")];
+        let breakdown = index.breakdown(root.path(), &repo_files).unwrap();
+        assert_eq!(breakdown.stats.total_chunks, 3);
+        assert_eq!(breakdown.average_chunk_lines, 5.0);
+        assert_eq!(
+            breakdown.languages,
+            [
+                ChunkCount {
+                    name: "rust".into(),
+                    chunks: 2
+                },
+                ChunkCount {
+                    name: "python".into(),
+                    chunks: 1
+                },
+            ]
+        );
+        assert_eq!(breakdown.files[0].name, "src/main.rs");
+        assert_eq!(breakdown.missing_files, ["src/lib.rs"]);
+        assert_eq!(breakdown.orphaned_files, ["src/gone.py"]);
+        assert_eq!(breakdown.orphaned_chunks, 1);
+
+        let text = breakdown.to_string();
+        assert!(text.contains("Not indexed (run `argus search --reindex`) (1):\n  src/lib.rs\n"));
+    }
+
     #[test]
     fn vector_search_finds_similar() {
         let index = CodeIndex::in_memory().unwrap();
@@ -1063,17 +1819,170 @@ mod tests {
         assert!(matches!(results[0].source, SearchSource::Keyword));
     }
 
+    fn index_with(tokenizer: FtsTokenizer, name: &str, content: &str) -> CodeIndex {
+        let index = CodeIndex::in_memory_with_tokenizer(tokenizer).unwrap();
+        index
+            .record_file(Path::new("src/main.rs"), "file_hash")
+            .unwrap();
+        index
+            .insert_chunk(&sample_chunk(name, content), &[0.1, 0.2])
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn porter_tokenizer_matches_word_stems() {
+        let content = "fn login() { /* runs authentication */ }";
+        let porter = index_with(FtsTokenizer::Porter, "login", content);
+        assert_eq!(porter.keyword_search("authenticate", 5).unwrap().len(), 1);
+
+        let plain = index_with(FtsTokenizer::Unicode61, "login", content);
+        assert!(plain.keyword_search("authenticate", 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn trigram_tokenizer_matches_identifier_substrings() {
+        let content = "fn parseHttpHeaders(raw: &str) {}";
+        let trigram = index_with(FtsTokenizer::Trigram, "parseHttpHeaders", content);
+        let results = trigram.keyword_search("HttpHead", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chunk.entity_name, "parseHttpHeaders");
+
+        let porter = index_with(FtsTokenizer::Porter, "parseHttpHeaders", content);
+        assert!(porter.keyword_search("HttpHead", 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn tokenizer_mismatch_is_detected_and_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("index.db");
+        {
+            let index = CodeIndex::open_with_tokenizer(&db, FtsTokenizer::Unicode61).unwrap();
+            index
+                .record_file(Path::new("src/main.rs"), "file_hash")
+                .unwrap();
+            let chunk = sample_chunk("login", "fn login() { /* runs authentication */ }");
+            index.insert_chunk(&chunk, &[0.1, 0.2]).unwrap();
+        }
+
+        let index = CodeIndex::open_with_tokenizer(&db, FtsTokenizer::Porter).unwrap();
+        assert_eq!(index.stored_tokenizer().unwrap(), FtsTokenizer::Unicode61);
+        let err = index.check_tokenizer().unwrap_err().to_string();
+        assert!(err.contains("unicode61"), "{err}");
+        assert!(index.keyword_search("authenticate", 5).unwrap().is_empty());
+
+        index.rebuild_fts().unwrap();
+        index.check_tokenizer().unwrap();
+        assert_eq!(index.keyword_search("authenticate", 5).unwrap().len(), 1);
+        assert_eq!(
+            index.get_metadata("schema_version").unwrap().as_deref(),
+            Some("4")
+        );
+    }
+
+    #[test]
+    fn keyword_search_matches_identifier_parts_without_stop_words() {
+        let content = "fn getUserById(user_id: u64) -> User { todo!() }";
+        let index = index_with(FtsTokenizer::Unicode61, "getUserById", content);
+        let results = index.keyword_search("user", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chunk.entity_name, "getUserById");
+        assert_eq!(index.keyword_search("by id", 5).unwrap().len(), 1);
+
+        index
+            .insert_chunk(&sample_chunk("getOrder", "fn getOrder() {}"), &[0.1, 0.2])
+            .unwrap();
+        assert_eq!(index.keyword_search("get user", 5).unwrap().len(), 2);
+        let index = index.with_stop_words(vec!["Get".into()]);
+        assert_eq!(index.keyword_search("get user", 5).unwrap().len(), 1);
+        assert_eq!(
+            identifier_terms(content, &["get".into(), "id".into()]),
+            "user by"
+        );
+        assert_eq!(identifier_terms("fn main() {}", &[]), "");
+    }
+
     #[test]
     fn has_chunk_dedup_works() {
         let index = CodeIndex::in_memory().unwrap();
         index
             .record_file(Path::new("src/main.rs"), "file_hash")
             .unwrap();
-        assert!(!index.has_chunk("hash_test").unwrap());
+        let main = Path::new("src/main.rs");
+        assert!(!index.has_chunk(main, "hash_test").unwrap());
 
         let chunk = sample_chunk("test", "fn test() {}");
         index.insert_chunk(&chunk, &[0.1]).unwrap();
-        assert!(index.has_chunk("hash_test").unwrap());
+        assert!(index.has_chunk(main, "hash_test").unwrap());
+
+        // The same chunk in another file is stored separately
+        let other = Path::new("src/other.rs");
+        assert!(!index.has_chunk(other, "hash_test").unwrap());
+        let copy = CodeChunk {
+            file_path: other.to_path_buf(),
+            ..chunk
+        };
+        index
+            .insert_files(
+                &[(other.to_path_buf(), "other_hash".into())],
+                &[(copy, vec![0.1])],
+            )
+            .unwrap();
+        assert!(index.has_chunk(other, "hash_test").unwrap());
+        assert_eq!(index.stats().unwrap().total_chunks, 2);
+    }
+
+    #[test]
+    fn old_index_is_rekeyed_by_file_and_keeps_its_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.db");
+        {
+            let index = CodeIndex::open(&path).unwrap();
+            index
+                .record_file(Path::new("src/main.rs"), "file_hash")
+                .unwrap();
+            index
+                .insert_chunk(
+                    &sample_chunk("authenticate", "fn authenticate() {}"),
+                    &[0.1],
+                )
+                .unwrap();
+            // Key its chunks by content hash alone, as before version 3
+            let old = CREATE_CHUNKS_TABLE
+                .replace(
+                    "content_hash TEXT NOT NULL,",
+                    "content_hash TEXT NOT NULL UNIQUE,",
+                )
+                .replace("UNIQUE (file_path, content_hash),", "")
+                .replace(
+                    "CREATE TABLE IF NOT EXISTS chunks",
+                    "CREATE TABLE chunks_old",
+                );
+            index
+                .conn
+                .execute_batch(&format!(
+                    "{old}
+                     INSERT INTO chunks_old SELECT * FROM chunks;
+                     DROP TABLE chunks;
+                     ALTER TABLE chunks_old RENAME TO chunks;"
+                ))
+                .unwrap();
+        }
+
+        let index = CodeIndex::open(&path).unwrap();
+        assert!(index
+            .has_chunk(Path::new("src/main.rs"), "hash_authenticate")
+            .unwrap());
+        assert_eq!(index.keyword_search("authenticate", 5).unwrap().len(), 1);
+        let sql: String = index
+            .conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE name = 'chunks'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(sql.contains("UNIQUE (file_path, content_hash)"), "{sql}");
     }
 
     #[test]
@@ -1156,6 +2065,28 @@ mod tests {
         assert!(err.contains("Re-index"));
     }
 
+    #[test]
+    fn vector_search_rejects_query_of_different_dimensions() {
+        let index = CodeIndex::in_memory().unwrap();
+        index.set_dimensions(3).unwrap();
+        index
+            .record_file(Path::new("src/main.rs"), "file_hash")
+            .unwrap();
+        index
+            .insert_chunk(&sample_chunk("auth", "fn auth() {}"), &[1.0, 0.0, 0.0])
+            .unwrap();
+
+        let err = index.vector_search(&[1.0, 0.0], 5).unwrap_err();
+        assert!(matches!(
+            err,
+            ArgusError::DimensionMismatch { index: 3, query: 2 }
+        ));
+
+        index.clear().unwrap();
+        assert_eq!(index.stats().unwrap().total_chunks, 0);
+        assert!(index.vector_search(&[1.0, 0.0], 5).unwrap().is_empty());
+    }
+
     #[test]
     fn get_dimensions_returns_none_for_new_index() {
         let index = CodeIndex::in_memory().unwrap();
@@ -1197,4 +2128,19 @@ mod tests {
         let negatives = index.get_negative_feedback(10).unwrap();
         assert!(negatives.is_empty());
     }
+
+    #[test]
+    fn second_index_lock_fails_fast_while_first_is_held() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_path = dir.path().join(".argus/index.db");
+
+        let first = IndexLock::acquire(&index_path).unwrap();
+        let err = IndexLock::acquire(&index_path).unwrap_err();
+        assert!(matches!(err, ArgusError::IndexLocked(ref path)
+            if path == &dir.path().join(".argus/index.db.lock")));
+        assert!(err.to_string().contains("locked by another process"));
+
+        drop(first);
+        assert!(IndexLock::acquire(&index_path).is_ok());
+    }
 }
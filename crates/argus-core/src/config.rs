@@ -1,15 +1,37 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::ArgusError;
 use crate::types::Severity;
 
+/// Language names accepted in `review.repo_map_languages`, matched
+/// case-insensitively.
+pub const REPO_MAP_LANGUAGES: &[&str] = &[
+    "rust",
+    "python",
+    "typescript",
+    "javascript",
+    "go",
+    "java",
+    "c",
+    "cpp",
+    "ruby",
+    "php",
+    "kotlin",
+    "swift",
+];
+
 /// A custom review rule defined in `.argus.toml`.
 ///
 /// Rules are injected into the LLM system prompt so the reviewer
-/// checks for project-specific patterns.
+/// checks for project-specific patterns. A rule with a `pattern` is
+/// instead enforced deterministically: the regex is matched against added
+/// lines, without the LLM. An invalid `pattern` or `paths` glob fails
+/// config loading.
+///
+/// Fields may be added over time; build rules with `..Default::default()`.
 ///
 /// # Examples
 ///
@@ -20,17 +42,26 @@ use crate::types::Severity;
 ///     name: "no-unwrap".into(),
 ///     severity: "warning".into(),
 ///     description: "Do not use .unwrap() in production code".into(),
+///     ..Default::default()
 /// };
 /// assert_eq!(rule.name, "no-unwrap");
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Rule {
     /// Short identifier for the rule (used in output).
     pub name: String,
     /// Severity level: "bug", "warning", or "suggestion".
     pub severity: String,
-    /// Natural language instruction for the LLM.
+    /// Natural language instruction for the LLM, or the message of a
+    /// `pattern` finding.
     pub description: String,
+    /// Regex matched against each added line; a match is reported as a
+    /// finding of this rule without asking the LLM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Globs limiting the files `pattern` is matched in (default: all).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paths: Option<Vec<String>>,
 }
 
 /// Top-level configuration loaded from `.argus.toml`.
@@ -56,10 +87,23 @@ pub struct ArgusConfig {
     /// Embedding provider settings for semantic search.
     #[serde(default)]
     pub embedding: EmbeddingConfig,
+    /// Repository walking limits shared by `map`, `search`, and `history`.
+    #[serde(default)]
+    pub path: WalkConfig,
+    /// Symbol graph settings for the repo map.
+    #[serde(default)]
+    pub map: MapConfig,
+    /// HTTP connection settings shared by the LLM and embedding clients.
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// Git history analysis settings.
+    #[serde(default)]
+    pub history: HistoryConfig,
     /// Per-path overrides for monorepo support.
     #[serde(default)]
     pub paths: HashMap<String, PathConfig>,
-    /// Custom review rules injected into the LLM prompt.
+    /// Custom review rules injected into the LLM prompt, or matched against
+    /// added lines when they have a `pattern`.
     #[serde(default)]
     pub rules: Vec<Rule>,
 }
@@ -89,7 +133,9 @@ impl ArgusConfig {
     ///
     /// # Errors
     ///
-    /// Returns [`ArgusError::Toml`] if parsing fails.
+    /// Returns [`ArgusError::Toml`] if parsing fails, or
+    /// [`ArgusError::Config`] if a setting has an unusable value, such as an
+    /// empty `todo_markers` entry or a rule `pattern` that is not a regex.
     ///
     /// # Examples
     ///
@@ -105,10 +151,130 @@ impl ArgusConfig {
     /// ```
     pub fn from_toml(content: &str) -> Result<Self, ArgusError> {
         let config: Self = toml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reject settings that parse but can't be used.
+    fn validate(&self) -> Result<(), ArgusError> {
+        if let Some(name) = self.review.repo_map_languages.iter().find(|name| {
+            !REPO_MAP_LANGUAGES
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(name))
+        }) {
+            return Err(ArgusError::Config(format!(
+                "unknown language '{name}' in review.repo_map_languages (expected one of: {})",
+                REPO_MAP_LANGUAGES.join(", ")
+            )));
+        }
+        if self
+            .review
+            .todo_markers
+            .iter()
+            .any(|marker| marker.trim().is_empty())
+        {
+            return Err(ArgusError::Config(
+                "review.todo_markers must not contain empty markers".into(),
+            ));
+        }
+        for rule in &self.rules {
+            if let Some(pattern) = &rule.pattern {
+                regex::Regex::new(pattern).map_err(|e| {
+                    ArgusError::Config(format!("rule '{}' has an invalid pattern: {e}", rule.name))
+                })?;
+            }
+            for path in rule.paths.iter().flatten() {
+                glob::Pattern::new(path).map_err(|e| {
+                    ArgusError::Config(format!(
+                        "rule '{}' has an invalid path glob '{path}': {e}",
+                        rule.name
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the nearest `.argus.toml` by walking up from `start`.
+    ///
+    /// Like git's search for `.git`, each directory from `start` upwards is
+    /// checked. The search stops after the git root (the first directory
+    /// containing `.git`) or at the filesystem root.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argus_core::ArgusConfig;
+    /// use std::path::Path;
+    ///
+    /// if let Some(path) = ArgusConfig::discover(Path::new("packages/app")) {
+    ///     let config = ArgusConfig::from_file(&path).unwrap();
+    /// }
+    /// ```
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        for dir in start.ancestors() {
+            let candidate = dir.join(".argus.toml");
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            if dir.join(".git").exists() {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// This configuration with the settings in `content`, the text of a
+    /// nested `.argus.toml`, laid over it.
+    ///
+    /// Tables are merged key by key, so a nested file only needs the keys it
+    /// changes. Any other value replaces the inherited one, including
+    /// arrays: a nested `rules` list replaces the outer rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Toml`] if `content` is not a valid
+    /// configuration, or [`ArgusError::Config`] if the merged settings are
+    /// unusable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::ArgusConfig;
+    ///
+    /// let outer = ArgusConfig::from_toml("[llm]\nprovider = \"anthropic\"\n").unwrap();
+    /// let nested = outer.overlay_toml("[review]\nmax_comments = 2\n").unwrap();
+    /// assert_eq!(nested.review.max_comments, 2);
+    /// assert_eq!(nested.llm.provider, "anthropic");
+    /// ```
+    pub fn overlay_toml(&self, content: &str) -> Result<Self, ArgusError> {
+        let mut base = toml::Value::try_from(self)
+            .map_err(|e| ArgusError::Config(format!("failed to serialize configuration: {e}")))?;
+        let overlay: toml::Value = toml::from_str(content)?;
+        merge_toml(&mut base, overlay);
+        let config: Self = base.try_into()?;
+        config.validate()?;
         Ok(config)
     }
 }
 
+/// Merge `overlay` into `base`: tables key by key, other values replaced.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 /// LLM provider configuration.
 ///
 /// # Examples
@@ -118,6 +284,10 @@ impl ArgusConfig {
 ///
 /// let config = LlmConfig::default();
 /// assert_eq!(config.model, "gpt-4o");
+/// assert!(config.fallback.is_empty());
+/// assert_eq!(config.temperature, 0.1);
+/// assert_eq!(config.seed, None);
+/// assert_eq!(config.max_output_tokens, None);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmConfig {
@@ -133,6 +303,24 @@ pub struct LlmConfig {
     pub base_url: Option<String>,
     /// Maximum input tokens to send per request.
     pub max_input_tokens: Option<usize>,
+    /// Providers to try, in order, when the primary provider keeps failing
+    /// with a retriable error (e.g. `["anthropic", "gemini"]`).
+    #[serde(default)]
+    pub fallback: Vec<String>,
+    /// Sampling temperature sent with every request (default: 0.1).
+    ///
+    /// `0.0` makes the model pick its most likely answer, for the most
+    /// repeatable reviews.
+    #[serde(default = "default_temperature")]
+    pub temperature: f64,
+    /// Sampling seed, for providers that support one: OpenAI, Gemini, and
+    /// Ollama (default: none). Anthropic has no seed parameter.
+    pub seed: Option<u64>,
+    /// Maximum tokens the provider may generate per response (default: a
+    /// per-provider value, 16384 for Anthropic and 8192 for Gemini and
+    /// Ollama; OpenAI-compatible servers keep their own limit). Raise it if
+    /// large reviews come back cut off.
+    pub max_output_tokens: Option<u32>,
 }
 
 fn default_provider() -> String {
@@ -143,6 +331,10 @@ fn default_model() -> String {
     "gpt-4o".into()
 }
 
+fn default_temperature() -> f64 {
+    0.1
+}
+
 impl Default for LlmConfig {
     fn default() -> Self {
         Self {
@@ -151,6 +343,10 @@ impl Default for LlmConfig {
             api_key: None,
             base_url: None,
             max_input_tokens: None,
+            fallback: Vec::new(),
+            temperature: default_temperature(),
+            seed: None,
+            max_output_tokens: None,
         }
     }
 }
@@ -165,17 +361,41 @@ impl Default for LlmConfig {
 /// let config = ReviewConfig::default();
 /// assert_eq!(config.min_confidence, 90.0);
 /// assert_eq!(config.max_comments, 5);
+/// assert_eq!(config.max_comments_per_file, None);
 /// assert!(!config.include_suggestions);
 /// assert_eq!(config.max_diff_tokens, 64000);
 /// assert!(config.cross_file);
 /// assert!(config.self_reflection);
 /// assert_eq!(config.self_reflection_score_threshold, 7);
+/// assert_eq!(config.self_reflection_mode, argus_core::SelfReflectionMode::Filter);
+/// assert_eq!(config.noise.min_hunk_lines, 0);
+/// assert_eq!(config.context_lines, 0);
+/// assert!(!config.expand_to_function);
+/// assert!(!config.flag_todos);
+/// assert!(!config.flag_breaking_changes);
+/// assert!(config.flag_new_dependencies);
+/// assert_eq!(config.focus, argus_core::ReviewFocus::General);
+/// assert_eq!(config.tone, argus_core::ReviewTone::Terse);
+/// assert!(config.summary);
+/// assert!(config.use_history_context);
+/// assert!(config.use_search_context);
+/// assert_eq!(config.max_message_chars, 2000);
+/// assert!(config.repo_map_languages.is_empty());
+/// assert!(config.include_intent);
+/// assert!(!config.recalibrate_confidence);
+/// assert!(!config.comments_on_changed_only);
+/// assert!(!config.test_presence_context);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewConfig {
     /// Maximum number of comments per review (default: 5).
     #[serde(default = "default_max_comments")]
     pub max_comments: usize,
+    /// Maximum number of comments on any one file, applied before
+    /// `max_comments` so one noisy file cannot use up the whole budget
+    /// (default: unlimited).
+    #[serde(default)]
+    pub max_comments_per_file: Option<usize>,
     /// Minimum LLM confidence to include a comment (default: 90.0).
     #[serde(default = "default_min_confidence")]
     pub min_confidence: f64,
@@ -204,8 +424,119 @@ pub struct ReviewConfig {
     #[serde(default = "default_self_reflection")]
     pub self_reflection: bool,
     /// Minimum score (1-10) a comment must receive during self-reflection to be kept (default: 7).
+    ///
+    /// `0` keeps every comment, so the pass only scores them.
     #[serde(default = "default_self_reflection_score_threshold")]
     pub self_reflection_score_threshold: u8,
+    /// What the self-reflection pass may do (default: `filter`).
+    ///
+    /// `filter_and_augment` also asks it for high-confidence issues the
+    /// first pass missed.
+    #[serde(default)]
+    pub self_reflection_mode: SelfReflectionMode,
+    /// Hunk-level noise reduction settings (`[review.noise]`).
+    #[serde(default)]
+    pub noise: NoiseConfig,
+    /// Lines of unchanged source to include around each hunk (default: 0, off).
+    ///
+    /// Only applies when the review has a repository path to read the
+    /// changed files from. Grounds the LLM in code just outside the hunk,
+    /// such as variables defined above it.
+    #[serde(default)]
+    pub context_lines: usize,
+    /// Widen each hunk's context to the whole enclosing function or method
+    /// (default: `false`).
+    ///
+    /// Needs a repository path, like `context_lines`. Expansion stops once
+    /// it would take the prompt past `max_diff_tokens`; hunks beyond that
+    /// get `context_lines` only.
+    #[serde(default)]
+    pub expand_to_function: bool,
+    /// Report added lines containing a TODO-style marker as findings,
+    /// without the LLM (default: `false`).
+    #[serde(default)]
+    pub flag_todos: bool,
+    /// Markers matched by `flag_todos`, as case-sensitive whole words
+    /// (default: `TODO`, `FIXME`, `XXX`, `HACK`).
+    #[serde(default = "default_todo_markers")]
+    pub todo_markers: Vec<String>,
+    /// Severity of `flag_todos` findings (default: `info`).
+    #[serde(default = "default_todo_severity")]
+    pub todo_severity: Severity,
+    /// Report public symbols the diff removes or changes the signature of
+    /// as `breaking-change` warnings, without the LLM (default: `false`).
+    #[serde(default)]
+    pub flag_breaking_changes: bool,
+    /// Summarize packages newly added to `Cargo.lock` or `package-lock.json`
+    /// as one `new-dependency` finding per lock file, without the LLM
+    /// (default: `true`). The lock files themselves stay unreviewed.
+    ///
+    /// The finding's severity is always `info`; a
+    /// [`SeverityOverride`] on the lock file's path can raise it.
+    #[serde(default = "default_flag_new_dependencies")]
+    pub flag_new_dependencies: bool,
+    /// Severity floors for matching paths (`[[review.severity_overrides]]`).
+    #[serde(default)]
+    pub severity_overrides: Vec<SeverityOverride>,
+    /// Area the review emphasizes (default: `general`).
+    #[serde(default)]
+    pub focus: ReviewFocus,
+    /// How much each finding explains (default: `terse`).
+    #[serde(default)]
+    pub tone: ReviewTone,
+    /// Ask the LLM for a short summary of the findings (default: `true`).
+    ///
+    /// Costs one extra LLM call per review that has comments.
+    #[serde(default = "default_summary")]
+    pub summary: bool,
+    /// Mine git history for hotspot and coupling context when reviewing
+    /// with `--repo` (default: `true`).
+    ///
+    /// Mining walks the whole history, which takes seconds on large repos.
+    #[serde(default = "default_use_history_context")]
+    pub use_history_context: bool,
+    /// Add related code from the `search` index to the prompt when one
+    /// exists under `--repo` (default: `true`).
+    #[serde(default = "default_use_search_context")]
+    pub use_search_context: bool,
+    /// Longest comment message shown in text and markdown output and PR
+    /// comments, in characters (default: 2000).
+    ///
+    /// Longer messages are cut with a note; JSON output keeps them whole.
+    #[serde(default = "default_max_message_chars")]
+    pub max_message_chars: usize,
+    /// Languages the repo map in the review prompt covers, by a name from
+    /// [`REPO_MAP_LANGUAGES`] such as `"python"` (default: empty, the
+    /// languages the diff touches). Related languages come along: mapping
+    /// TypeScript also maps JavaScript, and C also maps C++.
+    ///
+    /// Keeps a mostly-generated codebase in another language from using
+    /// up the map's token budget. An unknown name fails config loading.
+    #[serde(default)]
+    pub repo_map_languages: Vec<String>,
+    /// Add the commit messages (for `--commit` and `--compare`) or the PR
+    /// title and description (for `--pr`) to the prompt (default: `true`).
+    ///
+    /// Tells the LLM what the change is for, so a deliberate "temporarily
+    /// disable X" is not reported as a mistake.
+    #[serde(default = "default_include_intent")]
+    pub include_intent: bool,
+    /// Blend each comment's self-reflection score (1-10) into its
+    /// confidence before `min_confidence` filtering and sorting (default:
+    /// `false`).
+    ///
+    /// The score is mapped onto 0-100 and averaged with the LLM's own
+    /// confidence, so the second opinion also ranks the comments it keeps.
+    #[serde(default)]
+    pub recalibrate_confidence: bool,
+    /// Filter out LLM comments on lines the diff didn't add, such as
+    /// context lines around a change (default: `false`).
+    #[serde(default)]
+    pub comments_on_changed_only: bool,
+    /// Tell the LLM which changed functions no test file mentions
+    /// (default: `false`). Needs `--repo`.
+    #[serde(default)]
+    pub test_presence_context: bool,
 }
 
 fn default_max_comments() -> usize {
@@ -236,10 +567,43 @@ fn default_self_reflection_score_threshold() -> u8 {
     7
 }
 
+fn default_summary() -> bool {
+    true
+}
+
+fn default_use_history_context() -> bool {
+    true
+}
+
+fn default_use_search_context() -> bool {
+    true
+}
+
+fn default_max_message_chars() -> usize {
+    2000
+}
+
+fn default_include_intent() -> bool {
+    true
+}
+
+fn default_flag_new_dependencies() -> bool {
+    true
+}
+
+fn default_todo_markers() -> Vec<String> {
+    ["TODO", "FIXME", "XXX", "HACK"].map(String::from).to_vec()
+}
+
+fn default_todo_severity() -> Severity {
+    Severity::Info
+}
+
 impl Default for ReviewConfig {
     fn default() -> Self {
         Self {
             max_comments: default_max_comments(),
+            max_comments_per_file: None,
             min_confidence: default_min_confidence(),
             severity_filter: default_severity_filter(),
             skip_patterns: Vec::new(),
@@ -249,6 +613,345 @@ impl Default for ReviewConfig {
             cross_file: default_cross_file(),
             self_reflection: default_self_reflection(),
             self_reflection_score_threshold: default_self_reflection_score_threshold(),
+            self_reflection_mode: SelfReflectionMode::default(),
+            noise: NoiseConfig::default(),
+            context_lines: 0,
+            expand_to_function: false,
+            flag_todos: false,
+            todo_markers: default_todo_markers(),
+            todo_severity: default_todo_severity(),
+            flag_breaking_changes: false,
+            flag_new_dependencies: default_flag_new_dependencies(),
+            severity_overrides: Vec::new(),
+            focus: ReviewFocus::default(),
+            tone: ReviewTone::default(),
+            summary: default_summary(),
+            use_history_context: default_use_history_context(),
+            use_search_context: default_use_search_context(),
+            max_message_chars: default_max_message_chars(),
+            repo_map_languages: Vec::new(),
+            include_intent: default_include_intent(),
+            recalibrate_confidence: false,
+            comments_on_changed_only: false,
+            test_presence_context: false,
+        }
+    }
+}
+
+/// A minimum severity for comments on paths matching a glob
+/// (`[[review.severity_overrides]]`).
+///
+/// Comments below the floor are raised to it; comments above it are left
+/// alone.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusConfig, Severity};
+///
+/// let toml = r#"
+/// [[review.severity_overrides]]
+/// path = "payments/**"
+/// min_severity = "warning"
+/// "#;
+/// let config = ArgusConfig::from_toml(toml).unwrap();
+/// let rule = &config.review.severity_overrides[0];
+/// assert_eq!(rule.path, "payments/**");
+/// assert_eq!(rule.min_severity, Severity::Warning);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityOverride {
+    /// Glob matched against the comment's file path.
+    pub path: String,
+    /// Lowest severity a matching comment may have.
+    pub min_severity: Severity,
+}
+
+/// What the self-reflection pass does with the first-pass comments.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusConfig, SelfReflectionMode};
+///
+/// let config =
+///     ArgusConfig::from_toml("[review]\nself_reflection_mode = \"filter_and_augment\"\n").unwrap();
+/// assert_eq!(config.review.self_reflection_mode, SelfReflectionMode::FilterAndAugment);
+/// assert!(config.review.self_reflection_mode.augments());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfReflectionMode {
+    /// Score each comment and drop low-scored ones.
+    #[default]
+    Filter,
+    /// Filter, and also add high-confidence issues the first pass missed.
+    FilterAndAugment,
+}
+
+impl SelfReflectionMode {
+    /// Whether the pass may add comments of its own.
+    pub fn augments(self) -> bool {
+        self == Self::FilterAndAugment
+    }
+}
+
+/// Area a review emphasizes (`[review] focus`, `review --focus`).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusConfig, ReviewFocus};
+///
+/// let config = ArgusConfig::from_toml("[review]\nfocus = \"security\"\n").unwrap();
+/// assert_eq!(config.review.focus, ReviewFocus::Security);
+/// assert_eq!("performance".parse::<ReviewFocus>(), Ok(ReviewFocus::Performance));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewFocus {
+    /// Any genuine defect, with no extra emphasis.
+    #[default]
+    General,
+    /// Injection, authorization, secrets, and crypto misuse.
+    Security,
+    /// Allocations, N+1 queries, blocking I/O, and algorithmic cost.
+    Performance,
+    /// Logic errors, edge cases, and error handling.
+    Correctness,
+}
+
+impl std::str::FromStr for ReviewFocus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "general" => Ok(Self::General),
+            "security" => Ok(Self::Security),
+            "performance" => Ok(Self::Performance),
+            "correctness" => Ok(Self::Correctness),
+            other => Err(format!(
+                "unknown review focus: {other} (expected general, security, performance, or correctness)"
+            )),
+        }
+    }
+}
+
+/// How much each finding explains (`[review] tone`).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusConfig, ReviewTone};
+///
+/// let config = ArgusConfig::from_toml("[review]\ntone = \"explanatory\"\n").unwrap();
+/// assert_eq!(config.review.tone, ReviewTone::Explanatory);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewTone {
+    /// State the problem and the fix, for experienced teams.
+    #[default]
+    Terse,
+    /// Also explain why each problem matters and the principle behind it,
+    /// for newer contributors.
+    Explanatory,
+}
+
+/// Hunk-level noise reduction configuration (`[review.noise]`).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::NoiseConfig;
+///
+/// let config = NoiseConfig::default();
+/// assert_eq!(config.min_hunk_lines, 0);
+/// assert!(!config.exact_dedup);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoiseConfig {
+    /// Skip hunks with fewer added+removed lines than this (default: 0, disabled).
+    #[serde(default)]
+    pub min_hunk_lines: usize,
+    /// Treat comments on the same line as duplicates only when their
+    /// messages match exactly (default: `false`, which also compares
+    /// normalized message and suggestion text).
+    #[serde(default)]
+    pub exact_dedup: bool,
+}
+
+/// Limits applied when walking a repository (`[path]` section).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::WalkConfig;
+///
+/// let config = WalkConfig::default();
+/// assert_eq!(config.max_files, None);
+/// assert_eq!(config.max_file_bytes, 1_048_576);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkConfig {
+    /// Process at most this many files (default: unlimited).
+    #[serde(default)]
+    pub max_files: Option<usize>,
+    /// Skip files larger than this many bytes (default: 1 MB).
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+}
+
+fn default_max_file_bytes() -> u64 {
+    1_048_576
+}
+
+impl Default for WalkConfig {
+    fn default() -> Self {
+        Self {
+            max_files: None,
+            max_file_bytes: default_max_file_bytes(),
+        }
+    }
+}
+
+/// Symbol graph settings for the repo map (`[map]` section).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::MapConfig;
+///
+/// let config = MapConfig::default();
+/// assert!(config.ignored_references.iter().any(|name| name == "new"));
+/// assert_eq!(config.leaf_pruning_threshold, 50_000);
+/// assert_eq!(config.focus_hops, 2);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapConfig {
+    /// Identifiers that never create reference edges, because so many
+    /// symbols share them (`new`, `get`, `build`, ...) that they would turn
+    /// into ranking hubs. Setting this replaces the default list.
+    #[serde(default = "default_ignored_references")]
+    pub ignored_references: Vec<String>,
+    /// Above this many symbols, symbols nothing references are left out of
+    /// the PageRank iterations, trading a little accuracy for speed on huge
+    /// repositories (default: 50000).
+    #[serde(default = "default_leaf_pruning_threshold")]
+    pub leaf_pruning_threshold: usize,
+    /// Reference hops around the changed files that the repo maps built
+    /// for `review` and `describe` cover (default: 2).
+    #[serde(default = "default_focus_hops")]
+    pub focus_hops: usize,
+}
+
+fn default_leaf_pruning_threshold() -> usize {
+    50_000
+}
+
+fn default_ignored_references() -> Vec<String> {
+    [
+        "new",
+        "default",
+        "get",
+        "set",
+        "build",
+        "from",
+        "into",
+        "clone",
+        "len",
+        "is_empty",
+        "iter",
+        "next",
+        "map",
+        "fmt",
+        "to_string",
+        "eq",
+        "hash",
+        "drop",
+        "init",
+        "__init__",
+        "__str__",
+        "__repr__",
+        "constructor",
+        "toString",
+        "equals",
+        "hashCode",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_focus_hops() -> usize {
+    2
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self {
+            ignored_references: default_ignored_references(),
+            leaf_pruning_threshold: default_leaf_pruning_threshold(),
+            focus_hops: default_focus_hops(),
+        }
+    }
+}
+
+/// HTTP connection settings for the LLM and embedding clients (`[network]` section).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::NetworkConfig;
+///
+/// let config = NetworkConfig::default();
+/// assert_eq!(config.max_connections, 8);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Idle keep-alive connections kept open per host (default: 8).
+    #[serde(default = "default_max_connections")]
+    pub max_connections: usize,
+}
+
+fn default_max_connections() -> usize {
+    8
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: default_max_connections(),
+        }
+    }
+}
+
+/// Git history analysis settings (`[history]` section).
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::HistoryConfig;
+///
+/// let config = HistoryConfig::default();
+/// assert_eq!(config.min_commits, 10);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Below this many mined commits, history results are flagged as
+    /// low-confidence (default: 10).
+    #[serde(default = "default_min_commits")]
+    pub min_commits: usize,
+}
+
+fn default_min_commits() -> usize {
+    10
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            min_commits: default_min_commits(),
         }
     }
 }
@@ -286,6 +989,12 @@ pub struct PathConfig {
 /// assert_eq!(config.provider, "voyage");
 /// assert_eq!(config.model, "voyage-code-3");
 /// assert_eq!(config.dimensions, 1024);
+/// assert!(!config.fallback_chunking);
+/// assert!(!config.strip_comments);
+/// assert_eq!(config.fts_tokenizer, argus_core::FtsTokenizer::Porter);
+/// assert!(config.fts_stop_words.is_empty());
+/// assert_eq!(config.index_concurrency, 4);
+/// assert!(config.chunking.is_empty());
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
@@ -300,6 +1009,130 @@ pub struct EmbeddingConfig {
     /// Embedding dimensions (default: 1024).
     #[serde(default = "default_embedding_dimensions")]
     pub dimensions: usize,
+    /// Index files without a tree-sitter grammar (docs, YAML, SQL, ...) as
+    /// fixed-size line windows (default: `false`, increases index size).
+    #[serde(default)]
+    pub fallback_chunking: bool,
+    /// Lines per fallback chunk (default: 40).
+    #[serde(default = "default_fallback_window_lines")]
+    pub fallback_window_lines: usize,
+    /// Lines shared between consecutive fallback chunks (default: 10).
+    #[serde(default = "default_fallback_overlap_lines")]
+    pub fallback_overlap_lines: usize,
+    /// Strip comments and docstrings from chunks before embedding, so
+    /// boilerplate like license headers doesn't drive similarity
+    /// (default: `false`).
+    #[serde(default)]
+    pub strip_comments: bool,
+    /// Tokenizer for the keyword (full-text) half of the search index
+    /// (default: `porter`). Changing it requires rebuilding the index.
+    #[serde(default)]
+    pub fts_tokenizer: FtsTokenizer,
+    /// Words left out of keyword queries and of the identifier parts
+    /// indexed for them, such as `get` or `set` (default: none).
+    #[serde(default)]
+    pub fts_stop_words: Vec<String>,
+    /// Embedding batches in flight at once while indexing (default: 4).
+    /// Lower it for rate-limited APIs; 1 sends batches one at a time.
+    #[serde(default = "default_index_concurrency")]
+    pub index_concurrency: usize,
+    /// Chunking strategy per language name (`rust`, `python`, ...) or, for
+    /// files without a grammar, per extension (`md`, `toml`). Unlisted
+    /// languages chunk by function.
+    #[serde(default)]
+    pub chunking: BTreeMap<String, ChunkStrategy>,
+}
+
+impl EmbeddingConfig {
+    /// Chunking strategy for a language name or file extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::{ArgusConfig, ChunkStrategy};
+    ///
+    /// let config = ArgusConfig::from_toml("[embedding.chunking]\nrust = \"by_class\"\n").unwrap();
+    /// assert_eq!(config.embedding.chunk_strategy("rust"), ChunkStrategy::ByClass);
+    /// assert_eq!(config.embedding.chunk_strategy("go"), ChunkStrategy::ByFunction);
+    /// ```
+    pub fn chunk_strategy(&self, language: &str) -> ChunkStrategy {
+        self.chunking.get(language).copied().unwrap_or_default()
+    }
+}
+
+/// How a file is split into chunks for the search index.
+///
+/// Set per language under `[embedding.chunking]`, so each language is
+/// indexed at the granularity people search it by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// One chunk per function or method, plus one per type definition.
+    #[default]
+    ByFunction,
+    /// One chunk per class, `impl` block, trait, or interface, methods
+    /// included; functions outside them stay separate chunks.
+    ByClass,
+    /// One chunk per top-level block (items on consecutive lines together),
+    /// or per heading or table section in Markdown, TOML, INI, and YAML.
+    ByBlock,
+}
+
+/// Tokenizer used by the keyword search index.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusConfig, FtsTokenizer};
+///
+/// let config = ArgusConfig::from_toml("[embedding]\nfts_tokenizer = \"trigram\"\n").unwrap();
+/// assert_eq!(config.embedding.fts_tokenizer, FtsTokenizer::Trigram);
+/// assert_eq!(FtsTokenizer::Trigram.to_string(), "trigram");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FtsTokenizer {
+    /// Whole words, matched exactly (SQLite's default).
+    Unicode61,
+    /// Whole words reduced to their English stem, so "authenticate"
+    /// matches "authentication".
+    #[default]
+    Porter,
+    /// Overlapping three-character sequences, so any substring of an
+    /// identifier (three characters or longer) matches.
+    Trigram,
+}
+
+impl FtsTokenizer {
+    /// Parse a tokenizer name as written in `.argus.toml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::FtsTokenizer;
+    ///
+    /// assert_eq!(FtsTokenizer::from_name("porter"), Some(FtsTokenizer::Porter));
+    /// assert_eq!(FtsTokenizer::from_name("stemmed"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "unicode61" => Some(Self::Unicode61),
+            "porter" => Some(Self::Porter),
+            "trigram" => Some(Self::Trigram),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for FtsTokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Unicode61 => "unicode61",
+            Self::Porter => "porter",
+            Self::Trigram => "trigram",
+        };
+        f.write_str(name)
+    }
 }
 
 fn default_embedding_provider() -> String {
@@ -314,6 +1147,18 @@ fn default_embedding_dimensions() -> usize {
     1024
 }
 
+fn default_fallback_window_lines() -> usize {
+    40
+}
+
+fn default_fallback_overlap_lines() -> usize {
+    10
+}
+
+fn default_index_concurrency() -> usize {
+    4
+}
+
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
@@ -321,6 +1166,14 @@ impl Default for EmbeddingConfig {
             api_key: None,
             model: default_embedding_model(),
             dimensions: default_embedding_dimensions(),
+            fallback_chunking: false,
+            fallback_window_lines: default_fallback_window_lines(),
+            fallback_overlap_lines: default_fallback_overlap_lines(),
+            strip_comments: false,
+            fts_tokenizer: FtsTokenizer::default(),
+            fts_stop_words: Vec::new(),
+            index_concurrency: default_index_concurrency(),
+            chunking: BTreeMap::new(),
         }
     }
 }
@@ -343,9 +1196,13 @@ mod tests {
         assert_eq!(config.embedding.provider, "voyage");
         assert_eq!(config.embedding.model, "voyage-code-3");
         assert_eq!(config.embedding.dimensions, 1024);
+        assert!(!config.embedding.fallback_chunking);
         assert!(config.paths.is_empty());
+        assert_eq!(config.path.max_files, None);
         assert!(config.review.self_reflection);
         assert_eq!(config.review.self_reflection_score_threshold, 7);
+        assert_eq!(config.review.noise.min_hunk_lines, 0);
+        assert_eq!(config.network.max_connections, 8);
     }
 
     #[test]
@@ -368,6 +1225,7 @@ provider = "anthropic"
 model = "claude-sonnet-4-20250514"
 base_url = "https://api.anthropic.com"
 max_input_tokens = 50000
+max_output_tokens = 32000
 
 [review]
 max_comments = 3
@@ -381,6 +1239,7 @@ context_boundary = true
         let config = ArgusConfig::from_toml(toml).unwrap();
         assert_eq!(config.llm.provider, "anthropic");
         assert_eq!(config.llm.max_input_tokens, Some(50000));
+        assert_eq!(config.llm.max_output_tokens, Some(32000));
         assert_eq!(config.review.max_comments, 3);
         assert_eq!(config.review.severity_filter, vec![Severity::Bug]);
 
@@ -405,6 +1264,43 @@ context_boundary = true
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_network_config() {
+        let toml = r#"
+[network]
+max_connections = 32
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.network.max_connections, 32);
+    }
+
+    #[test]
+    fn parse_history_config() {
+        let toml = r#"
+[history]
+min_commits = 25
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.history.min_commits, 25);
+        assert_eq!(ArgusConfig::default().history.min_commits, 10);
+    }
+
+    #[test]
+    fn parse_map_ignored_references() {
+        let toml = r#"
+[map]
+ignored_references = ["new", "handle"]
+leaf_pruning_threshold = 1000
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.map.ignored_references, ["new", "handle"]);
+        assert_eq!(config.map.leaf_pruning_threshold, 1000);
+        assert!(ArgusConfig::default()
+            .map
+            .ignored_references
+            .contains(&"get".to_string()));
+    }
+
     #[test]
     fn parse_noise_reduction_config() {
         let toml = r#"
@@ -439,6 +1335,266 @@ max_comments = 10
         assert!(!config.review.include_suggestions);
     }
 
+    #[test]
+    fn discover_walks_up_to_nearest_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let nested = root.join("packages/app/src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".argus.toml"), "").unwrap();
+
+        assert_eq!(
+            ArgusConfig::discover(&nested),
+            Some(root.join(".argus.toml"))
+        );
+
+        // A closer config wins
+        std::fs::write(root.join("packages/.argus.toml"), "").unwrap();
+        assert_eq!(
+            ArgusConfig::discover(&nested),
+            Some(root.join("packages/.argus.toml"))
+        );
+    }
+
+    #[test]
+    fn discover_stops_at_git_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        // Config outside the repository must not be picked up
+        std::fs::write(dir.path().join(".argus.toml"), "").unwrap();
+
+        assert_eq!(ArgusConfig::discover(&nested), None);
+    }
+
+    #[test]
+    fn nested_config_overrides_only_the_keys_it_sets() {
+        let outer = ArgusConfig::from_toml(
+            r#"
+[llm]
+provider = "anthropic"
+
+[review]
+max_comments = 8
+skip_patterns = ["*.snap"]
+
+[[rules]]
+name = "outer"
+severity = "warning"
+description = "Outer rule"
+"#,
+        )
+        .unwrap();
+
+        let nested = outer
+            .overlay_toml(
+                r#"
+[review]
+skip_patterns = ["fixtures/**"]
+
+[[rules]]
+name = "inner"
+severity = "bug"
+description = "Inner rule"
+"#,
+            )
+            .unwrap();
+        assert_eq!(nested.llm.provider, "anthropic");
+        assert_eq!(nested.review.max_comments, 8);
+        assert_eq!(nested.review.skip_patterns, ["fixtures/**"]);
+        let names: Vec<&str> = nested.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["inner"]);
+
+        assert!(outer
+            .overlay_toml("[review]\nmax_comments = \"many\"\n")
+            .is_err());
+    }
+
+    #[test]
+    fn parse_llm_fallback_chain() {
+        let toml = r#"
+[llm]
+provider = "openai"
+fallback = ["anthropic", "gemini"]
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.llm.fallback, vec!["anthropic", "gemini"]);
+        assert!(ArgusConfig::default().llm.fallback.is_empty());
+    }
+
+    #[test]
+    fn parse_embedding_fallback_chunking() {
+        let toml = r#"
+[embedding]
+fallback_chunking = true
+fallback_window_lines = 60
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert!(config.embedding.fallback_chunking);
+        assert_eq!(config.embedding.fallback_window_lines, 60);
+        assert_eq!(config.embedding.fallback_overlap_lines, 10);
+        assert!(!config.embedding.strip_comments);
+        assert_eq!(config.embedding.provider, "voyage");
+
+        let config = ArgusConfig::from_toml("[embedding]\nstrip_comments = true\n").unwrap();
+        assert!(config.embedding.strip_comments);
+        assert_eq!(config.embedding.fts_tokenizer, FtsTokenizer::Porter);
+
+        let config =
+            ArgusConfig::from_toml("[embedding]\nfts_tokenizer = \"unicode61\"\n").unwrap();
+        assert_eq!(config.embedding.fts_tokenizer, FtsTokenizer::Unicode61);
+        assert!(ArgusConfig::from_toml("[embedding]\nfts_tokenizer = \"stem\"\n").is_err());
+
+        let config =
+            ArgusConfig::from_toml("[embedding]\nfts_stop_words = [\"get\", \"set\"]\n").unwrap();
+        assert_eq!(config.embedding.fts_stop_words, ["get", "set"]);
+
+        let config = ArgusConfig::from_toml("[embedding]\nindex_concurrency = 2\n").unwrap();
+        assert_eq!(config.embedding.index_concurrency, 2);
+    }
+
+    #[test]
+    fn parse_embedding_chunking_strategies() {
+        let toml = r#"
+[embedding.chunking]
+rust = "by_class"
+md = "by_block"
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(
+            config.embedding.chunk_strategy("rust"),
+            ChunkStrategy::ByClass
+        );
+        assert_eq!(
+            config.embedding.chunk_strategy("md"),
+            ChunkStrategy::ByBlock
+        );
+        assert_eq!(
+            config.embedding.chunk_strategy("python"),
+            ChunkStrategy::ByFunction
+        );
+        assert_eq!(config.embedding.provider, "voyage");
+        assert!(ArgusConfig::from_toml("[embedding.chunking]\nrust = \"by_file\"\n").is_err());
+    }
+
+    #[test]
+    fn parse_path_max_files() {
+        let toml = r#"
+[path]
+max_files = 5000
+max_file_bytes = 262144
+
+[paths."packages/auth"]
+context_boundary = true
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.path.max_files, Some(5000));
+        assert_eq!(config.path.max_file_bytes, 262_144);
+        assert!(config.paths["packages/auth"].context_boundary);
+    }
+
+    #[test]
+    fn parse_map_focus_hops() {
+        let config = ArgusConfig::from_toml("[map]\nfocus_hops = 1\n").unwrap();
+        assert_eq!(config.map.focus_hops, 1);
+        assert_eq!(ArgusConfig::default().map.focus_hops, 2);
+    }
+
+    #[test]
+    fn parse_review_noise_section() {
+        let toml = r#"
+[review]
+max_comments = 3
+max_comments_per_file = 2
+context_lines = 10
+expand_to_function = true
+
+self_reflection_mode = "filter_and_augment"
+focus = "correctness"
+tone = "explanatory"
+summary = false
+use_history_context = false
+use_search_context = false
+flag_todos = true
+todo_markers = ["TODO", "NOCOMMIT"]
+todo_severity = "suggestion"
+repo_map_languages = ["python"]
+include_intent = false
+recalibrate_confidence = true
+comments_on_changed_only = true
+test_presence_context = true
+flag_new_dependencies = false
+
+[review.noise]
+min_hunk_lines = 3
+exact_dedup = true
+"#;
+        let config = ArgusConfig::from_toml(toml).unwrap();
+        assert_eq!(config.review.max_comments, 3);
+        assert_eq!(config.review.max_comments_per_file, Some(2));
+        assert_eq!(config.review.context_lines, 10);
+        assert!(config.review.expand_to_function);
+        assert_eq!(
+            config.review.self_reflection_mode,
+            SelfReflectionMode::FilterAndAugment
+        );
+        assert_eq!(config.review.focus, ReviewFocus::Correctness);
+        assert_eq!(config.review.tone, ReviewTone::Explanatory);
+        assert!(!config.review.summary);
+        assert!(!config.review.use_history_context);
+        assert!(!config.review.use_search_context);
+        assert!(config.review.flag_todos);
+        assert_eq!(config.review.todo_markers, ["TODO", "NOCOMMIT"]);
+        assert_eq!(config.review.todo_severity, Severity::Suggestion);
+        assert_eq!(config.review.repo_map_languages, ["python"]);
+        assert!(!config.review.include_intent);
+        assert!(config.review.recalibrate_confidence);
+        assert!(config.review.comments_on_changed_only);
+        assert!(config.review.test_presence_context);
+        assert!(!config.review.flag_new_dependencies);
+        assert_eq!(config.review.noise.min_hunk_lines, 3);
+        assert!(config.review.noise.exact_dedup);
+    }
+
+    #[test]
+    fn empty_todo_marker_is_rejected() {
+        let err =
+            ArgusConfig::from_toml("[review]\ntodo_markers = [\"TODO\", \"\"]\n").unwrap_err();
+        assert!(err.to_string().contains("todo_markers"));
+        let outer = ArgusConfig::default();
+        assert!(outer
+            .overlay_toml("[review]\ntodo_markers = [\" \"]\n")
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_rule_pattern_is_rejected() {
+        let toml = "[[rules]]\nname = \"broken\"\nseverity = \"warning\"\ndescription = \"x\"\npattern = \"(\"\n";
+        let err = ArgusConfig::from_toml(toml).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("rule 'broken' has an invalid pattern"),
+            "{err}"
+        );
+
+        let toml = "[[rules]]\nname = \"globs\"\nseverity = \"warning\"\ndescription = \"x\"\npattern = \"x\"\npaths = [\"src/[\"]\n";
+        let err = ArgusConfig::from_toml(toml).unwrap_err();
+        assert!(err.to_string().contains("invalid path glob"), "{err}");
+    }
+
+    #[test]
+    fn unknown_repo_map_language_is_rejected() {
+        let err =
+            ArgusConfig::from_toml("[review]\nrepo_map_languages = [\"cobol\"]\n").unwrap_err();
+        assert!(err.to_string().contains("unknown language 'cobol'"));
+        let config =
+            ArgusConfig::from_toml("[review]\nrepo_map_languages = [\"TypeScript\"]\n").unwrap();
+        assert_eq!(config.review.repo_map_languages, ["TypeScript"]);
+    }
+
     #[test]
     fn parse_rules_from_toml() {
         let toml = r#"
@@ -451,9 +1607,16 @@ description = "Do not use .unwrap() in production code"
 name = "no-todo"
 severity = "suggestion"
 description = "Remove TODO comments before merging"
+
+[[rules]]
+name = "no-console-log"
+severity = "warning"
+description = "Remove console.log debugging"
+pattern = 'console\.log\('
+paths = ["src/**/*.ts"]
 "#;
         let config = ArgusConfig::from_toml(toml).unwrap();
-        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules.len(), 3);
         assert_eq!(config.rules[0].name, "no-unwrap");
         assert_eq!(config.rules[0].severity, "warning");
         assert_eq!(
@@ -462,6 +1625,9 @@ description = "Remove TODO comments before merging"
         );
         assert_eq!(config.rules[1].name, "no-todo");
         assert_eq!(config.rules[1].severity, "suggestion");
+        assert!(config.rules[1].pattern.is_none());
+        assert_eq!(config.rules[2].pattern.as_deref(), Some(r"console\.log\("));
+        assert_eq!(config.rules[2].paths, Some(vec!["src/**/*.ts".to_string()]));
     }
 
     #[test]
@@ -51,11 +51,16 @@ pub enum ArgusError {
     )]
     Parse(String),
 
-    /// LLM API or response error.
+    /// LLM setup error, such as an unknown provider.
     #[error("LLM error: {0}")]
     #[diagnostic(code(argus::llm), help("Check your API key and provider config in .argus.toml. Run `argus doctor` to diagnose."))]
     Llm(String),
 
+    /// A request to an LLM provider failed; see [`LlmError::kind`].
+    #[error("LLM error: {0}")]
+    #[diagnostic(transparent)]
+    LlmRequest(#[from] LlmError),
+
     /// JSON serialization / deserialization failure.
     #[error("Serialization error: {0}")]
     #[diagnostic(code(argus::serde))]
@@ -92,6 +97,131 @@ pub enum ArgusError {
         help("Try deleting .argus/index.db and re-indexing")
     )]
     Database(String),
+
+    /// Another process holds the search index write lock.
+    #[error("Index is locked by another process: {}", .0.display())]
+    #[diagnostic(
+        code(argus::index_locked),
+        help("Wait for the other `argus search --index` or `--reindex` to finish, then retry")
+    )]
+    IndexLocked(PathBuf),
+
+    /// The query embedding does not match the vectors stored in the index.
+    #[error(
+        "Embedding dimension mismatch: index has {index}-dimensional vectors but the query has {query}"
+    )]
+    #[diagnostic(
+        code(argus::dimension_mismatch),
+        help("The embedding model changed since the index was built. Run `argus search --index` to rebuild it, or pass --auto-reindex")
+    )]
+    DimensionMismatch {
+        /// Dimensions stored in the index.
+        index: usize,
+        /// Dimensions of the query embedding.
+        query: usize,
+    },
+}
+
+/// Why a request to an LLM provider failed.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::LlmErrorKind;
+///
+/// assert!(LlmErrorKind::RateLimit.is_retriable());
+/// assert!(!LlmErrorKind::Auth.is_retriable());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmErrorKind {
+    /// The API key is missing or was rejected.
+    Auth,
+    /// The provider is rate limiting requests.
+    RateLimit,
+    /// The request timed out.
+    Timeout,
+    /// The provider failed or is overloaded (5xx).
+    Server,
+    /// The provider rejected the request itself, e.g. an unknown model.
+    InvalidRequest,
+    /// The response could not be parsed or had an unexpected shape.
+    BadResponse,
+    /// No response arrived: DNS, connection, or TLS failure.
+    Network,
+}
+
+impl LlmErrorKind {
+    /// Whether the same request may succeed later or on another provider.
+    pub fn is_retriable(self) -> bool {
+        matches!(
+            self,
+            Self::RateLimit | Self::Timeout | Self::Server | Self::Network
+        )
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Self::Auth => "Your API key was missing or rejected. Check api_key under [llm] in .argus.toml or the provider's API key env var. Run `argus doctor` to diagnose.",
+            Self::RateLimit => "The provider is rate limiting requests. Wait a moment and try again, or list fallback providers under [llm] in .argus.toml.",
+            Self::Timeout => "The provider took too long to answer. Try again, or review a smaller diff.",
+            Self::Server => "The provider is failing or overloaded. Try again later, or list fallback providers under [llm] in .argus.toml.",
+            Self::InvalidRequest => "The provider rejected the request. Check the model name and base_url under [llm] in .argus.toml.",
+            Self::BadResponse => "The provider answered with something Argus could not read. Try again, or try a different model.",
+            Self::Network => "Could not reach the provider. Check your network connection and base_url under [llm] in .argus.toml.",
+        }
+    }
+}
+
+/// A failed LLM provider request, classified so callers can decide whether
+/// to retry and what to tell the user.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::{ArgusError, LlmError, LlmErrorKind};
+///
+/// let err = LlmError::new(LlmErrorKind::Auth, "OpenAI API error 401 Unauthorized: bad key")
+///     .with_status(401);
+/// assert_eq!(err.status, Some(401));
+/// let err: ArgusError = err.into();
+/// assert!(err.to_string().contains("401 Unauthorized"));
+/// ```
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub struct LlmError {
+    /// What went wrong.
+    pub kind: LlmErrorKind,
+    /// HTTP status code, when the provider answered.
+    pub status: Option<u16>,
+    /// Provider and reason, with secrets redacted.
+    pub message: String,
+}
+
+impl LlmError {
+    /// Create an error without an HTTP status.
+    pub fn new(kind: LlmErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            status: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attach the HTTP status the provider answered with.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
+
+impl Diagnostic for LlmError {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new("argus::llm"))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.kind.help()))
+    }
 }
 
 #[cfg(test)]
@@ -116,4 +246,37 @@ mod tests {
         let err = ArgusError::FileNotFound(PathBuf::from("/tmp/missing.rs"));
         assert!(err.to_string().contains("/tmp/missing.rs"));
     }
+
+    #[test]
+    fn index_locked_shows_lock_path() {
+        let err = ArgusError::IndexLocked(PathBuf::from(".argus/index.db.lock"));
+        assert_eq!(
+            err.to_string(),
+            "Index is locked by another process: .argus/index.db.lock"
+        );
+    }
+
+    #[test]
+    fn llm_request_help_depends_on_kind() {
+        let auth: ArgusError = LlmError::new(LlmErrorKind::Auth, "bad key").into();
+        let limited: ArgusError = LlmError::new(LlmErrorKind::RateLimit, "slow down").into();
+        assert_eq!(auth.to_string(), "LLM error: bad key");
+        assert!(auth.help().unwrap().to_string().contains("API key"));
+        assert!(limited
+            .help()
+            .unwrap()
+            .to_string()
+            .contains("rate limiting"));
+        assert_eq!(limited.code().unwrap().to_string(), "argus::llm");
+    }
+
+    #[test]
+    fn dimension_mismatch_shows_both_sizes() {
+        let err = ArgusError::DimensionMismatch {
+            index: 1024,
+            query: 768,
+        };
+        assert!(err.to_string().contains("index has 1024-dimensional"));
+        assert!(err.to_string().contains("the query has 768"));
+    }
 }
@@ -4,16 +4,22 @@
 //! - [`ArgusError`] — unified error type using `thiserror`
 //! - [`ArgusConfig`] — configuration loaded from `.argus.toml`
 //! - Shared types: [`FileNode`], [`DiffHunk`], [`RiskScore`], [`Severity`],
-//!   [`ReviewComment`], [`SearchResult`], [`OutputFormat`]
+//!   [`ReviewComment`], [`ReflectionScore`], [`Origin`], [`SearchResult`],
+//!   [`OutputFormat`]
 
 mod config;
 mod error;
 mod types;
 
-pub use config::{ArgusConfig, EmbeddingConfig, LlmConfig, PathConfig, ReviewConfig, Rule};
-pub use error::ArgusError;
+pub use config::{
+    ArgusConfig, ChunkStrategy, EmbeddingConfig, FtsTokenizer, HistoryConfig, LlmConfig, MapConfig,
+    NetworkConfig, NoiseConfig, PathConfig, ReviewConfig, ReviewFocus, ReviewTone, Rule,
+    SelfReflectionMode, SeverityOverride, WalkConfig, REPO_MAP_LANGUAGES,
+};
+pub use error::{ArgusError, LlmError, LlmErrorKind};
 pub use types::{
-    ChangeType, DiffHunk, FileNode, OutputFormat, ReviewComment, RiskScore, SearchResult, Severity,
+    ChangeType, DiffHunk, FileNode, Origin, OutputFormat, ReflectionScore, ReviewComment,
+    RiskScore, SearchResult, Severity,
 };
 
 /// A convenience `Result` type for Argus operations.
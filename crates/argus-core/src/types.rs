@@ -183,7 +183,7 @@ impl RiskScore {
 /// let s: Severity = serde_json::from_str("\"bug\"").unwrap();
 /// assert_eq!(s, Severity::Bug);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// A likely defect that should be fixed.
@@ -193,6 +193,7 @@ pub enum Severity {
     /// An optional improvement.
     Suggestion,
     /// Informational observation.
+    #[default]
     Info,
 }
 
@@ -251,6 +252,10 @@ impl Severity {
 
 /// A single review comment produced by the AI reviewer.
 ///
+/// Optional fields keep being added as the pipeline learns more about a
+/// finding, so code outside Argus should fill them in with
+/// `..ReviewComment::default()` rather than naming each one.
+///
 /// # Examples
 ///
 /// ```
@@ -264,12 +269,12 @@ impl Severity {
 ///     message: "Possible null dereference".into(),
 ///     confidence: 95.0,
 ///     suggestion: Some("Add a None check".into()),
-///     patch: None,
-///     rule: None,
+///     ..ReviewComment::default()
 /// };
 /// assert_eq!(comment.severity, Severity::Bug);
+/// assert!(comment.reflection.is_none());
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReviewComment {
     /// Path to the file being commented on.
@@ -290,6 +295,74 @@ pub struct ReviewComment {
     /// Custom rule name that matched this comment, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rule: Option<String>,
+    /// Last author of the commented line per `git blame` (only with `--blame`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_author: Option<String>,
+    /// Stable identity of the finding, for suppressing it through
+    /// `.argus/ignore-comments` (set by the review pipeline).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Why the issue matters, for readers new to the codebase or the
+    /// language (only with `[review] tone = "explanatory"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// The self-reflection pass's verdict on this comment (only when
+    /// self-reflection ran).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reflection: Option<ReflectionScore>,
+    /// Whether the compared branch introduced the flagged line (only with
+    /// `review --compare`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Origin>,
+}
+
+/// How the self-reflection pass rated a review comment.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::ReflectionScore;
+///
+/// let reflection = ReflectionScore {
+///     score: 8,
+///     reason: Some("Real null dereference on the error path".into()),
+/// };
+/// assert!(reflection.score >= 7);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReflectionScore {
+    /// Score from 1 (noise) to 10 (critical, must fix).
+    pub score: u8,
+    /// The evaluator's one-line justification, if it gave one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Whether a finding was introduced by the compared branch.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::Origin;
+///
+/// assert_eq!(Origin::PreExisting.to_string(), "pre-existing");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Origin {
+    /// The flagged line was added by the diff.
+    Introduced,
+    /// The flagged line already exists in the base.
+    PreExisting,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Introduced => write!(f, "introduced"),
+            Origin::PreExisting => write!(f, "pre-existing"),
+        }
+    }
 }
 
 /// A result from semantic code search.
@@ -354,6 +427,10 @@ pub enum OutputFormat {
     Markdown,
     /// SARIF v2.1.0 for GitHub Code Scanning.
     Sarif,
+    /// Flat comma-separated tables with a header row.
+    Csv,
+    /// JUnit XML for CI test dashboards.
+    Junit,
 }
 
 impl fmt::Display for OutputFormat {
@@ -363,6 +440,8 @@ impl fmt::Display for OutputFormat {
             OutputFormat::Json => write!(f, "json"),
             OutputFormat::Markdown => write!(f, "markdown"),
             OutputFormat::Sarif => write!(f, "sarif"),
+            OutputFormat::Csv => write!(f, "csv"),
+            OutputFormat::Junit => write!(f, "junit"),
         }
     }
 }
@@ -376,6 +455,8 @@ impl FromStr for OutputFormat {
             "json" => Ok(OutputFormat::Json),
             "markdown" | "md" => Ok(OutputFormat::Markdown),
             "sarif" => Ok(OutputFormat::Sarif),
+            "csv" => Ok(OutputFormat::Csv),
+            "junit" => Ok(OutputFormat::Junit),
             other => Err(format!("unknown output format: {other}")),
         }
     }
@@ -406,6 +487,11 @@ mod tests {
             "SARIF".parse::<OutputFormat>().unwrap(),
             OutputFormat::Sarif
         );
+        assert_eq!("csv".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
+        assert_eq!(
+            "junit".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Junit
+        );
         assert!("xml".parse::<OutputFormat>().is_err());
     }
 
@@ -415,6 +501,7 @@ mod tests {
         assert_eq!(OutputFormat::Json.to_string(), "json");
         assert_eq!(OutputFormat::Markdown.to_string(), "markdown");
         assert_eq!(OutputFormat::Sarif.to_string(), "sarif");
+        assert_eq!(OutputFormat::Csv.to_string(), "csv");
     }
 
     #[test]
@@ -507,8 +594,7 @@ mod tests {
             message: "test".into(),
             confidence: 99.0,
             suggestion: None,
-            patch: None,
-            rule: None,
+            ..Default::default()
         };
         let json = serde_json::to_value(&comment).unwrap();
         assert!(json.get("filePath").is_some());
@@ -0,0 +1,298 @@
+//! Deterministic findings for public API removed or changed by a diff.
+//!
+//! Parses the old and new side of each hunk with tree-sitter and compares
+//! the public symbols on each side. A public symbol that disappears, or
+//! whose signature changes, is reported as a potential breaking change
+//! without involving the LLM.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use argus_core::{ReviewComment, Severity};
+use argus_repomap::parser::{extract_symbols, Symbol, SymbolKind};
+use argus_repomap::walker::{Language, SourceFile};
+
+use crate::parser::FileDiff;
+
+/// Rule name set on every breaking-change finding.
+///
+/// `--fail-on`, PR labels, and rule filters can key off
+/// [`ReviewComment::rule`] having this value.
+pub const BREAKING_CHANGE_RULE: &str = "breaking-change";
+
+/// Report public symbols that a diff removes or whose signature it changes.
+///
+/// Each hunk's old side (context and removed lines) and new side (context
+/// and added lines) are parsed separately, so only symbols whose
+/// declaration appears in a hunk are compared. A hunk usually ends inside
+/// the body of the item it changes, so blocks it leaves open are closed
+/// before parsing. Visibility follows each
+/// language's convention: `pub` in Rust, a capitalized name in Go, no
+/// leading underscore in Python, and a `public` modifier in Java, Kotlin,
+/// PHP, and Swift. Other languages are skipped.
+///
+/// Findings are warnings tagged with [`BREAKING_CHANGE_RULE`]. A removal
+/// points at the first new-file line of the hunk it was removed from; a
+/// signature change points at the new declaration.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::breaking::{find_breaking_changes, BREAKING_CHANGE_RULE};
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/lib.rs b/lib.rs\n\
+///             --- a/lib.rs\n\
+///             +++ b/lib.rs\n\
+///             @@ -1,2 +1,1 @@\n\
+///             -pub fn foo() {}\n \
+///             fn bar() {}\n";
+/// let files = parse_unified_diff(diff).unwrap();
+/// let comments = find_breaking_changes(&files);
+/// assert_eq!(comments.len(), 1);
+/// assert!(comments[0].message.contains("public API `foo` removed"));
+/// assert_eq!(comments[0].rule.as_deref(), Some(BREAKING_CHANGE_RULE));
+/// ```
+pub fn find_breaking_changes(diffs: &[FileDiff]) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+
+    for diff in diffs.iter().filter(|d| !d.is_new_file) {
+        let language = language_of(&diff.old_path);
+        if !supports_visibility(language) {
+            continue;
+        }
+
+        // Old side: name -> (signatures, new-file line of the hunk).
+        // New side: name -> (signature, new-file line of the declaration).
+        let mut before: HashMap<String, (Vec<String>, u32)> = HashMap::new();
+        let mut after: HashMap<String, Vec<(String, u32)>> = HashMap::new();
+
+        for hunk in &diff.hunks {
+            let mut old_side = String::new();
+            let mut new_side = String::new();
+            for line in hunk.content.lines() {
+                if let Some(removed) = line.strip_prefix('-') {
+                    old_side.push_str(removed);
+                    old_side.push('\n');
+                } else if let Some(added) = line.strip_prefix('+') {
+                    new_side.push_str(added);
+                    new_side.push('\n');
+                } else {
+                    let context = line.strip_prefix(' ').unwrap_or(line);
+                    old_side.push_str(context);
+                    old_side.push('\n');
+                    new_side.push_str(context);
+                    new_side.push('\n');
+                }
+            }
+
+            for symbol in public_symbols(&diff.old_path, language, old_side) {
+                let entry = before
+                    .entry(symbol.name)
+                    .or_insert_with(|| (Vec::new(), hunk.new_start));
+                entry.0.push(symbol.signature);
+            }
+            if !diff.is_deleted_file {
+                for symbol in public_symbols(&diff.new_path, language, new_side) {
+                    after
+                        .entry(symbol.name)
+                        .or_default()
+                        .push((symbol.signature, hunk.new_start + symbol.line - 1));
+                }
+            }
+        }
+
+        let file_path = if diff.is_deleted_file {
+            &diff.old_path
+        } else {
+            &diff.new_path
+        };
+
+        let mut names: Vec<&String> = before.keys().collect();
+        names.sort();
+        for name in names {
+            let (old_signatures, removed_at) = &before[name];
+            let (line, message) = match after.get(name) {
+                None => (
+                    *removed_at,
+                    format!("public API `{name}` removed — potential breaking change"),
+                ),
+                Some(new) if new.iter().all(|(sig, _)| !old_signatures.contains(sig)) => (
+                    new[0].1,
+                    format!(
+                        "public API `{name}` changed — potential breaking change (was `{}`)",
+                        old_signatures[0]
+                    ),
+                ),
+                Some(_) => continue,
+            };
+
+            comments.push(ReviewComment {
+                file_path: file_path.clone(),
+                line: line.max(1),
+                severity: Severity::Warning,
+                message,
+                confidence: 100.0,
+                suggestion: None,
+                rule: Some(BREAKING_CHANGE_RULE.to_string()),
+                ..Default::default()
+            });
+        }
+    }
+
+    comments
+}
+
+fn language_of(path: &Path) -> Language {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(Language::from_extension)
+        .unwrap_or(Language::Unknown)
+}
+
+fn supports_visibility(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Rust
+            | Language::Go
+            | Language::Python
+            | Language::Java
+            | Language::Kotlin
+            | Language::Php
+            | Language::Swift
+    )
+}
+
+/// Parse `content` and keep the symbols visible outside their module.
+fn public_symbols(path: &Path, language: Language, mut content: String) -> Vec<Symbol> {
+    if language != Language::Python {
+        close_open_blocks(&mut content);
+    }
+    let file = SourceFile {
+        path: path.to_path_buf(),
+        language,
+        content,
+    };
+    extract_symbols(&file)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|symbol| symbol.kind != SymbolKind::Impl && is_public(symbol, language))
+        .collect()
+}
+
+/// Append a `}` for every `{` in `content` that is never closed, so an
+/// item cut off mid-body by the end of a hunk still parses as that item.
+/// Braces a hunk closes without opening them are ignored.
+fn close_open_blocks(content: &mut String) {
+    let mut depth = 0usize;
+    for c in content.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    for _ in 0..depth {
+        content.push_str("}\n");
+    }
+}
+
+fn is_public(symbol: &Symbol, language: Language) -> bool {
+    match language {
+        Language::Rust => symbol.signature.starts_with("pub "),
+        Language::Go => symbol.name.starts_with(|c: char| c.is_uppercase()),
+        Language::Python => !symbol.name.starts_with('_'),
+        Language::Java | Language::Kotlin | Language::Php | Language::Swift => symbol
+            .signature
+            .split_whitespace()
+            .any(|word| word == "public"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_unified_diff;
+
+    #[test]
+    fn reports_changed_signature_at_new_declaration() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,3 @@
+ // helpers
+-pub fn parse(input: &str) -> u32 {
++pub fn parse(input: &str, strict: bool) -> u32 {
+     0
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let comments = find_breaking_changes(&files);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 11);
+        assert_eq!(comments[0].severity, Severity::Warning);
+        assert!(
+            comments[0].message.contains("public API `parse` changed"),
+            "{}",
+            comments[0].message
+        );
+    }
+
+    #[test]
+    fn reports_changed_signature_in_hunk_cut_off_mid_body() {
+        let diff = "\
+diff --git a/api/Client.java b/api/Client.java
+--- a/api/Client.java
++++ b/api/Client.java
+@@ -4,5 +4,5 @@ public class Client {
+     }
+ 
+-    public User fetch(String id) {
++    public User fetch(String id, boolean cached) {
+         if (id.isEmpty()) {
+             return null;
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let comments = find_breaking_changes(&files);
+        assert_eq!(comments.len(), 1, "{comments:?}");
+        assert_eq!(comments[0].line, 6);
+        assert!(comments[0].message.contains("public API `fetch` changed"));
+    }
+
+    #[test]
+    fn ignores_private_items_and_body_only_changes() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,3 @@
+-fn private_helper() {}
+ pub fn stable() -> u32 {
+-    1
++    2
+ }
+";
+        let files = parse_unified_diff(diff).unwrap();
+        assert!(find_breaking_changes(&files).is_empty());
+    }
+
+    #[test]
+    fn deleted_file_reports_each_public_symbol() {
+        let diff = "\
+diff --git a/api.go b/api.go
+deleted file mode 100644
+--- a/api.go
++++ /dev/null
+@@ -1,3 +0,0 @@
+-package api
+-func Exported() {}
+-func internal() {}
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let comments = find_breaking_changes(&files);
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0].message.contains("`Exported` removed"));
+        assert_eq!(comments[0].file_path, Path::new("api.go"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Deterministic findings for unresolved merge conflict markers.
+//!
+//! Committed `<<<<<<<`, `=======`, and `>>>>>>>` lines are always a bug, so
+//! they are reported without involving the LLM, and in every file of the
+//! diff, including ones the pre-LLM filter skips.
+
+use argus_core::{ReviewComment, Severity};
+
+use crate::parser::FileDiff;
+
+/// Rule name set on every conflict-marker finding.
+pub const CONFLICT_MARKER_RULE: &str = "merge-conflict";
+
+/// Markers that open, close, or (in diff3 style) split a conflict.
+const FENCE_MARKERS: [&str; 3] = ["<<<<<<<", ">>>>>>>", "|||||||"];
+
+/// Separator between the two sides of a conflict.
+const SEPARATOR: &str = "=======";
+
+/// Report every added line that is a merge conflict marker.
+///
+/// A marker is seven `<`, `>`, or `|` at the start of a line, followed by a
+/// space or the end of the line. A bare `=======` line is only reported
+/// when the same file also adds one of those, so a Markdown or reST heading
+/// underline is not mistaken for a conflict. Findings are bugs with full
+/// confidence, tagged with [`CONFLICT_MARKER_RULE`].
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::Severity;
+/// use argus_difflens::conflicts::find_conflict_markers;
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/lib.rs b/lib.rs\n\
+///             --- a/lib.rs\n\
+///             +++ b/lib.rs\n\
+///             @@ -1,1 +1,2 @@\n \
+///             fn a() {}\n\
+///             +>>>>>>> feature\n";
+/// let files = parse_unified_diff(diff).unwrap();
+/// let comments = find_conflict_markers(&files);
+/// assert_eq!(comments.len(), 1);
+/// assert_eq!(comments[0].line, 2);
+/// assert_eq!(comments[0].severity, Severity::Bug);
+/// ```
+pub fn find_conflict_markers(diffs: &[FileDiff]) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+
+    for diff in diffs.iter().filter(|d| !d.is_deleted_file) {
+        let mut fences = Vec::new();
+        let mut separators = Vec::new();
+        for hunk in &diff.hunks {
+            let mut line_no = hunk.new_start;
+            for line in hunk.content.lines() {
+                if line.starts_with('-') {
+                    continue;
+                }
+                if let Some(added) = line.strip_prefix('+') {
+                    let added = added.trim_end();
+                    if let Some(marker) = FENCE_MARKERS.iter().find(|m| is_marker(added, m)) {
+                        fences.push((line_no, *marker));
+                    } else if added == SEPARATOR {
+                        separators.push((line_no, SEPARATOR));
+                    }
+                }
+                line_no += 1;
+            }
+        }
+        if fences.is_empty() {
+            continue;
+        }
+
+        let mut found = fences;
+        found.extend(separators);
+        found.sort_unstable();
+        comments.extend(found.into_iter().map(|(line, marker)| ReviewComment {
+            file_path: diff.new_path.clone(),
+            line,
+            severity: Severity::Bug,
+            message: format!("Unresolved merge conflict marker `{marker}`"),
+            confidence: 100.0,
+            suggestion: Some(
+                "Resolve the conflict and remove the marker lines before merging.".into(),
+            ),
+            rule: Some(CONFLICT_MARKER_RULE.to_string()),
+            ..Default::default()
+        }));
+    }
+    comments
+}
+
+/// Whether `line` starts with `marker` followed by a space or nothing.
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_unified_diff;
+
+    #[test]
+    fn committed_conflict_is_a_bug_per_marker() {
+        let diff = "\
+diff --git a/src/config.rs b/src/config.rs
+--- a/src/config.rs
++++ b/src/config.rs
+@@ -3,2 +3,7 @@
+ fn timeout() -> u64 {
++<<<<<<< HEAD
++    30
++=======
++    60
++>>>>>>> feature/longer-timeout
+ }
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1,1 +1,3 @@
+ intro
++Usage
++=======
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let comments = find_conflict_markers(&files);
+
+        let found: Vec<(u32, &str)> = comments
+            .iter()
+            .map(|c| (c.line, c.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (4, "Unresolved merge conflict marker `<<<<<<<`"),
+                (6, "Unresolved merge conflict marker `=======`"),
+                (8, "Unresolved merge conflict marker `>>>>>>>`"),
+            ]
+        );
+        assert!(comments.iter().all(|c| c.severity == Severity::Bug
+            && c.file_path.ends_with("config.rs")
+            && c.rule.as_deref() == Some(CONFLICT_MARKER_RULE)));
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! Filters out lock files, generated code, vendored dependencies,
 //! minified files, and files matching custom patterns before they
-//! reach the LLM, saving tokens and reducing false positives.
+//! reach the LLM, saving tokens and reducing false positives. Content
+//! heuristics catch generated and minified files that no configured
+//! pattern names.
 
 use std::path::{Path, PathBuf};
 
 use argus_core::ReviewConfig;
+use serde::Serialize;
 
 use crate::parser::FileDiff;
 
@@ -25,6 +28,7 @@ pub struct DiffFilter {
     skip_patterns: Vec<glob::Pattern>,
     skip_extensions: Vec<String>,
     max_file_size_lines: usize,
+    min_hunk_lines: usize,
 }
 
 impl DiffFilter {
@@ -43,6 +47,7 @@ impl DiffFilter {
             skip_patterns: Vec::new(),
             skip_extensions: Vec::new(),
             max_file_size_lines: 1000,
+            min_hunk_lines: 0,
         }
     }
 
@@ -70,6 +75,7 @@ impl DiffFilter {
             skip_patterns,
             skip_extensions: config.skip_extensions.clone(),
             max_file_size_lines: 1000,
+            min_hunk_lines: config.noise.min_hunk_lines,
         }
     }
 
@@ -85,11 +91,15 @@ impl DiffFilter {
     /// assert!(!filter.should_skip("src/lib.rs"));
     /// ```
     pub fn should_skip(&self, path: &str) -> bool {
-        self.check_skip(Path::new(path), "", 0).is_some()
+        self.check_skip(Path::new(path), "", "", 0).is_some()
     }
 
     /// Filter a list of `FileDiff`s, returning only reviewable ones.
     ///
+    /// When `[review.noise] min_hunk_lines` is set, hunks with fewer changed
+    /// lines are dropped from kept files and recorded in `skipped_hunks`.
+    /// Files whose hunks are all trivial are skipped entirely.
+    ///
     /// # Examples
     ///
     /// ```
@@ -111,15 +121,17 @@ impl DiffFilter {
     pub fn filter(&self, diffs: Vec<FileDiff>) -> FilterResult {
         let mut kept = Vec::new();
         let mut skipped = Vec::new();
+        let mut skipped_hunks = Vec::new();
 
-        for diff in diffs {
-            let path = &diff.new_path;
-            let path_str = path.to_string_lossy();
+        for mut diff in diffs {
+            let path = diff.new_path.clone();
+            let path_str = path.to_string_lossy().into_owned();
 
             let content = Self::collect_hunk_content(&diff);
+            let header = Self::collect_file_header(&diff);
             let changed_lines = Self::count_changed_lines(&diff);
 
-            if let Some(reason) = self.check_skip(path, &content, changed_lines) {
+            if let Some(reason) = self.check_skip(&path, &content, &header, changed_lines) {
                 skipped.push(SkippedFile {
                     path: path.clone(),
                     reason,
@@ -138,21 +150,74 @@ impl DiffFilter {
                     }
                 }
                 if !matched {
-                    kept.push(diff);
+                    if self.drop_trivial_hunks(&mut diff, &mut skipped_hunks) {
+                        skipped.push(SkippedFile {
+                            path: path.clone(),
+                            reason: SkipReason::TrivialHunk,
+                        });
+                    } else {
+                        kept.push(diff);
+                    }
                 }
             }
         }
 
-        FilterResult { kept, skipped }
+        FilterResult {
+            kept,
+            skipped,
+            skipped_hunks,
+        }
     }
 
-    fn check_skip(&self, path: &Path, content: &str, changed_lines: usize) -> Option<SkipReason> {
+    /// Remove hunks below `min_hunk_lines`, returning `true` when every hunk was trivial.
+    fn drop_trivial_hunks(
+        &self,
+        diff: &mut FileDiff,
+        skipped_hunks: &mut Vec<SkippedHunk>,
+    ) -> bool {
+        if self.min_hunk_lines == 0 || diff.hunks.is_empty() {
+            return false;
+        }
+
+        let (trivial, substantive): (Vec<_>, Vec<_>) = std::mem::take(&mut diff.hunks)
+            .into_iter()
+            .partition(|h| count_hunk_changed_lines(&h.content) < self.min_hunk_lines);
+
+        if substantive.is_empty() {
+            diff.hunks = trivial;
+            return true;
+        }
+
+        for hunk in trivial {
+            skipped_hunks.push(SkippedHunk {
+                path: diff.new_path.clone(),
+                new_start: hunk.new_start,
+                reason: SkipReason::TrivialHunk,
+            });
+        }
+        diff.hunks = substantive;
+        false
+    }
+
+    fn check_skip(
+        &self,
+        path: &Path,
+        content: &str,
+        header: &str,
+        changed_lines: usize,
+    ) -> Option<SkipReason> {
         let path_str = path.to_string_lossy();
         let file_name = path
             .file_name()
             .map(|f| f.to_string_lossy().to_string())
             .unwrap_or_default();
 
+        // Non-UTF-8 content: diffs are decoded lossily, so invalid bytes
+        // show up as U+FFFD replacement characters
+        if content.contains(char::REPLACEMENT_CHARACTER) {
+            return Some(SkipReason::NonUtf8);
+        }
+
         // Lock files
         if is_lock_file(&file_name) {
             return Some(SkipReason::LockFile);
@@ -178,6 +243,11 @@ impl DiffFilter {
             return Some(SkipReason::GeneratedFile);
         }
 
+        // Generated or minified content that no name or pattern caught
+        if let Some(detail) = detect_by_heuristics(header, content) {
+            return Some(SkipReason::Heuristic(detail));
+        }
+
         // Custom extension skip
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
             for skip_ext in &self.skip_extensions {
@@ -203,19 +273,42 @@ impl DiffFilter {
         content
     }
 
-    fn count_changed_lines(diff: &FileDiff) -> usize {
-        let mut count = 0;
+    /// The lines of the new file within the first [`GENERATED_MARKER_LINES`]
+    /// that the diff shows, without removed lines.
+    fn collect_file_header(diff: &FileDiff) -> String {
+        let mut header = String::new();
         for hunk in &diff.hunks {
+            let mut line_no = hunk.new_start as usize;
             for line in hunk.content.lines() {
-                if line.starts_with('+') || line.starts_with('-') {
-                    count += 1;
+                if line_no > GENERATED_MARKER_LINES {
+                    break;
+                }
+                if line.starts_with('-') || line.starts_with('\\') {
+                    continue;
                 }
+                header.push_str(line.get(1..).unwrap_or_default());
+                header.push('\n');
+                line_no += 1;
             }
         }
-        count
+        header
+    }
+
+    fn count_changed_lines(diff: &FileDiff) -> usize {
+        diff.hunks
+            .iter()
+            .map(|h| count_hunk_changed_lines(&h.content))
+            .sum()
     }
 }
 
+fn count_hunk_changed_lines(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|line| line.starts_with('+') || line.starts_with('-'))
+        .count()
+}
+
 /// Result of filtering diffs.
 ///
 /// # Examples
@@ -226,6 +319,7 @@ impl DiffFilter {
 /// let result = FilterResult {
 ///     kept: vec![],
 ///     skipped: vec![],
+///     skipped_hunks: vec![],
 /// };
 /// assert!(result.kept.is_empty());
 /// ```
@@ -234,6 +328,8 @@ pub struct FilterResult {
     pub kept: Vec<FileDiff>,
     /// Files that were skipped with reasons.
     pub skipped: Vec<SkippedFile>,
+    /// Individual hunks dropped from otherwise kept files.
+    pub skipped_hunks: Vec<SkippedHunk>,
 }
 
 /// A file that was skipped during filtering.
@@ -248,9 +344,9 @@ pub struct FilterResult {
 ///     path: PathBuf::from("package-lock.json"),
 ///     reason: SkipReason::LockFile,
 /// };
-/// assert!(matches!(skipped.reason, SkipReason::LockFile));
+/// assert_eq!(skipped.reason, SkipReason::LockFile);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SkippedFile {
     /// Path of the skipped file.
     pub path: PathBuf,
@@ -258,8 +354,37 @@ pub struct SkippedFile {
     pub reason: SkipReason,
 }
 
+/// A single hunk that was dropped from a kept file during filtering.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_difflens::filter::{SkippedHunk, SkipReason};
+///
+/// let skipped = SkippedHunk {
+///     path: PathBuf::from("Cargo.toml"),
+///     new_start: 3,
+///     reason: SkipReason::TrivialHunk,
+/// };
+/// assert_eq!(skipped.reason.to_string(), "trivial hunk");
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedHunk {
+    /// Path of the file containing the hunk.
+    pub path: PathBuf,
+    /// Starting line of the hunk in the new version.
+    pub new_start: u32,
+    /// Why the hunk was skipped.
+    pub reason: SkipReason,
+}
+
 /// Reason a file was skipped.
 ///
+/// Serializes to a stable `kind`, plus a `detail` for the variants that
+/// carry one, for tooling; `Display` is for humans.
+///
 /// # Examples
 ///
 /// ```
@@ -267,8 +392,16 @@ pub struct SkippedFile {
 ///
 /// let reason = SkipReason::LockFile;
 /// assert_eq!(format!("{reason}"), "lock file");
+/// assert_eq!(serde_json::to_string(&reason).unwrap(), r#"{"kind":"lock_file"}"#);
+///
+/// let pattern = SkipReason::PatternMatch("vendor/**".into());
+/// assert_eq!(
+///     serde_json::to_string(&pattern).unwrap(),
+///     r#"{"kind":"pattern_match","detail":"vendor/**"}"#
+/// );
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
 pub enum SkipReason {
     /// Package manager lock file.
     LockFile,
@@ -280,10 +413,16 @@ pub enum SkipReason {
     MinifiedFile,
     /// Binary file.
     BinaryFile,
+    /// Changed content is not valid UTF-8 (e.g. a Latin-1 source file).
+    NonUtf8,
     /// File exceeds max changed lines threshold.
     TooLarge,
     /// Matched a custom skip pattern.
     PatternMatch(String),
+    /// Every hunk changed fewer lines than `min_hunk_lines`.
+    TrivialHunk,
+    /// Content looks generated or minified; holds what gave it away.
+    Heuristic(String),
 }
 
 impl std::fmt::Display for SkipReason {
@@ -294,8 +433,11 @@ impl std::fmt::Display for SkipReason {
             SkipReason::VendoredCode => write!(f, "vendored code"),
             SkipReason::MinifiedFile => write!(f, "minified file"),
             SkipReason::BinaryFile => write!(f, "binary file"),
+            SkipReason::NonUtf8 => write!(f, "not valid UTF-8"),
             SkipReason::TooLarge => write!(f, "too large"),
             SkipReason::PatternMatch(pat) => write!(f, "pattern: {pat}"),
+            SkipReason::TrivialHunk => write!(f, "trivial hunk"),
+            SkipReason::Heuristic(detail) => write!(f, "heuristic: {detail}"),
         }
     }
 }
@@ -375,6 +517,53 @@ fn is_generated_by_content(content: &str) -> bool {
     false
 }
 
+/// Header comments that mark a file as generated, matched case-insensitively.
+const GENERATED_MARKERS: &[&str] = &[
+    "do not edit",
+    "@generated",
+    "auto-generated",
+    "autogenerated",
+    "automatically generated",
+];
+
+/// Lines of the diff searched for a generated-code marker.
+const GENERATED_MARKER_LINES: usize = 5;
+
+/// Shortest added line that can count as minified on its own.
+const MINIFIED_MIN_LINE_LEN: usize = 200;
+
+/// Share of the added characters that one line must hold to count as minified.
+const MINIFIED_MIN_SHARE: f64 = 0.8;
+
+/// Detect generated or minified content that name and pattern checks missed.
+///
+/// Returns a short description of the evidence: a generated-code marker in
+/// `header`, the start of the new file, or added content that is mostly one
+/// very long line (minified bundles, single-line JSON).
+fn detect_by_heuristics(header: &str, content: &str) -> Option<String> {
+    for line in header.lines() {
+        let text = line.to_lowercase();
+        if let Some(marker) = GENERATED_MARKERS.iter().find(|m| text.contains(**m)) {
+            return Some(format!("generated-code marker \"{marker}\" in header"));
+        }
+    }
+
+    let added: Vec<&str> = content
+        .lines()
+        .filter_map(|line| line.strip_prefix('+'))
+        .collect();
+    let total: usize = added.iter().map(|line| line.len()).sum();
+    let longest = added.iter().map(|line| line.len()).max().unwrap_or(0);
+    if longest >= MINIFIED_MIN_LINE_LEN && longest as f64 >= total as f64 * MINIFIED_MIN_SHARE {
+        return Some(format!(
+            "minified: one {longest}-char line is {:.0}% of added content",
+            longest as f64 / total as f64 * 100.0
+        ));
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,7 +588,7 @@ mod tests {
             let result = filter.filter(diffs);
             assert!(result.kept.is_empty(), "expected {name} to be skipped");
             assert_eq!(result.skipped.len(), 1);
-            assert!(matches!(result.skipped[0].reason, SkipReason::LockFile));
+            assert_eq!(result.skipped[0].reason, SkipReason::LockFile);
         }
     }
 
@@ -416,10 +605,7 @@ mod tests {
             let diffs = make_diff(name, "+new line");
             let result = filter.filter(diffs);
             assert!(result.kept.is_empty(), "expected {name} to be skipped");
-            assert!(matches!(
-                result.skipped[0].reason,
-                SkipReason::GeneratedFile
-            ));
+            assert_eq!(result.skipped[0].reason, SkipReason::GeneratedFile);
         }
     }
 
@@ -429,10 +615,17 @@ mod tests {
         let diffs = make_diff("gen.go", "+// Code generated by protoc. DO NOT EDIT.");
         let result = filter.filter(diffs);
         assert!(result.kept.is_empty());
-        assert!(matches!(
-            result.skipped[0].reason,
-            SkipReason::GeneratedFile
-        ));
+        assert_eq!(result.skipped[0].reason, SkipReason::GeneratedFile);
+    }
+
+    #[test]
+    fn non_utf8_files_skipped() {
+        let raw = b"+let s = \"caf\xe9\";";
+        let content = String::from_utf8_lossy(raw);
+        let result = DiffFilter::default_filter().filter(make_diff("latin1.rs", &content));
+        assert!(result.kept.is_empty());
+        assert_eq!(result.skipped[0].reason, SkipReason::NonUtf8);
+        assert_eq!(result.skipped[0].reason.to_string(), "not valid UTF-8");
     }
 
     #[test]
@@ -443,14 +636,72 @@ mod tests {
         let diffs = make_diff("app.min.js", "+var x=1;");
         let result = filter.filter(diffs);
         assert!(result.kept.is_empty());
-        assert!(matches!(result.skipped[0].reason, SkipReason::MinifiedFile));
+        assert_eq!(result.skipped[0].reason, SkipReason::MinifiedFile);
 
         // By long line heuristic
         let long_line = format!("+{}", "x".repeat(501));
         let diffs = make_diff("bundle.js", &long_line);
         let result = filter.filter(diffs);
         assert!(result.kept.is_empty());
-        assert!(matches!(result.skipped[0].reason, SkipReason::MinifiedFile));
+        assert_eq!(result.skipped[0].reason, SkipReason::MinifiedFile);
+    }
+
+    #[test]
+    fn minified_js_hunk_skipped_by_heuristic() {
+        let bundle = format!(
+            "+/*! app v1 */\n+!function(e){{{}}}(window);",
+            "var a=e.b;".repeat(30)
+        );
+        let result = DiffFilter::default_filter().filter(make_diff("dist/app.js", &bundle));
+        assert!(result.kept.is_empty());
+        let reason = result.skipped[0].reason.to_string();
+        assert!(
+            matches!(result.skipped[0].reason, SkipReason::Heuristic(_)),
+            "{reason}"
+        );
+        assert!(reason.starts_with("heuristic: minified"), "{reason}");
+
+        // Long lines among ordinary code are not enough
+        let mixed = format!(
+            "+const TABLE = \"{}\";\n{}",
+            "x".repeat(250),
+            "+let y = compute(x);\n".repeat(20)
+        );
+        let result = DiffFilter::default_filter().filter(make_diff("src/table.js", &mixed));
+        assert_eq!(result.kept.len(), 1);
+    }
+
+    #[test]
+    fn generated_marker_in_header_skipped_by_heuristic() {
+        let diffs = make_diff(
+            "src/schema.ts",
+            "+// @generated by schema-gen\n+export type A = 1;",
+        );
+        let result = DiffFilter::default_filter().filter(diffs);
+        assert!(result.kept.is_empty());
+        assert_eq!(
+            result.skipped[0].reason.to_string(),
+            "heuristic: generated-code marker \"@generated\" in header"
+        );
+    }
+
+    #[test]
+    fn generated_marker_outside_header_is_ignored() {
+        // A marker in a hunk far into the file is ordinary code
+        let diff = "diff --git a/src/codegen.rs b/src/codegen.rs\n\
+                    --- a/src/codegen.rs\n\
+                    +++ b/src/codegen.rs\n\
+                    @@ -120,2 +120,3 @@\n\
+                    \x20fn emit() {\n\
+                    +    out.push_str(\"// @generated\");\n\
+                    \x20}\n";
+        let result = DiffFilter::default_filter().filter(parse_unified_diff(diff).unwrap());
+        assert_eq!(result.kept.len(), 1);
+
+        // Removing a header marker doesn't make the new file generated
+        let diffs = make_diff("src/schema.ts", "-// DO NOT EDIT\n+export type A = 1;");
+        let result = DiffFilter::default_filter().filter(diffs);
+        assert_eq!(result.kept.len(), 1);
     }
 
     #[test]
@@ -465,7 +716,7 @@ mod tests {
             let diffs = make_diff(path, "+line");
             let result = filter.filter(diffs);
             assert!(result.kept.is_empty(), "expected {path} to be skipped");
-            assert!(matches!(result.skipped[0].reason, SkipReason::VendoredCode));
+            assert_eq!(result.skipped[0].reason, SkipReason::VendoredCode);
         }
     }
 
@@ -489,10 +740,10 @@ mod tests {
         let diffs = make_diff("auth.test.ts", "+test line");
         let result = filter.filter(diffs);
         assert!(result.kept.is_empty());
-        assert!(matches!(
+        assert_eq!(
             result.skipped[0].reason,
-            SkipReason::PatternMatch(_)
-        ));
+            SkipReason::PatternMatch("*.test.ts".into())
+        );
 
         // Normal file still kept
         let diffs = make_diff("src/auth.ts", "+real code");
@@ -539,6 +790,64 @@ mod tests {
         let diffs = parse_unified_diff(&diff).unwrap();
         let result = filter.filter(diffs);
         assert!(result.kept.is_empty());
-        assert!(matches!(result.skipped[0].reason, SkipReason::TooLarge));
+        assert_eq!(result.skipped[0].reason, SkipReason::TooLarge);
+    }
+
+    #[test]
+    fn trivial_hunks_skipped_at_min_hunk_lines() {
+        let mut config = ReviewConfig::default();
+        config.noise.min_hunk_lines = 3;
+        let filter = DiffFilter::from_config(&config);
+
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+ fn a() {}
+-const VERSION: &str = \"1.0\";
++const VERSION: &str = \"1.1\";
+@@ -10,1 +10,4 @@
+ fn b() {
++    one();
++    two();
++    three();
+diff --git a/Cargo.toml b/Cargo.toml
+--- a/Cargo.toml
++++ b/Cargo.toml
+@@ -3,1 +3,1 @@
+-version = \"0.1.0\"
++version = \"0.1.1\"
+";
+        let result = filter.filter(parse_unified_diff(diff).unwrap());
+
+        // The 3-line hunk survives; the 2-line hunk in the same file is dropped
+        assert_eq!(result.kept.len(), 1);
+        assert_eq!(result.kept[0].hunks.len(), 1);
+        assert_eq!(result.kept[0].hunks[0].new_start, 10);
+        assert_eq!(result.skipped_hunks.len(), 1);
+        assert_eq!(result.skipped_hunks[0].new_start, 1);
+        assert_eq!(result.skipped_hunks[0].reason, SkipReason::TrivialHunk);
+
+        // A file with only trivial hunks is skipped entirely
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].path, PathBuf::from("Cargo.toml"));
+        assert_eq!(result.skipped[0].reason.to_string(), "trivial hunk");
+    }
+
+    #[test]
+    fn one_line_hunk_skipped_at_min_hunk_lines_three() {
+        let mut config = ReviewConfig::default();
+        config.noise.min_hunk_lines = 3;
+        let filter = DiffFilter::from_config(&config);
+
+        let result = filter.filter(make_diff("src/main.rs", "+let x = 1;"));
+        assert!(result.kept.is_empty());
+        assert_eq!(result.skipped[0].reason, SkipReason::TrivialHunk);
+
+        // Disabled by default
+        let result = DiffFilter::default_filter().filter(make_diff("src/main.rs", "+let x = 1;"));
+        assert_eq!(result.kept.len(), 1);
+        assert!(result.skipped_hunks.is_empty());
     }
 }
@@ -1,8 +1,16 @@
 //! Diff parsing, complexity scoring, and risk analysis.
 //!
-//! Provides unified diff parsing, pre-LLM file filtering, complexity
-//! scoring, and risk analysis for code changes.
+//! Provides unified diff parsing, mbox patch series splitting, pre-LLM
+//! file filtering, moved-code detection, complexity scoring, risk analysis,
+//! merge conflict marker, TODO/FIXME marker, public API breaking-change,
+//! and new lock file dependency detection for code changes.
 
+pub mod breaking;
+pub mod conflicts;
 pub mod filter;
+pub mod lockfiles;
+pub mod markers;
+pub mod mbox;
+pub mod moves;
 pub mod parser;
 pub mod risk;
@@ -0,0 +1,363 @@
+//! Deterministic summary of dependencies added to lock files.
+//!
+//! Lock files are skipped by the pre-filter because reviewing them line by
+//! line is noise, but a regenerated lock file can also slip in an
+//! unexpected or typosquatted package. This pass reads the package entries
+//! on both sides of each lock file hunk and reports the newly added ones in
+//! a single comment per file, for a human to eyeball.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use argus_core::{ReviewComment, Severity};
+
+use crate::parser::FileDiff;
+
+/// Rule name set on every new-dependency finding.
+pub const NEW_DEPENDENCY_RULE: &str = "new-dependency";
+
+/// Dependencies listed by name in one comment; the rest are counted.
+const LISTED_DEPENDENCIES: usize = 30;
+
+/// Lock file formats whose package entries can be read from a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockFormat {
+    /// `Cargo.lock`: `name = "..."` followed by `version = "..."`.
+    Cargo,
+    /// `package-lock.json` and `npm-shrinkwrap.json`: a `"<path>": {` key
+    /// followed by `"version": "..."`.
+    Npm,
+}
+
+impl LockFormat {
+    fn of(diff: &FileDiff) -> Option<Self> {
+        match diff.new_path.file_name()?.to_str()? {
+            "Cargo.lock" => Some(Self::Cargo),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Self::Npm),
+            _ => None,
+        }
+    }
+}
+
+/// Package entries a diff adds to and removes from a lock file.
+#[derive(Debug, Default, PartialEq)]
+pub struct LockfileChanges {
+    /// `(name, version)` entries on the new side.
+    pub added: BTreeSet<(String, String)>,
+    /// `(name, version)` entries on the old side.
+    pub removed: BTreeSet<(String, String)>,
+    /// New-file line of the first added entry of each package.
+    added_at: HashMap<String, u32>,
+}
+
+impl LockfileChanges {
+    /// Added entries whose package had no removed entry, so they are new
+    /// dependencies (or an additional version of one) rather than upgrades.
+    pub fn new_dependencies(&self) -> Vec<(String, String)> {
+        let updated: HashSet<&str> = self.removed.iter().map(|(n, _)| n.as_str()).collect();
+        self.added
+            .iter()
+            .filter(|(name, _)| !updated.contains(name.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Read the package entries added and removed by a lock file diff.
+///
+/// Returns `None` for files that are not a supported lock file. Each side
+/// of a hunk tracks the package it is in from context lines and its own
+/// changed lines, so a version bump under an unchanged name counts as a
+/// removed and an added entry of the same package.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::lockfiles::lockfile_changes;
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/Cargo.lock b/Cargo.lock\n\
+///             --- a/Cargo.lock\n\
+///             +++ b/Cargo.lock\n\
+///             @@ -1,2 +1,6 @@\n \
+///             [[package]]\n\
+///             +name = \"serde\"\n\
+///             +version = \"1.0.200\"\n\
+///             +\n\
+///             +[[package]]\n \
+///             name = \"tokio\"\n";
+/// let files = parse_unified_diff(diff).unwrap();
+/// let changes = lockfile_changes(&files[0]).unwrap();
+/// assert_eq!(
+///     changes.new_dependencies(),
+///     [("serde".to_string(), "1.0.200".to_string())]
+/// );
+/// ```
+pub fn lockfile_changes(diff: &FileDiff) -> Option<LockfileChanges> {
+    let format = LockFormat::of(diff)?;
+    let mut changes = LockfileChanges::default();
+
+    for hunk in &diff.hunks {
+        let mut old_name: Option<String> = None;
+        let mut new_name: Option<(String, u32)> = None;
+        let mut line_no = hunk.new_start;
+        for line in hunk.content.lines() {
+            let (text, in_old, in_new) = if let Some(removed) = line.strip_prefix('-') {
+                (removed, true, false)
+            } else if let Some(added) = line.strip_prefix('+') {
+                (added, false, true)
+            } else if line.starts_with('\\') {
+                continue;
+            } else {
+                (line.strip_prefix(' ').unwrap_or(line), true, true)
+            };
+
+            match parse_entry_line(format, text.trim()) {
+                Some(EntryLine::Package(name)) => {
+                    if in_old {
+                        old_name.clone_from(&name);
+                    }
+                    if in_new {
+                        new_name = name.map(|name| (name, line_no));
+                    }
+                }
+                Some(EntryLine::Version(version)) => {
+                    let changed = in_old != in_new;
+                    if changed && in_old {
+                        if let Some(name) = &old_name {
+                            changes.removed.insert((name.clone(), version));
+                        }
+                    } else if changed {
+                        if let Some((name, at)) = &new_name {
+                            changes.added.insert((name.clone(), version));
+                            changes.added_at.entry(name.clone()).or_insert(*at);
+                        }
+                    }
+                }
+                None => {}
+            }
+            if in_new {
+                line_no += 1;
+            }
+        }
+    }
+    Some(changes)
+}
+
+enum EntryLine {
+    /// Start of a package entry, or `None` for a section that is not one.
+    Package(Option<String>),
+    Version(String),
+}
+
+fn parse_entry_line(format: LockFormat, line: &str) -> Option<EntryLine> {
+    match format {
+        LockFormat::Cargo => {
+            if line == "[[package]]" {
+                Some(EntryLine::Package(None))
+            } else if let Some(name) = quoted_value(line, "name = \"") {
+                Some(EntryLine::Package(Some(name)))
+            } else {
+                quoted_value(line, "version = \"").map(EntryLine::Version)
+            }
+        }
+        LockFormat::Npm => {
+            if let Some(key) = line
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix("\": {"))
+            {
+                // `node_modules/a/node_modules/@scope/b` is package `@scope/b`;
+                // in lockfile v1, packages are keys under `dependencies`
+                let name = key.rsplit("node_modules/").next().unwrap_or(key);
+                let is_section = !key.contains("node_modules/")
+                    && matches!(
+                        key,
+                        "" | "packages"
+                            | "dependencies"
+                            | "devDependencies"
+                            | "optionalDependencies"
+                            | "peerDependencies"
+                            | "peerDependenciesMeta"
+                            | "requires"
+                            | "engines"
+                            | "bin"
+                            | "funding"
+                    );
+                Some(EntryLine::Package(
+                    (!is_section && !name.is_empty()).then(|| name.to_string()),
+                ))
+            } else {
+                quoted_value(line, "\"version\": \"").map(EntryLine::Version)
+            }
+        }
+    }
+}
+
+/// The quoted string right after `prefix`, such as `1.0` in `version = "1.0"`.
+fn quoted_value(line: &str, prefix: &str) -> Option<String> {
+    let rest = line.strip_prefix(prefix)?;
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+/// Report the dependencies each lock file diff adds, one comment per file.
+///
+/// Supports `Cargo.lock`, `package-lock.json`, and `npm-shrinkwrap.json`.
+/// Upgrades of packages already in the lock file are not listed. Comments
+/// have `severity`, full confidence, and the rule [`NEW_DEPENDENCY_RULE`],
+/// and point at the first new package entry.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::Severity;
+/// use argus_difflens::lockfiles::{find_new_dependencies, NEW_DEPENDENCY_RULE};
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/package-lock.json b/package-lock.json\n\
+///             --- a/package-lock.json\n\
+///             +++ b/package-lock.json\n\
+///             @@ -10,1 +10,4 @@\n \
+///             \"packages\": {\n\
+///             +    \"node_modules/lodahs\": {\n\
+///             +      \"version\": \"4.17.21\",\n\
+///             +    },\n";
+/// let files = parse_unified_diff(diff).unwrap();
+/// let comments = find_new_dependencies(&files, Severity::Info);
+/// assert_eq!(comments.len(), 1);
+/// assert!(comments[0].message.contains("`lodahs 4.17.21`"));
+/// assert_eq!(comments[0].rule.as_deref(), Some(NEW_DEPENDENCY_RULE));
+/// ```
+pub fn find_new_dependencies(diffs: &[FileDiff], severity: Severity) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+    for diff in diffs.iter().filter(|d| !d.is_deleted_file) {
+        let Some(changes) = lockfile_changes(diff) else {
+            continue;
+        };
+        let added = changes.new_dependencies();
+        if added.is_empty() {
+            continue;
+        }
+
+        let mut listed: Vec<String> = added
+            .iter()
+            .take(LISTED_DEPENDENCIES)
+            .map(|(name, version)| format!("`{name} {version}`"))
+            .collect();
+        if added.len() > LISTED_DEPENDENCIES {
+            listed.push(format!("and {} more", added.len() - LISTED_DEPENDENCIES));
+        }
+        let line = added
+            .iter()
+            .filter_map(|(name, _)| changes.added_at.get(name))
+            .min()
+            .copied()
+            .unwrap_or(1);
+        let count = match added.len() {
+            1 => "1 new dependency".to_string(),
+            n => format!("{n} new dependencies"),
+        };
+        comments.push(ReviewComment {
+            file_path: diff.new_path.clone(),
+            line,
+            severity,
+            message: format!("Lock file adds {count}: {}", listed.join(", ")),
+            confidence: 100.0,
+            suggestion: Some(
+                "Check that each package is expected and its name is spelled \
+                 correctly (typosquatting)."
+                    .into(),
+            ),
+            rule: Some(NEW_DEPENDENCY_RULE.into()),
+            ..Default::default()
+        });
+    }
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_unified_diff;
+
+    fn pair(name: &str, version: &str) -> (String, String) {
+        (name.into(), version.into())
+    }
+
+    #[test]
+    fn cargo_lock_lists_new_packages_but_not_upgrades() {
+        let diff = "\
+diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -20,6 +20,11 @@
+ [[package]]
+ name = \"anyhow\"
+-version = \"1.0.80\"
++version = \"1.0.86\"
+ source = \"registry+https://github.com/rust-lang/crates.io-index\"
+ [[package]]
++name = \"serde_jsonn\"
++version = \"0.1.0\"
++source = \"registry+https://github.com/rust-lang/crates.io-index\"
++
++[[package]]
+ name = \"tokio\"
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let changes = lockfile_changes(&files[0]).unwrap();
+        assert_eq!(
+            changes.added,
+            BTreeSet::from([pair("anyhow", "1.0.86"), pair("serde_jsonn", "0.1.0")])
+        );
+        assert_eq!(changes.removed, BTreeSet::from([pair("anyhow", "1.0.80")]));
+        assert_eq!(changes.new_dependencies(), [pair("serde_jsonn", "0.1.0")]);
+
+        let comments = find_new_dependencies(&files, Severity::Warning);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 25);
+        assert_eq!(comments[0].severity, Severity::Warning);
+        assert_eq!(
+            comments[0].message,
+            "Lock file adds 1 new dependency: `serde_jsonn 0.1.0`"
+        );
+    }
+
+    #[test]
+    fn package_lock_reads_nested_and_scoped_packages() {
+        let diff = "\
+diff --git a/package-lock.json b/package-lock.json
+--- a/package-lock.json
++++ b/package-lock.json
+@@ -1,7 +1,14 @@
+ {
+   \"packages\": {
+     \"\": {
+-      \"version\": \"1.0.0\",
++      \"version\": \"1.1.0\",
+     },
++    \"node_modules/@types/node\": {
++      \"version\": \"20.11.0\",
++      \"dev\": true
++    },
++    \"node_modules/a/node_modules/left-pad\": {
++      \"version\": \"1.3.0\",
++    },
+     \"node_modules/react\": {
+-      \"version\": \"18.2.0\",
++      \"version\": \"18.3.1\",
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let changes = lockfile_changes(&files[0]).unwrap();
+        assert_eq!(
+            changes.new_dependencies(),
+            [pair("@types/node", "20.11.0"), pair("left-pad", "1.3.0")]
+        );
+        assert_eq!(changes.removed, BTreeSet::from([pair("react", "18.2.0")]));
+
+        let unrelated = parse_unified_diff(
+            "diff --git a/yarn.lock b/yarn.lock\n--- a/yarn.lock\n+++ b/yarn.lock\n@@ -1 +1,2 @@\n a\n+version \"1.0\"\n",
+        )
+        .unwrap();
+        assert!(lockfile_changes(&unrelated[0]).is_none());
+        assert!(find_new_dependencies(&unrelated, Severity::Info).is_empty());
+    }
+}
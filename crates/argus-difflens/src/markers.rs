@@ -0,0 +1,138 @@
+//! Deterministic findings for marker comments added by a diff.
+//!
+//! Scans added lines for markers such as `TODO` or `FIXME` and reports each
+//! one as a [`ReviewComment`], without involving the LLM.
+
+use argus_core::{ReviewComment, Severity};
+
+use crate::parser::FileDiff;
+
+/// Report every added line containing one of `markers` as a comment.
+///
+/// Markers match case-sensitively as whole words, so `TODO:` and
+/// `// FIXME(alice)` match but `TODOS` and `todo_list` do not. Each line
+/// produces at most one comment, for the first marker on it. Comments have
+/// full confidence and point at the line in the new file.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::Severity;
+/// use argus_difflens::markers::find_marker_comments;
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/lib.rs b/lib.rs\n\
+///             --- a/lib.rs\n\
+///             +++ b/lib.rs\n\
+///             @@ -1,0 +2,1 @@\n\
+///             +// TODO: handle errors\n";
+/// let files = parse_unified_diff(diff).unwrap();
+/// let comments = find_marker_comments(&files, &["TODO".to_string()], Severity::Info);
+/// assert_eq!(comments.len(), 1);
+/// assert_eq!(comments[0].line, 2);
+/// assert!(comments[0].message.contains("handle errors"));
+/// ```
+pub fn find_marker_comments(
+    diffs: &[FileDiff],
+    markers: &[String],
+    severity: Severity,
+) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+    if markers.is_empty() {
+        return comments;
+    }
+
+    for diff in diffs.iter().filter(|d| !d.is_deleted_file) {
+        for hunk in &diff.hunks {
+            let mut line_no = hunk.new_start;
+            for line in hunk.content.lines() {
+                if line.starts_with('-') {
+                    continue;
+                }
+                if let Some(added) = line.strip_prefix('+') {
+                    if let Some(marker) = markers.iter().find(|m| contains_word(added, m)) {
+                        comments.push(ReviewComment {
+                            file_path: diff.new_path.clone(),
+                            line: line_no,
+                            severity,
+                            message: format!("New `{marker}` added: {}", added.trim()),
+                            confidence: 100.0,
+                            suggestion: None,
+                            ..Default::default()
+                        });
+                    }
+                }
+                line_no += 1;
+            }
+        }
+    }
+    comments
+}
+
+/// Whether `word` occurs in `text` with no identifier character on either side.
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_unified_diff;
+
+    fn markers() -> Vec<String> {
+        ["TODO", "FIXME", "XXX", "HACK"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn tracks_new_line_numbers_across_removed_and_context_lines() {
+        let diff = "\
+diff --git a/app.py b/app.py
+--- a/app.py
++++ b/app.py
+@@ -10,4 +10,4 @@
+ def run():
+-    # TODO: old note
++    # FIXME: retries are unbounded
+     pass
++    # HACK around upstream bug
+";
+        let files = parse_unified_diff(diff).unwrap();
+        let comments = find_marker_comments(&files, &markers(), Severity::Suggestion);
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].line, 11);
+        assert_eq!(
+            comments[0].message,
+            "New `FIXME` added: # FIXME: retries are unbounded"
+        );
+        assert_eq!(comments[1].line, 13);
+        assert_eq!(comments[1].severity, Severity::Suggestion);
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        assert!(contains_word("// TODO: x", "TODO"));
+        assert!(contains_word("FIXME(alice)", "FIXME"));
+        assert!(!contains_word("let TODOS = 1;", "TODO"));
+        assert!(!contains_word("MY_TODO", "TODO"));
+        assert!(!contains_word("todo", "TODO"));
+    }
+
+    #[test]
+    fn no_markers_means_no_comments() {
+        let diff = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1 +1,2 @@
+ a
++// TODO
+";
+        let files = parse_unified_diff(diff).unwrap();
+        assert!(find_marker_comments(&files, &[], Severity::Info).is_empty());
+    }
+}
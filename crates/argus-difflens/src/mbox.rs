@@ -0,0 +1,306 @@
+//! Patch series in mbox format, as written by `git format-patch`.
+//!
+//! Mailing-list workflows exchange changes as emails: each message carries
+//! the commit message in its headers and body, then the unified diff. This
+//! module splits such a series into one [`Patch`] per message so each diff
+//! can be reviewed with its commit message as context.
+
+use crate::parser::parse_hunk_header;
+
+/// One patch from a series: the commit message and its diff.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::mbox::Patch;
+///
+/// let patch = Patch {
+///     subject: "Add parser".into(),
+///     author: Some("Alice <alice@example.com>".into()),
+///     body: "Parses the input format.".into(),
+///     diff: String::new(),
+/// };
+/// assert_eq!(patch.commit_message(), "Add parser\n\nParses the input format.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    /// Subject line, without the `[PATCH n/m]` prefix.
+    pub subject: String,
+    /// Value of the `From:` header.
+    pub author: Option<String>,
+    /// Commit message body, without the diffstat.
+    pub body: String,
+    /// Unified diff, without the trailing email signature.
+    pub diff: String,
+}
+
+impl Patch {
+    /// The full commit message: subject, then the body if there is one.
+    pub fn commit_message(&self) -> String {
+        if self.body.is_empty() {
+            self.subject.clone()
+        } else {
+            format!("{}\n\n{}", self.subject, self.body)
+        }
+    }
+}
+
+/// Whether `input` looks like an mbox patch series rather than a plain
+/// unified diff.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::mbox::is_mbox;
+///
+/// let mbox = "From 3f1c2a9b7d4e5f60718293a4b5c6d7e8f9a0b1c2 Mon Sep 17 00:00:00 2001\n\
+///             Subject: [PATCH] Fix\n";
+/// assert!(is_mbox(mbox));
+/// assert!(!is_mbox("diff --git a/a.rs b/a.rs\n"));
+/// ```
+pub fn is_mbox(input: &str) -> bool {
+    input
+        .trim_start()
+        .lines()
+        .next()
+        .is_some_and(is_message_start)
+        && input.lines().any(|line| line.starts_with("Subject:"))
+}
+
+/// Whether `line` is the `From <commit> Mon Sep 17 00:00:00 2001` line that
+/// `git format-patch` starts each message with. The fixed date tells it
+/// apart from a `From ` line in a commit message.
+fn is_message_start(line: &str) -> bool {
+    line.strip_prefix("From ")
+        .and_then(|rest| rest.split_once(' '))
+        .is_some_and(|(hash, date)| {
+            hash.len() == 40
+                && hash.bytes().all(|b| b.is_ascii_hexdigit())
+                && date == "Mon Sep 17 00:00:00 2001"
+        })
+}
+
+/// Split an mbox patch series into its patches, in order.
+///
+/// Messages are separated by the `From <commit> Mon Sep 17 00:00:00 2001`
+/// lines git writes. Headers end at the first blank line; the body runs up
+/// to the `---` line before the diffstat (or the first `diff --git` line),
+/// and the diff ends at the `-- ` signature git appends after the last
+/// hunk. Messages without a diff, such as a `[PATCH 0/n]` cover letter,
+/// are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::mbox::parse_mbox;
+///
+/// let mbox = "From 3f1c2a9b7d4e5f60718293a4b5c6d7e8f9a0b1c2 Mon Sep 17 00:00:00 2001\n\
+///             From: Alice <alice@example.com>\n\
+///             Subject: [PATCH] Bump limit\n\
+///             \n\
+///             The old limit was too low.\n\
+///             ---\n\
+///             diff --git a/a.rs b/a.rs\n\
+///             --- a/a.rs\n\
+///             +++ b/a.rs\n\
+///             @@ -1 +1 @@\n\
+///             -const LIMIT: u32 = 1;\n\
+///             +const LIMIT: u32 = 2;\n\
+///             -- \n\
+///             2.43.0\n";
+/// let patches = parse_mbox(mbox);
+/// assert_eq!(patches.len(), 1);
+/// assert_eq!(patches[0].subject, "Bump limit");
+/// assert_eq!(patches[0].body, "The old limit was too low.");
+/// assert!(patches[0].diff.ends_with("+const LIMIT: u32 = 2;\n"));
+/// ```
+pub fn parse_mbox(input: &str) -> Vec<Patch> {
+    let mut messages: Vec<Vec<&str>> = Vec::new();
+    for line in input.lines() {
+        if is_message_start(line) || messages.is_empty() {
+            messages.push(Vec::new());
+        }
+        if let Some(message) = messages.last_mut() {
+            message.push(line);
+        }
+    }
+
+    messages
+        .iter()
+        .filter_map(|lines| parse_message(lines))
+        .collect()
+}
+
+fn parse_message(lines: &[&str]) -> Option<Patch> {
+    let lines = match lines.split_first() {
+        Some((first, rest)) if is_message_start(first) => rest,
+        _ => lines,
+    };
+
+    // Headers, unfolding continuation lines
+    let header_end = lines
+        .iter()
+        .position(|line| line.is_empty())
+        .unwrap_or(lines.len());
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in &lines[..header_end] {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+    };
+
+    let rest = lines.get(header_end + 1..).unwrap_or_default();
+    let diff_start = rest
+        .iter()
+        .position(|line| line.starts_with("diff --git ") || line.starts_with("Index: "))?;
+    let body_end = rest[..diff_start]
+        .iter()
+        .position(|line| *line == "---")
+        .unwrap_or(diff_start);
+    let diff_end = signature_start(&rest[diff_start..]).map_or(rest.len(), |end| diff_start + end);
+
+    let mut diff = String::new();
+    for line in &rest[diff_start..diff_end] {
+        diff.push_str(line);
+        diff.push('\n');
+    }
+
+    Some(Patch {
+        subject: strip_patch_prefix(&header("subject").unwrap_or_default()).to_string(),
+        author: header("from"),
+        body: rest[..body_end].join("\n").trim().to_string(),
+        diff,
+    })
+}
+
+/// Index of the `-- ` signature line in `lines`, a message's diff.
+///
+/// Lines inside a hunk are skipped using the hunk header's line counts, so
+/// a removed line reading `- ` is not mistaken for the signature. Counts
+/// saturate at zero, so a hunk whose body disagrees with its header can't
+/// underflow them.
+fn signature_start(lines: &[&str]) -> Option<usize> {
+    let (mut old_left, mut new_left) = (0u32, 0u32);
+    for (i, line) in lines.iter().enumerate() {
+        if old_left > 0 || new_left > 0 {
+            match line.as_bytes().first() {
+                Some(b'-') => old_left = old_left.saturating_sub(1),
+                Some(b'+') => new_left = new_left.saturating_sub(1),
+                Some(b'\\') => {}
+                _ => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+        } else if *line == "-- " {
+            return Some(i);
+        } else if let Ok((_, old_lines, _, new_lines)) = parse_hunk_header(line) {
+            (old_left, new_left) = (old_lines, new_lines);
+        }
+    }
+    None
+}
+
+/// Drop a leading `[PATCH ...]` (or `[RFC ...]`) tag from a subject.
+fn strip_patch_prefix(subject: &str) -> &str {
+    match subject.strip_prefix('[').and_then(|s| s.split_once(']')) {
+        Some((_, rest)) => rest.trim_start(),
+        None => subject,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_patch_tags_from_subject() {
+        assert_eq!(strip_patch_prefix("[PATCH v2 3/7] Fix it"), "Fix it");
+        assert_eq!(strip_patch_prefix("Fix it"), "Fix it");
+    }
+
+    #[test]
+    fn skips_cover_letter_without_diff() {
+        let mbox = "From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH 0/1] Series\n\
+                    \n\
+                    Cover letter.\n";
+        assert!(parse_mbox(mbox).is_empty());
+    }
+
+    #[test]
+    fn from_lines_in_the_body_do_not_split_messages() {
+        let mbox = "From 3f1c2a9b7d4e5f60718293a4b5c6d7e8f9a0b1c2 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH] Quote the report\n\
+                    \n\
+                    From the bug report:\n\
+                    From 1a2b Mon Sep 17 00:00:00 2001\n\
+                    ---\n\
+                    diff --git a/a.rs b/a.rs\n";
+        let patches = parse_mbox(mbox);
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            patches[0].body,
+            "From the bug report:\nFrom 1a2b Mon Sep 17 00:00:00 2001"
+        );
+    }
+
+    #[test]
+    fn removed_dash_line_is_not_the_signature() {
+        let mbox = "From 3f1c2a9b7d4e5f60718293a4b5c6d7e8f9a0b1c2 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH] Trim list\n\
+                    \n\
+                    diff --git a/notes.md b/notes.md\n\
+                    --- a/notes.md\n\
+                    +++ b/notes.md\n\
+                    @@ -1,3 +1,2 @@\n\
+                    \x20- one\n\
+                    -- \n\
+                    \x20- two\n\
+                    -- \n\
+                    2.43.0\n";
+        let diff = &parse_mbox(mbox)[0].diff;
+        assert!(
+            diff.ends_with("@@ -1,3 +1,2 @@\n - one\n-- \n - two\n"),
+            "{diff}"
+        );
+    }
+
+    #[test]
+    fn context_line_past_a_side_count_does_not_underflow() {
+        let mbox = "From 3f1c2a9b7d4e5f60718293a4b5c6d7e8f9a0b1c2 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH] Add line\n\
+                    \n\
+                    diff --git a/notes.md b/notes.md\n\
+                    --- a/notes.md\n\
+                    +++ b/notes.md\n\
+                    @@ -1,0 +1,2 @@\n\
+                    +one\n\
+                    \x20two\n\
+                    -- \n\
+                    2.43.0\n";
+        let diff = &parse_mbox(mbox)[0].diff;
+        assert!(diff.ends_with("+one\n two\n"), "{diff}");
+    }
+
+    #[test]
+    fn unfolds_long_subject_headers() {
+        let mbox = "From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH] A subject that\n \
+                    wraps\n\
+                    \n\
+                    diff --git a/a.rs b/a.rs\n";
+        assert_eq!(parse_mbox(mbox)[0].subject, "A subject that wraps");
+    }
+}
@@ -0,0 +1,448 @@
+//! Detection of code moved between or within files.
+//!
+//! A refactor that moves a function shows up as a deletion in one place and
+//! an addition in another, and both sides would otherwise be reviewed as if
+//! they were new. [`detect_moves`] pairs removed and added blocks whose
+//! lines match once indentation and blank lines are ignored, and
+//! [`drop_moved_hunks`] removes the hunks that contain nothing but moved
+//! code, so the review focuses on what actually changed.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::parser::FileDiff;
+
+/// Fewest lines with code on them a block needs to count as moved, so
+/// closing braces and one-liners are not paired by accident.
+const MIN_MOVED_LINES: usize = 3;
+
+/// A block of code removed in one place and added unchanged in another.
+///
+/// Line ranges are inclusive: `from_start..=from_end` in the old version
+/// of `from`, and `to_start..=to_end` in the new version of `to`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_difflens::moves::MovedBlock;
+///
+/// let block = MovedBlock {
+///     from: PathBuf::from("src/a.rs"),
+///     from_start: 10,
+///     from_end: 14,
+///     to: PathBuf::from("src/b.rs"),
+///     to_start: 1,
+///     to_end: 5,
+///     lines: 5,
+/// };
+/// assert_eq!(block.to_string(), "src/a.rs:10-14 -> src/b.rs:1-5 (5 lines)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedBlock {
+    /// File the block was removed from.
+    pub from: PathBuf,
+    /// First removed line, in the old version.
+    pub from_start: u32,
+    /// Last removed line, in the old version.
+    pub from_end: u32,
+    /// File the block was added to.
+    pub to: PathBuf,
+    /// First added line, in the new version.
+    pub to_start: u32,
+    /// Last added line, in the new version.
+    pub to_end: u32,
+    /// Non-blank lines in the block.
+    pub lines: usize,
+}
+
+impl std::fmt::Display for MovedBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}-{} -> {}:{}-{} ({} lines)",
+            self.from.display(),
+            self.from_start,
+            self.from_end,
+            self.to.display(),
+            self.to_start,
+            self.to_end,
+            self.lines,
+        )
+    }
+}
+
+/// A contiguous run of removed or added lines in one hunk, without its
+/// blank lines.
+struct Run<'a> {
+    path: &'a Path,
+    /// `(file index, hunk index)`, so runs in the same hunk are not paired.
+    hunk: (usize, usize),
+    /// Line number and trimmed text of each non-blank line.
+    lines: Vec<(u32, &'a str)>,
+    /// Which of `lines` already belong to a detected move.
+    moved: Vec<bool>,
+}
+
+impl Run<'_> {
+    fn code_lines(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|(_, text)| text.chars().any(char::is_alphanumeric))
+            .count()
+    }
+}
+
+/// Find blocks that were removed in one hunk and re-added in another.
+///
+/// Lines are compared with surrounding whitespace trimmed and blank lines
+/// skipped, so re-indenting a moved function still counts as a move. The
+/// smaller of two runs must appear, line for line, inside the larger; the
+/// rest of the larger run is left as a real change. Each line belongs to at
+/// most one move.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::moves::detect_moves;
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,5 +1,1 @@\n keep();\n-fn f() {\n-    a();\n-    b();\n-}\n\
+///             diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1,1 +1,5 @@\n keep();\n+fn f() {\n+    a();\n+    b();\n+}\n";
+/// let moves = detect_moves(&parse_unified_diff(diff).unwrap());
+/// assert_eq!(moves.len(), 1);
+/// assert_eq!((moves[0].from_start, moves[0].to_start), (2, 2));
+/// ```
+pub fn detect_moves(diffs: &[FileDiff]) -> Vec<MovedBlock> {
+    let (mut removed, mut added) = collect_runs(diffs);
+    let mut moves = Vec::new();
+
+    for old in &mut removed {
+        for new in &mut added {
+            if old.hunk == new.hunk {
+                continue;
+            }
+            let Some((old_span, new_span)) = match_runs(old, new) else {
+                continue;
+            };
+            old.moved[old_span.clone()].fill(true);
+            new.moved[new_span.clone()].fill(true);
+            moves.push(MovedBlock {
+                from: old.path.to_path_buf(),
+                from_start: old.lines[old_span.start].0,
+                from_end: old.lines[old_span.end - 1].0,
+                to: new.path.to_path_buf(),
+                to_start: new.lines[new_span.start].0,
+                to_end: new.lines[new_span.end - 1].0,
+                lines: old_span.len(),
+            });
+        }
+    }
+    moves
+}
+
+/// Remove the hunks whose changed lines all belong to `moves`, and the
+/// files left with no hunks.
+///
+/// Hunks that mix moved and edited lines are kept whole, so the edit is
+/// reviewed with its surroundings.
+pub fn drop_moved_hunks(diffs: Vec<FileDiff>, moves: &[MovedBlock]) -> Vec<FileDiff> {
+    if moves.is_empty() {
+        return diffs;
+    }
+    diffs
+        .into_iter()
+        .filter_map(|mut diff| {
+            let before = diff.hunks.len();
+            let (old_path, new_path) = (diff.old_path.clone(), diff.new_path.clone());
+            diff.hunks.retain(|hunk| {
+                let mut changed = 0;
+                let mut covered = 0;
+                for_each_changed_line(hunk, |sign, line, text| {
+                    if text.trim().is_empty() {
+                        return;
+                    }
+                    changed += 1;
+                    let is_moved = moves.iter().any(|m| match sign {
+                        '-' => m.from == old_path && (m.from_start..=m.from_end).contains(&line),
+                        _ => m.to == new_path && (m.to_start..=m.to_end).contains(&line),
+                    });
+                    if is_moved {
+                        covered += 1;
+                    }
+                });
+                changed == 0 || covered < changed
+            });
+            (diff.hunks.len() == before || !diff.hunks.is_empty()).then_some(diff)
+        })
+        .collect()
+}
+
+/// The removed and added runs of every hunk in `diffs`.
+fn collect_runs(diffs: &[FileDiff]) -> (Vec<Run<'_>>, Vec<Run<'_>>) {
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for (file_idx, diff) in diffs.iter().enumerate() {
+        for (hunk_idx, hunk) in diff.hunks.iter().enumerate() {
+            let hunk_id = (file_idx, hunk_idx);
+            let mut current: Option<(char, Run<'_>)> = None;
+            let mut old_line = hunk.old_start;
+            let mut new_line = hunk.new_start;
+            for raw in hunk.content.lines() {
+                let sign = raw.chars().next().unwrap_or(' ');
+                if sign == '\\' {
+                    continue;
+                }
+                let line_no = match sign {
+                    '-' => old_line,
+                    _ => new_line,
+                };
+                match sign {
+                    '-' => old_line += 1,
+                    '+' => new_line += 1,
+                    _ => {
+                        old_line += 1;
+                        new_line += 1;
+                    }
+                }
+                if sign != '-' && sign != '+' {
+                    keep_run(current.take(), &mut removed, &mut added);
+                    continue;
+                }
+                if current.as_ref().is_some_and(|(s, _)| *s != sign) {
+                    keep_run(current.take(), &mut removed, &mut added);
+                }
+                let (_, run) = current.get_or_insert_with(|| {
+                    let path = if sign == '-' {
+                        diff.old_path.as_path()
+                    } else {
+                        diff.new_path.as_path()
+                    };
+                    (
+                        sign,
+                        Run {
+                            path,
+                            hunk: hunk_id,
+                            lines: Vec::new(),
+                            moved: Vec::new(),
+                        },
+                    )
+                });
+                let text = raw[1..].trim();
+                if !text.is_empty() {
+                    run.lines.push((line_no, text));
+                    run.moved.push(false);
+                }
+            }
+            keep_run(current.take(), &mut removed, &mut added);
+        }
+    }
+    (removed, added)
+}
+
+/// Add a finished run to `removed` or `added` by its sign, if it is long
+/// enough to be a move.
+fn keep_run<'a>(
+    run: Option<(char, Run<'a>)>,
+    removed: &mut Vec<Run<'a>>,
+    added: &mut Vec<Run<'a>>,
+) {
+    let Some((sign, run)) = run else {
+        return;
+    };
+    if run.code_lines() < MIN_MOVED_LINES {
+        return;
+    }
+    if sign == '-' {
+        removed.push(run);
+    } else {
+        added.push(run);
+    }
+}
+
+/// Where the smaller of `old` and `new` appears inside the larger, as
+/// index ranges into each run's lines. Lines already moved are not reused.
+fn match_runs(
+    old: &Run<'_>,
+    new: &Run<'_>,
+) -> Option<(std::ops::Range<usize>, std::ops::Range<usize>)> {
+    let (small, large, small_is_old) = if old.lines.len() <= new.lines.len() {
+        (old, new, true)
+    } else {
+        (new, old, false)
+    };
+    if small.code_lines() < MIN_MOVED_LINES || small.moved.iter().any(|&m| m) {
+        return None;
+    }
+    let len = small.lines.len();
+    let offset = (0..=large.lines.len() - len).find(|&start| {
+        !large.moved[start..start + len].iter().any(|&m| m)
+            && large.lines[start..start + len]
+                .iter()
+                .zip(&small.lines)
+                .all(|((_, a), (_, b))| a == b)
+    })?;
+    let small_span = 0..len;
+    let large_span = offset..offset + len;
+    Some(if small_is_old {
+        (small_span, large_span)
+    } else {
+        (large_span, small_span)
+    })
+}
+
+/// Call `f` with the sign, line number, and text of each `-` and `+` line
+/// in `hunk`; removed lines are numbered in the old version, added lines
+/// in the new.
+fn for_each_changed_line(hunk: &argus_core::DiffHunk, mut f: impl FnMut(char, u32, &str)) {
+    let mut old_line = hunk.old_start;
+    let mut new_line = hunk.new_start;
+    for raw in hunk.content.lines() {
+        match raw.chars().next() {
+            Some('-') => {
+                f('-', old_line, &raw[1..]);
+                old_line += 1;
+            }
+            Some('+') => {
+                f('+', new_line, &raw[1..]);
+                new_line += 1;
+            }
+            Some('\\') => {}
+            _ => {
+                old_line += 1;
+                new_line += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_unified_diff;
+
+    /// `parse_config` moves from `src/lib.rs` into an `impl` block in a new
+    /// `src/config.rs`, re-indented; `run` in `src/lib.rs` is also edited.
+    const MOVE_WITH_EDITS: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,11 +1,4 @@
+ mod config;
+-fn parse_config(text: &str) -> Config {
+-    let mut config = Config::default();
+-    for line in text.lines() {
+-        config.apply(line);
+-    }
+-    config
+-}
+ pub fn run() {
+-    start();
++    start_with_retries(3);
+ }
+diff --git a/src/config.rs b/src/config.rs
+new file mode 100644
+--- /dev/null
++++ b/src/config.rs
+@@ -0,0 +1,9 @@
++impl Config {
++    fn parse_config(text: &str) -> Config {
++        let mut config = Config::default();
++        for line in text.lines() {
++            config.apply(line);
++        }
++        config
++    }
++}
+";
+
+    /// The same move with nothing else changed.
+    const PURE_MOVE: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,9 +1,2 @@
+ mod config;
+-fn parse_config(text: &str) -> Config {
+-    let mut config = Config::default();
+-    for line in text.lines() {
+-        config.apply(line);
+-    }
+-    config
+-}
+ pub fn run() {
+diff --git a/src/config.rs b/src/config.rs
+new file mode 100644
+--- /dev/null
++++ b/src/config.rs
+@@ -0,0 +1,7 @@
++fn parse_config(text: &str) -> Config {
++    let mut config = Config::default();
++    for line in text.lines() {
++        config.apply(line);
++    }
++    config
++}
+";
+
+    #[test]
+    fn function_moved_between_files_is_detected_and_dropped() {
+        let diffs = parse_unified_diff(PURE_MOVE).unwrap();
+        let moves = detect_moves(&diffs);
+        assert_eq!(
+            moves,
+            [MovedBlock {
+                from: PathBuf::from("src/lib.rs"),
+                from_start: 2,
+                from_end: 8,
+                to: PathBuf::from("src/config.rs"),
+                to_start: 1,
+                to_end: 7,
+                lines: 7,
+            }]
+        );
+        assert!(drop_moved_hunks(diffs, &moves).is_empty());
+    }
+
+    #[test]
+    fn hunks_with_real_edits_around_a_move_are_kept() {
+        let diffs = parse_unified_diff(MOVE_WITH_EDITS).unwrap();
+        let moves = detect_moves(&diffs);
+        assert_eq!(moves.len(), 1);
+        assert_eq!((moves[0].from_start, moves[0].from_end), (2, 8));
+        assert_eq!((moves[0].to_start, moves[0].to_end), (2, 8));
+
+        // lib.rs also edits run() and config.rs adds the impl wrapper
+        let kept = drop_moved_hunks(diffs, &moves);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn edits_in_place_and_short_blocks_are_not_moves() {
+        let diff = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1,4 +1,4 @@
+-fn f() {
+-    work();
+-    rest();
+-}
++    fn f() {
++        work();
++        rest();
++    }
+diff --git a/b.rs b/b.rs
+--- a/b.rs
++++ b/b.rs
+@@ -1,2 +1,1 @@
+-}
+-}
++}
+";
+        let diffs = parse_unified_diff(diff).unwrap();
+        assert!(detect_moves(&diffs).is_empty());
+    }
+}
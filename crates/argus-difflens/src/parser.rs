@@ -1,5 +1,5 @@
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use argus_core::{ArgusError, ChangeType, DiffHunk};
 
@@ -211,15 +211,62 @@ fn parse_path(raw: &str) -> PathBuf {
         return PathBuf::from("/dev/null");
     }
 
-    let stripped = normalized
-        .strip_prefix("a/")
-        .or_else(|| normalized.strip_prefix("b/"))
-        .unwrap_or(normalized);
+    // The `a/` and `b/` diff prefixes are stripped here, once: a later pass
+    // over the parsed path must not eat a real top-level `a` or `b` directory
+    let path = Path::new(normalized);
+    let stripped = path
+        .strip_prefix("a")
+        .or_else(|_| path.strip_prefix("b"))
+        .ok()
+        .filter(|rest| !rest.as_os_str().is_empty())
+        .unwrap_or(path);
+    normalize_path(stripped, None)
+}
 
-    PathBuf::from(stripped)
+/// Put a file path in the canonical repository-relative form used for
+/// diffs and review comments.
+///
+/// Strips `.` components. An absolute path under `repo_root` is made
+/// relative to it; `repo_root` may be relative, in which case it is
+/// resolved against the current directory. Absolute paths outside it are
+/// returned as they are. The `a/` and `b/` diff prefixes are not touched:
+/// [`parse_unified_diff`] strips them from the headers, so normalizing a
+/// path again leaves it unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_difflens::parser::normalize_path;
+///
+/// let root = Some(Path::new("/work/repo"));
+/// assert_eq!(normalize_path(Path::new("/work/repo/src/x.rs"), root), Path::new("src/x.rs"));
+/// assert_eq!(normalize_path(Path::new("./src/x.rs"), None), Path::new("src/x.rs"));
+/// assert_eq!(normalize_path(Path::new("a/x.rs"), root), Path::new("a/x.rs"));
+/// ```
+pub fn normalize_path(path: &Path, repo_root: Option<&Path>) -> PathBuf {
+    if path.is_absolute() {
+        let relative = repo_root.and_then(|root| {
+            path.strip_prefix(root).ok().or_else(|| {
+                let resolved = root.canonicalize().ok()?;
+                path.strip_prefix(resolved).ok()
+            })
+        });
+        return match relative {
+            Some(relative) => without_cur_dir(relative),
+            None => path.to_path_buf(),
+        };
+    }
+    without_cur_dir(path)
+}
+
+fn without_cur_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
 }
 
-fn parse_hunk_header(line: &str) -> Result<(u32, u32, u32, u32), ArgusError> {
+pub(crate) fn parse_hunk_header(line: &str) -> Result<(u32, u32, u32, u32), ArgusError> {
     let inner = line
         .strip_prefix("@@ ")
         .and_then(|s| {
@@ -476,6 +523,51 @@ diff --git a/f.rs b/f.rs
         );
     }
 
+    #[test]
+    fn prefixed_absolute_and_relative_paths_normalize_alike() {
+        let root = Path::new("/work/repo");
+        for raw in ["/work/repo/src/x.rs", "src/x.rs", "./src/x.rs"] {
+            assert_eq!(
+                normalize_path(Path::new(raw), Some(root)),
+                PathBuf::from("src/x.rs"),
+                "{raw}"
+            );
+        }
+        for raw in ["a/src/x.rs", "b/src/x.rs"] {
+            assert_eq!(parse_path(raw), PathBuf::from("src/x.rs"), "{raw}");
+        }
+        assert_eq!(
+            normalize_path(Path::new("/elsewhere/src/x.rs"), Some(root)),
+            PathBuf::from("/elsewhere/src/x.rs")
+        );
+        // Only a whole leading `a`/`b` component is a diff prefix
+        assert_eq!(parse_path("api/x.rs"), PathBuf::from("api/x.rs"));
+
+        let diff = "--- a/src/x.rs\n+++ b/src/x.rs\n@@ -1,1 +1,1 @@\n-old\n+new\n";
+        let files = parse_unified_diff(diff).unwrap();
+        assert_eq!(
+            files[0].new_path,
+            normalize_path(Path::new("/work/repo/src/x.rs"), Some(root))
+        );
+    }
+
+    #[test]
+    fn top_level_a_directory_survives_normalizing_twice() {
+        let root = Path::new("/work/repo");
+        let diff = "--- a/a/lib.rs\n+++ b/a/lib.rs\n@@ -1,1 +1,1 @@\n-old\n+new\n";
+        let files = parse_unified_diff(diff).unwrap();
+        assert_eq!(files[0].new_path, PathBuf::from("a/lib.rs"));
+        assert_eq!(files[0].hunks[0].file_path, PathBuf::from("a/lib.rs"));
+
+        let once = normalize_path(&files[0].new_path, Some(root));
+        assert_eq!(once, PathBuf::from("a/lib.rs"));
+        assert_eq!(normalize_path(&once, Some(root)), once);
+        assert_eq!(
+            normalize_path(Path::new("/work/repo/a/lib.rs"), Some(root)),
+            once
+        );
+    }
+
     #[test]
     fn quoted_paths_are_parsed_in_unified_diff() {
         let diff = r#"--- "a/src/my file.rs"
@@ -1,9 +1,22 @@
+//! Risk scoring for diffs, as reported by `argus diff`.
+//!
+//! [`compute_risk`] is the stable programmatic entry point: it takes diffs
+//! from [`parse_unified_diff`](crate::parser::parse_unified_diff) and
+//! returns a [`RiskReport`] whose overall score, per-file breakdown, and
+//! summary are all public fields. Every report type implements `Serialize`
+//! and `Deserialize` with the same camelCase field names as
+//! `argus --format json diff`, so other tools can embed risk scoring, or
+//! read the CLI's JSON back, without shelling out.
+
 use std::fmt;
 use std::path::Path;
 
-use argus_core::{ChangeType, DiffHunk, RiskScore};
+use argus_core::DiffHunk;
 use serde::{Deserialize, Serialize};
 
+/// Re-exported so the whole report can be used from this module.
+pub use argus_core::{ChangeType, RiskScore};
+
 use crate::parser::FileDiff;
 
 /// Complete risk analysis for a set of diffs.
@@ -24,7 +37,7 @@ use crate::parser::FileDiff;
 /// let report = compute_risk(&files);
 /// assert!(report.overall.total >= 0.0);
 /// ```
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskReport {
     /// Aggregate risk score across all files.
@@ -33,10 +46,14 @@ pub struct RiskReport {
     pub per_file: Vec<FileRisk>,
     /// High-level summary statistics.
     pub summary: RiskSummary,
+    /// Change in each overall factor against a baseline report, if one was
+    /// given (see [`RiskReport::with_baseline`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trend: Vec<FactorTrend>,
 }
 
 /// Risk details for a single file.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileRisk {
     /// File path.
@@ -54,7 +71,7 @@ pub struct FileRisk {
 }
 
 /// Summary statistics for a diff.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskSummary {
     /// Number of files changed.
@@ -127,19 +144,123 @@ impl fmt::Display for RiskLevel {
     }
 }
 
+/// How one overall risk factor moved relative to a baseline report.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::risk::FactorTrend;
+///
+/// let t = FactorTrend {
+///     factor: "diffusion".into(),
+///     baseline: 40.0,
+///     current: 52.0,
+/// };
+/// assert_eq!(t.percent_change(), Some(30.0));
+/// assert_eq!(t.to_string(), "diffusion ↑ 30% (40.0 → 52.0)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorTrend {
+    /// Factor name: `overall`, `size`, `complexity`, `diffusion`,
+    /// `coverage`, or `file_type`.
+    pub factor: String,
+    /// Score in the baseline report (0–100).
+    pub baseline: f64,
+    /// Score in the current report (0–100).
+    pub current: f64,
+}
+
+impl FactorTrend {
+    /// Relative change in percent, or `None` when the baseline is zero.
+    pub fn percent_change(&self) -> Option<f64> {
+        (self.baseline > 0.0).then(|| (self.current - self.baseline) / self.baseline * 100.0)
+    }
+
+    /// `↑` when the factor got riskier, `↓` when it improved, `=` when it
+    /// moved by less than 0.05 points.
+    pub fn arrow(&self) -> &'static str {
+        let delta = self.current - self.baseline;
+        if delta > 0.05 {
+            "↑"
+        } else if delta < -0.05 {
+            "↓"
+        } else {
+            "="
+        }
+    }
+
+    /// The arrow plus percent change, e.g. `↑ 30%`.
+    fn change(&self) -> String {
+        match (self.arrow(), self.percent_change()) {
+            ("=", _) => "=".into(),
+            (arrow, Some(pct)) => format!("{arrow} {:.0}%", pct.abs()),
+            (arrow, None) => arrow.into(),
+        }
+    }
+}
+
+impl fmt::Display for FactorTrend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({:.1} → {:.1})",
+            self.factor,
+            self.change(),
+            self.baseline,
+            self.current
+        )
+    }
+}
+
 /// Compute a risk report from parsed file diffs.
 ///
 /// Scoring uses size, file-type heuristics, and keyword-based complexity
-/// deltas. Coverage is set to 0 (requires coverage data).
+/// deltas. Coverage is set to 0 (requires coverage data). The result is
+/// deterministic, so it is safe to compare across runs or against a
+/// baseline (see [`RiskReport::with_baseline`]).
 ///
 /// # Examples
 ///
 /// ```
-/// use argus_difflens::risk::compute_risk;
+/// use std::path::Path;
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_difflens::risk::{compute_risk, ChangeType, RiskLevel, RiskReport};
+///
+/// let diff = "diff --git a/src/auth.rs b/src/auth.rs\n\
+///             --- a/src/auth.rs\n\
+///             +++ b/src/auth.rs\n\
+///             @@ -1,2 +1,4 @@\n \
+///             fn check(user: &User) -> bool {\n\
+///             +    if user.is_admin() {\n\
+///             +        return true;\n\
+///             +    }\n \
+///             }\n";
+/// let report = compute_risk(&parse_unified_diff(diff).unwrap());
+///
+/// // Overall factors, each 0-100, and their weighted total
+/// assert_eq!(report.overall.size, 6.0);
+/// assert_eq!(report.overall.diffusion, 20.0);
+/// assert!(report.overall.complexity > 0.0);
+/// assert!(report.overall.total > 0.0 && report.overall.total <= 100.0);
 ///
-/// let report = compute_risk(&[]);
-/// assert_eq!(report.summary.total_files, 0);
-/// assert_eq!(report.overall.total, 0.0);
+/// // Per-file breakdown
+/// let file = &report.per_file[0];
+/// assert_eq!(file.path, Path::new("src/auth.rs"));
+/// assert_eq!((file.lines_added, file.lines_deleted, file.hunk_count), (3, 0, 1));
+/// assert_eq!(file.change_type, ChangeType::Modify);
+///
+/// // Summary, and the same JSON shape as `argus --format json diff`
+/// assert_eq!(report.summary.total_additions, 3);
+/// assert_eq!(report.summary.risk_level, RiskLevel::from_score(report.overall.total));
+/// let json = serde_json::to_value(&report).unwrap();
+/// assert_eq!(json["perFile"][0]["linesAdded"], 3);
+/// let parsed: RiskReport = serde_json::from_value(json).unwrap();
+/// assert_eq!(parsed.per_file.len(), 1);
+///
+/// let empty = compute_risk(&[]);
+/// assert_eq!(empty.summary.total_files, 0);
+/// assert_eq!(empty.overall.total, 0.0);
 /// ```
 pub fn compute_risk(diffs: &[FileDiff]) -> RiskReport {
     if diffs.is_empty() {
@@ -152,6 +273,7 @@ pub fn compute_risk(diffs: &[FileDiff]) -> RiskReport {
                 total_deletions: 0,
                 risk_level: RiskLevel::Low,
             },
+            trend: Vec::new(),
         };
     }
 
@@ -209,6 +331,7 @@ pub fn compute_risk(diffs: &[FileDiff]) -> RiskReport {
         overall,
         per_file,
         summary,
+        trend: Vec::new(),
     }
 }
 
@@ -340,6 +463,14 @@ impl fmt::Display for RiskReport {
             self.overall.total, self.summary.risk_level
         )?;
 
+        if !self.trend.is_empty() {
+            writeln!(f, "Trend vs baseline:")?;
+            for t in &self.trend {
+                writeln!(f, "  {t}")?;
+            }
+            writeln!(f)?;
+        }
+
         if !self.per_file.is_empty() {
             writeln!(
                 f,
@@ -369,6 +500,43 @@ impl fmt::Display for RiskReport {
 }
 
 impl RiskReport {
+    /// Annotate this report with how each overall factor moved since
+    /// `baseline`, a previously saved report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_difflens::parser::parse_unified_diff;
+    /// use argus_difflens::risk::compute_risk;
+    ///
+    /// let diff = "diff --git a/f.rs b/f.rs\n\
+    ///             --- a/f.rs\n\
+    ///             +++ b/f.rs\n\
+    ///             @@ -1,1 +1,2 @@\n\
+    ///              line\n\
+    ///             +new\n";
+    /// let baseline = compute_risk(&[]);
+    /// let report = compute_risk(&parse_unified_diff(diff).unwrap()).with_baseline(&baseline);
+    /// let size = report.trend.iter().find(|t| t.factor == "size").unwrap();
+    /// assert_eq!(size.arrow(), "↑");
+    /// ```
+    pub fn with_baseline(mut self, baseline: &RiskReport) -> Self {
+        let factor = |name: &str, get: fn(&RiskScore) -> f64| FactorTrend {
+            factor: name.into(),
+            baseline: get(&baseline.overall),
+            current: get(&self.overall),
+        };
+        self.trend = vec![
+            factor("overall", |s| s.total),
+            factor("size", |s| s.size),
+            factor("complexity", |s| s.complexity),
+            factor("diffusion", |s| s.diffusion),
+            factor("coverage", |s| s.coverage),
+            factor("file_type", |s| s.file_type),
+        ];
+        self
+    }
+
     /// Render the report as a markdown string.
     ///
     /// # Examples
@@ -388,6 +556,22 @@ impl RiskReport {
             self.overall.total, self.summary.risk_level
         ));
 
+        if !self.trend.is_empty() {
+            out.push_str("**Trend vs baseline:**\n\n");
+            out.push_str("| Factor | Baseline | Current | Change |\n");
+            out.push_str("|--------|----------|---------|--------|\n");
+            for t in &self.trend {
+                out.push_str(&format!(
+                    "| {} | {:.1} | {:.1} | {} |\n",
+                    t.factor,
+                    t.baseline,
+                    t.current,
+                    t.change(),
+                ));
+            }
+            out.push('\n');
+        }
+
         if !self.per_file.is_empty() {
             out.push_str("| File | Change | +/- | Risk |\n");
             out.push_str("|------|--------|-----|------|\n");
@@ -412,6 +596,142 @@ impl RiskReport {
     }
 }
 
+/// SARIF rule for files whose overall risk is high.
+const RULE_FILE: &str = "argus.risk.file";
+/// SARIF rule for files whose change adds (or removes) many branches.
+const RULE_COMPLEXITY: &str = "argus.risk.complexity";
+/// SARIF rule for files whose change is scattered across many hunks.
+const RULE_DIFFUSION: &str = "argus.risk.diffusion";
+
+/// Convert a risk report to SARIF v2.1.0 JSON.
+///
+/// Emits one result per risky file (`argus.risk.file`, high or critical
+/// overall risk), per complexity spike (`argus.risk.complexity`), and per
+/// scattered change (`argus.risk.diffusion`). Components scoring "low"
+/// produce no result. Rule IDs are distinct from the review SARIF rules so
+/// both can be uploaded side by side.
+///
+/// # Examples
+///
+/// ```
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_difflens::risk::{compute_risk, to_sarif};
+///
+/// let diff = "diff --git a/f.rs b/f.rs\n\
+///             --- a/f.rs\n\
+///             +++ b/f.rs\n\
+///             @@ -1,1 +1,4 @@\n\
+///             +if a {\n\
+///             +    for x in y {\n\
+///             +        while z {}\n\
+///              }\n";
+/// let report = compute_risk(&parse_unified_diff(diff).unwrap());
+/// let sarif = to_sarif(&report);
+/// assert_eq!(sarif["version"], "2.1.0");
+/// assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "argus.risk.complexity");
+/// ```
+pub fn to_sarif(report: &RiskReport) -> serde_json::Value {
+    let mut results = Vec::new();
+
+    for fr in &report.per_file {
+        let uri = fr.path.display().to_string();
+        let mut push = |rule_id: &str, level: &str, text: String| {
+            results.push(serde_json::json!({
+                "ruleId": rule_id,
+                "level": level,
+                "message": { "text": text },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": &uri },
+                        "region": { "startLine": 1 }
+                    }
+                }],
+                "properties": { "riskScore": fr.score.total }
+            }));
+        };
+
+        let file_level = RiskLevel::from_score(fr.score.total);
+        if matches!(file_level, RiskLevel::High | RiskLevel::Critical) {
+            push(
+                RULE_FILE,
+                sarif_level(file_level),
+                format!(
+                    "{file_level} risk change ({:.1}/100): +{}/-{} lines in {} hunks",
+                    fr.score.total, fr.lines_added, fr.lines_deleted, fr.hunk_count,
+                ),
+            );
+        }
+
+        let complexity_level = RiskLevel::from_score(fr.score.complexity);
+        if complexity_level != RiskLevel::Low {
+            push(
+                RULE_COMPLEXITY,
+                sarif_level(complexity_level),
+                format!(
+                    "Complexity spike: branching changed significantly (complexity delta {:.1}/100)",
+                    fr.score.complexity,
+                ),
+            );
+        }
+
+        let diffusion_level = RiskLevel::from_score(fr.score.diffusion);
+        if diffusion_level != RiskLevel::Low {
+            // Scattered edits are a review smell, not a defect: cap at warning.
+            let level = match diffusion_level {
+                RiskLevel::Critical => sarif_level(RiskLevel::High),
+                other => sarif_level(other),
+            };
+            push(
+                RULE_DIFFUSION,
+                level,
+                format!(
+                    "Change is scattered across {} hunks (diffusion {:.1}/100)",
+                    fr.hunk_count, fr.score.diffusion,
+                ),
+            );
+        }
+    }
+
+    let rules: Vec<serde_json::Value> = [
+        (RULE_FILE, "High-risk file change", "warning"),
+        (RULE_COMPLEXITY, "Complexity spike", "note"),
+        (RULE_DIFFUSION, "Scattered change", "note"),
+    ]
+    .iter()
+    .map(|(id, name, level)| {
+        serde_json::json!({
+            "id": id,
+            "shortDescription": { "text": name },
+            "defaultConfiguration": { "level": level }
+        })
+    })
+    .collect();
+
+    serde_json::json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "argus",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/Meru143/argus",
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    })
+}
+
+fn sarif_level(level: RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::Low | RiskLevel::Medium => "note",
+        RiskLevel::High => "warning",
+        RiskLevel::Critical => "error",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,6 +839,55 @@ diff --git a/f.rs b/f.rs
         assert!(md.contains("f.rs"));
     }
 
+    #[test]
+    fn baseline_trend_round_trips_through_json() {
+        let one_file = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1 +1,2 @@
+ a
++b
+";
+        let three_files = format!(
+            "{one_file}{}{}",
+            one_file.replace("a.rs", "b.rs"),
+            one_file.replace("a.rs", "c.rs")
+        );
+
+        let saved =
+            serde_json::to_string(&compute_risk(&parse_unified_diff(one_file).unwrap())).unwrap();
+        assert!(!saved.contains("trend"));
+        let baseline: RiskReport = serde_json::from_str(&saved).unwrap();
+
+        let report =
+            compute_risk(&parse_unified_diff(&three_files).unwrap()).with_baseline(&baseline);
+        let diffusion = report
+            .trend
+            .iter()
+            .find(|t| t.factor == "diffusion")
+            .unwrap();
+        assert_eq!(diffusion.arrow(), "↑");
+        assert_eq!(diffusion.percent_change(), Some(200.0));
+        let coverage = report
+            .trend
+            .iter()
+            .find(|t| t.factor == "coverage")
+            .unwrap();
+        assert_eq!(coverage.arrow(), "=");
+
+        let text = report.to_string();
+        assert!(text.contains("Trend vs baseline:"), "{text}");
+        assert!(text.contains("diffusion ↑ 200% (20.0 → 60.0)"), "{text}");
+        assert!(report
+            .to_markdown()
+            .contains("| diffusion | 20.0 | 60.0 | ↑ 200% |"));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let reloaded: RiskReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.trend, report.trend);
+    }
+
     #[test]
     fn complexity_delta_added_branches() {
         let hunk = DiffHunk {
@@ -606,4 +975,52 @@ diff --git a/complex.rs b/complex.rs
             "complexity should be non-zero for diffs with branch changes"
         );
     }
+
+    #[test]
+    fn sarif_reports_complexity_and_diffusion_with_distinct_rules() {
+        let mut diff = String::from(
+            "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n",
+        );
+        for i in 0..4 {
+            let start = i * 20 + 1;
+            diff.push_str(&format!(
+                "@@ -{start},1 +{start},4 @@\n+if a {{\n+    for x in y {{\n+        while z {{}}\n }}\n"
+            ));
+        }
+        let report = compute_risk(&parse_unified_diff(&diff).unwrap());
+        let sarif = to_sarif(&report);
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let rule_ids: Vec<&str> = results
+            .iter()
+            .map(|r| r["ruleId"].as_str().unwrap())
+            .collect();
+        assert!(rule_ids.contains(&"argus.risk.complexity"), "{rule_ids:?}");
+        assert!(rule_ids.contains(&"argus.risk.diffusion"), "{rule_ids:?}");
+
+        let diffusion = results
+            .iter()
+            .find(|r| r["ruleId"] == "argus.risk.diffusion")
+            .unwrap();
+        assert_eq!(diffusion["level"], "warning");
+        assert_eq!(
+            diffusion["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert!(rules
+            .iter()
+            .all(|r| r["id"].as_str().unwrap().starts_with("argus.risk.")));
+    }
+
+    #[test]
+    fn sarif_is_empty_for_low_risk_changes() {
+        let diff = "diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1,1 +1,2 @@\n line\n+typo fix\n";
+        let report = compute_risk(&parse_unified_diff(diff).unwrap());
+        let sarif = to_sarif(&report);
+        assert!(sarif["runs"][0]["results"].as_array().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,41 @@
+use argus_difflens::mbox::{is_mbox, parse_mbox};
+use argus_difflens::parser::parse_unified_diff;
+use std::path::PathBuf;
+
+#[test]
+fn two_patch_series_splits_into_messages_and_diffs() {
+    let mbox = include_str!("fixtures/series.mbox");
+    assert!(is_mbox(mbox));
+
+    let patches = parse_mbox(mbox);
+    assert_eq!(patches.len(), 2);
+
+    assert_eq!(patches[0].subject, "config: raise the default retry limit");
+    assert_eq!(
+        patches[0].author.as_deref(),
+        Some("Alice Example <alice@example.com>")
+    );
+    assert_eq!(
+        patches[0].body,
+        "Three retries are not enough behind flaky proxies, so allow five."
+    );
+    assert_eq!(patches[1].subject, "client: back off between retries");
+    assert!(patches[1]
+        .commit_message()
+        .starts_with("client: back off between retries\n\nSleep between attempts"));
+
+    let first = parse_unified_diff(&patches[0].diff).unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].new_path, PathBuf::from("src/config.rs"));
+    assert!(!patches[0].diff.contains("2.43.0"));
+
+    let second = parse_unified_diff(&patches[1].diff).unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].new_path, PathBuf::from("src/client.rs"));
+    assert_eq!(second[0].hunks[0].new_start, 10);
+}
+
+#[test]
+fn plain_diff_is_not_mbox() {
+    assert!(!is_mbox(include_str!("fixtures/simple.diff")));
+}
@@ -0,0 +1,165 @@
+//! Line-level authorship via `git blame`.
+//!
+//! Maps lines of a file to the author of the commit that last changed them.
+//! Blame runs against the working-tree contents, so line numbers match the
+//! new side of a diff; lines that are not committed yet have no author.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use argus_core::ArgusError;
+use git2::Repository;
+
+/// The author of the commit that last changed a line.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::blame::LineAuthor;
+///
+/// let author = LineAuthor {
+///     name: "Alice".into(),
+///     email: "alice@example.com".into(),
+/// };
+/// assert_eq!(author.to_string(), "Alice <alice@example.com>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineAuthor {
+    /// Author name.
+    pub name: String,
+    /// Author email.
+    pub email: String,
+}
+
+impl std::fmt::Display for LineAuthor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+/// Blame `lines` (1-based) of `file` and return the last author of each.
+///
+/// `file` is relative to the root of the repository containing
+/// `repo_path`. Lines that are uncommitted or out of range are left out of
+/// the result. When the file is missing from the working tree, the
+/// committed version is blamed instead.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Git`] if the repository cannot be opened or the
+/// file has no history.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_gitpulse::blame::blame_lines;
+///
+/// let authors = blame_lines(Path::new("."), Path::new("src/main.rs"), &[1, 10]).unwrap();
+/// if let Some(author) = authors.get(&10) {
+///     println!("line 10 last changed by {author}");
+/// }
+/// ```
+pub fn blame_lines(
+    repo_path: &Path,
+    file: &Path,
+    lines: &[u32],
+) -> Result<HashMap<u32, LineAuthor>, ArgusError> {
+    let repo = Repository::discover(repo_path)
+        .map_err(|e| ArgusError::Git(format!("failed to open repository: {e}")))?;
+    let committed = repo
+        .blame_file(file, None)
+        .map_err(|e| ArgusError::Git(format!("failed to blame {}: {e}", file.display())))?;
+
+    let working = repo
+        .workdir()
+        .and_then(|dir| std::fs::read(dir.join(file)).ok());
+    let buffered =
+        match &working {
+            Some(contents) => Some(committed.blame_buffer(contents).map_err(|e| {
+                ArgusError::Git(format!("failed to blame {}: {e}", file.display()))
+            })?),
+            None => None,
+        };
+    let blame = buffered.as_ref().unwrap_or(&committed);
+
+    let mut authors = HashMap::new();
+    for &line in lines {
+        let Some(hunk) = blame.get_line(line as usize) else {
+            continue;
+        };
+        // Uncommitted lines have no commit (and no signature)
+        if hunk.final_commit_id().is_zero() {
+            continue;
+        }
+        let signature = hunk.final_signature();
+        authors.insert(
+            line,
+            LineAuthor {
+                name: signature.name().unwrap_or("unknown").to_string(),
+                email: signature.email().unwrap_or_default().to_string(),
+            },
+        );
+    }
+    Ok(authors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, author: &str) {
+        let workdir = repo.workdir().unwrap();
+        std::fs::write(workdir.join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig =
+            Signature::now(author, &format!("{}@example.com", author.to_lowercase())).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn maps_lines_to_last_author() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "lib.rs", "fn a() {}\nfn b() {}\n", "Alice");
+        commit_file(
+            &repo,
+            "lib.rs",
+            "fn a() {}\nfn b() { 1 }\nfn c() {}\n",
+            "Bob",
+        );
+
+        let authors = blame_lines(dir.path(), Path::new("lib.rs"), &[1, 2, 3, 99]).unwrap();
+        assert_eq!(authors[&1].name, "Alice");
+        assert_eq!(authors[&2].name, "Bob");
+        assert_eq!(authors[&3].email, "bob@example.com");
+        assert!(!authors.contains_key(&99));
+    }
+
+    #[test]
+    fn uncommitted_lines_have_no_author() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "lib.rs", "fn a() {}\n", "Alice");
+        std::fs::write(dir.path().join("lib.rs"), "fn new() {}\nfn a() {}\n").unwrap();
+
+        let authors = blame_lines(dir.path(), Path::new("lib.rs"), &[1, 2]).unwrap();
+        assert!(!authors.contains_key(&1));
+        assert_eq!(authors[&2].name, "Alice");
+    }
+
+    #[test]
+    fn untracked_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "lib.rs", "fn a() {}\n", "Alice");
+        assert!(blame_lines(dir.path(), Path::new("missing.rs"), &[1]).is_err());
+    }
+}
@@ -0,0 +1,429 @@
+//! On-disk cache of mined commits.
+//!
+//! Old commits never change, so mining them again on every run is wasted
+//! work. The cache in `.argus/history-cache.db` stores each mined commit by
+//! OID together with the tip it was mined from; a later run only walks the
+//! commits added since that tip. If the tip is no longer an ancestor of the
+//! current one (a rebase or force-push), or a wider time window is asked for
+//! than the cache covers, the cache is dropped and rebuilt.
+
+use std::path::Path;
+
+use argus_core::ArgusError;
+use git2::{Oid, Repository, Sort};
+use rusqlite::{params, Connection};
+
+use crate::mining::{
+    commit_info, compute_cutoff, count_diff_files, extract_file_changes, resolve_tip, ChangeStatus,
+    CommitInfo, FileChange, MiningOptions,
+};
+
+/// Commits from [`mine_history_cached`], with where they came from.
+#[derive(Debug, Clone)]
+pub struct CachedHistory {
+    /// Commits in the window, newest first, as [`mine_history`] returns them.
+    ///
+    /// [`mine_history`]: crate::mining::mine_history
+    pub commits: Vec<CommitInfo>,
+    /// Commits in the window that were read from the cache.
+    pub reused: usize,
+    /// Commits in the window that were mined by this run.
+    pub mined: usize,
+}
+
+/// SQLite store of mined commits, keyed by full OID.
+pub struct HistoryCache {
+    conn: Connection,
+}
+
+impl HistoryCache {
+    /// Open or create the cache at `path`, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] if the database cannot be opened.
+    pub fn open(path: &Path) -> Result<Self, ArgusError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ArgusError::Database(format!("failed to create cache directory: {e}"))
+            })?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| ArgusError::Database(format!("failed to open history cache: {e}")))?;
+        conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS metadata (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS commits (
+                id INTEGER PRIMARY KEY,
+                oid TEXT NOT NULL UNIQUE,
+                author TEXT NOT NULL,
+                email TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                message TEXT NOT NULL,
+                parents INTEGER NOT NULL,
+                diff_files INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS changes (
+                commit_id INTEGER NOT NULL REFERENCES commits(id),
+                path TEXT NOT NULL,
+                lines_added INTEGER NOT NULL,
+                lines_deleted INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                renamed_from TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_commits_timestamp ON commits(timestamp);
+            CREATE INDEX IF NOT EXISTS idx_changes_commit ON changes(commit_id);
+            ",
+        )
+        .map_err(|e| ArgusError::Database(format!("failed to create history cache: {e}")))?;
+        Ok(Self { conn })
+    }
+
+    /// Number of cached commits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on query failure.
+    pub fn len(&self) -> Result<usize, ArgusError> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM commits", [], |row| row.get(0))
+            .map_err(|e| ArgusError::Database(format!("failed to count cached commits: {e}")))
+    }
+
+    /// Whether no commits are cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Database`] on query failure.
+    pub fn is_empty(&self) -> Result<bool, ArgusError> {
+        Ok(self.len()? == 0)
+    }
+
+    fn get_metadata(&self, key: &str) -> Result<Option<String>, ArgusError> {
+        match self.conn.query_row(
+            "SELECT value FROM metadata WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ) {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(ArgusError::Database(format!(
+                "failed to read history cache metadata '{key}': {e}"
+            ))),
+        }
+    }
+
+    fn set_metadata(&self, key: &str, value: &str) -> Result<(), ArgusError> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )
+            .map_err(|e| {
+                ArgusError::Database(format!(
+                    "failed to write history cache metadata '{key}': {e}"
+                ))
+            })?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), ArgusError> {
+        self.conn
+            .execute_batch("DELETE FROM changes; DELETE FROM commits; DELETE FROM metadata;")
+            .map_err(|e| ArgusError::Database(format!("failed to clear history cache: {e}")))
+    }
+
+    /// The tip mined up to last time, if the cache still covers `tip` and
+    /// everything since `cutoff`.
+    fn reusable_tip(
+        &self,
+        repo: &Repository,
+        tip: Oid,
+        cutoff: i64,
+    ) -> Result<Option<Oid>, ArgusError> {
+        let cached_tip = self
+            .get_metadata("tip")?
+            .and_then(|oid| Oid::from_str(&oid).ok());
+        let cached_cutoff = self
+            .get_metadata("cutoff")?
+            .and_then(|cutoff| cutoff.parse::<i64>().ok());
+        let (Some(cached_tip), Some(cached_cutoff)) = (cached_tip, cached_cutoff) else {
+            return Ok(None);
+        };
+        let ancestor =
+            cached_tip == tip || repo.graph_descendant_of(tip, cached_tip).unwrap_or(false);
+        Ok((ancestor && cached_cutoff <= cutoff).then_some(cached_tip))
+    }
+
+    /// Cached commits at or after `cutoff`, newest first, with their parent
+    /// and unfiltered diff file counts.
+    fn commits_since(&self, cutoff: i64) -> Result<Vec<(CommitInfo, u32, usize)>, ArgusError> {
+        let db_err =
+            |e: rusqlite::Error| ArgusError::Database(format!("failed to read history cache: {e}"));
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, oid, author, email, timestamp, message, parents, diff_files
+                 FROM commits WHERE timestamp >= ?1 ORDER BY timestamp DESC, id ASC",
+            )
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map(params![cutoff], |row| {
+                let oid: String = row.get(1)?;
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    CommitInfo {
+                        hash: oid[..oid.len().min(8)].to_string(),
+                        author: row.get(2)?,
+                        email: row.get(3)?,
+                        timestamp: row.get(4)?,
+                        message: row.get(5)?,
+                        files_changed: Vec::new(),
+                    },
+                    row.get::<_, u32>(6)?,
+                    row.get::<_, usize>(7)?,
+                ))
+            })
+            .map_err(db_err)?;
+        let commits: Vec<(i64, CommitInfo, u32, usize)> =
+            rows.collect::<Result<_, _>>().map_err(db_err)?;
+
+        let mut changes = self
+            .conn
+            .prepare(
+                "SELECT path, lines_added, lines_deleted, status, renamed_from
+                 FROM changes WHERE commit_id = ?1 ORDER BY rowid",
+            )
+            .map_err(db_err)?;
+        commits
+            .into_iter()
+            .map(|(id, mut commit, parents, diff_files)| {
+                commit.files_changed = changes
+                    .query_map(params![id], |row| {
+                        let status: String = row.get(3)?;
+                        let from: Option<String> = row.get(4)?;
+                        Ok(FileChange {
+                            path: row.get(0)?,
+                            lines_added: row.get(1)?,
+                            lines_deleted: row.get(2)?,
+                            status: match (status.as_str(), from) {
+                                ("added", _) => ChangeStatus::Added,
+                                ("deleted", _) => ChangeStatus::Deleted,
+                                ("renamed", Some(from)) => ChangeStatus::Renamed { from },
+                                _ => ChangeStatus::Modified,
+                            },
+                        })
+                    })
+                    .and_then(|rows| rows.collect())
+                    .map_err(db_err)?;
+                Ok((commit, parents, diff_files))
+            })
+            .collect()
+    }
+}
+
+/// Mine commit history like [`mine_history`], reusing the commits cached
+/// at `cache_path` and caching the new ones.
+///
+/// Only commits added since the cached tip are walked and diffed. The
+/// cache keeps every file change of a commit, so `max_files_per_commit`
+/// can differ between runs; it is applied to the combined result exactly as
+/// [`mine_history`] applies it.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Git`] if the repository cannot be opened or walked,
+/// or [`ArgusError::Database`] if the cache cannot be read or written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_gitpulse::cache::mine_history_cached;
+/// use argus_gitpulse::mining::MiningOptions;
+///
+/// let history = mine_history_cached(
+///     Path::new("."),
+///     &MiningOptions::default(),
+///     Path::new(".argus/history-cache.db"),
+/// )
+/// .unwrap();
+/// println!("{} commits, {} from cache", history.commits.len(), history.reused);
+/// ```
+///
+/// [`mine_history`]: crate::mining::mine_history
+pub fn mine_history_cached(
+    repo_path: &Path,
+    options: &MiningOptions,
+    cache_path: &Path,
+) -> Result<CachedHistory, ArgusError> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| ArgusError::Git(format!("failed to open repository: {e}")))?;
+    let tip = resolve_tip(&repo, options)?;
+    let cutoff = compute_cutoff(options.since_days);
+
+    let mut cache = HistoryCache::open(cache_path)?;
+    let cached_tip = cache.reusable_tip(&repo, tip, cutoff)?;
+    if cached_tip.is_none() {
+        cache.clear()?;
+        cache.set_metadata("cutoff", &cutoff.to_string())?;
+    }
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| ArgusError::Git(format!("failed to create revwalk: {e}")))?;
+    revwalk.set_sorting(Sort::TIME).ok();
+    revwalk
+        .push(tip)
+        .map_err(|e| ArgusError::Git(format!("failed to push oid: {e}")))?;
+    if let Some(cached_tip) = cached_tip {
+        revwalk
+            .hide(cached_tip)
+            .map_err(|e| ArgusError::Git(format!("failed to hide cached commits: {e}")))?;
+    }
+
+    let db_err =
+        |e: rusqlite::Error| ArgusError::Database(format!("failed to write history cache: {e}"));
+    let tx = cache.conn.transaction().map_err(db_err)?;
+    let mut mined = 0;
+    for oid_result in revwalk {
+        let oid = oid_result.map_err(|e| ArgusError::Git(format!("revwalk error: {e}")))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| ArgusError::Git(format!("failed to find commit: {e}")))?;
+        if commit.time().seconds() < cutoff {
+            break;
+        }
+
+        let files_changed = extract_file_changes(&repo, &commit)?;
+        let diff_files = if commit.parent_count() > 1 {
+            count_diff_files(&repo, &commit)?
+        } else {
+            files_changed.len()
+        };
+        let info = commit_info(&commit, files_changed);
+        tx.execute(
+            "INSERT OR REPLACE INTO commits
+             (oid, author, email, timestamp, message, parents, diff_files)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                oid.to_string(),
+                info.author,
+                info.email,
+                info.timestamp,
+                info.message,
+                commit.parent_count(),
+                diff_files,
+            ],
+        )
+        .map_err(db_err)?;
+        let commit_id = tx.last_insert_rowid();
+        for change in &info.files_changed {
+            let (status, from) = match &change.status {
+                ChangeStatus::Added => ("added", None),
+                ChangeStatus::Modified => ("modified", None),
+                ChangeStatus::Deleted => ("deleted", None),
+                ChangeStatus::Renamed { from } => ("renamed", Some(from.as_str())),
+            };
+            tx.execute(
+                "INSERT INTO changes
+                 (commit_id, path, lines_added, lines_deleted, status, renamed_from)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    commit_id,
+                    change.path,
+                    change.lines_added,
+                    change.lines_deleted,
+                    status,
+                    from
+                ],
+            )
+            .map_err(db_err)?;
+        }
+        mined += 1;
+    }
+    tx.commit().map_err(db_err)?;
+    cache.set_metadata("tip", &tip.to_string())?;
+
+    let cached = cache.commits_since(cutoff)?;
+    let reused = cached.len().saturating_sub(mined);
+    let commits = cached
+        .into_iter()
+        .filter(|(commit, parents, diff_files)| {
+            // Same skips as `mine_history`: oversized merges, then large refactors
+            !(*parents > 1 && *diff_files > options.max_files_per_commit)
+                && commit.files_changed.len() <= options.max_files_per_commit
+        })
+        .map(|(commit, _, _)| commit)
+        .collect();
+    Ok(CachedHistory {
+        commits,
+        reused,
+        mined,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mining::mine_history;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, message: &str) -> Oid {
+        let root = repo.workdir().unwrap();
+        std::fs::write(root.join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Alice", "alice@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn second_run_reuses_cached_commits_and_rewrites_invalidate() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.rs", "fn a() {}\n", "add a");
+        commit_file(&repo, "b.rs", "fn b() {}\n", "add b");
+        let cache_path = dir.path().join(".argus/history-cache.db");
+        let options = MiningOptions::default();
+
+        let first = mine_history_cached(dir.path(), &options, &cache_path).unwrap();
+        assert_eq!((first.mined, first.reused), (2, 0));
+
+        commit_file(&repo, "a.rs", "fn a() { 1 }\n", "change a");
+        let second = mine_history_cached(dir.path(), &options, &cache_path).unwrap();
+        assert_eq!((second.mined, second.reused), (1, 2));
+
+        let uncached = mine_history(dir.path(), &options).unwrap();
+        let summary = |commits: &[CommitInfo]| -> Vec<(String, String, Vec<String>)> {
+            let mut rows: Vec<_> = commits
+                .iter()
+                .map(|c| {
+                    let paths = c.files_changed.iter().map(|f| f.path.clone()).collect();
+                    (c.hash.clone(), c.message.clone(), paths)
+                })
+                .collect();
+            rows.sort();
+            rows
+        };
+        assert_eq!(summary(&second.commits), summary(&uncached));
+
+        // Amending the tip rewrites history: the old tip is no longer an ancestor
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        head.amend(Some("HEAD"), None, None, None, Some("change a again"), None)
+            .unwrap();
+        let rewritten = mine_history_cached(dir.path(), &options, &cache_path).unwrap();
+        assert_eq!((rewritten.mined, rewritten.reused), (3, 0));
+        assert_eq!(HistoryCache::open(&cache_path).unwrap().len().unwrap(), 3);
+    }
+}
@@ -0,0 +1,302 @@
+//! CSV rendering for history analyses.
+//!
+//! Each analysis becomes one flat table with a header row, suitable for
+//! spreadsheets. Nested data is flattened: ownership reports the dominant
+//! author per file, and timelines emit one row per monthly bucket.
+
+use crate::coupling::CoupledPair;
+use crate::hotspots::Hotspot;
+use crate::ownership::{FileOwnership, FileOwnershipTimeline};
+use crate::refactor::RefactorCandidate;
+
+/// Render hotspots as CSV.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::csv::hotspots_csv;
+/// use argus_gitpulse::hotspots::Hotspot;
+///
+/// let csv = hotspots_csv(&[Hotspot {
+///     path: "src/main.rs".into(),
+///     revisions: 10,
+///     total_churn: 500,
+///     relative_churn: 2.5,
+///     current_loc: 200,
+///     score: 0.85,
+///     last_modified: 1700000000,
+///     authors: 3,
+/// }]);
+/// assert_eq!(
+///     csv,
+///     "path,score,revisions,total_churn,relative_churn,current_loc,authors,last_modified\n\
+///      src/main.rs,0.85,10,500,2.5,200,3,1700000000\n"
+/// );
+/// ```
+pub fn hotspots_csv(hotspots: &[Hotspot]) -> String {
+    table(
+        &[
+            "path",
+            "score",
+            "revisions",
+            "total_churn",
+            "relative_churn",
+            "current_loc",
+            "authors",
+            "last_modified",
+        ],
+        hotspots.iter().map(|h| {
+            vec![
+                h.path.clone(),
+                h.score.to_string(),
+                h.revisions.to_string(),
+                h.total_churn.to_string(),
+                h.relative_churn.to_string(),
+                h.current_loc.to_string(),
+                h.authors.to_string(),
+                h.last_modified.to_string(),
+            ]
+        }),
+    )
+}
+
+/// Render temporally coupled file pairs as CSV.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::coupling::CoupledPair;
+/// use argus_gitpulse::csv::coupling_csv;
+///
+/// let csv = coupling_csv(&[CoupledPair {
+///     file_a: "src/auth.rs".into(),
+///     file_b: "src/session.rs".into(),
+///     co_changes: 15,
+///     coupling_degree: 0.75,
+///     changes_a: 20,
+///     changes_b: 18,
+/// }]);
+/// assert!(csv.ends_with("src/auth.rs,src/session.rs,0.75,15,20,18\n"));
+/// ```
+pub fn coupling_csv(pairs: &[CoupledPair]) -> String {
+    table(
+        &[
+            "file_a",
+            "file_b",
+            "coupling_degree",
+            "co_changes",
+            "changes_a",
+            "changes_b",
+        ],
+        pairs.iter().map(|p| {
+            vec![
+                p.file_a.clone(),
+                p.file_b.clone(),
+                p.coupling_degree.to_string(),
+                p.co_changes.to_string(),
+                p.changes_a.to_string(),
+                p.changes_b.to_string(),
+            ]
+        }),
+    )
+}
+
+/// Render per-file ownership as CSV, one row per file.
+///
+/// Only the dominant (first) author is included; `authors` counts all of
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::csv::ownership_csv;
+/// use argus_gitpulse::ownership::{AuthorContribution, FileOwnership};
+///
+/// let csv = ownership_csv(&[FileOwnership {
+///     path: "src/lib.rs".into(),
+///     total_commits: 4,
+///     authors: vec![AuthorContribution {
+///         name: "Alice".into(),
+///         email: "alice@example.com".into(),
+///         commits: 4,
+///         ratio: 1.0,
+///     }],
+///     bus_factor: 1,
+///     dominant_author_ratio: 1.0,
+///     is_knowledge_silo: true,
+/// }]);
+/// assert!(csv.ends_with("src/lib.rs,4,1,1,Alice,alice@example.com,1,true\n"));
+/// ```
+pub fn ownership_csv(files: &[FileOwnership]) -> String {
+    table(
+        &[
+            "path",
+            "total_commits",
+            "authors",
+            "bus_factor",
+            "dominant_author",
+            "dominant_author_email",
+            "dominant_author_ratio",
+            "is_knowledge_silo",
+        ],
+        files.iter().map(|f| {
+            let top = f.authors.first();
+            vec![
+                f.path.clone(),
+                f.total_commits.to_string(),
+                f.authors.len().to_string(),
+                f.bus_factor.to_string(),
+                top.map(|a| a.name.clone()).unwrap_or_default(),
+                top.map(|a| a.email.clone()).unwrap_or_default(),
+                f.dominant_author_ratio.to_string(),
+                f.is_knowledge_silo.to_string(),
+            ]
+        }),
+    )
+}
+
+/// Render ownership timelines as CSV, one row per file and month.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::csv::timeline_csv;
+/// use argus_gitpulse::ownership::{FileOwnershipTimeline, OwnershipBucket};
+///
+/// let csv = timeline_csv(&[FileOwnershipTimeline {
+///     path: "src/lib.rs".into(),
+///     buckets: vec![OwnershipBucket {
+///         month: "2024-01".into(),
+///         dominant_author: "Alice".into(),
+///         dominant_commits: 3,
+///         total_commits: 4,
+///         author_count: 2,
+///     }],
+///     handoffs: 0,
+/// }]);
+/// assert!(csv.ends_with("src/lib.rs,2024-01,Alice,3,4,2,0\n"));
+/// ```
+pub fn timeline_csv(timelines: &[FileOwnershipTimeline]) -> String {
+    table(
+        &[
+            "path",
+            "month",
+            "dominant_author",
+            "dominant_commits",
+            "total_commits",
+            "author_count",
+            "handoffs",
+        ],
+        timelines.iter().flat_map(|t| {
+            t.buckets.iter().map(move |b| {
+                vec![
+                    t.path.clone(),
+                    b.month.clone(),
+                    b.dominant_author.clone(),
+                    b.dominant_commits.to_string(),
+                    b.total_commits.to_string(),
+                    b.author_count.to_string(),
+                    t.handoffs.to_string(),
+                ]
+            })
+        }),
+    )
+}
+
+/// Render refactoring candidates as CSV.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::csv::refactor_csv;
+/// use argus_gitpulse::refactor::RefactorCandidate;
+///
+/// let csv = refactor_csv(&[RefactorCandidate {
+///     path: "src/engine.rs".into(),
+///     complexity: 42,
+///     total_churn: 300,
+///     revisions: 12,
+///     authors: 3,
+///     score: 12_600.0,
+/// }]);
+/// assert!(csv.ends_with("src/engine.rs,12600,42,300,12,3\n"));
+/// ```
+pub fn refactor_csv(candidates: &[RefactorCandidate]) -> String {
+    table(
+        &[
+            "path",
+            "score",
+            "complexity",
+            "total_churn",
+            "revisions",
+            "authors",
+        ],
+        candidates.iter().map(|c| {
+            vec![
+                c.path.clone(),
+                c.score.to_string(),
+                c.complexity.to_string(),
+                c.total_churn.to_string(),
+                c.revisions.to_string(),
+                c.authors.to_string(),
+            ]
+        }),
+    )
+}
+
+fn table(header: &[&str], rows: impl Iterator<Item = Vec<String>>) -> String {
+    let mut out = header.join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|f| escape(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break (RFC 4180).
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields_with_delimiters_and_quotes() {
+        assert_eq!(escape("plain.rs"), "plain.rs");
+        assert_eq!(escape("a,b.rs"), "\"a,b.rs\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn empty_input_still_has_header() {
+        assert_eq!(
+            coupling_csv(&[]),
+            "file_a,file_b,coupling_degree,co_changes,changes_a,changes_b\n"
+        );
+    }
+
+    #[test]
+    fn author_names_with_commas_are_quoted() {
+        let csv = timeline_csv(&[FileOwnershipTimeline {
+            path: "src/lib.rs".into(),
+            buckets: vec![crate::ownership::OwnershipBucket {
+                month: "2024-02".into(),
+                dominant_author: "Doe, Jane".into(),
+                dominant_commits: 1,
+                total_commits: 1,
+                author_count: 1,
+            }],
+            handoffs: 1,
+        }]);
+        assert!(csv.ends_with("src/lib.rs,2024-02,\"Doe, Jane\",1,1,1,1\n"));
+    }
+}
@@ -1,10 +1,16 @@
-//! Git history analysis: hotspots, temporal coupling, and knowledge silos.
+//! Git history analysis: hotspots, temporal coupling, knowledge silos,
+//! refactoring candidates, line blame, and CSV export. Mined commits are
+//! cached on disk so repeated runs only walk new history.
 //!
 //! Mines git history using git2 to detect high-churn hotspots, temporally
 //! coupled files, and knowledge silos (bus factor) to identify fragile code
 //! areas that deserve extra review attention.
 
+pub mod blame;
+pub mod cache;
 pub mod coupling;
+pub mod csv;
 pub mod hotspots;
 pub mod mining;
 pub mod ownership;
+pub mod refactor;
@@ -3,10 +3,11 @@
 //! Mines commit history from a repository, extracting per-commit
 //! file changes with line counts, author info, and timestamps.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use argus_core::ArgusError;
-use git2::{Delta, DiffOptions, Repository, Sort};
+use git2::{Delta, DiffOptions, Oid, Repository, Sort};
 
 /// Raw commit data extracted from git history.
 ///
@@ -155,23 +156,9 @@ pub fn mine_history(
         .map_err(|e| ArgusError::Git(format!("failed to create revwalk: {e}")))?;
 
     revwalk.set_sorting(Sort::TIME).ok();
-
-    // Start from HEAD or specified branch
-    if let Some(ref branch) = options.branch {
-        let reference = repo
-            .resolve_reference_from_short_name(branch)
-            .map_err(|e| ArgusError::Git(format!("failed to resolve branch '{branch}': {e}")))?;
-        let oid = reference
-            .target()
-            .ok_or_else(|| ArgusError::Git("branch has no target".into()))?;
-        revwalk
-            .push(oid)
-            .map_err(|e| ArgusError::Git(format!("failed to push oid: {e}")))?;
-    } else {
-        revwalk
-            .push_head()
-            .map_err(|e| ArgusError::Git(format!("failed to push HEAD: {e}")))?;
-    }
+    revwalk
+        .push(resolve_tip(&repo, options)?)
+        .map_err(|e| ArgusError::Git(format!("failed to push oid: {e}")))?;
 
     let cutoff = compute_cutoff(options.since_days);
     let mut commits = Vec::new();
@@ -205,29 +192,187 @@ pub fn mine_history(
             continue;
         }
 
-        let author = commit.author();
-        let hash = oid.to_string();
-
-        commits.push(CommitInfo {
-            hash: hash[..hash.len().min(8)].to_string(),
-            author: author.name().unwrap_or("unknown").to_string(),
-            email: author.email().unwrap_or("unknown").to_string(),
-            timestamp,
-            message: commit
-                .message()
-                .unwrap_or("")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .to_string(),
-            files_changed,
-        });
+        commits.push(commit_info(&commit, files_changed));
     }
 
     Ok(commits)
 }
 
-fn compute_cutoff(since_days: u64) -> i64 {
+/// The [`CommitInfo`] of `commit` with its already extracted changes.
+pub(crate) fn commit_info(commit: &git2::Commit, files_changed: Vec<FileChange>) -> CommitInfo {
+    let author = commit.author();
+    let hash = commit.id().to_string();
+
+    CommitInfo {
+        hash: hash[..hash.len().min(8)].to_string(),
+        author: author.name().unwrap_or("unknown").to_string(),
+        email: author.email().unwrap_or("unknown").to_string(),
+        timestamp: commit.time().seconds(),
+        message: commit
+            .message()
+            .unwrap_or("")
+            .lines()
+            .next()
+            .unwrap_or("")
+            .to_string(),
+        files_changed,
+    }
+}
+
+/// How much history an analysis had to work with.
+///
+/// Hotspots, coupling, and ownership computed from a handful of commits
+/// (a new project, or a shallow clone) look as authoritative as those from
+/// years of history. This records the commit count and whether the clone
+/// is shallow, so callers can flag such results as low-confidence.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::mining::HistoryDepth;
+///
+/// let depth = HistoryDepth { commits: 3, min_commits: 10, shallow: true };
+/// assert!(depth.is_low_confidence());
+/// let warning = depth.warning().unwrap();
+/// assert!(warning.contains("3 commits found"));
+/// assert!(warning.contains("git fetch --unshallow"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryDepth {
+    /// Commits mined.
+    pub commits: usize,
+    /// Fewest commits considered enough for reliable results.
+    pub min_commits: usize,
+    /// Whether the repository is a shallow clone.
+    pub shallow: bool,
+}
+
+impl HistoryDepth {
+    /// Describe the history mined from the repository at `repo_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Git`] if the repository cannot be opened.
+    pub fn of(repo_path: &Path, commits: usize, min_commits: usize) -> Result<Self, ArgusError> {
+        let repo = Repository::discover(repo_path)
+            .map_err(|e| ArgusError::Git(format!("failed to open repository: {e}")))?;
+        Ok(Self {
+            commits,
+            min_commits,
+            shallow: repo.is_shallow(),
+        })
+    }
+
+    /// Whether too few commits were mined for the results to be reliable.
+    pub fn is_low_confidence(&self) -> bool {
+        self.commits < self.min_commits
+    }
+
+    /// A warning to show alongside the results, if any.
+    ///
+    /// Sparse history gets an "insufficient history" warning; a shallow
+    /// clone additionally (or on its own) gets a hint to fetch the full
+    /// history.
+    pub fn warning(&self) -> Option<String> {
+        let unshallow = "run `git fetch --unshallow` to fetch the full history";
+        match (self.is_low_confidence(), self.shallow) {
+            (true, shallow) => Some(format!(
+                "insufficient history for reliable analysis; {} commit{} found (at least {} recommended){}",
+                self.commits,
+                if self.commits == 1 { "" } else { "s" },
+                self.min_commits,
+                if shallow {
+                    format!(". This is a shallow clone: {unshallow}")
+                } else {
+                    String::new()
+                }
+            )),
+            (false, true) => Some(format!(
+                "shallow clone: commits before the clone depth are missing; {unshallow}"
+            )),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Restrict mined history to the `max_files` most frequently changed files.
+///
+/// File changes outside the kept set are removed from each commit (ties
+/// broken by path), and commits left with no changes are dropped. Returns
+/// the number of distinct files dropped.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::mining::{cap_files, ChangeStatus, CommitInfo, FileChange};
+///
+/// let change = |path: &str| FileChange {
+///     path: path.into(),
+///     lines_added: 1,
+///     lines_deleted: 0,
+///     status: ChangeStatus::Modified,
+/// };
+/// let commit = |files: Vec<FileChange>| CommitInfo {
+///     hash: "abc".into(),
+///     author: "Alice".into(),
+///     email: "alice@example.com".into(),
+///     timestamp: 0,
+///     message: "change".into(),
+///     files_changed: files,
+/// };
+/// let mut commits = vec![
+///     commit(vec![change("hot.rs"), change("cold.rs")]),
+///     commit(vec![change("hot.rs")]),
+/// ];
+/// assert_eq!(cap_files(&mut commits, 1), 1);
+/// assert_eq!(commits[0].files_changed.len(), 1);
+/// assert_eq!(commits[0].files_changed[0].path, "hot.rs");
+/// ```
+pub fn cap_files(commits: &mut Vec<CommitInfo>, max_files: usize) -> usize {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for commit in commits.iter() {
+        for file in &commit.files_changed {
+            *counts.entry(file.path.clone()).or_default() += 1;
+        }
+    }
+    if counts.len() <= max_files {
+        return 0;
+    }
+
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let dropped = ranked.len() - max_files;
+    let keep: HashSet<String> = ranked
+        .into_iter()
+        .take(max_files)
+        .map(|(path, _)| path)
+        .collect();
+
+    for commit in commits.iter_mut() {
+        commit.files_changed.retain(|f| keep.contains(&f.path));
+    }
+    commits.retain(|c| !c.files_changed.is_empty());
+    dropped
+}
+
+/// The commit history is mined from: HEAD or the tip of `options.branch`.
+pub(crate) fn resolve_tip(repo: &Repository, options: &MiningOptions) -> Result<Oid, ArgusError> {
+    if let Some(ref branch) = options.branch {
+        let reference = repo
+            .resolve_reference_from_short_name(branch)
+            .map_err(|e| ArgusError::Git(format!("failed to resolve branch '{branch}': {e}")))?;
+        reference
+            .target()
+            .ok_or_else(|| ArgusError::Git("branch has no target".into()))
+    } else {
+        repo.head()
+            .ok()
+            .and_then(|head| head.target())
+            .ok_or_else(|| ArgusError::Git("failed to resolve HEAD".into()))
+    }
+}
+
+pub(crate) fn compute_cutoff(since_days: u64) -> i64 {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
@@ -235,7 +380,10 @@ fn compute_cutoff(since_days: u64) -> i64 {
     now - (since_days as i64 * 86400)
 }
 
-fn count_diff_files(repo: &Repository, commit: &git2::Commit) -> Result<usize, ArgusError> {
+pub(crate) fn count_diff_files(
+    repo: &Repository,
+    commit: &git2::Commit,
+) -> Result<usize, ArgusError> {
     let commit_tree = commit
         .tree()
         .map_err(|e| ArgusError::Git(format!("failed to get commit tree: {e}")))?;
@@ -265,7 +413,7 @@ fn count_diff_files(repo: &Repository, commit: &git2::Commit) -> Result<usize, A
     Ok(diff.deltas().len())
 }
 
-fn extract_file_changes(
+pub(crate) fn extract_file_changes(
     repo: &Repository,
     commit: &git2::Commit,
 ) -> Result<Vec<FileChange>, ArgusError> {
@@ -409,6 +557,26 @@ mod tests {
         assert!(opts.branch.is_none());
     }
 
+    #[test]
+    fn history_depth_warns_on_sparse_or_shallow_history() {
+        let depth = |commits, shallow| HistoryDepth {
+            commits,
+            min_commits: 10,
+            shallow,
+        };
+
+        assert_eq!(depth(10, false).warning(), None);
+        assert!(!depth(10, false).is_low_confidence());
+
+        let sparse = depth(1, false).warning().unwrap();
+        assert!(sparse.contains("1 commit found"), "{sparse}");
+        assert!(!sparse.contains("unshallow"), "{sparse}");
+
+        let shallow = depth(50, true).warning().unwrap();
+        assert!(shallow.starts_with("shallow clone"), "{shallow}");
+        assert!(!depth(50, true).is_low_confidence());
+    }
+
     #[test]
     fn mine_argus_repo_returns_commits() {
         // Find the repo root (this test runs from crate dir or workspace root)
@@ -461,6 +629,40 @@ mod tests {
         assert_ne!(renamed, ChangeStatus::Modified);
     }
 
+    #[test]
+    fn cap_files_keeps_most_changed_and_drops_empty_commits() {
+        let change = |path: &str| FileChange {
+            path: path.into(),
+            lines_added: 1,
+            lines_deleted: 0,
+            status: ChangeStatus::Modified,
+        };
+        let commit = |hash: &str, files: Vec<FileChange>| CommitInfo {
+            hash: hash.into(),
+            author: "alice".into(),
+            email: "alice@example.com".into(),
+            timestamp: 0,
+            message: "change".into(),
+            files_changed: files,
+        };
+        let mut commits = vec![
+            commit("1", vec![change("a.rs"), change("b.rs")]),
+            commit("2", vec![change("b.rs"), change("c.rs")]),
+            commit("3", vec![change("d.rs")]),
+        ];
+
+        assert_eq!(cap_files(&mut commits, 2), 2);
+        let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["1", "2"]);
+        let paths: Vec<&str> = commits
+            .iter()
+            .flat_map(|c| c.files_changed.iter().map(|f| f.path.as_str()))
+            .collect();
+        assert_eq!(paths, vec!["a.rs", "b.rs", "b.rs"]);
+
+        assert_eq!(cap_files(&mut commits, 10), 0);
+    }
+
     fn find_repo_root() -> Option<std::path::PathBuf> {
         let mut path = std::env::current_dir().ok()?;
         loop {
@@ -4,7 +4,7 @@
 //! knowledge silos (files dominated by a single author) and compute
 //! the project bus factor.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use argus_core::ArgusError;
 use serde::{Deserialize, Serialize};
@@ -215,6 +215,170 @@ pub fn analyze_ownership(commits: &[CommitInfo]) -> Result<OwnershipSummary, Arg
     })
 }
 
+/// Dominant author for one calendar month of a file's history.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::ownership::OwnershipBucket;
+///
+/// let bucket = OwnershipBucket {
+///     month: "2024-03".into(),
+///     dominant_author: "alice@example.com".into(),
+///     dominant_commits: 4,
+///     total_commits: 5,
+///     author_count: 2,
+/// };
+/// assert_eq!(bucket.month, "2024-03");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipBucket {
+    /// Month in `YYYY-MM` form (UTC).
+    pub month: String,
+    /// Email of the author with the most commits in this month.
+    pub dominant_author: String,
+    /// Commits by the dominant author in this month.
+    pub dominant_commits: u32,
+    /// Total commits touching the file in this month.
+    pub total_commits: u32,
+    /// Number of distinct authors in this month.
+    pub author_count: u32,
+}
+
+/// Month-by-month ownership history for a single file.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::ownership::FileOwnershipTimeline;
+///
+/// let timeline = FileOwnershipTimeline {
+///     path: "src/main.rs".into(),
+///     buckets: vec![],
+///     handoffs: 0,
+/// };
+/// assert_eq!(timeline.handoffs, 0);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileOwnershipTimeline {
+    /// File path relative to repo root.
+    pub path: String,
+    /// Monthly buckets in chronological order. Months without commits are omitted.
+    pub buckets: Vec<OwnershipBucket>,
+    /// Number of times the dominant author changed between consecutive buckets.
+    pub handoffs: u32,
+}
+
+/// Bucket each file's commits by month and report the dominant author per bucket.
+///
+/// Files are sorted by handoff count (descending), then by path, so files
+/// whose ownership is drifting come first.
+///
+/// # Errors
+///
+/// Returns [`ArgusError`] on processing failure.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::ownership::analyze_ownership_timeline;
+/// use argus_gitpulse::mining::{CommitInfo, FileChange, ChangeStatus};
+///
+/// let commit = |email: &str, timestamp: i64| CommitInfo {
+///     hash: "abc".into(),
+///     author: email.into(),
+///     email: email.into(),
+///     timestamp,
+///     message: "change".into(),
+///     files_changed: vec![
+///         FileChange { path: "main.rs".into(), lines_added: 1, lines_deleted: 0, status: ChangeStatus::Modified },
+///     ],
+/// };
+/// // 2024-01-15 and 2024-02-15
+/// let commits = vec![commit("alice@e.com", 1_705_276_800), commit("bob@e.com", 1_707_955_200)];
+/// let timelines = analyze_ownership_timeline(&commits).unwrap();
+/// assert_eq!(timelines[0].buckets.len(), 2);
+/// assert_eq!(timelines[0].buckets[0].month, "2024-01");
+/// assert_eq!(timelines[0].handoffs, 1);
+/// ```
+pub fn analyze_ownership_timeline(
+    commits: &[CommitInfo],
+) -> Result<Vec<FileOwnershipTimeline>, ArgusError> {
+    // Key: file path, Value: month -> (author email -> commit count)
+    let mut file_months: HashMap<String, BTreeMap<String, HashMap<String, u32>>> = HashMap::new();
+
+    for commit in commits {
+        let month = month_key(commit.timestamp);
+        for file in &commit.files_changed {
+            *file_months
+                .entry(file.path.clone())
+                .or_default()
+                .entry(month.clone())
+                .or_default()
+                .entry(commit.email.clone())
+                .or_default() += 1;
+        }
+    }
+
+    let mut timelines = Vec::with_capacity(file_months.len());
+    for (path, months) in file_months {
+        let mut buckets = Vec::with_capacity(months.len());
+        for (month, authors) in months {
+            // Ties are broken by email so the output is deterministic
+            let Some((dominant_author, dominant_commits)) = authors
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(email, count)| (email.clone(), *count))
+            else {
+                continue;
+            };
+            buckets.push(OwnershipBucket {
+                month,
+                dominant_author,
+                dominant_commits,
+                total_commits: authors.values().sum(),
+                author_count: authors.len() as u32,
+            });
+        }
+
+        let handoffs = buckets
+            .windows(2)
+            .filter(|w| w[0].dominant_author != w[1].dominant_author)
+            .count() as u32;
+
+        timelines.push(FileOwnershipTimeline {
+            path,
+            buckets,
+            handoffs,
+        });
+    }
+
+    timelines.sort_by(|a, b| {
+        b.handoffs
+            .cmp(&a.handoffs)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    Ok(timelines)
+}
+
+/// Format a unix timestamp as a `YYYY-MM` month key (UTC).
+fn month_key(timestamp: i64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = timestamp.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}")
+}
+
 /// Compute the project bus factor.
 ///
 /// Iteratively remove the top contributor until >50% of files lose
@@ -289,6 +453,13 @@ mod tests {
         }
     }
 
+    fn make_commit_at(email: &str, timestamp: i64, files: Vec<&str>) -> CommitInfo {
+        CommitInfo {
+            timestamp,
+            ..make_commit(email, email, files)
+        }
+    }
+
     #[test]
     fn single_author_file_is_knowledge_silo() {
         let commits = vec![
@@ -355,4 +526,54 @@ mod tests {
         // Removing any 2 authors orphans >50% of files (2 out of 3)
         assert_eq!(summary.project_bus_factor, 2);
     }
+
+    #[test]
+    fn month_key_conversion() {
+        assert_eq!(month_key(0), "1970-01");
+        // 2024-02-29T23:59:59Z
+        assert_eq!(month_key(1_709_251_199), "2024-02");
+        // 2024-03-01T00:00:00Z
+        assert_eq!(month_key(1_709_251_200), "2024-03");
+        // 1969-12-31T23:59:59Z
+        assert_eq!(month_key(-1), "1969-12");
+    }
+
+    #[test]
+    fn timeline_tracks_handoff_between_authors() {
+        // Jan and Feb 2024: alice; Mar 2024: bob takes over
+        let commits = vec![
+            make_commit_at("alice@e.com", 1_705_276_800, vec!["main.rs"]),
+            make_commit_at("alice@e.com", 1_705_363_200, vec!["main.rs"]),
+            make_commit_at("bob@e.com", 1_705_449_600, vec!["main.rs"]),
+            make_commit_at("alice@e.com", 1_707_955_200, vec!["main.rs"]),
+            make_commit_at("bob@e.com", 1_710_460_800, vec!["main.rs", "lib.rs"]),
+        ];
+
+        let timelines = analyze_ownership_timeline(&commits).unwrap();
+        assert_eq!(timelines.len(), 2);
+
+        let main = &timelines[0];
+        assert_eq!(main.path, "main.rs");
+        assert_eq!(main.handoffs, 1);
+        let months: Vec<&str> = main.buckets.iter().map(|b| b.month.as_str()).collect();
+        assert_eq!(months, vec!["2024-01", "2024-02", "2024-03"]);
+
+        let jan = &main.buckets[0];
+        assert_eq!(jan.dominant_author, "alice@e.com");
+        assert_eq!(jan.dominant_commits, 2);
+        assert_eq!(jan.total_commits, 3);
+        assert_eq!(jan.author_count, 2);
+        assert_eq!(main.buckets[2].dominant_author, "bob@e.com");
+
+        let lib = &timelines[1];
+        assert_eq!(lib.path, "lib.rs");
+        assert_eq!(lib.handoffs, 0);
+        assert_eq!(lib.buckets.len(), 1);
+    }
+
+    #[test]
+    fn timeline_empty_history() {
+        let timelines = analyze_ownership_timeline(&[]).unwrap();
+        assert!(timelines.is_empty());
+    }
 }
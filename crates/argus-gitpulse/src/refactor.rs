@@ -0,0 +1,171 @@
+//! Refactoring candidates: files that are both complex and churny.
+//!
+//! Joins current per-file cyclomatic complexity with churn mined from
+//! history and ranks files by `complexity × churn`, the classic
+//! "hotspot × complexity" quadrant analysis.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::mining::CommitInfo;
+
+/// A file ranked for refactoring by complexity and churn.
+///
+/// # Examples
+///
+/// ```
+/// use argus_gitpulse::refactor::RefactorCandidate;
+///
+/// let c = RefactorCandidate {
+///     path: "src/engine.rs".into(),
+///     complexity: 42,
+///     total_churn: 300,
+///     revisions: 12,
+///     authors: 3,
+///     score: 12_600.0,
+/// };
+/// assert_eq!(c.score, 42.0 * 300.0);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefactorCandidate {
+    /// File path relative to repo root.
+    pub path: String,
+    /// Current cyclomatic complexity of the file.
+    pub complexity: u32,
+    /// Total lines added + deleted across all commits.
+    pub total_churn: u64,
+    /// Number of commits touching this file.
+    pub revisions: u32,
+    /// Number of distinct authors.
+    pub authors: u32,
+    /// `complexity × total_churn`.
+    pub score: f64,
+}
+
+/// Rank files by `complexity × churn`.
+///
+/// `complexity` maps repo-relative paths to their current cyclomatic
+/// complexity. Files missing from it (deleted, or without a grammar) and
+/// files with no churn are skipped. Results are sorted by score
+/// descending, then by path.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use argus_gitpulse::mining::{CommitInfo, FileChange, ChangeStatus};
+/// use argus_gitpulse::refactor::rank_refactor_candidates;
+///
+/// let commit = CommitInfo {
+///     hash: "abc".into(),
+///     author: "Alice".into(),
+///     email: "alice@example.com".into(),
+///     timestamp: 1700000000,
+///     message: "change".into(),
+///     files_changed: vec![FileChange {
+///         path: "src/lib.rs".into(),
+///         lines_added: 10,
+///         lines_deleted: 5,
+///         status: ChangeStatus::Modified,
+///     }],
+/// };
+/// let complexity = HashMap::from([("src/lib.rs".to_string(), 4)]);
+/// let ranked = rank_refactor_candidates(&[commit], &complexity);
+/// assert_eq!(ranked[0].score, 60.0);
+/// ```
+pub fn rank_refactor_candidates(
+    commits: &[CommitInfo],
+    complexity: &HashMap<String, u32>,
+) -> Vec<RefactorCandidate> {
+    let mut churn: HashMap<&str, u64> = HashMap::new();
+    let mut revisions: HashMap<&str, u32> = HashMap::new();
+    let mut authors: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+    for commit in commits {
+        for file in &commit.files_changed {
+            let path = file.path.as_str();
+            *churn.entry(path).or_default() += file.lines_added + file.lines_deleted;
+            *revisions.entry(path).or_default() += 1;
+            authors
+                .entry(path)
+                .or_default()
+                .insert(commit.author.as_str());
+        }
+    }
+
+    let mut candidates: Vec<RefactorCandidate> = churn
+        .into_iter()
+        .filter(|(_, total_churn)| *total_churn > 0)
+        .filter_map(|(path, total_churn)| {
+            let complexity = *complexity.get(path)?;
+            Some(RefactorCandidate {
+                path: path.to_string(),
+                complexity,
+                total_churn,
+                revisions: revisions.get(path).copied().unwrap_or(0),
+                authors: authors.get(path).map_or(0, |a| a.len() as u32),
+                score: complexity as f64 * total_churn as f64,
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mining::{ChangeStatus, FileChange};
+
+    fn commit(author: &str, files: &[(&str, u64)]) -> CommitInfo {
+        CommitInfo {
+            hash: format!("{author}-{}", files.len()),
+            author: author.into(),
+            email: format!("{author}@example.com"),
+            timestamp: 1_700_000_000,
+            message: "change".into(),
+            files_changed: files
+                .iter()
+                .map(|(path, added)| FileChange {
+                    path: (*path).into(),
+                    lines_added: *added,
+                    lines_deleted: 0,
+                    status: ChangeStatus::Modified,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn ranks_by_complexity_times_churn() {
+        let commits = vec![
+            commit("alice", &[("simple.rs", 100), ("tangled.rs", 20)]),
+            commit("bob", &[("tangled.rs", 20)]),
+        ];
+        let complexity =
+            HashMap::from([("simple.rs".to_string(), 2), ("tangled.rs".to_string(), 30)]);
+
+        let ranked = rank_refactor_candidates(&commits, &complexity);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].path, "tangled.rs");
+        assert_eq!(ranked[0].score, 1200.0);
+        assert_eq!(ranked[0].revisions, 2);
+        assert_eq!(ranked[0].authors, 2);
+        assert_eq!(ranked[1].path, "simple.rs");
+        assert_eq!(ranked[1].score, 200.0);
+    }
+
+    #[test]
+    fn skips_files_without_complexity_or_churn() {
+        let commits = vec![commit("alice", &[("deleted.rs", 50), ("untouched.rs", 0)])];
+        let complexity = HashMap::from([("untouched.rs".to_string(), 10)]);
+        assert!(rank_refactor_candidates(&commits, &complexity).is_empty());
+    }
+}
@@ -11,7 +11,7 @@
 //! use std::path::PathBuf;
 //!
 //! # async fn example() -> Result<(), argus_core::ArgusError> {
-//! argus_mcp::server::run_server(PathBuf::from(".")).await?;
+//! argus_mcp::server::run_server(PathBuf::from("."), false).await?;
 //! # Ok(())
 //! # }
 //! ```
@@ -40,7 +40,8 @@ impl ServerHandler for ArgusServer {
 /// Start the MCP server on stdio transport.
 ///
 /// This is called by the `argus mcp` CLI subcommand. It blocks until
-/// the client closes stdin.
+/// the client closes stdin. With `quiet`, tool responses leave out
+/// warnings.
 ///
 /// # Errors
 ///
@@ -53,12 +54,12 @@ impl ServerHandler for ArgusServer {
 /// use std::path::PathBuf;
 ///
 /// # async fn example() -> Result<(), argus_core::ArgusError> {
-/// argus_mcp::server::run_server(PathBuf::from(".")).await?;
+/// argus_mcp::server::run_server(PathBuf::from("."), false).await?;
 /// # Ok(())
 /// # }
 /// ```
-pub async fn run_server(repo_path: PathBuf) -> Result<(), ArgusError> {
-    let server = ArgusServer::new(repo_path);
+pub async fn run_server(repo_path: PathBuf, quiet: bool) -> Result<(), ArgusError> {
+    let server = ArgusServer::new(repo_path).with_quiet(quiet);
     let service = server
         .serve(stdio())
         .await
@@ -4,7 +4,7 @@
 //! `get_hotspots`, and `get_history`. Each delegates to the appropriate Argus
 //! crate and returns JSON via `CallToolResult`.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
@@ -26,6 +26,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone)]
 pub struct ArgusServer {
     pub(crate) repo_path: PathBuf,
+    pub(crate) quiet: bool,
     pub(crate) tool_router: ToolRouter<Self>,
 }
 
@@ -171,10 +172,18 @@ impl ArgusServer {
     pub fn new(repo_path: PathBuf) -> Self {
         Self {
             repo_path,
+            quiet: false,
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Leave warnings, such as the shallow-history one, out of tool
+    /// responses (`argus --quiet mcp`).
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
     fn resolve_path(&self, path: &Option<String>) -> Result<PathBuf, McpError> {
         let canonical_repo_path = self.repo_path.canonicalize().map_err(|e| {
             mcp_err(format!(
@@ -290,12 +299,24 @@ impl ArgusServer {
             ))
             })?;
 
-        let code_index = argus_codelens::store::CodeIndex::open(&index_path).map_err(|e| {
-            mcp_err(format!(
-                "Failed to open index at {}: {e}",
-                index_path.display()
-            ))
-        })?;
+        // Keyword queries leave out the repository's configured stop words
+        let stop_words = match argus_core::ArgusConfig::discover(&repo_path) {
+            Some(path) => {
+                argus_core::ArgusConfig::from_file(&path)
+                    .map_err(|e| mcp_err(format!("Invalid config {}: {e}", path.display())))?
+                    .embedding
+                    .fts_stop_words
+            }
+            None => Vec::new(),
+        };
+        let code_index = argus_codelens::store::CodeIndex::open(&index_path)
+            .map_err(|e| {
+                mcp_err(format!(
+                    "Failed to open index at {}: {e}",
+                    index_path.display()
+                ))
+            })?
+            .with_stop_words(stop_words);
 
         // HybridSearch is Send but not Sync (rusqlite Connection uses RefCell).
         // Move it into a blocking task and use Handle::block_on for the async parts.
@@ -307,11 +328,18 @@ impl ArgusServer {
                 let stats = search.index().stats().map_err(|e| mcp_err(e.to_string()))?;
                 let mut indexed = false;
                 if stats.total_chunks == 0 {
-                    search
-                        .index_repo(&repo_path)
-                        .await
+                    // Concurrent calls (or an `argus search --index`) must not
+                    // index the same repository twice
+                    let _lock = argus_codelens::store::IndexLock::acquire(&index_path)
                         .map_err(|e| mcp_err(format!("Failed to index repository: {e}")))?;
-                    indexed = true;
+                    let stats = search.index().stats().map_err(|e| mcp_err(e.to_string()))?;
+                    if stats.total_chunks == 0 {
+                        search
+                            .index_repo(&repo_path)
+                            .await
+                            .map_err(|e| mcp_err(format!("Failed to index repository: {e}")))?;
+                        indexed = true;
+                    }
                 }
 
                 let results = search
@@ -421,7 +449,7 @@ impl ArgusServer {
             .map(|h| serde_json::to_value(h).unwrap_or_default())
             .collect();
 
-        let summary = if top.is_empty() {
+        let mut summary = if top.is_empty() {
             format!("No hotspots found in the last {since_days} days.")
         } else {
             let top_three: Vec<String> = top
@@ -435,6 +463,9 @@ impl ArgusServer {
                 top_three.join(", ")
             )
         };
+        if let Some(warning) = self.history_warning(&repo_path, commits.len()) {
+            summary.push_str(&format!(" Warning: {warning}."));
+        }
 
         let response = HotspotsResponse {
             hotspots: hotspot_values,
@@ -509,6 +540,10 @@ impl ArgusServer {
             None
         };
 
+        if let Some(warning) = self.history_warning(&repo_path, commits.len()) {
+            summary_parts.push(format!("Warning: {warning}."));
+        }
+
         let response = HistoryResponse {
             coupling,
             ownership,
@@ -520,6 +555,20 @@ impl ArgusServer {
     }
 }
 
+impl ArgusServer {
+    /// Low-confidence or shallow-clone warning for mined history, using the
+    /// default `[history] min_commits`. Always `None` when quiet.
+    fn history_warning(&self, repo_path: &Path, commits: usize) -> Option<String> {
+        if self.quiet {
+            return None;
+        }
+        let min_commits = argus_core::HistoryConfig::default().min_commits;
+        argus_gitpulse::mining::HistoryDepth::of(repo_path, commits, min_commits)
+            .ok()?
+            .warning()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -575,4 +624,20 @@ mod tests {
 
         assert!(err.message.contains("outside the configured repository"));
     }
+
+    #[test]
+    fn quiet_server_leaves_out_history_warnings() {
+        let repo = tempfile::tempdir().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let server = ArgusServer::new(repo.path().to_path_buf());
+        assert!(server.history_warning(repo.path(), 2).is_some());
+        let server = server.with_quiet(true);
+        assert!(server.history_warning(repo.path(), 2).is_none());
+    }
 }
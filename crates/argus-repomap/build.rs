@@ -0,0 +1,32 @@
+//! Exposes the locked version of each tree-sitter grammar crate to the
+//! parser self-test as `ARGUS_GRAMMAR_<CRATE>` (e.g.
+//! `ARGUS_GRAMMAR_TREE_SITTER_RUST`).
+//!
+//! The versions come from the workspace `Cargo.lock`, which Cargo resolves
+//! before running build scripts. Outside the workspace, such as when built
+//! from crates.io, no variables are set.
+
+use std::path::Path;
+
+fn main() {
+    let lock = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+    let Ok(text) = std::fs::read_to_string(&lock) else {
+        return;
+    };
+
+    let mut name = None;
+    for line in text.lines() {
+        if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"'));
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            if let Some(crate_name) = name.take().filter(|n| n.starts_with("tree-sitter-")) {
+                println!(
+                    "cargo:rustc-env=ARGUS_GRAMMAR_{}={}",
+                    crate_name.to_uppercase().replace('-', "_"),
+                    value.trim_matches('"')
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,750 @@
+//! Cyclomatic complexity measured on the tree-sitter AST.
+//!
+//! Counts decision points (branches, loops, match/switch arms, catch
+//! clauses, ternaries, and short-circuit boolean operators) across all
+//! supported languages. A file with no decision points has complexity 1.
+//! Functions are measured the same way, each without the functions nested
+//! in it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use argus_core::ArgusError;
+use serde::Serialize;
+use tree_sitter::{Node, Parser, Tree};
+
+use crate::walker::{Language, SourceFile};
+
+/// Cyclomatic complexity of a single source file.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::complexity::FileComplexity;
+///
+/// let c = FileComplexity {
+///     path: PathBuf::from("src/lib.rs"),
+///     complexity: 4,
+///     loc: 30,
+/// };
+/// assert_eq!(c.complexity, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileComplexity {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// 1 + the number of decision points in the file.
+    pub complexity: u32,
+    /// Number of lines in the file.
+    pub loc: u32,
+}
+
+/// Measure the cyclomatic complexity of a parsed source file.
+///
+/// Returns `None` when the language has no tree-sitter grammar or the
+/// file cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::complexity::file_complexity;
+/// use argus_repomap::walker::{Language, SourceFile};
+///
+/// let file = SourceFile {
+///     path: PathBuf::from("lib.rs"),
+///     language: Language::Rust,
+///     content: "fn f(x: i32) -> i32 { if x > 0 && x < 10 { 1 } else { 0 } }".into(),
+/// };
+/// let c = file_complexity(&file).unwrap();
+/// assert_eq!(c.complexity, 3);
+/// ```
+pub fn file_complexity(file: &SourceFile) -> Option<FileComplexity> {
+    let tree = parse(file)?;
+    Some(measure_file(file, &tree))
+}
+
+/// Cyclomatic complexity of a single function or method.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::complexity::FunctionComplexity;
+///
+/// let c = FunctionComplexity {
+///     path: PathBuf::from("src/lib.rs"),
+///     name: "parse".into(),
+///     line: 12,
+///     complexity: 7,
+/// };
+/// assert_eq!(c.to_string(), "src/lib.rs:12 parse");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionComplexity {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Function or method name as written at its definition.
+    pub name: String,
+    /// Line where the definition starts (1-indexed).
+    pub line: u32,
+    /// 1 + the number of decision points in the body, not counting
+    /// functions nested in it (closures and lambdas are counted).
+    pub complexity: u32,
+}
+
+impl fmt::Display for FunctionComplexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} {}", self.path.display(), self.line, self.name)
+    }
+}
+
+/// Measure the cyclomatic complexity of each named function and method in
+/// a source file, in source order.
+///
+/// Returns an empty list when the language has no tree-sitter grammar or
+/// the file cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::complexity::function_complexity;
+/// use argus_repomap::walker::{Language, SourceFile};
+///
+/// let file = SourceFile {
+///     path: PathBuf::from("lib.rs"),
+///     language: Language::Rust,
+///     content: "fn flat() {}\nfn branchy(x: i32) { if x > 0 {} else if x < 0 {} }".into(),
+/// };
+/// let functions = function_complexity(&file);
+/// assert_eq!(functions[0].name, "flat");
+/// assert_eq!(functions[0].complexity, 1);
+/// assert_eq!(functions[1].complexity, 3);
+/// ```
+pub fn function_complexity(file: &SourceFile) -> Vec<FunctionComplexity> {
+    let Some(tree) = parse(file) else {
+        return Vec::new();
+    };
+    let mut functions = Vec::new();
+    collect_functions(tree.root_node(), file, &mut functions);
+    functions
+}
+
+fn parse(file: &SourceFile) -> Option<Tree> {
+    let ts_language = file.language.tree_sitter_language()?;
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    parser.parse(&file.content, None)
+}
+
+fn measure_file(file: &SourceFile, tree: &Tree) -> FileComplexity {
+    FileComplexity {
+        path: file.path.clone(),
+        complexity: 1 + count_decision_points(tree.root_node(), file.language),
+        loc: file.content.lines().count() as u32,
+    }
+}
+
+/// Per-file and per-function complexity of a repository, worst first.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::complexity::{ComplexityReport, FunctionComplexity};
+///
+/// let function = |name: &str, complexity| FunctionComplexity {
+///     path: PathBuf::from("src/lib.rs"),
+///     name: name.into(),
+///     line: 1,
+///     complexity,
+/// };
+/// let report = ComplexityReport {
+///     functions: vec![function("tangled", 14), function("simple", 2)],
+///     total_functions: 2,
+///     ..ComplexityReport::default()
+/// }
+/// .with_threshold(10);
+/// assert_eq!(report.functions.len(), 1);
+/// assert!(report.exceeds_threshold());
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplexityReport {
+    /// Files by complexity descending, then path.
+    pub files: Vec<FileComplexity>,
+    /// Functions by complexity descending, then path and line.
+    pub functions: Vec<FunctionComplexity>,
+    /// Number of files measured, before any truncation or threshold.
+    pub total_files: usize,
+    /// Number of functions measured, before any truncation or threshold.
+    pub total_functions: usize,
+    /// Functions at or below this complexity were left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+}
+
+impl ComplexityReport {
+    /// Keep only functions with complexity above `threshold`.
+    ///
+    /// File totals are dropped: they sum every function in the file, so
+    /// they are not comparable to a per-function limit.
+    pub fn with_threshold(mut self, threshold: u32) -> Self {
+        self.functions.retain(|f| f.complexity > threshold);
+        self.files.clear();
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Keep the `limit` most complex files and functions.
+    pub fn truncate(&mut self, limit: usize) {
+        self.files.truncate(limit);
+        self.functions.truncate(limit);
+    }
+
+    /// Whether a threshold is set and some function exceeds it.
+    pub fn exceeds_threshold(&self) -> bool {
+        self.threshold.is_some() && !self.functions.is_empty()
+    }
+
+    /// Render the report as markdown tables.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Complexity Report\n\n");
+        out.push_str(&format!(
+            "{} files, {} functions measured.\n\n",
+            self.total_files, self.total_functions
+        ));
+        if let Some(threshold) = self.threshold {
+            out.push_str(&format!(
+                "**{} function(s) above complexity {threshold}.**\n\n",
+                self.functions.len()
+            ));
+        }
+        if !self.files.is_empty() {
+            out.push_str("| File | Complexity | Lines |\n");
+            out.push_str("|------|------------|-------|\n");
+            for c in &self.files {
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    c.path.display(),
+                    c.complexity,
+                    c.loc
+                ));
+            }
+            out.push('\n');
+        }
+        if !self.functions.is_empty() {
+            out.push_str("| Function | Location | Complexity |\n");
+            out.push_str("|----------|----------|------------|\n");
+            for c in &self.functions {
+                out.push_str(&format!(
+                    "| `{}` | {}:{} | {} |\n",
+                    c.name,
+                    c.path.display(),
+                    c.line,
+                    c.complexity
+                ));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for ComplexityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Complexity Report")?;
+        writeln!(f, "=================")?;
+        writeln!(
+            f,
+            "{} files, {} functions measured\n",
+            self.total_files, self.total_functions
+        )?;
+        if let Some(threshold) = self.threshold {
+            if self.functions.is_empty() {
+                return writeln!(f, "No functions above complexity {threshold}.");
+            }
+            writeln!(
+                f,
+                "{} function(s) above complexity {threshold}:",
+                self.functions.len()
+            )?;
+        }
+        if !self.files.is_empty() {
+            writeln!(f, "{:>10}  {:>6}  File", "Complexity", "Lines")?;
+            for c in &self.files {
+                writeln!(
+                    f,
+                    "{:>10}  {:>6}  {}",
+                    c.complexity,
+                    c.loc,
+                    c.path.display()
+                )?;
+            }
+            writeln!(f)?;
+        }
+        if !self.functions.is_empty() {
+            writeln!(f, "{:>10}  Function", "Complexity")?;
+            for c in &self.functions {
+                writeln!(f, "{:>10}  {c}", c.complexity)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Measure every parseable file under `root` and each function in it.
+///
+/// Files larger than `max_file_bytes` are skipped, as in the repo map.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::complexity::complexity_report;
+/// use argus_repomap::walker::DEFAULT_MAX_FILE_BYTES;
+///
+/// let report = complexity_report(Path::new("."), DEFAULT_MAX_FILE_BYTES).unwrap();
+/// print!("{report}");
+/// ```
+pub fn complexity_report(root: &Path, max_file_bytes: u64) -> Result<ComplexityReport, ArgusError> {
+    let walk = crate::walker::walk_repo_detailed(root, false, max_file_bytes)?;
+    let mut report = ComplexityReport::default();
+    for file in &walk.files {
+        let Some(tree) = parse(file) else {
+            continue;
+        };
+        report.files.push(measure_file(file, &tree));
+        collect_functions(tree.root_node(), file, &mut report.functions);
+    }
+
+    report.files.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    report.functions.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    report.total_files = report.files.len();
+    report.total_functions = report.functions.len();
+    Ok(report)
+}
+
+/// Measure the cyclomatic complexity of every parseable file under `root`.
+///
+/// Results are sorted by complexity descending, then by path.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::complexity::repo_complexity;
+///
+/// for c in repo_complexity(Path::new(".")).unwrap().iter().take(5) {
+///     println!("{}: {}", c.path.display(), c.complexity);
+/// }
+/// ```
+pub fn repo_complexity(root: &Path) -> Result<Vec<FileComplexity>, ArgusError> {
+    let files = crate::walker::walk_repo(root)?;
+    let mut results: Vec<FileComplexity> = files.iter().filter_map(file_complexity).collect();
+    results.sort_by(|a, b| {
+        b.complexity
+            .cmp(&a.complexity)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(results)
+}
+
+/// Record each named function under `node`, nested ones included.
+fn collect_functions(node: Node, file: &SourceFile, out: &mut Vec<FunctionComplexity>) {
+    if let Some(name) = function_name(node, file) {
+        out.push(FunctionComplexity {
+            path: file.path.clone(),
+            name,
+            line: node.start_position().row as u32 + 1,
+            complexity: 1 + function_decision_points(node, file),
+        });
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_functions(child, file, out);
+    }
+}
+
+/// Count decision points under a function, skipping nested named functions
+/// (they are reported on their own).
+fn function_decision_points(node: Node, file: &SourceFile) -> u32 {
+    let mut cursor = node.walk();
+    let count = node
+        .children(&mut cursor)
+        .filter(|child| function_name(*child, file).is_none())
+        .map(|child| {
+            u32::from(is_decision_point(child, file.language))
+                + function_decision_points(child, file)
+        })
+        .sum();
+    count
+}
+
+/// The name of `node` if it defines a function or method.
+///
+/// Anonymous functions are only named when bound to a variable
+/// (`const f = () => ...`); other closures and lambdas count toward the
+/// function that contains them.
+fn function_name(node: Node, file: &SourceFile) -> Option<String> {
+    let kinds: &[&str] = match file.language {
+        Language::Rust => &["function_item"],
+        Language::Python => &["function_definition"],
+        Language::TypeScript | Language::JavaScript => &[
+            "function_declaration",
+            "generator_function_declaration",
+            "method_definition",
+            "arrow_function",
+            "function_expression",
+            "function",
+        ],
+        Language::Go => &["function_declaration", "method_declaration"],
+        Language::Java => &["method_declaration", "constructor_declaration"],
+        Language::C | Language::Cpp => &["function_definition"],
+        Language::Ruby => &["method", "singleton_method"],
+        Language::Php => &["function_definition", "method_declaration"],
+        Language::Kotlin | Language::Swift => &["function_declaration"],
+        Language::Unknown => &[],
+    };
+    if !node.is_named() || !kinds.contains(&node.kind()) {
+        return None;
+    }
+
+    let name = if let Some(name) = node.child_by_field_name("name") {
+        name
+    } else if let Some(mut declarator) = node.child_by_field_name("declarator") {
+        // C/C++: the name sits at the bottom of a declarator chain
+        while let Some(inner) = declarator.child_by_field_name("declarator") {
+            declarator = inner;
+        }
+        declarator
+    } else if matches!(
+        node.kind(),
+        "arrow_function" | "function_expression" | "function"
+    ) {
+        node.parent()
+            .filter(|parent| parent.kind() == "variable_declarator")?
+            .child_by_field_name("name")?
+    } else {
+        // Kotlin's grammar has no field names
+        let mut cursor = node.walk();
+        let found = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "simple_identifier");
+        found?
+    };
+    name.utf8_text(file.content.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
+/// Count decision points in the subtree rooted at `node`.
+fn count_decision_points(node: Node, language: Language) -> u32 {
+    let mut count = 0;
+    let mut cursor = node.walk();
+    loop {
+        if is_decision_point(cursor.node(), language) {
+            count += 1;
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        loop {
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            if !cursor.goto_parent() || cursor.node() == node {
+                return count;
+            }
+        }
+    }
+}
+
+fn is_decision_point(node: Node, language: Language) -> bool {
+    if !node.is_named() {
+        return false;
+    }
+    let kind = node.kind();
+    let branches: &[&str] = match language {
+        Language::Rust => &[
+            "if_expression",
+            "while_expression",
+            "for_expression",
+            "match_arm",
+        ],
+        Language::Python => &[
+            "if_statement",
+            "elif_clause",
+            "for_statement",
+            "while_statement",
+            "except_clause",
+            "conditional_expression",
+            "boolean_operator",
+            "for_in_clause",
+            "if_clause",
+        ],
+        Language::TypeScript | Language::JavaScript => &[
+            "if_statement",
+            "for_statement",
+            "for_in_statement",
+            "while_statement",
+            "do_statement",
+            "switch_case",
+            "catch_clause",
+            "ternary_expression",
+        ],
+        Language::Go => &[
+            "if_statement",
+            "for_statement",
+            "expression_case",
+            "type_case",
+            "communication_case",
+        ],
+        Language::Java => &[
+            "if_statement",
+            "for_statement",
+            "enhanced_for_statement",
+            "while_statement",
+            "do_statement",
+            "switch_label",
+            "catch_clause",
+            "ternary_expression",
+        ],
+        Language::C | Language::Cpp => &[
+            "if_statement",
+            "for_statement",
+            "for_range_loop",
+            "while_statement",
+            "do_statement",
+            "case_statement",
+            "catch_clause",
+            "conditional_expression",
+        ],
+        Language::Ruby => &[
+            "if",
+            "elsif",
+            "unless",
+            "while",
+            "until",
+            "for",
+            "when",
+            "rescue",
+            "conditional",
+            "if_modifier",
+            "unless_modifier",
+            "while_modifier",
+            "until_modifier",
+        ],
+        Language::Php => &[
+            "if_statement",
+            "else_if_clause",
+            "for_statement",
+            "foreach_statement",
+            "while_statement",
+            "do_statement",
+            "case_statement",
+            "catch_clause",
+            "conditional_expression",
+        ],
+        Language::Kotlin => &[
+            "if_expression",
+            "for_statement",
+            "while_statement",
+            "do_while_statement",
+            "when_entry",
+            "catch_block",
+            "conjunction_expression",
+            "disjunction_expression",
+        ],
+        Language::Swift => &[
+            "if_statement",
+            "guard_statement",
+            "for_statement",
+            "while_statement",
+            "repeat_while_statement",
+            "switch_entry",
+            "catch_block",
+            "ternary_expression",
+            "conjunction_expression",
+            "disjunction_expression",
+        ],
+        Language::Unknown => &[],
+    };
+    if branches.contains(&kind) {
+        return true;
+    }
+
+    // Short-circuit operators appear as binary expressions with a `&&`/`||`
+    // operator token in most grammars.
+    matches!(kind, "binary_expression" | "binary") && has_logical_operator(node)
+}
+
+fn has_logical_operator(node: Node) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .any(|child| matches!(child.kind(), "&&" | "||" | "and" | "or" | "??"));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complexity_of(path: &str, language: Language, content: &str) -> u32 {
+        let file = SourceFile {
+            path: PathBuf::from(path),
+            language,
+            content: content.into(),
+        };
+        file_complexity(&file).unwrap().complexity
+    }
+
+    #[test]
+    fn straight_line_code_has_complexity_one() {
+        assert_eq!(
+            complexity_of("a.rs", Language::Rust, "fn a() { let x = 1; }"),
+            1
+        );
+        assert_eq!(complexity_of("a.py", Language::Python, "x = 1\n"), 1);
+    }
+
+    #[test]
+    fn counts_rust_branches_loops_and_arms() {
+        let content = r#"
+fn f(x: Option<i32>) -> i32 {
+    for i in 0..3 {
+        if i == 1 || i == 2 {
+            continue;
+        }
+    }
+    match x {
+        Some(v) => v,
+        None => 0,
+    }
+}
+"#;
+        // for + if + || + 2 match arms
+        assert_eq!(complexity_of("f.rs", Language::Rust, content), 6);
+    }
+
+    #[test]
+    fn counts_python_and_javascript_constructs() {
+        let py = "def f(x):\n    if x and x > 1:\n        return 1\n    elif x:\n        return 2\n    return 0\n";
+        // if + and + elif
+        assert_eq!(complexity_of("f.py", Language::Python, py), 4);
+
+        let js = "function f(x) { while (x) { x = x > 1 ? x - 1 : 0; } switch (x) { case 0: return 1; default: return 2; } }";
+        // while + ternary + case (default is not a decision)
+        assert_eq!(complexity_of("f.js", Language::JavaScript, js), 4);
+    }
+
+    #[test]
+    fn unknown_language_is_not_measured() {
+        let file = SourceFile {
+            path: PathBuf::from("README.md"),
+            language: Language::Unknown,
+            content: "# hi".into(),
+        };
+        assert!(file_complexity(&file).is_none());
+    }
+
+    #[test]
+    fn functions_are_measured_without_nested_functions() {
+        let py = "def outer(x):\n    def inner(y):\n        return 1 if y else 2\n    if x:\n        return inner(x)\n    return 0\n";
+        let file = SourceFile {
+            path: PathBuf::from("f.py"),
+            language: Language::Python,
+            content: py.into(),
+        };
+        let functions = function_complexity(&file);
+        let summary: Vec<(&str, u32, u32)> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.line, f.complexity))
+            .collect();
+        // outer: its own `if`; inner: the conditional expression
+        assert_eq!(summary, [("outer", 1, 2), ("inner", 2, 2)]);
+    }
+
+    #[test]
+    fn names_c_functions_and_bound_arrow_functions() {
+        let c = SourceFile {
+            path: PathBuf::from("a.c"),
+            language: Language::C,
+            content: "static char *dup(const char *s) { if (!s) return 0; return 0; }".into(),
+        };
+        assert_eq!(function_complexity(&c)[0].name, "dup");
+
+        let js = SourceFile {
+            path: PathBuf::from("a.js"),
+            language: Language::JavaScript,
+            content: "const pick = (a, b) => a || b;\n[1].map(x => x);".into(),
+        };
+        let functions = function_complexity(&js);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "pick");
+        assert_eq!(functions[0].complexity, 2);
+    }
+
+    #[test]
+    fn report_ranks_functions_and_applies_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "fn a() {}\nfn b(x: i32) { if x > 0 { } if x < 0 { } }\n",
+        )
+        .unwrap();
+
+        let report = complexity_report(dir.path(), u64::MAX).unwrap();
+        assert_eq!(report.total_files, 1);
+        assert_eq!(report.total_functions, 2);
+        assert_eq!(report.functions[0].name, "b");
+        assert_eq!(report.functions[0].complexity, 3);
+
+        let gated = report.clone().with_threshold(2);
+        assert!(gated.exceeds_threshold());
+        assert!(gated.files.is_empty());
+        assert!(gated
+            .to_string()
+            .contains("1 function(s) above complexity 2:"));
+        assert!(!report.with_threshold(3).exceeds_threshold());
+    }
+
+    #[test]
+    fn repo_complexity_sorts_worst_first() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("simple.rs"), "fn a() {}").unwrap();
+        std::fs::write(
+            dir.path().join("branchy.rs"),
+            "fn b(x: i32) { if x > 0 { } if x < 0 { } }",
+        )
+        .unwrap();
+
+        let results = repo_complexity(dir.path()).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, PathBuf::from("branchy.rs"));
+        assert_eq!(results[0].complexity, 3);
+        assert_eq!(results[1].complexity, 1);
+    }
+}
@@ -1,7 +1,9 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
 
 use crate::parser::{Reference, Symbol};
 
@@ -35,13 +37,45 @@ pub struct SymbolNode {
     pub rank: f64,
 }
 
+/// The symbols that reference a changed symbol: how far a change can reach.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::graph::BlastRadius;
+///
+/// let radius = BlastRadius {
+///     symbol: "parse_config".into(),
+///     file: PathBuf::from("src/config.rs"),
+///     dependents: vec!["main".into(), "load".into()],
+/// };
+/// assert_eq!(radius.dependents.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlastRadius {
+    /// Name of the changed symbol.
+    pub symbol: String,
+    /// File defining the changed symbol.
+    pub file: PathBuf,
+    /// Names of the symbols that reference it, sorted and deduplicated.
+    pub dependents: Vec<String>,
+}
+
 /// Directed graph of symbols linked by cross-references, with PageRank ranking.
 ///
+/// Edges carry the [`Reference::weight`] of the reference that created them,
+/// so a call counts for more than a type mention or an import. A name
+/// defined more than once resolves to the definition in the referencing
+/// file if there is one, and otherwise splits the weight evenly across all
+/// definitions, so a common name does not funnel every reference into one
+/// symbol.
+///
 /// # Examples
 ///
 /// ```
 /// use std::path::PathBuf;
-/// use argus_repomap::parser::{Symbol, SymbolKind, Reference};
+/// use argus_repomap::parser::{Symbol, SymbolKind, Reference, REFERENCE_WEIGHT_CALL};
 /// use argus_repomap::graph::SymbolGraph;
 ///
 /// let symbols = vec![
@@ -68,6 +102,7 @@ pub struct SymbolNode {
 ///         from_symbol: Some("caller".into()),
 ///         to_name: "callee".into(),
 ///         line: 2,
+///         weight: REFERENCE_WEIGHT_CALL,
 ///     },
 /// ];
 /// let mut graph = SymbolGraph::build(symbols, refs);
@@ -76,9 +111,10 @@ pub struct SymbolNode {
 /// assert!(!ranked.is_empty());
 /// ```
 pub struct SymbolGraph {
-    graph: DiGraph<SymbolNode, ()>,
+    graph: DiGraph<SymbolNode, f32>,
     #[allow(dead_code)]
-    name_to_index: HashMap<String, NodeIndex>,
+    name_to_index: HashMap<String, Vec<NodeIndex>>,
+    leaf_pruning_threshold: Option<usize>,
 }
 
 impl SymbolGraph {
@@ -86,48 +122,239 @@ impl SymbolGraph {
     ///
     /// Each symbol becomes a node. Each reference that resolves to a known
     /// symbol name creates a directed edge from the referencing context to
-    /// the referenced symbol.
+    /// the referenced symbol, weighted by the reference's syntactic context.
     pub fn build(symbols: Vec<Symbol>, references: Vec<Reference>) -> Self {
+        Self::build_ignoring(symbols, references, &[])
+    }
+
+    /// Like [`build`](Self::build), but references to any name in `ignored`
+    /// create no edges.
+    ///
+    /// Meant for ubiquitous method names such as `new` or `get`, which
+    /// otherwise link nearly every symbol to every type that defines them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use argus_repomap::parser::{Reference, Symbol, SymbolKind, REFERENCE_WEIGHT_CALL};
+    /// use argus_repomap::graph::SymbolGraph;
+    ///
+    /// let symbol = |name: &str, file: &str| Symbol {
+    ///     name: name.into(),
+    ///     kind: SymbolKind::Function,
+    ///     file: PathBuf::from(file),
+    ///     line: 1,
+    ///     signature: format!("fn {name}()"),
+    ///     token_cost: 3,
+    /// };
+    /// let graph = SymbolGraph::build_ignoring(
+    ///     vec![symbol("new", "config.rs"), symbol("main", "main.rs")],
+    ///     vec![Reference {
+    ///         from_file: PathBuf::from("main.rs"),
+    ///         from_symbol: Some("main".into()),
+    ///         to_name: "new".into(),
+    ///         line: 2,
+    ///         weight: REFERENCE_WEIGHT_CALL,
+    ///     }],
+    ///     &["new".to_string()],
+    /// );
+    /// assert!(graph.blast_radius(&[(PathBuf::from("config.rs"), 1..=1)]).is_empty());
+    /// ```
+    pub fn build_ignoring(
+        symbols: Vec<Symbol>,
+        references: Vec<Reference>,
+        ignored: &[String],
+    ) -> Self {
         let mut graph = DiGraph::new();
-        let mut name_to_index: HashMap<String, NodeIndex> = HashMap::new();
+        let mut name_to_index: HashMap<String, Vec<NodeIndex>> = HashMap::new();
 
         for symbol in symbols {
             let name = symbol.name.clone();
             let idx = graph.add_node(SymbolNode { symbol, rank: 0.0 });
-            // First symbol with a given name wins
-            name_to_index.entry(name).or_insert(idx);
+            name_to_index.entry(name).or_default().push(idx);
         }
 
+        let ignored: HashSet<&str> = ignored.iter().map(String::as_str).collect();
         for reference in &references {
-            let Some(&to_idx) = name_to_index.get(&reference.to_name) else {
+            if ignored.contains(reference.to_name.as_str()) {
+                continue;
+            }
+            let Some(candidates) = name_to_index.get(&reference.to_name) else {
                 continue; // Unresolved reference
             };
 
             // Find the "from" node: prefer the enclosing symbol, fall back to file-level
-            let from_idx = reference
-                .from_symbol
-                .as_ref()
-                .and_then(|name| name_to_index.get(name).copied());
+            let from_idx = reference.from_symbol.as_ref().and_then(|name| {
+                let defined = name_to_index.get(name)?;
+                prefer_file(&graph, defined, &reference.from_file)
+                    .first()
+                    .copied()
+            });
 
             let Some(from_idx) = from_idx else {
                 continue;
             };
 
-            // Don't add self-loops
-            if from_idx == to_idx {
-                continue;
+            let targets = prefer_file(&graph, candidates, &reference.from_file);
+            let weight = reference.weight / targets.len() as f32;
+            for to_idx in targets {
+                // Don't add self-loops
+                if from_idx != to_idx {
+                    graph.add_edge(from_idx, to_idx, weight);
+                }
             }
-
-            graph.add_edge(from_idx, to_idx, ());
         }
 
         Self {
             graph,
             name_to_index,
+            leaf_pruning_threshold: None,
+        }
+    }
+
+    /// Prune leaf symbols from [`compute_pagerank`](Self::compute_pagerank)
+    /// when the graph has more than `symbols` nodes.
+    ///
+    /// A leaf is a symbol nothing references. Its rank never changes from
+    /// the teleport share, so on a pruned run leaves keep that rank and
+    /// pass it on to their targets once, and each iteration walks only the
+    /// edges of referenced symbols. On a huge repository most symbols are
+    /// leaves, which makes PageRank much faster. Scores move a little,
+    /// because leaves skip the first iteration's uniform start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use argus_repomap::parser::{Reference, Symbol, SymbolKind, REFERENCE_WEIGHT_CALL};
+    /// use argus_repomap::graph::SymbolGraph;
+    ///
+    /// let symbol = |name: &str| Symbol {
+    ///     name: name.into(),
+    ///     kind: SymbolKind::Function,
+    ///     file: PathBuf::from("lib.rs"),
+    ///     line: 1,
+    ///     signature: format!("fn {name}()"),
+    ///     token_cost: 3,
+    /// };
+    /// let call = Reference {
+    ///     from_file: PathBuf::from("lib.rs"),
+    ///     from_symbol: Some("main".into()),
+    ///     to_name: "run".into(),
+    ///     line: 2,
+    ///     weight: REFERENCE_WEIGHT_CALL,
+    /// };
+    /// let mut graph = SymbolGraph::build(vec![symbol("main"), symbol("run")], vec![call])
+    ///     .with_leaf_pruning_threshold(1);
+    /// graph.compute_pagerank();
+    /// assert_eq!(graph.ranked_symbols()[0].symbol.name, "run");
+    /// ```
+    pub fn with_leaf_pruning_threshold(mut self, symbols: usize) -> Self {
+        self.leaf_pruning_threshold = Some(symbols);
+        self
+    }
+
+    /// Find the dependents of every symbol touched by `changed` line ranges.
+    ///
+    /// A symbol counts as touched when its definition line falls inside a
+    /// range, or when it is the last symbol defined in the same file before
+    /// the range starts (the one whose body the range most likely edits).
+    /// Symbols nobody references are left out; the rest are sorted by
+    /// dependent count, highest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use argus_repomap::parser::{Reference, Symbol, SymbolKind, REFERENCE_WEIGHT_CALL};
+    /// use argus_repomap::graph::SymbolGraph;
+    ///
+    /// let symbol = |name: &str, file: &str, line: u32| Symbol {
+    ///     name: name.into(),
+    ///     kind: SymbolKind::Function,
+    ///     file: PathBuf::from(file),
+    ///     line,
+    ///     signature: format!("fn {name}()"),
+    ///     token_cost: 3,
+    /// };
+    /// let call = |from: &str, to: &str| Reference {
+    ///     from_file: PathBuf::from("main.rs"),
+    ///     from_symbol: Some(from.into()),
+    ///     to_name: to.into(),
+    ///     line: 1,
+    ///     weight: REFERENCE_WEIGHT_CALL,
+    /// };
+    /// let graph = SymbolGraph::build(
+    ///     vec![symbol("parse", "lib.rs", 10), symbol("main", "main.rs", 1)],
+    ///     vec![call("main", "parse")],
+    /// );
+    /// let radius = graph.blast_radius(&[(PathBuf::from("lib.rs"), 12..=14)]);
+    /// assert_eq!(radius[0].symbol, "parse");
+    /// assert_eq!(radius[0].dependents, ["main"]);
+    /// ```
+    pub fn blast_radius(&self, changed: &[(PathBuf, RangeInclusive<u32>)]) -> Vec<BlastRadius> {
+        let mut touched: Vec<NodeIndex> = Vec::new();
+        for (file, range) in changed {
+            let in_file: Vec<NodeIndex> = self
+                .graph
+                .node_indices()
+                .filter(|&idx| &self.graph[idx].symbol.file == file)
+                .collect();
+            touched.extend(
+                in_file
+                    .iter()
+                    .copied()
+                    .filter(|&idx| range.contains(&self.graph[idx].symbol.line)),
+            );
+            if let Some(enclosing) = in_file
+                .iter()
+                .copied()
+                .filter(|&idx| self.graph[idx].symbol.line < *range.start())
+                .max_by_key(|&idx| self.graph[idx].symbol.line)
+            {
+                touched.push(enclosing);
+            }
         }
+        touched.sort();
+        touched.dedup();
+
+        let mut radius: Vec<BlastRadius> = touched
+            .into_iter()
+            .filter_map(|idx| {
+                let mut dependents: Vec<String> = self
+                    .graph
+                    .neighbors_directed(idx, petgraph::Direction::Incoming)
+                    .map(|n| self.graph[n].symbol.name.clone())
+                    .collect();
+                if dependents.is_empty() {
+                    return None;
+                }
+                dependents.sort();
+                dependents.dedup();
+                let symbol = &self.graph[idx].symbol;
+                Some(BlastRadius {
+                    symbol: symbol.name.clone(),
+                    file: symbol.file.clone(),
+                    dependents,
+                })
+            })
+            .collect();
+        radius.sort_by(|a, b| {
+            b.dependents
+                .len()
+                .cmp(&a.dependents.len())
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        radius
     }
 
     /// Run PageRank (damping=0.85, 20 iterations) and store scores on nodes.
+    ///
+    /// Each node splits its rank across outgoing edges in proportion to
+    /// their weights. Above the
+    /// [leaf pruning threshold](Self::with_leaf_pruning_threshold), symbols
+    /// nothing references are left out of the iterations.
     pub fn compute_pagerank(&mut self) {
         let n = self.graph.node_count();
         if n == 0 {
@@ -138,32 +365,32 @@ impl SymbolGraph {
         let n_f64 = n as f64;
         let base = (1.0 - d) / n_f64;
 
+        // Every node gets the teleport share; a pruned leaf also passes its
+        // fixed rank on once, here, instead of in every iteration
+        let prune = self.leaf_pruning_threshold.is_some_and(|max| n > max);
+        let mut fixed = vec![base; n];
+        let mut active = Vec::new();
+        for node_idx in self.graph.node_indices() {
+            let is_leaf = self
+                .graph
+                .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+                .next()
+                .is_none();
+            if prune && is_leaf {
+                self.spread_rank(node_idx, d * base, &mut fixed);
+            } else {
+                active.push(node_idx);
+            }
+        }
+
         // Initialize all ranks to 1/N
         let mut ranks = vec![1.0 / n_f64; n];
 
         for _ in 0..20 {
-            let mut new_ranks = vec![base; n];
-
-            for node_idx in self.graph.node_indices() {
-                let i = node_idx.index();
-                let out_degree = self
-                    .graph
-                    .neighbors_directed(node_idx, petgraph::Direction::Outgoing)
-                    .count();
-
-                if out_degree == 0 {
-                    continue;
-                }
-
-                let contribution = d * ranks[i] / out_degree as f64;
-                for neighbor in self
-                    .graph
-                    .neighbors_directed(node_idx, petgraph::Direction::Outgoing)
-                {
-                    new_ranks[neighbor.index()] += contribution;
-                }
+            let mut new_ranks = fixed.clone();
+            for &node_idx in &active {
+                self.spread_rank(node_idx, d * ranks[node_idx.index()], &mut new_ranks);
             }
-
             ranks = new_ranks;
         }
 
@@ -173,6 +400,26 @@ impl SymbolGraph {
         }
     }
 
+    /// Add `amount` to the ranks of the targets of `node_idx`, split in
+    /// proportion to the edge weights.
+    fn spread_rank(&self, node_idx: NodeIndex, amount: f64, ranks: &mut [f64]) {
+        let out_weight: f64 = self
+            .graph
+            .edges_directed(node_idx, petgraph::Direction::Outgoing)
+            .map(|edge| f64::from(*edge.weight()))
+            .sum();
+        if out_weight <= 0.0 {
+            return;
+        }
+        for edge in self
+            .graph
+            .edges_directed(node_idx, petgraph::Direction::Outgoing)
+        {
+            let share = f64::from(*edge.weight()) / out_weight;
+            ranks[edge.target().index()] += amount * share;
+        }
+    }
+
     /// Get all symbols sorted by rank (highest first).
     pub fn ranked_symbols(&self) -> Vec<&SymbolNode> {
         let mut nodes: Vec<&SymbolNode> = self.graph.node_weights().collect();
@@ -202,12 +449,157 @@ impl SymbolGraph {
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         scored.into_iter().map(|(node, _)| node).collect()
     }
+
+    /// Rank only the symbols near `focus_files` with personalized PageRank.
+    ///
+    /// Seeds are the symbols defined in the focus files. The walk is limited
+    /// to the seeds' `hops`-hop neighborhood, following references in both
+    /// directions so callers and callees both count, and random jumps return
+    /// to the seeds. Cost scales with the neighborhood instead of the whole
+    /// graph. Scores are stored on the neighborhood nodes; every other node
+    /// gets rank 0 and is left out of the result.
+    ///
+    /// Falls back to [`compute_pagerank`](Self::compute_pagerank) over the
+    /// full graph when no focus file defines a symbol.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use argus_repomap::parser::{Reference, Symbol, SymbolKind, REFERENCE_WEIGHT_CALL};
+    /// use argus_repomap::graph::SymbolGraph;
+    ///
+    /// let symbol = |name: &str, file: &str| Symbol {
+    ///     name: name.into(),
+    ///     kind: SymbolKind::Function,
+    ///     file: PathBuf::from(file),
+    ///     line: 1,
+    ///     signature: format!("fn {name}()"),
+    ///     token_cost: 3,
+    /// };
+    /// let call = |from: &str, to: &str| Reference {
+    ///     from_file: PathBuf::from("x.rs"),
+    ///     from_symbol: Some(from.into()),
+    ///     to_name: to.into(),
+    ///     line: 1,
+    ///     weight: REFERENCE_WEIGHT_CALL,
+    /// };
+    /// let mut graph = SymbolGraph::build(
+    ///     vec![symbol("a", "a.rs"), symbol("b", "b.rs"), symbol("far", "far.rs")],
+    ///     vec![call("a", "b"), call("far", "a")],
+    /// );
+    /// let ranked = graph.ranked_symbols_near_files(&[PathBuf::from("b.rs")], 1);
+    /// let names: Vec<&str> = ranked.iter().map(|n| n.symbol.name.as_str()).collect();
+    /// assert_eq!(names, ["b", "a"]);
+    /// ```
+    pub fn ranked_symbols_near_files(
+        &mut self,
+        focus_files: &[PathBuf],
+        hops: usize,
+    ) -> Vec<&SymbolNode> {
+        let seeds: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&idx| focus_files.contains(&self.graph[idx].symbol.file))
+            .collect();
+        if seeds.is_empty() {
+            self.compute_pagerank();
+            return self.ranked_symbols();
+        }
+
+        // Breadth-first expansion to the k-hop neighborhood
+        let mut local: Vec<NodeIndex> = seeds.clone();
+        let mut seen: HashSet<NodeIndex> = seeds.iter().copied().collect();
+        let mut queue: VecDeque<(NodeIndex, usize)> = seeds.iter().map(|&idx| (idx, 0)).collect();
+        while let Some((idx, depth)) = queue.pop_front() {
+            if depth == hops {
+                continue;
+            }
+            for neighbor in self.graph.neighbors_undirected(idx) {
+                if seen.insert(neighbor) {
+                    local.push(neighbor);
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        let position: HashMap<NodeIndex, usize> =
+            local.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+        let adjacency: Vec<Vec<usize>> = local
+            .iter()
+            .map(|&idx| {
+                self.graph
+                    .neighbors_undirected(idx)
+                    .filter_map(|n| position.get(&n).copied())
+                    .collect()
+            })
+            .collect();
+
+        let d: f64 = 0.85;
+        let n = local.len();
+        let mut teleport = vec![0.0; n];
+        for seed in &seeds {
+            teleport[position[seed]] = 1.0 / seeds.len() as f64;
+        }
+
+        let mut ranks = teleport.clone();
+        for _ in 0..20 {
+            let mut new_ranks: Vec<f64> = teleport.iter().map(|t| (1.0 - d) * t).collect();
+            for (i, neighbors) in adjacency.iter().enumerate() {
+                if neighbors.is_empty() {
+                    // Dangling node: its mass jumps back to the seeds
+                    for (j, t) in teleport.iter().enumerate() {
+                        new_ranks[j] += d * ranks[i] * t;
+                    }
+                    continue;
+                }
+                let contribution = d * ranks[i] / neighbors.len() as f64;
+                for &j in neighbors {
+                    new_ranks[j] += contribution;
+                }
+            }
+            ranks = new_ranks;
+        }
+
+        for node in self.graph.node_weights_mut() {
+            node.rank = 0.0;
+        }
+        for (i, &idx) in local.iter().enumerate() {
+            self.graph[idx].rank = ranks[i];
+        }
+
+        let mut nodes: Vec<&SymbolNode> = local.iter().map(|&idx| &self.graph[idx]).collect();
+        nodes.sort_by(|a, b| {
+            b.rank
+                .partial_cmp(&a.rank)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        nodes
+    }
+}
+
+/// The `candidates` defined in `file`, or all of them when none is.
+fn prefer_file(
+    graph: &DiGraph<SymbolNode, f32>,
+    candidates: &[NodeIndex],
+    file: &Path,
+) -> Vec<NodeIndex> {
+    let local: Vec<NodeIndex> = candidates
+        .iter()
+        .copied()
+        .filter(|&idx| graph[idx].symbol.file == file)
+        .collect();
+    if local.is_empty() {
+        candidates.to_vec()
+    } else {
+        local
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::SymbolKind;
+    use crate::parser::{SymbolKind, REFERENCE_WEIGHT_CALL, REFERENCE_WEIGHT_MENTION};
 
     fn make_symbol(name: &str, file: &str) -> Symbol {
         Symbol {
@@ -226,7 +618,57 @@ mod tests {
             from_symbol: Some(from.to_string()),
             to_name: to.to_string(),
             line: 1,
+            weight: REFERENCE_WEIGHT_CALL,
+        }
+    }
+
+    #[test]
+    fn leaf_pruning_preserves_hub_ranking() {
+        // Many leaf callers feed three hubs unevenly; the hubs also call
+        // each other, so their order depends on more than the leaf counts
+        let mut symbols = vec![
+            make_symbol("store", "store.rs"),
+            make_symbol("parse", "parse.rs"),
+            make_symbol("log", "log.rs"),
+        ];
+        let mut refs = vec![make_ref("parse", "log"), make_ref("store", "log")];
+        for (i, targets) in [3, 2, 1, 1, 1, 2, 3, 2, 1, 1].iter().enumerate() {
+            let name = format!("handler{i}");
+            symbols.push(make_symbol(&name, "handlers.rs"));
+            for hub in ["store", "parse", "log"].iter().take(*targets) {
+                refs.push(make_ref(&name, hub));
+            }
         }
+        let hub_order = |graph: &SymbolGraph| -> Vec<String> {
+            graph
+                .ranked_symbols()
+                .iter()
+                .map(|n| n.symbol.name.clone())
+                .filter(|name| !name.starts_with("handler"))
+                .collect()
+        };
+
+        let mut full = SymbolGraph::build(symbols.clone(), refs.clone());
+        full.compute_pagerank();
+        let mut pruned =
+            SymbolGraph::build(symbols.clone(), refs.clone()).with_leaf_pruning_threshold(5);
+        pruned.compute_pagerank();
+        assert_eq!(hub_order(&pruned), hub_order(&full));
+        assert_eq!(hub_order(&full), ["log", "store", "parse"]);
+        // Leaves still get ranked, below every hub
+        assert_eq!(pruned.ranked_symbols().len(), 13);
+        assert!(pruned.ranked_symbols()[3]
+            .symbol
+            .name
+            .starts_with("handler"));
+
+        // Below the threshold nothing is pruned
+        let mut unpruned = SymbolGraph::build(symbols, refs).with_leaf_pruning_threshold(1_000);
+        unpruned.compute_pagerank();
+        let ranks = |graph: &SymbolGraph| -> Vec<f64> {
+            graph.ranked_symbols().iter().map(|n| n.rank).collect()
+        };
+        assert_eq!(ranks(&unpruned), ranks(&full));
     }
 
     #[test]
@@ -249,6 +691,124 @@ mod tests {
         assert!(ranked[0].rank > ranked[2].rank);
     }
 
+    #[test]
+    fn ubiquitous_constructor_name_is_not_a_hub() {
+        // Five types each define `new`; five callers each call `new`, and
+        // two of them also call `helper`
+        let mut symbols = vec![make_symbol("helper", "helper.rs")];
+        let mut refs = Vec::new();
+        for i in 0..5 {
+            symbols.push(make_symbol("new", &format!("type{i}.rs")));
+            symbols.push(make_symbol(&format!("caller{i}"), &format!("caller{i}.rs")));
+            refs.push(Reference {
+                from_file: PathBuf::from(format!("caller{i}.rs")),
+                ..make_ref(&format!("caller{i}"), "new")
+            });
+        }
+        refs.push(make_ref("caller0", "helper"));
+        refs.push(make_ref("caller1", "helper"));
+
+        for ignored in [vec![], vec!["new".to_string()]] {
+            let mut graph = SymbolGraph::build_ignoring(symbols.clone(), refs.clone(), &ignored);
+            graph.compute_pagerank();
+            let ranked = graph.ranked_symbols();
+            assert_eq!(ranked[0].symbol.name, "helper", "ignored: {ignored:?}");
+        }
+
+        // With `new` ignored, no constructor ranks above a symbol nobody calls
+        let mut graph = SymbolGraph::build_ignoring(symbols, refs, &["new".to_string()]);
+        graph.compute_pagerank();
+        let ranked = graph.ranked_symbols();
+        let uncalled = ranked
+            .iter()
+            .find(|n| n.symbol.name == "caller4")
+            .unwrap()
+            .rank;
+        assert!(ranked
+            .iter()
+            .filter(|n| n.symbol.name == "new")
+            .all(|n| n.rank <= uncalled + f64::EPSILON));
+    }
+
+    #[test]
+    fn same_file_definition_wins_over_other_files() {
+        let symbols = vec![
+            make_symbol("parse", "a.rs"),
+            make_symbol("parse", "b.rs"),
+            make_symbol("run", "a.rs"),
+        ];
+        let refs = vec![Reference {
+            from_file: PathBuf::from("a.rs"),
+            ..make_ref("run", "parse")
+        }];
+        let graph = SymbolGraph::build(symbols, refs);
+
+        let radius = graph.blast_radius(&[(PathBuf::from("b.rs"), 1..=1)]);
+        assert!(radius.is_empty(), "parse in b.rs should not be referenced");
+        let radius = graph.blast_radius(&[(PathBuf::from("a.rs"), 1..=1)]);
+        assert_eq!(radius[0].dependents, ["run"]);
+    }
+
+    #[test]
+    fn called_symbol_outranks_merely_referenced_one() {
+        let symbols = vec![
+            make_symbol("A", "a.rs"),
+            make_symbol("called", "b.rs"),
+            make_symbol("mentioned", "c.rs"),
+        ];
+        let refs = vec![
+            make_ref("A", "called"),
+            Reference {
+                weight: REFERENCE_WEIGHT_MENTION,
+                ..make_ref("A", "mentioned")
+            },
+        ];
+
+        let mut graph = SymbolGraph::build(symbols, refs);
+        graph.compute_pagerank();
+        let ranked = graph.ranked_symbols();
+
+        assert_eq!(ranked[0].symbol.name, "called");
+        assert_eq!(ranked[1].symbol.name, "mentioned");
+        assert!(ranked[0].rank > ranked[1].rank);
+    }
+
+    #[test]
+    fn blast_radius_counts_dependents_of_changed_symbols() {
+        let mut callee = make_symbol("callee", "lib.rs");
+        callee.line = 10;
+        let mut untouched = make_symbol("untouched", "lib.rs");
+        untouched.line = 30;
+        let symbols = vec![
+            make_symbol("A", "a.rs"),
+            make_symbol("B", "b.rs"),
+            callee,
+            untouched,
+        ];
+        let refs = vec![
+            make_ref("A", "callee"),
+            make_ref("B", "callee"),
+            make_ref("B", "untouched"),
+        ];
+        let graph = SymbolGraph::build(symbols, refs);
+
+        // Body edit inside `callee`, which starts before the hunk
+        let radius = graph.blast_radius(&[(PathBuf::from("lib.rs"), 12..=15)]);
+        assert_eq!(
+            radius,
+            vec![BlastRadius {
+                symbol: "callee".into(),
+                file: PathBuf::from("lib.rs"),
+                dependents: vec!["A".into(), "B".into()],
+            }]
+        );
+
+        // Unreferenced symbols are left out
+        assert!(graph
+            .blast_radius(&[(PathBuf::from("a.rs"), 1..=3)])
+            .is_empty());
+    }
+
     #[test]
     fn disconnected_nodes_get_base_rank() {
         let symbols = vec![make_symbol("X", "x.rs"), make_symbol("Y", "y.rs")];
@@ -282,6 +842,51 @@ mod tests {
         assert_eq!(boosted[0].symbol.name, "B");
     }
 
+    #[test]
+    fn personalized_ranking_is_limited_to_neighborhood() {
+        // A -> B -> C -> D -> E, focus on C
+        let symbols = vec![
+            make_symbol("A", "a.rs"),
+            make_symbol("B", "b.rs"),
+            make_symbol("C", "c.rs"),
+            make_symbol("D", "d.rs"),
+            make_symbol("E", "e.rs"),
+        ];
+        let refs = vec![
+            make_ref("A", "B"),
+            make_ref("B", "C"),
+            make_ref("C", "D"),
+            make_ref("D", "E"),
+        ];
+
+        let mut graph = SymbolGraph::build(symbols, refs);
+        let ranked = graph.ranked_symbols_near_files(&[PathBuf::from("c.rs")], 1);
+        let mut names: Vec<&str> = ranked.iter().map(|n| n.symbol.name.as_str()).collect();
+        assert_eq!(names[0], "C");
+        names.sort_unstable();
+        assert_eq!(names, ["B", "C", "D"]);
+
+        // Nodes outside the neighborhood are not ranked
+        let outside: Vec<f64> = graph
+            .ranked_symbols()
+            .iter()
+            .filter(|n| matches!(n.symbol.name.as_str(), "A" | "E"))
+            .map(|n| n.rank)
+            .collect();
+        assert_eq!(outside, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn personalized_ranking_falls_back_without_focus_symbols() {
+        let symbols = vec![make_symbol("A", "a.rs"), make_symbol("B", "b.rs")];
+        let refs = vec![make_ref("A", "B")];
+
+        let mut graph = SymbolGraph::build(symbols, refs);
+        let ranked = graph.ranked_symbols_near_files(&[PathBuf::from("README.md")], 2);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].symbol.name, "B");
+    }
+
     #[test]
     fn empty_graph() {
         let mut graph = SymbolGraph::build(vec![], vec![]);
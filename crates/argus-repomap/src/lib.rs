@@ -3,25 +3,35 @@
 //! Generates a compressed, ranked map of codebase symbols (classes, functions,
 //! signatures) optimized for LLM token efficiency. Uses tree-sitter for AST
 //! parsing, petgraph for PageRank, and the `ignore` crate for file walking.
+//! [`structure`] compares the maps of two revisions.
 
 pub mod budget;
+pub mod complexity;
 pub mod graph;
 pub mod output;
 pub mod parser;
+pub mod selftest;
+pub mod structure;
 pub mod walker;
 
+use std::cell::Cell;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 
 use argus_core::{ArgusError, OutputFormat};
 
+use parser::{Reference, Symbol};
+
 /// Generate a ranked map of the codebase at `root`.
 ///
 /// Walks the repository, parses source files, builds a symbol graph, runs
-/// PageRank, fits to the token budget, and formats the output.
+/// PageRank, fits to the token budget, and formats the output. Files that
+/// cannot be read or parsed are left out, as in [`generate_map_with`].
 ///
 /// # Errors
 ///
-/// Returns [`ArgusError`] if file walking or parsing fails.
+/// Returns [`ArgusError`] if the root directory cannot be walked or the
+/// output cannot be formatted.
 ///
 /// # Examples
 ///
@@ -39,35 +49,327 @@ pub fn generate_map(
     focus_files: &[PathBuf],
     format: OutputFormat,
 ) -> Result<String, ArgusError> {
-    let files = walker::walk_repo(root)?;
+    let options = MapOptions {
+        max_tokens,
+        focus_files: focus_files.to_vec(),
+        format,
+        ..MapOptions::default()
+    };
+    generate_map_with(root, &options).map(|map| map.output)
+}
 
-    let mut all_symbols = Vec::new();
-    let mut all_references = Vec::new();
+/// Options for [`generate_map_with`].
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::OutputFormat;
+/// use argus_repomap::MapOptions;
+///
+/// let options = MapOptions::default();
+/// assert_eq!(options.max_tokens, 1024);
+/// assert_eq!(options.format, OutputFormat::Text);
+/// assert_eq!(options.max_files, None);
+/// assert_eq!(options.focus_hops, None);
+/// assert!(options.changed_ranges.is_empty());
+/// assert!(options.languages.is_empty());
+/// assert!(options.ignored_references.contains(&"new".to_string()));
+/// assert_eq!(options.leaf_pruning_threshold, 50_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MapOptions {
+    /// Token budget for the map (default: 1024).
+    pub max_tokens: usize,
+    /// Files whose symbols get boosted in the ranking.
+    pub focus_files: Vec<PathBuf>,
+    /// Output format (default: text).
+    pub format: OutputFormat,
+    /// Parse at most this many files (default: unlimited).
+    pub max_files: Option<usize>,
+    /// Skip files larger than this many bytes (default:
+    /// [`walker::DEFAULT_MAX_FILE_BYTES`], 1 MB).
+    pub max_file_bytes: u64,
+    /// Rank only symbols within this many reference hops of the focus
+    /// files, using personalized PageRank (default: `None`, rank the whole
+    /// graph and boost focus files). Ignored when `focus_files` is empty.
+    pub focus_hops: Option<usize>,
+    /// Changed line ranges (new-file lines) whose symbols get a
+    /// [`RepoMap::blast_radius`] entry (default: none).
+    pub changed_ranges: Vec<(PathBuf, RangeInclusive<u32>)>,
+    /// Names whose references create no graph edges (default: the
+    /// `[map] ignored_references` default list). See
+    /// [`graph::SymbolGraph::build_ignoring`].
+    pub ignored_references: Vec<String>,
+    /// Leave symbols nothing references out of PageRank when the graph has
+    /// more symbols than this (default: the `[map] leaf_pruning_threshold`
+    /// default). See [`graph::SymbolGraph::with_leaf_pruning_threshold`].
+    pub leaf_pruning_threshold: usize,
+    /// Map only files in these languages (default: empty, all supported
+    /// languages). See [`walker::walk_repo_languages`].
+    pub languages: Vec<walker::Language>,
+}
 
-    for file in &files {
-        let symbols = parser::extract_symbols(file)?;
-        let references = parser::extract_references(file)?;
-        all_symbols.extend(symbols);
-        all_references.extend(references);
+impl Default for MapOptions {
+    fn default() -> Self {
+        Self {
+            max_tokens: 1024,
+            focus_files: Vec::new(),
+            format: OutputFormat::Text,
+            max_files: None,
+            max_file_bytes: walker::DEFAULT_MAX_FILE_BYTES,
+            focus_hops: None,
+            changed_ranges: Vec::new(),
+            ignored_references: argus_core::MapConfig::default().ignored_references,
+            leaf_pruning_threshold: argus_core::MapConfig::default().leaf_pruning_threshold,
+            languages: Vec::new(),
+        }
     }
+}
+
+/// A generated repo map plus details about how it was built.
+#[derive(Debug, Clone)]
+pub struct RepoMap {
+    /// The formatted map.
+    pub output: String,
+    /// Number of source files parsed.
+    pub files_mapped: usize,
+    /// Number of files dropped by [`MapOptions::max_files`].
+    pub files_capped: usize,
+    /// Files skipped because they are not valid UTF-8.
+    pub non_utf8_files: Vec<PathBuf>,
+    /// Files skipped for exceeding [`MapOptions::max_file_bytes`].
+    pub oversized_files: Vec<PathBuf>,
+    /// Files skipped because they could not be read.
+    pub unreadable_files: Vec<PathBuf>,
+    /// Files skipped because parsing them failed or panicked.
+    pub unparsed_files: Vec<UnparsedFile>,
+    /// Dependents of the symbols in [`MapOptions::changed_ranges`], most
+    /// referenced first.
+    pub blast_radius: Vec<graph::BlastRadius>,
+}
+
+/// A file left out of a [`RepoMap`] because it could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnparsedFile {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// The parse error, or the panic message if the parser panicked.
+    pub reason: String,
+}
+
+/// Generate a ranked map of the codebase at `root` with explicit options.
+///
+/// Like [`generate_map`], but applies [`MapOptions::max_files`] before any
+/// parsing and reports how many files the cap dropped, so callers can warn
+/// that the map is partial.
+///
+/// One bad file does not cost the whole map: a file that cannot be read is
+/// listed in [`RepoMap::unreadable_files`], and one whose parse fails or
+/// panics in [`RepoMap::unparsed_files`], and the map is built from the rest.
+///
+/// # Errors
+///
+/// Returns [`ArgusError`] if the root directory cannot be walked or the
+/// output cannot be formatted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::{generate_map_with, MapOptions};
+///
+/// let options = MapOptions { max_files: Some(5000), ..MapOptions::default() };
+/// let map = generate_map_with(Path::new("."), &options).unwrap();
+/// if map.files_capped > 0 {
+///     eprintln!("map is partial: skipped {} files", map.files_capped);
+/// }
+/// println!("{}", map.output);
+/// ```
+pub fn generate_map_with(root: &Path, options: &MapOptions) -> Result<RepoMap, ArgusError> {
+    let walker::Walk {
+        mut files,
+        non_utf8,
+        oversized,
+        unreadable,
+    } = walker::walk_repo_languages(root, &options.languages, options.max_file_bytes)?;
+    let files_capped = options
+        .max_files
+        .map_or(0, |max| walker::cap_files(&mut files, max));
+
+    let (all_symbols, all_references, unparsed) = parse_files(&files, |file| {
+        Ok((
+            parser::extract_symbols(file)?,
+            parser::extract_references(file)?,
+        ))
+    });
 
-    let mut symbol_graph = graph::SymbolGraph::build(all_symbols, all_references);
-    symbol_graph.compute_pagerank();
+    let mut symbol_graph = graph::SymbolGraph::build_ignoring(
+        all_symbols,
+        all_references,
+        &options.ignored_references,
+    )
+    .with_leaf_pruning_threshold(options.leaf_pruning_threshold);
+    let blast_radius = symbol_graph.blast_radius(&options.changed_ranges);
 
-    let ranked = if focus_files.is_empty() {
-        symbol_graph.ranked_symbols()
-    } else {
-        symbol_graph.ranked_symbols_for_files(focus_files)
+    let ranked = match options.focus_hops {
+        Some(hops) if !options.focus_files.is_empty() => {
+            symbol_graph.ranked_symbols_near_files(&options.focus_files, hops)
+        }
+        _ => {
+            symbol_graph.compute_pagerank();
+            if options.focus_files.is_empty() {
+                symbol_graph.ranked_symbols()
+            } else {
+                symbol_graph.ranked_symbols_for_files(&options.focus_files)
+            }
+        }
     };
 
-    let selected = budget::fit_to_budget(&ranked, max_tokens);
+    let selected = budget::fit_to_budget(&ranked, options.max_tokens);
+
+    let output = match options.format {
+        OutputFormat::Text => output::format_tree(&selected),
+        OutputFormat::Json => output::format_json(&selected)?,
+        OutputFormat::Markdown => output::format_markdown(&selected),
+        OutputFormat::Sarif => {
+            return Err(ArgusError::Config(
+                "SARIF output is only supported for the review and diff subcommands.".into(),
+            ))
+        }
+        OutputFormat::Csv => {
+            return Err(ArgusError::Config(
+                "CSV output is only supported for the history subcommand.".into(),
+            ))
+        }
+        OutputFormat::Junit => {
+            return Err(ArgusError::Config(
+                "JUnit output is only supported for the review subcommand.".into(),
+            ))
+        }
+    };
+
+    Ok(RepoMap {
+        output,
+        files_mapped: files.len() - unparsed.len(),
+        files_capped,
+        non_utf8_files: non_utf8,
+        oversized_files: oversized,
+        unreadable_files: unreadable,
+        unparsed_files: unparsed,
+        blast_radius,
+    })
+}
+
+thread_local! {
+    /// Set while this thread runs a parser whose panics are caught.
+    static QUIET_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install, once per process, a panic hook that stays silent for panics
+/// raised under [`QUIET_PANICS`] and hands every other panic to the hook
+/// that was installed before.
+fn silence_caught_panics() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !QUIET_PANICS.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Parse each file with `parse`, collecting the symbols and references of
+/// the files that succeed and an [`UnparsedFile`] for each one whose parse
+/// returns an error or panics. A caught panic prints nothing; it is
+/// reported through the [`UnparsedFile`] instead.
+fn parse_files(
+    files: &[walker::SourceFile],
+    parse: impl Fn(&walker::SourceFile) -> Result<(Vec<Symbol>, Vec<Reference>), ArgusError>,
+) -> (Vec<Symbol>, Vec<Reference>, Vec<UnparsedFile>) {
+    let mut symbols = Vec::new();
+    let mut references = Vec::new();
+    let mut unparsed = Vec::new();
+    silence_caught_panics();
+    for file in files {
+        QUIET_PANICS.with(|quiet| quiet.set(true));
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| parse(file)));
+        QUIET_PANICS.with(|quiet| quiet.set(false));
+        let reason = match outcome {
+            Ok(Ok((file_symbols, file_references))) => {
+                symbols.extend(file_symbols);
+                references.extend(file_references);
+                continue;
+            }
+            Ok(Err(e)) => e.to_string(),
+            Err(panic) => panic
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .map_or_else(
+                    || "parser panicked".into(),
+                    |msg| format!("parser panicked: {msg}"),
+                ),
+        };
+        unparsed.push(UnparsedFile {
+            path: file.path.clone(),
+            reason,
+        });
+    }
+    (symbols, references, unparsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use walker::{Language, SourceFile};
+
+    #[test]
+    fn one_unparseable_file_does_not_discard_the_map() {
+        let file = |path: &str, content: &str| SourceFile {
+            path: PathBuf::from(path),
+            language: Language::Rust,
+            content: content.into(),
+        };
+        let files = [
+            file("src/a.rs", "fn alpha() { beta(); }\n"),
+            file("src/bad.rs", "fn broken() {}\n"),
+            file("src/b.rs", "fn beta() {}\n"),
+            file("src/worse.rs", "fn worse() {}\n"),
+        ];
+
+        let (symbols, references, unparsed) =
+            parse_files(&files, |file| match file.path.to_str() {
+                Some("src/bad.rs") => Err(ArgusError::Parse("grammar unavailable".into())),
+                Some("src/worse.rs") => panic!("tree-sitter exploded"),
+                _ => Ok((
+                    parser::extract_symbols(file)?,
+                    parser::extract_references(file)?,
+                )),
+            });
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+        assert!(references.iter().any(|r| r.to_name == "beta"));
+        assert_eq!(unparsed.len(), 2);
+        assert_eq!(unparsed[0].path, PathBuf::from("src/bad.rs"));
+        assert!(unparsed[0].reason.contains("grammar unavailable"));
+        assert_eq!(unparsed[1].path, PathBuf::from("src/worse.rs"));
+        assert_eq!(unparsed[1].reason, "parser panicked: tree-sitter exploded");
+    }
+
+    #[test]
+    fn panics_outside_the_parser_are_not_silenced() {
+        let files = [SourceFile {
+            path: PathBuf::from("src/worse.rs"),
+            language: Language::Rust,
+            content: "fn worse() {}\n".into(),
+        }];
+
+        let (_, _, unparsed) = parse_files(&files, |_| panic!("tree-sitter exploded"));
 
-    match format {
-        OutputFormat::Text => Ok(output::format_tree(&selected)),
-        OutputFormat::Json => output::format_json(&selected),
-        OutputFormat::Markdown => Ok(output::format_markdown(&selected)),
-        OutputFormat::Sarif => Err(ArgusError::Config(
-            "SARIF output is only supported for the review subcommand.".into(),
-        )),
+        assert_eq!(unparsed.len(), 1);
+        assert!(!QUIET_PANICS.with(Cell::get));
     }
 }
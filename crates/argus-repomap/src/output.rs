@@ -155,7 +155,7 @@ pub fn format_markdown(symbols: &[&SymbolNode]) -> String {
     out
 }
 
-fn kind_label(kind: SymbolKind) -> &'static str {
+pub(crate) fn kind_label(kind: SymbolKind) -> &'static str {
     match kind {
         SymbolKind::Function => "fn",
         SymbolKind::Method => "method",
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use argus_core::ArgusError;
+use serde::Serialize;
 use tree_sitter::{Node, Parser};
 
 use crate::walker::{Language, SourceFile};
@@ -49,7 +50,7 @@ pub struct Symbol {
 /// let kind = SymbolKind::Function;
 /// assert_eq!(format!("{kind:?}"), "Function");
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum SymbolKind {
     Function,
     Method,
@@ -68,13 +69,14 @@ pub enum SymbolKind {
 ///
 /// ```
 /// use std::path::PathBuf;
-/// use argus_repomap::parser::Reference;
+/// use argus_repomap::parser::{Reference, REFERENCE_WEIGHT_CALL};
 ///
 /// let reference = Reference {
 ///     from_file: PathBuf::from("src/main.rs"),
 ///     from_symbol: Some("main".into()),
 ///     to_name: "Config".into(),
 ///     line: 5,
+///     weight: REFERENCE_WEIGHT_CALL,
 /// };
 /// assert_eq!(reference.to_name, "Config");
 /// ```
@@ -88,8 +90,18 @@ pub struct Reference {
     pub to_name: String,
     /// Line where the reference occurs.
     pub line: u32,
+    /// How strongly this reference ties the two symbols together, from its
+    /// syntactic context (see the `REFERENCE_WEIGHT_*` constants).
+    pub weight: f32,
 }
 
+/// Weight of a reference that calls the symbol.
+pub const REFERENCE_WEIGHT_CALL: f32 = 1.0;
+/// Weight of a reference that only mentions the symbol, e.g. in a type annotation.
+pub const REFERENCE_WEIGHT_MENTION: f32 = 0.5;
+/// Weight of a reference in an import or `use` declaration.
+pub const REFERENCE_WEIGHT_IMPORT: f32 = 0.25;
+
 /// Extract all symbols from a source file using tree-sitter.
 ///
 /// Returns an empty vec for unparseable files. Tree-sitter is error-tolerant,
@@ -1131,6 +1143,7 @@ fn collect_references(
                     from_symbol: current_enclosing.clone(),
                     to_name: name,
                     line: node.start_position().row as u32 + 1,
+                    weight: reference_weight(&node),
                 });
             }
         }
@@ -1142,6 +1155,54 @@ fn collect_references(
     }
 }
 
+/// Weight a reference by its syntactic context: a call, an import, or any
+/// other mention (type annotations, arguments, field types, ...).
+fn reference_weight(node: &Node) -> f32 {
+    let mut target = *node;
+    while let Some(parent) = target.parent() {
+        match parent.kind() {
+            // Path and member wrappers: look at what contains the whole path
+            "scoped_identifier"
+            | "field_expression"
+            | "member_expression"
+            | "attribute"
+            | "selector_expression"
+            | "navigation_expression" => target = parent,
+            "call_expression"
+            | "call"
+            | "method_invocation"
+            | "object_creation_expression"
+            | "function_call_expression"
+            | "macro_invocation" => {
+                let is_callee = parent
+                    .child_by_field_name("function")
+                    .or_else(|| parent.child_by_field_name("name"))
+                    .or_else(|| parent.child_by_field_name("macro"))
+                    .is_some_and(|callee| callee.id() == target.id());
+                return if is_callee {
+                    REFERENCE_WEIGHT_CALL
+                } else {
+                    REFERENCE_WEIGHT_MENTION
+                };
+            }
+            "use_declaration"
+            | "use_list"
+            | "scoped_use_list"
+            | "use_as_clause"
+            | "import_statement"
+            | "import_from_statement"
+            | "import_declaration"
+            | "import_spec"
+            | "import_clause"
+            | "namespace_use_declaration" => {
+                return REFERENCE_WEIGHT_IMPORT;
+            }
+            _ => return REFERENCE_WEIGHT_MENTION,
+        }
+    }
+    REFERENCE_WEIGHT_MENTION
+}
+
 /// Extract the signature of a node: text from start to opening `{` or `:`.
 fn extract_signature(node: &Node, source: &[u8]) -> String {
     let text = node_text(node, source);
@@ -1404,6 +1465,34 @@ fn helper() {}
         );
     }
 
+    #[test]
+    fn reference_weights_follow_syntactic_context() {
+        let file = SourceFile {
+            path: PathBuf::from("main.rs"),
+            language: Language::Rust,
+            content: r#"
+use crate::config::Loader;
+
+fn caller(limit: Limit) {
+    helper();
+    Config::new();
+}
+"#
+            .to_string(),
+        };
+        let refs = extract_references(&file).unwrap();
+        let weight = |name: &str| {
+            refs.iter()
+                .find(|r| r.to_name == name)
+                .map(|r| r.weight)
+                .unwrap_or_else(|| panic!("no reference to {name}"))
+        };
+        assert_eq!(weight("helper"), REFERENCE_WEIGHT_CALL);
+        assert_eq!(weight("new"), REFERENCE_WEIGHT_CALL);
+        assert_eq!(weight("Limit"), REFERENCE_WEIGHT_MENTION);
+        assert_eq!(weight("Loader"), REFERENCE_WEIGHT_IMPORT);
+    }
+
     #[test]
     fn parse_java_file() {
         let file = SourceFile {
@@ -0,0 +1,255 @@
+//! Self-test of the bundled tree-sitter grammars.
+//!
+//! Grammar upgrades can rename node kinds, which makes [`extract_symbols`]
+//! silently miss symbols. [`check_parsers`] runs a tiny snippet per
+//! supported language through the extractor and reports whether the one
+//! symbol it defines is still found, together with the grammar crate's
+//! version and ABI version. Used by `argus doctor --parsers`.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::parser::extract_symbols;
+use crate::walker::{Language, SourceFile};
+
+/// Language, file name, snippet, and the symbol the snippet defines.
+const SNIPPETS: &[(Language, &str, &str, &str)] = &[
+    (
+        Language::Rust,
+        "selftest.rs",
+        "pub fn greet() {}\n",
+        "greet",
+    ),
+    (
+        Language::Python,
+        "selftest.py",
+        "def greet():\n    pass\n",
+        "greet",
+    ),
+    (
+        Language::TypeScript,
+        "selftest.ts",
+        "function greet(name: string): string {\n    return name;\n}\n",
+        "greet",
+    ),
+    (
+        Language::JavaScript,
+        "selftest.js",
+        "function greet() {}\n",
+        "greet",
+    ),
+    (
+        Language::Go,
+        "selftest.go",
+        "package main\n\nfunc greet() {}\n",
+        "greet",
+    ),
+    (
+        Language::Java,
+        "Selftest.java",
+        "class Greeter {\n    void greet() {}\n}\n",
+        "greet",
+    ),
+    (
+        Language::C,
+        "selftest.c",
+        "int greet(void) { return 0; }\n",
+        "greet",
+    ),
+    (
+        Language::Cpp,
+        "selftest.cpp",
+        "int greet() { return 0; }\n",
+        "greet",
+    ),
+    (Language::Ruby, "selftest.rb", "def greet\nend\n", "greet"),
+    (
+        Language::Php,
+        "selftest.php",
+        "<?php\nfunction greet() {}\n",
+        "greet",
+    ),
+    (Language::Kotlin, "selftest.kt", "fun greet() {}\n", "greet"),
+    (
+        Language::Swift,
+        "selftest.swift",
+        "func greet() {}\n",
+        "greet",
+    ),
+];
+
+/// Grammar crate and locked version behind each language.
+///
+/// The build script reads the versions from `Cargo.lock`; a build without
+/// the workspace lock file reports them as `"unknown"`.
+fn grammar_crate(language: Language) -> (&'static str, &'static str) {
+    let (grammar, version) = match language {
+        Language::Rust => (
+            "tree-sitter-rust",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_RUST"),
+        ),
+        Language::Python => (
+            "tree-sitter-python",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_PYTHON"),
+        ),
+        Language::TypeScript => (
+            "tree-sitter-typescript",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_TYPESCRIPT"),
+        ),
+        Language::JavaScript => (
+            "tree-sitter-javascript",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_JAVASCRIPT"),
+        ),
+        Language::Go => (
+            "tree-sitter-go",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_GO"),
+        ),
+        Language::Java => (
+            "tree-sitter-java",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_JAVA"),
+        ),
+        Language::C => ("tree-sitter-c", option_env!("ARGUS_GRAMMAR_TREE_SITTER_C")),
+        Language::Cpp => (
+            "tree-sitter-cpp",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_CPP"),
+        ),
+        Language::Ruby => (
+            "tree-sitter-ruby",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_RUBY"),
+        ),
+        Language::Php => (
+            "tree-sitter-php",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_PHP"),
+        ),
+        Language::Kotlin => (
+            "tree-sitter-kotlin-ng",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_KOTLIN_NG"),
+        ),
+        Language::Swift => (
+            "tree-sitter-swift",
+            option_env!("ARGUS_GRAMMAR_TREE_SITTER_SWIFT"),
+        ),
+        _ => return ("", ""),
+    };
+    (grammar, version.unwrap_or("unknown"))
+}
+
+/// Result of running one language's built-in snippet.
+///
+/// # Examples
+///
+/// ```
+/// use argus_repomap::selftest::check_parsers;
+///
+/// let rust = check_parsers().into_iter().find(|c| c.language == "rust").unwrap();
+/// assert!(rust.passed, "{:?}", rust.error);
+/// assert_eq!(rust.grammar, "tree-sitter-rust");
+/// assert!(rust.abi_version > 0);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParserCheck {
+    /// Lowercase language name, e.g. `"rust"` or `"cpp"`.
+    pub language: String,
+    /// Name of the grammar crate, e.g. `"tree-sitter-rust"`.
+    pub grammar: &'static str,
+    /// Version of the grammar crate linked into this build, or `"unknown"`
+    /// when built outside the workspace.
+    pub grammar_version: &'static str,
+    /// ABI version the grammar was generated with.
+    pub abi_version: usize,
+    /// Symbol the snippet defines.
+    pub expected_symbol: &'static str,
+    /// Symbols the extractor actually found.
+    pub found: Vec<String>,
+    /// Whether `expected_symbol` was among them.
+    pub passed: bool,
+    /// Why the check failed, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Oldest and newest grammar ABI versions the linked tree-sitter can load.
+pub fn supported_abi_range() -> (usize, usize) {
+    (
+        tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION,
+        tree_sitter::LANGUAGE_VERSION,
+    )
+}
+
+/// Run every supported language's snippet through [`extract_symbols`].
+///
+/// Returns one check per language, in a fixed order. A failed check means
+/// the grammar no longer produces what the extractor expects, usually
+/// after a grammar version bump.
+pub fn check_parsers() -> Vec<ParserCheck> {
+    SNIPPETS
+        .iter()
+        .map(|&(language, file_name, snippet, expected_symbol)| {
+            let abi_version = language
+                .tree_sitter_language()
+                .map_or(0, |grammar| grammar.version());
+            let file = SourceFile {
+                path: PathBuf::from(file_name),
+                language,
+                content: snippet.to_string(),
+            };
+            let (found, error): (Vec<String>, Option<String>) = match extract_symbols(&file) {
+                Ok(symbols) => (symbols.into_iter().map(|s| s.name).collect(), None),
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+            let (grammar, grammar_version) = grammar_crate(language);
+            let passed = found.iter().any(|name| name == expected_symbol);
+            let error = error.or_else(|| {
+                (!passed).then(|| format!("expected symbol `{expected_symbol}` was not extracted"))
+            });
+            ParserCheck {
+                language: format!("{language:?}").to_lowercase(),
+                grammar,
+                grammar_version,
+                abi_version,
+                expected_symbol,
+                found,
+                passed,
+                error,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_check_per_language_and_tested_grammars_pass() {
+        let checks = check_parsers();
+        let mut languages: Vec<&str> = checks.iter().map(|c| c.language.as_str()).collect();
+        languages.sort_unstable();
+        languages.dedup();
+        assert_eq!(languages.len(), 12);
+
+        // The languages whose extractors the parser tests pin down
+        for check in &checks {
+            if ["rust", "python", "typescript", "java", "c", "cpp", "ruby"]
+                .contains(&check.language.as_str())
+            {
+                assert!(check.passed, "{}: {:?}", check.language, check.error);
+            }
+        }
+    }
+
+    #[test]
+    fn grammar_versions_match_the_lock_file() {
+        let lock_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../Cargo.lock");
+        let lock = std::fs::read_to_string(lock_path).unwrap();
+        for check in check_parsers() {
+            let entry = format!(
+                "name = \"{}\"\nversion = \"{}\"",
+                check.grammar, check.grammar_version
+            );
+            assert!(lock.contains(&entry), "{entry} is not in Cargo.lock");
+        }
+    }
+}
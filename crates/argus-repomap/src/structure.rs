@@ -0,0 +1,455 @@
+//! Structural comparison of the repo map at two revisions.
+//!
+//! [`diff_structure`] ranks the symbols of both file sets the way the repo
+//! map does and reports top-level symbols that were added, removed, or
+//! moved to another file, plus symbols whose PageRank changed enough to
+//! matter, such as a helper that became a hub. Used by `argus map --diff`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use argus_core::ArgusError;
+use serde::Serialize;
+
+use crate::graph::SymbolGraph;
+use crate::output::kind_label;
+use crate::parser::{extract_references, extract_symbols, SymbolKind};
+use crate::walker::SourceFile;
+
+/// A rank change is reported when the score at least doubles or halves.
+const RANK_SHIFT_FACTOR: f64 = 2.0;
+
+/// A rank change is only reported when the symbol is at least this
+/// central on one side, so shuffles among leaf symbols stay quiet.
+const HUB_SCORE: f64 = 2.0;
+
+/// Entries listed per section in text and markdown output.
+const LISTED_PER_SECTION: usize = 20;
+
+/// A top-level symbol and how central it is.
+///
+/// `score` is the symbol's PageRank divided by the mean PageRank of its
+/// map, so `1.0` is an average symbol and `3.0` one referenced three times
+/// as heavily. Scores from maps of different sizes stay comparable.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedSymbol {
+    /// Symbol name.
+    pub name: String,
+    /// What kind of symbol it is.
+    pub kind: SymbolKind,
+    /// File defining it.
+    pub file: PathBuf,
+    /// PageRank relative to the map's mean.
+    pub score: f64,
+}
+
+/// A top-level symbol that is defined in a different file at the newer
+/// revision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedSymbol {
+    /// Symbol name.
+    pub name: String,
+    /// What kind of symbol it is.
+    pub kind: SymbolKind,
+    /// File defining it at the older revision.
+    pub from: PathBuf,
+    /// File defining it at the newer revision.
+    pub to: PathBuf,
+}
+
+/// A symbol, present at both revisions, whose score changed significantly.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankShift {
+    /// Symbol name.
+    pub name: String,
+    /// What kind of symbol it is.
+    pub kind: SymbolKind,
+    /// File defining it at the newer revision.
+    pub file: PathBuf,
+    /// Score at the older revision.
+    pub before: f64,
+    /// Score at the newer revision.
+    pub after: f64,
+}
+
+/// How the symbol structure changed between two revisions.
+///
+/// # Examples
+///
+/// ```
+/// use argus_repomap::structure::StructureDiff;
+///
+/// let diff = StructureDiff::default();
+/// assert!(diff.is_empty());
+/// assert!(diff.to_text().contains("No structural changes"));
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructureDiff {
+    /// New top-level symbols, most central first.
+    pub added: Vec<RankedSymbol>,
+    /// Top-level symbols that are gone, most central (before) first.
+    pub removed: Vec<RankedSymbol>,
+    /// Top-level symbols now defined in another file, by name.
+    pub moved: Vec<MovedSymbol>,
+    /// Significant score changes, largest change first.
+    pub rank_shifts: Vec<RankShift>,
+}
+
+/// Rank the top-level symbols in `files`, most central first.
+///
+/// Methods and impl blocks are left out; their names repeat across types,
+/// so they say little about structure. References to a name in
+/// `ignored_references` create no edges, as in the repo map.
+///
+/// # Errors
+///
+/// Returns [`ArgusError`] if a file's grammar cannot be loaded.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::structure::rank_symbols;
+/// use argus_repomap::walker::{Language, SourceFile};
+///
+/// let file = SourceFile {
+///     path: PathBuf::from("lib.rs"),
+///     language: Language::Rust,
+///     content: "fn a() { b(); }\nfn b() {}\n".into(),
+/// };
+/// let ranked = rank_symbols(&[file], &[]).unwrap();
+/// assert_eq!(ranked[0].name, "b");
+/// ```
+pub fn rank_symbols(
+    files: &[SourceFile],
+    ignored_references: &[String],
+) -> Result<Vec<RankedSymbol>, ArgusError> {
+    let mut symbols = Vec::new();
+    let mut references = Vec::new();
+    for file in files {
+        symbols.extend(extract_symbols(file)?);
+        references.extend(extract_references(file)?);
+    }
+
+    let mut graph = SymbolGraph::build_ignoring(symbols, references, ignored_references);
+    graph.compute_pagerank();
+    let ranked = graph.ranked_symbols();
+    let mean = ranked.iter().map(|node| node.rank).sum::<f64>() / ranked.len().max(1) as f64;
+
+    Ok(ranked
+        .into_iter()
+        .filter(|node| !matches!(node.symbol.kind, SymbolKind::Method | SymbolKind::Impl))
+        .map(|node| RankedSymbol {
+            name: node.symbol.name.clone(),
+            kind: node.symbol.kind,
+            file: node.symbol.file.clone(),
+            score: if mean > 0.0 { node.rank / mean } else { 0.0 },
+        })
+        .collect())
+}
+
+/// Compare the top-level symbols of `before` and `after`.
+///
+/// Symbols are matched by kind and name. One that is defined once on each
+/// side, in different files, counts as moved; otherwise each definition is
+/// matched by file. A rank shift is reported when a matched symbol's score
+/// at least doubles or halves and it is a hub (score 2.0 or more) on at
+/// least one side.
+///
+/// # Errors
+///
+/// Returns [`ArgusError`] if a file's grammar cannot be loaded.
+pub fn diff_structure(
+    before: &[SourceFile],
+    after: &[SourceFile],
+    ignored_references: &[String],
+) -> Result<StructureDiff, ArgusError> {
+    let old = by_kind_and_name(rank_symbols(before, ignored_references)?);
+    let new = by_kind_and_name(rank_symbols(after, ignored_references)?);
+    let keys: BTreeSet<&(SymbolKind, String)> = old.keys().chain(new.keys()).collect();
+
+    let mut diff = StructureDiff::default();
+    let mut matched: Vec<(&RankedSymbol, &RankedSymbol)> = Vec::new();
+    for key in keys {
+        let (olds, news) = (
+            old.get(key).map_or(&[][..], Vec::as_slice),
+            new.get(key).map_or(&[][..], Vec::as_slice),
+        );
+        if let ([was], [now]) = (olds, news) {
+            if was.file != now.file {
+                diff.moved.push(MovedSymbol {
+                    name: now.name.clone(),
+                    kind: now.kind,
+                    from: was.file.clone(),
+                    to: now.file.clone(),
+                });
+                matched.push((was, now));
+                continue;
+            }
+        }
+        for now in news {
+            match olds.iter().find(|was| was.file == now.file) {
+                Some(was) => matched.push((was, now)),
+                None => diff.added.push(now.clone()),
+            }
+        }
+        for was in olds {
+            if !news.iter().any(|now| now.file == was.file) {
+                diff.removed.push(was.clone());
+            }
+        }
+    }
+
+    diff.rank_shifts = matched
+        .into_iter()
+        .filter(|(was, now)| {
+            let ratio = shift_ratio(was.score, now.score);
+            ratio >= RANK_SHIFT_FACTOR && was.score.max(now.score) >= HUB_SCORE
+        })
+        .map(|(was, now)| RankShift {
+            name: now.name.clone(),
+            kind: now.kind,
+            file: now.file.clone(),
+            before: was.score,
+            after: now.score,
+        })
+        .collect();
+
+    let by_score = |a: &RankedSymbol, b: &RankedSymbol| b.score.total_cmp(&a.score);
+    diff.added.sort_by(by_score);
+    diff.removed.sort_by(by_score);
+    diff.rank_shifts
+        .sort_by(|a, b| shift_ratio(b.before, b.after).total_cmp(&shift_ratio(a.before, a.after)));
+    Ok(diff)
+}
+
+/// Group ranked symbols by kind and name, for matching across revisions.
+fn by_kind_and_name(
+    symbols: Vec<RankedSymbol>,
+) -> BTreeMap<(SymbolKind, String), Vec<RankedSymbol>> {
+    let mut grouped: BTreeMap<(SymbolKind, String), Vec<RankedSymbol>> = BTreeMap::new();
+    for symbol in symbols {
+        grouped
+            .entry((symbol.kind, symbol.name.clone()))
+            .or_default()
+            .push(symbol);
+    }
+    grouped
+}
+
+/// How many times larger the bigger score is than the smaller one.
+fn shift_ratio(before: f64, after: f64) -> f64 {
+    let (low, high) = if before < after {
+        (before, after)
+    } else {
+        (after, before)
+    };
+    if low > 0.0 {
+        high / low
+    } else {
+        f64::INFINITY
+    }
+}
+
+impl StructureDiff {
+    /// Whether nothing changed structurally.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.rank_shifts.is_empty()
+    }
+
+    /// Render as plain text, listing up to 20 entries per section.
+    pub fn to_text(&self) -> String {
+        let mut out = String::from("Structural changes\n==================\n\n");
+        if self.is_empty() {
+            out.push_str("No structural changes.\n");
+            return out;
+        }
+        section(&mut out, "Added", &self.added, |out, s| {
+            let _ = writeln!(
+                out,
+                "  + {} {}  {}  (score {:.1})",
+                kind_label(s.kind),
+                s.name,
+                s.file.display(),
+                s.score
+            );
+        });
+        section(&mut out, "Removed", &self.removed, |out, s| {
+            let _ = writeln!(
+                out,
+                "  - {} {}  {}",
+                kind_label(s.kind),
+                s.name,
+                s.file.display()
+            );
+        });
+        section(&mut out, "Moved", &self.moved, |out, m| {
+            let _ = writeln!(
+                out,
+                "  ~ {} {}  {} -> {}",
+                kind_label(m.kind),
+                m.name,
+                m.from.display(),
+                m.to.display()
+            );
+        });
+        section(&mut out, "Rank shifts", &self.rank_shifts, |out, r| {
+            let arrow = if r.after > r.before { '↑' } else { '↓' };
+            let _ = writeln!(
+                out,
+                "  {arrow} {} {}  {}  (score {:.1} -> {:.1})",
+                kind_label(r.kind),
+                r.name,
+                r.file.display(),
+                r.before,
+                r.after
+            );
+        });
+        out
+    }
+
+    /// Render as markdown, listing up to 20 entries per section.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Structural Changes\n\n");
+        if self.is_empty() {
+            out.push_str("No structural changes.\n");
+            return out;
+        }
+        markdown_section(&mut out, "Added", &self.added, |s| {
+            format!(
+                "**{}** `{}` in `{}` (score {:.1})",
+                kind_label(s.kind),
+                s.name,
+                s.file.display(),
+                s.score
+            )
+        });
+        markdown_section(&mut out, "Removed", &self.removed, |s| {
+            format!(
+                "**{}** `{}` in `{}`",
+                kind_label(s.kind),
+                s.name,
+                s.file.display()
+            )
+        });
+        markdown_section(&mut out, "Moved", &self.moved, |m| {
+            format!(
+                "**{}** `{}`: `{}` → `{}`",
+                kind_label(m.kind),
+                m.name,
+                m.from.display(),
+                m.to.display()
+            )
+        });
+        markdown_section(&mut out, "Rank shifts", &self.rank_shifts, |r| {
+            format!(
+                "**{}** `{}` in `{}`: score {:.1} → {:.1}",
+                kind_label(r.kind),
+                r.name,
+                r.file.display(),
+                r.before,
+                r.after
+            )
+        });
+        out
+    }
+}
+
+/// Append a text section for non-empty `items`, capped at
+/// [`LISTED_PER_SECTION`] entries.
+fn section<T>(out: &mut String, title: &str, items: &[T], mut line: impl FnMut(&mut String, &T)) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "{title} ({}):", items.len());
+    for item in items.iter().take(LISTED_PER_SECTION) {
+        line(out, item);
+    }
+    if items.len() > LISTED_PER_SECTION {
+        let _ = writeln!(out, "  ... and {} more", items.len() - LISTED_PER_SECTION);
+    }
+    out.push('\n');
+}
+
+/// Append a markdown section for non-empty `items`, capped at
+/// [`LISTED_PER_SECTION`] entries.
+fn markdown_section<T>(out: &mut String, title: &str, items: &[T], item: impl Fn(&T) -> String) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "## {title} ({})\n", items.len());
+    for entry in items.iter().take(LISTED_PER_SECTION) {
+        let _ = writeln!(out, "- {}", item(entry));
+    }
+    if items.len() > LISTED_PER_SECTION {
+        let _ = writeln!(out, "- … and {} more", items.len() - LISTED_PER_SECTION);
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::walker::Language;
+
+    fn rust(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(path),
+            language: Language::Rust,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn reports_added_removed_moved_and_new_hubs() {
+        let before = [rust(
+            "src/a.rs",
+            "fn helper() {}\nfn legacy() {}\nfn relocated() {}\nfn main() {}\n",
+        )];
+        let after = [
+            rust(
+                "src/a.rs",
+                "fn helper() {}\n\
+                 fn main() { helper(); }\n\
+                 fn load() { helper(); }\n\
+                 fn save() { helper(); }\n",
+            ),
+            rust("src/b.rs", "fn relocated() { helper(); }\n"),
+        ];
+
+        let diff = diff_structure(&before, &after, &[]).unwrap();
+        let names = |symbols: &[RankedSymbol]| -> Vec<String> {
+            let mut names: Vec<String> = symbols.iter().map(|s| s.name.clone()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&diff.added), ["load", "save"]);
+        assert_eq!(names(&diff.removed), ["legacy"]);
+        assert_eq!(
+            diff.moved,
+            [MovedSymbol {
+                name: "relocated".into(),
+                kind: SymbolKind::Function,
+                from: PathBuf::from("src/a.rs"),
+                to: PathBuf::from("src/b.rs"),
+            }]
+        );
+        assert_eq!(diff.rank_shifts.len(), 1);
+        let hub = &diff.rank_shifts[0];
+        assert_eq!(hub.name, "helper");
+        assert!(hub.before < 1.5 && hub.after >= HUB_SCORE, "{hub:?}");
+
+        let text = diff.to_text();
+        assert!(text.contains("Moved (1):\n  ~ fn relocated  src/a.rs -> src/b.rs\n"));
+        assert!(text.contains("↑ fn helper  src/a.rs"));
+        assert!(diff_structure(&before, &before, &[]).unwrap().is_empty());
+    }
+}
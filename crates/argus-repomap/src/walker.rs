@@ -2,8 +2,9 @@ use std::path::{Path, PathBuf};
 
 use argus_core::ArgusError;
 
-/// Maximum file size to process (1 MB).
-const MAX_FILE_SIZE: u64 = 1_048_576;
+/// Default maximum file size to process (1 MB), overridable with
+/// `[path] max_file_bytes`.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 1_048_576;
 
 /// Number of bytes to check for binary detection.
 const BINARY_CHECK_SIZE: usize = 8192;
@@ -88,6 +89,59 @@ impl Language {
         }
     }
 
+    /// Look up a language by its lowercase name, such as `"python"` or
+    /// `"cpp"`, as used in config files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_repomap::walker::Language;
+    ///
+    /// assert_eq!(Language::from_name("typescript"), Language::TypeScript);
+    /// assert_eq!(Language::from_name("Python"), Language::Python);
+    /// assert_eq!(Language::from_name("cobol"), Language::Unknown);
+    /// ```
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" => Language::Rust,
+            "python" => Language::Python,
+            "typescript" => Language::TypeScript,
+            "javascript" => Language::JavaScript,
+            "go" => Language::Go,
+            "java" => Language::Java,
+            "c" => Language::C,
+            "cpp" => Language::Cpp,
+            "ruby" => Language::Ruby,
+            "php" => Language::Php,
+            "kotlin" => Language::Kotlin,
+            "swift" => Language::Swift,
+            _ => Language::Unknown,
+        }
+    }
+
+    /// Languages whose files usually sit alongside and reference this one's:
+    /// TypeScript and JavaScript, and C and C++ (which share `.h` headers).
+    /// Empty for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_repomap::walker::Language;
+    ///
+    /// assert!(Language::TypeScript.related().contains(&Language::JavaScript));
+    /// assert!(Language::Cpp.related().contains(&Language::C));
+    /// assert!(Language::Rust.related().is_empty());
+    /// ```
+    pub fn related(self) -> &'static [Language] {
+        match self {
+            Language::TypeScript => &[Language::JavaScript],
+            Language::JavaScript => &[Language::TypeScript],
+            Language::C => &[Language::Cpp],
+            Language::Cpp => &[Language::C],
+            _ => &[],
+        }
+    }
+
     /// Get the tree-sitter language grammar for this language.
     ///
     /// Returns `None` for `Language::Unknown`.
@@ -110,10 +164,18 @@ impl Language {
     }
 }
 
+/// Argus-only ignore file, in gitignore syntax.
+pub const IGNORE_FILE_NAME: &str = ".argusignore";
+
 /// Walk a repository, respecting `.gitignore`, returning parseable source files.
 ///
-/// Skips binary files, files larger than 1 MB, and files with unknown extensions.
-/// Returned paths are relative to `root`.
+/// An `.argusignore` file (gitignore syntax) excludes paths from Argus only,
+/// and works outside git repositories too.
+///
+/// Skips binary files, files that are not valid UTF-8, files larger than
+/// 1 MB, and files with unknown extensions. Returned paths are relative to
+/// `root`. Use [`walk_repo_detailed`] to learn which files were skipped, or
+/// to change the size limit.
 ///
 /// # Errors
 ///
@@ -131,8 +193,166 @@ impl Language {
 /// }
 /// ```
 pub fn walk_repo(root: &Path) -> Result<Vec<SourceFile>, ArgusError> {
-    let walker = ignore::WalkBuilder::new(root).build();
+    Ok(walk_repo_detailed(root, false, DEFAULT_MAX_FILE_BYTES)?.files)
+}
+
+/// Walk a repository like [`walk_repo`], but also return text files without a
+/// tree-sitter grammar (docs, YAML, SQL, ...) tagged as [`Language::Unknown`].
+///
+/// Binary files, non-UTF-8 files, and files larger than 1 MB are still skipped.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::walker::{walk_repo_all, Language};
+///
+/// let files = walk_repo_all(Path::new(".")).unwrap();
+/// let docs = files.iter().filter(|f| f.language == Language::Unknown).count();
+/// println!("{docs} non-code text files");
+/// ```
+pub fn walk_repo_all(root: &Path) -> Result<Vec<SourceFile>, ArgusError> {
+    Ok(walk_repo_detailed(root, true, DEFAULT_MAX_FILE_BYTES)?.files)
+}
+
+/// Keep at most `max_files` files, dropping the rest deterministically.
+///
+/// Files are ordered by path and the first `max_files` are kept, so the
+/// same tree always yields the same subset. Returns how many files were
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::walker::{cap_files, Language, SourceFile};
+///
+/// let file = |p: &str| SourceFile {
+///     path: PathBuf::from(p),
+///     language: Language::Rust,
+///     content: String::new(),
+/// };
+/// let mut files = vec![file("c.rs"), file("a.rs"), file("b.rs")];
+/// assert_eq!(cap_files(&mut files, 2), 1);
+/// assert_eq!(files[0].path, PathBuf::from("a.rs"));
+/// assert_eq!(files[1].path, PathBuf::from("b.rs"));
+/// ```
+pub fn cap_files(files: &mut Vec<SourceFile>, max_files: usize) -> usize {
+    if files.len() <= max_files {
+        return 0;
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let dropped = files.len() - max_files;
+    files.truncate(max_files);
+    dropped
+}
+
+/// Source files found by [`walk_repo_detailed`], plus the files it skipped
+/// because their contents are not valid UTF-8, they are too large, or they
+/// could not be read.
+///
+/// # Examples
+///
+/// ```
+/// use argus_repomap::walker::Walk;
+///
+/// let walk = Walk::default();
+/// assert!(walk.files.is_empty() && walk.non_utf8.is_empty() && walk.oversized.is_empty());
+/// assert!(walk.unreadable.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Walk {
+    /// Readable source files, as returned by [`walk_repo`].
+    pub files: Vec<SourceFile>,
+    /// Paths (relative to the root) of text files that are not valid UTF-8,
+    /// such as Latin-1 sources.
+    pub non_utf8: Vec<PathBuf>,
+    /// Paths (relative to the root) of files larger than the size limit,
+    /// such as generated bundles committed by accident.
+    pub oversized: Vec<PathBuf>,
+    /// Paths (relative to the root) of files whose metadata or contents
+    /// could not be read, such as files without read permission.
+    pub unreadable: Vec<PathBuf>,
+}
+
+/// Walk a repository like [`walk_repo`] (or [`walk_repo_all`] when
+/// `include_unknown` is set), and also report files skipped for not being
+/// valid UTF-8 or for exceeding `max_file_bytes`.
+///
+/// Binary files (a NUL byte in the first 8 KB) are skipped silently; text
+/// in another encoding is listed in [`Walk::non_utf8`], and files larger
+/// than `max_file_bytes` in [`Walk::oversized`], and files that fail to
+/// read in [`Walk::unreadable`], so callers can warn instead of producing
+/// quietly incomplete results. Oversized files are never read.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::walker::{walk_repo_detailed, DEFAULT_MAX_FILE_BYTES};
+///
+/// let walk = walk_repo_detailed(Path::new("."), false, DEFAULT_MAX_FILE_BYTES).unwrap();
+/// for path in &walk.non_utf8 {
+///     eprintln!("skipped {} (not UTF-8)", path.display());
+/// }
+/// ```
+pub fn walk_repo_detailed(
+    root: &Path,
+    include_unknown: bool,
+    max_file_bytes: u64,
+) -> Result<Walk, ArgusError> {
+    walk(root, include_unknown, max_file_bytes, &[])
+}
+
+/// Walk a repository like [`walk_repo_detailed`], keeping only source
+/// files in `languages` (all supported languages when it is empty).
+///
+/// Files in other languages are skipped before they are read, so they
+/// cost nothing and are not reported as non-UTF-8 or oversized.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_repomap::walker::{walk_repo_languages, Language, DEFAULT_MAX_FILE_BYTES};
+///
+/// let walk = walk_repo_languages(Path::new("."), &[Language::Python], DEFAULT_MAX_FILE_BYTES)
+///     .unwrap();
+/// assert!(walk.files.iter().all(|f| f.language == Language::Python));
+/// ```
+pub fn walk_repo_languages(
+    root: &Path,
+    languages: &[Language],
+    max_file_bytes: u64,
+) -> Result<Walk, ArgusError> {
+    walk(root, false, max_file_bytes, languages)
+}
+
+fn walk(
+    root: &Path,
+    include_unknown: bool,
+    max_file_bytes: u64,
+    languages: &[Language],
+) -> Result<Walk, ArgusError> {
+    let walker = ignore::WalkBuilder::new(root)
+        .add_custom_ignore_filename(IGNORE_FILE_NAME)
+        .build();
     let mut files = Vec::new();
+    let mut non_utf8 = Vec::new();
+    let mut oversized = Vec::new();
+    let mut unreadable = Vec::new();
 
     for entry in walker {
         let entry = match entry {
@@ -149,51 +369,135 @@ pub fn walk_repo(root: &Path) -> Result<Vec<SourceFile>, ArgusError> {
 
         let path = entry.path();
 
-        // Check file size
-        let metadata = match std::fs::metadata(path) {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-        if metadata.len() > MAX_FILE_SIZE {
+        // Detect language from extension
+        let language = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map_or(Language::Unknown, Language::from_extension);
+        if language == Language::Unknown && !include_unknown {
+            continue;
+        }
+        if !languages.is_empty() && !languages.contains(&language) {
             continue;
         }
 
-        // Detect language from extension
-        let ext = match path.extension().and_then(|e| e.to_str()) {
-            Some(e) => e,
-            None => continue,
+        // Make path relative to root
+        let relative = match path.strip_prefix(root) {
+            Ok(r) => r.to_path_buf(),
+            Err(_) => path.to_path_buf(),
+        };
+
+        // Check file size
+        let metadata = match std::fs::metadata(path) {
+            Ok(m) => m,
+            Err(_) => {
+                unreadable.push(relative);
+                continue;
+            }
         };
-        let language = Language::from_extension(ext);
-        if language == Language::Unknown {
+        if metadata.len() > max_file_bytes {
+            oversized.push(relative);
             continue;
         }
 
         // Read content
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => continue,
+        let bytes = match std::fs::read(path) {
+            Ok(b) => b,
+            Err(_) => {
+                unreadable.push(relative);
+                continue;
+            }
         };
 
         // Check for binary content (null bytes in first 8KB)
-        let check_len = content.len().min(BINARY_CHECK_SIZE);
-        if content.as_bytes()[..check_len].contains(&0) {
+        let check_len = bytes.len().min(BINARY_CHECK_SIZE);
+        if bytes[..check_len].contains(&0) {
             continue;
         }
 
-        // Make path relative to root
-        let relative = match path.strip_prefix(root) {
-            Ok(r) => r.to_path_buf(),
-            Err(_) => path.to_path_buf(),
-        };
+        match String::from_utf8(bytes) {
+            Ok(content) => files.push(SourceFile {
+                path: relative,
+                language,
+                content,
+            }),
+            Err(_) => non_utf8.push(relative),
+        }
+    }
+
+    Ok(Walk {
+        files,
+        non_utf8,
+        oversized,
+        unreadable,
+    })
+}
+
+/// Ignore rules read from somewhere other than the working tree, such as
+/// the blobs of a git tree, applied the way the walker applies them on disk.
+///
+/// Each ignore file covers its own directory, and a deeper file takes
+/// precedence over a shallower one. Hidden paths are always ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_repomap::walker::IgnoreRules;
+///
+/// let mut rules = IgnoreRules::default();
+/// rules.add(Path::new(""), "gen/\n");
+/// rules.add(Path::new("gen"), "!keep.rs\n");
+/// assert!(rules.is_ignored(Path::new("gen/out.rs")));
+/// assert!(!rules.is_ignored(Path::new("gen/keep.rs")));
+/// assert!(rules.is_ignored(Path::new(".github/x.rs")));
+/// assert!(!rules.is_ignored(Path::new("src/lib.rs")));
+/// ```
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    // Deepest directory first
+    rules: Vec<(PathBuf, ignore::gitignore::Gitignore)>,
+}
 
-        files.push(SourceFile {
-            path: relative,
-            language,
-            content,
-        });
+impl IgnoreRules {
+    /// Names of the ignore files the walker honors in every directory.
+    pub const FILE_NAMES: &'static [&'static str] = &[".gitignore", ".ignore", IGNORE_FILE_NAME];
+
+    /// Add the rules of an ignore file in `dir`, relative to the repository
+    /// root. Lines that are not valid globs are skipped.
+    pub fn add(&mut self, dir: &Path, contents: &str) {
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+        for line in contents.lines() {
+            let _ = builder.add_line(None, line);
+        }
+        if let Ok(gitignore) = builder.build() {
+            self.rules.push((dir.to_path_buf(), gitignore));
+            self.rules
+                .sort_by_key(|(dir, _)| std::cmp::Reverse(dir.components().count()));
+        }
     }
 
-    Ok(files)
+    /// Whether the walker would skip the file at `path`, relative to the
+    /// repository root.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let hidden = path
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        if hidden {
+            return true;
+        }
+        for (dir, gitignore) in &self.rules {
+            if !path.starts_with(dir) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(path, false) {
+                ignore::Match::Ignore(_) => return true,
+                ignore::Match::Whitelist(_) => return false,
+                ignore::Match::None => {}
+            }
+        }
+        false
+    }
 }
 
 #[cfg(test)]
@@ -247,6 +551,19 @@ mod tests {
         assert!(languages.contains(&Language::Ruby));
     }
 
+    #[test]
+    fn walk_respects_argusignore_without_git() {
+        let dir = make_temp_repo();
+        let root = dir.path();
+        fs::write(root.join(IGNORE_FILE_NAME), "*.rb\n").unwrap();
+
+        let files = walk_repo(root).unwrap();
+        assert!(!files.is_empty());
+        assert!(files
+            .iter()
+            .all(|f| f.path.extension().is_none_or(|ext| ext != "rb")));
+    }
+
     #[test]
     fn walk_respects_gitignore() {
         let dir = make_temp_repo();
@@ -290,6 +607,20 @@ mod tests {
         assert_eq!(files[0].path, PathBuf::from("normal.rs"));
     }
 
+    #[test]
+    fn walk_reports_non_utf8_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("latin1.rs"), b"fn caf\xe9() {}").unwrap();
+        fs::write(root.join("normal.rs"), "fn normal() {}").unwrap();
+        fs::write(root.join("binary.rs"), b"fn a() { \0\xff }").unwrap();
+
+        let walk = walk_repo_detailed(root, false, DEFAULT_MAX_FILE_BYTES).unwrap();
+        assert_eq!(walk.files.len(), 1);
+        assert_eq!(walk.files[0].path, PathBuf::from("normal.rs"));
+        assert_eq!(walk.non_utf8, [PathBuf::from("latin1.rs")]);
+    }
+
     #[test]
     fn walk_skips_large_and_unknown_files() {
         let dir = tempfile::tempdir().unwrap();
@@ -309,4 +640,64 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, PathBuf::from("ok.rs"));
     }
+
+    #[test]
+    fn walk_reports_files_above_the_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("generated.rs"), "x".repeat(101)).unwrap();
+        fs::write(root.join("small.rs"), "fn small() {}").unwrap();
+        fs::write(root.join("big.txt"), "x".repeat(200)).unwrap();
+
+        let walk = walk_repo_detailed(root, false, 100).unwrap();
+        assert_eq!(walk.files.len(), 1);
+        assert_eq!(walk.files[0].path, PathBuf::from("small.rs"));
+        // Files the walk would skip anyway are not reported as oversized
+        assert_eq!(walk.oversized, [PathBuf::from("generated.rs")]);
+    }
+
+    #[test]
+    fn config_language_names_are_all_known() {
+        for name in argus_core::REPO_MAP_LANGUAGES {
+            assert_ne!(Language::from_name(name), Language::Unknown, "{name}");
+        }
+    }
+
+    #[test]
+    fn walk_languages_skips_other_languages_unread() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("app.py"), "def app():\n    pass\n").unwrap();
+        fs::write(root.join("gen.ts"), "x".repeat(200)).unwrap();
+        fs::write(root.join("lib.rs"), "fn lib() {}").unwrap();
+
+        let walk = walk_repo_languages(root, &[Language::Python], 100).unwrap();
+        assert_eq!(walk.files.len(), 1);
+        assert_eq!(walk.files[0].path, PathBuf::from("app.py"));
+        assert!(walk.oversized.is_empty());
+
+        let all = walk_repo_languages(root, &[], 100).unwrap();
+        assert_eq!(all.files.len(), 2);
+    }
+
+    #[test]
+    fn walk_all_includes_unknown_text_files() {
+        let dir = make_temp_repo();
+        let root = dir.path();
+        fs::write(root.join("Makefile"), "all:\n\tcargo build\n").unwrap();
+        fs::write(root.join("blob.bin"), [0u8, 1, 2, 3]).unwrap();
+
+        let files = walk_repo_all(root).unwrap();
+        assert_eq!(files.len(), 12);
+
+        let unknown: Vec<&Path> = files
+            .iter()
+            .filter(|f| f.language == Language::Unknown)
+            .map(|f| f.path.as_path())
+            .collect();
+        assert!(unknown.contains(&Path::new("README.md")));
+        assert!(unknown.contains(&Path::new("data.csv")));
+        assert!(unknown.contains(&Path::new("Makefile")));
+        assert!(!unknown.contains(&Path::new("blob.bin")));
+    }
 }
@@ -109,3 +109,77 @@ fn generate_map_json_is_valid() {
     let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
     assert!(parsed.is_array());
 }
+
+#[test]
+fn generate_map_with_max_files_reports_capped_files() {
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap();
+
+    let total = argus_repomap::walker::walk_repo(repo_root).unwrap().len();
+    let options = argus_repomap::MapOptions {
+        max_tokens: 500,
+        max_files: Some(3),
+        ..argus_repomap::MapOptions::default()
+    };
+    let map = argus_repomap::generate_map_with(repo_root, &options).unwrap();
+    assert_eq!(map.files_mapped, 3);
+    assert_eq!(map.files_capped, total - 3);
+    assert!(!map.output.is_empty());
+}
+
+#[test]
+fn generate_map_with_focus_hops_stays_near_focus_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("a.rs"),
+        "fn entry() { helper(); }\nfn helper() {}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("b.rs"), "fn unrelated() {}\n").unwrap();
+
+    let options = argus_repomap::MapOptions {
+        focus_files: vec![std::path::PathBuf::from("a.rs")],
+        focus_hops: Some(1),
+        ..argus_repomap::MapOptions::default()
+    };
+    let map = argus_repomap::generate_map_with(dir.path(), &options).unwrap();
+    assert!(map.output.contains("helper"), "map: {}", map.output);
+    assert!(!map.output.contains("unrelated"), "map: {}", map.output);
+
+    let full = argus_repomap::generate_map_with(
+        dir.path(),
+        &argus_repomap::MapOptions {
+            focus_hops: None,
+            ..options
+        },
+    )
+    .unwrap();
+    assert!(full.output.contains("unrelated"));
+}
+
+#[test]
+fn generate_map_with_changed_ranges_reports_blast_radius() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "fn parse_config() {\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("main.rs"),
+        "fn main() { parse_config(); }\nfn load() { parse_config(); }\n",
+    )
+    .unwrap();
+
+    let options = argus_repomap::MapOptions {
+        changed_ranges: vec![(std::path::PathBuf::from("lib.rs"), 2..=2)],
+        ..argus_repomap::MapOptions::default()
+    };
+    let map = argus_repomap::generate_map_with(dir.path(), &options).unwrap();
+    assert_eq!(map.blast_radius.len(), 1, "{:?}", map.blast_radius);
+    assert_eq!(map.blast_radius[0].symbol, "parse_config");
+    assert_eq!(map.blast_radius[0].dependents, ["load", "main"]);
+}
@@ -0,0 +1,310 @@
+//! Branch comparison for `argus review --compare base..head`.
+//!
+//! A review of a branch can flag lines the branch did not touch, such as
+//! context lines around a hunk. For release gating only the issues the
+//! branch *introduced* matter, so each finding is classified by whether its
+//! line was added by the diff (introduced) or already existed in the base
+//! (pre-existing).
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+pub use argus_core::Origin;
+use argus_core::ReviewComment;
+use argus_difflens::breaking::BREAKING_CHANGE_RULE;
+use argus_difflens::parser::FileDiff;
+
+use crate::pipeline::{FilterReason, FilteredComment, ReviewResult};
+
+/// Split a `--compare` range into its base and head refs.
+///
+/// Accepts `base..head` and `base...head`; both name the same comparison,
+/// since the review always diffs `head` against its merge base with `base`.
+/// Returns `None` if the range has no `..` separator or an empty side.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::compare::parse_compare_range;
+///
+/// assert_eq!(
+///     parse_compare_range("main..feature"),
+///     Some(("main".to_string(), "feature".to_string()))
+/// );
+/// assert_eq!(parse_compare_range("v1.0...HEAD").unwrap().0, "v1.0");
+/// assert_eq!(parse_compare_range("main"), None);
+/// ```
+pub fn parse_compare_range(range: &str) -> Option<(String, String)> {
+    range
+        .split_once("...")
+        .or_else(|| range.split_once(".."))
+        .filter(|(base, head)| !base.is_empty() && !head.is_empty())
+        .map(|(base, head)| (base.to_string(), head.to_string()))
+}
+
+/// Lines (on the new side) added by a diff, per file.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::compare::{AddedLines, Origin};
+/// use argus_difflens::parser::parse_unified_diff;
+///
+/// let diff = "diff --git a/lib.rs b/lib.rs\n\
+///             --- a/lib.rs\n\
+///             +++ b/lib.rs\n\
+///             @@ -1,0 +2,1 @@\n\
+///             +let x = 1;\n";
+/// let added = AddedLines::from_diffs(&parse_unified_diff(diff).unwrap());
+/// assert_eq!(added.origin("lib.rs".as_ref(), 2), Origin::Introduced);
+/// assert_eq!(added.origin("lib.rs".as_ref(), 1), Origin::PreExisting);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AddedLines {
+    lines: HashMap<PathBuf, HashSet<u32>>,
+}
+
+impl AddedLines {
+    /// Collect the added lines of every file in `diffs`.
+    pub fn from_diffs(diffs: &[FileDiff]) -> Self {
+        let mut lines: HashMap<PathBuf, HashSet<u32>> = HashMap::new();
+        for diff in diffs.iter().filter(|d| !d.is_deleted_file) {
+            let added = lines.entry(diff.new_path.clone()).or_default();
+            for hunk in &diff.hunks {
+                let mut line_no = hunk.new_start;
+                for line in hunk.content.lines() {
+                    if line.starts_with('-') {
+                        continue;
+                    }
+                    if line.starts_with('+') {
+                        added.insert(line_no);
+                    }
+                    line_no += 1;
+                }
+            }
+        }
+        Self { lines }
+    }
+
+    /// Classify line `line` of `path`: lines the diff did not add exist in
+    /// the base.
+    pub fn origin(&self, path: &std::path::Path, line: u32) -> Origin {
+        if self.lines.get(path).is_some_and(|l| l.contains(&line)) {
+            Origin::Introduced
+        } else {
+            Origin::PreExisting
+        }
+    }
+
+    /// Classify a review comment by the line it points at.
+    ///
+    /// A breaking-change finding is always introduced: it reports what the
+    /// diff removed or changed, and a removal can only point at a line the
+    /// diff kept.
+    pub fn comment_origin(&self, comment: &ReviewComment) -> Origin {
+        if comment.rule.as_deref() == Some(BREAKING_CHANGE_RULE) {
+            return Origin::Introduced;
+        }
+        self.origin(&comment.file_path, comment.line)
+    }
+}
+
+/// Set [`ReviewComment::origin`] on every comment in `result`, filtered
+/// ones included. Returns the number of pre-existing comments left in
+/// `result.comments`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_review::compare::{mark_origins, AddedLines, Origin};
+/// use argus_review::pipeline::{ReviewResult, ReviewStats};
+///
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("lib.rs"),
+///     line: 1,
+///     severity: Severity::Bug,
+///     message: "old bug".into(),
+///     confidence: 95.0,
+///     suggestion: None,
+///     ..Default::default()
+/// };
+/// let mut result = ReviewResult {
+///     comments: vec![comment],
+///     filtered_comments: vec![],
+///     summary: None,
+///     stats: ReviewStats::default(),
+/// };
+/// assert_eq!(mark_origins(&mut result, &AddedLines::default()), 1);
+/// assert_eq!(result.comments[0].origin, Some(Origin::PreExisting));
+/// ```
+pub fn mark_origins(result: &mut ReviewResult, added: &AddedLines) -> usize {
+    for comment in result
+        .comments
+        .iter_mut()
+        .chain(result.filtered_comments.iter_mut().map(|f| &mut f.comment))
+    {
+        comment.origin = Some(added.comment_origin(comment));
+    }
+    result
+        .comments
+        .iter()
+        .filter(|c| c.origin == Some(Origin::PreExisting))
+        .count()
+}
+
+/// Move pre-existing findings out of `result.comments`.
+///
+/// They are kept in [`ReviewResult::filtered_comments`] with
+/// [`FilterReason::Preexisting`], so JSON output still shows them. Returns the
+/// number of comments moved.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_review::compare::{drop_preexisting, AddedLines};
+/// use argus_review::pipeline::{ReviewResult, ReviewStats};
+///
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("lib.rs"),
+///     line: 1,
+///     severity: Severity::Bug,
+///     message: "old bug".into(),
+///     confidence: 95.0,
+///     suggestion: None,
+///     ..Default::default()
+/// };
+/// let mut result = ReviewResult {
+///     comments: vec![comment],
+///     filtered_comments: vec![],
+///     summary: None,
+///     stats: ReviewStats::default(),
+/// };
+/// assert_eq!(drop_preexisting(&mut result, &AddedLines::default()), 1);
+/// assert!(result.comments.is_empty());
+/// assert_eq!(result.filtered_comments.len(), 1);
+/// ```
+pub fn drop_preexisting(result: &mut ReviewResult, added: &AddedLines) -> usize {
+    let (introduced, preexisting): (Vec<_>, Vec<_>) = std::mem::take(&mut result.comments)
+        .into_iter()
+        .partition(|c| added.comment_origin(c) == Origin::Introduced);
+    result.comments = introduced;
+    let moved = preexisting.len();
+    result
+        .filtered_comments
+        .extend(preexisting.into_iter().map(|comment| FilteredComment {
+            comment,
+            reason: FilterReason::Preexisting,
+        }));
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argus_difflens::parser::parse_unified_diff;
+
+    #[test]
+    fn context_and_removed_lines_are_preexisting() {
+        let diff = "\
+diff --git a/app.py b/app.py
+--- a/app.py
++++ b/app.py
+@@ -10,3 +10,3 @@
+ def run():
+-    old()
++    new()
+     return
+diff --git a/gone.py b/gone.py
+deleted file mode 100644
+--- a/gone.py
++++ /dev/null
+@@ -1 +0,0 @@
+-x = 1
+";
+        let added = AddedLines::from_diffs(&parse_unified_diff(diff).unwrap());
+        let path = std::path::Path::new("app.py");
+        assert_eq!(added.origin(path, 10), Origin::PreExisting);
+        assert_eq!(added.origin(path, 11), Origin::Introduced);
+        assert_eq!(added.origin(path, 12), Origin::PreExisting);
+        assert_eq!(added.origin("other.py".as_ref(), 11), Origin::PreExisting);
+    }
+
+    #[test]
+    fn origins_are_marked_on_reported_and_filtered_comments() {
+        let diff = "\
+diff --git a/app.py b/app.py
+--- a/app.py
++++ b/app.py
+@@ -1,1 +1,2 @@
+ def run():
++    new()
+";
+        let added = AddedLines::from_diffs(&parse_unified_diff(diff).unwrap());
+        let comment = |line| ReviewComment {
+            file_path: "app.py".into(),
+            line,
+            message: "issue".into(),
+            ..Default::default()
+        };
+        let mut result = ReviewResult {
+            comments: vec![comment(1), comment(2)],
+            filtered_comments: vec![FilteredComment {
+                comment: comment(2),
+                reason: FilterReason::BelowConfidence,
+            }],
+            summary: None,
+            stats: Default::default(),
+        };
+        assert_eq!(mark_origins(&mut result, &added), 1);
+        assert_eq!(result.comments[0].origin, Some(Origin::PreExisting));
+        assert_eq!(result.comments[1].origin, Some(Origin::Introduced));
+        assert_eq!(
+            result.filtered_comments[0].comment.origin,
+            Some(Origin::Introduced)
+        );
+        let json = serde_json::to_value(&result.comments[0]).unwrap();
+        assert_eq!(json["origin"], "pre-existing");
+        assert!(result.to_string().contains("Origin: pre-existing"));
+    }
+
+    #[test]
+    fn removed_public_api_is_introduced() {
+        let diff = "\
+diff --git a/lib.rs b/lib.rs
+--- a/lib.rs
++++ b/lib.rs
+@@ -1,3 +1,2 @@
+ fn helper() {}
+-pub fn old_api() {}
+ fn other() {}
+";
+        let diffs = parse_unified_diff(diff).unwrap();
+        let added = AddedLines::from_diffs(&diffs);
+        let mut result = ReviewResult {
+            comments: argus_difflens::breaking::find_breaking_changes(&diffs),
+            filtered_comments: Vec::new(),
+            summary: None,
+            stats: Default::default(),
+        };
+        assert_eq!(result.comments.len(), 1);
+
+        assert_eq!(drop_preexisting(&mut result, &added), 0);
+        assert!(result.comments[0].message.contains("`old_api` removed"));
+        assert_eq!(mark_origins(&mut result, &added), 0);
+        assert_eq!(result.comments[0].origin, Some(Origin::Introduced));
+    }
+
+    #[test]
+    fn range_needs_both_sides() {
+        assert_eq!(parse_compare_range("..feature"), None);
+        assert_eq!(parse_compare_range("main.."), None);
+        assert_eq!(
+            parse_compare_range("origin/main..HEAD"),
+            Some(("origin/main".to_string(), "HEAD".to_string()))
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! In-depth explanation of a single finding.
+//!
+//! `argus explain --file src/x.rs --line 42` asks the LLM to elaborate on
+//! one line: the finding the last review left there, or, without one, the
+//! most important issue the model sees. The prompt shows the whole
+//! function around the line, found with tree-sitter as for `[review]
+//! expand_to_function`.
+
+use std::path::Path;
+
+use argus_core::{ArgusError, ReviewComment};
+
+use crate::pipeline::function_ranges;
+
+/// Lines shown on each side of a line that is not inside a function.
+const FALLBACK_CONTEXT_LINES: usize = 20;
+
+/// The finding the last review reported at `file:line`, if any.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_review::explain::find_finding;
+///
+/// let comments = vec![ReviewComment {
+///     file_path: PathBuf::from("src/lib.rs"),
+///     line: 42,
+///     severity: Severity::Bug,
+///     message: "Index can overflow".into(),
+///     confidence: 95.0,
+///     suggestion: None,
+///     ..Default::default()
+/// }];
+/// assert!(find_finding(&comments, Path::new("src/lib.rs"), 42).is_some());
+/// assert!(find_finding(&comments, Path::new("src/lib.rs"), 43).is_none());
+/// ```
+pub fn find_finding<'a>(
+    comments: &'a [ReviewComment],
+    file: &Path,
+    line: u32,
+) -> Option<&'a ReviewComment> {
+    comments
+        .iter()
+        .find(|c| c.file_path == file && c.line == line)
+}
+
+/// The code around `line` of `file` (relative to `root`), as a numbered
+/// markdown block with the line marked by `>`.
+///
+/// Shows the enclosing function or method when there is one, otherwise
+/// [`FALLBACK_CONTEXT_LINES`] lines on each side.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::FileNotFound`] if the file cannot be read, or
+/// [`ArgusError::Config`] if it has fewer than `line` lines.
+pub fn finding_context(root: &Path, file: &Path, line: u32) -> Result<String, ArgusError> {
+    let content = std::fs::read_to_string(root.join(file))
+        .map_err(|_| ArgusError::FileNotFound(file.to_path_buf()))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let target = line as usize;
+    if target == 0 || target > lines.len() {
+        return Err(ArgusError::Config(format!(
+            "{} has {} lines, so there is no line {line}",
+            file.display(),
+            lines.len()
+        )));
+    }
+
+    // The innermost function containing the line
+    let (from, to) = function_ranges(file, &content)
+        .into_iter()
+        .filter(|(first, last)| *first <= target && target <= *last)
+        .min_by_key(|(first, last)| last - first)
+        .unwrap_or_else(|| {
+            (
+                target.saturating_sub(FALLBACK_CONTEXT_LINES).max(1),
+                (target + FALLBACK_CONTEXT_LINES).min(lines.len()),
+            )
+        });
+
+    let width = to.to_string().len();
+    let body: Vec<String> = (from..=to)
+        .map(|n| {
+            let marker = if n == target { '>' } else { ' ' };
+            format!("{marker} {n:>width$} | {}", lines[n - 1])
+        })
+        .collect();
+    Ok(format!(
+        "### {} (lines {from}-{to})\n```\n{}\n```\n",
+        file.display(),
+        body.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_is_the_enclosing_function_with_the_line_marked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut source =
+            String::from("fn first() -> u32 {\n    1\n}\n\nfn divide(a: u32, b: u32) -> u32 {\n");
+        source.push_str("    let q = a / b;\n    q\n}\n");
+        for i in 0..60 {
+            source.push_str(&format!("// filler {i}\n"));
+        }
+        std::fs::write(dir.path().join("lib.rs"), &source).unwrap();
+
+        let context = finding_context(dir.path(), Path::new("lib.rs"), 6).unwrap();
+        assert!(context.starts_with("### lib.rs (lines 5-8)\n"));
+        assert!(context.contains("> 6 |     let q = a / b;"));
+        assert!(context.contains("  5 | fn divide"));
+        assert!(!context.contains("fn first"));
+
+        // Outside any function, a window around the line
+        let context = finding_context(dir.path(), Path::new("lib.rs"), 50).unwrap();
+        assert!(context.starts_with("### lib.rs (lines 30-68)\n"));
+        assert!(context.contains("> 50 | // filler 41"));
+
+        assert!(matches!(
+            finding_context(dir.path(), Path::new("lib.rs"), 500),
+            Err(ArgusError::Config(_))
+        ));
+        assert!(matches!(
+            finding_context(dir.path(), Path::new("missing.rs"), 1),
+            Err(ArgusError::FileNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+//! Whole-repository review for `argus review --full`.
+//!
+//! There is no diff to review, so the riskiest files are presented to the
+//! pipeline as newly added files. Files are ranked by cyclomatic complexity
+//! weighted by how often recent history touched them, and only the top
+//! `limit` are reviewed to keep the cost predictable. A file too large for
+//! one LLM request is cut at function boundaries into several parts.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use argus_core::ArgusError;
+use argus_gitpulse::mining::{mine_history, CommitInfo, MiningOptions};
+use argus_repomap::complexity::file_complexity;
+use argus_repomap::walker::{walk_repo_detailed, SourceFile};
+use serde::Serialize;
+
+/// A file selected for a full review, with the numbers that ranked it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedFile {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Cyclomatic complexity of the file.
+    pub complexity: u32,
+    /// Commits touching the file in recent history.
+    pub revisions: u32,
+    /// `complexity × (1 + revisions)`.
+    pub score: f64,
+}
+
+/// The files chosen for a full review and the diff presenting them.
+#[derive(Debug, Clone)]
+pub struct FullReview {
+    /// Reviewed files, riskiest first.
+    pub files: Vec<RankedFile>,
+    /// Parseable source files found in the repository.
+    pub total_files: usize,
+    /// Unified diff adding every reviewed file, ready for the pipeline.
+    pub diff: String,
+}
+
+/// Rank source files by `complexity × (1 + revisions)`, riskiest first.
+///
+/// `revisions` counts the commits in `commits` that touch each file, so a
+/// repository without history is ranked by complexity alone. Files without
+/// a grammar are left out. Ties are broken by path.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::walker::{Language, SourceFile};
+/// use argus_review::full::rank_files;
+///
+/// let file = |path: &str, content: &str| SourceFile {
+///     path: PathBuf::from(path),
+///     language: Language::Rust,
+///     content: content.into(),
+/// };
+/// let files = [
+///     file("flat.rs", "fn a() {}\n"),
+///     file("branchy.rs", "fn b(x: bool) { if x { } }\n"),
+/// ];
+/// let ranked = rank_files(&files, &[]);
+/// assert_eq!(ranked[0].path, PathBuf::from("branchy.rs"));
+/// assert_eq!(ranked[0].score, 2.0);
+/// ```
+pub fn rank_files(files: &[SourceFile], commits: &[CommitInfo]) -> Vec<RankedFile> {
+    let mut revisions: HashMap<&str, u32> = HashMap::new();
+    for commit in commits {
+        for change in &commit.files_changed {
+            *revisions.entry(change.path.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut ranked: Vec<RankedFile> = files
+        .iter()
+        .filter_map(file_complexity)
+        .map(|c| {
+            let key = c.path.to_string_lossy().replace('\\', "/");
+            let revisions = revisions.get(key.as_str()).copied().unwrap_or(0);
+            RankedFile {
+                score: f64::from(c.complexity) * f64::from(1 + revisions),
+                path: c.path,
+                complexity: c.complexity,
+                revisions,
+            }
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    ranked
+}
+
+/// Select the `limit` riskiest files under `root` and build their diff.
+///
+/// History is mined with the default options; outside a git repository
+/// files are ranked by complexity alone. Files larger than
+/// `max_file_bytes` are skipped, as in the repo map, and a file above
+/// `max_chunk_tokens` is split as described in [`file_as_diff`].
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the root directory cannot be read.
+pub fn build_full_review(
+    root: &Path,
+    limit: usize,
+    max_chunk_tokens: usize,
+    max_file_bytes: u64,
+) -> Result<FullReview, ArgusError> {
+    let walk = walk_repo_detailed(root, false, max_file_bytes)?;
+    let commits = mine_history(root, &MiningOptions::default()).unwrap_or_default();
+    let mut files = rank_files(&walk.files, &commits);
+    let total_files = files.len();
+    files.truncate(limit);
+
+    let by_path: HashMap<&Path, &SourceFile> =
+        walk.files.iter().map(|f| (f.path.as_path(), f)).collect();
+    let diff = files
+        .iter()
+        .filter_map(|ranked| by_path.get(ranked.path.as_path()))
+        .map(|file| file_as_diff(file, max_chunk_tokens))
+        .collect();
+    Ok(FullReview {
+        files,
+        total_files,
+        diff,
+    })
+}
+
+/// Render `file` as a unified diff that adds all of its lines.
+///
+/// A file of at most `max_chunk_tokens` (estimated as bytes / 4) becomes one
+/// hunk. A larger one is cut at the start of its functions, classes, and
+/// other top-level items into parts that each fit when possible, and every
+/// part becomes its own `diff --git` section, so the pipeline can send the
+/// parts in separate requests. Line numbers always match the file.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_repomap::walker::{Language, SourceFile};
+/// use argus_review::full::file_as_diff;
+///
+/// let file = SourceFile {
+///     path: PathBuf::from("src/lib.rs"),
+///     language: Language::Rust,
+///     content: "fn a() {}\n".into(),
+/// };
+/// let diff = file_as_diff(&file, 1000);
+/// assert!(diff.contains("+++ b/src/lib.rs\n@@ -0,0 +1,1 @@\n+fn a() {}\n"));
+/// ```
+pub fn file_as_diff(file: &SourceFile, max_chunk_tokens: usize) -> String {
+    let lines: Vec<&str> = file.content.lines().collect();
+    let path = file.path.to_string_lossy().replace('\\', "/");
+    let mut out = String::new();
+    for (start, end) in chunk_ranges(file, &lines, max_chunk_tokens) {
+        let _ = writeln!(out, "diff --git a/{path} b/{path}");
+        out.push_str("new file mode 100644\n--- /dev/null\n");
+        let _ = writeln!(out, "+++ b/{path}");
+        let _ = writeln!(out, "@@ -0,0 +{},{} @@", start + 1, end - start);
+        for line in &lines[start..end] {
+            let _ = writeln!(out, "+{line}");
+        }
+    }
+    out
+}
+
+/// Half-open 0-based line ranges covering `lines`, each within
+/// `max_chunk_tokens` unless a single item is larger on its own.
+fn chunk_ranges(file: &SourceFile, lines: &[&str], max_chunk_tokens: usize) -> Vec<(usize, usize)> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+    if file.content.len() / 4 <= max_chunk_tokens {
+        return vec![(0, lines.len())];
+    }
+
+    let mut starts: Vec<usize> =
+        argus_codelens::chunker::chunk_file(&file.path, &file.content, file.language)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|chunk| (chunk.start_line as usize).saturating_sub(1))
+            .filter(|&start| start < lines.len())
+            .collect();
+    starts.push(0);
+    starts.sort_unstable();
+    starts.dedup();
+
+    let tokens = |from: usize, to: usize| -> usize {
+        lines[from..to].iter().map(|l| l.len() + 1).sum::<usize>() / 4
+    };
+    let mut ranges = Vec::new();
+    let mut chunk_start = 0;
+    for (i, &item_start) in starts.iter().enumerate().skip(1) {
+        let item_end = starts.get(i + 1).copied().unwrap_or(lines.len());
+        if item_start > chunk_start && tokens(chunk_start, item_end) > max_chunk_tokens {
+            ranges.push((chunk_start, item_start));
+            chunk_start = item_start;
+        }
+    }
+    ranges.push((chunk_start, lines.len()));
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argus_difflens::parser::parse_unified_diff;
+    use argus_gitpulse::mining::{ChangeStatus, FileChange};
+    use argus_repomap::walker::Language;
+
+    fn rust(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(path),
+            language: Language::Rust,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn churn_outranks_equal_complexity_and_large_files_split_by_function() {
+        let touched = CommitInfo {
+            hash: "abc".into(),
+            author: "Alice".into(),
+            email: "alice@example.com".into(),
+            timestamp: 1_700_000_000,
+            message: "change".into(),
+            files_changed: vec![FileChange {
+                path: "src/hot.rs".into(),
+                lines_added: 3,
+                lines_deleted: 1,
+                status: ChangeStatus::Modified,
+            }],
+        };
+        let body = "fn f(x: bool) { if x { } }\n";
+        let ranked = rank_files(
+            &[rust("src/cold.rs", body), rust("src/hot.rs", body)],
+            &[touched],
+        );
+        assert_eq!(ranked[0].path, PathBuf::from("src/hot.rs"));
+        assert_eq!((ranked[0].revisions, ranked[0].score), (1, 4.0));
+
+        let big = rust(
+            "src/big.rs",
+            "fn one() {\n    let a = 1;\n}\n\nfn two() {\n    let b = 2;\n}\n\nfn three() {\n    let c = 3;\n}\n",
+        );
+        let diffs = parse_unified_diff(&file_as_diff(&big, 8)).unwrap();
+        let parts: Vec<(u32, u32)> = diffs
+            .iter()
+            .map(|d| (d.hunks[0].new_start, d.hunks[0].new_lines))
+            .collect();
+        assert_eq!(parts, [(1, 4), (5, 4), (9, 3)]);
+        assert!(diffs
+            .iter()
+            .all(|d| d.is_new_file && d.new_path == Path::new("src/big.rs")));
+
+        let whole = parse_unified_diff(&file_as_diff(&big, 1000)).unwrap();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].hunks[0].new_lines, 11);
+    }
+}
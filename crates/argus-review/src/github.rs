@@ -1,4 +1,10 @@
+use std::ops::RangeInclusive;
+
 use argus_core::{ArgusError, ReviewComment, Severity};
+use argus_difflens::parser::FileDiff;
+
+use crate::patch::hunk_for;
+use crate::pipeline::{GroupBy, ReviewResult};
 
 /// GitHub Pull Request client for fetching diffs and posting reviews.
 ///
@@ -94,6 +100,28 @@ impl GitHubClient {
             .map_err(|e| ArgusError::GitHub(format!("failed to read diff response: {e}")))
     }
 
+    /// Fetch a pull request's title and description, as one text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::GitHub`] on network or API errors.
+    pub async fn get_pr_description(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<String, ArgusError> {
+        let pr = self
+            .api_request(
+                reqwest::Method::GET,
+                &format!("https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}"),
+                None,
+                None,
+            )
+            .await?;
+        Ok(pr_description(&pr))
+    }
+
     /// Post review comments to a pull request.
     ///
     /// Creates a single review with all comments using the GitHub PR Review API.
@@ -102,6 +130,10 @@ impl GitHubClient {
     ///
     /// If `REQUEST_CHANGES` fails (e.g., self-review not allowed), it falls back to `COMMENT`.
     ///
+    /// Comments with a patch that [`suggestion_range`] can place in `diffs`
+    /// carry it as a `suggestion` block, which reviewers can apply with one
+    /// click; other comments are posted as prose.
+    ///
     /// # Errors
     ///
     /// Returns [`ArgusError::GitHub`] on API errors.
@@ -111,46 +143,179 @@ impl GitHubClient {
         repo: &str,
         pr_number: u64,
         comments: &[ReviewComment],
+        diffs: &[FileDiff],
         summary: &str,
     ) -> Result<(), ArgusError> {
         let review_comments: Vec<serde_json::Value> = comments
             .iter()
-            .map(|c| {
-                let emoji = match c.severity {
-                    Severity::Bug => "\u{1f41b}",
-                    Severity::Warning => "\u{26a0}\u{fe0f}",
-                    Severity::Suggestion => "\u{1f4a1}",
-                    Severity::Info => "\u{2139}\u{fe0f}",
-                };
-                let label = match c.severity {
-                    Severity::Bug => "Bug",
-                    Severity::Warning => "Warning",
-                    Severity::Suggestion => "Suggestion",
-                    Severity::Info => "Info",
-                };
-                let mut body = format!(
-                    "**{emoji} {label}** (confidence: {:.0}%)\n\n{}",
-                    c.confidence, c.message
-                );
-                if let Some(s) = &c.suggestion {
-                    body.push_str(&format!("\n\n**Suggestion:** {s}"));
-                }
-                serde_json::json!({
-                    "path": c.file_path.to_string_lossy(),
-                    "line": c.line,
-                    "side": "RIGHT",
-                    "body": body,
-                })
-            })
+            .map(|c| review_comment_json(c, diffs))
             .collect();
+        self.submit_review(
+            owner,
+            repo,
+            pr_number,
+            review_event(comments),
+            summary,
+            review_comments,
+        )
+        .await
+    }
 
-        let has_bugs = comments.iter().any(|c| c.severity == Severity::Bug);
-        let event = if has_bugs {
-            "REQUEST_CHANGES"
-        } else {
-            "COMMENT"
+    /// Post all findings as the body of one review, without inline comments.
+    ///
+    /// `body` is usually [`ReviewResult::to_markdown_collapsible`]; it is cut
+    /// to GitHub's comment size limit with a note when longer. The review
+    /// event follows `comments` as in [`post_review`](Self::post_review).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::GitHub`] on API errors.
+    ///
+    /// [`ReviewResult::to_markdown_collapsible`]: crate::pipeline::ReviewResult::to_markdown_collapsible
+    pub async fn post_summary_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        comments: &[ReviewComment],
+        body: &str,
+    ) -> Result<(), ArgusError> {
+        self.submit_review(
+            owner,
+            repo,
+            pr_number,
+            review_event(comments),
+            &fit_comment_body(body),
+            Vec::new(),
+        )
+        .await
+    }
+
+    /// Report the review as a completed GitHub Check Run on the PR's head commit.
+    ///
+    /// Each comment becomes a line annotation (see [`check_annotation_json`])
+    /// and `conclusion` is usually [`check_conclusion`]; the title counts the
+    /// findings. GitHub takes at most 50 annotations per request, so the rest
+    /// are added with follow-up updates. `summary` is cut to the size GitHub
+    /// accepts. Returns the check run's web URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::GitHub`] on API errors, with a hint about the
+    /// required `checks: write` permission when GitHub refuses access.
+    pub async fn post_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        conclusion: &str,
+        summary: &str,
+        comments: &[ReviewComment],
+    ) -> Result<String, ArgusError> {
+        let pr = self
+            .api_request(
+                reqwest::Method::GET,
+                &format!("https://api.github.com/repos/{owner}/{repo}/pulls/{pr_number}"),
+                None,
+                None,
+            )
+            .await?;
+        let Some(head_sha) = pr["head"]["sha"].as_str() else {
+            return Err(ArgusError::GitHub(format!(
+                "PR {owner}/{repo}#{pr_number} has no head commit"
+            )));
         };
 
+        let title = match comments.len() {
+            0 => "No findings".to_string(),
+            1 => "1 finding".to_string(),
+            n => format!("{n} findings"),
+        };
+        let annotations: Vec<serde_json::Value> =
+            comments.iter().map(check_annotation_json).collect();
+        let mut batches = annotations.chunks(MAX_ANNOTATIONS_PER_REQUEST);
+        let output = |batch: &[serde_json::Value]| {
+            serde_json::json!({
+                "title": title,
+                "summary": fit_comment_body(summary),
+                "annotations": batch,
+            })
+        };
+
+        let created = self
+            .api_request(
+                reqwest::Method::POST,
+                &format!("https://api.github.com/repos/{owner}/{repo}/check-runs"),
+                Some(serde_json::json!({
+                    "name": CHECK_RUN_NAME,
+                    "head_sha": head_sha,
+                    "status": "completed",
+                    "conclusion": conclusion,
+                    "output": output(batches.next().unwrap_or_default()),
+                })),
+                Some(CHECK_RUN_PERMISSION_HINT),
+            )
+            .await?;
+        let Some(id) = created["id"].as_u64() else {
+            return Err(ArgusError::GitHub("check run response has no id".into()));
+        };
+        for batch in batches {
+            self.api_request(
+                reqwest::Method::PATCH,
+                &format!("https://api.github.com/repos/{owner}/{repo}/check-runs/{id}"),
+                Some(serde_json::json!({ "output": output(batch) })),
+                Some(CHECK_RUN_PERMISSION_HINT),
+            )
+            .await?;
+        }
+        Ok(created["html_url"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Send a JSON request to the REST API and return the response body.
+    ///
+    /// A refused request (403 or 404) gets `refused_hint`, if any, about the
+    /// permission it needs.
+    async fn api_request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+        refused_hint: Option<&str>,
+    ) -> Result<serde_json::Value, ArgusError> {
+        let mut request = self
+            .http
+            .request(method, url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("User-Agent", "argus");
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ArgusError::GitHub(format!("request to {url} failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(api_error(status, &body, refused_hint));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| ArgusError::GitHub(format!("failed to read GitHub response: {e}")))
+    }
+
+    async fn submit_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        event: &str,
+        summary: &str,
+        review_comments: Vec<serde_json::Value>,
+    ) -> Result<(), ArgusError> {
         let route = format!("/repos/{owner}/{repo}/pulls/{pr_number}/reviews");
         let body = serde_json::json!({
             "event": event,
@@ -200,11 +365,399 @@ impl GitHubClient {
     }
 }
 
-/// Parse a PR reference string (`owner/repo#number`) into its components.
+/// Whether `--post-comments` should post a review of `comments`.
+///
+/// A review is always posted unless `only_if_findings` is set and there are
+/// no comments; then nothing is posted at all, rather than an empty
+/// `COMMENT` or an `APPROVE`.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::github::should_post_review;
+///
+/// assert!(should_post_review(&[], false));
+/// assert!(!should_post_review(&[], true));
+/// ```
+pub fn should_post_review(comments: &[ReviewComment], only_if_findings: bool) -> bool {
+    !only_if_findings || !comments.is_empty()
+}
+
+/// What `--post-comments` sends to the PR, decided by [`plan_review_post`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewPost {
+    /// One inline comment per finding via [`GitHubClient::post_review`],
+    /// under this review body.
+    Inline {
+        /// The review body.
+        summary: String,
+    },
+    /// Every finding in one review body via
+    /// [`GitHubClient::post_summary_review`].
+    Summary {
+        /// The review body.
+        body: String,
+    },
+    /// Nothing, because [`should_post_review`] said no.
+    Nothing,
+}
+
+/// Decide what `--post-comments` posts for `result`.
+///
+/// `inline` picks one comment per finding over a single summary review;
+/// `group_by` orders the findings in the summary body.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::github::{plan_review_post, ReviewPost};
+/// use argus_review::pipeline::{GroupBy, ReviewResult};
+///
+/// let result = ReviewResult {
+///     comments: Vec::new(),
+///     filtered_comments: Vec::new(),
+///     summary: None,
+///     stats: Default::default(),
+/// };
+/// assert_eq!(plan_review_post(&result, true, true, GroupBy::Severity), ReviewPost::Nothing);
+/// ```
+pub fn plan_review_post(
+    result: &ReviewResult,
+    only_if_findings: bool,
+    inline: bool,
+    group_by: GroupBy,
+) -> ReviewPost {
+    if should_post_review(&result.comments, only_if_findings) {
+        if inline {
+            ReviewPost::Inline {
+                summary: format!(
+                    "Argus Code Review: {} comments ({} files reviewed)",
+                    result.comments.len(),
+                    result.stats.files_reviewed,
+                ),
+            }
+        } else {
+            ReviewPost::Summary {
+                body: result.to_markdown_collapsible(group_by),
+            }
+        }
+    } else {
+        ReviewPost::Nothing
+    }
+}
+
+/// `REQUEST_CHANGES` when any comment is a bug, otherwise `COMMENT`.
+fn review_event(comments: &[ReviewComment]) -> &'static str {
+    if comments.iter().any(|c| c.severity == Severity::Bug) {
+        "REQUEST_CHANGES"
+    } else {
+        "COMMENT"
+    }
+}
+
+/// Name the check run is shown under in the PR's checks list.
+const CHECK_RUN_NAME: &str = "Argus";
+
+/// Most annotations GitHub accepts in one check run request.
+const MAX_ANNOTATIONS_PER_REQUEST: usize = 50;
+
+/// Conclusion for a check run reporting `comments`.
+///
+/// `failure` when a comment meets `fail_on`, as `--fail-on` would exit
+/// non-zero; otherwise `neutral` when there are comments and `success` when
+/// there are none.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_review::github::check_conclusion;
+///
+/// let warning = ReviewComment {
+///     file_path: PathBuf::from("src/lib.rs"),
+///     line: 3,
+///     severity: Severity::Warning,
+///     message: "unchecked index".into(),
+///     confidence: 90.0,
+///     suggestion: None,
+///     ..Default::default()
+/// };
+/// assert_eq!(check_conclusion(&[], Some(Severity::Warning)), "success");
+/// assert_eq!(check_conclusion(&[warning.clone()], Some(Severity::Bug)), "neutral");
+/// assert_eq!(check_conclusion(&[warning], Some(Severity::Warning)), "failure");
+/// ```
+pub fn check_conclusion(comments: &[ReviewComment], fail_on: Option<Severity>) -> &'static str {
+    if fail_on.is_some_and(|threshold| {
+        comments
+            .iter()
+            .any(|c| c.severity.meets_threshold(threshold))
+    }) {
+        "failure"
+    } else if comments.is_empty() {
+        "success"
+    } else {
+        "neutral"
+    }
+}
+
+/// Build the Checks API annotation for one comment.
+///
+/// Bugs are `failure` annotations, warnings `warning`, and suggestions and
+/// info `notice`. The title is the matched rule, or the severity.
+pub fn check_annotation_json(c: &ReviewComment) -> serde_json::Value {
+    let (level, label) = match c.severity {
+        Severity::Bug => ("failure", "Bug"),
+        Severity::Warning => ("warning", "Warning"),
+        Severity::Suggestion => ("notice", "Suggestion"),
+        Severity::Info => ("notice", "Info"),
+    };
+    let mut message = c.message.clone();
+    if let Some(s) = &c.suggestion {
+        message.push_str(&format!("\n\nSuggestion: {s}"));
+    }
+    let line = c.line.max(1);
+    serde_json::json!({
+        "path": c.file_path.to_string_lossy(),
+        "start_line": line,
+        "end_line": line,
+        "annotation_level": level,
+        "title": c.rule.as_deref().unwrap_or(label),
+        "message": message,
+    })
+}
+
+/// Added to a refused check run request's error.
+const CHECK_RUN_PERMISSION_HINT: &str = "Creating a check run needs a GitHub App token with the \
+     checks: write permission, such as GITHUB_TOKEN in Actions with `permissions: checks: write`. \
+     Personal access tokens cannot create check runs.";
+
+/// The error for a REST API response with a failure `status`, with `hint`
+/// on its own line when the request was refused (403 or 404).
+fn api_error(status: reqwest::StatusCode, body: &str, hint: Option<&str>) -> ArgusError {
+    let refused =
+        status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::NOT_FOUND;
+    match hint {
+        Some(hint) if refused => {
+            ArgusError::GitHub(format!("GitHub API error {status}: {body}\n{hint}"))
+        }
+        _ => ArgusError::GitHub(format!("GitHub API error {status}: {body}")),
+    }
+}
+
+/// A pull request's title and description, as one text, from its REST API
+/// representation.
+fn pr_description(pr: &serde_json::Value) -> String {
+    let title = pr["title"].as_str().unwrap_or_default().trim();
+    let body = pr["body"].as_str().unwrap_or_default().trim();
+    if body.is_empty() {
+        title.to_string()
+    } else {
+        format!("{title}\n\n{body}")
+    }
+}
+
+/// Largest comment or review body GitHub accepts, in characters.
+const MAX_BODY_CHARS: usize = 65_536;
+
+/// Cut `body` to [`MAX_BODY_CHARS`], ending with a note when anything was
+/// dropped. An open code fence or `<details>` block is closed so the rest of
+/// the page renders normally.
+fn fit_comment_body(body: &str) -> String {
+    if body.chars().count() <= MAX_BODY_CHARS {
+        return body.to_string();
+    }
+    let note = "\n\n_Review truncated to fit GitHub's comment size limit. Run `argus review` locally for the full report._\n";
+    let fence = "\n```";
+    let closing = "\n</details>\n";
+    let keep =
+        MAX_BODY_CHARS - note.chars().count() - fence.chars().count() - closing.chars().count();
+    let mut out: String = body.chars().take(keep).collect();
+    // Drop the partial line the cut landed in
+    if let Some(end) = out.rfind('\n') {
+        out.truncate(end);
+    }
+    let fences = out
+        .lines()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count();
+    if fences % 2 == 1 {
+        out.push_str(fence);
+    }
+    if out.matches("<details>").count() > out.matches("</details>").count() {
+        out.push_str(closing);
+    }
+    out.push_str(note);
+    out
+}
+
+/// Build the review API entry for one comment.
+fn review_comment_json(c: &ReviewComment, diffs: &[FileDiff]) -> serde_json::Value {
+    let emoji = match c.severity {
+        Severity::Bug => "\u{1f41b}",
+        Severity::Warning => "\u{26a0}\u{fe0f}",
+        Severity::Suggestion => "\u{1f4a1}",
+        Severity::Info => "\u{2139}\u{fe0f}",
+    };
+    let label = match c.severity {
+        Severity::Bug => "Bug",
+        Severity::Warning => "Warning",
+        Severity::Suggestion => "Suggestion",
+        Severity::Info => "Info",
+    };
+    let mut body = format!(
+        "**{emoji} {label}** (confidence: {:.0}%)\n\n{}",
+        c.confidence, c.message
+    );
+    if let Some(rationale) = &c.rationale {
+        body.push_str(&format!("\n\n**Why it matters:** {rationale}"));
+    }
+    if let Some(s) = &c.suggestion {
+        body.push_str(&format!("\n\n**Suggestion:** {s}"));
+    }
+    let range = suggestion_range(c, diffs);
+    if let (Some(_), Some(patch)) = (&range, &c.patch) {
+        body.push_str(&format!(
+            "\n\n```suggestion\n{}\n```",
+            patch.trim_end_matches('\n')
+        ));
+    }
+    if let Some(author) = &c.last_author {
+        body.push_str(&format!("\n\n_Last changed by {}_", mention(author)));
+    }
+
+    let mut entry = serde_json::json!({
+        "path": c.file_path.to_string_lossy(),
+        "line": c.line,
+        "side": "RIGHT",
+        "body": body,
+    });
+    if let Some(range) = range {
+        entry["line"] = (*range.end()).into();
+        if range.start() != range.end() {
+            entry["start_line"] = (*range.start()).into();
+            entry["start_side"] = "RIGHT".into();
+        }
+    }
+    entry
+}
+
+/// The new-file lines a comment's patch replaces, when it can be posted as
+/// a GitHub `suggestion` block.
+///
+/// Like [`apply_patches`](crate::patch::apply_patches), the patch replaces
+/// the commented line. When the patch starts or ends with lines that
+/// already sit right above or below that line in the diff (context the LLM
+/// copied along with the fix), the range widens to cover them, so applying
+/// the suggestion does not duplicate them. The range never leaves the hunk
+/// containing the commented line, so it is always contiguous.
+///
+/// Returns `None` when the comment has no patch, the patch contains a code
+/// fence, or the commented line is not on the new side of any hunk: GitHub
+/// only accepts suggestions on lines in the diff.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_review::github::suggestion_range;
+///
+/// let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,2 +1,3 @@\n fn f() {\n+    x.unwrap();\n }\n";
+/// let diffs = parse_unified_diff(diff).unwrap();
+/// let mut comment = ReviewComment {
+///     file_path: PathBuf::from("a.rs"),
+///     line: 2,
+///     severity: Severity::Warning,
+///     message: "may panic".into(),
+///     confidence: 90.0,
+///     suggestion: None,
+///     patch: Some("    x?;".into()),
+///     ..Default::default()
+/// };
+/// assert_eq!(suggestion_range(&comment, &diffs), Some(2..=2));
+///
+/// // Leading context in the patch widens the range to include it
+/// comment.patch = Some("fn f() {\n    x?;".into());
+/// assert_eq!(suggestion_range(&comment, &diffs), Some(1..=2));
+/// ```
+pub fn suggestion_range(
+    comment: &ReviewComment,
+    diffs: &[FileDiff],
+) -> Option<RangeInclusive<u32>> {
+    let patch = comment.patch.as_deref().filter(|p| !p.trim().is_empty())?;
+    if patch.contains("```") {
+        return None;
+    }
+    let hunk = hunk_for(diffs, comment)?;
+
+    // New-side lines of the hunk, numbered from `new_start` without gaps
+    let new_side: Vec<&str> = hunk
+        .content
+        .lines()
+        .filter(|line| !line.starts_with('-') && !line.starts_with('\\'))
+        .map(|line| line.get(1..).unwrap_or_default())
+        .collect();
+    let target = (comment.line - hunk.new_start) as usize;
+    if target >= new_side.len() {
+        return None;
+    }
+
+    let patch_lines: Vec<&str> = patch.lines().collect();
+    // Keep at least one patch line as the replacement for the target
+    let max_context = patch_lines.len() - 1;
+    let lead = (1..=max_context.min(target))
+        .rev()
+        .find(|&k| patch_lines[..k] == new_side[target - k..target])
+        .unwrap_or(0);
+    let trail = (1..=(max_context - lead).min(new_side.len() - target - 1))
+        .rev()
+        .find(|&k| patch_lines[patch_lines.len() - k..] == new_side[target + 1..target + 1 + k])
+        .unwrap_or(0);
+
+    Some(comment.line - lead as u32..=comment.line + trail as u32)
+}
+
+/// Render a `Name <email>` author for a PR comment.
+///
+/// GitHub noreply addresses (`123+login@users.noreply.github.com`) carry the
+/// account login, so those authors are @-mentioned; anyone else is shown by
+/// name, since a git email cannot be reliably mapped to a GitHub account.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::github::mention;
+///
+/// assert_eq!(mention("Octo Cat <583231+octocat@users.noreply.github.com>"), "@octocat");
+/// assert_eq!(mention("Alice <alice@example.com>"), "Alice");
+/// ```
+pub fn mention(author: &str) -> String {
+    let (name, email) = match author.split_once(" <") {
+        Some((name, rest)) => (name, rest.trim_end_matches('>')),
+        None => (author, ""),
+    };
+    let login = email
+        .strip_suffix("@users.noreply.github.com")
+        .map(|local| local.rsplit('+').next().unwrap_or(local));
+    match login {
+        Some(login) if !login.is_empty() => format!("@{login}"),
+        _ => name.to_string(),
+    }
+}
+
+/// Parse a PR reference into its owner, repository, and number.
+///
+/// Accepts the short form `owner/repo#number` and a PR URL,
+/// `https://github.com/owner/repo/pull/number`, optionally followed by a
+/// tab such as `/files` or `/commits`, a query string, or a fragment. The
+/// scheme may be left out.
 ///
 /// # Errors
 ///
-/// Returns [`ArgusError::Config`] if the format is invalid.
+/// Returns [`ArgusError::Config`] listing the accepted formats if
+/// `pr_ref` matches none of them.
 ///
 /// # Examples
 ///
@@ -215,28 +768,267 @@ impl GitHubClient {
 /// assert_eq!(owner, "octocat");
 /// assert_eq!(repo, "hello-world");
 /// assert_eq!(num, 42);
+///
+/// let url = "https://github.com/octocat/hello-world/pull/42/files";
+/// assert_eq!(parse_pr_reference(url).unwrap(), (owner, repo, num));
 /// ```
 pub fn parse_pr_reference(pr_ref: &str) -> Result<(String, String, u64), ArgusError> {
-    let Some((owner_repo, number_str)) = pr_ref.split_once('#') else {
-        return Err(ArgusError::Config(format!(
-            "invalid PR reference '{pr_ref}', expected owner/repo#number"
-        )));
+    let trimmed = pr_ref.trim();
+    let parsed = match strip_github_host(trimmed) {
+        Some(path) => parse_pr_url_path(path),
+        None => trimmed.split_once('#').and_then(|(owner_repo, number)| {
+            let (owner, repo) = owner_repo.split_once('/')?;
+            pr_parts(owner, repo, number)
+        }),
     };
-    let Some((owner, repo)) = owner_repo.split_once('/') else {
-        return Err(ArgusError::Config(format!(
-            "invalid PR reference '{pr_ref}', expected owner/repo#number"
-        )));
-    };
-    let number: u64 = number_str
-        .parse()
-        .map_err(|_| ArgusError::Config(format!("invalid PR number: {number_str}")))?;
-    Ok((owner.to_string(), repo.to_string(), number))
+    parsed.ok_or_else(|| {
+        ArgusError::Config(format!(
+            "invalid PR reference '{pr_ref}', expected one of:\n  \
+             owner/repo#123\n  \
+             https://github.com/owner/repo/pull/123\n  \
+             https://github.com/owner/repo/pull/123/files"
+        ))
+    })
+}
+
+/// The path after `github.com/` in a PR URL, with or without a scheme.
+fn strip_github_host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    rest.strip_prefix("github.com/")
+}
+
+/// Parse `owner/repo/pull/number[/tab]`, ignoring any query or fragment.
+fn parse_pr_url_path(path: &str) -> Option<(String, String, u64)> {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    match segments.as_slice() {
+        [owner, repo, "pull", number] => pr_parts(owner, repo, number),
+        [owner, repo, "pull", number, "files" | "commits" | "checks"] => {
+            pr_parts(owner, repo, number)
+        }
+        _ => None,
+    }
+}
+
+/// Validate the three parts of a PR reference.
+fn pr_parts(owner: &str, repo: &str, number: &str) -> Option<(String, String, u64)> {
+    let valid_name = |name: &str| !name.is_empty() && !name.contains(['/', '#', ' ']);
+    if !valid_name(owner) || !valid_name(repo) {
+        return None;
+    }
+    let number: u64 = number.parse().ok().filter(|&n| n > 0)?;
+    Some((owner.to_string(), repo.to_string(), number))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use argus_difflens::parser::parse_unified_diff;
+
     use super::*;
 
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,4 @@
+ fn load(path: &str) -> String {
+-    std::fs::read_to_string(path).unwrap()
++    let text = std::fs::read_to_string(path).unwrap();
++    text
+ }
+";
+
+    fn patched(line: u32, patch: &str) -> ReviewComment {
+        ReviewComment {
+            file_path: PathBuf::from("src/lib.rs"),
+            line,
+            severity: Severity::Bug,
+            message: "panics on a missing file".into(),
+            confidence: 95.0,
+            suggestion: None,
+            patch: Some(patch.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn suggestion_covers_the_commented_line() {
+        let diffs = parse_unified_diff(DIFF).unwrap();
+        let comment = patched(11, "    let text = std::fs::read_to_string(path)?;");
+        assert_eq!(suggestion_range(&comment, &diffs), Some(11..=11));
+
+        let entry = review_comment_json(&comment, &diffs);
+        assert_eq!(entry["line"], 11);
+        assert!(entry.get("start_line").is_none());
+        let body = entry["body"].as_str().unwrap();
+        assert!(
+            body.ends_with("```suggestion\n    let text = std::fs::read_to_string(path)?;\n```"),
+            "{body}"
+        );
+    }
+
+    #[test]
+    fn suggestion_widens_over_context_copied_into_the_patch() {
+        let diffs = parse_unified_diff(DIFF).unwrap();
+        let comment = patched(
+            11,
+            "fn load(path: &str) -> String {\n    let text = std::fs::read_to_string(path).unwrap_or_default();\n    text",
+        );
+        assert_eq!(suggestion_range(&comment, &diffs), Some(10..=12));
+
+        let entry = review_comment_json(&comment, &diffs);
+        assert_eq!(entry["start_line"], 10);
+        assert_eq!(entry["start_side"], "RIGHT");
+        assert_eq!(entry["line"], 12);
+    }
+
+    #[test]
+    fn falls_back_to_prose_outside_the_diff() {
+        let diffs = parse_unified_diff(DIFF).unwrap();
+        let outside = patched(40, "let x = 1;");
+        assert_eq!(suggestion_range(&outside, &diffs), None);
+        let entry = review_comment_json(&outside, &diffs);
+        assert_eq!(entry["line"], 40);
+        assert!(!entry["body"].as_str().unwrap().contains("```suggestion"));
+
+        let mut no_patch = patched(11, "");
+        assert_eq!(suggestion_range(&no_patch, &diffs), None);
+        no_patch.patch = Some("```rust\nlet x = 1;\n```".into());
+        assert_eq!(suggestion_range(&no_patch, &diffs), None);
+    }
+
+    #[test]
+    fn annotation_level_follows_severity() {
+        let mut comment = patched(11, "");
+        comment.suggestion = Some("use `?`".into());
+        let bug = check_annotation_json(&comment);
+        assert_eq!(bug["path"], "src/lib.rs");
+        assert_eq!(
+            (bug["start_line"].as_u64(), bug["end_line"].as_u64()),
+            (Some(11), Some(11))
+        );
+        assert_eq!(bug["annotation_level"], "failure");
+        assert_eq!(bug["title"], "Bug");
+        assert_eq!(
+            bug["message"],
+            "panics on a missing file\n\nSuggestion: use `?`"
+        );
+
+        comment.severity = Severity::Suggestion;
+        comment.rule = Some("no-unwrap".into());
+        let notice = check_annotation_json(&comment);
+        assert_eq!(notice["annotation_level"], "notice");
+        assert_eq!(notice["title"], "no-unwrap");
+        assert_eq!(check_conclusion(&[comment], None), "neutral");
+    }
+
+    #[test]
+    fn pr_description_joins_title_and_body() {
+        let pr = serde_json::json!({ "title": " Add retries ", "body": "Retries 429s.\n" });
+        assert_eq!(pr_description(&pr), "Add retries\n\nRetries 429s.");
+        let pr = serde_json::json!({ "title": "Add retries", "body": null });
+        assert_eq!(pr_description(&pr), "Add retries");
+    }
+
+    #[test]
+    fn refused_request_error_carries_the_callers_hint() {
+        let err = api_error(
+            reqwest::StatusCode::FORBIDDEN,
+            "denied",
+            Some(CHECK_RUN_PERMISSION_HINT),
+        );
+        assert!(err.to_string().contains("checks: write"), "{err}");
+
+        let err = api_error(reqwest::StatusCode::FORBIDDEN, "denied", None);
+        assert!(!err.to_string().contains("checks: write"), "{err}");
+        let err = api_error(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            "oops",
+            Some(CHECK_RUN_PERMISSION_HINT),
+        );
+        assert!(!err.to_string().contains("checks: write"), "{err}");
+    }
+
+    #[test]
+    fn oversized_summary_is_cut_with_details_closed() {
+        let section = format!(
+            "<details>\n<summary><code>src/lib.rs</code></summary>\n\n{}\n</details>\n\n",
+            "finding\n".repeat(2_000)
+        );
+        let body = section.repeat(10);
+        let fitted = fit_comment_body(&body);
+        assert!(fitted.chars().count() <= MAX_BODY_CHARS);
+        assert!(fitted.ends_with("for the full report._\n"));
+        assert_eq!(
+            fitted.matches("<details>").count(),
+            fitted.matches("</details>").count()
+        );
+
+        assert_eq!(fit_comment_body(&section), section);
+    }
+
+    #[test]
+    fn oversized_body_closes_an_open_code_fence() {
+        let body = format!(
+            "**Bug**\n\n```suggestion\n{}```\n",
+            "let x = 1;\n".repeat(10_000)
+        );
+        let fitted = fit_comment_body(&body);
+        assert!(fitted.chars().count() <= MAX_BODY_CHARS);
+        let fences = fitted
+            .lines()
+            .filter(|line| line.starts_with("```"))
+            .count();
+        assert_eq!(fences, 2, "{}", &fitted[fitted.len() - 200..]);
+        assert!(fitted.ends_with("for the full report._\n"));
+    }
+
+    #[test]
+    fn empty_result_is_not_posted_only_if_findings_required() {
+        let mut result = ReviewResult {
+            comments: Vec::new(),
+            filtered_comments: Vec::new(),
+            summary: None,
+            stats: Default::default(),
+        };
+        assert_eq!(
+            plan_review_post(&result, true, true, GroupBy::Severity),
+            ReviewPost::Nothing
+        );
+        assert_eq!(
+            plan_review_post(&result, true, false, GroupBy::Severity),
+            ReviewPost::Nothing
+        );
+        assert_eq!(
+            plan_review_post(&result, false, true, GroupBy::Severity),
+            ReviewPost::Inline {
+                summary: "Argus Code Review: 0 comments (0 files reviewed)".into()
+            }
+        );
+
+        result.comments.push(patched(11, "let text = read(path)?;"));
+        result.stats.files_reviewed = 1;
+        assert_eq!(
+            plan_review_post(&result, true, true, GroupBy::Severity),
+            ReviewPost::Inline {
+                summary: "Argus Code Review: 1 comments (1 files reviewed)".into()
+            }
+        );
+        let ReviewPost::Summary { body } =
+            plan_review_post(&result, true, false, GroupBy::Severity)
+        else {
+            panic!("expected a summary review");
+        };
+        assert!(body.contains("panics on a missing file"), "{body}");
+        assert_eq!(review_event(&result.comments), "REQUEST_CHANGES");
+    }
+
     #[test]
     fn parse_valid_pr_reference() {
         let (owner, repo, num) = parse_pr_reference("rust-lang/rust#12345").unwrap();
@@ -262,4 +1054,43 @@ mod tests {
         let result = parse_pr_reference("owner/repo#abc");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn parse_pr_reference_from_url() {
+        let expected = ("rust-lang".to_string(), "rust".to_string(), 12345);
+        for url in [
+            "https://github.com/rust-lang/rust/pull/12345",
+            "https://github.com/rust-lang/rust/pull/12345/",
+            "https://github.com/rust-lang/rust/pull/12345/files",
+            "https://github.com/rust-lang/rust/pull/12345/files#diff-abc123",
+            "https://www.github.com/rust-lang/rust/pull/12345?w=1",
+            "http://github.com/rust-lang/rust/pull/12345",
+            "github.com/rust-lang/rust/pull/12345",
+        ] {
+            assert_eq!(parse_pr_reference(url).unwrap(), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn parse_pr_reference_rejects_malformed_input_listing_formats() {
+        for bad in [
+            "",
+            "owner/repo#",
+            "owner/repo#0",
+            "/repo#1",
+            "owner/#1",
+            "https://github.com/owner/repo/issues/12",
+            "https://github.com/owner/repo/pull/abc",
+            "https://github.com/owner/repo/pull/12/blame",
+            "https://github.com/owner/pull/12",
+            "https://gitlab.com/owner/repo/pull/12",
+        ] {
+            let err = parse_pr_reference(bad).unwrap_err().to_string();
+            assert!(err.contains("owner/repo#123"), "{bad}: {err}");
+            assert!(
+                err.contains("https://github.com/owner/repo/pull/123/files"),
+                "{bad}: {err}"
+            );
+        }
+    }
 }
@@ -0,0 +1,131 @@
+//! What the author says a change is for, added to the review prompt.
+//!
+//! For `argus review --commit` and `--compare` that is the commit messages;
+//! for `--pr` it is the pull request's title and description, fetched by
+//! [`GitHubClient::get_pr_description`](crate::github::GitHubClient::get_pr_description).
+
+use std::path::Path;
+use std::process::Command;
+
+use argus_core::ArgusError;
+
+/// Longest commit-message text added to a review prompt, in characters.
+pub const MAX_INTENT_CHARS: usize = 4000;
+
+/// Messages of the commits `spec` names, newest first, for the review
+/// prompt: one commit, or every commit in a `base..head` range.
+///
+/// Cut to [`MAX_INTENT_CHARS`] so a long branch doesn't crowd out the diff.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Git`] if `git log` cannot be run or fails, such
+/// as for an unknown ref.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use argus_review::intent::commit_messages;
+///
+/// let messages = commit_messages(Path::new("."), "main..HEAD").unwrap();
+/// println!("{messages}");
+/// ```
+pub fn commit_messages(repo_root: &Path, spec: &str) -> Result<String, ArgusError> {
+    let mut args = vec![
+        "-C",
+        repo_root.to_str().unwrap_or("."),
+        "log",
+        "--format=%B%x1e",
+    ];
+    if !spec.contains("..") {
+        args.push("-1");
+    }
+    args.push(spec);
+    let output = Command::new("git")
+        .args(&args)
+        .output()
+        .map_err(|e| ArgusError::Git(format!("failed to run git log {spec}: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ArgusError::Git(format!(
+            "git log failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let messages: Vec<&str> = log
+        .split('\x1e')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    let mut text = messages.join("\n\n---\n\n");
+    if text.chars().count() > MAX_INTENT_CHARS {
+        text = text.chars().take(MAX_INTENT_CHARS).collect();
+        text.push_str("\n… (more commit messages omitted)");
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn commit_messages_reads_one_commit_or_a_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(
+            root,
+            &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+        );
+        git(
+            root,
+            &[
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "Add parser\n\nHandles quoted paths.",
+            ],
+        );
+        git(
+            root,
+            &["commit", "-q", "--allow-empty", "-m", "Fix overflow"],
+        );
+
+        assert_eq!(commit_messages(root, "HEAD").unwrap(), "Fix overflow");
+        assert_eq!(
+            commit_messages(root, "HEAD~2..HEAD").unwrap(),
+            "Fix overflow\n\n---\n\nAdd parser\n\nHandles quoted paths."
+        );
+
+        let err = commit_messages(root, "nope..HEAD").unwrap_err();
+        assert!(err.to_string().contains("git log failed"), "{err}");
+    }
+
+    #[test]
+    fn long_commit_messages_are_cut() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        let message = "x".repeat(MAX_INTENT_CHARS + 10);
+        git(root, &["commit", "-q", "--allow-empty", "-m", &message]);
+
+        let text = commit_messages(root, "HEAD").unwrap();
+        assert!(text.ends_with("(more commit messages omitted)"));
+        assert!(text.chars().count() < MAX_INTENT_CHARS + 40);
+    }
+}
@@ -0,0 +1,303 @@
+//! Interactive, one-comment-at-a-time triage of review findings.
+//!
+//! [`run`] draws a ratatui terminal UI showing each comment with the diff
+//! hunk it points at, and records what the user decides for it. The UI only
+//! collects decisions: applying accepted patches and storing feedback is
+//! left to the caller, so this module has no side effects on the repository.
+
+use std::io::IsTerminal;
+
+use argus_core::{ArgusError, DiffHunk, ReviewComment, Severity};
+use argus_difflens::parser::FileDiff;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::patch::hunk_for;
+
+/// What the user decided for one comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Keep the comment and apply its patch, if it has one.
+    Accept,
+    /// Reject the comment as a false positive.
+    Dismiss,
+    /// Leave the comment undecided.
+    Skip,
+}
+
+/// Navigation state of an interactive session.
+///
+/// Kept separate from the drawing code so the key handling can be tested
+/// without a terminal.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::interactive::{Decision, Session};
+/// use ratatui::crossterm::event::KeyCode;
+///
+/// let mut session = Session::new(2);
+/// assert!(session.handle_key(KeyCode::Char('a')));
+/// assert!(!session.handle_key(KeyCode::Char('d')));
+/// assert_eq!(session.decisions(), &[Some(Decision::Accept), Some(Decision::Dismiss)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    current: usize,
+    decisions: Vec<Option<Decision>>,
+}
+
+impl Session {
+    /// Start a session over `len` comments, positioned at the first.
+    pub fn new(len: usize) -> Self {
+        Self {
+            current: 0,
+            decisions: vec![None; len],
+        }
+    }
+
+    /// Index of the comment being shown.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The decision recorded for each comment so far.
+    pub fn decisions(&self) -> &[Option<Decision>] {
+        &self.decisions
+    }
+
+    /// Apply one key press. Returns `false` once the session is over, either
+    /// because the last comment was decided or the user quit.
+    ///
+    /// `a`/Enter accepts, `d` dismisses, `s`/Right/`l` skips, Left/`h` goes
+    /// back to the previous comment, and `q`/Esc quits.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        let decision = match key {
+            KeyCode::Char('a') | KeyCode::Enter => Decision::Accept,
+            KeyCode::Char('d') => Decision::Dismiss,
+            KeyCode::Char('s') | KeyCode::Char('l') | KeyCode::Right => Decision::Skip,
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.current = self.current.saturating_sub(1);
+                return true;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            _ => return true,
+        };
+        if let Some(slot) = self.decisions.get_mut(self.current) {
+            *slot = Some(decision);
+        }
+        self.current += 1;
+        self.current < self.decisions.len()
+    }
+}
+
+/// Comments sorted by what the user decided in [`run`].
+#[derive(Debug, Clone, Default)]
+pub struct Outcome {
+    /// Comments the user accepted.
+    pub accepted: Vec<ReviewComment>,
+    /// Comments the user dismissed as false positives.
+    pub dismissed: Vec<ReviewComment>,
+    /// Comments skipped or never reached before quitting.
+    pub skipped: Vec<ReviewComment>,
+}
+
+impl Outcome {
+    fn from_session(comments: &[ReviewComment], session: &Session) -> Self {
+        let mut outcome = Self::default();
+        for (comment, decision) in comments.iter().zip(session.decisions()) {
+            let bucket = match decision {
+                Some(Decision::Accept) => &mut outcome.accepted,
+                Some(Decision::Dismiss) => &mut outcome.dismissed,
+                Some(Decision::Skip) | None => &mut outcome.skipped,
+            };
+            bucket.push(comment.clone());
+        }
+        outcome
+    }
+}
+
+/// Step through `comments` in a terminal UI and return what was decided.
+///
+/// Each comment is shown with its message, suggestion, patch, and the diff
+/// hunk from `diffs` it points at, with the commented line highlighted.
+/// Quitting early leaves the remaining comments in [`Outcome::skipped`].
+///
+/// Fails with [`ArgusError::Config`] when stdout is not a terminal.
+pub fn run(comments: &[ReviewComment], diffs: &[FileDiff]) -> Result<Outcome, ArgusError> {
+    if !std::io::stdout().is_terminal() {
+        return Err(ArgusError::Config(
+            "interactive review needs a terminal on stdout".into(),
+        ));
+    }
+    let mut session = Session::new(comments.len());
+    if comments.is_empty() {
+        return Ok(Outcome::default());
+    }
+
+    let mut terminal = ratatui::init();
+    let result = (|| -> Result<(), ArgusError> {
+        loop {
+            terminal.draw(|frame| draw(frame, comments, diffs, &session))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !session.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    })();
+    ratatui::restore();
+    result?;
+
+    Ok(Outcome::from_session(comments, &session))
+}
+
+fn draw(frame: &mut Frame, comments: &[ReviewComment], diffs: &[FileDiff], session: &Session) {
+    let comment = &comments[session.current()];
+    let [header, details, context, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Percentage(40),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let mut title = vec![
+        Span::styled(
+            format!("[{}/{}] ", session.current() + 1, comments.len()),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            comment.severity.to_string().to_uppercase(),
+            Style::default()
+                .fg(severity_color(comment.severity))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(" {}:{}", comment.file_path.display(), comment.line)),
+    ];
+    if let Some(Some(decision)) = session.decisions().get(session.current()) {
+        title.push(Span::styled(
+            format!("  ({decision:?})").to_lowercase(),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    frame.render_widget(Line::from(title), header);
+
+    let mut text = Text::from(comment.message.as_str());
+    if let Some(suggestion) = &comment.suggestion {
+        text.push_line(Line::default());
+        text.push_line(Line::styled(
+            "Suggestion:",
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        text.extend(Text::from(suggestion.as_str()));
+    }
+    if let Some(patch) = comment.patch.as_deref().filter(|p| !p.is_empty()) {
+        text.push_line(Line::default());
+        text.push_line(Line::styled(
+            "Patch (applied on accept):",
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        text.extend(Text::styled(patch, Style::default().fg(Color::Green)));
+    }
+    frame.render_widget(
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title("Comment")),
+        details,
+    );
+
+    let hunk_lines = match hunk_for(diffs, comment) {
+        Some(hunk) => hunk_lines(hunk, comment.line),
+        None => vec![Line::styled(
+            "(no diff hunk covers this line)",
+            Style::default().fg(Color::DarkGray),
+        )],
+    };
+    frame.render_widget(
+        Paragraph::new(hunk_lines).block(Block::default().borders(Borders::ALL).title("Diff")),
+        context,
+    );
+
+    frame.render_widget(
+        Line::styled(
+            "a/Enter accept · d dismiss · s/→ skip · ← back · q quit",
+            Style::default().fg(Color::DarkGray),
+        ),
+        footer,
+    );
+}
+
+/// Color the hunk's lines and highlight the one at new-file line `target`.
+fn hunk_lines(hunk: &DiffHunk, target: u32) -> Vec<Line<'_>> {
+    let mut new_line = hunk.new_start;
+    hunk.content
+        .lines()
+        .map(|line| {
+            let (style, number) = match line.chars().next() {
+                Some('+') => (Style::default().fg(Color::Green), Some(new_line)),
+                Some('-') => (Style::default().fg(Color::Red), None),
+                _ => (Style::default(), Some(new_line)),
+            };
+            if number.is_some() {
+                new_line += 1;
+            }
+            let style = if number == Some(target) {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            };
+            Line::styled(line, style)
+        })
+        .collect()
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Bug => Color::Red,
+        Severity::Warning => Color::Yellow,
+        Severity::Suggestion => Color::Cyan,
+        Severity::Info => Color::Blue,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_revisits_and_overrides_a_decision() {
+        let mut session = Session::new(3);
+        assert!(session.handle_key(KeyCode::Char('d')));
+        assert!(session.handle_key(KeyCode::Left));
+        assert_eq!(session.current(), 0);
+        assert!(session.handle_key(KeyCode::Char('a')));
+        assert!(session.handle_key(KeyCode::Char('x')));
+        assert_eq!(session.current(), 1);
+        assert!(!session.handle_key(KeyCode::Char('q')));
+        assert_eq!(session.decisions(), &[Some(Decision::Accept), None, None]);
+    }
+
+    #[test]
+    fn highlights_the_commented_line_in_the_hunk() {
+        let hunk = DiffHunk {
+            file_path: "a.rs".into(),
+            old_start: 10,
+            old_lines: 2,
+            new_start: 10,
+            new_lines: 2,
+            content: " keep\n-old\n+new\n".into(),
+            change_type: argus_core::ChangeType::Modify,
+        };
+        let lines = hunk_lines(&hunk, 11);
+        let reversed: Vec<bool> = lines
+            .iter()
+            .map(|line| line.style.add_modifier.contains(Modifier::REVERSED))
+            .collect();
+        assert_eq!(reversed, [false, false, true]);
+    }
+}
@@ -0,0 +1,216 @@
+//! JUnit XML output for `argus review --format junit`.
+//!
+//! CI systems such as Jenkins and GitLab render JUnit reports natively, so
+//! review findings show up in the same test dashboards as the build's own
+//! test results.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use argus_core::{ReviewComment, Severity};
+
+use crate::pipeline::ReviewResult;
+
+/// Convert a review result to a JUnit XML report.
+///
+/// Each reviewed file with findings becomes a `<testsuite>`, and each
+/// comment a `<testcase>` in it. Bugs and warnings are reported as
+/// `<failure>`s carrying the message; suggestions and info comments are
+/// `<skipped>`, so they show up in CI test dashboards without failing the
+/// build there.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_review::junit::to_junit;
+/// use argus_review::pipeline::{ReviewResult, ReviewStats};
+///
+/// let result = ReviewResult {
+///     comments: vec![ReviewComment {
+///         file_path: PathBuf::from("src/lib.rs"),
+///         line: 7,
+///         severity: Severity::Bug,
+///         message: "unchecked index".into(),
+///         confidence: 95.0,
+///         suggestion: None,
+///         ..Default::default()
+///     }],
+///     filtered_comments: vec![],
+///     summary: None,
+///     stats: ReviewStats::default(),
+/// };
+/// let xml = to_junit(&result);
+/// assert!(xml.contains(r#"<testsuite name="src/lib.rs" tests="1" failures="1" skipped="0">"#));
+/// assert!(xml.contains(r#"<failure message="unchecked index" type="bug">"#));
+/// ```
+pub fn to_junit(result: &ReviewResult) -> String {
+    let mut by_file: BTreeMap<String, Vec<&ReviewComment>> = BTreeMap::new();
+    for c in &result.comments {
+        by_file
+            .entry(c.file_path.display().to_string())
+            .or_default()
+            .push(c);
+    }
+
+    let failures = result.comments.iter().filter(|c| is_failure(c)).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"argus\" tests=\"{}\" failures=\"{failures}\" skipped=\"{}\">",
+        result.comments.len(),
+        result.comments.len() - failures,
+    );
+
+    for (file, comments) in &by_file {
+        let file_failures = comments.iter().filter(|c| is_failure(c)).count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{file_failures}\" skipped=\"{}\">",
+            escape(file),
+            comments.len(),
+            comments.len() - file_failures,
+        );
+        for c in comments {
+            let rule = c.rule.as_deref().unwrap_or(severity_name(c.severity));
+            let _ = writeln!(
+                xml,
+                "    <testcase name=\"{}:{} [{}]\" classname=\"{}\">",
+                escape(file),
+                c.line,
+                escape(rule),
+                escape(file),
+            );
+            if is_failure(c) {
+                let _ = writeln!(
+                    xml,
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                    escape(&c.message),
+                    severity_name(c.severity),
+                    escape(&failure_body(c)),
+                );
+            } else {
+                let _ = writeln!(
+                    xml,
+                    "      <skipped message=\"{}\"/>",
+                    escape(&format!("{}: {}", severity_name(c.severity), c.message)),
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn is_failure(comment: &ReviewComment) -> bool {
+    matches!(comment.severity, Severity::Bug | Severity::Warning)
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Warning => "warning",
+        Severity::Suggestion => "suggestion",
+        Severity::Info => "info",
+    }
+}
+
+/// Location, message, and suggestion, as shown in a dashboard's failure view.
+fn failure_body(comment: &ReviewComment) -> String {
+    let mut body = format!(
+        "{}:{}: {}",
+        comment.file_path.display(),
+        comment.line,
+        comment.message
+    );
+    if let Some(suggestion) = &comment.suggestion {
+        body.push_str("\nSuggestion: ");
+        body.push_str(suggestion);
+    }
+    body
+}
+
+/// Escape text for use in XML attributes and element content.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            // Control characters other than tab are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::pipeline::ReviewStats;
+
+    fn comment(file: &str, line: u32, severity: Severity, message: &str) -> ReviewComment {
+        ReviewComment {
+            file_path: PathBuf::from(file),
+            line,
+            severity,
+            message: message.into(),
+            confidence: 90.0,
+            suggestion: None,
+            ..Default::default()
+        }
+    }
+
+    fn make_result(comments: Vec<ReviewComment>) -> ReviewResult {
+        ReviewResult {
+            comments,
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats::default(),
+        }
+    }
+
+    #[test]
+    fn groups_by_file_and_maps_severity_to_failure_or_skipped() {
+        let xml = to_junit(&make_result(vec![
+            comment("b.rs", 3, Severity::Suggestion, "rename"),
+            comment("a.rs", 1, Severity::Warning, "leak"),
+            comment("b.rs", 9, Severity::Bug, "panic"),
+        ]));
+
+        assert!(xml.contains(r#"<testsuites name="argus" tests="3" failures="2" skipped="1">"#));
+        let a = xml.find(r#"<testsuite name="a.rs""#).unwrap();
+        let b = xml.find(r#"<testsuite name="b.rs" tests="2" failures="1" skipped="1">"#);
+        assert!(a < b.unwrap(), "suites sorted by path:\n{xml}");
+        assert!(xml.contains(r#"<skipped message="suggestion: rename"/>"#));
+        assert!(xml.contains(r#"<testcase name="b.rs:9 [bug]" classname="b.rs">"#));
+    }
+
+    #[test]
+    fn escapes_markup_in_messages() {
+        let mut c = comment("a.rs", 1, Severity::Bug, "use `Vec<u8>` & \"bytes\"");
+        c.rule = Some("no-<raw>".into());
+        let xml = to_junit(&make_result(vec![c]));
+        assert!(xml.contains("message=\"use `Vec&lt;u8&gt;` &amp; &quot;bytes&quot;\""));
+        assert!(xml.contains("[no-&lt;raw&gt;]"));
+        assert!(!xml.contains("Vec<u8>"));
+    }
+
+    #[test]
+    fn empty_result_is_an_empty_report() {
+        let xml = to_junit(&make_result(vec![]));
+        assert!(xml.contains(r#"<testsuites name="argus" tests="0" failures="0" skipped="0">"#));
+        assert!(!xml.contains("<testsuite "));
+    }
+}
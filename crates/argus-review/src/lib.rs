@@ -3,11 +3,23 @@
 //! Provides the review pipeline: LLM client, prompt construction,
 //! review orchestration with filtering, and GitHub PR integration.
 
+pub mod compare;
+pub mod explain;
 pub mod feedback;
+pub mod full;
 pub mod github;
+pub mod intent;
+pub mod interactive;
+pub mod junit;
 pub mod llm;
 pub mod patch;
+pub mod patterns;
 pub mod pipeline;
+pub mod progress;
 pub mod prompt;
+pub mod ruletest;
 pub mod sarif;
+pub mod scopes;
 pub mod state;
+pub mod suppress;
+pub mod testpresence;
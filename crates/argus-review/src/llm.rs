@@ -1,7 +1,9 @@
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use argus_core::{ArgusError, LlmConfig};
+use argus_core::{ArgusError, LlmConfig, LlmError, LlmErrorKind, NetworkConfig};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// A message in a chat conversation with the LLM.
 ///
@@ -54,6 +56,21 @@ enum Provider {
     Ollama,
 }
 
+impl Provider {
+    /// Output token limit used when `[llm] max_output_tokens` is unset:
+    /// enough for a long review, within what the default models accept.
+    ///
+    /// OpenAI-compatible servers get none, since reasoning models and many
+    /// compatible servers reject or cap the parameter.
+    fn default_max_output_tokens(self) -> Option<u32> {
+        match self {
+            Provider::OpenAi => None,
+            Provider::Anthropic => Some(16384),
+            Provider::Gemini | Provider::Ollama => Some(8192),
+        }
+    }
+}
+
 /// Multi-provider LLM chat client.
 ///
 /// Supports OpenAI-compatible (`/v1/chat/completions`), Anthropic
@@ -78,16 +95,82 @@ pub struct LlmClient {
     api_key: Option<String>,
     model: String,
     base_url: Option<String>,
+    response_cache: Option<PathBuf>,
+    temperature: f64,
+    seed: Option<u64>,
+    max_output_tokens: Option<u32>,
 }
 
 const MAX_ERROR_REASON_CHARS: usize = 320;
 
+/// Name of the tool Anthropic is forced to call for structured output.
+const ANTHROPIC_JSON_TOOL: &str = "respond";
+
+/// The shape of answer a chat request asks the provider for.
+///
+/// [`Json`](Self::Json) turns on each provider's structured-output mode:
+/// OpenAI `response_format: json_object`, Gemini `responseMimeType:
+/// application/json` (plus `responseSchema` when a schema is given),
+/// Anthropic a forced tool call whose input is the answer, and Ollama
+/// `format: json`. Callers should still strip code fences, for
+/// OpenAI-compatible servers that ignore the request.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::llm::ResponseFormat;
+///
+/// let schema = serde_json::json!({"type": "object"});
+/// let format = ResponseFormat::Json(Some(schema));
+/// assert_ne!(format, ResponseFormat::Text);
+/// assert_eq!(ResponseFormat::default(), ResponseFormat::Text);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ResponseFormat {
+    /// Free-form text.
+    #[default]
+    Text,
+    /// A JSON object, matching the JSON Schema when one is given.
+    Json(Option<serde_json::Value>),
+}
+
+/// The text of a chat response, and whether the provider stopped it early.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::llm::ChatResponse;
+///
+/// let response = ChatResponse::from("{\"comments\": []}");
+/// assert!(!response.truncated);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatResponse {
+    /// Text of the answer.
+    pub content: String,
+    /// Whether generation stopped at the output token limit, so `content`
+    /// may end mid-answer: OpenAI `finish_reason: length`, Anthropic
+    /// `stop_reason: max_tokens`, Gemini `finishReason: MAX_TOKENS`, or
+    /// Ollama `done_reason: length`.
+    pub truncated: bool,
+}
+
+impl From<&str> for ChatResponse {
+    fn from(content: &str) -> Self {
+        Self {
+            content: content.into(),
+            truncated: false,
+        }
+    }
+}
+
 impl std::fmt::Debug for LlmClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LlmClient")
             .field("provider", &self.provider)
             .field("model", &self.model)
             .field("base_url", &self.base_url)
+            .field("response_cache", &self.response_cache)
             .finish_non_exhaustive()
     }
 }
@@ -150,25 +233,143 @@ impl LlmClient {
             _ => config.model.clone(),
         };
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
-            .build()
-            .map_err(|e| ArgusError::Llm(format!("failed to create HTTP client: {e}")))?;
-
         Ok(Self {
-            client,
+            client: http_client(&NetworkConfig::default())?,
             provider,
             api_key,
             model,
             base_url: config.base_url.clone(),
+            response_cache: None,
+            temperature: config.temperature,
+            seed: config.seed,
+            max_output_tokens: config
+                .max_output_tokens
+                .or_else(|| provider.default_max_output_tokens()),
         })
     }
 
+    /// Cache responses on disk under `dir`, keyed by provider, model,
+    /// sampling settings, response format, and the exact messages sent.
+    ///
+    /// A request seen before is answered from the cache without calling the
+    /// provider, which makes repeated runs deterministic and offline (e.g.
+    /// `argus rule-test` in CI). Misses call the provider and store the
+    /// response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::LlmConfig;
+    /// use argus_review::llm::LlmClient;
+    ///
+    /// let client = LlmClient::new(&LlmConfig::default())
+    ///     .unwrap()
+    ///     .with_response_cache("fixtures/llm-cache");
+    /// ```
+    pub fn with_response_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.response_cache = Some(dir.into());
+        self
+    }
+
+    /// Apply `[network]` connection pool settings.
+    ///
+    /// The client keeps one connection pool for its lifetime, so review
+    /// passes and retries reuse open keep-alive connections instead of
+    /// repeating the TLS handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Llm`] if the HTTP client cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::{LlmConfig, NetworkConfig};
+    /// use argus_review::llm::LlmClient;
+    ///
+    /// let network = NetworkConfig { max_connections: 16 };
+    /// let client = LlmClient::new(&LlmConfig::default())
+    ///     .unwrap()
+    ///     .with_network(&network)
+    ///     .unwrap();
+    /// ```
+    pub fn with_network(mut self, network: &NetworkConfig) -> Result<Self, ArgusError> {
+        self.client = http_client(network)?;
+        Ok(self)
+    }
+
+    /// Build clients for the `[llm] fallback` providers, in order.
+    ///
+    /// Each fallback resolves its own API key from the provider's env var
+    /// and uses that provider's default model; the primary's `api_key`,
+    /// `model`, and `base_url` are not inherited. `temperature` and `seed`
+    /// are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Llm`] if a fallback names an unknown provider.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::LlmConfig;
+    /// use argus_review::llm::LlmClient;
+    ///
+    /// let config = LlmConfig {
+    ///     fallback: vec!["anthropic".into(), "ollama".into()],
+    ///     ..LlmConfig::default()
+    /// };
+    /// let fallbacks = LlmClient::fallbacks(&config).unwrap();
+    /// assert_eq!(fallbacks.len(), 2);
+    /// assert_eq!(fallbacks[0].provider_name(), "anthropic");
+    /// ```
+    pub fn fallbacks(config: &LlmConfig) -> Result<Vec<Self>, ArgusError> {
+        config
+            .fallback
+            .iter()
+            .map(|provider| {
+                Self::new(&LlmConfig {
+                    provider: provider.clone(),
+                    temperature: config.temperature,
+                    seed: config.seed,
+                    ..LlmConfig::default()
+                })
+            })
+            .collect()
+    }
+
     /// Return the model name from the configuration.
     pub fn model(&self) -> &str {
         &self.model
     }
 
+    /// Return the most tokens a response may contain: `[llm]
+    /// max_output_tokens`, or the provider's default. `None` leaves the
+    /// limit to an OpenAI-compatible server.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::LlmConfig;
+    /// use argus_review::llm::LlmClient;
+    ///
+    /// let client = LlmClient::new(&LlmConfig::default()).unwrap();
+    /// assert_eq!(client.max_output_tokens(), None);
+    /// ```
+    pub fn max_output_tokens(&self) -> Option<u32> {
+        self.max_output_tokens
+    }
+
+    /// Return the provider name (`"openai"`, `"anthropic"`, `"gemini"`, or `"ollama"`).
+    pub fn provider_name(&self) -> &'static str {
+        match self.provider {
+            Provider::OpenAi => "openai",
+            Provider::Anthropic => "anthropic",
+            Provider::Gemini => "gemini",
+            Provider::Ollama => "ollama",
+        }
+    }
+
     /// Send a chat request and return the text response.
     ///
     /// Dispatches to the OpenAI, Anthropic, or Gemini API based on the
@@ -178,32 +379,176 @@ impl LlmClient {
     ///
     /// # Errors
     ///
-    /// Returns [`ArgusError::Llm`] on HTTP errors or response parsing failures.
+    /// Returns [`ArgusError::LlmRequest`] on HTTP errors or response parsing
+    /// failures, classified by [`LlmErrorKind`].
     pub async fn chat(&self, messages: Vec<ChatMessage>) -> Result<String, ArgusError> {
+        self.chat_with_format(messages, &ResponseFormat::Text).await
+    }
+
+    /// Like [`chat`](Self::chat), but asks the provider for an answer in
+    /// `format`, using its structured-output feature for JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::LlmRequest`] on HTTP errors or response parsing
+    /// failures, classified by [`LlmErrorKind`].
+    pub async fn chat_with_format(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<String, ArgusError> {
+        self.chat_response(messages, format)
+            .await
+            .map(|response| response.content)
+    }
+
+    /// Like [`chat_with_format`](Self::chat_with_format), but also reports
+    /// whether the answer was cut off at the output token limit.
+    ///
+    /// Truncated answers are not stored in the response cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::LlmRequest`] on HTTP errors or response parsing
+    /// failures, classified by [`LlmErrorKind`].
+    pub async fn chat_response(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
+        let Some(dir) = &self.response_cache else {
+            return self.chat_uncached(messages, format).await;
+        };
+
+        let path = dir.join(format!("{}.txt", self.cache_key(&messages, format)));
+        if let Ok(cached) = std::fs::read_to_string(&path) {
+            return Ok(ChatResponse::from(cached.as_str()));
+        }
+        let response = self.chat_uncached(messages, format).await?;
+        if !response.truncated {
+            store_cached_response(&path, &response.content)?;
+        }
+        Ok(response)
+    }
+
+    async fn chat_uncached(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
         match self.provider {
-            Provider::OpenAi => self.chat_openai(messages).await,
-            Provider::Anthropic => self.chat_anthropic(messages).await,
-            Provider::Gemini => self.chat_gemini(messages).await,
-            Provider::Ollama => self.chat_ollama(messages).await,
+            Provider::OpenAi => self.chat_openai(messages, format).await,
+            Provider::Anthropic => self.chat_anthropic(messages, format).await,
+            Provider::Gemini => self.chat_gemini(messages, format).await,
+            Provider::Ollama => self.chat_ollama(messages, format).await,
+        }
+    }
+
+    /// Set the configured temperature, and the seed where the provider
+    /// takes one, in the request `body` built for this client's provider.
+    ///
+    /// Anthropic has no seed parameter, so its requests only get the
+    /// temperature.
+    fn apply_sampling(&self, body: &mut serde_json::Value) {
+        let params = match self.provider {
+            Provider::OpenAi | Provider::Anthropic => body,
+            Provider::Gemini => &mut body["generationConfig"],
+            Provider::Ollama => &mut body["options"],
+        };
+        params["temperature"] = self.temperature.into();
+        if let Some(seed) = self.seed.filter(|_| self.provider != Provider::Anthropic) {
+            params["seed"] = seed.into();
+        }
+    }
+
+    /// Set `[llm] max_output_tokens` in an OpenAI-compatible request `body`,
+    /// leaving the server's default when it is unset.
+    ///
+    /// OpenAI's reasoning models only accept `max_completion_tokens`, while
+    /// compatible servers behind a `base_url` mostly expect `max_tokens`.
+    fn apply_openai_output_limit(&self, body: &mut serde_json::Value) {
+        let Some(limit) = self.max_output_tokens else {
+            return;
+        };
+        let key = if self.base_url.is_some() {
+            "max_tokens"
+        } else {
+            "max_completion_tokens"
+        };
+        body[key] = limit.into();
+    }
+
+    /// Whether a successful response body says generation stopped at the
+    /// output token limit.
+    fn hit_output_limit(&self, response_body: &serde_json::Value) -> bool {
+        let (reason, limit) = match self.provider {
+            Provider::OpenAi => (&response_body["choices"][0]["finish_reason"], "length"),
+            Provider::Anthropic => (&response_body["stop_reason"], "max_tokens"),
+            Provider::Gemini => (
+                &response_body["candidates"][0]["finishReason"],
+                "MAX_TOKENS",
+            ),
+            Provider::Ollama => (&response_body["done_reason"], "length"),
+        };
+        reason.as_str() == Some(limit)
+    }
+
+    /// Hex SHA-256 of provider, model, sampling settings, output limit,
+    /// response format, and messages: the response cache key.
+    fn cache_key(&self, messages: &[ChatMessage], format: &ResponseFormat) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.provider_name().as_bytes());
+        hasher.update([0]);
+        hasher.update(self.model.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.temperature.to_bits().to_le_bytes());
+        hasher.update(format!("{:?}", self.seed));
+        hasher.update([0]);
+        hasher.update(format!("{:?}", self.max_output_tokens));
+        hasher.update([0]);
+        match format {
+            ResponseFormat::Text => hasher.update("text"),
+            ResponseFormat::Json(schema) => {
+                hasher.update("json");
+                if let Some(schema) = schema {
+                    hasher.update(schema.to_string());
+                }
+            }
+        }
+        hasher.update([0]);
+        for message in messages {
+            hasher.update(serde_json::to_string(&message.role).unwrap_or_default());
+            hasher.update([0]);
+            hasher.update(message.content.as_bytes());
+            hasher.update([0]);
         }
+        format!("{:x}", hasher.finalize())
     }
 
-    async fn chat_openai(&self, messages: Vec<ChatMessage>) -> Result<String, ArgusError> {
+    async fn chat_openai(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
         let api_key = self.api_key.as_deref().ok_or_else(|| {
-            ArgusError::Llm(
-                "OpenAI API key required. Set it in .argus.toml or export OPENAI_API_KEY".into(),
+            LlmError::new(
+                LlmErrorKind::Auth,
+                "OpenAI API key required. Set it in .argus.toml or export OPENAI_API_KEY",
             )
         })?;
 
         let base_url = self.base_url.as_deref().unwrap_or("https://api.openai.com");
         let url = format!("{base_url}/v1/chat/completions");
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
             "messages": messages,
-            "temperature": 0.1,
-            "response_format": { "type": "json_object" },
         });
+        self.apply_openai_output_limit(&mut body);
+        self.apply_sampling(&mut body);
+        if let ResponseFormat::Json(_) = format {
+            body["response_format"] = serde_json::json!({ "type": "json_object" });
+        }
 
         let mut request = self.client.post(&url);
         request = request.header("Authorization", format!("Bearer {api_key}"));
@@ -213,30 +558,27 @@ impl LlmClient {
             .json(&body)
             .send()
             .await
-            .map_err(|e| ArgusError::Llm(format!("OpenAI request failed: {e}")))?;
+            .map_err(|e| request_error("OpenAI", &e))?;
 
         let status = response.status();
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Err(ArgusError::Llm(
-                "OpenAI API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds."
-                    .into(),
-            ));
+            return Err(LlmError::new(
+                LlmErrorKind::RateLimit,
+                "OpenAI API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds.",
+            )
+            .with_status(429)
+            .into());
         }
 
         if !status.is_success() {
             let body_text = response.text().await.unwrap_or_default();
-            return Err(ArgusError::Llm(sanitize_provider_error(
-                "OpenAI",
-                status,
-                &body_text,
-                &[],
-            )));
+            return Err(status_error("OpenAI", status, &body_text, &[]).into());
         }
 
         let response_body: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| ArgusError::Llm(format!("failed to parse OpenAI response: {e}")))?;
+            .map_err(|e| bad_response(format!("failed to parse OpenAI response: {e}")))?;
 
         let content = response_body
             .get("choices")
@@ -245,19 +587,26 @@ impl LlmClient {
             .and_then(|m| m.get("content"))
             .and_then(|c| c.as_str())
             .ok_or_else(|| {
-                ArgusError::Llm(format!(
+                bad_response(format!(
                     "unexpected OpenAI response structure: {response_body}"
                 ))
             })?;
 
-        Ok(content.to_string())
+        Ok(ChatResponse {
+            content: content.to_string(),
+            truncated: self.hit_output_limit(&response_body),
+        })
     }
 
-    async fn chat_anthropic(&self, messages: Vec<ChatMessage>) -> Result<String, ArgusError> {
+    async fn chat_anthropic(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
         let api_key = self.api_key.as_deref().ok_or_else(|| {
-            ArgusError::Llm(
-                "Anthropic API key required. Set it in .argus.toml or export ANTHROPIC_API_KEY"
-                    .into(),
+            LlmError::new(
+                LlmErrorKind::Auth,
+                "Anthropic API key required. Set it in .argus.toml or export ANTHROPIC_API_KEY",
             )
         })?;
 
@@ -299,12 +648,21 @@ impl LlmClient {
 
         let mut body = serde_json::json!({
             "model": self.model,
-            "max_tokens": 4096,
+            "max_tokens": self.max_output_tokens,
             "messages": api_messages,
         });
         if let Some(system) = &system_text {
             body["system"] = serde_json::Value::String(system.clone());
         }
+        self.apply_sampling(&mut body);
+        if let ResponseFormat::Json(schema) = format {
+            // Forcing a tool call makes the tool input the structured answer
+            body["tools"] = serde_json::json!([anthropic_json_tool(schema.as_ref())]);
+            body["tool_choice"] = serde_json::json!({
+                "type": "tool",
+                "name": ANTHROPIC_JSON_TOOL,
+            });
+        }
 
         let mut request = self.client.post(&url);
         request = request.header("x-api-key", api_key);
@@ -316,55 +674,68 @@ impl LlmClient {
             .json(&body)
             .send()
             .await
-            .map_err(|e| ArgusError::Llm(format!("Anthropic request failed: {e}")))?;
+            .map_err(|e| request_error("Anthropic", &e))?;
 
         let status = response.status();
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Err(ArgusError::Llm(
-                "Anthropic API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds."
-                    .into(),
-            ));
+            return Err(LlmError::new(
+                LlmErrorKind::RateLimit,
+                "Anthropic API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds.",
+            )
+            .with_status(429)
+            .into());
         }
 
         if !status.is_success() {
             let body_text = response.text().await.unwrap_or_default();
-            return Err(ArgusError::Llm(sanitize_provider_error(
-                "Anthropic",
-                status,
-                &body_text,
-                &[],
-            )));
+            return Err(status_error("Anthropic", status, &body_text, &[]).into());
         }
 
         let response_body: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| ArgusError::Llm(format!("failed to parse Anthropic response: {e}")))?;
+            .map_err(|e| bad_response(format!("failed to parse Anthropic response: {e}")))?;
 
         // Iterate content blocks to find the first "text" type, skipping "thinking" blocks
         let content_array = response_body
             .get("content")
             .and_then(|c| c.as_array())
             .ok_or_else(|| {
-                ArgusError::Llm(format!(
+                bad_response(format!(
                     "unexpected Anthropic response structure: {response_body}"
                 ))
             })?;
 
+        let truncated = self.hit_output_limit(&response_body);
+        if let Some(input) = anthropic_tool_input(content_array) {
+            return Ok(ChatResponse {
+                content: input,
+                truncated,
+            });
+        }
+
         let text = content_array
             .iter()
             .find(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"))
             .and_then(|block| block.get("text"))
             .and_then(|t| t.as_str())
-            .ok_or_else(|| ArgusError::Llm("No text content in Anthropic response".into()))?;
+            .ok_or_else(|| bad_response("No text content in Anthropic response".into()))?;
 
-        Ok(text.to_string())
+        Ok(ChatResponse {
+            content: text.to_string(),
+            truncated,
+        })
     }
 
-    async fn chat_gemini(&self, messages: Vec<ChatMessage>) -> Result<String, ArgusError> {
+    async fn chat_gemini(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
         let api_key = self.api_key.as_deref().ok_or_else(|| {
-            ArgusError::Llm(
-                "Gemini API key required. Set it in .argus.toml or export GEMINI_API_KEY".into(),
+            LlmError::new(
+                LlmErrorKind::Auth,
+                "Gemini API key required. Set it in .argus.toml or export GEMINI_API_KEY",
             )
         })?;
 
@@ -381,6 +752,10 @@ impl LlmClient {
         // Redact the API key from error messages to prevent leaking it via
         // URLs embedded in reqwest errors.
         let redact = |msg: String| -> String { msg.replace(api_key, "[REDACTED]") };
+        let redact_error = |err: LlmError| LlmError {
+            message: redact(err.message),
+            ..err
+        };
 
         // Extract system messages and build contents array
         let mut system_parts: Vec<String> = Vec::new();
@@ -404,16 +779,22 @@ impl LlmClient {
         let mut body = serde_json::json!({
             "contents": contents,
             "generationConfig": {
-                "temperature": 0.1,
-                "maxOutputTokens": 4096,
+                "maxOutputTokens": self.max_output_tokens,
             },
         });
+        self.apply_sampling(&mut body);
         if !system_parts.is_empty() {
             let system_text = system_parts.join("\n\n");
             body["systemInstruction"] = serde_json::json!({
                 "parts": [{"text": system_text}],
             });
         }
+        if let ResponseFormat::Json(schema) = format {
+            body["generationConfig"]["responseMimeType"] = "application/json".into();
+            if let Some(schema) = schema {
+                body["generationConfig"]["responseSchema"] = gemini_schema(schema);
+            }
+        }
 
         // Gemini uses key in URL, no Authorization header needed
         let response = self
@@ -423,30 +804,30 @@ impl LlmClient {
             .json(&body)
             .send()
             .await
-            .map_err(|e| ArgusError::Llm(redact(format!("Gemini request failed: {e}"))))?;
+            .map_err(|e| redact_error(request_error("Gemini", &e)))?;
 
         let status = response.status();
 
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Err(ArgusError::Llm(redact(
-                "Gemini API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds."
-                    .to_string(),
-            )));
+            return Err(LlmError::new(
+                LlmErrorKind::RateLimit,
+                "Gemini API error 429 Too Many Requests: Rate limit exceeded. Please retry in a few seconds.",
+            )
+            .with_status(429)
+            .into());
         }
 
         if !status.is_success() {
             let body_text = response.text().await.unwrap_or_default();
-            return Err(ArgusError::Llm(redact(sanitize_provider_error(
-                "Gemini",
-                status,
-                &body_text,
-                &[api_key],
-            ))));
+            return Err(
+                redact_error(status_error("Gemini", status, &body_text, &[api_key])).into(),
+            );
         }
 
-        let response_body: serde_json::Value = response.json().await.map_err(|e| {
-            ArgusError::Llm(redact(format!("failed to parse Gemini response: {e}")))
-        })?;
+        let response_body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| bad_response(redact(format!("failed to parse Gemini response: {e}"))))?;
 
         let text = response_body
             .get("candidates")
@@ -457,27 +838,38 @@ impl LlmClient {
             .and_then(|p| p.get("text"))
             .and_then(|t| t.as_str())
             .ok_or_else(|| {
-                ArgusError::Llm(redact(format!(
+                bad_response(redact(format!(
                     "unexpected Gemini response structure: {response_body}"
                 )))
             })?;
 
-        Ok(text.to_string())
+        Ok(ChatResponse {
+            content: text.to_string(),
+            truncated: self.hit_output_limit(&response_body),
+        })
     }
 
-    async fn chat_ollama(&self, messages: Vec<ChatMessage>) -> Result<String, ArgusError> {
+    async fn chat_ollama(
+        &self,
+        messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
+    ) -> Result<ChatResponse, ArgusError> {
         let base_url = self.base_url.as_deref().unwrap_or("http://localhost:11434");
         let url = format!("{base_url}/api/chat");
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
             "messages": messages,
             "stream": false,
             "options": {
-                "temperature": 0.1,
                 "num_ctx": 4096,
+                "num_predict": self.max_output_tokens,
             }
         });
+        self.apply_sampling(&mut body);
+        if let ResponseFormat::Json(_) = format {
+            body["format"] = "json".into();
+        }
 
         let response = self
             .client
@@ -485,35 +877,162 @@ impl LlmClient {
             .json(&body)
             .send()
             .await
-            .map_err(|e| ArgusError::Llm(format!("Ollama request failed: {e}")))?;
+            .map_err(|e| request_error("Ollama", &e))?;
 
         let status = response.status();
         if !status.is_success() {
             let body_text = response.text().await.unwrap_or_default();
-            return Err(ArgusError::Llm(sanitize_provider_error(
-                "Ollama",
-                status,
-                &body_text,
-                &[],
-            )));
+            return Err(status_error("Ollama", status, &body_text, &[]).into());
         }
 
         let response_body: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| ArgusError::Llm(format!("failed to parse Ollama response: {e}")))?;
+            .map_err(|e| bad_response(format!("failed to parse Ollama response: {e}")))?;
 
         let content = response_body
             .get("message")
             .and_then(|m| m.get("content"))
             .and_then(|c| c.as_str())
             .ok_or_else(|| {
-                ArgusError::Llm(format!(
+                bad_response(format!(
                     "unexpected Ollama response structure: {response_body}"
                 ))
             })?;
 
-        Ok(content.to_string())
+        Ok(ChatResponse {
+            content: content.to_string(),
+            truncated: self.hit_output_limit(&response_body),
+        })
+    }
+}
+
+/// The tool Anthropic is forced to call for JSON output. Without a schema,
+/// any JSON object is accepted.
+fn anthropic_json_tool(schema: Option<&serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
+        "name": ANTHROPIC_JSON_TOOL,
+        "description": "Return the answer as structured JSON.",
+        "input_schema": schema
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({ "type": "object" })),
+    })
+}
+
+/// The serialized input of the structured-output tool call, if the
+/// response made one.
+fn anthropic_tool_input(content: &[serde_json::Value]) -> Option<String> {
+    content
+        .iter()
+        .find(|block| {
+            block.get("type").and_then(|t| t.as_str()) == Some("tool_use")
+                && block.get("name").and_then(|n| n.as_str()) == Some(ANTHROPIC_JSON_TOOL)
+        })
+        .and_then(|block| block.get("input"))
+        .map(|input| input.to_string())
+}
+
+/// Convert a JSON Schema to Gemini's OpenAPI subset: upper-case type names
+/// and no `additionalProperties`.
+fn gemini_schema(schema: &serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| key.as_str() != "additionalProperties")
+            .map(|(key, value)| {
+                let value = match (key.as_str(), value) {
+                    ("type", serde_json::Value::String(name)) => name.to_uppercase().into(),
+                    ("properties", serde_json::Value::Object(props)) => props
+                        .iter()
+                        .map(|(name, prop)| (name.clone(), gemini_schema(prop)))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into(),
+                    ("items", items) => gemini_schema(items),
+                    _ => value.clone(),
+                };
+                (key.clone(), value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        other => other.clone(),
+    }
+}
+
+/// Build an HTTP client with a keep-alive connection pool sized by `network`.
+fn http_client(network: &NetworkConfig) -> Result<reqwest::Client, ArgusError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .pool_max_idle_per_host(network.max_connections)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(60))
+        .http2_keep_alive_interval(Duration::from_secs(30))
+        .http2_keep_alive_while_idle(true)
+        .build()
+        .map_err(|e| ArgusError::Llm(format!("failed to create HTTP client: {e}")))
+}
+
+/// Error for a request that got no response: a timeout, or a network
+/// failure such as DNS, connection, or TLS.
+fn request_error(provider: &str, err: &reqwest::Error) -> LlmError {
+    let kind = if err.is_timeout() {
+        LlmErrorKind::Timeout
+    } else {
+        LlmErrorKind::Network
+    };
+    LlmError::new(kind, format!("{provider} request failed: {err}"))
+}
+
+/// Error for a non-success response, with a sanitized reason.
+fn status_error(
+    provider: &str,
+    status: reqwest::StatusCode,
+    body_text: &str,
+    extra_redactions: &[&str],
+) -> LlmError {
+    LlmError::new(
+        classify_status(status, body_text),
+        sanitize_provider_error(provider, status, body_text, extra_redactions),
+    )
+    .with_status(status.as_u16())
+}
+
+fn bad_response(message: String) -> LlmError {
+    LlmError::new(LlmErrorKind::BadResponse, message)
+}
+
+/// Classify a failed response. Provider error codes in the body win over
+/// the HTTP status: Gemini reports an invalid key as 400 `API_KEY_INVALID`,
+/// and OpenAI and Anthropic name auth and rate-limit errors explicitly.
+fn classify_status(status: reqwest::StatusCode, body_text: &str) -> LlmErrorKind {
+    const AUTH_CODES: &[&str] = &[
+        "invalid_api_key",
+        "authentication_error",
+        "permission_error",
+        "api_key_invalid",
+        "permission_denied",
+    ];
+    const RATE_LIMIT_CODES: &[&str] = &[
+        "rate_limit_exceeded",
+        "rate_limit_error",
+        "resource_exhausted",
+    ];
+
+    let lower = body_text.to_ascii_lowercase();
+    if AUTH_CODES.iter().any(|code| lower.contains(code)) {
+        return LlmErrorKind::Auth;
+    }
+    if RATE_LIMIT_CODES.iter().any(|code| lower.contains(code)) {
+        return LlmErrorKind::RateLimit;
+    }
+    if lower.contains("overloaded_error") {
+        return LlmErrorKind::Server;
+    }
+    match status.as_u16() {
+        401 | 403 => LlmErrorKind::Auth,
+        408 | 504 => LlmErrorKind::Timeout,
+        429 => LlmErrorKind::RateLimit,
+        500..=599 => LlmErrorKind::Server,
+        _ => LlmErrorKind::InvalidRequest,
     }
 }
 
@@ -660,6 +1179,14 @@ fn merge_consecutive_messages(messages: Vec<ChatMessage>) -> Vec<ChatMessage> {
     merged
 }
 
+fn store_cached_response(path: &Path, response: &str) -> Result<(), ArgusError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, response)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -682,6 +1209,33 @@ mod tests {
         assert_eq!(client.model(), "gpt-4o-mini");
     }
 
+    #[test]
+    fn fallbacks_resolve_their_own_models() {
+        let config = LlmConfig {
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            api_key: Some("primary-key".into()),
+            fallback: vec!["anthropic".into(), "gemini".into()],
+            ..LlmConfig::default()
+        };
+        let fallbacks = LlmClient::fallbacks(&config).unwrap();
+        assert_eq!(fallbacks.len(), 2);
+        assert_eq!(fallbacks[0].provider_name(), "anthropic");
+        assert_eq!(fallbacks[0].model(), "claude-sonnet-4-5");
+        assert_ne!(fallbacks[0].api_key.as_deref(), Some("primary-key"));
+        assert_eq!(fallbacks[1].provider_name(), "gemini");
+        assert_eq!(fallbacks[1].model(), "gemini-2.0-flash");
+    }
+
+    #[test]
+    fn unknown_fallback_provider_returns_error() {
+        let config = LlmConfig {
+            fallback: vec!["cohere".into()],
+            ..LlmConfig::default()
+        };
+        assert!(LlmClient::fallbacks(&config).is_err());
+    }
+
     #[test]
     fn chat_message_serializes() {
         let msg = ChatMessage {
@@ -848,14 +1402,126 @@ mod tests {
             "messages": messages,
         });
         body["system"] = serde_json::Value::String(system_text.to_string());
+        let client = LlmClient::new(&LlmConfig {
+            provider: "anthropic".into(),
+            seed: Some(7),
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        client.apply_sampling(&mut body);
 
         assert_eq!(body["model"], "claude-sonnet-4-5");
         assert_eq!(body["max_tokens"], 4096);
         assert_eq!(body["system"], "You are a reviewer.");
-        assert!(body.get("temperature").is_none());
+        assert_eq!(body["temperature"], 0.1);
+        // Anthropic has no seed parameter
+        assert!(body.get("seed").is_none());
         assert_eq!(body["messages"][0]["role"], "user");
     }
 
+    #[test]
+    fn output_limit_is_configurable_and_detected_per_provider() {
+        let client = |provider: &str, max_output_tokens: Option<u32>| {
+            LlmClient::new(&LlmConfig {
+                provider: provider.into(),
+                max_output_tokens,
+                ..LlmConfig::default()
+            })
+            .unwrap()
+        };
+        assert_eq!(client("openai", None).max_output_tokens(), None);
+        assert_eq!(client("gemini", None).max_output_tokens(), Some(8192));
+        assert_eq!(
+            client("anthropic", Some(32000)).max_output_tokens(),
+            Some(32000)
+        );
+
+        let openai_body = |max_output_tokens, base_url: Option<&str>| {
+            let client = LlmClient::new(&LlmConfig {
+                max_output_tokens,
+                base_url: base_url.map(String::from),
+                ..LlmConfig::default()
+            })
+            .unwrap();
+            let mut body = serde_json::json!({});
+            client.apply_openai_output_limit(&mut body);
+            body
+        };
+        assert_eq!(openai_body(None, None), serde_json::json!({}));
+        assert_eq!(
+            openai_body(Some(4000), None),
+            serde_json::json!({ "max_completion_tokens": 4000 })
+        );
+        assert_eq!(
+            openai_body(Some(4000), Some("http://localhost:8000")),
+            serde_json::json!({ "max_tokens": 4000 })
+        );
+
+        let cut_off = [
+            ("openai", r#"{"choices":[{"finish_reason":"length"}]}"#),
+            ("anthropic", r#"{"stop_reason":"max_tokens"}"#),
+            (
+                "gemini",
+                r#"{"candidates":[{"finishReason":"MAX_TOKENS"}]}"#,
+            ),
+            ("ollama", r#"{"done_reason":"length"}"#),
+        ];
+        for (provider, body) in cut_off {
+            let body: serde_json::Value = serde_json::from_str(body).unwrap();
+            assert!(client(provider, None).hit_output_limit(&body), "{provider}");
+        }
+        let finished = [
+            ("openai", r#"{"choices":[{"finish_reason":"stop"}]}"#),
+            ("anthropic", r#"{"stop_reason":"tool_use"}"#),
+            ("gemini", r#"{"candidates":[{"finishReason":"STOP"}]}"#),
+            ("ollama", r#"{"done_reason":"stop"}"#),
+        ];
+        for (provider, body) in finished {
+            let body: serde_json::Value = serde_json::from_str(body).unwrap();
+            assert!(
+                !client(provider, None).hit_output_limit(&body),
+                "{provider}"
+            );
+        }
+    }
+
+    #[test]
+    fn temperature_and_seed_go_where_each_provider_reads_them() {
+        let client = |provider: &str| {
+            LlmClient::new(&LlmConfig {
+                provider: provider.into(),
+                temperature: 0.0,
+                seed: Some(42),
+                ..LlmConfig::default()
+            })
+            .unwrap()
+        };
+
+        let mut openai = serde_json::json!({ "model": "gpt-4o" });
+        client("openai").apply_sampling(&mut openai);
+        assert_eq!(openai["temperature"], 0.0);
+        assert_eq!(openai["seed"], 42);
+
+        let mut gemini = serde_json::json!({ "generationConfig": { "maxOutputTokens": 4096 } });
+        client("gemini").apply_sampling(&mut gemini);
+        assert_eq!(gemini["generationConfig"]["temperature"], 0.0);
+        assert_eq!(gemini["generationConfig"]["seed"], 42);
+        assert_eq!(gemini["generationConfig"]["maxOutputTokens"], 4096);
+
+        let mut ollama = serde_json::json!({ "options": { "num_ctx": 4096 } });
+        client("ollama").apply_sampling(&mut ollama);
+        assert_eq!(ollama["options"]["temperature"], 0.0);
+        assert_eq!(ollama["options"]["seed"], 42);
+
+        let fallbacks = LlmClient::fallbacks(&LlmConfig {
+            fallback: vec!["gemini".into()],
+            seed: Some(42),
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        assert_eq!(fallbacks[0].seed, Some(42));
+    }
+
     #[test]
     fn anthropic_response_parsing() {
         let response = serde_json::json!({
@@ -1219,4 +1885,268 @@ mod tests {
         assert!(sanitized.contains("[REDACTED]"));
         assert!(!sanitized.contains(api_key));
     }
+
+    #[test]
+    fn classify_status_prefers_provider_error_codes() {
+        use reqwest::StatusCode;
+
+        let gemini_bad_key = r#"{"error":{"code":400,"status":"INVALID_ARGUMENT","details":[{"reason":"API_KEY_INVALID"}]}}"#;
+        assert_eq!(
+            classify_status(StatusCode::BAD_REQUEST, gemini_bad_key),
+            LlmErrorKind::Auth
+        );
+        assert_eq!(
+            classify_status(
+                StatusCode::from_u16(529).unwrap(),
+                r#"{"type":"error","error":{"type":"overloaded_error"}}"#
+            ),
+            LlmErrorKind::Server
+        );
+        assert_eq!(
+            classify_status(StatusCode::UNAUTHORIZED, ""),
+            LlmErrorKind::Auth
+        );
+        assert_eq!(
+            classify_status(StatusCode::GATEWAY_TIMEOUT, ""),
+            LlmErrorKind::Timeout
+        );
+        assert_eq!(
+            classify_status(StatusCode::SERVICE_UNAVAILABLE, ""),
+            LlmErrorKind::Server
+        );
+        assert_eq!(
+            classify_status(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":"context length exceeded"}"#
+            ),
+            LlmErrorKind::InvalidRequest
+        );
+    }
+
+    #[test]
+    fn status_error_keeps_status_and_sanitized_message() {
+        let err = status_error(
+            "Gemini",
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            "RESOURCE_EXHAUSTED for key AIzaSecret",
+            &["AIzaSecret"],
+        );
+        assert_eq!(err.kind, LlmErrorKind::RateLimit);
+        assert_eq!(err.status, Some(429));
+        assert!(!err.message.contains("AIzaSecret"));
+    }
+
+    #[test]
+    fn gemini_schema_uppercases_types_and_drops_additional_properties() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "comments": {"type": "array", "items": {"type": "string"}},
+            },
+            "required": ["comments"],
+        });
+        assert_eq!(
+            gemini_schema(&schema),
+            serde_json::json!({
+                "type": "OBJECT",
+                "properties": {
+                    "comments": {"type": "ARRAY", "items": {"type": "STRING"}},
+                },
+                "required": ["comments"],
+            })
+        );
+    }
+
+    #[test]
+    fn anthropic_tool_input_is_the_structured_answer() {
+        let tool = anthropic_json_tool(None);
+        assert_eq!(tool["name"], ANTHROPIC_JSON_TOOL);
+        assert_eq!(tool["input_schema"]["type"], "object");
+
+        let content = vec![
+            serde_json::json!({"type": "text", "text": "Here is the review."}),
+            serde_json::json!({
+                "type": "tool_use",
+                "id": "toolu_1",
+                "name": ANTHROPIC_JSON_TOOL,
+                "input": {"comments": []},
+            }),
+        ];
+        assert_eq!(
+            anthropic_tool_input(&content).as_deref(),
+            Some(r#"{"comments":[]}"#)
+        );
+        assert_eq!(anthropic_tool_input(&content[..1]), None);
+    }
+
+    #[tokio::test]
+    async fn json_format_is_requested_only_when_asked() {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                tx.send(serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+                    .unwrap();
+                let reply = r#"{"message":{"content":"{}"}}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{reply}",
+                    reply.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let client = LlmClient::new(&LlmConfig {
+            provider: "ollama".into(),
+            base_url: Some(format!("http://{addr}")),
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        let messages = vec![ChatMessage {
+            role: Role::User,
+            content: "review this".into(),
+        }];
+
+        client
+            .chat_with_format(messages.clone(), &ResponseFormat::Json(None))
+            .await
+            .unwrap();
+        assert_eq!(rx.recv().unwrap()["format"], "json");
+
+        client.chat(messages).await.unwrap();
+        assert!(rx.recv().unwrap().get("format").is_none());
+    }
+
+    #[tokio::test]
+    async fn cached_response_is_returned_without_calling_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        // No API key: an uncached request would fail
+        let client = LlmClient::new(&LlmConfig {
+            api_key: None,
+            provider: "anthropic".into(),
+            ..LlmConfig::default()
+        })
+        .unwrap()
+        .with_response_cache(dir.path());
+        let messages = vec![ChatMessage {
+            role: Role::User,
+            content: "review this".into(),
+        }];
+
+        let key = client.cache_key(&messages, &ResponseFormat::Text);
+        std::fs::write(dir.path().join(format!("{key}.txt")), "{\"comments\": []}").unwrap();
+        let response = client.chat(messages.clone()).await.unwrap();
+        assert_eq!(response, "{\"comments\": []}");
+
+        // Different content misses the cache
+        let other = vec![ChatMessage {
+            role: Role::User,
+            content: "review that".into(),
+        }];
+        assert_ne!(client.cache_key(&other, &ResponseFormat::Text), key);
+        // So do other response formats and sampling settings
+        assert_ne!(
+            client.cache_key(&messages, &ResponseFormat::Json(None)),
+            key
+        );
+        let seeded = LlmClient::new(&LlmConfig {
+            provider: "anthropic".into(),
+            seed: Some(7),
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        assert_ne!(seeded.cache_key(&messages, &ResponseFormat::Text), key);
+        let warmer = LlmClient::new(&LlmConfig {
+            provider: "anthropic".into(),
+            temperature: 0.7,
+            ..LlmConfig::default()
+        })
+        .unwrap();
+        assert_ne!(warmer.cache_key(&messages, &ResponseFormat::Text), key);
+    }
+
+    #[tokio::test]
+    async fn requests_reuse_one_pooled_connection() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = Arc::clone(&connections);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                accepted.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    loop {
+                        let mut content_length = 0;
+                        let mut line = String::new();
+                        loop {
+                            line.clear();
+                            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                                return;
+                            }
+                            if line == "\r\n" {
+                                break;
+                            }
+                            if let Some(value) =
+                                line.to_ascii_lowercase().strip_prefix("content-length:")
+                            {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                        let mut body = vec![0; content_length];
+                        reader.read_exact(&mut body).unwrap();
+                        let reply = r#"{"message":{"content":"ok"}}"#;
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{reply}",
+                            reply.len()
+                        );
+                        stream.write_all(response.as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = LlmClient::new(&LlmConfig {
+            provider: "ollama".into(),
+            base_url: Some(format!("http://{addr}")),
+            ..LlmConfig::default()
+        })
+        .unwrap()
+        .with_network(&NetworkConfig::default())
+        .unwrap();
+        let messages = vec![ChatMessage {
+            role: Role::User,
+            content: "review this".into(),
+        }];
+
+        for _ in 0..3 {
+            assert_eq!(client.chat(messages.clone()).await.unwrap(), "ok");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
 }
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use argus_core::{ArgusError, ReviewComment};
+use argus_core::{ArgusError, DiffHunk, ReviewComment};
+use argus_difflens::parser::{normalize_path, FileDiff};
 
 /// A successfully applied patch.
 pub struct AppliedPatch {
@@ -20,7 +21,41 @@ pub struct SkippedPatch {
     /// Line number targeted by the patch.
     pub line: usize,
     /// Why the patch was skipped.
-    pub reason: String,
+    pub reason: PatchSkipReason,
+}
+
+/// Why a patch could not be applied.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::patch::PatchSkipReason;
+///
+/// let reason = PatchSkipReason::LineOutOfRange { line: 12, file_lines: 10 };
+/// assert_eq!(reason.to_string(), "line 12 out of range (file has 10 lines)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchSkipReason {
+    /// The target file could not be read; holds the IO error.
+    Unreadable(String),
+    /// The target line is 0 or past the end of the file.
+    LineOutOfRange {
+        /// The line the patch targeted.
+        line: usize,
+        /// Number of lines in the file.
+        file_lines: usize,
+    },
+}
+
+impl std::fmt::Display for PatchSkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchSkipReason::Unreadable(e) => write!(f, "cannot read file: {e}"),
+            PatchSkipReason::LineOutOfRange { line, file_lines } => {
+                write!(f, "line {line} out of range (file has {file_lines} lines)")
+            }
+        }
+    }
 }
 
 /// Outcome of applying patches from review comments.
@@ -34,6 +69,8 @@ pub struct PatchResult {
 /// Apply patches from review comments to the working tree.
 /// Only applies comments that have a non-empty `patch` field.
 /// Uses simple line-based replacement: reads the file, finds the target line range, replaces with patch content.
+/// Comment paths are resolved the way the diff parser writes them, so `b/src/x.rs`, an absolute path
+/// under `repo_root`, and `src/x.rs` all patch the same file.
 ///
 /// # Examples
 ///
@@ -62,7 +99,9 @@ pub fn apply_patches(
         if patch.is_empty() {
             continue;
         }
-        let key = comment.file_path.to_string_lossy().to_string();
+        let key = normalize_path(&comment.file_path, Some(repo_root))
+            .to_string_lossy()
+            .to_string();
         patches_by_file.entry(key).or_default().push(comment);
     }
 
@@ -77,7 +116,7 @@ pub fn apply_patches(
                     skipped.push(SkippedPatch {
                         file_path: file_path_str.clone(),
                         line: comment.line as usize,
-                        reason: format!("cannot read file: {e}"),
+                        reason: PatchSkipReason::Unreadable(e.to_string()),
                     });
                 }
                 continue;
@@ -97,11 +136,10 @@ pub fn apply_patches(
                 skipped.push(SkippedPatch {
                     file_path: file_path_str.clone(),
                     line: target_line,
-                    reason: format!(
-                        "line {} out of range (file has {} lines)",
-                        target_line,
-                        lines.len()
-                    ),
+                    reason: PatchSkipReason::LineOutOfRange {
+                        line: target_line,
+                        file_lines: lines.len(),
+                    },
                 });
                 continue;
             }
@@ -143,6 +181,39 @@ pub fn apply_patches(
     Ok(PatchResult { applied, skipped })
 }
 
+/// Find the hunk in `diffs` whose new-file range contains `comment`'s line.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_review::patch::hunk_for;
+///
+/// let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -5,1 +5,2 @@\n let x = 1;\n+let y = 2;\n";
+/// let diffs = parse_unified_diff(diff).unwrap();
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("a.rs"),
+///     line: 6,
+///     severity: Severity::Warning,
+///     message: "unused".into(),
+///     confidence: 90.0,
+///     suggestion: None,
+///     ..Default::default()
+/// };
+/// assert_eq!(hunk_for(&diffs, &comment).unwrap().new_start, 5);
+/// ```
+pub fn hunk_for<'a>(diffs: &'a [FileDiff], comment: &ReviewComment) -> Option<&'a DiffHunk> {
+    diffs
+        .iter()
+        .filter(|diff| diff.new_path == comment.file_path)
+        .flat_map(|diff| &diff.hunks)
+        .find(|hunk| {
+            comment.line >= hunk.new_start && comment.line < hunk.new_start + hunk.new_lines.max(1)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,7 +234,7 @@ mod tests {
             confidence: 90.0,
             suggestion: None,
             patch: patch.map(String::from),
-            rule: None,
+            ..Default::default()
         }
     }
 
@@ -189,6 +260,29 @@ mod tests {
         assert!(!content.contains("\"old\""));
     }
 
+    #[test]
+    fn test_dotted_absolute_and_relative_paths_patch_one_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        let file_path = dir.path().join("src/x.rs");
+        std::fs::write(&file_path, "one\ntwo\nthree\n").unwrap();
+
+        let absolute = file_path.to_string_lossy().to_string();
+        let comments = vec![
+            make_comment("./src/x.rs", 1, Some("ONE"), "dotted"),
+            make_comment(&absolute, 2, Some("TWO"), "absolute"),
+            make_comment("src/x.rs", 3, Some("THREE"), "relative"),
+        ];
+
+        let result = apply_patches(&comments, dir.path()).unwrap();
+        assert_eq!(result.applied.len(), 3);
+        assert!(result.applied.iter().all(|p| p.file_path == "src/x.rs"));
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "ONE\nTWO\nTHREE\n"
+        );
+    }
+
     #[test]
     fn test_skip_no_patch() {
         let dir = tempfile::tempdir().unwrap();
@@ -216,7 +310,28 @@ mod tests {
         let result = apply_patches(&comments, dir.path()).unwrap();
         assert_eq!(result.applied.len(), 0);
         assert_eq!(result.skipped.len(), 1);
-        assert!(result.skipped[0].reason.contains("cannot read file"));
+        assert!(matches!(
+            result.skipped[0].reason,
+            PatchSkipReason::Unreadable(_)
+        ));
+    }
+
+    #[test]
+    fn test_skip_line_out_of_range() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("short.rs"), "line1\nline2\n").unwrap();
+
+        let comments = vec![make_comment("short.rs", 5, Some("patched"), "past the end")];
+
+        let result = apply_patches(&comments, dir.path()).unwrap();
+        assert!(result.applied.is_empty());
+        assert_eq!(
+            result.skipped[0].reason,
+            PatchSkipReason::LineOutOfRange {
+                line: 5,
+                file_lines: 2
+            }
+        );
     }
 
     #[test]
@@ -0,0 +1,212 @@
+//! Deterministic enforcement of custom rules with a `pattern`.
+//!
+//! Rules in the LLM prompt are followed most of the time; mechanical rules
+//! such as "no `dbg!`" are better checked exactly. A rule with a `pattern`
+//! is a regex matched against every added line, optionally limited to files
+//! matching its `paths` globs, and each match becomes a finding tagged with
+//! the rule, without involving the LLM.
+
+use argus_core::{ArgusError, ReviewComment, Rule, Severity};
+use argus_difflens::parser::FileDiff;
+use regex::Regex;
+
+/// A custom rule compiled for matching added lines.
+#[derive(Debug, Clone)]
+pub struct PatternRule {
+    name: String,
+    severity: Severity,
+    description: String,
+    regex: Regex,
+    paths: Vec<glob::Pattern>,
+}
+
+impl PatternRule {
+    /// Compile `rule`, or return `None` for a rule without a pattern, which
+    /// stays LLM-enforced.
+    ///
+    /// An unknown severity falls back to `warning`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Config`] if the pattern is not a valid regex or
+    /// a path is not a valid glob.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::Rule;
+    /// use argus_review::patterns::PatternRule;
+    ///
+    /// let mut rule = Rule {
+    ///     name: "no-dbg".into(),
+    ///     severity: "warning".into(),
+    ///     description: "Remove dbg! before merging".into(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(PatternRule::compile(&rule).unwrap().is_none());
+    ///
+    /// rule.pattern = Some(r"\bdbg!\(".into());
+    /// assert!(PatternRule::compile(&rule).unwrap().is_some());
+    ///
+    /// rule.pattern = Some("(".into());
+    /// assert!(PatternRule::compile(&rule).is_err());
+    /// ```
+    pub fn compile(rule: &Rule) -> Result<Option<Self>, ArgusError> {
+        let Some(pattern) = &rule.pattern else {
+            return Ok(None);
+        };
+        let regex = Regex::new(pattern).map_err(|e| {
+            ArgusError::Config(format!("rule '{}' has an invalid pattern: {e}", rule.name))
+        })?;
+        let paths = rule
+            .paths
+            .iter()
+            .flatten()
+            .map(|path| {
+                glob::Pattern::new(path).map_err(|e| {
+                    ArgusError::Config(format!(
+                        "rule '{}' has an invalid path glob '{path}': {e}",
+                        rule.name
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Some(Self {
+            name: rule.name.clone(),
+            severity: rule.severity.parse().unwrap_or(Severity::Warning),
+            description: rule.description.clone(),
+            regex,
+            paths,
+        }))
+    }
+
+    /// Whether the rule checks `diff`: any file when it has no `paths`.
+    fn applies_to(&self, diff: &FileDiff) -> bool {
+        self.paths.is_empty() || self.paths.iter().any(|p| p.matches_path(&diff.new_path))
+    }
+}
+
+/// Report every added line matching a rule's pattern, in files within the
+/// rule's paths.
+///
+/// Each line produces one comment per matching rule, tagged with the rule
+/// name, at full confidence and the rule's severity. The message is the
+/// rule's description (or the pattern, if the description is empty)
+/// followed by the offending line.
+///
+/// # Examples
+///
+/// ```
+/// use argus_core::Rule;
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_review::patterns::{find_pattern_violations, PatternRule};
+///
+/// let rule = Rule {
+///     name: "no-console-log".into(),
+///     severity: "warning".into(),
+///     description: "Remove console.log debugging".into(),
+///     pattern: Some(r"console\.log\(".into()),
+///     paths: Some(vec!["src/**".into()]),
+/// };
+/// let rules = vec![PatternRule::compile(&rule).unwrap().unwrap()];
+/// let diff = "diff --git a/src/app.ts b/src/app.ts\n\
+///             --- a/src/app.ts\n\
+///             +++ b/src/app.ts\n\
+///             @@ -1,0 +1,1 @@\n\
+///             +console.log(user);\n";
+/// let comments = find_pattern_violations(&parse_unified_diff(diff).unwrap(), &rules);
+/// assert_eq!(comments.len(), 1);
+/// assert_eq!(comments[0].rule.as_deref(), Some("no-console-log"));
+/// ```
+pub fn find_pattern_violations(diffs: &[FileDiff], rules: &[PatternRule]) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+    for diff in diffs.iter().filter(|d| !d.is_deleted_file) {
+        let applicable: Vec<&PatternRule> = rules.iter().filter(|r| r.applies_to(diff)).collect();
+        if applicable.is_empty() {
+            continue;
+        }
+        for hunk in &diff.hunks {
+            let mut line_no = hunk.new_start;
+            for line in hunk.content.lines() {
+                if line.starts_with('-') {
+                    continue;
+                }
+                if let Some(added) = line.strip_prefix('+') {
+                    for rule in applicable.iter().filter(|r| r.regex.is_match(added)) {
+                        let what = if rule.description.is_empty() {
+                            format!("Matches `{}`", rule.regex.as_str())
+                        } else {
+                            rule.description.clone()
+                        };
+                        comments.push(ReviewComment {
+                            file_path: diff.new_path.clone(),
+                            line: line_no,
+                            severity: rule.severity,
+                            message: format!("{what}: {}", added.trim()),
+                            confidence: 100.0,
+                            suggestion: None,
+                            rule: Some(rule.name.clone()),
+                            ..Default::default()
+                        });
+                    }
+                }
+                line_no += 1;
+            }
+        }
+    }
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argus_difflens::parser::parse_unified_diff;
+
+    fn rule(pattern: &str, paths: Option<Vec<String>>) -> PatternRule {
+        PatternRule::compile(&Rule {
+            name: "no-dbg".into(),
+            severity: "bug".into(),
+            description: "Remove dbg! before merging".into(),
+            pattern: Some(pattern.into()),
+            paths,
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn regex_rule_fires_on_added_lines_within_its_paths() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,3 @@
+ fn run() {
+-    dbg!(old);
++    dbg!(config);
+ }
+diff --git a/tests/it.rs b/tests/it.rs
+--- a/tests/it.rs
++++ b/tests/it.rs
+@@ -1,0 +1,1 @@
++dbg!(fixture);
+";
+        let diffs = parse_unified_diff(diff).unwrap();
+        let scoped = rule(r"\bdbg!\(", Some(vec!["src/**/*.rs".into()]));
+
+        let comments = find_pattern_violations(&diffs, &[scoped]);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].file_path, std::path::Path::new("src/lib.rs"));
+        assert_eq!(comments[0].line, 11);
+        assert_eq!(comments[0].severity, Severity::Bug);
+        assert_eq!(comments[0].rule.as_deref(), Some("no-dbg"));
+        assert_eq!(
+            comments[0].message,
+            "Remove dbg! before merging: dbg!(config);"
+        );
+
+        let everywhere = find_pattern_violations(&diffs, &[rule(r"\bdbg!\(", None)]);
+        assert_eq!(everywhere.len(), 2);
+        assert!(find_pattern_violations(&diffs, &[rule(r"\bprintln!", None)]).is_empty());
+    }
+}
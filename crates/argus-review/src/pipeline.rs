@@ -1,24 +1,36 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
-use std::io::IsTerminal;
 use std::path::Path;
-
-use argus_core::{ArgusError, OutputFormat, ReviewComment, ReviewConfig, Rule, Severity};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+use argus_core::{
+    ArgusError, LlmErrorKind, ReflectionScore, ReviewComment, ReviewConfig, Rule, Severity,
+    SeverityOverride,
+};
 use serde::Serialize;
 
+use argus_difflens::conflicts::find_conflict_markers;
 use argus_difflens::filter::{DiffFilter, SkippedFile};
-use argus_difflens::parser::FileDiff;
-
-use crate::llm::{ChatMessage, LlmClient, Role};
+use argus_difflens::lockfiles::find_new_dependencies;
+use argus_difflens::moves::{detect_moves, drop_moved_hunks, MovedBlock};
+use argus_difflens::parser::{normalize_path, FileDiff};
+use argus_repomap::walker::Language;
+
+use crate::compare::{AddedLines, Origin};
+use crate::llm::{ChatMessage, ChatResponse, LlmClient, ResponseFormat, Role};
+use crate::patterns::{find_pattern_violations, PatternRule};
+use crate::progress::{NoopReporter, Reporter};
 use crate::prompt;
+use crate::suppress::{attach_fingerprints, Allowlist};
 
 /// Result of a completed code review.
 ///
 /// # Examples
 ///
 /// ```
-/// use argus_review::pipeline::{ReviewResult, ReviewStats};
+/// use argus_review::pipeline::{ReviewResult, ReviewStats, ReviewTimings};
 ///
 /// let result = ReviewResult {
 ///     comments: vec![],
@@ -38,6 +50,8 @@ use crate::prompt;
 ///         llm_retries: 0,
 ///         file_groups: vec![],
 ///         hotspot_files: 0,
+///         moved_blocks: vec![],
+///         timings: ReviewTimings::default(),
 ///     },
 /// };
 /// assert!(result.comments.is_empty());
@@ -47,8 +61,8 @@ use crate::prompt;
 pub struct ReviewResult {
     /// Filtered and sorted review comments.
     pub comments: Vec<ReviewComment>,
-    /// Comments that were removed by filtering, with reasons.
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// Comments that were removed by filtering, with reasons. Always
+    /// serialized, even when empty, so tooling can rely on the key.
     pub filtered_comments: Vec<FilteredComment>,
     /// High-level summary of the review findings.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -64,7 +78,7 @@ pub struct ReviewResult {
 /// ```
 /// use std::path::PathBuf;
 /// use argus_core::{ReviewComment, Severity};
-/// use argus_review::pipeline::FilteredComment;
+/// use argus_review::pipeline::{FilterReason, FilteredComment};
 ///
 /// let fc = FilteredComment {
 ///     comment: ReviewComment {
@@ -74,12 +88,11 @@ pub struct ReviewResult {
 ///         message: "minor note".into(),
 ///         confidence: 95.0,
 ///         suggestion: None,
-///         patch: None,
-///         rule: None,
+///         ..Default::default()
 ///     },
-///     reason: "below confidence threshold".into(),
+///     reason: FilterReason::BelowConfidence,
 /// };
-/// assert!(fc.reason.contains("confidence"));
+/// assert_eq!(fc.reason.to_string(), "below confidence threshold");
 /// ```
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -87,7 +100,89 @@ pub struct FilteredComment {
     /// The original comment that was filtered out.
     pub comment: ReviewComment,
     /// Why this comment was filtered.
-    pub reason: String,
+    pub reason: FilterReason,
+}
+
+impl FilteredComment {
+    /// The human wording of [`FilteredComment::reason`], naming the
+    /// severity when it was excluded by `severity_filter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::{ReviewComment, Severity};
+    /// use argus_review::pipeline::{FilterReason, FilteredComment};
+    ///
+    /// let fc = FilteredComment {
+    ///     comment: ReviewComment {
+    ///         severity: Severity::Suggestion,
+    ///         ..Default::default()
+    ///     },
+    ///     reason: FilterReason::SeverityExcluded,
+    /// };
+    /// assert_eq!(fc.reason_text(), "severity suggestion excluded");
+    /// ```
+    pub fn reason_text(&self) -> String {
+        match self.reason {
+            FilterReason::SeverityExcluded => {
+                format!("severity {} excluded", self.comment.severity)
+            }
+            reason => reason.to_string(),
+        }
+    }
+}
+
+/// Why a comment was left out of the review, as a stable machine key.
+///
+/// Serializes as a snake_case string (`"below_confidence"`, ...) for JSON
+/// consumers; `Display` gives the human wording, which
+/// [`FilteredComment::reason_text`] completes for `--show-filtered`.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::pipeline::FilterReason;
+///
+/// let json = serde_json::to_string(&FilterReason::MaxComments).unwrap();
+/// assert_eq!(json, "\"max_comments\"");
+/// assert_eq!(FilterReason::MaxComments.to_string(), "exceeded max comment limit");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterReason {
+    /// Confidence was below `min_confidence`.
+    BelowConfidence,
+    /// Severity is not in `severity_filter`.
+    SeverityExcluded,
+    /// Cut by `max_comments` after sorting.
+    MaxComments,
+    /// Cut by `max_comments_per_file` after sorting.
+    MaxCommentsPerFile,
+    /// Scored below the threshold by the self-reflection pass.
+    SelfReflection,
+    /// On a line unchanged from the base branch (`review --compare`).
+    Preexisting,
+    /// Fingerprint listed in `.argus/ignore-comments`.
+    Suppressed,
+    /// On a line the diff didn't add (`comments_on_changed_only`).
+    UnchangedLine,
+}
+
+impl std::fmt::Display for FilterReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterReason::BelowConfidence => write!(f, "below confidence threshold"),
+            FilterReason::SeverityExcluded => write!(f, "severity excluded"),
+            FilterReason::MaxComments => write!(f, "exceeded max comment limit"),
+            FilterReason::MaxCommentsPerFile => write!(f, "exceeded per-file limit"),
+            FilterReason::SelfReflection => write!(f, "rejected by self-reflection"),
+            FilterReason::Preexisting => {
+                write!(f, "pre-existing: line is unchanged from the base branch")
+            }
+            FilterReason::Suppressed => write!(f, "suppressed by allowlist"),
+            FilterReason::UnchangedLine => write!(f, "comment on unchanged line"),
+        }
+    }
 }
 
 /// Statistics about a review run.
@@ -95,7 +190,7 @@ pub struct FilteredComment {
 /// # Examples
 ///
 /// ```
-/// use argus_review::pipeline::ReviewStats;
+/// use argus_review::pipeline::{ReviewStats, ReviewTimings};
 ///
 /// let stats = ReviewStats {
 ///     files_reviewed: 3,
@@ -111,10 +206,12 @@ pub struct FilteredComment {
 ///     llm_retries: 0,
 ///     file_groups: vec![],
 ///     hotspot_files: 0,
+///     moved_blocks: vec![],
+///     timings: ReviewTimings::default(),
 /// };
 /// assert_eq!(stats.files_reviewed, 3);
 /// ```
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReviewStats {
     /// Number of files that were reviewed.
@@ -132,7 +229,6 @@ pub struct ReviewStats {
     /// Comments removed by self-reflection pass.
     pub comments_reflected_out: usize,
     /// Files that were skipped with reasons.
-    #[serde(skip)]
     pub skipped_files: Vec<SkippedFile>,
     /// Model identifier used for the review.
     pub model_used: String,
@@ -145,6 +241,305 @@ pub struct ReviewStats {
     pub file_groups: Vec<Vec<String>>,
     /// Number of files identified as hotspots (score ≥ 0.7).
     pub hotspot_files: usize,
+    /// Code moved unchanged between or within files. Hunks holding only
+    /// moved code are left out of the review.
+    pub moved_blocks: Vec<MovedBlock>,
+    /// Wall-clock time spent in each pipeline phase.
+    pub timings: ReviewTimings,
+}
+
+/// Wall-clock time, in milliseconds, spent in each phase of a review.
+///
+/// Phases that did not run (no `--repo`, no search index, self-reflection
+/// or summary disabled) stay at zero.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::pipeline::ReviewTimings;
+///
+/// let timings = ReviewTimings {
+///     repo_map_ms: 120,
+///     llm_ms: 4_000,
+///     llm_group_ms: vec![1_500, 2_500],
+///     ..ReviewTimings::default()
+/// };
+/// assert_eq!(timings.total_ms(), 4_120);
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewTimings {
+    /// Repo map and blast-radius generation.
+    pub repo_map_ms: u64,
+    /// Git history mining for hotspots and coupling.
+    pub history_ms: u64,
+    /// Semantic search for related code in the index.
+    pub related_code_ms: u64,
+    /// All review LLM calls, including rate-limit retries.
+    pub llm_ms: u64,
+    /// Time of each review LLM call, in group order.
+    pub llm_group_ms: Vec<u64>,
+    /// The self-reflection LLM call.
+    pub self_reflection_ms: u64,
+    /// The summary LLM call.
+    pub summary_ms: u64,
+}
+
+impl ReviewTimings {
+    /// Sum of the phases, not counting the per-group breakdown twice.
+    pub fn total_ms(&self) -> u64 {
+        self.repo_map_ms
+            + self.history_ms
+            + self.related_code_ms
+            + self.llm_ms
+            + self.self_reflection_ms
+            + self.summary_ms
+    }
+
+    fn add(&mut self, other: ReviewTimings) {
+        self.repo_map_ms += other.repo_map_ms;
+        self.history_ms += other.history_ms;
+        self.related_code_ms += other.related_code_ms;
+        self.llm_ms += other.llm_ms;
+        self.llm_group_ms.extend(other.llm_group_ms);
+        self.self_reflection_ms += other.self_reflection_ms;
+        self.summary_ms += other.summary_ms;
+    }
+}
+
+/// Milliseconds elapsed since `start`.
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+/// Prompts and context prepared for a review, before any LLM call.
+struct PreparedReview {
+    kept_diffs: Vec<FileDiff>,
+    skipped_files: Vec<SkippedFile>,
+    moved_blocks: Vec<MovedBlock>,
+    /// Fingerprinted conflict-marker and new-dependency findings from every
+    /// file in the diff, including the skipped ones.
+    deterministic: Vec<ReviewComment>,
+    total_hunks: usize,
+    hotspot_file_count: usize,
+    diff_text: String,
+    system: String,
+    /// Time spent gathering context; the LLM phases are filled in later.
+    timings: ReviewTimings,
+    /// One entry per review LLM call.
+    requests: Vec<PlannedRequest>,
+    /// Whether the diff was split into groups.
+    split: bool,
+}
+
+/// A single review LLM call: the files it covers and its user prompt.
+struct PlannedRequest {
+    label: String,
+    files: Vec<String>,
+    user: String,
+}
+
+/// [`ReviewStats::model_used`] for a review made by
+/// [`ReviewPipeline::review_offline`].
+pub const OFFLINE_MODEL: &str = "offline";
+
+/// Rough token allowance for the comment list in reflection/summary prompts.
+const PLANNED_COMMENTS_TOKENS: usize = 500;
+/// Rough output tokens assumed per LLM call when estimating cost.
+const PLANNED_OUTPUT_TOKENS_PER_CALL: usize = 1000;
+/// Changed symbols listed in the blast radius prompt section.
+const BLAST_RADIUS_MAX_SYMBOLS: usize = 10;
+/// Dependents named per changed symbol in the blast radius section.
+const BLAST_RADIUS_MAX_NAMES: usize = 5;
+/// Untested functions listed in the test presence prompt section.
+const TEST_PRESENCE_MAX_FUNCTIONS: usize = 20;
+
+/// Estimated LLM usage for a review, produced by [`ReviewPipeline::plan`].
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::pipeline::{PlannedGroup, ReviewPlan};
+///
+/// let plan = ReviewPlan {
+///     model: "gpt-4o".into(),
+///     files_reviewed: 2,
+///     files_skipped: 0,
+///     groups: vec![PlannedGroup {
+///         files: vec!["src/a.rs".into(), "src/b.rs".into()],
+///         estimated_tokens: 1200,
+///     }],
+///     review_calls: 1,
+///     estimated_llm_calls: 3,
+///     estimated_input_tokens: 2400,
+///     estimated_output_tokens: 3000,
+///     estimated_cost_usd: Some(0.036),
+/// };
+/// assert!(plan.to_string().contains("Estimated LLM calls: 3"));
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewPlan {
+    /// Model the review would use.
+    pub model: String,
+    /// Files that would be sent to the LLM.
+    pub files_reviewed: usize,
+    /// Files removed by pre-filtering.
+    pub files_skipped: usize,
+    /// One entry per review LLM call.
+    pub groups: Vec<PlannedGroup>,
+    /// Number of review (non-reflection, non-summary) LLM calls.
+    pub review_calls: usize,
+    /// Total LLM calls, including self-reflection and summary.
+    pub estimated_llm_calls: usize,
+    /// Estimated input tokens across all calls.
+    pub estimated_input_tokens: usize,
+    /// Estimated output tokens across all calls.
+    pub estimated_output_tokens: usize,
+    /// Estimated cost in USD, when pricing for the model is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_usd: Option<f64>,
+}
+
+/// A planned review LLM call.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedGroup {
+    /// Files included in this call.
+    pub files: Vec<String>,
+    /// Estimated input tokens (system + user prompt).
+    pub estimated_tokens: usize,
+}
+
+impl fmt::Display for ReviewPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Review plan (dry run, no API calls made)")?;
+        writeln!(f, "Model: {}", self.model)?;
+        writeln!(
+            f,
+            "Files: {} to review, {} skipped",
+            self.files_reviewed, self.files_skipped
+        )?;
+        writeln!(f, "Groups: {}", self.groups.len())?;
+        for (i, group) in self.groups.iter().enumerate() {
+            let label = if group.files.len() == 1 {
+                "file"
+            } else {
+                "files"
+            };
+            writeln!(
+                f,
+                "  Group {} ({} {label}, ~{} tokens): {}",
+                i + 1,
+                group.files.len(),
+                group.estimated_tokens,
+                group.files.join(", "),
+            )?;
+        }
+        writeln!(
+            f,
+            "Estimated LLM calls: {} ({} review + reflection/summary)",
+            self.estimated_llm_calls, self.review_calls
+        )?;
+        writeln!(
+            f,
+            "Estimated tokens: ~{} input, ~{} output",
+            self.estimated_input_tokens, self.estimated_output_tokens
+        )?;
+        match self.estimated_cost_usd {
+            Some(cost) => writeln!(f, "Estimated cost: ~${cost:.4}"),
+            None => writeln!(f, "Estimated cost: unknown (no pricing for {})", self.model),
+        }
+    }
+}
+
+impl ReviewPlan {
+    /// Combine the plans of several reviews, such as one per config scope,
+    /// into one.
+    ///
+    /// Counts are summed and groups concatenated. Distinct models are
+    /// joined with commas, and the cost is only known if it is known for
+    /// every plan.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_review::pipeline::ReviewPlan;
+    ///
+    /// let plan = |model: &str, cost| ReviewPlan {
+    ///     model: model.into(),
+    ///     files_reviewed: 1,
+    ///     files_skipped: 0,
+    ///     groups: vec![],
+    ///     review_calls: 1,
+    ///     estimated_llm_calls: 2,
+    ///     estimated_input_tokens: 1000,
+    ///     estimated_output_tokens: 2000,
+    ///     estimated_cost_usd: cost,
+    /// };
+    /// let merged = ReviewPlan::merge(vec![plan("gpt-4o", Some(0.01)), plan("llama3", None)]);
+    /// assert_eq!(merged.model, "gpt-4o, llama3");
+    /// assert_eq!(merged.estimated_llm_calls, 4);
+    /// assert_eq!(merged.estimated_cost_usd, None);
+    /// ```
+    pub fn merge(plans: Vec<ReviewPlan>) -> ReviewPlan {
+        let mut models: Vec<String> = Vec::new();
+        let mut merged = ReviewPlan {
+            model: String::new(),
+            files_reviewed: 0,
+            files_skipped: 0,
+            groups: Vec::new(),
+            review_calls: 0,
+            estimated_llm_calls: 0,
+            estimated_input_tokens: 0,
+            estimated_output_tokens: 0,
+            estimated_cost_usd: Some(0.0),
+        };
+        for plan in plans {
+            if !models.contains(&plan.model) {
+                models.push(plan.model);
+            }
+            merged.files_reviewed += plan.files_reviewed;
+            merged.files_skipped += plan.files_skipped;
+            merged.groups.extend(plan.groups);
+            merged.review_calls += plan.review_calls;
+            merged.estimated_llm_calls += plan.estimated_llm_calls;
+            merged.estimated_input_tokens += plan.estimated_input_tokens;
+            merged.estimated_output_tokens += plan.estimated_output_tokens;
+            merged.estimated_cost_usd = merged
+                .estimated_cost_usd
+                .zip(plan.estimated_cost_usd)
+                .map(|(total, cost)| total + cost);
+        }
+        merged.model = models.join(", ");
+        merged
+    }
+}
+
+/// Estimate the USD cost of a run from published per-million-token prices.
+///
+/// Returns `None` for models without a known price. Local Ollama models are free.
+fn estimate_cost_usd(
+    provider: &str,
+    model: &str,
+    input_tokens: usize,
+    output_tokens: usize,
+) -> Option<f64> {
+    if provider == "ollama" {
+        return Some(0.0);
+    }
+    // (model prefix, input $/1M, output $/1M); more specific prefixes first
+    const PRICES: &[(&str, f64, f64)] = &[
+        ("gpt-4o-mini", 0.15, 0.60),
+        ("gpt-4o", 2.50, 10.00),
+        ("gpt-4.1-mini", 0.40, 1.60),
+        ("gpt-4.1", 2.00, 8.00),
+        ("claude-sonnet", 3.00, 15.00),
+        ("claude-3-5-haiku", 0.80, 4.00),
+        ("gemini-2.0-flash", 0.10, 0.40),
+    ];
+    let (_, input_price, output_price) = PRICES.iter().find(|(p, _, _)| model.starts_with(p))?;
+    Some((input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0)
 }
 
 /// Review orchestrator that drives the full review pipeline.
@@ -153,14 +548,143 @@ pub struct ReviewStats {
 /// and applies confidence/severity filtering.
 pub struct ReviewPipeline {
     llm: LlmClient,
+    fallbacks: Vec<LlmClient>,
+    /// Index of the client currently serving requests (0 = primary).
+    active_client: AtomicUsize,
     config: ReviewConfig,
     rules: Vec<Rule>,
+    reporter: Arc<dyn Reporter>,
+    blame: bool,
+    suppressions: Allowlist,
+    ignored_references: Vec<String>,
+    leaf_pruning_threshold: usize,
+    focus_hops: usize,
+    max_file_bytes: u64,
+    fts_stop_words: Vec<String>,
+    instructions: Vec<String>,
+    /// `rules` with a `pattern`, compiled on first use
+    pattern_rules: OnceLock<Vec<PatternRule>>,
 }
 
 impl ReviewPipeline {
     /// Create a new pipeline from an LLM client, review config, and custom rules.
     pub fn new(llm: LlmClient, config: ReviewConfig, rules: Vec<Rule>) -> Self {
-        Self { llm, config, rules }
+        Self {
+            llm,
+            fallbacks: Vec::new(),
+            active_client: AtomicUsize::new(0),
+            config,
+            rules,
+            reporter: Arc::new(NoopReporter),
+            blame: false,
+            suppressions: Allowlist::default(),
+            ignored_references: argus_core::MapConfig::default().ignored_references,
+            leaf_pruning_threshold: argus_core::MapConfig::default().leaf_pruning_threshold,
+            focus_hops: argus_core::MapConfig::default().focus_hops,
+            max_file_bytes: argus_repomap::walker::DEFAULT_MAX_FILE_BYTES,
+            fts_stop_words: Vec::new(),
+            instructions: Vec::new(),
+            pattern_rules: OnceLock::new(),
+        }
+    }
+
+    /// Attach the last author of each commented line, via `git blame`.
+    ///
+    /// Only applies when [`review`](Self::review) gets a repository path.
+    /// Off by default because it exposes author identities in the output.
+    pub fn with_blame(mut self) -> Self {
+        self.blame = true;
+        self
+    }
+
+    /// Filter out findings whose fingerprint is on `allowlist`, as
+    /// [`FilterReason::Suppressed`].
+    pub fn with_suppressions(mut self, allowlist: Allowlist) -> Self {
+        self.suppressions = allowlist;
+        self
+    }
+
+    /// Set the names whose references are left out of the repo map's symbol
+    /// graph (`[map] ignored_references`).
+    pub fn with_ignored_references(mut self, names: Vec<String>) -> Self {
+        self.ignored_references = names;
+        self
+    }
+
+    /// Set the symbol count above which the repo map's PageRank skips
+    /// unreferenced symbols (`[map] leaf_pruning_threshold`).
+    pub fn with_leaf_pruning_threshold(mut self, threshold: usize) -> Self {
+        self.leaf_pruning_threshold = threshold;
+        self
+    }
+
+    /// Set how many reference hops around the changed files the repo map
+    /// covers (`[map] focus_hops`).
+    pub fn with_focus_hops(mut self, hops: usize) -> Self {
+        self.focus_hops = hops;
+        self
+    }
+
+    /// Set the size above which the repo map skips a file (`[path]
+    /// max_file_bytes`).
+    pub fn with_max_file_bytes(mut self, max_file_bytes: u64) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Set the words left out of the search index queries that find related
+    /// code (`[embedding] fts_stop_words`).
+    pub fn with_fts_stop_words(mut self, stop_words: Vec<String>) -> Self {
+        self.fts_stop_words = stop_words;
+        self
+    }
+
+    /// Add free-form guidance for this review, such as "pay special
+    /// attention to the retry logic", to every review prompt after the diff
+    /// (see [`prompt::build_instructions_section`]).
+    pub fn with_instructions(mut self, instructions: Vec<String>) -> Self {
+        self.instructions = instructions;
+        self
+    }
+
+    /// Set the reporter that receives progress events and warnings.
+    ///
+    /// Defaults to [`NoopReporter`], so the pipeline writes nothing to
+    /// stdout/stderr unless a reporter such as
+    /// [`TerminalReporter`](crate::progress::TerminalReporter) is installed.
+    pub fn with_reporter(mut self, reporter: Arc<dyn Reporter>) -> Self {
+        self.reporter = reporter;
+        self
+    }
+
+    /// Set the clients to fall back to, in order, when the current one
+    /// exhausts its retries with a retriable error.
+    ///
+    /// Once a fallback serves a request, later requests in the same review
+    /// go to it directly.
+    pub fn with_fallbacks(mut self, fallbacks: Vec<LlmClient>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    fn client_at(&self, index: usize) -> &LlmClient {
+        if index == 0 {
+            &self.llm
+        } else {
+            &self.fallbacks[index - 1]
+        }
+    }
+
+    /// Model that served the review: the primary model name, or
+    /// `provider:model` when a fallback took over.
+    fn model_used(&self) -> String {
+        let index = self.active_client.load(Ordering::Relaxed);
+        let client = self.client_at(index);
+        if index == 0 {
+            client.model().to_string()
+        } else {
+            format!("{}:{}", client.provider_name(), client.model())
+        }
     }
 
     /// Run a review on parsed diffs and return filtered comments.
@@ -169,10 +693,16 @@ impl ReviewPipeline {
     /// file paths as focus files and included in the LLM prompt for context.
     ///
     /// The pipeline:
-    /// 1. Pre-filters diffs (lock files, generated, vendored, etc.)
+    /// 1. Makes absolute diff paths relative to `repo_path`, reports merge
+    ///    conflict markers in every file, pre-filters diffs (lock files,
+    ///    generated, vendored, etc.), and drops hunks that only move code
+    ///    unchanged (see [`ReviewStats::moved_blocks`])
     /// 2. Splits large diffs into per-file LLM calls if needed
-    /// 3. Deduplicates comments
+    /// 3. Makes comment paths match the diffs and deduplicates comments,
+    ///    merging issues repeated in a file and its test
     /// 4. Applies confidence/severity filtering
+    /// 5. Adds the deterministic findings, applies severity overrides to
+    ///    every comment, and sorts them all by severity, path, and line
     ///
     /// # Errors
     ///
@@ -182,20 +712,124 @@ impl ReviewPipeline {
         diffs: Vec<FileDiff>,
         repo_path: Option<&Path>,
     ) -> Result<ReviewResult, ArgusError> {
-        // 1. Pre-filter diffs
-        let diff_filter = DiffFilter::from_config(&self.config);
-        let filter_result = diff_filter.filter(diffs);
+        self.review_inner(diffs, repo_path, None).await
+    }
+
+    /// Like [`review`](Self::review), with the author's description of the
+    /// change, such as its commit messages or PR description, added to every
+    /// review prompt so the LLM can tell intended behavior from mistakes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Llm`] if the LLM call fails.
+    pub async fn review_described(
+        &self,
+        diffs: Vec<FileDiff>,
+        repo_path: Option<&Path>,
+        description: &str,
+    ) -> Result<ReviewResult, ArgusError> {
+        self.review_inner(diffs, repo_path, Some(description)).await
+    }
+
+    /// Run only the deterministic passes, without calling the LLM.
+    ///
+    /// Reports merge conflict markers in every file, and dependencies added
+    /// to lock files unless `flag_new_dependencies` is off, as
+    /// [`review`](Self::review) does. Then applies the same pre-filter and
+    /// reports TODO/FIXME markers and removed or changed public API
+    /// (whatever `flag_todos` and `flag_breaking_changes` say) and violations
+    /// of rules with a `pattern`, and applies severity overrides. The summary
+    /// is the diff's risk score. The result is reproducible and costs nothing:
+    /// `model_used` is [`OFFLINE_MODEL`] and `llm_calls` is zero.
+    pub fn review_offline(
+        &self,
+        mut diffs: Vec<FileDiff>,
+        repo_path: Option<&Path>,
+    ) -> ReviewResult {
+        normalize_diff_paths(&mut diffs, repo_path);
+        let mut comments = find_conflict_markers(&diffs);
+        if self.config.flag_new_dependencies {
+            comments.extend(find_new_dependencies(&diffs, Severity::Info));
+        }
+        attach_fingerprints(&mut comments, &diffs);
+        let filter_result = DiffFilter::from_config(&self.config).filter(diffs);
         let kept_diffs = filter_result.kept;
         let skipped_files = filter_result.skipped;
-        let files_skipped = skipped_files.len();
 
+        let mut found = argus_difflens::markers::find_marker_comments(
+            &kept_diffs,
+            &self.config.todo_markers,
+            self.config.todo_severity,
+        );
+        found.extend(argus_difflens::breaking::find_breaking_changes(&kept_diffs));
+        found.extend(self.pattern_violations(&kept_diffs));
+        attach_fingerprints(&mut found, &kept_diffs);
+        comments.extend(found);
+        let comments_generated = comments.len();
+        let (mut comments, filtered_comments) = split_suppressed(comments, &self.suppressions);
+        apply_severity_overrides(&mut comments, &self.config.severity_overrides);
+        sort_comments(&mut comments);
+
+        if let (true, Some(root)) = (self.blame, repo_path) {
+            tokio::task::block_in_place(|| self.attach_last_authors(&mut comments, root));
+        }
+
+        let summary = (!kept_diffs.is_empty()).then(|| {
+            let risk = argus_difflens::risk::compute_risk(&kept_diffs);
+            format!(
+                "{} risk (score {:.1}/100): {} file(s), +{} -{} lines.",
+                risk.summary.risk_level,
+                risk.overall.total,
+                risk.summary.total_files,
+                risk.summary.total_additions,
+                risk.summary.total_deletions,
+            )
+        });
+
+        ReviewResult {
+            comments,
+            filtered_comments,
+            summary,
+            stats: ReviewStats {
+                files_reviewed: kept_diffs.len(),
+                files_skipped: skipped_files.len(),
+                total_hunks: kept_diffs.iter().map(|d| d.hunks.len()).sum(),
+                comments_generated,
+                skipped_files,
+                model_used: OFFLINE_MODEL.to_string(),
+                ..ReviewStats::default()
+            },
+        }
+    }
+
+    async fn review_inner(
+        &self,
+        diffs: Vec<FileDiff>,
+        repo_path: Option<&Path>,
+        description: Option<&str>,
+    ) -> Result<ReviewResult, ArgusError> {
+        let PreparedReview {
+            kept_diffs,
+            skipped_files,
+            moved_blocks,
+            deterministic,
+            total_hunks,
+            hotspot_file_count,
+            diff_text,
+            system,
+            mut timings,
+            requests,
+            split,
+        } = self.prepare(diffs, repo_path, description);
+        let files_skipped = skipped_files.len();
         let files_reviewed = kept_diffs.len();
-        let total_hunks: usize = kept_diffs.iter().map(|d| d.hunks.len()).sum();
+        let (deterministic, suppressed_deterministic) =
+            split_suppressed(deterministic, &self.suppressions);
 
         if kept_diffs.is_empty() {
             return Ok(ReviewResult {
-                comments: Vec::new(),
-                filtered_comments: Vec::new(),
+                comments: deterministic,
+                filtered_comments: suppressed_deterministic,
                 summary: None,
                 stats: ReviewStats {
                     files_reviewed: 0,
@@ -206,238 +840,81 @@ impl ReviewPipeline {
                     comments_deduplicated: 0,
                     comments_reflected_out: 0,
                     skipped_files,
-                    model_used: self.llm.model().to_string(),
+                    model_used: self.model_used(),
                     llm_calls: 0,
                     llm_retries: 0,
                     file_groups: vec![],
                     hotspot_files: 0,
+                    moved_blocks,
+                    timings: ReviewTimings::default(),
                 },
             });
         }
 
-        // Generate repo map if a repo path is provided
-        let repo_map = if let Some(root) = repo_path {
-            let focus_files: Vec<std::path::PathBuf> =
-                kept_diffs.iter().map(|d| d.new_path.clone()).collect();
-            tokio::task::block_in_place(|| {
-                match argus_repomap::generate_map(root, 1024, &focus_files, OutputFormat::Text) {
-                    Ok(map) if !map.is_empty() => Some(map),
-                    _ => None,
-                }
-            })
-        } else {
-            None
-        };
+        let mut all_comments = Vec::new();
+        let mut llm_calls: usize = 0;
+        let mut llm_retries: usize = 0;
+        let mut file_groups: Vec<Vec<String>> = Vec::new();
+        let review_format = ResponseFormat::Json(Some(prompt::review_response_schema()));
 
-        // Search for related code context if an index exists
-        let related_code = if let Some(root) = repo_path {
-            let index_path = root.join(".argus/index.db");
-            if index_path.exists() {
-                tokio::task::block_in_place(|| build_related_code_context(&kept_diffs, &index_path))
-            } else {
-                None
+        if split {
+            // Record groups for verbose output
+            for request in &requests {
+                file_groups.push(request.files.clone());
             }
-        } else {
-            None
-        };
-
-        // Build git history insights if repo is available
-        let history_insights = if let Some(root) = repo_path {
-            tokio::task::block_in_place(|| build_history_insights(&kept_diffs, root))
-        } else {
-            None
-        };
 
-        let history_context = history_insights
-            .as_ref()
-            .filter(|h| !h.context.is_empty())
-            .map(|h| h.context.as_str());
-        let hotspot_scores = history_insights
-            .as_ref()
-            .map(|h| &h.hotspot_scores)
-            .cloned()
-            .unwrap_or_default();
+            let group_count = requests.len();
+            let file_count: usize = requests.iter().map(|r| r.files.len()).sum();
+            self.reporter.groups_started(group_count, file_count);
 
-        // Sort diffs so hotspot files come first (higher score = reviewed first)
-        let mut kept_diffs = kept_diffs;
-        kept_diffs.sort_by(|a, b| {
-            let score_a = hotspot_scores
-                .get(&a.new_path.to_string_lossy().to_string())
-                .copied()
-                .unwrap_or(0.0);
-            let score_b = hotspot_scores
-                .get(&b.new_path.to_string_lossy().to_string())
-                .copied()
-                .unwrap_or(0.0);
-            score_b
-                .partial_cmp(&score_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+            for (i, request) in requests.into_iter().enumerate() {
+                let label = request.label;
+                self.reporter.group_started(i, group_count, &label);
 
-        // Count hotspot files for stats
-        let hotspot_file_count: usize = kept_diffs
-            .iter()
-            .filter(|d| {
-                hotspot_scores
-                    .get(&d.new_path.to_string_lossy().to_string())
-                    .is_some_and(|&s| s >= 0.7)
-            })
-            .count();
+                let messages = vec![
+                    ChatMessage {
+                        role: Role::System,
+                        content: system.clone(),
+                    },
+                    ChatMessage {
+                        role: Role::User,
+                        content: request.user,
+                    },
+                ];
 
-        // 2. Decide whether to split or send as one call
-        let diff_text = diffs_to_text(&kept_diffs);
-        let total_tokens = estimate_tokens(&diff_text);
+                let started = Instant::now();
+                let response = self
+                    .chat_with_rate_limit_retries(
+                        messages,
+                        &review_format,
+                        &mut llm_calls,
+                        &mut llm_retries,
+                    )
+                    .await?;
+                timings.llm_group_ms.push(elapsed_ms(started));
+                let mut parsed = self.parse_review(&response);
 
-        // Fetch negative feedback examples
-        let negative_examples = if let Some(root) = repo_path {
-            match crate::feedback::FeedbackStore::open(root) {
-                Ok(store) => store.get_negative_examples().unwrap_or_default(),
-                Err(_) => Vec::new(),
-            }
-        } else {
-            Vec::new()
-        };
-
-        let system = prompt::build_system_prompt(&self.config, &self.rules, &negative_examples);
-        let mut all_comments = Vec::new();
-        let mut llm_calls: usize = 0;
-        let mut llm_retries: usize = 0;
-        let mut file_groups: Vec<Vec<String>> = Vec::new();
-
-        if total_tokens > self.config.max_diff_tokens && kept_diffs.len() > 1 {
-            // Split into groups and review each group
-            let groups = if self.config.cross_file {
-                group_related_diffs(&kept_diffs, self.config.max_diff_tokens)
-            } else {
-                // Disable grouping: each file is its own group
-                kept_diffs.iter().map(|d| vec![d]).collect()
-            };
-
-            // Record groups for verbose output
-            for group in &groups {
-                let names: Vec<String> = group
-                    .iter()
-                    .map(|d| d.new_path.to_string_lossy().into_owned())
-                    .collect();
-                file_groups.push(names);
-            }
-
-            let is_tty = std::io::stderr().is_terminal();
-            let group_count = groups.len();
-            let mp = MultiProgress::new();
-
-            let main_pb = if is_tty {
-                let file_count: usize = groups.iter().map(|g| g.len()).sum();
-                let pb = mp.add(ProgressBar::new(group_count as u64));
-                pb.set_style(
-                    ProgressStyle::with_template(
-                        "{spinner:.cyan} Reviewing {msg} [{bar:20.cyan/dim}] {pos}/{len} groups ({elapsed})"
-                    )
-                    .unwrap()
-                    .progress_chars("━╸─"),
-                );
-                pb.set_message(format!("{file_count} files"));
-                pb.enable_steady_tick(std::time::Duration::from_millis(120));
-                Some(pb)
-            } else {
-                None
-            };
-
-            for (i, group) in groups.iter().enumerate() {
-                let group_pb = if is_tty {
-                    let label = group_display_name(group.as_slice());
-                    let pb = mp.add(ProgressBar::new_spinner());
-                    pb.set_style(ProgressStyle::with_template("  {spinner:.dim} {msg}").unwrap());
-                    pb.set_message(format!("[{}/{}] {label}...", i + 1, group_count));
-                    pb.enable_steady_tick(std::time::Duration::from_millis(120));
-                    Some(pb)
-                } else {
-                    None
-                };
-
-                let group_diff_text = diffs_to_text(group);
-                let is_cross_file = group.len() > 1;
-
-                // Build per-file hotspot context for this group
-                let file_ctx = build_hotspot_file_context(group, &hotspot_scores);
-
-                let user = prompt::build_review_prompt(
-                    &group_diff_text,
-                    repo_map.as_deref(),
-                    related_code.as_deref(),
-                    history_context,
-                    file_ctx.as_deref(),
-                    is_cross_file,
-                );
-
-                let messages = vec![
-                    ChatMessage {
-                        role: Role::System,
-                        content: system.clone(),
-                    },
-                    ChatMessage {
-                        role: Role::User,
-                        content: user,
-                    },
-                ];
-
-                let response = self
-                    .chat_with_rate_limit_retries(messages, &mut llm_calls, &mut llm_retries)
-                    .await?;
-                let mut parsed = prompt::parse_review_response(&response)?;
-
-                if let Some(pb) = &group_pb {
-                    let label = group_display_name(group.as_slice());
-                    let comment_count = parsed.len();
-                    pb.finish_with_message(format!(
-                        "[{}/{}] {label} → {comment_count} comment{}",
-                        i + 1,
-                        group_count,
-                        if comment_count == 1 { "" } else { "s" },
-                    ));
-                }
-                if let Some(pb) = &main_pb {
-                    pb.inc(1);
-                }
-                all_comments.append(&mut parsed);
-            }
-
-            if let Some(pb) = main_pb {
-                pb.finish_and_clear();
+                self.reporter
+                    .group_finished(i, group_count, &label, parsed.len());
+                all_comments.append(&mut parsed);
             }
+
+            self.reporter.groups_finished();
         } else {
             // Single LLM call
-            let is_tty = std::io::stderr().is_terminal();
-            let spinner = if is_tty {
-                let pb = ProgressBar::new_spinner();
-                pb.set_style(
-                    ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})").unwrap(),
-                );
-                let file_label = if kept_diffs.len() == 1 {
-                    "file"
-                } else {
-                    "files"
-                };
-                pb.set_message(format!("Reviewing {} {file_label}...", kept_diffs.len(),));
-                pb.enable_steady_tick(std::time::Duration::from_millis(120));
-                Some(pb)
+            let file_label = if kept_diffs.len() == 1 {
+                "file"
             } else {
-                None
+                "files"
             };
+            self.reporter
+                .task_started(&format!("Reviewing {} {file_label}...", kept_diffs.len()));
 
-            let is_cross_file = kept_diffs.len() > 1;
-
-            // Build per-file hotspot context
-            let file_ctx = build_hotspot_file_context(&kept_diffs, &hotspot_scores);
-
-            let user = prompt::build_review_prompt(
-                &diff_text,
-                repo_map.as_deref(),
-                related_code.as_deref(),
-                history_context,
-                file_ctx.as_deref(),
-                is_cross_file,
-            );
+            let user = requests
+                .into_iter()
+                .next()
+                .map(|r| r.user)
+                .unwrap_or_default();
 
             let messages = vec![
                 ChatMessage {
@@ -450,72 +927,147 @@ impl ReviewPipeline {
                 },
             ];
 
+            let started = Instant::now();
             let response = self
-                .chat_with_rate_limit_retries(messages, &mut llm_calls, &mut llm_retries)
+                .chat_with_rate_limit_retries(
+                    messages,
+                    &review_format,
+                    &mut llm_calls,
+                    &mut llm_retries,
+                )
                 .await?;
-            all_comments = prompt::parse_review_response(&response)?;
-            if let Some(pb) = spinner {
-                pb.finish_with_message(format!(
-                    "Reviewed → {} comment{}",
-                    all_comments.len(),
-                    if all_comments.len() == 1 { "" } else { "s" },
-                ));
-            }
+            timings.llm_group_ms.push(elapsed_ms(started));
+            all_comments = self.parse_review(&response);
+            self.reporter.task_finished(&format!(
+                "Reviewed → {} comment{}",
+                all_comments.len(),
+                if all_comments.len() == 1 { "" } else { "s" },
+            ));
         }
 
+        timings.llm_ms = timings.llm_group_ms.iter().sum();
         let comments_generated = all_comments.len();
 
+        // Paths are matched against the diffs, rules, and each other from here on
+        normalize_comment_paths(&mut all_comments, &kept_diffs, repo_path);
+
         // Tag comments that match custom rules
         tag_rule_matches(&mut all_comments, &self.rules);
 
         // 3. Deduplicate
-        let (deduped, comments_deduplicated) = deduplicate(all_comments);
-
-        // 3.5. Self-reflection pass: filter false positives
-        let (reflected, comments_reflected_out) =
-            if self.config.self_reflection && !deduped.is_empty() {
-                let spinner = make_spinner("Self-reflecting on comments...");
+        let (deduped, duplicates) = deduplicate(all_comments, self.config.noise.exact_dedup);
+        let (deduped, merged_counterparts) = merge_test_counterparts(deduped);
+        let comments_deduplicated = duplicates + merged_counterparts;
+
+        // 3.5. Self-reflection pass: filter false positives (and, in augment
+        // mode, add missed issues — worth a call even with no comments yet)
+        let augment = self.config.self_reflection_mode.augments();
+        let started = Instant::now();
+        let (mut reflected, reflected_out) =
+            if self.config.self_reflection && (augment || !deduped.is_empty()) {
+                self.reporter.task_started("Self-reflecting on comments...");
                 match self
                     .self_reflect(&deduped, &diff_text, &mut llm_calls, &mut llm_retries)
                     .await
                 {
-                    Ok((kept, removed_count)) => {
-                        if let Some(pb) = spinner {
-                            pb.finish_with_message(format!(
-                                "Self-reflection → {removed_count} filtered out"
-                            ));
-                        }
-                        (kept, removed_count)
+                    Ok((kept, removed, additions)) => {
+                        let removed_count = removed.len();
+                        let (kept, added_count) = merge_reflection_additions(
+                            kept,
+                            additions,
+                            &self.rules,
+                            self.config.noise.exact_dedup,
+                        );
+                        self.reporter.task_finished(&if augment {
+                            format!(
+                            "Self-reflection → {removed_count} filtered out, {added_count} added"
+                        )
+                        } else {
+                            format!("Self-reflection → {removed_count} filtered out")
+                        });
+                        (kept, removed)
                     }
                     Err(e) => {
-                        if let Some(pb) = spinner {
-                            pb.finish_with_message("Self-reflection failed, keeping all");
-                        }
-                        eprintln!("warning: self-reflection failed ({e}), keeping all comments");
-                        (deduped, 0)
+                        self.reporter
+                            .task_finished("Self-reflection failed, keeping all");
+                        self.reporter.warning(&format!(
+                            "self-reflection failed ({e}), keeping all comments"
+                        ));
+                        (deduped, Vec::new())
                     }
                 }
             } else {
-                (deduped, 0)
+                (deduped, Vec::new())
             };
+        timings.self_reflection_ms = elapsed_ms(started);
+        let comments_reflected_out = reflected_out.len();
+        // Findings the reflection pass added have not been normalized yet
+        normalize_comment_paths(&mut reflected, &kept_diffs, repo_path);
 
-        // 4. Filter and sort
-        let (final_comments, filtered_comments) = filter_and_sort(reflected, &self.config);
-        let comments_filtered = filtered_comments.len();
+        // 3.75. Raise comments on stricter paths to their severity floor, before
+        // the severity filter sees them (the deterministic findings below are
+        // raised once merged)
+        apply_severity_overrides(&mut reflected, &self.config.severity_overrides);
 
-        if std::io::stderr().is_terminal() {
-            eprintln!(
-                "✓ Done. {} comments ({} filtered, {} deduped, {} reflected out)",
-                final_comments.len(),
-                comments_filtered,
-                comments_deduplicated,
-                comments_reflected_out,
-            );
+        // 4. Filter and sort
+        attach_fingerprints(&mut reflected, &kept_diffs);
+        let (reflected, unchanged_line) = if self.config.comments_on_changed_only {
+            split_unchanged_line_comments(reflected, &kept_diffs)
+        } else {
+            (reflected, Vec::new())
+        };
+        let (mut final_comments, threshold_filtered) =
+            filter_and_sort(reflected, &self.config, &self.suppressions);
+        let comments_filtered = unchanged_line.len() + threshold_filtered.len();
+        let mut filtered_comments: Vec<FilteredComment> = reflected_out
+            .into_iter()
+            .map(|comment| FilteredComment {
+                comment,
+                reason: FilterReason::SelfReflection,
+            })
+            .chain(unchanged_line)
+            .chain(threshold_filtered)
+            .collect();
+
+        // 4.5. Deterministic conflict-marker, TODO/FIXME, breaking-change, and
+        // pattern-rule findings bypass the LLM noise filters, but not the allowlist
+        let mut found = Vec::new();
+        if self.config.flag_todos {
+            found.extend(argus_difflens::markers::find_marker_comments(
+                &kept_diffs,
+                &self.config.todo_markers,
+                self.config.todo_severity,
+            ));
+        }
+        if self.config.flag_breaking_changes {
+            found.extend(argus_difflens::breaking::find_breaking_changes(&kept_diffs));
         }
+        found.extend(self.pattern_violations(&kept_diffs));
+        attach_fingerprints(&mut found, &kept_diffs);
+        let (found, suppressed) = split_suppressed(found, &self.suppressions);
+        final_comments.extend(deterministic);
+        final_comments.extend(found);
+        apply_severity_overrides(&mut final_comments, &self.config.severity_overrides);
+        sort_comments(&mut final_comments);
+        filtered_comments.extend(suppressed_deterministic);
+        filtered_comments.extend(suppressed);
+
+        if let (true, Some(root)) = (self.blame, repo_path) {
+            tokio::task::block_in_place(|| self.attach_last_authors(&mut final_comments, root));
+        }
+
+        self.reporter.status(&format!(
+            "✓ Done. {} comments ({} filtered, {} deduped, {} reflected out)",
+            final_comments.len(),
+            comments_filtered,
+            comments_deduplicated,
+            comments_reflected_out,
+        ));
 
         // 5. Generate summary if there are comments
-        let summary = if !final_comments.is_empty() {
-            let spinner = make_spinner("Generating summary...");
+        let started = Instant::now();
+        let summary = if self.config.summary && !final_comments.is_empty() {
+            self.reporter.task_started("Generating summary...");
             let summary_messages = vec![
                 ChatMessage {
                     role: Role::System,
@@ -527,25 +1079,27 @@ impl ReviewPipeline {
                 },
             ];
             match self
-                .chat_with_rate_limit_retries(summary_messages, &mut llm_calls, &mut llm_retries)
+                .chat_with_rate_limit_retries(
+                    summary_messages,
+                    &ResponseFormat::Text,
+                    &mut llm_calls,
+                    &mut llm_retries,
+                )
                 .await
             {
-                Ok(text) => {
-                    if let Some(pb) = spinner {
-                        pb.finish_with_message("Summary generated");
-                    }
-                    Some(text.trim().to_string())
+                Ok(response) => {
+                    self.reporter.task_finished("Summary generated");
+                    Some(response.content.trim().to_string())
                 }
                 Err(_) => {
-                    if let Some(pb) = spinner {
-                        pb.finish_with_message("Summary generation failed");
-                    }
+                    self.reporter.task_finished("Summary generation failed");
                     None
                 }
             }
         } else {
             None
         };
+        timings.summary_ms = elapsed_ms(started);
 
         Ok(ReviewResult {
             comments: final_comments,
@@ -560,28 +1114,501 @@ impl ReviewPipeline {
                 comments_deduplicated,
                 comments_reflected_out,
                 skipped_files,
-                model_used: self.llm.model().to_string(),
+                model_used: self.model_used(),
                 llm_calls,
                 llm_retries,
                 file_groups,
                 hotspot_files: hotspot_file_count,
+                moved_blocks,
+                timings,
             },
         })
     }
 
+    /// Estimate the LLM work a review would do, without calling the LLM.
+    ///
+    /// Runs the same filtering, context gathering, splitting, and prompt
+    /// construction as [`review`](Self::review), then estimates tokens with
+    /// the same heuristic used for splitting. The self-reflection and
+    /// summary calls are counted as if they will run (when enabled), so the
+    /// estimate is an upper bound. Like `review`, this must be called from within a
+    /// multi-threaded Tokio runtime when `repo_path` is set.
+    pub fn plan(&self, diffs: Vec<FileDiff>, repo_path: Option<&Path>) -> ReviewPlan {
+        let prepared = self.prepare(diffs, repo_path, None);
+        let system_tokens = estimate_tokens(&prepared.system);
+
+        let groups: Vec<PlannedGroup> = prepared
+            .requests
+            .iter()
+            .map(|r| PlannedGroup {
+                files: r.files.clone(),
+                estimated_tokens: system_tokens + estimate_tokens(&r.user),
+            })
+            .collect();
+
+        let review_calls = groups.len();
+        let mut llm_calls = review_calls;
+        let mut input_tokens: usize = groups.iter().map(|g| g.estimated_tokens).sum();
+        if review_calls > 0 {
+            let diff_tokens = estimate_tokens(&prepared.diff_text);
+            if self.config.self_reflection {
+                llm_calls += 1;
+                input_tokens += diff_tokens + PLANNED_COMMENTS_TOKENS;
+            }
+            if self.config.summary {
+                llm_calls += 1;
+                input_tokens += diff_tokens + PLANNED_COMMENTS_TOKENS;
+            }
+        }
+        let output_tokens = llm_calls * PLANNED_OUTPUT_TOKENS_PER_CALL;
+
+        let client = self.client_at(self.active_client.load(Ordering::Relaxed));
+        let estimated_cost_usd = estimate_cost_usd(
+            client.provider_name(),
+            client.model(),
+            input_tokens,
+            output_tokens,
+        );
+
+        ReviewPlan {
+            model: self.model_used(),
+            files_reviewed: prepared.kept_diffs.len(),
+            files_skipped: prepared.skipped_files.len(),
+            groups,
+            review_calls,
+            estimated_llm_calls: llm_calls,
+            estimated_input_tokens: input_tokens,
+            estimated_output_tokens: output_tokens,
+            estimated_cost_usd,
+        }
+    }
+
+    /// Languages the repo map covers: `repo_map_languages` if set, else
+    /// those of the files in `diffs`, each with its
+    /// [related](Language::related) languages. Empty (every language) when
+    /// the diff has no source files the map can parse.
+    fn repo_map_languages(&self, diffs: &[FileDiff]) -> Vec<Language> {
+        let found: Vec<Language> = if self.config.repo_map_languages.is_empty() {
+            diffs
+                .iter()
+                .filter_map(|d| d.new_path.extension()?.to_str())
+                .map(Language::from_extension)
+                .collect()
+        } else {
+            // Config loading has already rejected unknown names
+            self.config
+                .repo_map_languages
+                .iter()
+                .map(|name| Language::from_name(name))
+                .collect()
+        };
+        let mut languages = Vec::new();
+        for language in found {
+            if language == Language::Unknown {
+                continue;
+            }
+            for &language in std::iter::once(&language).chain(language.related()) {
+                if !languages.contains(&language) {
+                    languages.push(language);
+                }
+            }
+        }
+        languages
+    }
+
+    /// Warn about files left out of the repo map because they could not be
+    /// read or parsed; the review still uses the rest of the map.
+    fn warn_partial_map(&self, map: &argus_repomap::RepoMap) {
+        let skipped: Vec<String> = map
+            .unreadable_files
+            .iter()
+            .map(|path| format!("{} (unreadable)", path.display()))
+            .chain(
+                map.unparsed_files
+                    .iter()
+                    .map(|file| format!("{} ({})", file.path.display(), file.reason)),
+            )
+            .collect();
+        if skipped.is_empty() {
+            return;
+        }
+        // A broken grammar can fail every file; name only the first few
+        let mut listed = skipped[..skipped.len().min(5)].join(", ");
+        if skipped.len() > 5 {
+            listed.push_str(&format!(", +{} more", skipped.len() - 5));
+        }
+        self.reporter.warning(&format!(
+            "repo map skipped {} file(s): {listed}",
+            skipped.len()
+        ));
+    }
+
+    /// Findings of the rules with a `pattern` in `diffs`.
+    ///
+    /// The rules are compiled once per pipeline. Config loading rejects
+    /// invalid patterns, but a rule built in code whose pattern or paths do
+    /// not compile is skipped with a warning rather than failing the review.
+    fn pattern_violations(&self, diffs: &[FileDiff]) -> Vec<ReviewComment> {
+        let rules = self.pattern_rules.get_or_init(|| {
+            let mut rules = Vec::new();
+            for rule in &self.rules {
+                match PatternRule::compile(rule) {
+                    Ok(Some(compiled)) => rules.push(compiled),
+                    Ok(None) => {}
+                    Err(e) => self.reporter.warning(&format!("{e}, skipping it")),
+                }
+            }
+            rules
+        });
+        find_pattern_violations(diffs, rules)
+    }
+
+    /// Filter diffs, gather context, and build the review prompts.
+    fn prepare(
+        &self,
+        mut diffs: Vec<FileDiff>,
+        repo_path: Option<&Path>,
+        description: Option<&str>,
+    ) -> PreparedReview {
+        normalize_diff_paths(&mut diffs, repo_path);
+
+        // 1. Pre-filter diffs, after scanning all of them for conflict markers,
+        // since a lock file with a committed conflict is broken too, and for
+        // dependencies added to lock files
+        let mut deterministic = find_conflict_markers(&diffs);
+        if self.config.flag_new_dependencies {
+            deterministic.extend(find_new_dependencies(&diffs, Severity::Info));
+        }
+        attach_fingerprints(&mut deterministic, &diffs);
+        let diff_filter = DiffFilter::from_config(&self.config);
+        let filter_result = diff_filter.filter(diffs);
+        let skipped_files = filter_result.skipped;
+
+        // 1.5. Leave out hunks that only move code around
+        let moved_blocks = detect_moves(&filter_result.kept);
+        let kept_diffs = drop_moved_hunks(filter_result.kept, &moved_blocks);
+        let total_hunks: usize = kept_diffs.iter().map(|d| d.hunks.len()).sum();
+
+        if kept_diffs.is_empty() {
+            return PreparedReview {
+                kept_diffs,
+                skipped_files,
+                moved_blocks,
+                deterministic,
+                total_hunks,
+                hotspot_file_count: 0,
+                diff_text: String::new(),
+                system: String::new(),
+                timings: ReviewTimings::default(),
+                requests: Vec::new(),
+                split: false,
+            };
+        }
+
+        let mut timings = ReviewTimings::default();
+
+        // Generate repo map if a repo path is provided. The same graph
+        // build yields the blast radius of the changed symbols.
+        let started = Instant::now();
+        let (repo_map, blast_radius) = if let Some(root) = repo_path {
+            let focus_files: Vec<std::path::PathBuf> =
+                kept_diffs.iter().map(|d| d.new_path.clone()).collect();
+            let changed_ranges = kept_diffs
+                .iter()
+                .flat_map(|d| {
+                    d.hunks.iter().filter(|h| h.new_lines > 0).map(|h| {
+                        (
+                            d.new_path.clone(),
+                            h.new_start..=h.new_start + h.new_lines - 1,
+                        )
+                    })
+                })
+                .collect();
+            // Only the neighborhood of the changed files matters here, so
+            // skip ranking the whole graph, and files in other languages.
+            let options = argus_repomap::MapOptions {
+                focus_files,
+                focus_hops: Some(self.focus_hops),
+                changed_ranges,
+                ignored_references: self.ignored_references.clone(),
+                max_file_bytes: self.max_file_bytes,
+                leaf_pruning_threshold: self.leaf_pruning_threshold,
+                languages: self.repo_map_languages(&kept_diffs),
+                ..argus_repomap::MapOptions::default()
+            };
+            tokio::task::block_in_place(|| match argus_repomap::generate_map_with(root, &options) {
+                Ok(map) => {
+                    self.warn_partial_map(&map);
+                    (
+                        Some(map.output).filter(|output| !output.is_empty()),
+                        map.blast_radius,
+                    )
+                }
+                Err(_) => (None, Vec::new()),
+            })
+        } else {
+            (None, Vec::new())
+        };
+        timings.repo_map_ms = elapsed_ms(started);
+
+        // Search for related code context if an index exists
+        let started = Instant::now();
+        let related_code = match repo_path {
+            Some(root) if self.config.use_search_context => {
+                let index_path = root.join(".argus/index.db");
+                if index_path.exists() {
+                    tokio::task::block_in_place(|| {
+                        build_related_code_context(&kept_diffs, &index_path, &self.fts_stop_words)
+                    })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        timings.related_code_ms = elapsed_ms(started);
+
+        // Find changed functions that no test mentions
+        let untested = match repo_path {
+            Some(root) if self.config.test_presence_context => tokio::task::block_in_place(|| {
+                crate::testpresence::find_untested_functions(&kept_diffs, root)
+            }),
+            _ => Vec::new(),
+        };
+
+        // Build git history insights if repo is available
+        let started = Instant::now();
+        let history_insights = match repo_path {
+            Some(root) if self.config.use_history_context => {
+                tokio::task::block_in_place(|| build_history_insights(&kept_diffs, root))
+            }
+            _ => None,
+        };
+        timings.history_ms = elapsed_ms(started);
+
+        let history_context = history_insights
+            .as_ref()
+            .filter(|h| !h.context.is_empty())
+            .map(|h| h.context.as_str());
+        let hotspot_scores = history_insights
+            .as_ref()
+            .map(|h| &h.hotspot_scores)
+            .cloned()
+            .unwrap_or_default();
+
+        // Sort diffs so hotspot files come first (higher score = reviewed first)
+        let mut kept_diffs = kept_diffs;
+        kept_diffs.sort_by(|a, b| {
+            let score_a = hotspot_scores
+                .get(&a.new_path.to_string_lossy().to_string())
+                .copied()
+                .unwrap_or(0.0);
+            let score_b = hotspot_scores
+                .get(&b.new_path.to_string_lossy().to_string())
+                .copied()
+                .unwrap_or(0.0);
+            score_b
+                .partial_cmp(&score_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // Count hotspot files for stats
+        let hotspot_file_count: usize = kept_diffs
+            .iter()
+            .filter(|d| {
+                hotspot_scores
+                    .get(&d.new_path.to_string_lossy().to_string())
+                    .is_some_and(|&s| s >= 0.7)
+            })
+            .count();
+
+        // 2. Decide whether to split or send as one call
+        let diff_text = diffs_to_text(&kept_diffs);
+        let total_tokens = estimate_tokens(&diff_text);
+
+        // Fetch negative feedback examples
+        let negative_examples = if let Some(root) = repo_path {
+            match crate::feedback::FeedbackStore::open(root) {
+                Ok(store) => store.get_negative_examples().unwrap_or_default(),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
+        let system = prompt::build_system_prompt(&self.config, &self.rules, &negative_examples);
+        let description_ctx = description.map(|d| format!("{INTENT_HEADING}\n{}\n", d.trim()));
+
+        let split = total_tokens > self.config.max_diff_tokens && kept_diffs.len() > 1;
+        let mut requests = if split {
+            let groups = if self.config.cross_file {
+                group_related_diffs(&kept_diffs, self.config.max_diff_tokens)
+            } else {
+                // Disable grouping: each file is its own group
+                kept_diffs.iter().map(|d| vec![d]).collect()
+            };
+
+            groups
+                .iter()
+                .map(|group| {
+                    let group_diff_text = diffs_to_text(group);
+                    let is_cross_file = group.len() > 1;
+
+                    // Build per-file hotspot and surrounding-code context for this group
+                    let file_ctx = join_context(
+                        join_context(
+                            join_context(
+                                join_context(
+                                    description_ctx.clone(),
+                                    build_hotspot_file_context(group, &hotspot_scores),
+                                ),
+                                build_blast_radius_context(group, &blast_radius),
+                            ),
+                            build_test_presence_context(group, &untested),
+                        ),
+                        repo_path.and_then(|root| {
+                            build_surrounding_context(
+                                group,
+                                root,
+                                self.config.context_lines,
+                                self.function_budget(&group_diff_text),
+                            )
+                        }),
+                    );
+
+                    PlannedRequest {
+                        label: group_display_name(group.as_slice()),
+                        files: group
+                            .iter()
+                            .map(|d| d.new_path.to_string_lossy().into_owned())
+                            .collect(),
+                        user: prompt::build_review_prompt(
+                            &group_diff_text,
+                            repo_map.as_deref(),
+                            related_code.as_deref(),
+                            history_context,
+                            file_ctx.as_deref(),
+                            is_cross_file,
+                        ),
+                    }
+                })
+                .collect()
+        } else {
+            let is_cross_file = kept_diffs.len() > 1;
+
+            // Build per-file hotspot and surrounding-code context
+            let file_ctx = join_context(
+                join_context(
+                    join_context(
+                        join_context(
+                            description_ctx,
+                            build_hotspot_file_context(&kept_diffs, &hotspot_scores),
+                        ),
+                        build_blast_radius_context(&kept_diffs, &blast_radius),
+                    ),
+                    build_test_presence_context(&kept_diffs, &untested),
+                ),
+                repo_path.and_then(|root| {
+                    build_surrounding_context(
+                        &kept_diffs,
+                        root,
+                        self.config.context_lines,
+                        self.function_budget(&diff_text),
+                    )
+                }),
+            );
+
+            vec![PlannedRequest {
+                label: group_display_name(&kept_diffs.iter().collect::<Vec<_>>()),
+                files: kept_diffs
+                    .iter()
+                    .map(|d| d.new_path.to_string_lossy().into_owned())
+                    .collect(),
+                user: prompt::build_review_prompt(
+                    &diff_text,
+                    repo_map.as_deref(),
+                    related_code.as_deref(),
+                    history_context,
+                    file_ctx.as_deref(),
+                    is_cross_file,
+                ),
+            }]
+        };
+        if let Some(section) = prompt::build_instructions_section(&self.instructions) {
+            for request in &mut requests {
+                request.user.push_str(&section);
+            }
+        }
+
+        PreparedReview {
+            kept_diffs,
+            skipped_files,
+            moved_blocks,
+            deterministic,
+            total_hunks,
+            hotspot_file_count,
+            diff_text,
+            system,
+            timings,
+            requests,
+            split,
+        }
+    }
+
+    /// Tokens left for `expand_to_function` context once `diff_text` is
+    /// in the prompt, or `None` when expansion is off.
+    fn function_budget(&self, diff_text: &str) -> Option<usize> {
+        self.config.expand_to_function.then(|| {
+            self.config
+                .max_diff_tokens
+                .saturating_sub(estimate_tokens(diff_text))
+        })
+    }
+
+    /// Set `last_author` on each comment from `git blame` of its line.
+    ///
+    /// Files that cannot be blamed (untracked, outside the repository) are
+    /// reported as warnings and left without authors.
+    fn attach_last_authors(&self, comments: &mut [ReviewComment], root: &Path) {
+        let mut lines_by_file: BTreeMap<std::path::PathBuf, Vec<u32>> = BTreeMap::new();
+        for c in comments.iter() {
+            lines_by_file
+                .entry(c.file_path.clone())
+                .or_default()
+                .push(c.line);
+        }
+
+        for (file, lines) in lines_by_file {
+            match argus_gitpulse::blame::blame_lines(root, &file, &lines) {
+                Ok(authors) => {
+                    for c in comments.iter_mut().filter(|c| c.file_path == file) {
+                        c.last_author = authors.get(&c.line).map(|a| a.to_string());
+                    }
+                }
+                Err(e) => self.reporter.warning(&format!("blame skipped: {e}")),
+            }
+        }
+    }
+
     /// Run self-reflection on the generated comments.
     ///
     /// Sends the comments and diff to the LLM for a second evaluation pass.
     /// Comments scoring below `self_reflection_score_threshold` are removed.
-    /// Returns the surviving comments and the count of removed ones.
+    /// Returns the surviving comments, the count of removed ones, and, in
+    /// `filter_and_augment` mode, the issues the LLM reported as missed.
     async fn self_reflect(
         &self,
         comments: &[ReviewComment],
         diff_text: &str,
         llm_calls: &mut usize,
         llm_retries: &mut usize,
-    ) -> Result<(Vec<ReviewComment>, usize), ArgusError> {
-        let reflection_prompt = prompt::build_self_reflection_prompt(comments, diff_text);
+    ) -> Result<(Vec<ReviewComment>, Vec<ReviewComment>, Vec<ReviewComment>), ArgusError> {
+        let augment = self.config.self_reflection_mode.augments();
+        let reflection_prompt = if augment {
+            prompt::build_augmenting_self_reflection_prompt(comments, diff_text)
+        } else {
+            prompt::build_self_reflection_prompt(comments, diff_text)
+        };
         let messages = vec![
             ChatMessage {
                 role: Role::System,
@@ -596,58 +1623,126 @@ impl ReviewPipeline {
         ];
 
         let response = self
-            .chat_with_rate_limit_retries(messages, llm_calls, llm_retries)
+            .chat_with_rate_limit_retries(
+                messages,
+                &ResponseFormat::Json(None),
+                llm_calls,
+                llm_retries,
+            )
             .await?;
 
-        let evaluations = prompt::parse_self_reflection_response(&response)?;
+        let evaluations = match prompt::decode_self_reflection_response(&response.content) {
+            Ok(evaluations) => evaluations,
+            Err(e) => {
+                self.warn_unparsed("self-reflection response", &response, &e);
+                Vec::new()
+            }
+        };
 
-        // Build a score map: index -> (score, optional revised severity)
-        let mut score_map: HashMap<usize, (u8, Option<Severity>)> = HashMap::new();
-        for (idx, score, revised_sev) in evaluations {
-            score_map.insert(idx, (score, revised_sev));
-        }
+        let (kept, removed) = self.apply_reflection_scores(comments, evaluations);
+
+        let additions = if augment {
+            prompt::parse_self_reflection_additions(&response.content)
+        } else {
+            Vec::new()
+        };
+
+        Ok((kept, removed, additions))
+    }
+
+    /// Split comments by their self-reflection scores into kept and removed.
+    ///
+    /// Every evaluated comment records its score and reason in
+    /// [`ReviewComment::reflection`]. Comments scoring below
+    /// `self_reflection_score_threshold` are removed; the rest take any
+    /// revised severity and, with `recalibrate_confidence`, a confidence
+    /// blended with their score by [`recalibrated_confidence`]. Comments the
+    /// LLM did not evaluate are kept unchanged.
+    fn apply_reflection_scores(
+        &self,
+        comments: &[ReviewComment],
+        evaluations: Vec<prompt::ReflectionEval>,
+    ) -> (Vec<ReviewComment>, Vec<ReviewComment>) {
+        let mut score_map: HashMap<usize, prompt::ReflectionEval> = evaluations
+            .into_iter()
+            .map(|eval| (eval.index, eval))
+            .collect();
 
         let threshold = self.config.self_reflection_score_threshold;
         let mut kept = Vec::new();
-        let mut removed = 0usize;
+        let mut removed = Vec::new();
 
         for (i, mut comment) in comments.iter().cloned().enumerate() {
-            if let Some((score, revised_sev)) = score_map.get(&i) {
-                if *score < threshold {
-                    removed += 1;
+            if let Some(eval) = score_map.remove(&i) {
+                let score = eval.score;
+                comment.reflection = Some(ReflectionScore {
+                    score,
+                    reason: eval.reason,
+                });
+                if score < threshold {
+                    removed.push(comment);
                     continue;
                 }
                 // Apply revised severity if provided
-                if let Some(sev) = revised_sev {
-                    comment.severity = *sev;
+                if let Some(sev) = eval.revised_severity {
+                    comment.severity = sev;
+                }
+                if self.config.recalibrate_confidence {
+                    comment.confidence = recalibrated_confidence(comment.confidence, score);
                 }
             }
             // If a comment wasn't evaluated (LLM missed it), keep it
             kept.push(comment);
         }
+        (kept, removed)
+    }
+
+    /// Parse review comments, reporting malformed responses as a warning.
+    fn parse_review(&self, response: &ChatResponse) -> Vec<ReviewComment> {
+        prompt::decode_review_response(&response.content).unwrap_or_else(|e| {
+            self.warn_unparsed("LLM response", response, &e);
+            Vec::new()
+        })
+    }
 
-        Ok((kept, removed))
+    /// Warn that `what` could not be parsed, pointing at `[llm]
+    /// max_output_tokens` when the provider cut the response off.
+    fn warn_unparsed(&self, what: &str, response: &ChatResponse, err: &serde_json::Error) {
+        if response.truncated {
+            self.reporter.warning(&format!(
+                "failed to parse {what}: it was cut off at the output token limit \
+                 ({err}); raise [llm] max_output_tokens"
+            ));
+        } else {
+            self.reporter
+                .warning(&format!("failed to parse {what}: {err}"));
+        }
     }
 
     async fn chat_with_rate_limit_retries(
         &self,
         messages: Vec<ChatMessage>,
+        format: &ResponseFormat,
         llm_calls: &mut usize,
         llm_retries: &mut usize,
-    ) -> Result<String, ArgusError> {
-        const MAX_RETRIES: u32 = 3;
-        const INITIAL_BACKOFF_MS: u64 = 1000;
-
-        let mut attempt: u32 = 0;
+    ) -> Result<ChatResponse, ArgusError> {
+        let total_clients = 1 + self.fallbacks.len();
+        let mut index = self.active_client.load(Ordering::Relaxed);
         loop {
-            *llm_calls += 1;
-            match self.llm.chat(messages.clone()).await {
-                Ok(response) => return Ok(response),
-                Err(err) if is_rate_limit_error(&err) && attempt < MAX_RETRIES => {
-                    *llm_retries += 1;
-                    let backoff_ms = INITIAL_BACKOFF_MS * (1_u64 << attempt);
-                    tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
-                    attempt += 1;
+            let client = self.client_at(index);
+            match chat_with_retries(client, &messages, format, llm_calls, llm_retries).await {
+                Ok(response) => {
+                    self.active_client.store(index, Ordering::Relaxed);
+                    return Ok(response);
+                }
+                Err(err) if is_retriable_error(&err) && index + 1 < total_clients => {
+                    let next = self.client_at(index + 1);
+                    self.reporter.warning(&format!(
+                        "{} failed ({err}), falling back to {}",
+                        client.provider_name(),
+                        next.provider_name(),
+                    ));
+                    index += 1;
                 }
                 Err(err) => return Err(err),
             }
@@ -655,26 +1750,41 @@ impl ReviewPipeline {
     }
 }
 
-fn is_rate_limit_error(err: &ArgusError) -> bool {
-    match err {
-        ArgusError::Llm(message) => {
-            let lower = message.to_ascii_lowercase();
-            lower.contains("429") || lower.contains("too many requests")
+async fn chat_with_retries(
+    client: &LlmClient,
+    messages: &[ChatMessage],
+    format: &ResponseFormat,
+    llm_calls: &mut usize,
+    llm_retries: &mut usize,
+) -> Result<ChatResponse, ArgusError> {
+    const MAX_RETRIES: u32 = 3;
+    const INITIAL_BACKOFF_MS: u64 = 1000;
+
+    let mut attempt: u32 = 0;
+    loop {
+        *llm_calls += 1;
+        match client.chat_response(messages.to_vec(), format).await {
+            Ok(response) => return Ok(response),
+            Err(err) if is_rate_limit_error(&err) && attempt < MAX_RETRIES => {
+                *llm_retries += 1;
+                let backoff_ms = INITIAL_BACKOFF_MS * (1_u64 << attempt);
+                tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
         }
-        _ => false,
     }
 }
 
-/// Create a stderr spinner that only displays when stderr is a terminal.
-fn make_spinner(message: &str) -> Option<ProgressBar> {
-    if !std::io::stderr().is_terminal() {
-        return None;
-    }
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})").unwrap());
-    pb.set_message(message.to_string());
-    pb.enable_steady_tick(std::time::Duration::from_millis(120));
-    Some(pb)
+/// Whether an error suggests the provider is unavailable rather than the
+/// request being bad: rate limits, timeouts, 5xx responses, and network
+/// failures.
+fn is_retriable_error(err: &ArgusError) -> bool {
+    matches!(err, ArgusError::LlmRequest(e) if e.kind.is_retriable())
+}
+
+fn is_rate_limit_error(err: &ArgusError) -> bool {
+    matches!(err, ArgusError::LlmRequest(e) if e.kind == LlmErrorKind::RateLimit)
 }
 
 fn diffs_to_text<D: std::borrow::Borrow<FileDiff>>(diffs: &[D]) -> String {
@@ -696,6 +1806,31 @@ fn diffs_to_text<D: std::borrow::Borrow<FileDiff>>(diffs: &[D]) -> String {
     text
 }
 
+/// Blend a comment's self-reported confidence with its self-reflection score.
+///
+/// The 1-10 score is mapped linearly onto 0-100 (1 is 0, 10 is 100) and
+/// averaged with `confidence`, so a confident comment that the second pass
+/// rates as mediocre loses confidence.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::pipeline::recalibrated_confidence;
+///
+/// assert_eq!(recalibrated_confidence(90.0, 10), 95.0);
+/// assert_eq!(recalibrated_confidence(90.0, 1), 45.0);
+/// ```
+pub fn recalibrated_confidence(confidence: f64, reflection_score: u8) -> f64 {
+    let factor = f64::from(reflection_score.clamp(1, 10) - 1) / 9.0 * 100.0;
+    (confidence + factor) / 2.0
+}
+
+/// Heading of the change description in review prompts.
+const INTENT_HEADING: &str =
+    "Author's description of the change (commit message or PR description). \
+     Use it to understand intent: don't report behavior it says is deliberate, \
+     but do report code that doesn't do what it says:";
+
 fn estimate_tokens(text: &str) -> usize {
     text.len() / 4
 }
@@ -825,7 +1960,14 @@ fn common_directory<'a>(paths: &'a [&'a Path]) -> Option<&'a Path> {
     Some(common)
 }
 
-fn deduplicate(comments: Vec<ReviewComment>) -> (Vec<ReviewComment>, usize) {
+/// Drop comments that repeat another comment on the same line.
+///
+/// With `exact`, only identical messages count as duplicates. Otherwise
+/// messages are compared after [`normalize_for_dedup`], and two comments
+/// whose suggestions normalize to the same text are duplicates even when
+/// their messages are phrased differently. The surviving comment keeps the
+/// higher confidence and picks up a suggestion it was missing.
+fn deduplicate(comments: Vec<ReviewComment>, exact: bool) -> (Vec<ReviewComment>, usize) {
     let before = comments.len();
     let mut seen: Vec<ReviewComment> = Vec::new();
 
@@ -834,12 +1976,18 @@ fn deduplicate(comments: Vec<ReviewComment>) -> (Vec<ReviewComment>, usize) {
         for existing in &mut seen {
             if existing.file_path == comment.file_path
                 && existing.line == comment.line
-                && existing.message == comment.message
+                && same_finding(existing, &comment, exact)
             {
                 // Keep the higher confidence one
                 if comment.confidence > existing.confidence {
                     existing.confidence = comment.confidence;
                 }
+                if existing.suggestion.is_none() {
+                    existing.suggestion.clone_from(&comment.suggestion);
+                }
+                if existing.rationale.is_none() {
+                    existing.rationale.clone_from(&comment.rationale);
+                }
                 is_dup = true;
                 break;
             }
@@ -853,40 +2001,298 @@ fn deduplicate(comments: Vec<ReviewComment>) -> (Vec<ReviewComment>, usize) {
     (seen, deduped_count)
 }
 
+fn same_finding(a: &ReviewComment, b: &ReviewComment, exact: bool) -> bool {
+    if exact {
+        return a.message == b.message;
+    }
+    if normalize_for_dedup(&a.message) == normalize_for_dedup(&b.message) {
+        return true;
+    }
+    match (&a.suggestion, &b.suggestion) {
+        (Some(x), Some(y)) => {
+            let x = normalize_for_dedup(x);
+            !x.is_empty() && x == normalize_for_dedup(y)
+        }
+        _ => false,
+    }
+}
+
+/// Lowercase, strip punctuation, and collapse whitespace.
+fn normalize_for_dedup(text: &str) -> String {
+    let stripped: String = text
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Append the issues an augmenting self-reflection pass reported as missed.
+///
+/// Additions are tagged with matching rules and deduplicated against the
+/// kept comments, so an "addition" that repeats an existing finding is
+/// dropped. Returns the merged comments and the number actually added.
+fn merge_reflection_additions(
+    kept: Vec<ReviewComment>,
+    mut additions: Vec<ReviewComment>,
+    rules: &[Rule],
+    exact: bool,
+) -> (Vec<ReviewComment>, usize) {
+    if additions.is_empty() {
+        return (kept, 0);
+    }
+    tag_rule_matches(&mut additions, rules);
+    let before = kept.len();
+    let (merged, _) = deduplicate(kept.into_iter().chain(additions).collect(), exact);
+    let added = merged.len() - before;
+    (merged, added)
+}
+
+/// Merge comments that report the same issue in a source file and its test.
+///
+/// Two comments are merged only when one file is the test counterpart of the
+/// other by naming convention (`foo.rs`/`foo_test.rs`, `foo.py`/`test_foo.py`,
+/// `foo.ts`/`foo.test.ts`, ...) and both messages mention the same
+/// backtick-quoted identifier. The source comment is kept, takes the more
+/// severe severity and higher confidence, and lists the test location.
+/// Returns the merged comments and the number of comments folded in.
+fn merge_test_counterparts(comments: Vec<ReviewComment>) -> (Vec<ReviewComment>, usize) {
+    let before = comments.len();
+    let mut merged_into: Vec<Option<usize>> = vec![None; comments.len()];
+
+    for (ti, test) in comments.iter().enumerate() {
+        let Some(subject) = test_subject(&test.file_path) else {
+            continue;
+        };
+        let test_idents = quoted_identifiers(&test.message);
+        if test_idents.is_empty() {
+            continue;
+        }
+        let source = comments.iter().enumerate().position(|(si, src)| {
+            si != ti
+                && merged_into[si].is_none()
+                && test_subject(&src.file_path).is_none()
+                && source_subject(&src.file_path).as_ref() == Some(&subject)
+                && quoted_identifiers(&src.message)
+                    .iter()
+                    .any(|id| test_idents.contains(id))
+        });
+        if let Some(si) = source {
+            merged_into[ti] = Some(si);
+        }
+    }
+
+    let mut result: Vec<ReviewComment> = Vec::with_capacity(comments.len());
+    let mut index_map: Vec<Option<usize>> = vec![None; comments.len()];
+    for (i, comment) in comments.iter().enumerate() {
+        if merged_into[i].is_none() {
+            index_map[i] = Some(result.len());
+            result.push(comment.clone());
+        }
+    }
+    for (ti, target) in merged_into.iter().enumerate() {
+        let Some(si) = target else {
+            continue;
+        };
+        let Some(ri) = index_map[*si] else {
+            continue;
+        };
+        let test = &comments[ti];
+        let merged = &mut result[ri];
+        if severity_rank(test.severity) < severity_rank(merged.severity) {
+            merged.severity = test.severity;
+        }
+        if test.confidence > merged.confidence {
+            merged.confidence = test.confidence;
+        }
+        merged.message.push_str(&format!(
+            "\n\nAlso affects `{}:{}`: {}",
+            test.file_path.display(),
+            test.line,
+            test.message
+        ));
+    }
+
+    let merged_count = before - result.len();
+    (result, merged_count)
+}
+
+/// For a test file, return `(subject stem, extension)` of the source file it tests.
+fn test_subject(path: &Path) -> Option<(String, String)> {
+    let file_name = path.file_name()?.to_str()?;
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    let subject = stem
+        .strip_suffix("_test")
+        .or_else(|| stem.strip_suffix("_tests"))
+        .or_else(|| stem.strip_suffix(".test"))
+        .or_else(|| stem.strip_suffix(".spec"))
+        .or_else(|| stem.strip_prefix("test_"))?;
+    if subject.is_empty() {
+        return None;
+    }
+    Some((subject.to_string(), ext.to_string()))
+}
+
+/// For a non-test file, return its `(stem, extension)`.
+fn source_subject(path: &Path) -> Option<(String, String)> {
+    let file_name = path.file_name()?.to_str()?;
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    Some((stem.to_string(), ext.to_string()))
+}
+
+/// Extract identifiers quoted in backticks, reduced to their last path
+/// segment (`Foo::bar()` → `bar`). Very short names are ignored.
+fn quoted_identifiers(message: &str) -> Vec<String> {
+    message
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .filter_map(|span| {
+            let span = span.trim().trim_end_matches("()");
+            let last = span.rsplit([':', '.']).next()?;
+            let is_ident = last.len() >= 3
+                && last.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !last.chars().next()?.is_ascii_digit();
+            is_ident.then(|| last.to_string())
+        })
+        .collect()
+}
+
+/// Make the paths in `diffs` repository-relative (see [`normalize_path`]),
+/// so comments, fingerprints, and patches all refer to files the same way.
+fn normalize_diff_paths(diffs: &mut [FileDiff], repo_path: Option<&Path>) {
+    for diff in diffs {
+        diff.old_path = normalize_path(&diff.old_path, repo_path);
+        diff.new_path = normalize_path(&diff.new_path, repo_path);
+        for hunk in &mut diff.hunks {
+            hunk.file_path = normalize_path(&hunk.file_path, repo_path);
+        }
+    }
+}
+
+/// Make comment paths repository-relative, matching [`normalize_diff_paths`].
+///
+/// The LLM sometimes echoes the `b/` prefix or an absolute path. The prefix
+/// is only dropped when the path without it, and not the path itself, is a
+/// file in `diffs`, so a real top-level `a` or `b` directory is kept.
+fn normalize_comment_paths(
+    comments: &mut [ReviewComment],
+    diffs: &[FileDiff],
+    repo_path: Option<&Path>,
+) {
+    let in_diff = |path: &Path| diffs.iter().any(|d| d.new_path == path);
+    for comment in comments {
+        let path = normalize_path(&comment.file_path, repo_path);
+        let unprefixed = path
+            .strip_prefix("a")
+            .or_else(|_| path.strip_prefix("b"))
+            .ok()
+            .filter(|rest| !in_diff(&path) && in_diff(rest))
+            .map(Path::to_path_buf);
+        comment.file_path = unprefixed.unwrap_or(path);
+    }
+}
+
+/// Move comments whose fingerprint is on `allowlist` out of `comments`.
+fn split_suppressed(
+    comments: Vec<ReviewComment>,
+    allowlist: &Allowlist,
+) -> (Vec<ReviewComment>, Vec<FilteredComment>) {
+    let (suppressed, kept): (Vec<_>, Vec<_>) =
+        comments.into_iter().partition(|c| allowlist.suppresses(c));
+    let suppressed = suppressed
+        .into_iter()
+        .map(|comment| FilteredComment {
+            comment,
+            reason: FilterReason::Suppressed,
+        })
+        .collect();
+    (kept, suppressed)
+}
+
+/// Split off comments on lines that `diffs` didn't add, as
+/// [`FilterReason::UnchangedLine`]. Comments on deleted files are kept,
+/// since such a file has no added lines to point at.
+fn split_unchanged_line_comments(
+    comments: Vec<ReviewComment>,
+    diffs: &[FileDiff],
+) -> (Vec<ReviewComment>, Vec<FilteredComment>) {
+    let added = AddedLines::from_diffs(diffs);
+    let deleted: Vec<&Path> = diffs
+        .iter()
+        .filter(|d| d.is_deleted_file)
+        .map(|d| d.old_path.as_path())
+        .collect();
+    let (kept, unchanged): (Vec<_>, Vec<_>) = comments.into_iter().partition(|c| {
+        deleted.contains(&c.file_path.as_path()) || added.comment_origin(c) == Origin::Introduced
+    });
+    let unchanged = unchanged
+        .into_iter()
+        .map(|comment| FilteredComment {
+            comment,
+            reason: FilterReason::UnchangedLine,
+        })
+        .collect();
+    (kept, unchanged)
+}
+
 fn filter_and_sort(
     comments: Vec<ReviewComment>,
     config: &ReviewConfig,
+    suppressions: &Allowlist,
 ) -> (Vec<ReviewComment>, Vec<FilteredComment>) {
     let mut kept: Vec<ReviewComment> = Vec::new();
     let mut filtered: Vec<FilteredComment> = Vec::new();
 
     for comment in comments {
+        if suppressions.suppresses(&comment) {
+            filtered.push(FilteredComment {
+                comment,
+                reason: FilterReason::Suppressed,
+            });
+            continue;
+        }
         if comment.confidence < config.min_confidence {
             filtered.push(FilteredComment {
                 comment,
-                reason: "below confidence threshold".into(),
+                reason: FilterReason::BelowConfidence,
             });
             continue;
         }
         if !config.severity_filter.contains(&comment.severity) {
-            let sev = format!("{:?}", comment.severity).to_lowercase();
             filtered.push(FilteredComment {
                 comment,
-                reason: format!("{sev}-level excluded"),
+                reason: FilterReason::SeverityExcluded,
             });
             continue;
         }
         kept.push(comment);
     }
 
-    kept.sort_by_key(|c| severity_rank(c.severity));
+    sort_comments(&mut kept);
+
+    // Cap each file before the global cap, so the most severe comments of
+    // every file compete for the remaining slots
+    if let Some(per_file) = config.max_comments_per_file {
+        let mut counts: HashMap<std::path::PathBuf, usize> = HashMap::new();
+        let (within, excess): (Vec<_>, Vec<_>) = kept.into_iter().partition(|comment| {
+            let count = counts.entry(comment.file_path.clone()).or_default();
+            *count += 1;
+            *count <= per_file
+        });
+        kept = within;
+        filtered.extend(excess.into_iter().map(|comment| FilteredComment {
+            comment,
+            reason: FilterReason::MaxCommentsPerFile,
+        }));
+    }
 
     if kept.len() > config.max_comments {
         let truncated = kept.split_off(config.max_comments);
         for comment in truncated {
             filtered.push(FilteredComment {
                 comment,
-                reason: "exceeded max comment limit".into(),
+                reason: FilterReason::MaxComments,
             });
         }
     }
@@ -894,6 +2300,19 @@ fn filter_and_sort(
     (kept, filtered)
 }
 
+/// Sort comments most severe first, then by path, line, and message.
+///
+/// A total order, so identical inputs always yield identical output.
+fn sort_comments(comments: &mut [ReviewComment]) {
+    comments.sort_by(|a, b| {
+        severity_rank(a.severity)
+            .cmp(&severity_rank(b.severity))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+}
+
 fn severity_rank(s: Severity) -> u8 {
     match s {
         Severity::Bug => 0,
@@ -903,28 +2322,82 @@ fn severity_rank(s: Severity) -> u8 {
     }
 }
 
-/// Tag comments that reference a custom rule by name.
+/// Raise comments on paths matching a `[[review.severity_overrides]]` glob
+/// to at least that override's `min_severity`.
 ///
-/// Checks if any rule name appears in the comment's message
-/// and sets the `rule` field on matching comments.
-fn tag_rule_matches(comments: &mut [ReviewComment], rules: &[Rule]) {
+/// Severities are only ever raised, never lowered. When several overrides
+/// match, the strictest floor wins. Invalid globs are ignored.
+fn apply_severity_overrides(comments: &mut [ReviewComment], overrides: &[SeverityOverride]) {
+    let patterns: Vec<(glob::Pattern, Severity)> = overrides
+        .iter()
+        .filter_map(|o| {
+            glob::Pattern::new(&o.path)
+                .ok()
+                .map(|pattern| (pattern, o.min_severity))
+        })
+        .collect();
+    if patterns.is_empty() {
+        return;
+    }
+
     for comment in comments.iter_mut() {
-        for rule in rules {
-            if comment.message.contains(&rule.name) {
-                comment.rule = Some(rule.name.clone());
-                break;
+        for (pattern, floor) in &patterns {
+            if pattern.matches_path(&comment.file_path)
+                && severity_rank(*floor) < severity_rank(comment.severity)
+            {
+                comment.severity = *floor;
             }
         }
     }
 }
 
+/// Tag comments with the custom rule they report.
+///
+/// The `rule` field echoed by the LLM wins when it names a configured
+/// rule (case-insensitively). Otherwise the comment is tagged with the
+/// first rule whose name appears in the message as a whole word, so
+/// `no-unwrap` does not match `no-unwrap-or-default`, and untagged if none
+/// does.
+fn tag_rule_matches(comments: &mut [ReviewComment], rules: &[Rule]) {
+    for comment in comments.iter_mut() {
+        let echoed = comment
+            .rule
+            .as_deref()
+            .and_then(|name| rules.iter().find(|r| r.name.eq_ignore_ascii_case(name)));
+        let matched = echoed.or_else(|| {
+            rules
+                .iter()
+                .find(|r| mentions_rule(&comment.message, &r.name))
+        });
+        comment.rule = matched.map(|r| r.name.clone());
+    }
+}
+
+/// Whether `name` appears in `message` delimited by non-identifier
+/// characters (anything but alphanumerics, `-`, and `_`).
+fn mentions_rule(message: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    message.match_indices(name).any(|(start, _)| {
+        let before = message[..start].chars().next_back();
+        let after = message[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
 /// Build related code context from the search index for the given diffs.
 ///
 /// For each file in the diff, performs a keyword search for its entity names.
 /// Returns the top 3 results formatted for inclusion in the review prompt.
-fn build_related_code_context(diffs: &[FileDiff], index_path: &std::path::Path) -> Option<String> {
+fn build_related_code_context(
+    diffs: &[FileDiff],
+    index_path: &std::path::Path,
+    stop_words: &[String],
+) -> Option<String> {
     let index = match argus_codelens::store::CodeIndex::open(index_path) {
-        Ok(idx) => idx,
+        Ok(idx) => idx.with_stop_words(stop_words.to_vec()),
         Err(_) => return None,
     };
 
@@ -1017,6 +2490,212 @@ fn build_hotspot_file_context<D: std::borrow::Borrow<FileDiff>>(
     }
 }
 
+/// Build the blast radius prompt section for the changed symbols in `diffs`.
+///
+/// Lists how many symbols reference each changed symbol defined in these
+/// files, so the LLM weighs a change by its real impact on callers.
+fn build_blast_radius_context<D: std::borrow::Borrow<FileDiff>>(
+    diffs: &[D],
+    radius: &[argus_repomap::graph::BlastRadius],
+) -> Option<String> {
+    let lines: Vec<String> = radius
+        .iter()
+        .filter(|r| diffs.iter().any(|d| d.borrow().new_path == r.file))
+        .take(BLAST_RADIUS_MAX_SYMBOLS)
+        .map(|r| {
+            let count = r.dependents.len();
+            let noun = if count == 1 { "symbol" } else { "symbols" };
+            let mut names = r.dependents[..count.min(BLAST_RADIUS_MAX_NAMES)].join(", ");
+            if count > BLAST_RADIUS_MAX_NAMES {
+                names.push_str(", ...");
+            }
+            format!(
+                "- `{}` ({}) is referenced by {count} {noun} ({names}); verify callers.",
+                r.symbol,
+                r.file.display()
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(format!("## Blast Radius\n\n{}\n", lines.join("\n")))
+    }
+}
+
+/// Build the test presence prompt section for the files in `diffs`.
+///
+/// Lists the changed functions of these files that no test mentions, so
+/// the LLM can weigh untested changes more heavily.
+fn build_test_presence_context<D: std::borrow::Borrow<FileDiff>>(
+    diffs: &[D],
+    untested: &[crate::testpresence::UntestedFunction],
+) -> Option<String> {
+    let lines: Vec<String> = untested
+        .iter()
+        .filter(|f| diffs.iter().any(|d| d.borrow().new_path == f.file))
+        .take(TEST_PRESENCE_MAX_FUNCTIONS)
+        .map(|f| {
+            format!(
+                "- function `{}` ({}:{}) has no detected tests",
+                f.name,
+                f.file.display(),
+                f.line
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "## Test Presence\n\nNo test file mentions these changed functions (a name-based \
+             heuristic). Weigh risky changes to them more heavily.\n\n{}\n",
+            lines.join("\n")
+        ))
+    }
+}
+
+/// Build a block of unchanged source lines around each hunk.
+///
+/// Reads each changed file from `root` and includes `context_lines` lines
+/// before and after every hunk (overlapping windows are merged), with line
+/// numbers from the new version of the file. New and deleted files are
+/// skipped: the diff already shows all there is.
+///
+/// With a `function_budget` (in estimated tokens), each hunk inside a
+/// function or method is widened to the whole function, found with
+/// tree-sitter. Once the expanded functions would exceed the budget, later
+/// hunks fall back to `context_lines`. Returns `None` when there is no
+/// context to show or nothing could be read.
+fn build_surrounding_context<D: std::borrow::Borrow<FileDiff>>(
+    diffs: &[D],
+    root: &Path,
+    context_lines: usize,
+    function_budget: Option<usize>,
+) -> Option<String> {
+    if context_lines == 0 && function_budget.is_none() {
+        return None;
+    }
+    let mut budget_left = function_budget.unwrap_or(0);
+
+    let mut sections = Vec::new();
+    for d in diffs {
+        let diff = d.borrow();
+        if diff.is_new_file || diff.is_deleted_file || diff.hunks.is_empty() {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(root.join(&diff.new_path)) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let functions = if function_budget.is_some() {
+            function_ranges(&diff.new_path, &content)
+        } else {
+            Vec::new()
+        };
+
+        // 1-based inclusive windows, merged below when they overlap or touch
+        let mut windows: Vec<(usize, usize)> = Vec::new();
+        let mut hunks: Vec<_> = diff.hunks.iter().collect();
+        hunks.sort_by_key(|h| h.new_start);
+        for hunk in hunks {
+            let start = (hunk.new_start as usize).max(1);
+            let end = start + (hunk.new_lines as usize).saturating_sub(1);
+            let mut from = start.saturating_sub(context_lines).max(1);
+            let mut to = (end + context_lines).min(lines.len());
+            let mut expanded = false;
+
+            // The functions the hunk touches, if they still fit the budget
+            let enclosing = functions
+                .iter()
+                .filter(|(first, last)| *first <= end && start <= *last)
+                .fold(None, |acc: Option<(usize, usize)>, &(first, last)| {
+                    Some(acc.map_or((first, last), |(a, b)| (a.min(first), b.max(last))))
+                });
+            if let Some((first, last)) = enclosing {
+                let (first, last) = (first.min(from), last.max(to).min(lines.len()));
+                // A function already shown for an earlier hunk costs nothing
+                let shown = windows.iter().any(|&(a, b)| a <= first && last <= b);
+                let cost = if shown {
+                    0
+                } else {
+                    estimate_tokens(&lines[first - 1..last].join("\n"))
+                };
+                if cost <= budget_left {
+                    budget_left -= cost;
+                    (from, to) = (first, last);
+                    expanded = true;
+                }
+            }
+            if from > to || (context_lines == 0 && !expanded) {
+                continue;
+            }
+            windows.push((from, to));
+        }
+
+        // An expanded function can start before an earlier hunk's window
+        windows.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (from, to) in windows {
+            match merged.last_mut() {
+                Some(last) if from <= last.1 + 1 => last.1 = last.1.max(to),
+                _ => merged.push((from, to)),
+            }
+        }
+
+        for (from, to) in merged {
+            let width = to.to_string().len();
+            let body: Vec<String> = (from..=to)
+                .map(|n| format!("{n:>width$} | {}", lines[n - 1]))
+                .collect();
+            sections.push(format!(
+                "### {} (lines {from}-{to})\n```\n{}\n```",
+                diff.new_path.display(),
+                body.join("\n")
+            ));
+        }
+    }
+
+    if sections.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "## Surrounding Code\n\n\
+             Current file contents around each hunk, for reference. Only comment on \
+             lines changed in the diff.\n\n{}\n",
+            sections.join("\n\n")
+        ))
+    }
+}
+
+/// 1-based inclusive line ranges of the functions and methods in a file.
+pub(crate) fn function_ranges(path: &Path, content: &str) -> Vec<(usize, usize)> {
+    let language = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(argus_repomap::walker::Language::from_extension)
+        .unwrap_or(argus_repomap::walker::Language::Unknown);
+    argus_codelens::chunker::chunk_file(path, content, language)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|chunk| matches!(chunk.entity_type.as_str(), "function" | "method"))
+        .map(|chunk| (chunk.start_line as usize, chunk.end_line as usize))
+        .collect()
+}
+
+/// Concatenate two optional prompt context blocks.
+fn join_context(a: Option<String>, b: Option<String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(format!("{a}\n{b}")),
+        (a, b) => a.or(b),
+    }
+}
+
 /// Structured insights from git history analysis.
 ///
 /// Contains both the text context for LLM prompts and the hotspot scores
@@ -1032,9 +2711,15 @@ struct HistoryInsights {
 ///
 /// Mines recent history and identifies hotspots, coupling, and knowledge silos
 /// for the changed files. Returns both text context and structured hotspot data.
+/// Commits cached in `.argus/history-cache.db` are reused; without a usable
+/// cache the history is mined directly.
 fn build_history_insights(diffs: &[FileDiff], repo_path: &Path) -> Option<HistoryInsights> {
     let options = argus_gitpulse::mining::MiningOptions::default();
-    let commits = match argus_gitpulse::mining::mine_history(repo_path, &options) {
+    let cache_path = repo_path.join(".argus/history-cache.db");
+    let mined = argus_gitpulse::cache::mine_history_cached(repo_path, &options, &cache_path)
+        .map(|history| history.commits)
+        .or_else(|_| argus_gitpulse::mining::mine_history(repo_path, &options));
+    let commits = match mined {
         Ok(c) if !c.is_empty() => c,
         _ => return None,
     };
@@ -1108,88 +2793,193 @@ fn build_history_insights(diffs: &[FileDiff], repo_path: &Path) -> Option<Histor
     })
 }
 
+/// How rendered review output arranges its comments.
+///
+/// Only affects text and markdown rendering; JSON and SARIF always list
+/// `comments` in their stored order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Most severe findings first (the default).
+    #[default]
+    Severity,
+    /// One section per file in path order, comments sorted by line.
+    File,
+}
+
 impl fmt::Display for ReviewResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Review Results")?;
-        writeln!(f, "==============")?;
-        writeln!(
-            f,
-            "Model: {} | Files: {} (skipped: {}) | Hunks: {} | Comments: {} (filtered: {}, deduped: {}, reflected: {}) | LLM calls: {} (retries: {})\n",
-            self.stats.model_used,
-            self.stats.files_reviewed,
-            self.stats.files_skipped,
-            self.stats.total_hunks,
-            self.comments.len(),
-            self.stats.comments_filtered,
-            self.stats.comments_deduplicated,
-            self.stats.comments_reflected_out,
-            self.stats.llm_calls,
-            self.stats.llm_retries,
-        )?;
-
-        if self.stats.hotspot_files > 0 {
-            writeln!(
-                f,
-                "🔥 {} hotspot file{} detected — reviewed with extra scrutiny\n",
-                self.stats.hotspot_files,
-                if self.stats.hotspot_files == 1 {
-                    ""
-                } else {
-                    "s"
-                },
-            )?;
-        }
-
-        if let Some(summary) = &self.summary {
-            writeln!(f, "Summary: {summary}\n")?;
-        }
+        self.write_text(f, GroupBy::Severity)
+    }
+}
 
-        if !self.stats.skipped_files.is_empty() {
-            writeln!(f, "Skipped files:")?;
+impl ReviewResult {
+    /// Combine the results of several reviews, such as one per patch of a
+    /// series, into one.
+    ///
+    /// Comments are re-sorted by severity, path, line, and message; summaries
+    /// are joined by blank lines, and counters are summed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_review::pipeline::{ReviewResult, ReviewStats};
+    ///
+    /// let part = |llm_calls| ReviewResult {
+    ///     comments: vec![],
+    ///     filtered_comments: vec![],
+    ///     summary: None,
+    ///     stats: ReviewStats { llm_calls, ..ReviewStats::default() },
+    /// };
+    /// let merged = ReviewResult::merge(vec![part(2), part(3)]);
+    /// assert_eq!(merged.stats.llm_calls, 5);
+    /// ```
+    pub fn merge(results: Vec<ReviewResult>) -> ReviewResult {
+        let mut merged = ReviewResult {
+            comments: Vec::new(),
+            filtered_comments: Vec::new(),
+            summary: None,
+            stats: ReviewStats::default(),
+        };
+        let mut summaries = Vec::new();
+        for result in results {
+            merged.comments.extend(result.comments);
+            merged.filtered_comments.extend(result.filtered_comments);
+            summaries.extend(result.summary);
+
+            let (total, part) = (&mut merged.stats, result.stats);
+            total.files_reviewed += part.files_reviewed;
+            total.files_skipped += part.files_skipped;
+            total.total_hunks += part.total_hunks;
+            total.comments_generated += part.comments_generated;
+            total.comments_filtered += part.comments_filtered;
+            total.comments_deduplicated += part.comments_deduplicated;
+            total.comments_reflected_out += part.comments_reflected_out;
+            total.skipped_files.extend(part.skipped_files);
+            if !part.model_used.is_empty() {
+                total.model_used = part.model_used;
+            }
+            total.llm_calls += part.llm_calls;
+            total.llm_retries += part.llm_retries;
+            total.file_groups.extend(part.file_groups);
+            total.hotspot_files += part.hotspot_files;
+            total.moved_blocks.extend(part.moved_blocks);
+            total.timings.add(part.timings);
+        }
+        sort_comments(&mut merged.comments);
+        merged.summary = (!summaries.is_empty()).then(|| summaries.join("\n\n"));
+        merged
+    }
+
+    /// Render the review result as plain text, arranged by `group_by`.
+    ///
+    /// `GroupBy::Severity` matches the [`Display`](fmt::Display) output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_review::pipeline::{GroupBy, ReviewResult, ReviewStats, ReviewTimings};
+    ///
+    /// let result = ReviewResult {
+    ///     comments: vec![],
+    ///     filtered_comments: vec![],
+    ///     summary: None,
+    ///     stats: ReviewStats {
+    ///         files_reviewed: 0,
+    ///         files_skipped: 0,
+    ///         total_hunks: 0,
+    ///         comments_generated: 0,
+    ///         comments_filtered: 0,
+    ///         comments_deduplicated: 0,
+    ///         comments_reflected_out: 0,
+    ///         skipped_files: vec![],
+    ///         model_used: "gpt-4o".into(),
+    ///         llm_calls: 0,
+    ///         llm_retries: 0,
+    ///         file_groups: vec![],
+    ///         hotspot_files: 0,
+    ///         moved_blocks: vec![],
+    ///         timings: ReviewTimings::default(),
+    ///     },
+    /// };
+    /// assert_eq!(result.to_text(GroupBy::Severity), result.to_string());
+    /// ```
+    pub fn to_text(&self, group_by: GroupBy) -> String {
+        let mut out = String::new();
+        let _ = self.write_text(&mut out, group_by);
+        out
+    }
+
+    fn write_text(&self, f: &mut dyn fmt::Write, group_by: GroupBy) -> fmt::Result {
+        writeln!(f, "Review Results")?;
+        writeln!(f, "==============")?;
+        writeln!(
+            f,
+            "Model: {} | Files: {} (skipped: {}) | Hunks: {} | Comments: {} (filtered: {}, deduped: {}, reflected: {}) | LLM calls: {} (retries: {})\n",
+            self.stats.model_used,
+            self.stats.files_reviewed,
+            self.stats.files_skipped,
+            self.stats.total_hunks,
+            self.comments.len(),
+            self.stats.comments_filtered,
+            self.stats.comments_deduplicated,
+            self.stats.comments_reflected_out,
+            self.stats.llm_calls,
+            self.stats.llm_retries,
+        )?;
+
+        if self.stats.hotspot_files > 0 {
+            writeln!(
+                f,
+                "🔥 {} hotspot file{} detected — reviewed with extra scrutiny\n",
+                self.stats.hotspot_files,
+                if self.stats.hotspot_files == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+            )?;
+        }
+
+        if let Some(summary) = &self.summary {
+            writeln!(f, "Summary: {summary}\n")?;
+        }
+
+        if !self.stats.skipped_files.is_empty() {
+            writeln!(f, "Skipped files:")?;
             for sf in &self.stats.skipped_files {
                 writeln!(f, "  {} ({})", sf.path.display(), sf.reason)?;
             }
             writeln!(f)?;
         }
 
+        if !self.stats.moved_blocks.is_empty() {
+            writeln!(f, "Moved code (not reviewed):")?;
+            for block in &self.stats.moved_blocks {
+                writeln!(f, "  {block}")?;
+            }
+            writeln!(f)?;
+        }
+
         if self.comments.is_empty() {
             writeln!(f, "No issues found.")?;
-        } else {
-            for c in &self.comments {
-                let label = match c.severity {
-                    Severity::Bug => "BUG",
-                    Severity::Warning => "WARNING",
-                    Severity::Suggestion => "SUGGESTION",
-                    Severity::Info => "INFO",
-                };
-                if let Some(rule) = &c.rule {
-                    writeln!(
-                        f,
-                        "[{label}] {}:{} (confidence: {:.0}%, rule: {rule})",
-                        c.file_path.display(),
-                        c.line,
-                        c.confidence,
-                    )?;
-                } else {
-                    writeln!(
-                        f,
-                        "[{label}] {}:{} (confidence: {:.0}%)",
-                        c.file_path.display(),
-                        c.line,
-                        c.confidence,
-                    )?;
-                }
-                writeln!(f, "  {}", c.message)?;
-                if let Some(s) = &c.suggestion {
-                    writeln!(f, "  Suggestion: {s}")?;
+            return Ok(());
+        }
+
+        match group_by {
+            GroupBy::Severity => {
+                for c in &self.comments {
+                    write_text_comment(f, c)?;
                 }
-                if let Some(patch) = &c.patch {
-                    writeln!(f, "  Patch:")?;
-                    for line in patch.lines() {
-                        writeln!(f, "    {line}")?;
+            }
+            GroupBy::File => {
+                for (path, comments) in comments_by_path(&self.comments) {
+                    let heading = format!("{} ({})", path.display(), comments.len());
+                    writeln!(f, "{heading}")?;
+                    writeln!(f, "{}", "-".repeat(heading.chars().count()))?;
+                    for c in comments {
+                        write_text_comment(f, c)?;
                     }
                 }
-                writeln!(f)?;
             }
         }
 
@@ -1197,13 +2987,79 @@ impl fmt::Display for ReviewResult {
     }
 }
 
+/// Cut `message` to `max_chars` characters and note how long it was.
+fn truncate_message(message: &mut String, max_chars: usize) {
+    let total = message.chars().count();
+    if total <= max_chars {
+        return;
+    }
+    let cut = message
+        .char_indices()
+        .nth(max_chars)
+        .map_or(message.len(), |(i, _)| i);
+    message.truncate(cut);
+    message.truncate(message.trim_end().len());
+    message.push_str(&format!(
+        "… (truncated from {total} characters; JSON output has the full message)"
+    ));
+}
+
+fn write_text_comment(f: &mut dyn fmt::Write, c: &ReviewComment) -> fmt::Result {
+    let label = match c.severity {
+        Severity::Bug => "BUG",
+        Severity::Warning => "WARNING",
+        Severity::Suggestion => "SUGGESTION",
+        Severity::Info => "INFO",
+    };
+    if let Some(rule) = &c.rule {
+        writeln!(
+            f,
+            "[{label}] {}:{} (confidence: {:.0}%, rule: {rule})",
+            c.file_path.display(),
+            c.line,
+            c.confidence,
+        )?;
+    } else {
+        writeln!(
+            f,
+            "[{label}] {}:{} (confidence: {:.0}%)",
+            c.file_path.display(),
+            c.line,
+            c.confidence,
+        )?;
+    }
+    writeln!(f, "  {}", c.message)?;
+    if let Some(rationale) = &c.rationale {
+        writeln!(f, "  Why it matters: {rationale}")?;
+    }
+    if let Some(s) = &c.suggestion {
+        writeln!(f, "  Suggestion: {s}")?;
+    }
+    if let Some(author) = &c.last_author {
+        writeln!(f, "  Last changed by: {author}")?;
+    }
+    if let Some(origin) = c.origin {
+        writeln!(f, "  Origin: {origin}")?;
+    }
+    if let Some(fingerprint) = &c.fingerprint {
+        writeln!(f, "  Fingerprint: {fingerprint}")?;
+    }
+    if let Some(patch) = &c.patch {
+        writeln!(f, "  Patch:")?;
+        for line in patch.lines() {
+            writeln!(f, "    {line}")?;
+        }
+    }
+    writeln!(f)
+}
+
 impl ReviewResult {
     /// Render the review result as markdown.
     ///
     /// # Examples
     ///
     /// ```
-    /// use argus_review::pipeline::{ReviewResult, ReviewStats};
+    /// use argus_review::pipeline::{ReviewResult, ReviewStats, ReviewTimings};
     ///
     /// let result = ReviewResult {
     ///     comments: vec![],
@@ -1223,12 +3079,50 @@ impl ReviewResult {
     ///         llm_retries: 0,
     ///         file_groups: vec![],
     ///         hotspot_files: 0,
+    ///         moved_blocks: vec![],
+    ///         timings: ReviewTimings::default(),
     ///     },
     /// };
     /// let md = result.to_markdown();
     /// assert!(md.contains("# Review Results"));
     /// ```
     pub fn to_markdown(&self) -> String {
+        self.to_markdown_grouped(GroupBy::Severity)
+    }
+
+    /// Render the review result as markdown, arranged by `group_by`.
+    ///
+    /// Both layouts have one section per file. `GroupBy::Severity` puts the
+    /// files with the most severe findings first; `GroupBy::File` lists files
+    /// by path with each file's comments in line order.
+    pub fn to_markdown_grouped(&self, group_by: GroupBy) -> String {
+        self.render_markdown(group_by, false)
+    }
+
+    /// Render the review result as markdown with each file's section folded
+    /// into a `<details>` block, for posting as one PR comment.
+    ///
+    /// Same content as [`to_markdown_grouped`](Self::to_markdown_grouped);
+    /// the contents list stays expanded and each fold's summary repeats the
+    /// file's severity counts.
+    pub fn to_markdown_collapsible(&self, group_by: GroupBy) -> String {
+        self.render_markdown(group_by, true)
+    }
+
+    /// Copy of this result with comment messages longer than `max_chars`
+    /// characters cut short, for text and markdown output and PR comments.
+    ///
+    /// Each cut message ends with an ellipsis and a note giving its full
+    /// length. JSON output renders the result as is, so nothing is lost.
+    pub fn with_truncated_messages(&self, max_chars: usize) -> ReviewResult {
+        let mut result = self.clone();
+        for comment in &mut result.comments {
+            truncate_message(&mut comment.message, max_chars);
+        }
+        result
+    }
+
+    fn render_markdown(&self, group_by: GroupBy, collapsible: bool) -> String {
         let mut out = String::new();
         out.push_str("# Review Results\n\n");
         out.push_str(&format!(
@@ -1251,14 +3145,54 @@ impl ReviewResult {
 
         if self.comments.is_empty() {
             out.push_str("No issues found.\n");
-        } else {
-            for c in &self.comments {
-                let emoji = match c.severity {
-                    Severity::Bug => "\u{1f41b}",
-                    Severity::Warning => "\u{26a0}\u{fe0f}",
-                    Severity::Suggestion => "\u{1f4a1}",
-                    Severity::Info => "\u{2139}\u{fe0f}",
-                };
+            return out;
+        }
+
+        let files = match group_by {
+            GroupBy::Severity => group_comments_by_file(&self.comments),
+            GroupBy::File => comments_by_path(&self.comments),
+        };
+        let mut anchors = AnchorSlugger::default();
+        let anchors: Vec<String> = files
+            .iter()
+            .map(|(path, _)| anchors.slug(&path.display().to_string()))
+            .collect();
+
+        let counts: Vec<String> = files
+            .iter()
+            .map(|(_, comments)| {
+                let mut counts: Vec<String> = Vec::new();
+                for severity in [
+                    Severity::Bug,
+                    Severity::Warning,
+                    Severity::Suggestion,
+                    Severity::Info,
+                ] {
+                    let n = comments.iter().filter(|c| c.severity == severity).count();
+                    if n > 0 {
+                        counts.push(format!("{} {n}", severity_emoji(severity)));
+                    }
+                }
+                counts.join(", ")
+            })
+            .collect();
+
+        out.push_str("## Contents\n\n");
+        for (((path, _), anchor), counts) in files.iter().zip(&anchors).zip(&counts) {
+            out.push_str(&format!("- [`{}`](#{anchor}) — {counts}\n", path.display()));
+        }
+        out.push('\n');
+
+        for ((path, comments), counts) in files.iter().zip(&counts) {
+            if collapsible {
+                out.push_str(&format!(
+                    "<details>\n<summary><code>{}</code> — {counts}</summary>\n\n",
+                    path.display()
+                ));
+            }
+            out.push_str(&format!("### `{}`\n\n", path.display()));
+            for c in comments {
+                let emoji = severity_emoji(c.severity);
                 let label = match c.severity {
                     Severity::Bug => "Bug",
                     Severity::Warning => "Warning",
@@ -1267,32 +3201,110 @@ impl ReviewResult {
                 };
                 if let Some(rule) = &c.rule {
                     out.push_str(&format!(
-                        "## {emoji} {label} — `{}:{}` ({:.0}%, rule: {rule})\n\n",
+                        "#### {emoji} {label} — `{}:{}` ({:.0}%, rule: {rule})\n\n",
                         c.file_path.display(),
                         c.line,
                         c.confidence,
                     ));
                 } else {
                     out.push_str(&format!(
-                        "## {emoji} {label} — `{}:{}` ({:.0}%)\n\n",
+                        "#### {emoji} {label} — `{}:{}` ({:.0}%)\n\n",
                         c.file_path.display(),
                         c.line,
                         c.confidence,
                     ));
                 }
                 out.push_str(&format!("{}\n\n", c.message));
+                if let Some(rationale) = &c.rationale {
+                    out.push_str(&format!("> **Why it matters:** {rationale}\n\n"));
+                }
                 if let Some(s) = &c.suggestion {
                     out.push_str(&format!("> **Suggestion:** {s}\n\n"));
                 }
+                if let Some(author) = &c.last_author {
+                    out.push_str(&format!("_Last changed by {author}_\n\n"));
+                }
                 if let Some(patch) = &c.patch {
                     out.push_str(&format!("```\n{patch}\n```\n\n"));
                 }
             }
+            if collapsible {
+                out.push_str("</details>\n\n");
+            }
         }
         out
     }
 }
 
+fn severity_emoji(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "\u{1f41b}",
+        Severity::Warning => "\u{26a0}\u{fe0f}",
+        Severity::Suggestion => "\u{1f4a1}",
+        Severity::Info => "\u{2139}\u{fe0f}",
+    }
+}
+
+/// Group comments by file, ordering files by their most severe comment
+/// (then by path). Comments keep their relative order within a file.
+fn group_comments_by_file(comments: &[ReviewComment]) -> Vec<(&Path, Vec<&ReviewComment>)> {
+    let mut by_file: BTreeMap<&Path, Vec<&ReviewComment>> = BTreeMap::new();
+    for c in comments {
+        by_file.entry(c.file_path.as_path()).or_default().push(c);
+    }
+    let mut files: Vec<(&Path, Vec<&ReviewComment>)> = by_file.into_iter().collect();
+    // Stable sort keeps the BTreeMap's path order among equal severities
+    files.sort_by_key(|(_, cs)| {
+        cs.iter()
+            .map(|c| severity_rank(c.severity))
+            .min()
+            .unwrap_or(u8::MAX)
+    });
+    files
+}
+
+/// Group comments by file in path order, sorting each file's comments by
+/// line (ties keep their severity order).
+fn comments_by_path(comments: &[ReviewComment]) -> Vec<(&Path, Vec<&ReviewComment>)> {
+    let mut by_file: BTreeMap<&Path, Vec<&ReviewComment>> = BTreeMap::new();
+    for c in comments {
+        by_file.entry(c.file_path.as_path()).or_default().push(c);
+    }
+    for cs in by_file.values_mut() {
+        cs.sort_by_key(|c| c.line);
+    }
+    by_file.into_iter().collect()
+}
+
+/// Generates GitHub-compatible heading anchors, de-duplicating repeats
+/// with `-1`, `-2`, ... suffixes the same way GitHub does.
+#[derive(Default)]
+struct AnchorSlugger {
+    seen: HashMap<String, usize>,
+}
+
+impl AnchorSlugger {
+    fn slug(&mut self, heading: &str) -> String {
+        let base: String = heading
+            .to_lowercase()
+            .chars()
+            .filter_map(|ch| match ch {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
+            .collect();
+        let count = self.seen.entry(base.clone()).or_insert(0);
+        let slug = if *count == 0 {
+            base
+        } else {
+            format!("{base}-{count}")
+        };
+        *count += 1;
+        slug
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1307,8 +3319,7 @@ mod tests {
                 message: "info comment".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("b.rs"),
@@ -1317,8 +3328,7 @@ mod tests {
                 message: "real bug".into(),
                 confidence: 98.0,
                 suggestion: Some("fix it".into()),
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("c.rs"),
@@ -1327,8 +3337,7 @@ mod tests {
                 message: "potential issue".into(),
                 confidence: 85.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("d.rs"),
@@ -1337,8 +3346,7 @@ mod tests {
                 message: "low confidence bug".into(),
                 confidence: 50.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ]
     }
@@ -1351,11 +3359,13 @@ mod tests {
             max_comments: 10,
             ..ReviewConfig::default()
         };
-        let (kept, filtered) = filter_and_sort(make_comments(), &config);
+        let (kept, filtered) = filter_and_sort(make_comments(), &config, &Allowlist::default());
         // c.rs (85%) and d.rs (50%) should be removed
         assert_eq!(kept.len(), 2);
         assert_eq!(filtered.len(), 2);
-        assert!(filtered.iter().all(|f| f.reason.contains("confidence")));
+        assert!(filtered
+            .iter()
+            .all(|f| f.reason == FilterReason::BelowConfidence));
     }
 
     #[test]
@@ -1366,12 +3376,32 @@ mod tests {
             max_comments: 10,
             ..ReviewConfig::default()
         };
-        let (kept, filtered) = filter_and_sort(make_comments(), &config);
+        let (kept, filtered) = filter_and_sort(make_comments(), &config, &Allowlist::default());
         // Info comment should be removed
         for c in &kept {
             assert!(c.severity == Severity::Bug || c.severity == Severity::Warning);
         }
-        assert!(filtered.iter().any(|f| f.reason.contains("excluded")));
+        assert!(filtered
+            .iter()
+            .any(|f| f.reason == FilterReason::SeverityExcluded
+                && f.reason_text() == "severity info excluded"));
+    }
+
+    #[test]
+    fn severity_override_raises_but_never_lowers() {
+        let overrides = vec![SeverityOverride {
+            path: "payments/**".into(),
+            min_severity: Severity::Warning,
+        }];
+        let mut comments = vec![
+            comment_at("payments/charge.rs", 1, Severity::Suggestion, "logic error"),
+            comment_at("payments/refund.rs", 2, Severity::Bug, "real bug"),
+            comment_at("src/lib.rs", 3, Severity::Suggestion, "elsewhere"),
+        ];
+        apply_severity_overrides(&mut comments, &overrides);
+        assert_eq!(comments[0].severity, Severity::Warning);
+        assert_eq!(comments[1].severity, Severity::Bug);
+        assert_eq!(comments[2].severity, Severity::Suggestion);
     }
 
     #[test]
@@ -1387,10 +3417,78 @@ mod tests {
             max_comments: 10,
             ..ReviewConfig::default()
         };
-        let (kept, _) = filter_and_sort(make_comments(), &config);
-        assert!(kept.len() >= 2);
-        // Bugs should come before warnings/info
-        assert_eq!(kept[0].severity, Severity::Bug);
+        let mut comments = make_comments();
+        comments.extend([
+            comment_at("b.rs", 10, Severity::Bug, "another bug"),
+            comment_at("a.rs", 99, Severity::Bug, "bug in a"),
+            comment_at("c.rs", 5, Severity::Warning, "earlier warning"),
+        ]);
+
+        let order = |kept: &[ReviewComment]| -> Vec<(Severity, String, u32, String)> {
+            kept.iter()
+                .map(|c| {
+                    (
+                        c.severity,
+                        c.file_path.display().to_string(),
+                        c.line,
+                        c.message.clone(),
+                    )
+                })
+                .collect()
+        };
+
+        let (kept, _) = filter_and_sort(comments.clone(), &config, &Allowlist::default());
+        // Severity, then file path, then line, then message
+        let expected = vec![
+            (
+                Severity::Bug,
+                "a.rs".to_string(),
+                99,
+                "bug in a".to_string(),
+            ),
+            (
+                Severity::Bug,
+                "b.rs".to_string(),
+                10,
+                "another bug".to_string(),
+            ),
+            (
+                Severity::Bug,
+                "b.rs".to_string(),
+                10,
+                "real bug".to_string(),
+            ),
+            (
+                Severity::Bug,
+                "d.rs".to_string(),
+                30,
+                "low confidence bug".to_string(),
+            ),
+            (
+                Severity::Warning,
+                "c.rs".to_string(),
+                5,
+                "earlier warning".to_string(),
+            ),
+            (
+                Severity::Warning,
+                "c.rs".to_string(),
+                20,
+                "potential issue".to_string(),
+            ),
+            (
+                Severity::Info,
+                "a.rs".to_string(),
+                1,
+                "info comment".to_string(),
+            ),
+        ];
+        assert_eq!(order(&kept), expected);
+
+        // Input order does not affect output order
+        comments.reverse();
+        let (reversed, _) = filter_and_sort(comments, &config, &Allowlist::default());
+        assert_eq!(order(&reversed), expected);
     }
 
     #[test]
@@ -1406,11 +3504,68 @@ mod tests {
             max_comments: 2,
             ..ReviewConfig::default()
         };
-        let (kept, filtered) = filter_and_sort(make_comments(), &config);
+        let (kept, filtered) = filter_and_sort(make_comments(), &config, &Allowlist::default());
         assert_eq!(kept.len(), 2);
         assert!(filtered
             .iter()
-            .any(|f| f.reason.contains("max comment limit")));
+            .any(|f| f.reason == FilterReason::MaxComments));
+    }
+
+    #[test]
+    fn per_file_cap_applies_before_the_global_cap() {
+        let config = ReviewConfig {
+            min_confidence: 0.0,
+            max_comments: 5,
+            max_comments_per_file: Some(3),
+            ..ReviewConfig::default()
+        };
+        let mut comments: Vec<ReviewComment> = (1..=10)
+            .map(|line| comment_at("src/noisy.rs", line, Severity::Warning, "noise"))
+            .collect();
+        comments.push(comment_at(
+            "src/quiet.rs",
+            7,
+            Severity::Warning,
+            "real issue",
+        ));
+
+        let (kept, filtered) = filter_and_sort(comments, &config, &Allowlist::default());
+        let noisy: Vec<u32> = kept
+            .iter()
+            .filter(|c| c.file_path == Path::new("src/noisy.rs"))
+            .map(|c| c.line)
+            .collect();
+        assert_eq!(noisy, [1, 2, 3]);
+        assert!(kept
+            .iter()
+            .any(|c| c.file_path == Path::new("src/quiet.rs")));
+        assert_eq!(
+            filtered
+                .iter()
+                .filter(|f| f.reason == FilterReason::MaxCommentsPerFile)
+                .count(),
+            7
+        );
+        assert_eq!(
+            FilterReason::MaxCommentsPerFile.to_string(),
+            "exceeded per-file limit"
+        );
+    }
+
+    #[test]
+    fn allowlisted_fingerprints_are_filtered_as_suppressed() {
+        let mut accepted = comment_at("tests/io.rs", 4, Severity::Bug, "unwrap may panic");
+        accepted.fingerprint = Some("0123456789abcdef".into());
+        let other = comment_at("src/lib.rs", 9, Severity::Bug, "unwrap may panic");
+
+        let allowlist = Allowlist::parse("0123456789abcdef\n");
+        let (kept, filtered) =
+            filter_and_sort(vec![accepted, other], &ReviewConfig::default(), &allowlist);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].file_path, PathBuf::from("src/lib.rs"));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].reason, FilterReason::Suppressed);
+        assert_eq!(filtered[0].reason.to_string(), "suppressed by allowlist");
     }
 
     #[test]
@@ -1423,8 +3578,7 @@ mod tests {
                 message: "null deref".into(),
                 confidence: 85.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("a.rs"),
@@ -1433,8 +3587,7 @@ mod tests {
                 message: "null deref".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("b.rs"),
@@ -1443,11 +3596,10 @@ mod tests {
                 message: "different issue".into(),
                 confidence: 90.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
-        let (deduped, count) = deduplicate(comments);
+        let (deduped, count) = deduplicate(comments, false);
         assert_eq!(deduped.len(), 2);
         assert_eq!(count, 1);
         // Should keep the higher confidence
@@ -1458,6 +3610,113 @@ mod tests {
         assert!((a_comment.confidence - 95.0).abs() < f64::EPSILON);
     }
 
+    fn suggesting(mut comment: ReviewComment, suggestion: &str) -> ReviewComment {
+        comment.suggestion = Some(suggestion.into());
+        comment
+    }
+
+    #[test]
+    fn deduplicate_ignores_trailing_punctuation() {
+        let comments = vec![
+            comment_at("a.rs", 10, Severity::Warning, "Missing null check"),
+            comment_at("a.rs", 10, Severity::Warning, "Missing null check."),
+        ];
+        let (deduped, count) = deduplicate(comments, false);
+        assert_eq!(count, 1);
+        assert_eq!(deduped.len(), 1);
+    }
+
+    #[test]
+    fn comment_paths_keep_a_top_level_a_directory() {
+        let diffs = vec![
+            modified_diff("a/lib.rs", &[(1, 1)]),
+            modified_diff("src/x.rs", &[(1, 1)]),
+        ];
+        let mut comments = make_comments();
+        comments[0].file_path = PathBuf::from("a/lib.rs");
+        comments[1].file_path = PathBuf::from("b/src/x.rs");
+        comments[2].file_path = PathBuf::from("/work/repo/a/lib.rs");
+
+        normalize_comment_paths(&mut comments, &diffs, Some(Path::new("/work/repo")));
+        assert_eq!(comments[0].file_path, PathBuf::from("a/lib.rs"));
+        assert_eq!(comments[1].file_path, PathBuf::from("src/x.rs"));
+        assert_eq!(comments[2].file_path, PathBuf::from("a/lib.rs"));
+
+        normalize_comment_paths(&mut comments, &diffs, Some(Path::new("/work/repo")));
+        assert_eq!(comments[0].file_path, PathBuf::from("a/lib.rs"));
+    }
+
+    #[test]
+    fn deduplicate_normalizes_case_whitespace_and_punctuation() {
+        let comments = vec![
+            comment_at(
+                "a.rs",
+                10,
+                Severity::Warning,
+                "Missing null check on `user`.",
+            ),
+            suggesting(
+                comment_at("a.rs", 10, Severity::Warning, "missing  null check on user"),
+                "Check `user` first",
+            ),
+            comment_at(
+                "a.rs",
+                11,
+                Severity::Warning,
+                "Missing null check on `user`.",
+            ),
+        ];
+        let (deduped, count) = deduplicate(comments.clone(), false);
+        assert_eq!(count, 1);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].suggestion.as_deref(), Some("Check `user` first"));
+
+        // Exact mode only merges identical messages
+        let (deduped, count) = deduplicate(comments, true);
+        assert_eq!(count, 0);
+        assert_eq!(deduped.len(), 3);
+    }
+
+    #[test]
+    fn deduplicate_matches_identical_suggestions() {
+        let comments = vec![
+            suggesting(
+                comment_at("a.rs", 5, Severity::Warning, "Index may be out of bounds"),
+                "Use `get(i)`.",
+            ),
+            suggesting(
+                comment_at("a.rs", 5, Severity::Warning, "Possible panic on indexing"),
+                "use get(i)",
+            ),
+            comment_at("a.rs", 5, Severity::Warning, "Unrelated issue"),
+        ];
+        let (deduped, count) = deduplicate(comments, false);
+        assert_eq!(count, 1);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].message, "Index may be out of bounds");
+    }
+
+    #[test]
+    fn reflection_additions_are_tagged_and_deduplicated() {
+        let kept = vec![comment_at("a.rs", 5, Severity::Bug, "Missing null check")];
+        let additions = vec![
+            comment_at("a.rs", 5, Severity::Bug, "missing null check!"),
+            comment_at("a.rs", 9, Severity::Bug, "no-unwrap: unwrap on user input"),
+        ];
+        let rules = [Rule {
+            name: "no-unwrap".into(),
+            severity: "warning".into(),
+            description: String::new(),
+            ..Default::default()
+        }];
+
+        let (merged, added) = merge_reflection_additions(kept, additions, &rules, false);
+        assert_eq!(added, 1);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].line, 9);
+        assert_eq!(merged[1].rule.as_deref(), Some("no-unwrap"));
+    }
+
     #[test]
     fn estimate_tokens_rough_calc() {
         let text = "a".repeat(400);
@@ -1474,8 +3733,7 @@ mod tests {
                 message: "test bug".into(),
                 confidence: 99.0,
                 suggestion: Some("fix it".into()),
-                patch: None,
-                rule: None,
+                ..Default::default()
             }],
             filtered_comments: vec![],
             summary: None,
@@ -1493,6 +3751,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let text = format!("{result}");
@@ -1504,9 +3764,89 @@ mod tests {
         assert!(md.contains("Bug"));
     }
 
-    fn make_file_diff(path: &str, content: &str) -> FileDiff {
-        use argus_core::{ChangeType, DiffHunk};
-        FileDiff {
+    #[test]
+    fn rationale_is_rendered_under_the_message() {
+        let mut comment = make_comments().remove(0);
+        comment.rationale = Some("Unchecked input reaches the query.".into());
+        let result = ReviewResult {
+            comments: vec![comment],
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats::default(),
+        };
+        let text = format!("{result}");
+        assert!(text.contains("  Why it matters: Unchecked input reaches the query."));
+        let md = result.to_markdown();
+        assert!(md.contains("> **Why it matters:** Unchecked input reaches the query."));
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json["comments"][0]["rationale"],
+            "Unchecked input reaches the query."
+        );
+    }
+
+    #[test]
+    fn long_message_is_cut_in_text_but_whole_in_json() {
+        let mut comment = make_comments().remove(0);
+        comment.message = "é".repeat(5000);
+        let result = ReviewResult {
+            comments: vec![comment],
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats::default(),
+        };
+
+        let text = result
+            .with_truncated_messages(2000)
+            .to_text(GroupBy::Severity);
+        assert!(text.contains(&format!(
+            "  {}… (truncated from 5000 characters; JSON output has the full message)\n",
+            "é".repeat(2000)
+        )));
+        assert!(!text.contains(&"é".repeat(2001)));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["comments"][0]["message"], "é".repeat(5000));
+        let short = result.with_truncated_messages(5000);
+        assert_eq!(short.comments[0].message, result.comments[0].message);
+    }
+
+    #[test]
+    fn json_always_includes_filtered_comments_with_stable_reasons() {
+        let mut result = ReviewResult {
+            comments: vec![],
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats::default(),
+        };
+        let json: serde_json::Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["filteredComments"], serde_json::json!([]));
+
+        let (_, filtered) = filter_and_sort(
+            vec![comment_at(
+                "src/lib.rs",
+                3,
+                Severity::Warning,
+                "unused import",
+            )],
+            &ReviewConfig {
+                min_confidence: 100.0,
+                ..ReviewConfig::default()
+            },
+            &Allowlist::default(),
+        );
+        result.filtered_comments = filtered;
+        let json: serde_json::Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["filteredComments"][0]["reason"], "below_confidence");
+        assert_eq!(
+            json["filteredComments"][0]["comment"]["filePath"],
+            "src/lib.rs"
+        );
+    }
+
+    fn make_file_diff(path: &str, content: &str) -> FileDiff {
+        use argus_core::{ChangeType, DiffHunk};
+        FileDiff {
             old_path: PathBuf::from(path),
             new_path: PathBuf::from(path),
             hunks: vec![DiffHunk {
@@ -1591,6 +3931,7 @@ mod tests {
             name: "no-unwrap".into(),
             severity: "warning".into(),
             description: "Don't use unwrap".into(),
+            ..Default::default()
         }];
         let mut comments = vec![
             ReviewComment {
@@ -1600,8 +3941,7 @@ mod tests {
                 message: "Using .unwrap() violates no-unwrap rule".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("b.rs"),
@@ -1610,8 +3950,7 @@ mod tests {
                 message: "Null pointer dereference".into(),
                 confidence: 90.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
         tag_rule_matches(&mut comments, &rules);
@@ -1619,6 +3958,42 @@ mod tests {
         assert!(comments[1].rule.is_none());
     }
 
+    #[test]
+    fn tag_rule_matches_prefers_echoed_rule_and_whole_words() {
+        let rules = vec![
+            Rule {
+                name: "no-unwrap".into(),
+                severity: "warning".into(),
+                description: String::new(),
+                ..Default::default()
+            },
+            Rule {
+                name: "no-panic".into(),
+                severity: "bug".into(),
+                description: String::new(),
+                ..Default::default()
+            },
+        ];
+        let tagged = |rule: Option<&str>, message: &str| {
+            let mut comment = comment_at("a.rs", 1, Severity::Warning, message);
+            comment.rule = rule.map(String::from);
+            comment
+        };
+        let mut comments = vec![
+            tagged(Some("NO-PANIC"), "unwrap here can panic"),
+            tagged(Some("made-up-rule"), "no-unwrap violated"),
+            tagged(Some("made-up-rule"), "unrelated"),
+            tagged(None, "prefer no-unwrap-or-default here"),
+            tagged(None, "violates (no-unwrap)"),
+        ];
+        tag_rule_matches(&mut comments, &rules);
+        assert_eq!(comments[0].rule.as_deref(), Some("no-panic"));
+        assert_eq!(comments[1].rule.as_deref(), Some("no-unwrap"));
+        assert!(comments[2].rule.is_none());
+        assert!(comments[3].rule.is_none());
+        assert_eq!(comments[4].rule.as_deref(), Some("no-unwrap"));
+    }
+
     #[test]
     fn group_display_name_single_file() {
         let diffs = vec![make_file_diff(
@@ -1704,8 +4079,7 @@ mod tests {
                 message: "test bug".into(),
                 confidence: 99.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             }],
             filtered_comments: vec![],
             summary: Some("High risk. Key issue is a null dereference.".into()),
@@ -1723,6 +4097,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let text = format!("{result}");
@@ -1749,6 +4125,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let text = format!("{result}");
@@ -1765,8 +4143,7 @@ mod tests {
                 message: "test bug".into(),
                 confidence: 99.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             }],
             filtered_comments: vec![],
             summary: Some("Medium risk due to missing error handling.".into()),
@@ -1784,6 +4161,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let md = result.to_markdown();
@@ -1801,7 +4180,7 @@ mod tests {
                 confidence: 99.0,
                 suggestion: Some("fix it".into()),
                 patch: Some("let x = safe_call();\nuse(x);".into()),
-                rule: None,
+                ..Default::default()
             }],
             filtered_comments: vec![],
             summary: None,
@@ -1819,6 +4198,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let text = format!("{result}");
@@ -1838,7 +4219,7 @@ mod tests {
                 confidence: 99.0,
                 suggestion: None,
                 patch: Some("let x = safe_call();".into()),
-                rule: None,
+                ..Default::default()
             }],
             filtered_comments: vec![],
             summary: None,
@@ -1856,25 +4237,969 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         };
         let md = result.to_markdown();
         assert!(md.contains("```\nlet x = safe_call();\n```"));
     }
 
+    fn llm_error(kind: LlmErrorKind) -> ArgusError {
+        argus_core::LlmError::new(kind, "provider failure").into()
+    }
+
     #[test]
-    fn detects_rate_limit_like_errors() {
-        assert!(is_rate_limit_error(&ArgusError::Llm(
-            "OpenAI API error 429 Too Many Requests".into(),
-        )));
-        assert!(is_rate_limit_error(&ArgusError::Llm(
-            "rate LIMIT: too many requests, retry later".into(),
-        )));
+    fn detects_rate_limit_errors_by_kind() {
+        assert!(is_rate_limit_error(&llm_error(LlmErrorKind::RateLimit)));
+        assert!(!is_rate_limit_error(&llm_error(LlmErrorKind::Timeout)));
         assert!(!is_rate_limit_error(&ArgusError::Llm(
-            "provider timeout".into(),
+            "API error 429 Too Many Requests".into(),
         )));
         assert!(!is_rate_limit_error(&ArgusError::Git(
             "not an llm error".into(),
         )));
     }
+
+    #[test]
+    fn detects_retriable_errors_for_fallback() {
+        for kind in [
+            LlmErrorKind::RateLimit,
+            LlmErrorKind::Timeout,
+            LlmErrorKind::Server,
+            LlmErrorKind::Network,
+        ] {
+            assert!(is_retriable_error(&llm_error(kind)), "{kind:?}");
+        }
+        for kind in [
+            LlmErrorKind::Auth,
+            LlmErrorKind::InvalidRequest,
+            LlmErrorKind::BadResponse,
+        ] {
+            assert!(!is_retriable_error(&llm_error(kind)), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_review_response_is_reported_not_printed() {
+        #[derive(Default)]
+        struct Capture(std::sync::Mutex<Vec<String>>);
+        impl Reporter for Capture {
+            fn warning(&self, message: &str) {
+                self.0.lock().unwrap().push(message.to_string());
+            }
+        }
+
+        let capture = Arc::new(Capture::default());
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new())
+            .with_reporter(capture.clone());
+
+        assert!(pipeline.parse_review(&"not json".into()).is_empty());
+        let warnings = capture.0.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("failed to parse LLM response"));
+        assert!(!warnings[0].contains("max_output_tokens"));
+    }
+
+    #[test]
+    fn truncated_review_response_suggests_raising_output_limit() {
+        #[derive(Default)]
+        struct Capture(std::sync::Mutex<Vec<String>>);
+        impl Reporter for Capture {
+            fn warning(&self, message: &str) {
+                self.0.lock().unwrap().push(message.to_string());
+            }
+        }
+
+        let capture = Arc::new(Capture::default());
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new())
+            .with_reporter(capture.clone());
+
+        let cut_off = ChatResponse {
+            content: r#"{"comments": [{"file": "src/lib.rs", "line": 3, "mess"#.into(),
+            truncated: true,
+        };
+        assert!(pipeline.parse_review(&cut_off).is_empty());
+
+        // A complete answer is parsed even when the provider reports the limit
+        let complete = ChatResponse {
+            content: r#"{"comments": []}"#.into(),
+            truncated: true,
+        };
+        assert!(pipeline.parse_review(&complete).is_empty());
+
+        let warnings = capture.0.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("failed to parse LLM response"));
+        assert!(warnings[0].contains("cut off at the output token limit"));
+        assert!(warnings[0].contains("raise [llm] max_output_tokens"));
+    }
+
+    #[test]
+    fn partial_map_warning_names_only_the_first_files() {
+        #[derive(Default)]
+        struct Capture(std::sync::Mutex<Vec<String>>);
+        impl Reporter for Capture {
+            fn warning(&self, message: &str) {
+                self.0.lock().unwrap().push(message.to_string());
+            }
+        }
+
+        let capture = Arc::new(Capture::default());
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new())
+            .with_reporter(capture.clone());
+        let map = argus_repomap::RepoMap {
+            output: String::new(),
+            files_mapped: 0,
+            files_capped: 0,
+            non_utf8_files: Vec::new(),
+            oversized_files: Vec::new(),
+            unreadable_files: vec![PathBuf::from("src/locked.rs")],
+            unparsed_files: (0..7)
+                .map(|i| argus_repomap::UnparsedFile {
+                    path: PathBuf::from(format!("src/f{i}.rs")),
+                    reason: "parser panicked".into(),
+                })
+                .collect(),
+            blast_radius: Vec::new(),
+        };
+
+        pipeline.warn_partial_map(&map);
+
+        let warnings = capture.0.lock().unwrap();
+        assert_eq!(
+            warnings.as_slice(),
+            ["repo map skipped 8 file(s): src/locked.rs (unreadable), \
+              src/f0.rs (parser panicked), src/f1.rs (parser panicked), \
+              src/f2.rs (parser panicked), src/f3.rs (parser panicked), +3 more"]
+        );
+    }
+
+    #[test]
+    fn model_used_reports_serving_fallback() {
+        let primary = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let fallback = LlmClient::new(&argus_core::LlmConfig {
+            provider: "anthropic".into(),
+            ..argus_core::LlmConfig::default()
+        })
+        .unwrap();
+        let pipeline = ReviewPipeline::new(primary, ReviewConfig::default(), Vec::new())
+            .with_fallbacks(vec![fallback]);
+        assert_eq!(pipeline.model_used(), "gpt-4o");
+
+        pipeline.active_client.store(1, Ordering::Relaxed);
+        assert_eq!(pipeline.model_used(), "anthropic:claude-sonnet-4-5");
+    }
+
+    #[test]
+    fn markdown_groups_files_with_toc_sorted_by_severity() {
+        let comment = |path: &str, line: u32, severity: Severity| ReviewComment {
+            file_path: PathBuf::from(path),
+            line,
+            severity,
+            message: format!("issue at {path}:{line}"),
+            confidence: 95.0,
+            suggestion: None,
+            ..Default::default()
+        };
+        let result = ReviewResult {
+            comments: vec![
+                comment("src/a.rs", 3, Severity::Warning),
+                comment("src/z_mod.rs", 7, Severity::Bug),
+                comment("src/a.rs", 9, Severity::Suggestion),
+            ],
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats {
+                files_reviewed: 2,
+                files_skipped: 0,
+                total_hunks: 2,
+                comments_generated: 3,
+                comments_filtered: 0,
+                comments_deduplicated: 0,
+                comments_reflected_out: 0,
+                skipped_files: vec![],
+                model_used: "test".into(),
+                llm_calls: 1,
+                llm_retries: 0,
+                file_groups: vec![],
+                hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
+            },
+        };
+        let md = result.to_markdown();
+
+        assert!(md.contains("## Contents"));
+        assert!(md.contains("- [`src/z_mod.rs`](#srcz_modrs) — \u{1f41b} 1\n"));
+        assert!(md.contains("- [`src/a.rs`](#srcars) — \u{26a0}\u{fe0f} 1, \u{1f4a1} 1\n"));
+
+        // The file with a bug comes first even though it sorts later by path
+        let z = md.find("### `src/z_mod.rs`").unwrap();
+        let a = md.find("### `src/a.rs`").unwrap();
+        assert!(z < a);
+        assert!(md.find("src/a.rs:3").unwrap() < md.find("src/a.rs:9").unwrap());
+        assert!(md.contains("#### \u{1f41b} Bug — `src/z_mod.rs:7`"));
+    }
+
+    #[test]
+    fn group_by_file_lists_files_by_path_and_comments_by_line() {
+        let result = ReviewResult {
+            comments: vec![
+                comment_at("src/z.rs", 4, Severity::Bug, "z bug"),
+                comment_at("src/a.rs", 20, Severity::Warning, "a late warning"),
+                comment_at("src/a.rs", 5, Severity::Suggestion, "a early suggestion"),
+            ],
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats {
+                files_reviewed: 2,
+                files_skipped: 0,
+                total_hunks: 2,
+                comments_generated: 3,
+                comments_filtered: 0,
+                comments_deduplicated: 0,
+                comments_reflected_out: 0,
+                skipped_files: vec![],
+                model_used: "test".into(),
+                llm_calls: 1,
+                llm_retries: 0,
+                file_groups: vec![],
+                hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
+            },
+        };
+
+        let text = result.to_text(GroupBy::File);
+        assert!(text.contains("src/a.rs (2)\n------------\n"), "{text}");
+        let order: Vec<usize> = ["src/a.rs:5", "src/a.rs:20", "src/z.rs (1)", "src/z.rs:4"]
+            .iter()
+            .map(|needle| text.find(needle).unwrap())
+            .collect();
+        assert!(order.windows(2).all(|w| w[0] < w[1]), "{text}");
+        assert_eq!(result.to_text(GroupBy::Severity), result.to_string());
+
+        let md = result.to_markdown_grouped(GroupBy::File);
+        assert!(md.find("### `src/a.rs`").unwrap() < md.find("### `src/z.rs`").unwrap());
+        assert!(md.find("src/a.rs:5").unwrap() < md.find("src/a.rs:20").unwrap());
+        assert!(!md.contains("<details>"));
+
+        let folded = result.to_markdown_collapsible(GroupBy::File);
+        assert_eq!(folded.matches("<details>").count(), 2);
+        assert_eq!(folded.matches("</details>").count(), 2);
+        assert!(folded.contains(
+            "<summary><code>src/a.rs</code> — \u{26a0}\u{fe0f} 1, \u{1f4a1} 1</summary>"
+        ));
+        assert!(md.lines().all(|line| folded.contains(line)));
+    }
+
+    #[test]
+    fn anchor_slugger_matches_github_style() {
+        let mut slugger = AnchorSlugger::default();
+        assert_eq!(slugger.slug("src/My File.rs"), "srcmy-filers");
+        assert_eq!(
+            slugger.slug("crates/argus-core/lib.rs"),
+            "cratesargus-corelibrs"
+        );
+        assert_eq!(slugger.slug("a.rs"), "ars");
+        assert_eq!(slugger.slug("a.rs"), "ars-1");
+    }
+
+    fn comment_at(path: &str, line: u32, severity: Severity, message: &str) -> ReviewComment {
+        ReviewComment {
+            file_path: PathBuf::from(path),
+            line,
+            severity,
+            message: message.into(),
+            confidence: 90.0,
+            suggestion: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn comments_on_context_lines_are_filtered_as_unchanged() {
+        let mut removed = make_file_diff("src/old.rs", "-fn gone() {}\n");
+        removed.is_deleted_file = true;
+        let diffs = vec![
+            make_file_diff("src/lib.rs", " fn run() {\n+    retry();\n }\n"),
+            removed,
+        ];
+        let comments = vec![
+            comment_at("src/lib.rs", 1, Severity::Warning, "on context line"),
+            comment_at("src/lib.rs", 2, Severity::Bug, "on added line"),
+            comment_at("src/old.rs", 1, Severity::Warning, "on deleted file"),
+        ];
+
+        let (kept, filtered) = split_unchanged_line_comments(comments, &diffs);
+        let kept: Vec<&str> = kept.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(kept, ["on added line", "on deleted file"]);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].comment.message, "on context line");
+        assert_eq!(filtered[0].reason, FilterReason::UnchangedLine);
+        assert_eq!(filtered[0].reason.to_string(), "comment on unchanged line");
+    }
+
+    #[test]
+    fn merges_source_and_test_comments_on_same_identifier() {
+        let comments = vec![
+            comment_at(
+                "src/parser.rs",
+                12,
+                Severity::Warning,
+                "`parse_header` now returns `Option` but callers still unwrap",
+            ),
+            comment_at(
+                "src/parser_test.rs",
+                40,
+                Severity::Bug,
+                "Test calls `parse_header()` with the old signature and will not compile",
+            ),
+            comment_at(
+                "src/lexer.rs",
+                3,
+                Severity::Warning,
+                "`parse_header` shadowed",
+            ),
+        ];
+
+        let (merged, count) = merge_test_counterparts(comments);
+        assert_eq!(count, 1);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].file_path, PathBuf::from("src/parser.rs"));
+        assert_eq!(merged[0].severity, Severity::Bug);
+        assert!(merged[0]
+            .message
+            .contains("Also affects `src/parser_test.rs:40`"));
+        assert_eq!(merged[1].file_path, PathBuf::from("src/lexer.rs"));
+    }
+
+    #[test]
+    fn does_not_merge_without_shared_identifier_or_counterpart() {
+        let comments = vec![
+            comment_at("app/models.py", 5, Severity::Bug, "`save` skips validation"),
+            comment_at(
+                "tests/test_models.py",
+                9,
+                Severity::Bug,
+                "`load` fixture is stale",
+            ),
+            comment_at("web/api.ts", 1, Severity::Bug, "`fetchUser` ignores errors"),
+            comment_at(
+                "web/client.test.ts",
+                2,
+                Severity::Bug,
+                "`fetchUser` mock is wrong",
+            ),
+        ];
+
+        let (merged, count) = merge_test_counterparts(comments);
+        assert_eq!(count, 0);
+        assert_eq!(merged.len(), 4);
+    }
+
+    #[test]
+    fn test_counterpart_naming_conventions() {
+        let subject = |p: &str| test_subject(Path::new(p));
+        assert_eq!(subject("foo_test.go"), Some(("foo".into(), "go".into())));
+        assert_eq!(
+            subject("tests/test_foo.py"),
+            Some(("foo".into(), "py".into()))
+        );
+        assert_eq!(
+            subject("src/foo.spec.ts"),
+            Some(("foo".into(), "ts".into()))
+        );
+        assert_eq!(
+            subject("src/foo.test.tsx"),
+            Some(("foo".into(), "tsx".into()))
+        );
+        assert_eq!(subject("src/foo.rs"), None);
+        assert_eq!(subject("src/test_.py"), None);
+    }
+
+    fn reflection_eval(
+        index: usize,
+        score: u8,
+        revised_severity: Option<Severity>,
+        reason: Option<&str>,
+    ) -> prompt::ReflectionEval {
+        prompt::ReflectionEval {
+            index,
+            score,
+            revised_severity,
+            reason: reason.map(String::from),
+        }
+    }
+
+    #[test]
+    fn low_reflection_score_lowers_confidence_when_recalibrating() {
+        let comments = vec![
+            comment_at("src/a.rs", 1, Severity::Warning, "weak"),
+            comment_at("src/a.rs", 2, Severity::Warning, "strong"),
+            comment_at("src/a.rs", 3, Severity::Warning, "unscored"),
+        ];
+        let evaluations = || {
+            vec![
+                reflection_eval(0, 7, None, None),
+                reflection_eval(1, 10, Some(Severity::Bug), None),
+            ]
+        };
+        let pipeline = |recalibrate_confidence| {
+            let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+            let config = ReviewConfig {
+                recalibrate_confidence,
+                ..ReviewConfig::default()
+            };
+            ReviewPipeline::new(llm, config, Vec::new())
+        };
+
+        let (kept, removed) = pipeline(true).apply_reflection_scores(&comments, evaluations());
+        assert!(removed.is_empty());
+        let confidences: Vec<f64> = kept.iter().map(|c| c.confidence).collect();
+        let original = comments[0].confidence;
+        assert!(confidences[0] < original, "{confidences:?}");
+        assert_eq!(confidences[0], recalibrated_confidence(original, 7));
+        assert_eq!(confidences[2], original);
+        assert_eq!(kept[1].severity, Severity::Bug);
+
+        let (kept, _) = pipeline(false).apply_reflection_scores(&comments, evaluations());
+        assert!(kept.iter().all(|c| c.confidence == original));
+    }
+
+    #[test]
+    fn passed_comment_carries_its_reflection_score_in_json() {
+        let comments = vec![
+            comment_at("src/a.rs", 1, Severity::Warning, "real"),
+            comment_at("src/a.rs", 2, Severity::Warning, "nit"),
+        ];
+        let evaluations = vec![
+            reflection_eval(0, 8, None, Some("Unwrap on user input")),
+            reflection_eval(1, 3, None, None),
+        ];
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new());
+
+        let (kept, removed) = pipeline.apply_reflection_scores(&comments, evaluations);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].reflection.as_ref().unwrap().score, 3);
+        let result = ReviewResult {
+            comments: kept,
+            filtered_comments: vec![],
+            summary: None,
+            stats: ReviewStats::default(),
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["comments"][0]["reflection"]["score"], 8);
+        assert_eq!(
+            json["comments"][0]["reflection"]["reason"],
+            "Unwrap on user input"
+        );
+    }
+
+    #[test]
+    fn plan_counts_groups_reflection_and_summary() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let config = ReviewConfig {
+            max_diff_tokens: 1,
+            cross_file: false,
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm, config, Vec::new());
+        let diffs = vec![
+            make_file_diff("src/a.rs", "+let a = 1;\n"),
+            make_file_diff("src/b.rs", "+let b = 2;\n"),
+            make_file_diff("Cargo.lock", "+lock\n"),
+        ];
+
+        let plan = pipeline.plan(diffs, None);
+        assert_eq!(plan.model, "gpt-4o");
+        assert_eq!(plan.files_reviewed, 2);
+        assert_eq!(plan.files_skipped, 1);
+        assert_eq!(plan.groups.len(), 2);
+        assert_eq!(plan.review_calls, 2);
+        // 2 review calls + self-reflection + summary
+        assert_eq!(plan.estimated_llm_calls, 4);
+        assert!(plan.groups.iter().all(|g| g.estimated_tokens > 0));
+        assert!(plan.estimated_input_tokens >= plan.groups[0].estimated_tokens * 2);
+        assert!(plan.estimated_cost_usd.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn plan_skips_summary_call_when_disabled() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let config = ReviewConfig {
+            max_diff_tokens: 1,
+            cross_file: false,
+            summary: false,
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm, config, Vec::new());
+        let diffs = vec![make_file_diff("src/a.rs", "+let a = 1;\n")];
+
+        // 1 review call + self-reflection, no summary
+        assert_eq!(pipeline.plan(diffs, None).estimated_llm_calls, 2);
+    }
+
+    #[test]
+    fn repo_map_covers_the_diffs_languages_unless_configured() {
+        let llm = || LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let diffs = vec![
+            make_file_diff("app/models.py", "+x = 1\n"),
+            make_file_diff("app/views.py", "+y = 2\n"),
+            make_file_diff("README.md", "+docs\n"),
+        ];
+        let pipeline = ReviewPipeline::new(llm(), ReviewConfig::default(), Vec::new());
+        assert_eq!(pipeline.repo_map_languages(&diffs), [Language::Python]);
+
+        let config = ReviewConfig {
+            repo_map_languages: vec!["python".into(), "TypeScript".into()],
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm(), config, Vec::new());
+        assert_eq!(
+            pipeline.repo_map_languages(&diffs),
+            [Language::Python, Language::TypeScript, Language::JavaScript]
+        );
+
+        // A C header change maps the C++ that includes it too
+        let diffs = vec![make_file_diff("include/api.h", "+int f(void);\n")];
+        let pipeline = ReviewPipeline::new(llm(), ReviewConfig::default(), Vec::new());
+        assert_eq!(
+            pipeline.repo_map_languages(&diffs),
+            [Language::C, Language::Cpp]
+        );
+    }
+
+    #[test]
+    fn description_is_added_to_every_review_prompt() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let config = ReviewConfig {
+            max_diff_tokens: 1,
+            cross_file: false,
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm, config, Vec::new());
+        let diffs = vec![
+            make_file_diff("src/a.rs", "+let a = 1;\n"),
+            make_file_diff("src/b.rs", "+let b = 2;\n"),
+        ];
+
+        let prepared = pipeline.prepare(diffs, None, Some("Fix overflow\n\nUse u64."));
+        assert_eq!(prepared.requests.len(), 2);
+        for request in &prepared.requests {
+            assert!(
+                request
+                    .user
+                    .contains(&format!("{INTENT_HEADING}\nFix overflow\n\nUse u64.")),
+                "{}",
+                request.user
+            );
+        }
+    }
+
+    #[test]
+    fn instructions_follow_the_diff_in_every_review_prompt() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new())
+            .with_instructions(vec!["Pay special attention to the retry logic".into()]);
+        let diffs = vec![make_file_diff("src/retry.rs", "+let attempts = 3;\n")];
+
+        let prepared = pipeline.prepare(diffs, None, None);
+        let user = &prepared.requests[0].user;
+        let instruction = user
+            .find("- Pay special attention to the retry logic\n")
+            .expect(user);
+        assert!(
+            user.find("+let attempts = 3;").unwrap() < instruction,
+            "{user}"
+        );
+        assert!(!prepared.system.contains("retry logic"));
+    }
+
+    #[test]
+    fn merge_resorts_comments_and_joins_summaries() {
+        let part = |comment: ReviewComment, summary: &str| ReviewResult {
+            comments: vec![comment],
+            filtered_comments: Vec::new(),
+            summary: Some(summary.into()),
+            stats: ReviewStats {
+                files_reviewed: 1,
+                ..ReviewStats::default()
+            },
+        };
+        let merged = ReviewResult::merge(vec![
+            part(comment_at("b.rs", 3, Severity::Warning, "w"), "first"),
+            part(comment_at("a.rs", 9, Severity::Bug, "b"), "second"),
+            part(comment_at("b.rs", 3, Severity::Warning, "a"), "third"),
+        ]);
+        assert_eq!(merged.comments[0].severity, Severity::Bug);
+        assert_eq!(merged.comments[1].file_path, PathBuf::from("b.rs"));
+        // Same severity, path, and line: the message breaks the tie
+        assert_eq!(merged.comments[1].message, "a");
+        assert_eq!(merged.comments[2].message, "w");
+        assert_eq!(merged.summary.as_deref(), Some("first\n\nsecond\n\nthird"));
+        assert_eq!(merged.stats.files_reviewed, 3);
+    }
+
+    #[test]
+    fn merge_sums_timings_and_keeps_each_group() {
+        let part = |llm_ms: u64| ReviewResult {
+            comments: Vec::new(),
+            filtered_comments: Vec::new(),
+            summary: None,
+            stats: ReviewStats {
+                timings: ReviewTimings {
+                    history_ms: 10,
+                    llm_ms,
+                    llm_group_ms: vec![llm_ms],
+                    ..ReviewTimings::default()
+                },
+                ..ReviewStats::default()
+            },
+        };
+        let merged = ReviewResult::merge(vec![part(300), part(500)]);
+        let timings = &merged.stats.timings;
+        assert_eq!(timings.history_ms, 20);
+        assert_eq!(timings.llm_ms, 800);
+        assert_eq!(timings.llm_group_ms, [300, 500]);
+        assert_eq!(timings.total_ms(), 820);
+    }
+
+    fn modified_diff(path: &str, hunks: &[(u32, u32)]) -> FileDiff {
+        use argus_core::{ChangeType, DiffHunk};
+        FileDiff {
+            old_path: PathBuf::from(path),
+            new_path: PathBuf::from(path),
+            hunks: hunks
+                .iter()
+                .map(|&(new_start, new_lines)| DiffHunk {
+                    file_path: PathBuf::from(path),
+                    old_start: new_start,
+                    old_lines: new_lines,
+                    new_start,
+                    new_lines,
+                    content: "+changed\n".into(),
+                    change_type: ChangeType::Modify,
+                })
+                .collect(),
+            is_new_file: false,
+            is_deleted_file: false,
+            is_rename: false,
+        }
+    }
+
+    #[test]
+    fn surrounding_context_includes_and_merges_hunk_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let content: String = (1..=30).map(|n| format!("line{n}\n")).collect();
+        std::fs::write(dir.path().join("lib.rs"), content).unwrap();
+
+        // Hunks at 10 and 14 overlap once widened by 2; 28 stands alone
+        let diffs = vec![modified_diff("lib.rs", &[(10, 1), (14, 1), (28, 2)])];
+        let ctx = build_surrounding_context(&diffs, dir.path(), 2, None).unwrap();
+
+        assert!(ctx.contains("### lib.rs (lines 8-16)"), "{ctx}");
+        assert!(ctx.contains("### lib.rs (lines 26-30)"), "{ctx}");
+        assert!(ctx.contains(" 8 | line8"));
+        assert!(!ctx.contains("line7\n"));
+        assert!(!ctx.contains("line17"));
+    }
+
+    #[test]
+    fn surrounding_context_skips_new_files_and_zero_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("new.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn b() {}\n").unwrap();
+
+        let new_file = vec![make_file_diff("new.rs", "+fn a() {}\n")];
+        assert!(build_surrounding_context(&new_file, dir.path(), 5, None).is_none());
+
+        let modified = vec![modified_diff("lib.rs", &[(1, 1)])];
+        assert!(build_surrounding_context(&modified, dir.path(), 0, None).is_none());
+        assert!(build_surrounding_context(&modified, dir.path(), 5, None).is_some());
+    }
+
+    #[test]
+    fn expand_to_function_includes_whole_enclosing_function() {
+        let dir = tempfile::tempdir().unwrap();
+        let body: String = (1..=8).map(|n| format!("    let v{n} = {n};\n")).collect();
+        let content = format!("use std::fmt;\n\nfn process() {{\n{body}}}\n\nfn other() {{}}\n");
+        std::fs::write(dir.path().join("lib.rs"), content).unwrap();
+
+        // A one-line hunk in the middle of `process` (lines 3-12)
+        let diffs = vec![modified_diff("lib.rs", &[(7, 1)])];
+        let ctx = build_surrounding_context(&diffs, dir.path(), 0, Some(10_000)).unwrap();
+        assert!(ctx.contains("### lib.rs (lines 3-12)"), "{ctx}");
+        assert!(ctx.contains(" 3 | fn process() {"));
+        assert!(ctx.contains("12 | }"));
+        assert!(!ctx.contains("fn other"));
+
+        // Over budget: back to plain line context
+        let ctx = build_surrounding_context(&diffs, dir.path(), 1, Some(5)).unwrap();
+        assert!(ctx.contains("### lib.rs (lines 6-8)"), "{ctx}");
+        assert!(build_surrounding_context(&diffs, dir.path(), 0, Some(5)).is_none());
+    }
+
+    #[test]
+    fn expand_to_function_charges_a_function_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let body: String = (1..=8).map(|n| format!("    let v{n} = {n};\n")).collect();
+        let content = format!("fn first() {{\n{body}}}\nfn second() {{\n{body}}}\n");
+        std::fs::write(dir.path().join("lib.rs"), &content).unwrap();
+
+        // Budget for each function once; the second hunk in `first` is free
+        let lines: Vec<&str> = content.lines().collect();
+        let budget =
+            estimate_tokens(&lines[..10].join("\n")) + estimate_tokens(&lines[10..].join("\n"));
+        let diffs = vec![modified_diff("lib.rs", &[(3, 1), (8, 1), (15, 1)])];
+        let ctx = build_surrounding_context(&diffs, dir.path(), 0, Some(budget)).unwrap();
+        assert!(ctx.contains("### lib.rs (lines 1-20)"), "{ctx}");
+    }
+
+    #[test]
+    fn test_presence_context_lists_untested_functions_of_files_in_group() {
+        use crate::testpresence::UntestedFunction;
+        let untested = [UntestedFunction {
+            name: "render".into(),
+            file: PathBuf::from("src/view.rs"),
+            line: 12,
+        }];
+
+        let diffs = vec![modified_diff("src/view.rs", &[(13, 2)])];
+        let ctx = build_test_presence_context(&diffs, &untested).unwrap();
+        assert_eq!(
+            ctx,
+            "## Test Presence\n\nNo test file mentions these changed functions (a name-based \
+             heuristic). Weigh risky changes to them more heavily.\n\n\
+             - function `render` (src/view.rs:12) has no detected tests\n"
+        );
+
+        let other = vec![modified_diff("src/model.rs", &[(1, 1)])];
+        assert!(build_test_presence_context(&other, &untested).is_none());
+    }
+
+    #[test]
+    fn blast_radius_context_lists_dependents_of_files_in_group() {
+        use argus_repomap::graph::BlastRadius;
+
+        let radius = vec![
+            BlastRadius {
+                symbol: "parse_config".into(),
+                file: PathBuf::from("config.rs"),
+                dependents: (1..=7).map(|i| format!("caller{i}")).collect(),
+            },
+            BlastRadius {
+                symbol: "other".into(),
+                file: PathBuf::from("other.rs"),
+                dependents: vec!["main".into()],
+            },
+        ];
+        let diffs = vec![modified_diff("config.rs", &[(3, 2)])];
+
+        let ctx = build_blast_radius_context(&diffs, &radius).unwrap();
+        assert!(ctx.contains("## Blast Radius"));
+        assert!(
+            ctx.contains("`parse_config` (config.rs) is referenced by 7 symbols"),
+            "{ctx}"
+        );
+        assert!(ctx.contains("caller1, caller2, caller3, caller4, caller5, ..."));
+        assert!(!ctx.contains("other"));
+
+        let unrelated = vec![modified_diff("lib.rs", &[(1, 1)])];
+        assert!(build_blast_radius_context(&unrelated, &radius).is_none());
+    }
+
+    #[test]
+    fn offline_review_reports_deterministic_findings_without_llm() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new());
+        let diffs = vec![
+            make_file_diff("src/a.rs", "+// TODO: handle errors\n"),
+            make_file_diff("Cargo.lock", "+// TODO: not reviewed\n"),
+        ];
+
+        let result = pipeline.review_offline(diffs, None);
+        assert_eq!(result.stats.model_used, OFFLINE_MODEL);
+        assert_eq!(result.stats.llm_calls, 0);
+        assert_eq!(result.stats.files_reviewed, 1);
+        assert_eq!(result.stats.files_skipped, 1);
+        assert_eq!(result.comments.len(), 1);
+        assert_eq!(result.comments[0].file_path, PathBuf::from("src/a.rs"));
+        assert!(result.summary.unwrap().contains("risk (score"));
+    }
+
+    #[test]
+    fn offline_review_honors_flag_new_dependencies() {
+        let llm = || LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let diffs = || {
+            vec![make_file_diff(
+                "Cargo.lock",
+                "+[[package]]\n+name = \"left-pad\"\n+version = \"1.3.0\"\n",
+            )]
+        };
+
+        let pipeline = ReviewPipeline::new(llm(), ReviewConfig::default(), Vec::new());
+        let result = pipeline.review_offline(diffs(), None);
+        assert_eq!(result.comments.len(), 1);
+        assert_eq!(result.comments[0].rule.as_deref(), Some("new-dependency"));
+
+        let config = ReviewConfig {
+            flag_new_dependencies: false,
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm(), config, Vec::new());
+        assert!(pipeline.review_offline(diffs(), None).comments.is_empty());
+    }
+
+    #[test]
+    fn offline_review_enforces_pattern_rules_and_skips_invalid_ones() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let rule = |name: &str, pattern: &str| Rule {
+            name: name.into(),
+            severity: "warning".into(),
+            description: "Remove dbg! before merging".into(),
+            pattern: Some(pattern.into()),
+            paths: Some(vec!["src/**".into()]),
+        };
+        let pipeline = ReviewPipeline::new(
+            llm,
+            ReviewConfig::default(),
+            vec![rule("no-dbg", r"\bdbg!\("), rule("broken", "(")],
+        );
+        let diffs = vec![
+            make_file_diff("src/a.rs", "+let x = 1;\n+dbg!(x);\n"),
+            make_file_diff("benches/b.rs", "+dbg!(y);\n"),
+        ];
+
+        let result = pipeline.review_offline(diffs, None);
+        assert_eq!(result.comments.len(), 1);
+        assert_eq!(result.comments[0].file_path, PathBuf::from("src/a.rs"));
+        assert_eq!(result.comments[0].line, 2);
+        assert_eq!(result.comments[0].rule.as_deref(), Some("no-dbg"));
+    }
+
+    #[test]
+    fn plan_for_fully_filtered_diff_makes_no_calls() {
+        let llm = LlmClient::new(&argus_core::LlmConfig::default()).unwrap();
+        let pipeline = ReviewPipeline::new(llm, ReviewConfig::default(), Vec::new());
+        let plan = pipeline.plan(vec![make_file_diff("yarn.lock", "+x\n")], None);
+        assert_eq!(plan.estimated_llm_calls, 0);
+        assert_eq!(plan.estimated_input_tokens, 0);
+        assert!(plan.groups.is_empty());
+    }
+
+    #[test]
+    fn cost_estimate_uses_model_prices() {
+        let cost = estimate_cost_usd("openai", "gpt-4o", 1_000_000, 0).unwrap();
+        assert!((cost - 2.5).abs() < 1e-9);
+        let mini = estimate_cost_usd("openai", "gpt-4o-mini", 1_000_000, 1_000_000).unwrap();
+        assert!((mini - 0.75).abs() < 1e-9);
+        assert_eq!(estimate_cost_usd("ollama", "llama3", 5000, 5000), Some(0.0));
+        assert_eq!(
+            estimate_cost_usd("openai", "my-custom-model", 5000, 5000),
+            None
+        );
+    }
+
+    /// An ollama-compatible client whose every reply is `content`.
+    fn canned_llm(content: serde_json::Value) -> LlmClient {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let reply =
+            serde_json::json!({ "message": { "content": content.to_string() } }).to_string();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{reply}",
+                    reply.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        LlmClient::new(&argus_core::LlmConfig {
+            provider: "ollama".into(),
+            base_url: Some(format!("http://{addr}")),
+            ..argus_core::LlmConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn conflict_marker_sorts_before_llm_warnings() {
+        let llm = canned_llm(serde_json::json!({ "comments": [{
+            "file": "src/a.rs",
+            "line": 1,
+            "severity": "warning",
+            "message": "unchecked index",
+            "confidence": 95,
+        }] }));
+        let config = ReviewConfig {
+            self_reflection: false,
+            summary: false,
+            ..ReviewConfig::default()
+        };
+        let pipeline = ReviewPipeline::new(llm, config, Vec::new());
+        let diffs = vec![
+            make_file_diff("src/a.rs", "+let x = v[i];\n"),
+            make_file_diff("src/z.rs", "+<<<<<<< HEAD\n"),
+        ];
+
+        let result = pipeline.review(diffs, None).await.unwrap();
+
+        let order: Vec<(Severity, &str)> = result
+            .comments
+            .iter()
+            .map(|c| (c.severity, c.file_path.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            [(Severity::Bug, "src/z.rs"), (Severity::Warning, "src/a.rs")]
+        );
+    }
+
+    #[tokio::test]
+    async fn online_review_raises_deterministic_findings_on_override_paths() {
+        let config = ReviewConfig {
+            self_reflection: false,
+            summary: false,
+            flag_todos: true,
+            severity_overrides: vec![SeverityOverride {
+                path: "src/**".into(),
+                min_severity: Severity::Warning,
+            }],
+            ..ReviewConfig::default()
+        };
+        let llm = canned_llm(serde_json::json!({ "comments": [] }));
+        let pipeline = ReviewPipeline::new(llm, config, Vec::new());
+        let diffs = vec![make_file_diff("src/a.rs", "+// TODO: handle errors\n")];
+
+        let result = pipeline.review(diffs, None).await.unwrap();
+
+        assert_eq!(result.comments.len(), 1);
+        assert_eq!(result.comments[0].severity, Severity::Warning);
+    }
 }
@@ -0,0 +1,231 @@
+//! Progress reporting for the review pipeline.
+//!
+//! [`ReviewPipeline`](crate::pipeline::ReviewPipeline) never writes to
+//! stdout/stderr itself. It emits progress events and warnings to a
+//! [`Reporter`], so library consumers can suppress or capture them. The CLI
+//! installs a [`TerminalReporter`] that draws indicatif progress bars.
+
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// Receives progress events from the review pipeline.
+///
+/// Every method has a no-op default, so implementors only override the
+/// events they care about. Calls arrive sequentially from a single review.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Mutex;
+/// use argus_review::progress::Reporter;
+///
+/// #[derive(Default)]
+/// struct Capture(Mutex<Vec<String>>);
+///
+/// impl Reporter for Capture {
+///     fn warning(&self, message: &str) {
+///         self.0.lock().unwrap().push(message.to_string());
+///     }
+/// }
+///
+/// let capture = Capture::default();
+/// capture.warning("self-reflection failed");
+/// capture.task_started("ignored by default");
+/// assert_eq!(capture.0.lock().unwrap().len(), 1);
+/// ```
+pub trait Reporter: Send + Sync {
+    /// A review split into `groups` LLM calls covering `files` files is starting.
+    fn groups_started(&self, groups: usize, files: usize) {
+        let _ = (groups, files);
+    }
+
+    /// Group `index` (0-based) of `total` is being sent to the LLM.
+    fn group_started(&self, index: usize, total: usize, label: &str) {
+        let _ = (index, total, label);
+    }
+
+    /// Group `index` (0-based) of `total` produced `comments` comments.
+    fn group_finished(&self, index: usize, total: usize, label: &str, comments: usize) {
+        let _ = (index, total, label, comments);
+    }
+
+    /// All groups of a split review have been reviewed.
+    fn groups_finished(&self) {}
+
+    /// A single pipeline step (review call, self-reflection, summary) is starting.
+    fn task_started(&self, message: &str) {
+        let _ = message;
+    }
+
+    /// The step started by the last [`task_started`](Reporter::task_started) call ended.
+    fn task_finished(&self, message: &str) {
+        let _ = message;
+    }
+
+    /// A one-line status update, such as the final comment tally.
+    fn status(&self, message: &str) {
+        let _ = message;
+    }
+
+    /// A recoverable problem the review worked around.
+    fn warning(&self, message: &str) {
+        let _ = message;
+    }
+}
+
+/// A [`Reporter`] that discards every event.
+///
+/// This is the pipeline default, so embedding argus produces no output.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::progress::{NoopReporter, Reporter};
+///
+/// NoopReporter.warning("nobody hears this");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopReporter;
+
+impl Reporter for NoopReporter {}
+
+/// A [`Reporter`] that draws progress bars and spinners on stderr.
+///
+/// Bars, spinners, and status lines are only drawn when stderr is a
+/// terminal; warnings are always printed.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::progress::{Reporter, TerminalReporter};
+///
+/// let reporter = TerminalReporter::new();
+/// reporter.task_started("Reviewing 3 files...");
+/// reporter.task_finished("Reviewed → 2 comments");
+/// ```
+pub struct TerminalReporter {
+    enabled: bool,
+    multi: MultiProgress,
+    main_bar: Mutex<Option<ProgressBar>>,
+    group_bar: Mutex<Option<ProgressBar>>,
+    task: Mutex<Option<ProgressBar>>,
+}
+
+impl TerminalReporter {
+    /// Create a reporter that draws only when stderr is a terminal.
+    pub fn new() -> Self {
+        Self {
+            enabled: std::io::stderr().is_terminal(),
+            multi: MultiProgress::new(),
+            main_bar: Mutex::new(None),
+            group_bar: Mutex::new(None),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Create a reporter that never draws bars, spinners, or status lines.
+    ///
+    /// Warnings are still printed. Use this for `--quiet`, where stderr may
+    /// be a terminal (e.g. behind a pager) but progress output is unwanted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_review::progress::{Reporter, TerminalReporter};
+    ///
+    /// let reporter = TerminalReporter::quiet();
+    /// reporter.status("not shown");
+    /// ```
+    pub fn quiet() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new()
+        }
+    }
+}
+
+impl Default for TerminalReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reporter for TerminalReporter {
+    fn groups_started(&self, groups: usize, files: usize) {
+        if !self.enabled {
+            return;
+        }
+        let pb = self.multi.add(ProgressBar::new(groups as u64));
+        pb.set_style(
+            ProgressStyle::with_template(
+                "{spinner:.cyan} Reviewing {msg} [{bar:20.cyan/dim}] {pos}/{len} groups ({elapsed})",
+            )
+            .unwrap()
+            .progress_chars("━╸─"),
+        );
+        pb.set_message(format!("{files} files"));
+        pb.enable_steady_tick(Duration::from_millis(120));
+        *self.main_bar.lock().unwrap() = Some(pb);
+    }
+
+    fn group_started(&self, index: usize, total: usize, label: &str) {
+        if !self.enabled {
+            return;
+        }
+        let pb = self.multi.add(ProgressBar::new_spinner());
+        pb.set_style(ProgressStyle::with_template("  {spinner:.dim} {msg}").unwrap());
+        pb.set_message(format!("[{}/{}] {label}...", index + 1, total));
+        pb.enable_steady_tick(Duration::from_millis(120));
+        *self.group_bar.lock().unwrap() = Some(pb);
+    }
+
+    fn group_finished(&self, index: usize, total: usize, label: &str, comments: usize) {
+        if let Some(pb) = self.group_bar.lock().unwrap().take() {
+            pb.finish_with_message(format!(
+                "[{}/{}] {label} → {comments} comment{}",
+                index + 1,
+                total,
+                if comments == 1 { "" } else { "s" },
+            ));
+        }
+        if let Some(pb) = self.main_bar.lock().unwrap().as_ref() {
+            pb.inc(1);
+        }
+    }
+
+    fn groups_finished(&self) {
+        if let Some(pb) = self.main_bar.lock().unwrap().take() {
+            pb.finish_and_clear();
+        }
+    }
+
+    fn task_started(&self, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})").unwrap());
+        pb.set_message(message.to_string());
+        pb.enable_steady_tick(Duration::from_millis(120));
+        *self.task.lock().unwrap() = Some(pb);
+    }
+
+    fn task_finished(&self, message: &str) {
+        if let Some(pb) = self.task.lock().unwrap().take() {
+            pb.finish_with_message(message.to_string());
+        }
+    }
+
+    fn status(&self, message: &str) {
+        if self.enabled {
+            eprintln!("{message}");
+        }
+    }
+
+    fn warning(&self, message: &str) {
+        eprintln!("warning: {message}");
+    }
+}
@@ -1,14 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use argus_core::{ArgusError, ReviewComment, ReviewConfig, Rule, Severity};
+use argus_codelens::search::MAX_EXPANSION_TERMS;
+use argus_core::{
+    ArgusError, ReviewComment, ReviewConfig, ReviewFocus, ReviewTone, Rule, Severity,
+};
 use serde::{Deserialize, Serialize};
 
 /// Build the system prompt for the code review LLM.
 ///
 /// Incorporates `max_comments` and severity configuration from [`ReviewConfig`]
-/// into the prompt text for better LLM adherence. When `rules` is non-empty,
-/// appends a project-specific rules section so the LLM checks for custom
-/// patterns defined by the project maintainers.
+/// into the prompt text for better LLM adherence. A `focus` other than
+/// [`ReviewFocus::General`] appends a block steering the review toward that
+/// area, and [`ReviewTone::Explanatory`] asks for a `rationale` on each
+/// finding. When `rules` is non-empty, appends a project-specific rules section
+/// so the LLM checks for custom patterns defined by the project maintainers.
+/// Rules with a `pattern` are left out, since they are matched
+/// deterministically by [`crate::patterns`].
 ///
 /// # Examples
 ///
@@ -73,8 +80,24 @@ pub fn build_system_prompt(
         max_comments = config.max_comments,
     );
 
-    if !rules.is_empty() {
-        let mut sorted_rules: Vec<&Rule> = rules.iter().collect();
+    if let Some(focus) = focus_instructions(config.focus) {
+        prompt.push_str("\n\n## Review Focus\n\n");
+        prompt.push_str(focus);
+    }
+
+    if config.tone == ReviewTone::Explanatory {
+        prompt.push_str("\n\n## Explanations\n\n");
+        prompt.push_str(
+            "The author may be new to this codebase or language. For each comment, add a \
+             \"rationale\" field: two or three sentences on why the issue matters and the \
+             general principle behind it (e.g. why unchecked indexing can panic, or why \
+             holding a lock across an await blocks other tasks), written to teach rather \
+             than to judge. Keep \"message\" itself short and specific to the code.",
+        );
+    }
+
+    let mut sorted_rules: Vec<&Rule> = rules.iter().filter(|r| r.pattern.is_none()).collect();
+    if !sorted_rules.is_empty() {
         sorted_rules.sort_by_key(|r| match r.severity.as_str() {
             "bug" => 0u8,
             "warning" => 1,
@@ -90,6 +113,7 @@ pub fn build_system_prompt(
                 rule.severity, rule.name, rule.description
             ));
         }
+        prompt.push_str("\nWhen a comment reports a violation of one of these rules, add a \"rule\" field to it with the rule name exactly as written above. Omit the field for comments that are not rule violations.\n");
     }
 
     if !negative_examples.is_empty() {
@@ -110,6 +134,34 @@ pub fn build_system_prompt(
     prompt
 }
 
+/// Extra instructions for a focused review, or `None` for a general one.
+fn focus_instructions(focus: ReviewFocus) -> Option<&'static str> {
+    match focus {
+        ReviewFocus::General => None,
+        ReviewFocus::Security => Some(
+            "This is a security-focused review. Prioritize vulnerabilities: injection \
+             (SQL, command, path traversal, template), missing or bypassable authentication \
+             and authorization checks, secrets or credentials in code or logs, cryptographic \
+             misuse (weak algorithms, hard-coded keys, predictable randomness), unsafe \
+             deserialization, and untrusted input reaching sensitive sinks. Skip issues with \
+             no security impact unless they are certain bugs.",
+        ),
+        ReviewFocus::Performance => Some(
+            "This is a performance-focused review, so performance problems count as \
+             defects. Prioritize unnecessary allocations and copies in hot paths, N+1 queries \
+             and repeated I/O inside loops, blocking I/O or locks held in async code, \
+             quadratic or worse algorithms on unbounded input, and unbounded memory growth. Explain the expected cost with a concrete input size. \
+             Skip issues with no performance impact unless they are certain bugs.",
+        ),
+        ReviewFocus::Correctness => Some(
+            "This is a correctness-focused review. Prioritize logic errors, unhandled edge \
+             cases (empty input, boundaries, overflow), swallowed or mishandled errors, \
+             incorrect state transitions, and concurrency bugs. Trace how each changed branch \
+             behaves for the inputs it can actually receive.",
+        ),
+    }
+}
+
 /// Build the user prompt containing the diff to review.
 ///
 /// When `cross_file_review` is `true`, appends an instruction block asking
@@ -171,6 +223,81 @@ pub fn build_review_prompt(
     prompt
 }
 
+/// Prompt section with the reviewer's instructions for one run
+/// (`review --instruction`), appended after the diff and its context.
+///
+/// Blank instructions are ignored; returns `None` if none are left.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::build_instructions_section;
+///
+/// let section = build_instructions_section(&["Check the retry logic".into()]).unwrap();
+/// assert!(section.contains("\n- Check the retry logic\n"));
+/// assert!(build_instructions_section(&[" ".into()]).is_none());
+/// ```
+pub fn build_instructions_section(instructions: &[String]) -> Option<String> {
+    let items: Vec<&str> = instructions
+        .iter()
+        .map(|i| i.trim())
+        .filter(|i| !i.is_empty())
+        .collect();
+    if items.is_empty() {
+        return None;
+    }
+    let mut section = String::from(
+        "\n## Reviewer Instructions\n\
+         The person requesting this review asked you to also follow these \
+         instructions. They add focus; they don't replace your review guidelines:\n",
+    );
+    for item in items {
+        section.push_str(&format!("- {item}\n"));
+    }
+    Some(section)
+}
+
+/// JSON Schema of the review response, for providers' structured-output
+/// modes (see [`ResponseFormat::Json`](crate::llm::ResponseFormat::Json)).
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::review_response_schema;
+///
+/// let schema = review_response_schema();
+/// assert_eq!(schema["required"][0], "comments");
+/// ```
+pub fn review_response_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "comments": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "file": { "type": "string" },
+                        "line": { "type": "integer" },
+                        "severity": {
+                            "type": "string",
+                            "enum": ["bug", "warning", "suggestion", "info"],
+                        },
+                        "message": { "type": "string" },
+                        "confidence": { "type": "number" },
+                        "suggestion": { "type": "string" },
+                        "patch": { "type": "string" },
+                        "rule": { "type": "string" },
+                        "rationale": { "type": "string" },
+                    },
+                    "required": ["file", "line", "severity", "message", "confidence"],
+                },
+            },
+        },
+        "required": ["comments"],
+    })
+}
+
 #[derive(Deserialize)]
 struct LlmResponse {
     comments: Vec<LlmComment>,
@@ -185,12 +312,15 @@ struct LlmComment {
     confidence: Option<serde_json::Value>,
     suggestion: Option<String>,
     patch: Option<String>,
+    rule: Option<String>,
+    rationale: Option<String>,
 }
 
 /// Parse the LLM JSON response into validated [`ReviewComment`] entries.
 ///
 /// Handles markdown code fences around JSON. Returns an empty vec on
-/// parse failure rather than propagating the error.
+/// parse failure rather than propagating the error, without printing
+/// anything.
 ///
 /// # Examples
 ///
@@ -202,18 +332,24 @@ struct LlmComment {
 /// assert!(comments.is_empty());
 /// ```
 pub fn parse_review_response(response: &str) -> Result<Vec<ReviewComment>, ArgusError> {
-    let cleaned = strip_code_fences(response);
+    Ok(decode_review_response(response).unwrap_or_default())
+}
 
-    let parsed: LlmResponse = match serde_json::from_str(cleaned) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("warning: failed to parse LLM response: {e}");
-            return Ok(Vec::new());
-        }
-    };
+/// Like [`parse_review_response`], but surfaces malformed JSON so the
+/// caller can report it.
+pub(crate) fn decode_review_response(
+    response: &str,
+) -> Result<Vec<ReviewComment>, serde_json::Error> {
+    let cleaned = strip_code_fences(response);
+    let parsed: LlmResponse = serde_json::from_str(cleaned)?;
+    Ok(convert_comments(parsed.comments))
+}
 
+/// Validate raw LLM comments, dropping entries with a missing line or an
+/// unknown severity.
+fn convert_comments(raw: Vec<LlmComment>) -> Vec<ReviewComment> {
     let mut comments = Vec::new();
-    for c in parsed.comments {
+    for c in raw {
         let line = match &c.line {
             Some(serde_json::Value::Number(n)) => {
                 let Some(l) = n.as_u64() else { continue };
@@ -249,11 +385,23 @@ pub fn parse_review_response(response: &str) -> Result<Vec<ReviewComment>, Argus
             confidence,
             suggestion: c.suggestion.clone(),
             patch: c.patch.clone(),
-            rule: None,
+            rule: c
+                .rule
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from),
+            rationale: c
+                .rationale
+                .as_deref()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(String::from),
+            ..Default::default()
         });
     }
 
-    Ok(comments)
+    comments
 }
 
 fn strip_code_fences(s: &str) -> &str {
@@ -291,14 +439,36 @@ fn strip_code_fences(s: &str) -> &str {
 ///     message: "Null dereference".into(),
 ///     confidence: 95.0,
 ///     suggestion: None,
-///     patch: None,
-///     rule: None,
+///     ..Default::default()
 /// }];
 /// let prompt = build_self_reflection_prompt(&comments, "+added line");
 /// assert!(prompt.contains("Null dereference"));
 /// assert!(prompt.contains("score"));
 /// ```
 pub fn build_self_reflection_prompt(comments: &[ReviewComment], diff: &str) -> String {
+    self_reflection_prompt(comments, diff, false)
+}
+
+/// Build a self-reflection prompt that also asks for missed issues.
+///
+/// Used by `self_reflection_mode = "filter_and_augment"`. On top of the
+/// evaluations requested by [`build_self_reflection_prompt`], the LLM may
+/// return an `additional_comments` array of high-confidence issues the
+/// first pass missed, read back with [`parse_self_reflection_additions`].
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::build_augmenting_self_reflection_prompt;
+///
+/// let prompt = build_augmenting_self_reflection_prompt(&[], "+added line");
+/// assert!(prompt.contains("additional_comments"));
+/// ```
+pub fn build_augmenting_self_reflection_prompt(comments: &[ReviewComment], diff: &str) -> String {
+    self_reflection_prompt(comments, diff, true)
+}
+
+fn self_reflection_prompt(comments: &[ReviewComment], diff: &str, augment: bool) -> String {
     use std::fmt::Write;
 
     let mut prompt = String::from(
@@ -343,6 +513,18 @@ pub fn build_self_reflection_prompt(comments: &[ReviewComment], diff: &str) -> S
         );
     }
 
+    if augment {
+        prompt.push_str(
+            "\n## Missed Issues\n\n\
+             After evaluating the comments above, read the diff again critically.\n\
+             If it contains a real bug or serious issue that none of the comments cover, add it to an\n\
+             \"additional_comments\" array next to \"evaluations\", using the same fields as a review:\n\
+             {\"file\": \"src/lib.rs\", \"line\": 42, \"severity\": \"bug\", \"message\": \"...\", \"confidence\": 95}\n\
+             Only add issues you are highly confident about and that are verifiable from the diff alone.\n\
+             Omit \"additional_comments\" (or leave it empty) if nothing was missed.\n",
+        );
+    }
+
     let _ = write!(prompt, "\n## Original Diff\n\n```diff\n{diff}\n```");
     prompt
 }
@@ -350,13 +532,14 @@ pub fn build_self_reflection_prompt(comments: &[ReviewComment], diff: &str) -> S
 #[derive(Deserialize)]
 struct SelfReflectionResponse {
     evaluations: Vec<SelfReflectionEval>,
+    #[serde(default)]
+    additional_comments: Vec<LlmComment>,
 }
 
 #[derive(Deserialize)]
 struct SelfReflectionEval {
     index: usize,
     score: u8,
-    #[allow(dead_code)]
     reason: Option<String>,
     revised_severity: Option<String>,
 }
@@ -364,7 +547,7 @@ struct SelfReflectionEval {
 /// Parse the self-reflection LLM response and return scored evaluations.
 ///
 /// Returns a vec of `(index, score, optional_revised_severity)` tuples.
-/// Invalid entries are silently skipped.
+/// Invalid entries are silently skipped, and malformed JSON yields an empty vec.
 ///
 /// # Examples
 ///
@@ -380,15 +563,33 @@ struct SelfReflectionEval {
 pub fn parse_self_reflection_response(
     response: &str,
 ) -> Result<Vec<(usize, u8, Option<Severity>)>, ArgusError> {
-    let cleaned = strip_code_fences(response);
+    Ok(decode_self_reflection_response(response)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|eval| (eval.index, eval.score, eval.revised_severity))
+        .collect())
+}
 
-    let parsed: SelfReflectionResponse = match serde_json::from_str(cleaned) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("warning: failed to parse self-reflection response: {e}");
-            return Ok(Vec::new());
-        }
-    };
+/// One comment's verdict from the self-reflection pass.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReflectionEval {
+    /// Index of the evaluated comment in the reviewed list.
+    pub index: usize,
+    /// Score from 1 to 10, capped at 10.
+    pub score: u8,
+    /// Severity the evaluator would give the comment instead, if any.
+    pub revised_severity: Option<Severity>,
+    /// The evaluator's justification, if it gave one.
+    pub reason: Option<String>,
+}
+
+/// Like [`parse_self_reflection_response`], but surfaces malformed JSON so
+/// the caller can report it, and keeps each evaluation's reason.
+pub(crate) fn decode_self_reflection_response(
+    response: &str,
+) -> Result<Vec<ReflectionEval>, serde_json::Error> {
+    let cleaned = strip_code_fences(response);
+    let parsed: SelfReflectionResponse = serde_json::from_str(cleaned)?;
 
     let mut results = Vec::new();
     for eval in parsed.evaluations {
@@ -402,12 +603,45 @@ pub fn parse_self_reflection_response(
                 "info" => Some(Severity::Info),
                 _ => None,
             });
-        results.push((eval.index, score, revised_sev));
+        let reason = eval
+            .reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+        results.push(ReflectionEval {
+            index: eval.index,
+            score,
+            revised_severity: revised_sev,
+            reason,
+        });
     }
 
     Ok(results)
 }
 
+/// Parse the `additional_comments` of an augmenting self-reflection
+/// response (see [`build_augmenting_self_reflection_prompt`]).
+///
+/// Entries are validated like review comments. A response without the
+/// array, or malformed JSON, yields an empty vec.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::parse_self_reflection_additions;
+///
+/// let json = r#"{"evaluations":[],"additional_comments":[
+///     {"file":"src/lib.rs","line":7,"severity":"bug","message":"Off-by-one","confidence":95}
+/// ]}"#;
+/// let added = parse_self_reflection_additions(json);
+/// assert_eq!(added.len(), 1);
+/// assert_eq!(added[0].line, 7);
+/// ```
+pub fn parse_self_reflection_additions(response: &str) -> Vec<ReviewComment> {
+    serde_json::from_str::<SelfReflectionResponse>(strip_code_fences(response))
+        .map(|parsed| convert_comments(parsed.additional_comments))
+        .unwrap_or_default()
+}
+
 /// Build the system prompt for PR description generation.
 ///
 /// Instructs the LLM to generate a title, description, and labels from a diff.
@@ -510,6 +744,77 @@ pub fn parse_describe_response(response: &str) -> Result<PrDescription, ArgusErr
     Ok(parsed)
 }
 
+/// Build the system prompt for `argus explain`.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::build_explain_system_prompt;
+///
+/// let prompt = build_explain_system_prompt();
+/// assert!(prompt.contains("step by step"));
+/// ```
+pub fn build_explain_system_prompt() -> String {
+    "You are Argus, a senior code reviewer explaining one review finding to the developer who \
+     wrote the code. They saw a terse comment and want to understand it.\n\
+     \n\
+     Answer in markdown with these sections:\n\
+     ## What is wrong\n\
+     The problem, tied to the specific lines involved.\n\
+     ## Why it matters\n\
+     What goes wrong at runtime, and under which inputs or conditions.\n\
+     ## How to fix it\n\
+     The fix step by step, ending with the corrected code in a fenced block.\n\
+     \n\
+     Be concrete and refer to names in the code. If the finding turns out to be wrong, say so \
+     plainly and explain why instead of inventing a problem."
+        .into()
+}
+
+/// Build the user prompt for `argus explain`: the code around `file:line`
+/// from [`crate::explain::finding_context`], and the finding there, if the
+/// last review left one. Without a finding the LLM is asked to find the
+/// most important issue on the line itself.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_review::prompt::build_explain_prompt;
+///
+/// let context = "### src/lib.rs (lines 1-3)\n```\n> 1 | let q = a / b;\n```\n";
+/// let prompt = build_explain_prompt(Path::new("src/lib.rs"), 1, context, None);
+/// assert!(prompt.contains("line 1 of src/lib.rs"));
+/// assert!(prompt.contains("let q = a / b;"));
+/// ```
+pub fn build_explain_prompt(
+    file: &Path,
+    line: u32,
+    context: &str,
+    finding: Option<&ReviewComment>,
+) -> String {
+    let location = format!("line {line} of {}", file.display());
+    let mut prompt = match finding {
+        Some(c) => {
+            let mut ask = format!(
+                "A review flagged {location} ({}):\n\n> {}\n",
+                c.severity, c.message
+            );
+            if let Some(suggestion) = &c.suggestion {
+                ask.push_str(&format!("\nIts suggestion: {suggestion}\n"));
+            }
+            ask.push_str("\nExplain this finding in depth.\n\n");
+            ask
+        }
+        None => format!(
+            "Find the most important bug, risk, or maintainability problem on {location}, \
+             marked with `>` below, and explain it in depth.\n\n"
+        ),
+    };
+    prompt.push_str(context);
+    prompt
+}
+
 /// Build a prompt asking the LLM to summarize the review findings.
 ///
 /// Takes the final review comments and the original diff text, producing
@@ -530,8 +835,7 @@ pub fn parse_describe_response(response: &str) -> Result<PrDescription, ArgusErr
 ///     message: "Null dereference".into(),
 ///     confidence: 95.0,
 ///     suggestion: None,
-///     patch: None,
-///     rule: None,
+///     ..Default::default()
 /// }];
 /// let prompt = build_summary_prompt(&comments, "+added line");
 /// assert!(prompt.contains("Null dereference"));
@@ -559,6 +863,63 @@ pub fn build_summary_prompt(comments: &[ReviewComment], diff: &str) -> String {
     prompt
 }
 
+/// Build a prompt asking the LLM for terms code might use instead of the
+/// words in a search query.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::build_query_expansion_prompt;
+///
+/// let prompt = build_query_expansion_prompt("auth");
+/// assert!(prompt.contains("\"auth\""));
+/// ```
+pub fn build_query_expansion_prompt(query: &str) -> String {
+    format!(
+        "A developer is searching a codebase for \"{query}\". Code often names the same \
+         idea differently (e.g. \"auth\" vs. \"login\", \"credentials\", \"session\").\n\n\
+         List up to {MAX_EXPANSION_TERMS} single-word or identifier-style terms the code \
+         might use instead. One term per line, no numbering, no explanation."
+    )
+}
+
+/// Parse the terms from a [`build_query_expansion_prompt`] response.
+///
+/// Strips list markers and quotes, skips terms already in `query`, and keeps
+/// at most [`MAX_EXPANSION_TERMS`].
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::prompt::parse_query_expansion_response;
+///
+/// let terms = parse_query_expansion_response("- login\n2. `session`\nauth\n", "auth");
+/// assert_eq!(terms, ["login", "session"]);
+/// ```
+pub fn parse_query_expansion_response(response: &str, query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    let mut terms: Vec<String> = Vec::new();
+    for line in strip_code_fences(response).lines() {
+        let mut term = line.trim().trim_start_matches(['-', '*']).trim_start();
+        if let Some((number, rest)) = term.split_once(['.', ')']) {
+            if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                term = rest.trim_start();
+            }
+        }
+        let term = term.trim_matches(['`', '"', '\'']).trim();
+        if term.is_empty()
+            || term.contains(char::is_whitespace)
+            || query.split_whitespace().any(|w| w == term.to_lowercase())
+            || terms.iter().any(|t| t.eq_ignore_ascii_case(term))
+        {
+            continue;
+        }
+        terms.push(term.to_string());
+    }
+    terms.truncate(MAX_EXPANSION_TERMS);
+    terms
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -594,6 +955,61 @@ mod tests {
         assert!(!prompt.contains("ONLY include if explicitly enabled"));
     }
 
+    #[test]
+    fn system_prompt_adds_focus_block_only_when_focused() {
+        let prompt = build_system_prompt(&ReviewConfig::default(), &[], &[]);
+        assert!(!prompt.contains("## Review Focus"));
+
+        let config = ReviewConfig {
+            focus: ReviewFocus::Security,
+            ..ReviewConfig::default()
+        };
+        let prompt = build_system_prompt(&config, &[], &[]);
+        assert!(prompt.contains("## Review Focus"));
+        assert!(prompt.contains("security-focused"));
+        assert!(prompt.contains("injection"));
+
+        let config = ReviewConfig {
+            focus: ReviewFocus::Performance,
+            ..ReviewConfig::default()
+        };
+        assert!(build_system_prompt(&config, &[], &[]).contains("N+1 queries"));
+    }
+
+    #[test]
+    fn explanatory_tone_asks_for_rationale() {
+        let prompt = build_system_prompt(&ReviewConfig::default(), &[], &[]);
+        assert!(!prompt.contains("\"rationale\""));
+
+        let config = ReviewConfig {
+            tone: ReviewTone::Explanatory,
+            ..ReviewConfig::default()
+        };
+        let prompt = build_system_prompt(&config, &[], &[]);
+        assert!(prompt.contains("## Explanations"));
+        assert!(prompt.contains("\"rationale\" field"));
+
+        let json = r#"{"comments":[
+            {"file":"a.rs","line":3,"severity":"bug","message":"index may be out of bounds","confidence":95,"rationale":" Indexing a Vec panics when the index is past the end. "},
+            {"file":"a.rs","line":4,"severity":"bug","message":"other","confidence":95}
+        ]}"#;
+        let comments = parse_review_response(json).unwrap();
+        assert_eq!(
+            comments[0].rationale.as_deref(),
+            Some("Indexing a Vec panics when the index is past the end.")
+        );
+        assert!(comments[1].rationale.is_none());
+    }
+
+    #[test]
+    fn response_schema_allows_optional_rationale() {
+        let schema = review_response_schema();
+        let item = &schema["properties"]["comments"]["items"];
+        assert_eq!(item["properties"]["rationale"]["type"], "string");
+        let required = item["required"].as_array().unwrap();
+        assert!(!required.iter().any(|field| field == "rationale"));
+    }
+
     #[test]
     fn review_prompt_includes_diff() {
         let prompt = build_review_prompt("+added line", None, None, None, None, false);
@@ -730,11 +1146,13 @@ mod tests {
                 name: "no-unwrap".into(),
                 severity: "warning".into(),
                 description: "Do not use .unwrap() in production code".into(),
+                ..Default::default()
             },
             Rule {
                 name: "no-panic".into(),
                 severity: "bug".into(),
                 description: "Never use panic! in library code".into(),
+                ..Default::default()
             },
         ];
         let prompt = build_system_prompt(&config, &rules, &[]);
@@ -743,6 +1161,18 @@ mod tests {
         assert!(prompt.contains("no-panic"));
         assert!(prompt.contains("Do not use .unwrap() in production code"));
         assert!(prompt.contains("Never use panic! in library code"));
+        assert!(prompt.contains("add a \"rule\" field"));
+    }
+
+    #[test]
+    fn parse_review_response_reads_rule_field() {
+        let json = r#"{"comments":[
+            {"file":"a.rs","line":1,"severity":"warning","message":"unwrap on input","confidence":95,"rule":" no-unwrap "},
+            {"file":"a.rs","line":2,"severity":"warning","message":"other","confidence":95,"rule":""}
+        ]}"#;
+        let comments = parse_review_response(json).unwrap();
+        assert_eq!(comments[0].rule.as_deref(), Some("no-unwrap"));
+        assert!(comments[1].rule.is_none());
     }
 
     #[test]
@@ -752,6 +1182,20 @@ mod tests {
         assert!(!prompt.contains("Project-Specific Rules"));
     }
 
+    #[test]
+    fn system_prompt_omits_pattern_rules() {
+        let config = ReviewConfig::default();
+        let rules = vec![Rule {
+            name: "no-dbg".into(),
+            severity: "warning".into(),
+            description: "Remove dbg! before merging".into(),
+            pattern: Some(r"\bdbg!\(".into()),
+            paths: None,
+        }];
+        let prompt = build_system_prompt(&config, &rules, &[]);
+        assert!(!prompt.contains("Project-Specific Rules"));
+    }
+
     #[test]
     fn system_prompt_rules_sorted_by_severity() {
         let config = ReviewConfig::default();
@@ -760,16 +1204,19 @@ mod tests {
                 name: "style-check".into(),
                 severity: "suggestion".into(),
                 description: "Check style".into(),
+                ..Default::default()
             },
             Rule {
                 name: "warn-check".into(),
                 severity: "warning".into(),
                 description: "Check warnings".into(),
+                ..Default::default()
             },
             Rule {
                 name: "critical-bug".into(),
                 severity: "bug".into(),
                 description: "Check bugs".into(),
+                ..Default::default()
             },
         ];
         let prompt = build_system_prompt(&config, &rules, &[]);
@@ -793,8 +1240,7 @@ mod tests {
                 message: "Null pointer dereference".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("src/db.rs"),
@@ -803,8 +1249,7 @@ mod tests {
                 message: "SQL injection risk".into(),
                 confidence: 88.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
         let prompt = build_summary_prompt(&comments, "+added line");
@@ -856,8 +1301,7 @@ mod tests {
                 message: "Null pointer dereference".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("src/db.rs"),
@@ -866,8 +1310,7 @@ mod tests {
                 message: "SQL injection risk".into(),
                 confidence: 88.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
         let prompt = build_self_reflection_prompt(&comments, "+added line");
@@ -880,6 +1323,35 @@ mod tests {
         assert!(prompt.contains("1-10"));
     }
 
+    #[test]
+    fn only_augmenting_reflection_prompt_asks_for_missed_issues() {
+        assert!(!build_self_reflection_prompt(&[], "+x").contains("additional_comments"));
+        let prompt = build_augmenting_self_reflection_prompt(&[], "+x");
+        assert!(prompt.contains("additional_comments"));
+        assert!(prompt.ends_with("```diff\n+x\n```"));
+    }
+
+    #[test]
+    fn parse_self_reflection_additions_validates_entries() {
+        let json = r#"{"evaluations":[{"index":0,"score":9}],"additional_comments":[
+            {"file":"src/a.rs","line":3,"severity":"bug","message":"Missed","confidence":92},
+            {"file":"src/a.rs","line":0,"severity":"bug","message":"No line"},
+            {"file":"src/a.rs","line":4,"severity":"nitpick","message":"Bad severity"}
+        ]}"#;
+        let added = parse_self_reflection_additions(json);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].message, "Missed");
+        assert_eq!(added[0].confidence, 92.0);
+
+        // Evaluations still parse alongside the extra array
+        assert_eq!(
+            parse_self_reflection_response(json).unwrap(),
+            [(0, 9, None)]
+        );
+        assert!(parse_self_reflection_additions(r#"{"evaluations":[]}"#).is_empty());
+        assert!(parse_self_reflection_additions("not json").is_empty());
+    }
+
     #[test]
     fn parse_self_reflection_valid() {
         let json = r#"{"evaluations":[
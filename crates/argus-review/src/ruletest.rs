@@ -0,0 +1,535 @@
+//! Fixture-based tests for custom `[[rules]]`.
+//!
+//! A fixture directory holds pairs of files: `<name>.diff` with the change
+//! to review, and `<name>.json` listing the rules expected to fire on it:
+//!
+//! ```json
+//! { "expected": ["no-unwrap"] }
+//! ```
+//!
+//! Every configured rule not listed is expected to stay silent. After each
+//! fixture is reviewed, [`evaluate`] compares the rules that fired with the
+//! expectation and [`RuleTestReport`] summarizes pass/fail per rule.
+//!
+//! Outside fixtures, [`rule_matches`] reports which rules fired on a single
+//! review (`argus review --rules-report`).
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use argus_core::{ArgusError, ReviewComment, Rule};
+use serde::{Deserialize, Serialize};
+
+/// A diff plus the rules expected to fire on it.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::ruletest::RuleFixture;
+///
+/// let fixture = RuleFixture {
+///     name: "unwrap-in-handler".into(),
+///     diff: String::new(),
+///     expected: vec!["no-unwrap".into()],
+/// };
+/// assert_eq!(fixture.expected.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuleFixture {
+    /// Fixture name (the diff file stem).
+    pub name: String,
+    /// Unified diff to review.
+    pub diff: String,
+    /// Names of the rules expected to fire.
+    pub expected: Vec<String>,
+}
+
+/// On-disk expectation spec (`<name>.json`).
+#[derive(Debug, Deserialize)]
+struct FixtureSpec {
+    #[serde(default)]
+    expected: Vec<String>,
+}
+
+/// Load every `<name>.diff` / `<name>.json` pair in `dir`, sorted by name.
+///
+/// # Errors
+///
+/// Returns [`ArgusError::Io`] if the directory or a fixture cannot be read,
+/// and [`ArgusError::Config`] if a diff has no spec, a spec is malformed,
+/// or the directory contains no fixtures.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::ruletest::load_fixtures;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(dir.path().join("case.diff"), "diff --git a/a.rs b/a.rs\n").unwrap();
+/// std::fs::write(dir.path().join("case.json"), r#"{"expected": ["no-unwrap"]}"#).unwrap();
+///
+/// let fixtures = load_fixtures(dir.path()).unwrap();
+/// assert_eq!(fixtures[0].name, "case");
+/// assert_eq!(fixtures[0].expected, ["no-unwrap"]);
+/// ```
+pub fn load_fixtures(dir: &Path) -> Result<Vec<RuleFixture>, ArgusError> {
+    let mut fixtures = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("diff") {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let spec_path = path.with_extension("json");
+        let spec = std::fs::read_to_string(&spec_path).map_err(|_| {
+            ArgusError::Config(format!(
+                "fixture {name} has no spec: expected {}",
+                spec_path.display()
+            ))
+        })?;
+        let spec: FixtureSpec = serde_json::from_str(&spec)
+            .map_err(|e| ArgusError::Config(format!("invalid fixture spec {name}.json: {e}")))?;
+        fixtures.push(RuleFixture {
+            name,
+            diff: std::fs::read_to_string(&path)?,
+            expected: spec.expected,
+        });
+    }
+
+    if fixtures.is_empty() {
+        return Err(ArgusError::Config(format!(
+            "no fixtures found in {} (expected <name>.diff + <name>.json pairs)",
+            dir.display()
+        )));
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(fixtures)
+}
+
+/// Outcome of reviewing one fixture.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::ruletest::FixtureResult;
+///
+/// let result = FixtureResult {
+///     name: "case".into(),
+///     fired: vec!["no-unwrap".into()],
+///     missed: vec![],
+///     false_fired: vec![],
+/// };
+/// assert!(result.passed());
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureResult {
+    /// Fixture name.
+    pub name: String,
+    /// Rules that fired.
+    pub fired: Vec<String>,
+    /// Expected rules that did not fire.
+    pub missed: Vec<String>,
+    /// Rules that fired without being expected.
+    pub false_fired: Vec<String>,
+}
+
+impl FixtureResult {
+    /// Whether the fired rules matched the expectation exactly.
+    pub fn passed(&self) -> bool {
+        self.missed.is_empty() && self.false_fired.is_empty()
+    }
+}
+
+/// Compare the rules tagged on a fixture's review comments with its
+/// expectation.
+///
+/// Only configured `rules` are considered: a comment tagged with an unknown
+/// rule name is ignored, and an expected name that is not configured always
+/// counts as missed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Rule, Severity};
+/// use argus_review::ruletest::{evaluate, RuleFixture};
+///
+/// let rule = |name: &str| Rule {
+///     name: name.into(),
+///     severity: "warning".into(),
+///     description: String::new(),
+///     ..Default::default()
+/// };
+/// let fixture = RuleFixture {
+///     name: "case".into(),
+///     diff: String::new(),
+///     expected: vec!["no-unwrap".into()],
+/// };
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("a.rs"),
+///     line: 1,
+///     severity: Severity::Warning,
+///     message: "no-todo: remove TODO".into(),
+///     confidence: 90.0,
+///     suggestion: None,
+///     rule: Some("no-todo".into()),
+///     ..Default::default()
+/// };
+///
+/// let result = evaluate(&fixture, &[comment], &[rule("no-unwrap"), rule("no-todo")]);
+/// assert_eq!(result.missed, ["no-unwrap"]);
+/// assert_eq!(result.false_fired, ["no-todo"]);
+/// ```
+pub fn evaluate(
+    fixture: &RuleFixture,
+    comments: &[ReviewComment],
+    rules: &[Rule],
+) -> FixtureResult {
+    let configured: BTreeSet<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+    let fired: BTreeSet<&str> = comments
+        .iter()
+        .filter_map(|c| c.rule.as_deref())
+        .filter(|name| configured.contains(name))
+        .collect();
+    let expected: BTreeSet<&str> = fixture.expected.iter().map(String::as_str).collect();
+
+    FixtureResult {
+        name: fixture.name.clone(),
+        fired: fired.iter().map(|s| s.to_string()).collect(),
+        missed: expected.difference(&fired).map(|s| s.to_string()).collect(),
+        false_fired: fired.difference(&expected).map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Pass/fail tally for one rule across all fixtures.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::ruletest::RuleSummary;
+///
+/// let summary = RuleSummary {
+///     rule: "no-unwrap".into(),
+///     passed: 3,
+///     missed: 1,
+///     false_fired: 0,
+/// };
+/// assert!(!summary.ok());
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSummary {
+    /// Rule name.
+    pub rule: String,
+    /// Fixtures where the rule behaved as expected (fired or stayed silent).
+    pub passed: usize,
+    /// Fixtures where the rule was expected but did not fire.
+    pub missed: usize,
+    /// Fixtures where the rule fired without being expected.
+    pub false_fired: usize,
+}
+
+impl RuleSummary {
+    /// Whether the rule behaved as expected on every fixture.
+    pub fn ok(&self) -> bool {
+        self.missed == 0 && self.false_fired == 0
+    }
+}
+
+/// Results for a whole fixture directory.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestReport {
+    /// Per-fixture outcomes, in fixture order.
+    pub fixtures: Vec<FixtureResult>,
+    /// Per-rule tallies, in configuration order.
+    pub rules: Vec<RuleSummary>,
+}
+
+impl RuleTestReport {
+    /// Tally `fixtures` per configured rule.
+    ///
+    /// Rules that are expected by a fixture but not configured get their own
+    /// (always failing) summary after the configured ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argus_core::Rule;
+    /// use argus_review::ruletest::{FixtureResult, RuleTestReport};
+    ///
+    /// let rules = vec![Rule {
+    ///     name: "no-unwrap".into(),
+    ///     severity: "warning".into(),
+    ///     description: String::new(),
+    ///     ..Default::default()
+    /// }];
+    /// let fixtures = vec![
+    ///     FixtureResult { name: "hit".into(), fired: vec!["no-unwrap".into()], missed: vec![], false_fired: vec![] },
+    ///     FixtureResult { name: "clean".into(), fired: vec![], missed: vec![], false_fired: vec![] },
+    /// ];
+    /// let report = RuleTestReport::new(fixtures, &rules);
+    /// assert_eq!(report.rules[0].passed, 2);
+    /// assert!(report.passed());
+    /// ```
+    pub fn new(fixtures: Vec<FixtureResult>, rules: &[Rule]) -> Self {
+        let mut names: Vec<String> = rules.iter().map(|r| r.name.clone()).collect();
+        for fixture in &fixtures {
+            for name in &fixture.missed {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+
+        let rules = names
+            .into_iter()
+            .map(|rule| {
+                let missed = fixtures.iter().filter(|f| f.missed.contains(&rule)).count();
+                let false_fired = fixtures
+                    .iter()
+                    .filter(|f| f.false_fired.contains(&rule))
+                    .count();
+                RuleSummary {
+                    passed: fixtures.len() - missed - false_fired,
+                    rule,
+                    missed,
+                    false_fired,
+                }
+            })
+            .collect();
+
+        Self { fixtures, rules }
+    }
+
+    /// Whether every fixture passed.
+    pub fn passed(&self) -> bool {
+        self.fixtures.iter().all(FixtureResult::passed)
+    }
+}
+
+/// The comments one rule was tagged on in a review.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_review::ruletest::RuleMatches;
+///
+/// let matches = RuleMatches {
+///     rule: "no-unwrap".into(),
+///     locations: vec![(PathBuf::from("src/lib.rs"), 12)],
+/// };
+/// assert_eq!(matches.count(), 1);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleMatches {
+    /// Rule name.
+    pub rule: String,
+    /// File and line of each matched comment, sorted.
+    pub locations: Vec<(PathBuf, u32)>,
+}
+
+impl RuleMatches {
+    /// Number of comments the rule matched.
+    pub fn count(&self) -> usize {
+        self.locations.len()
+    }
+}
+
+/// Group review comments by the rule they were tagged with.
+///
+/// Every configured rule gets an entry, in configuration order, so rules
+/// that never fire show up with a count of zero. Tags that are not
+/// configured rules, such as deterministic breaking-change findings,
+/// follow in name order.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Rule, Severity};
+/// use argus_review::ruletest::rule_matches;
+///
+/// let rules = vec![Rule {
+///     name: "no-unwrap".into(),
+///     severity: "warning".into(),
+///     description: String::new(),
+///     ..Default::default()
+/// }];
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("src/lib.rs"),
+///     line: 12,
+///     severity: Severity::Warning,
+///     message: "unwrap on user input".into(),
+///     confidence: 95.0,
+///     suggestion: None,
+///     rule: Some("no-unwrap".into()),
+///     ..Default::default()
+/// };
+///
+/// let report = rule_matches(&[comment], &rules);
+/// assert_eq!(report[0].rule, "no-unwrap");
+/// assert_eq!(report[0].count(), 1);
+/// ```
+pub fn rule_matches(comments: &[ReviewComment], rules: &[Rule]) -> Vec<RuleMatches> {
+    let unconfigured: BTreeSet<&str> = comments
+        .iter()
+        .filter_map(|c| c.rule.as_deref())
+        .filter(|name| !rules.iter().any(|r| r.name == *name))
+        .collect();
+
+    rules
+        .iter()
+        .map(|r| r.name.as_str())
+        .chain(unconfigured)
+        .map(|name| {
+            let mut locations: Vec<(PathBuf, u32)> = comments
+                .iter()
+                .filter(|c| c.rule.as_deref() == Some(name))
+                .map(|c| (c.file_path.clone(), c.line))
+                .collect();
+            locations.sort();
+            RuleMatches {
+                rule: name.to_string(),
+                locations,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argus_core::Severity;
+
+    fn rule(name: &str) -> Rule {
+        Rule {
+            name: name.into(),
+            severity: "warning".into(),
+            description: format!("{name} description"),
+            ..Default::default()
+        }
+    }
+
+    fn tagged(rule: Option<&str>) -> ReviewComment {
+        ReviewComment {
+            file_path: PathBuf::from("src/lib.rs"),
+            line: 3,
+            severity: Severity::Warning,
+            message: "finding".into(),
+            confidence: 90.0,
+            suggestion: None,
+            rule: rule.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn fixture(expected: &[&str]) -> RuleFixture {
+        RuleFixture {
+            name: "case".into(),
+            diff: String::new(),
+            expected: expected.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn evaluate_classifies_fired_missed_and_false_fired() {
+        let rules = [rule("a"), rule("b"), rule("c")];
+        let comments = [tagged(Some("a")), tagged(Some("c")), tagged(None)];
+
+        let result = evaluate(&fixture(&["a", "b"]), &comments, &rules);
+        assert_eq!(result.fired, ["a", "c"]);
+        assert_eq!(result.missed, ["b"]);
+        assert_eq!(result.false_fired, ["c"]);
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn evaluate_ignores_unconfigured_rule_tags() {
+        let result = evaluate(&fixture(&[]), &[tagged(Some("other"))], &[rule("a")]);
+        assert!(result.fired.is_empty());
+        assert!(result.passed());
+    }
+
+    #[test]
+    fn report_tallies_per_rule() {
+        let rules = [rule("a"), rule("b")];
+        let fixtures = vec![
+            evaluate(&fixture(&["a"]), &[tagged(Some("a"))], &rules),
+            evaluate(&fixture(&["a"]), &[], &rules),
+            evaluate(&fixture(&[]), &[tagged(Some("b"))], &rules),
+            evaluate(&fixture(&["typo"]), &[], &rules),
+        ];
+
+        let report = RuleTestReport::new(fixtures, &rules);
+        assert!(!report.passed());
+        let by_name = |name: &str| report.rules.iter().find(|r| r.rule == name).unwrap();
+        assert_eq!(
+            (
+                by_name("a").passed,
+                by_name("a").missed,
+                by_name("a").false_fired
+            ),
+            (3, 1, 0)
+        );
+        assert_eq!(
+            (
+                by_name("b").passed,
+                by_name("b").missed,
+                by_name("b").false_fired
+            ),
+            (3, 0, 1)
+        );
+        assert_eq!(by_name("typo").missed, 1);
+        assert!(!by_name("typo").ok());
+    }
+
+    #[test]
+    fn rule_matches_counts_every_configured_rule() {
+        let at = |path: &str, line: u32, rule: &str| ReviewComment {
+            file_path: PathBuf::from(path),
+            line,
+            rule: Some(rule.into()),
+            ..tagged(None)
+        };
+        let comments = [
+            at("src/b.rs", 9, "a"),
+            at("src/a.rs", 4, "a"),
+            at("src/a.rs", 2, "breaking-change"),
+            tagged(None),
+        ];
+
+        let report = rule_matches(&comments, &[rule("a"), rule("b")]);
+        let names: Vec<&str> = report.iter().map(|m| m.rule.as_str()).collect();
+        assert_eq!(names, ["a", "b", "breaking-change"]);
+        assert_eq!(
+            report[0].locations,
+            [
+                (PathBuf::from("src/a.rs"), 4),
+                (PathBuf::from("src/b.rs"), 9)
+            ]
+        );
+        assert_eq!(report[1].count(), 0);
+        assert_eq!(report[2].count(), 1);
+    }
+
+    #[test]
+    fn load_fixtures_requires_a_spec_per_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("orphan.diff"), "").unwrap();
+        let err = load_fixtures(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("orphan"), "{err}");
+    }
+
+    #[test]
+    fn load_fixtures_rejects_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_fixtures(dir.path()).is_err());
+    }
+}
@@ -11,7 +11,7 @@ use crate::pipeline::ReviewResult;
 /// # Examples
 ///
 /// ```
-/// use argus_review::pipeline::{ReviewResult, ReviewStats};
+/// use argus_review::pipeline::{ReviewResult, ReviewStats, ReviewTimings};
 /// use argus_review::sarif::to_sarif;
 ///
 /// let result = ReviewResult {
@@ -32,6 +32,8 @@ use crate::pipeline::ReviewResult;
 ///         llm_retries: 0,
 ///         file_groups: vec![],
 ///         hotspot_files: 0,
+///         moved_blocks: vec![],
+///         timings: ReviewTimings::default(),
 ///     },
 /// };
 /// let sarif = to_sarif(&result);
@@ -158,7 +160,7 @@ mod tests {
     use argus_core::{ReviewComment, Severity};
 
     use super::*;
-    use crate::pipeline::{ReviewResult, ReviewStats};
+    use crate::pipeline::{ReviewResult, ReviewStats, ReviewTimings};
 
     fn make_result(comments: Vec<ReviewComment>) -> ReviewResult {
         ReviewResult {
@@ -179,6 +181,8 @@ mod tests {
                 llm_retries: 0,
                 file_groups: vec![],
                 hotspot_files: 0,
+                moved_blocks: vec![],
+                timings: ReviewTimings::default(),
             },
         }
     }
@@ -230,8 +234,7 @@ mod tests {
                 message: "Null dereference".into(),
                 confidence: 95.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("src/db.rs"),
@@ -240,8 +243,7 @@ mod tests {
                 message: "SQL injection risk".into(),
                 confidence: 88.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
         let result = make_result(comments);
@@ -275,8 +277,7 @@ mod tests {
                 message: "bug 1".into(),
                 confidence: 90.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
             ReviewComment {
                 file_path: PathBuf::from("b.rs"),
@@ -285,8 +286,7 @@ mod tests {
                 message: "bug 2".into(),
                 confidence: 90.0,
                 suggestion: None,
-                patch: None,
-                rule: None,
+                ..Default::default()
             },
         ];
         let result = make_result(comments);
@@ -0,0 +1,237 @@
+//! Nested `.argus.toml` files for monorepos.
+//!
+//! A directory below the repository root can hold its own `.argus.toml`.
+//! Each changed file is reviewed with the configuration of its nearest
+//! ancestor directory that has one, laid over the root configuration with
+//! [`ArgusConfig::overlay_toml`], so a service can set its own rules, skip
+//! patterns, and LLM settings. Paths in a nested file, such as
+//! `skip_patterns` and rule `paths`, stay relative to the repository root.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use argus_core::{ArgusConfig, ArgusError};
+use argus_difflens::parser::{normalize_path, FileDiff};
+
+/// The configuration that applies to the files under a directory.
+#[derive(Debug, Clone)]
+pub struct ConfigScope {
+    /// Directory relative to the repository root; empty for the root scope.
+    pub dir: PathBuf,
+    /// The root configuration with the directory's `.argus.toml` laid over it.
+    pub config: ArgusConfig,
+}
+
+/// The scopes the files in `diffs` fall in: first the repository root with
+/// `root_config`, then each directory below `root` with a `.argus.toml`
+/// that is the nearest one to a changed file, in diff order.
+///
+/// A nested file is laid over `root_config` alone, not over the files of
+/// directories between it and the root.
+///
+/// # Errors
+///
+/// Returns [`ArgusError`] if a nested `.argus.toml` cannot be read or is
+/// not a valid configuration.
+pub fn discover_scopes(
+    diffs: &[FileDiff],
+    root: &Path,
+    root_config: &ArgusConfig,
+) -> Result<Vec<ConfigScope>, ArgusError> {
+    let mut scopes = vec![ConfigScope {
+        dir: PathBuf::new(),
+        config: root_config.clone(),
+    }];
+    let mut has_config: HashMap<PathBuf, bool> = HashMap::new();
+    for diff in diffs {
+        let file = normalize_path(&diff.new_path, Some(root));
+        let Some(dir) = nearest_config_dir(&file, root, &mut has_config) else {
+            continue;
+        };
+        if scopes.iter().any(|scope| scope.dir == dir) {
+            continue;
+        }
+        let path = root.join(&dir).join(".argus.toml");
+        let content = std::fs::read_to_string(&path)?;
+        let config = root_config.overlay_toml(&content).map_err(|e| {
+            ArgusError::Config(format!("invalid configuration in {}: {e}", path.display()))
+        })?;
+        scopes.push(ConfigScope { dir, config });
+    }
+    Ok(scopes)
+}
+
+/// Split `diffs` by scope: for each scope with changed files, its index in
+/// `scopes` and those files' diffs. Each file goes to the scope with the
+/// longest `dir` containing its path relative to `root`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use argus_core::ArgusConfig;
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_review::scopes::{split_by_scope, ConfigScope};
+///
+/// let scopes = vec![
+///     ConfigScope { dir: PathBuf::new(), config: ArgusConfig::default() },
+///     ConfigScope { dir: PathBuf::from("services/api"), config: ArgusConfig::default() },
+/// ];
+/// let diff = "\
+/// --- a/services/api/main.go
+/// +++ b/services/api/main.go
+/// @@ -1 +1 @@
+/// -a
+/// +b
+/// ";
+/// let parts = split_by_scope(&scopes, parse_unified_diff(diff).unwrap(), Path::new("."));
+/// assert_eq!(parts.len(), 1);
+/// assert_eq!(parts[0].0, 1);
+/// ```
+pub fn split_by_scope(
+    scopes: &[ConfigScope],
+    diffs: Vec<FileDiff>,
+    root: &Path,
+) -> Vec<(usize, Vec<FileDiff>)> {
+    let mut parts: Vec<(usize, Vec<FileDiff>)> = Vec::new();
+    for diff in diffs {
+        let file = normalize_path(&diff.new_path, Some(root));
+        let index = scopes
+            .iter()
+            .enumerate()
+            .filter(|(_, scope)| file.starts_with(&scope.dir))
+            .max_by_key(|(_, scope)| scope.dir.components().count())
+            .map_or(0, |(index, _)| index);
+        match parts.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, part)) => part.push(diff),
+            None => parts.push((index, vec![diff])),
+        }
+    }
+    parts.sort_by_key(|(index, _)| *index);
+    parts
+}
+
+/// The nearest directory above `file` (relative to `root`), below `root`,
+/// holding a `.argus.toml`. `has_config` caches the lookups.
+fn nearest_config_dir(
+    file: &Path,
+    root: &Path,
+    has_config: &mut HashMap<PathBuf, bool>,
+) -> Option<PathBuf> {
+    if file.is_absolute() {
+        return None;
+    }
+    file.ancestors()
+        .skip(1)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .find(|dir| {
+            *has_config
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| root.join(dir).join(".argus.toml").is_file())
+        })
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::LlmClient;
+    use crate::pipeline::{ReviewPipeline, ReviewResult};
+    use argus_difflens::parser::parse_unified_diff;
+
+    #[test]
+    fn each_service_is_reviewed_with_its_own_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (service, rule) in [("billing", "no-float-money"), ("auth", "no-plaintext")] {
+            std::fs::create_dir_all(root.join("services").join(service).join("src")).unwrap();
+            std::fs::write(
+                root.join("services").join(service).join(".argus.toml"),
+                format!(
+                    "[review]\nskip_patterns = [\"services/{service}/generated/**\"]\n\n\
+                     [[rules]]\nname = \"{rule}\"\nseverity = \"bug\"\n\
+                     description = \"Forbidden\"\npattern = \"TODO_{service}\"\n"
+                ),
+            )
+            .unwrap();
+        }
+        let root_config = ArgusConfig::from_toml(
+            "[[rules]]\nname = \"root-rule\"\nseverity = \"warning\"\n\
+             description = \"Root\"\npattern = \"TODO_\"\n",
+        )
+        .unwrap();
+
+        let diff = "\
+diff --git a/services/billing/src/pay.rs b/services/billing/src/pay.rs
+--- a/services/billing/src/pay.rs
++++ b/services/billing/src/pay.rs
+@@ -1 +1,2 @@
+ fn pay() {}
++// TODO_billing TODO_auth
+diff --git a/services/auth/src/login.rs b/services/auth/src/login.rs
+--- a/services/auth/src/login.rs
++++ b/services/auth/src/login.rs
+@@ -1 +1,2 @@
+ fn login() {}
++// TODO_auth TODO_billing
+diff --git a/services/auth/generated/api.rs b/services/auth/generated/api.rs
+--- a/services/auth/generated/api.rs
++++ b/services/auth/generated/api.rs
+@@ -1 +1,2 @@
+ fn api() {}
++// TODO_auth
+diff --git a/tools/build.rs b/tools/build.rs
+--- a/tools/build.rs
++++ b/tools/build.rs
+@@ -1 +1,2 @@
+ fn main() {}
++// TODO_tools
+";
+        let diffs = parse_unified_diff(diff).unwrap();
+        let scopes = discover_scopes(&diffs, root, &root_config).unwrap();
+        let dirs: Vec<&Path> = scopes.iter().map(|s| s.dir.as_path()).collect();
+        assert_eq!(
+            dirs,
+            [
+                Path::new(""),
+                Path::new("services/billing"),
+                Path::new("services/auth"),
+            ]
+        );
+
+        let results = split_by_scope(&scopes, diffs, root)
+            .into_iter()
+            .map(|(index, part)| {
+                let config = &scopes[index].config;
+                let llm = LlmClient::new(&config.llm).unwrap();
+                ReviewPipeline::new(llm, config.review.clone(), config.rules.clone())
+                    .review_offline(part, Some(root))
+            })
+            .collect();
+        let result = ReviewResult::merge(results);
+
+        let mut found: Vec<(String, String)> = result
+            .comments
+            .iter()
+            .map(|c| {
+                (
+                    c.file_path.display().to_string(),
+                    c.rule.clone().unwrap_or_default(),
+                )
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("services/auth/src/login.rs".into(), "no-plaintext".into()),
+                (
+                    "services/billing/src/pay.rs".into(),
+                    "no-float-money".into()
+                ),
+                ("tools/build.rs".into(), "root-rule".into()),
+            ]
+        );
+        assert_eq!(result.stats.files_skipped, 1);
+    }
+}
@@ -0,0 +1,323 @@
+//! Allowlist of findings accepted as intentional.
+//!
+//! Each finding gets a [`fingerprint`] built from its file, its normalized
+//! message, and the code around its line, but not the line number, so the
+//! same finding keeps its fingerprint when unrelated edits shift it up or
+//! down. Fingerprints listed in `.argus/ignore-comments` are filtered out
+//! of every later review without disabling the rule that produced them.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+use argus_core::{ArgusError, ReviewComment};
+use argus_difflens::parser::FileDiff;
+use sha2::{Digest, Sha256};
+
+use crate::patch::hunk_for;
+
+/// Allowlist location, relative to the repository root.
+pub const ALLOWLIST_PATH: &str = ".argus/ignore-comments";
+
+/// Hex digits in a fingerprint.
+const FINGERPRINT_LEN: usize = 16;
+
+/// Compute the stable fingerprint of a finding.
+///
+/// Hashes the file path, the message with case, whitespace, and numbers
+/// normalized, and the trimmed new-side code of the commented line and its
+/// neighbors in the hunk from `diffs`. The line number itself is left out.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use argus_core::{ReviewComment, Severity};
+/// use argus_difflens::parser::parse_unified_diff;
+/// use argus_review::suppress::fingerprint;
+///
+/// let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,1 +1,2 @@\n fn f() {\n+    x.unwrap();\n";
+/// let comment = ReviewComment {
+///     file_path: PathBuf::from("a.rs"),
+///     line: 2,
+///     severity: Severity::Warning,
+///     message: "unwrap may panic".into(),
+///     confidence: 90.0,
+///     suggestion: None,
+///     ..Default::default()
+/// };
+/// let fp = fingerprint(&comment, &parse_unified_diff(diff).unwrap());
+/// assert_eq!(fp.len(), 16);
+/// ```
+pub fn fingerprint(comment: &ReviewComment, diffs: &[FileDiff]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(comment.file_path.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update(normalize_message(&comment.message).as_bytes());
+    hasher.update([0]);
+    hasher.update(code_context(comment, diffs).as_bytes());
+    let mut hex = format!("{:x}", hasher.finalize());
+    hex.truncate(FINGERPRINT_LEN);
+    hex
+}
+
+/// Set [`ReviewComment::fingerprint`] on each comment.
+pub fn attach_fingerprints(comments: &mut [ReviewComment], diffs: &[FileDiff]) {
+    for comment in comments {
+        comment.fingerprint = Some(fingerprint(comment, diffs));
+    }
+}
+
+/// Lowercase, with runs of digits replaced by `#` and whitespace collapsed,
+/// so "line 42" and "Line 57" in an otherwise equal message match.
+fn normalize_message(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut last = ' ';
+    for ch in message.trim().chars().flat_map(char::to_lowercase) {
+        let ch = if ch.is_ascii_digit() {
+            '#'
+        } else if ch.is_whitespace() {
+            ' '
+        } else {
+            ch
+        };
+        if !(ch == last && (ch == '#' || ch == ' ')) {
+            out.push(ch);
+        }
+        last = ch;
+    }
+    out
+}
+
+/// The commented line and the lines right above and below it, trimmed,
+/// from the new side of the hunk containing it. Empty when no hunk does.
+fn code_context(comment: &ReviewComment, diffs: &[FileDiff]) -> String {
+    let Some(hunk) = hunk_for(diffs, comment) else {
+        return String::new();
+    };
+    let new_side: Vec<&str> = hunk
+        .content
+        .lines()
+        .filter(|line| !line.starts_with('-') && !line.starts_with('\\'))
+        .map(|line| line.get(1..).unwrap_or_default().trim())
+        .collect();
+    let target = (comment.line - hunk.new_start) as usize;
+    new_side
+        .get(target.saturating_sub(1)..(target + 2).min(new_side.len()))
+        .unwrap_or_default()
+        .join("\n")
+}
+
+/// Fingerprints of findings accepted as intentional.
+///
+/// # Examples
+///
+/// ```
+/// use argus_review::suppress::Allowlist;
+///
+/// let allowlist = Allowlist::parse("# deliberate unwrap in tests\n0123456789abcdef\n");
+/// assert!(allowlist.contains("0123456789abcdef"));
+/// assert!(!allowlist.contains("fedcba9876543210"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    fingerprints: HashSet<String>,
+}
+
+impl Allowlist {
+    /// Parse allowlist text: one fingerprint per line, with blank lines and
+    /// `#` comments (whole-line or trailing) ignored.
+    pub fn parse(text: &str) -> Self {
+        let fingerprints = text
+            .lines()
+            .filter_map(|line| line.split('#').next())
+            .map(str::trim)
+            .filter(|fp| !fp.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        Self { fingerprints }
+    }
+
+    /// Load the allowlist of the repository at `repo_root`.
+    ///
+    /// A missing file is an empty allowlist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Config`] if the file exists but cannot be read.
+    pub fn load(repo_root: &Path) -> Result<Self, ArgusError> {
+        let path = repo_root.join(ALLOWLIST_PATH);
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Ok(Self::parse(&text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ArgusError::Config(format!(
+                "failed to read {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Whether `fingerprint` is on the allowlist.
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.fingerprints.contains(fingerprint)
+    }
+
+    /// Whether `comment` carries a fingerprint on the allowlist.
+    pub fn suppresses(&self, comment: &ReviewComment) -> bool {
+        comment
+            .fingerprint
+            .as_deref()
+            .is_some_and(|fp| self.contains(fp))
+    }
+
+    /// Number of fingerprints on the allowlist.
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Whether the allowlist is empty.
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Append `fingerprint` to the allowlist of the repository at
+    /// `repo_root`, creating the file if needed.
+    ///
+    /// Returns `false`, without writing, when it is already listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArgusError::Config`] if `fingerprint` is not 16 hex digits,
+    /// or the file cannot be read or written.
+    pub fn append(repo_root: &Path, fingerprint: &str) -> Result<bool, ArgusError> {
+        let fingerprint = fingerprint.trim().to_ascii_lowercase();
+        if fingerprint.len() != FINGERPRINT_LEN
+            || !fingerprint.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(ArgusError::Config(format!(
+                "invalid fingerprint '{fingerprint}', expected {FINGERPRINT_LEN} hex digits"
+            )));
+        }
+        let path = repo_root.join(ALLOWLIST_PATH);
+        let existing = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(ArgusError::Config(format!(
+                    "failed to read {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        if Self::parse(&existing).contains(&fingerprint) {
+            return Ok(false);
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                ArgusError::Config(format!("failed to create {}: {e}", dir.display()))
+            })?;
+        }
+        // Start on a fresh line if the file was hand-edited without a final newline
+        let separator = if existing.is_empty() || existing.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{separator}{fingerprint}"))
+            .map_err(|e| ArgusError::Config(format!("failed to write {}: {e}", path.display())))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use argus_core::Severity;
+    use argus_difflens::parser::parse_unified_diff;
+
+    use super::*;
+
+    fn diff_at(start: u32) -> Vec<FileDiff> {
+        let diff = format!(
+            "diff --git a/tests/io.rs b/tests/io.rs\n\
+             --- a/tests/io.rs\n\
+             +++ b/tests/io.rs\n\
+             @@ -{start},2 +{start},3 @@\n \
+             fn reads_fixture() {{\n\
+             +    let text = std::fs::read_to_string(\"fixture.txt\").unwrap();\n \
+             }}\n"
+        );
+        parse_unified_diff(&diff).unwrap()
+    }
+
+    fn unwrap_comment(line: u32, message: &str) -> ReviewComment {
+        ReviewComment {
+            file_path: PathBuf::from("tests/io.rs"),
+            line,
+            severity: Severity::Warning,
+            message: message.into(),
+            confidence: 92.0,
+            suggestion: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fingerprint_survives_a_line_shift() {
+        let before = fingerprint(
+            &unwrap_comment(11, "unwrap on line 11 may panic"),
+            &diff_at(10),
+        );
+        let after = fingerprint(
+            &unwrap_comment(26, "Unwrap on line 26  may panic"),
+            &diff_at(25),
+        );
+        assert_eq!(before, after);
+
+        let allowlist = Allowlist::parse(&format!("{before}  # fixture is checked in\n"));
+        let mut shifted = vec![unwrap_comment(26, "unwrap on line 26 may panic")];
+        attach_fingerprints(&mut shifted, &diff_at(25));
+        assert!(allowlist.suppresses(&shifted[0]));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_code_or_file() {
+        let base = fingerprint(&unwrap_comment(11, "unwrap may panic"), &diff_at(10));
+        let mut other_file = unwrap_comment(11, "unwrap may panic");
+        other_file.file_path = PathBuf::from("tests/other.rs");
+        assert_ne!(base, fingerprint(&other_file, &diff_at(10)));
+
+        let edited = parse_unified_diff(
+            "diff --git a/tests/io.rs b/tests/io.rs\n\
+             --- a/tests/io.rs\n\
+             +++ b/tests/io.rs\n\
+             @@ -10,2 +10,3 @@\n \
+             fn reads_fixture() {\n\
+             +    let text = std::fs::read_to_string(\"other.txt\").unwrap();\n \
+             }\n",
+        )
+        .unwrap();
+        assert_ne!(
+            base,
+            fingerprint(&unwrap_comment(11, "unwrap may panic"), &edited)
+        );
+    }
+
+    #[test]
+    fn append_validates_and_skips_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Allowlist::append(dir.path(), "not-a-fingerprint").is_err());
+        assert!(Allowlist::append(dir.path(), "0123456789ABCDEF").unwrap());
+        assert!(!Allowlist::append(dir.path(), "0123456789abcdef").unwrap());
+
+        let allowlist = Allowlist::load(dir.path()).unwrap();
+        assert_eq!(allowlist.len(), 1);
+        assert!(allowlist.contains("0123456789abcdef"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Heuristic detection of changed functions that no test mentions.
+//!
+//! With `[review] test_presence_context`, the review prompt lists the
+//! functions a diff changes whose name appears in no test file, so the LLM
+//! can weigh untested changes more heavily. A function counts as tested
+//! when its name appears as a whole word in a test file (`tests/`,
+//! `test_*.py`, `*_test.go`, `*.spec.ts`, ...) or in a Rust `#[cfg(test)]`
+//! section. This is only a heuristic: a mention is not a test, and a
+//! function exercised through its callers has no mention.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use argus_difflens::parser::FileDiff;
+use argus_repomap::walker::{walk_repo_detailed, Language, DEFAULT_MAX_FILE_BYTES};
+
+/// Marker opening the test section of a Rust file.
+const RUST_TEST_MARKER: &str = "#[cfg(test)]";
+
+/// A function changed by the diff that no test mentions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UntestedFunction {
+    /// Function or method name.
+    pub name: String,
+    /// File defining the function, relative to the repository root.
+    pub file: PathBuf,
+    /// First line of the function (1-based).
+    pub line: u32,
+}
+
+/// Whether `path` looks like a test file, by directory or file name.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use argus_review::testpresence::is_test_file;
+///
+/// assert!(is_test_file(Path::new("tests/integration.rs")));
+/// assert!(is_test_file(Path::new("pkg/server_test.go")));
+/// assert!(is_test_file(Path::new("src/app.spec.ts")));
+/// assert!(is_test_file(Path::new("test_parser.py")));
+/// assert!(!is_test_file(Path::new("src/contest.rs")));
+/// ```
+pub fn is_test_file(path: &Path) -> bool {
+    let in_test_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            matches!(
+                c.as_os_str().to_str(),
+                Some("test" | "tests" | "__tests__" | "spec" | "testing")
+            )
+        })
+    });
+    if in_test_dir {
+        return true;
+    }
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_tests")
+        || stem.ends_with(".test")
+        || stem.ends_with(".spec")
+        || stem.ends_with("Test")
+        || stem.ends_with("Tests")
+}
+
+/// Functions and methods touched by `diffs` that no test under `root`
+/// mentions, in diff order.
+///
+/// Changed functions are found with tree-sitter in the current version of
+/// each file under `root`; deleted files, test files, and functions inside
+/// a Rust `#[cfg(test)]` section are left out. Every supported source file
+/// in the repository is then searched for their names.
+pub fn find_untested_functions(diffs: &[FileDiff], root: &Path) -> Vec<UntestedFunction> {
+    let changed = changed_functions(diffs, root);
+    if changed.is_empty() {
+        return Vec::new();
+    }
+    let Ok(walk) = walk_repo_detailed(root, false, DEFAULT_MAX_FILE_BYTES) else {
+        return Vec::new();
+    };
+
+    let mut tested: HashSet<&str> = HashSet::new();
+    for file in &walk.files {
+        let test_code = if is_test_file(&file.path) {
+            file.content.as_str()
+        } else {
+            match rust_test_section(&file.path, &file.content) {
+                Some(start) => &file.content[start..],
+                None => continue,
+            }
+        };
+        for function in &changed {
+            if contains_word(test_code, &function.name) {
+                tested.insert(function.name.as_str());
+            }
+        }
+    }
+    changed
+        .iter()
+        .filter(|f| !tested.contains(f.name.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Functions and methods overlapping the added lines of each diff.
+fn changed_functions(diffs: &[FileDiff], root: &Path) -> Vec<UntestedFunction> {
+    let mut changed = Vec::new();
+    let mut seen = HashSet::new();
+    for diff in diffs {
+        if diff.is_deleted_file || is_test_file(&diff.new_path) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(root.join(&diff.new_path)) else {
+            continue;
+        };
+        let language = diff
+            .new_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(Language::Unknown, Language::from_extension);
+        let test_start_line = rust_test_section(&diff.new_path, &content)
+            .map(|offset| content[..offset].lines().count() as u32 + 1);
+        let chunks = argus_codelens::chunker::chunk_file(&diff.new_path, &content, language)
+            .unwrap_or_default();
+        for chunk in chunks {
+            if !matches!(chunk.entity_type.as_str(), "function" | "method")
+                || test_start_line.is_some_and(|line| chunk.start_line >= line)
+            {
+                continue;
+            }
+            let touched = diff.hunks.iter().any(|hunk| {
+                let end = hunk.new_start + hunk.new_lines.saturating_sub(1);
+                hunk.new_lines > 0 && hunk.new_start <= chunk.end_line && chunk.start_line <= end
+            });
+            if touched && seen.insert((diff.new_path.clone(), chunk.entity_name.clone())) {
+                changed.push(UntestedFunction {
+                    name: chunk.entity_name,
+                    file: diff.new_path.clone(),
+                    line: chunk.start_line,
+                });
+            }
+        }
+    }
+    changed
+}
+
+/// Byte offset of the `#[cfg(test)]` section of a Rust file, if any.
+fn rust_test_section(path: &Path, content: &str) -> Option<usize> {
+    if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+        return None;
+    }
+    content.find(RUST_TEST_MARKER)
+}
+
+/// Whether `word` appears in `text` surrounded by non-identifier characters.
+fn contains_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argus_difflens::parser::parse_unified_diff;
+
+    #[test]
+    fn functions_without_test_mentions_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "pub fn parse(input: &str) -> u32 {\n    input.len() as u32\n}\n\n\
+             pub fn render(value: u32) -> String {\n    value.to_string()\n}\n\n\
+             pub fn escape(text: &str) -> String {\n    text.replace('<', \"&lt;\")\n}\n\n\
+             #[cfg(test)]\nmod tests {\n    #[test]\n    fn escapes() {\n        \
+             assert_eq!(super::escape(\"<\"), \"&lt;\");\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("tests/parse.rs"),
+            "#[test]\nfn parses() {\n    assert_eq!(mylib::parse(\"ab\"), 2);\n}\n",
+        )
+        .unwrap();
+
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -2 +2 @@
+-    input.len()
++    input.len() as u32
+@@ -6 +6 @@
+-    format!(\"{value}\")
++    value.to_string()
+@@ -10 +10 @@
+-    text.to_string()
++    text.replace('<', \"&lt;\")
+";
+        let diffs = parse_unified_diff(diff).unwrap();
+        let untested = find_untested_functions(&diffs, root);
+        assert_eq!(
+            untested,
+            [UntestedFunction {
+                name: "render".into(),
+                file: PathBuf::from("src/lib.rs"),
+                line: 5,
+            }]
+        );
+
+        std::fs::write(
+            root.join("tests/render.rs"),
+            "#[test]\nfn renders() {\n    assert_eq!(mylib::render(2), \"2\");\n}\n",
+        )
+        .unwrap();
+        assert!(find_untested_functions(&diffs, root).is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 
 use argus_review::state::ReviewState;
 use chrono::Utc;
-use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use miette::{Context, IntoDiagnostic, Result};
 
 use argus_core::{OutputFormat, ReviewComment, Severity};
@@ -18,7 +18,7 @@ use argus_core::{OutputFormat, ReviewComment, Severity};
                    Composable subcommands for codebase mapping, diff analysis, semantic search,\n\
                    git history intelligence, AI reviews, and MCP server integration.\n\n\
                    Examples:\n  \
-                     argus review --repo .           Review staged changes with AI\n  \
+                     argus review --all --repo .     Review your current changes with AI\n  \
                      git diff main | argus review    Review a diff from stdin\n  \
                      argus review --pr owner/repo#1  Review a GitHub pull request\n  \
                      argus map --path .              Generate a ranked codebase map\n  \
@@ -30,28 +30,50 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Command>,
 
-    /// Path to configuration file (default: .argus.toml)
+    /// Default repository for every subcommand (alias: --path)
+    #[arg(
+        long = "repo",
+        visible_alias = "path",
+        value_name = "PATH",
+        long_help = "Default repository for every subcommand (alias: --path).\n\n\
+                       Given before the subcommand, e.g. argus --repo ../app map. A subcommand's\n\
+                       own --path or --repo flag takes precedence; without either, commands use\n\
+                       the current directory."
+    )]
+    repo: Option<PathBuf>,
+
+    /// Path to configuration file (default: nearest .argus.toml up to the git root)
     #[arg(long, global = true)]
     config: Option<PathBuf>,
 
     /// Output format
     #[arg(
-        long,
+        long = "format",
         global = true,
         default_value = "text",
+        action = clap::ArgAction::Append,
         long_help = "Output format for command results.\n\n\
                        Formats:\n  \
                          text      Human-readable tables and summaries (default)\n  \
                          json      Machine-readable JSON with camelCase keys\n  \
                          markdown  GitHub-flavored Markdown\n  \
-                         sarif     SARIF v2.1.0 (review subcommand only)"
+                         sarif     SARIF v2.1.0 (review and diff subcommands)\n  \
+                         csv       Flat tables with a header row (history subcommand)\n  \
+                         junit     JUnit XML, one testsuite per file (review subcommand)\n\n\
+                       review accepts --format more than once: the first format goes to\n\
+                       stdout and each extra format is written to the matching --output\n\
+                       file, e.g. --format text --format sarif --output results.sarif"
     )]
-    format: OutputFormat,
+    formats: Vec<OutputFormat>,
 
     /// Enable verbose output
     #[arg(long, short, global = true)]
     verbose: bool,
 
+    /// Suppress spinners, progress bars, and status messages (errors, warnings, and results are still printed)
+    #[arg(long, short, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
     /// When to use colors
     #[arg(long, global = true, default_value = "auto")]
     color: ColorChoice,
@@ -63,11 +85,12 @@ enum Command {
     #[command(long_about = "Generate a ranked map of the codebase structure.\n\n\
         Uses tree-sitter to parse source files and PageRank to rank symbols by importance.\n\
         Output is a token-budgeted summary suitable for LLM context windows.\n\n\
-        Examples:\n  argus map --path .\n  argus map --max-tokens 2048 --focus src/main.rs")]
+        Examples:\n  argus map --path .\n  argus map --max-tokens 2048 --focus src/main.rs\n  \
+        argus map --diff main..HEAD")]
     Map {
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
 
         /// Maximum tokens for the map (default: 1024)
         #[arg(long, default_value = "1024")]
@@ -76,31 +99,57 @@ enum Command {
         /// Focus files (boost ranking for symbols in these files)
         #[arg(long)]
         focus: Vec<PathBuf>,
+
+        /// Map at most N files (overrides [path] max_files)
+        #[arg(long)]
+        max_files: Option<usize>,
+
+        /// Compare the symbol structure of two revisions instead of printing the map
+        #[arg(
+            long,
+            value_name = "BASE..HEAD",
+            conflicts_with_all = ["focus", "max_files"],
+            long_help = "Compare the symbol structure of two revisions instead of printing the map.\n\n\
+                         Reads both trees from git, ranks their symbols, and reports top-level\n\
+                         symbols that were added, removed, or moved to another file, plus symbols\n\
+                         whose rank at least doubled or halved. `main..` and `main` compare\n\
+                         against HEAD, and `main...feature` starts from their merge base.\n\
+                         Working-tree changes are not included."
+        )]
+        diff: Option<String>,
     },
     /// Analyze diffs and compute risk scores
     #[command(long_about = "Analyze diffs and compute risk scores.\n\n\
         Parses unified diffs and scores risk based on file count, complexity delta,\n\
-        and file types. Reads from stdin or a file.\n\n\
-        Examples:\n  git diff | argus diff\n  argus diff --file changes.patch")]
+        and file types. Reads from stdin, a file, or a git ref.\n\n\
+        Examples:\n  git diff | argus diff\n  argus diff --file changes.patch\n  argus diff --base main\n  \
+        argus --format json diff > risk.json; argus diff --baseline risk.json")]
     Diff {
         /// Read diff from file instead of stdin
         #[arg(long)]
         file: Option<PathBuf>,
+        /// Diff the working tree against this git ref instead of reading stdin
+        #[arg(long, conflicts_with = "file")]
+        base: Option<String>,
+        /// Compare against a previous `--format json` risk report and show each factor's trend
+        #[arg(long)]
+        baseline: Option<PathBuf>,
     },
     /// Search the codebase semantically
     #[command(
         long_about = "Search the codebase using hybrid semantic + keyword search.\n\n\
         Requires an embedding provider API key. Index the repo first with --index,\n\
-        then search with a natural language query. Use --reindex for incremental updates.\n\n\
-        Examples:\n  argus search --index --path .\n  argus search 'error handling logic'\n  argus search 'auth middleware' --limit 5"
+        then search with a natural language query. Use --reindex for incremental updates,\n\
+        and --prune to drop files newly excluded by .gitignore or .argusignore.\n\n\
+        Examples:\n  argus search --index --path .\n  argus search 'error handling logic'\n  argus search 'auth middleware' --limit 5\n  argus search --stats"
     )]
     Search {
-        /// Search query (omit to just index or reindex)
+        /// Search query (omit to just index, reindex, or prune)
         query: Option<String>,
 
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
 
         /// Maximum results to return (default: 10)
         #[arg(long, default_value = "10")]
@@ -113,18 +162,55 @@ enum Command {
         /// Re-index only changed files
         #[arg(long)]
         reindex: bool,
+
+        /// Remove chunks for files now excluded by .gitignore or .argusignore
+        #[arg(long)]
+        prune: bool,
+
+        /// Rebuild the index if it was built with a different embedding model than the query
+        #[arg(long, requires = "query")]
+        auto_reindex: bool,
+
+        /// Index at most N files (overrides [path] max_files)
+        #[arg(long)]
+        max_files: Option<usize>,
+
+        /// Also keyword-search related terms (default: built-in synonyms)
+        #[arg(
+            long,
+            value_enum,
+            num_args = 0..=1,
+            default_missing_value = "synonyms",
+            requires = "query",
+            long_help = "Also keyword-search terms related to the query, fused with the other results.\n\n\
+                synonyms (default): a built-in synonym map, offline and free.\n\
+                llm: ask the configured LLM for up to three terms; falls back to the\n\
+                synonym map if the call fails."
+        )]
+        expand: Option<QueryExpansion>,
+
+        /// Show what the index holds instead of searching
+        #[arg(
+            long,
+            conflicts_with_all = ["query", "index", "reindex", "prune"],
+            long_help = "Show what the index holds instead of searching.\n\n\
+                         Lists chunks per language and per file, the average chunk size,\n\
+                         repository files that were never indexed, and orphaned chunks whose\n\
+                         file no longer exists. Read-only; needs no embedding API key."
+        )]
+        stats: bool,
     },
     /// Analyze git history for hotspots, coupling, and ownership
     #[command(
         long_about = "Analyze git history for hotspots, coupling, and ownership.\n\n\
         Mines commit history using git2 to detect high-churn hotspots, temporal coupling\n\
         between files, knowledge silos, and project bus factor.\n\n\
-        Examples:\n  argus history --path .\n  argus history --analysis hotspots --since 90\n  argus history --analysis coupling --min-coupling 0.5"
+        Examples:\n  argus history --path .\n  argus history --analysis hotspots --since 90\n  argus history --analysis coupling --min-coupling 0.5\n  argus history --analysis ownership --timeline\n  argus history --analysis refactor-candidates"
     )]
     History {
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
 
         /// Analysis type
         #[arg(long, default_value = "all")]
@@ -141,85 +227,53 @@ enum Command {
         /// Minimum coupling degree to show (default: 0.3)
         #[arg(long, default_value = "0.3")]
         min_coupling: f64,
-    },
-    /// Run an AI-powered code review
-    #[command(long_about = "Run an AI-powered code review.\n\n\
-        Accepts diffs from stdin, a file, or a GitHub PR. Combines diff analysis with\n\
-        codebase context (repo map, git history) for behaviorally-informed reviews.\n\
-        Supports cross-file analysis, custom rules, and SARIF output.\n\n\
-        Examples:\n  git diff | argus review --repo .\n  argus review --pr owner/repo#123 --post-comments\n  argus review --file changes.patch --fail-on warning")]
-    Review {
-        /// GitHub PR to review (format: owner/repo#123)
-        #[arg(
-            long,
-            long_help = "GitHub PR to review.\n\nFormat: owner/repo#123\nRequires GITHUB_TOKEN or GH_TOKEN env var."
-        )]
-        pr: Option<String>,
-        /// Read diff from file instead of stdin
-        #[arg(long)]
-        file: Option<PathBuf>,
-        /// Post comments to GitHub PR
-        #[arg(
-            long,
-            long_help = "Post review comments directly to the GitHub PR.\n\nRequires --pr and GITHUB_TOKEN. Uses REQUEST_CHANGES event if any\nbug-level findings are present, otherwise COMMENT."
-        )]
-        post_comments: bool,
-        /// Repository path for codebase context
-        #[arg(
-            long,
-            long_help = "Repository path for codebase context.\n\nEnables repo map generation and git history analysis to provide\nthe LLM with richer context for more accurate reviews."
-        )]
-        repo: Option<PathBuf>,
-        /// Additional glob patterns to skip (e.g. "*.test.ts")
-        #[arg(long)]
-        skip_pattern: Vec<String>,
-        /// Include suggestion-level comments (default: only bug+warning)
-        #[arg(long)]
-        include_suggestions: bool,
-        /// Exit with non-zero code if findings meet severity threshold
-        #[arg(
-            long,
-            long_help = "Exit with non-zero code if findings of this severity or higher are found.\n\nSeverity ranking: bug > warning > suggestion > info.\nUseful in CI pipelines to fail builds on serious issues."
-        )]
-        fail_on: Option<Severity>,
-        /// Show comments that were filtered out, with reasons
-        #[arg(long)]
-        show_filtered: bool,
-        /// Apply suggested patches to the working tree
+
+        /// Show month-by-month dominant authors per file (ownership analysis)
         #[arg(long)]
-        apply_patches: bool,
-        /// Disable the self-reflection pass that filters false positives
+        timeline: bool,
+
+        /// Analyze at most N files, keeping the most frequently changed
+        /// (overrides [path] max_files)
         #[arg(long)]
-        no_self_reflection: bool,
-        /// Incremental review: only review changes since the last review
-        #[arg(
-            long,
-            long_help = "Enable incremental review mode.\n\n\
-                Only review hunks that are NEW or CHANGED since the last review.\n\
-                Compares the current diff against a saved review state in .argus/review-state.json.\n\
-                On first run (no saved state), reviews everything and saves state.\n\
-                Use --base-sha to explicitly set the comparison point."
-        )]
-        incremental: bool,
-        /// Base commit SHA for incremental review (overrides saved state)
+        max_files: Option<usize>,
+
+        /// Flag results from fewer commits than this as low-confidence
+        /// (overrides [history] min_commits)
         #[arg(long)]
-        base_sha: Option<String>,
-        /// Output issues in AI-agent-friendly format (for copy/paste)
+        min_commits: Option<usize>,
+    },
+    /// Report cyclomatic complexity per file and per function
+    #[command(
+        long_about = "Report cyclomatic complexity per file and per function.\n\n\
+        Parses source files with tree-sitter and counts decision points (branches, loops,\n\
+        match arms, catch clauses, boolean operators), listing the most complex files and\n\
+        functions first. With --threshold, lists only functions above the limit and exits\n\
+        with status 1 if there are any, so it can gate CI.\n\n\
+        Examples:\n  argus complexity --path .\n  argus complexity --limit 50\n  argus complexity --threshold 15\n  argus --format json complexity > complexity.json"
+    )]
+    Complexity {
+        /// Repository path (default: the global --repo, then the current directory)
         #[arg(long)]
-        copy: bool,
-        /// Review already-committed changes (e.g., HEAD, HEAD~3, or HEAD~3..HEAD)
-        #[arg(long, conflicts_with = "pr", conflicts_with = "file")]
-        commit: Option<String>,
-        /// Print metadata for commit message (e.g., "Argus: reviewed (3 comments)")
+        path: Option<PathBuf>,
+
+        /// Only list functions with complexity above N, and exit 1 if any exist
         #[arg(long)]
-        print_metadata: bool,
-        /// Skip AI review, take personal responsibility (records coverage from prior reviews)
-        #[arg(long, conflicts_with_all = ["skip", "copy", "print_metadata", "apply_patches", "post_comments"])]
-        vouch: bool,
-        /// Skip review entirely (no AI review, no personal responsibility)
-        #[arg(long, conflicts_with_all = ["vouch", "copy", "print_metadata", "apply_patches", "post_comments"])]
-        skip: bool,
+        threshold: Option<u32>,
+
+        /// Maximum files and functions to show (default: 20; ignored with --threshold)
+        #[arg(long, default_value = "20")]
+        limit: usize,
     },
+    /// Run an AI-powered code review
+    #[command(long_about = "Run an AI-powered code review.\n\n\
+        Accepts diffs from stdin, a file, a GitHub PR, or the working tree\n\
+        (--staged, --unstaged, --all). Without one of those sources the diff is read\n\
+        from stdin. Combines diff analysis with codebase context (repo map, git\n\
+        history) for behaviorally-informed reviews.\n\
+        Supports cross-file analysis, custom rules, and SARIF output.\n\n\
+        Examples:\n  argus review --all --repo .\n  argus review --staged\n  git diff | argus review --repo .\n  argus review --pr owner/repo#123 --post-comments\n  argus review --file changes.patch --fail-on warning\n  git diff main | argus review --dry-run\n  argus review --compare main..feature --repo .\n  argus review --before backup/lib.rs --after src/lib.rs\n  \
+        argus review --full --repo . --full-limit 10")]
+    Review(Box<ReviewArgs>),
     /// Start the MCP server for IDE integration
     #[command(
         long_about = "Start the MCP (Model Context Protocol) server for IDE integration.\n\n\
@@ -228,37 +282,60 @@ enum Command {
         Example:\n  argus mcp --path /my/project"
     )]
     Mcp {
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
     },
     /// Generate a PR title, description, and labels from a diff
     #[command(
         long_about = "Generate a PR title, description, and labels from a diff.\n\n\
         Analyzes code changes and uses an LLM to produce a well-formatted PR description\n\
         with conventional commit-style title, structured body, and suggested labels.\n\n\
-        Examples:\n  git diff main | argus describe\n  argus describe --file changes.patch\n  argus describe --pr owner/repo#123"
+        Examples:\n  git diff main | argus describe\n  argus describe --base main\n  argus describe --file changes.patch\n  argus describe --pr owner/repo#123"
     )]
     Describe {
-        /// GitHub PR to describe (format: owner/repo#123)
+        /// GitHub PR to describe (owner/repo#123 or a PR URL)
         #[arg(long)]
         pr: Option<String>,
         /// Read diff from file instead of stdin
         #[arg(long)]
         file: Option<PathBuf>,
+        /// Diff the working tree against this git ref instead of reading stdin
+        #[arg(long, conflicts_with_all = ["file", "pr"])]
+        base: Option<String>,
         /// Repository path for codebase context
         #[arg(long)]
         repo: Option<PathBuf>,
     },
+    /// Explain one finding in depth, with a step-by-step fix
+    #[command(
+        long_about = "Explain one finding in depth, with a step-by-step fix.\n\n\
+        Sends the function around --file:--line to the LLM and asks why the code is a\n\
+        problem and how to fix it. If the last review left a finding on that line, the\n\
+        explanation is about that finding; otherwise the LLM picks the most important\n\
+        issue on the line. Output is text or markdown.\n\n\
+        Examples:\n  argus explain --file src/parser.rs --line 42\n  argus --format markdown explain --file src/parser.rs --line 42 --repo ."
+    )]
+    Explain {
+        /// File the finding is in, relative to the repository
+        #[arg(long)]
+        file: PathBuf,
+        /// Line of the finding (1-based)
+        #[arg(long)]
+        line: u32,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
     /// Provide feedback on review comments (thumbs up/down)
     #[command(long_about = "Provide feedback on review comments.\n\n\
         Interactive mode that loads the most recent review and allows you to\n\
         rate comments as useful (\u{1F44D}) or not useful (\u{1F44E}).\n\n\
         Your feedback is stored locally and used to improve future reviews.")]
     Feedback {
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
     },
     /// Install or uninstall git hooks for Argus
     #[command(long_about = "Install or uninstall git hooks for Argus.\n\n\
@@ -269,9 +346,9 @@ enum Command {
         /// Hook action: install or uninstall
         #[arg(value_enum)]
         action: HookAction,
-        /// Repository path (default: current directory)
-        #[arg(long, default_value = ".")]
-        path: PathBuf,
+        /// Repository path (default: the global --repo, then the current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
     },
     /// Create a default .argus.toml configuration file
     #[command(long_about = "Create a default .argus.toml configuration file.\n\n\
@@ -282,8 +359,39 @@ enum Command {
     #[command(long_about = "Check your Argus setup and environment.\n\n\
         Runs diagnostics for git repo, config file, LLM/embedding API keys,\n\
         search index, GitHub token, and git history. Use --format json for\n\
-        machine-readable output.")]
-    Doctor,
+        machine-readable output.\n\n\
+        With --parsers, instead runs a built-in snippet per supported language through\n\
+        the symbol extractor and lists each tree-sitter grammar's ABI version. A failing\n\
+        language means a grammar upgrade changed what the extractor relies on; the\n\
+        command then exits with status 1.")]
+    Doctor {
+        /// Self-test the tree-sitter grammars instead of the environment
+        #[arg(long)]
+        parsers: bool,
+    },
+    /// Check that custom rules fire on fixture diffs as expected
+    #[command(
+        long_about = "Check that custom rules fire on fixture diffs as expected.\n\n\
+        Each fixture is a <name>.diff file plus a <name>.json spec listing the rules\n\
+        expected to fire, e.g. {\"expected\": [\"no-unwrap\"]}. Every configured rule not\n\
+        listed must stay silent. Each diff is reviewed with the normal pipeline and\n\
+        [review] settings, and a pass/fail summary is printed per rule. Exits with\n\
+        code 1 if any fixture fails.\n\n\
+        LLM responses are cached under <fixtures>/llm-cache by default. Commit the\n\
+        cache to make CI runs deterministic and offline; delete it (or pass\n\
+        --no-cache) after changing a rule.\n\n\
+        Examples:\n  argus rule-test tests/rules\n  argus rule-test tests/rules --format json"
+    )]
+    RuleTest {
+        /// Directory of <name>.diff + <name>.json fixture pairs
+        fixtures: PathBuf,
+        /// Directory for cached LLM responses (default: <fixtures>/llm-cache)
+        #[arg(long, conflicts_with = "no_cache")]
+        cache_dir: Option<PathBuf>,
+        /// Always call the LLM; don't read or write cached responses
+        #[arg(long)]
+        no_cache: bool,
+    },
     /// Generate shell completion scripts
     #[command(hide = true)]
     Completions {
@@ -293,6 +401,260 @@ enum Command {
     },
 }
 
+/// Arguments of `argus review`, boxed in [`Command::Review`] to keep the
+/// enum small.
+#[derive(Args)]
+struct ReviewArgs {
+    /// GitHub PR to review (owner/repo#123 or a PR URL)
+    #[arg(
+        long,
+        long_help = "GitHub PR to review.\n\nFormat: owner/repo#123, https://github.com/owner/repo/pull/123,\nor https://github.com/owner/repo/pull/123/files\nRequires GITHUB_TOKEN or GH_TOKEN env var."
+    )]
+    pr: Option<String>,
+    /// Read diff from file instead of stdin
+    #[arg(long)]
+    file: Option<PathBuf>,
+    /// Post comments to GitHub PR
+    #[arg(
+        long,
+        long_help = "Post review comments directly to the GitHub PR.\n\nRequires --pr and GITHUB_TOKEN. Uses REQUEST_CHANGES event if any\nbug-level findings are present, otherwise COMMENT."
+    )]
+    post_comments: bool,
+    /// How --post-comments posts findings: inline per line, or one summary
+    #[arg(
+        long,
+        value_enum,
+        default_value = "inline",
+        requires = "post_comments",
+        long_help = "How --post-comments posts findings.\n\n\
+            inline: one review comment per finding, on the line it is about.\n\
+            summary: a single review whose body is the markdown report, with a\n\
+            contents list and a collapsible section per file."
+    )]
+    mode: CommentMode,
+    /// With --post-comments, post nothing when the review has no findings
+    #[arg(
+        long,
+        requires = "post_comments",
+        long_help = "With --post-comments, skip posting when the review has no findings.\n\n\
+            Without it, a clean review is still posted as a COMMENT saying\n\
+            \"0 comments\". With it, nothing is posted, not even an APPROVE, so an\n\
+            earlier REQUEST_CHANGES review stays in place until a person resolves\n\
+            it. When there are findings, the event is chosen as usual:\n\
+            REQUEST_CHANGES if any is a bug, otherwise COMMENT."
+    )]
+    post_only_if_findings: bool,
+    /// Report the review as a GitHub Check Run with line annotations
+    #[arg(
+        long,
+        long_help = "Report the review as a GitHub Check Run on the PR's head commit.\n\n\
+            Requires --pr and a token allowed to write checks (GITHUB_TOKEN in Actions\n\
+            with `permissions: checks: write`; personal access tokens cannot create\n\
+            check runs). Each finding becomes a line annotation. The conclusion is\n\
+            failure when a finding meets --fail-on, neutral when there are other\n\
+            findings, and success when there are none."
+    )]
+    post_check: bool,
+    /// Repository path for codebase context
+    #[arg(
+        long,
+        long_help = "Repository path for codebase context.\n\nEnables repo map generation and git history analysis to provide\nthe LLM with richer context for more accurate reviews."
+    )]
+    repo: Option<PathBuf>,
+    /// Additional glob patterns to skip (e.g. "*.test.ts")
+    #[arg(long)]
+    skip_pattern: Vec<String>,
+    /// Include suggestion-level comments (default: only bug+warning)
+    #[arg(long)]
+    include_suggestions: bool,
+    /// Emphasize one area: general, security, performance, or correctness (overrides [review] focus)
+    #[arg(long)]
+    focus: Option<argus_core::ReviewFocus>,
+    /// Extra guidance for this review only, e.g. "check the retry logic" (repeatable)
+    #[arg(long = "instruction", value_name = "TEXT")]
+    instructions: Vec<String>,
+    /// Exit with non-zero code if findings meet severity threshold
+    #[arg(
+        long,
+        long_help = "Exit with non-zero code if findings of this severity or higher are found.\n\nSeverity ranking: bug > warning > suggestion > info.\nUseful in CI pipelines to fail builds on serious issues."
+    )]
+    fail_on: Option<Severity>,
+    /// Show comments that were filtered out, with reasons
+    #[arg(long)]
+    show_filtered: bool,
+    /// After the review, list how many comments each [[rules]] entry matched and where
+    #[arg(long, conflicts_with_all = ["dry_run", "vouch", "skip"])]
+    rules_report: bool,
+    /// Apply suggested patches to the working tree
+    #[arg(long)]
+    apply_patches: bool,
+    /// Step through findings in a terminal UI: accept (apply patch), dismiss, or skip each one
+    #[arg(
+        long,
+        conflicts_with_all = ["dry_run", "summary_only", "copy", "vouch", "skip", "apply_patches", "post_comments"],
+        long_help = "Step through findings one at a time in a terminal UI.\n\n\
+            Each comment is shown with the diff hunk it points at. Press a (or Enter) to\n\
+            accept it and apply its patch, d to dismiss it as a false positive, s to skip,\n\
+            the left arrow to go back, and q to quit. Dismissals are stored as negative\n\
+            feedback and dropped from the result. Requires a terminal on stdout."
+    )]
+    interactive: bool,
+    /// Disable the self-reflection pass that filters false positives
+    #[arg(long)]
+    no_self_reflection: bool,
+    /// Incremental review: only review changes since the last review
+    #[arg(
+        long,
+        long_help = "Enable incremental review mode.\n\n\
+            Only review hunks that are NEW or CHANGED since the last review.\n\
+            Compares the current diff against a saved review state in .argus/review-state.json.\n\
+            On first run (no saved state), reviews everything and saves state.\n\
+            Use --base-sha to explicitly set the comparison point."
+    )]
+    incremental: bool,
+    /// Base commit SHA for incremental review (overrides saved state)
+    #[arg(long)]
+    base_sha: Option<String>,
+    /// Output issues in AI-agent-friendly format (for copy/paste)
+    #[arg(long)]
+    copy: bool,
+    /// Review already-committed changes (e.g., HEAD, HEAD~3, or HEAD~3..HEAD)
+    #[arg(long, conflicts_with = "pr", conflicts_with = "file")]
+    commit: Option<String>,
+    /// Review a branch against a base (e.g. main..feature), reporting only issues it introduced
+    #[arg(
+        long,
+        value_name = "BASE..HEAD",
+        conflicts_with_all = ["pr", "file", "commit", "incremental", "base_sha", "working_tree"],
+        long_help = "Review the changes HEAD makes relative to BASE (e.g. main..feature).
+
+                Diffs HEAD against its merge base with BASE, like `git diff BASE...HEAD`.
+                Each finding is classified as introduced by the branch (its line was added)
+                or pre-existing (its line already exists in the base). Only introduced
+                findings are reported unless --include-preexisting is set; pre-existing
+                ones are listed with --show-filtered. Each finding records its origin."
+    )]
+    compare: Option<String>,
+    /// With --compare, also report findings on lines that already exist in the base
+    #[arg(long, requires = "compare")]
+    include_preexisting: bool,
+    /// Review the change from this file to --after, outside git
+    #[arg(
+        long,
+        requires = "after",
+        conflicts_with_all = ["pr", "file", "commit", "compare", "incremental", "base_sha", "working_tree"],
+        long_help = "Review the change from this file to the --after file.\n\n\
+            For changes captured outside git, such as generated output before and after\n\
+            a regeneration or a vendored file before and after an upgrade. The unified\n\
+            diff is computed internally and labeled with the --after file's path\n\
+            (relative to --repo when it is inside it)."
+    )]
+    before: Option<PathBuf>,
+    /// New version of the file given with --before
+    #[arg(long, requires = "before")]
+    after: Option<PathBuf>,
+    /// Review staged changes (`git diff --cached`) instead of reading stdin
+    #[arg(long, group = "working_tree", conflicts_with_all = ["pr", "file", "commit", "incremental", "base_sha"])]
+    staged: bool,
+    /// Review unstaged changes (`git diff`) instead of reading stdin
+    #[arg(long, group = "working_tree", conflicts_with_all = ["pr", "file", "commit", "incremental", "base_sha"])]
+    unstaged: bool,
+    /// Review staged and unstaged changes (`git diff HEAD`) instead of reading stdin
+    #[arg(long, group = "working_tree", conflicts_with_all = ["pr", "file", "commit", "incremental", "base_sha"])]
+    all: bool,
+    /// Review the riskiest files of the whole repository instead of a diff
+    #[arg(
+        long,
+        conflicts_with_all = ["pr", "file", "commit", "incremental", "base_sha", "compare", "before", "working_tree"],
+        long_help = "Review the riskiest files of the whole repository instead of a diff.\n\n\
+                     Files under --repo (default: the current directory) are ranked by\n\
+                     cyclomatic complexity weighted by how often recent commits touched them,\n\
+                     and the top --full-limit files are reviewed as if newly added. Files\n\
+                     larger than max_diff_tokens are split at function boundaries."
+    )]
+    full: bool,
+    /// Review at most N files with --full, riskiest first
+    #[arg(long, default_value = "20", requires = "full")]
+    full_limit: usize,
+    /// Print metadata for commit message (e.g., "Argus: reviewed (3 comments)")
+    #[arg(long)]
+    print_metadata: bool,
+    /// Skip AI review, take personal responsibility (records coverage from prior reviews)
+    #[arg(long, conflicts_with_all = ["skip", "copy", "print_metadata", "apply_patches", "post_comments", "post_check"])]
+    vouch: bool,
+    /// Skip review entirely (no AI review, no personal responsibility)
+    #[arg(long, conflicts_with_all = ["vouch", "copy", "print_metadata", "apply_patches", "post_comments", "post_check"])]
+    skip: bool,
+    /// Run only the deterministic checks (TODO markers, public API changes, new dependencies, risk) without the LLM
+    #[arg(
+        long,
+        conflicts_with_all = ["vouch", "skip", "dry_run", "no_self_reflection", "post_comments", "post_check"],
+        long_help = "Run only the deterministic, non-LLM checks.\n\n\
+            Reports added TODO/FIXME markers and removed or changed public API\n\
+            regardless of flag_todos and flag_breaking_changes, dependencies added\n\
+            to lock files unless flag_new_dependencies is false, and violations of\n\
+            [[rules]] with a pattern. Applies severity overrides and summarizes\n\
+            the diff's risk score. No API key is needed;\n\
+            the result has model \"offline\" and zero LLM calls."
+    )]
+    offline: bool,
+    /// Estimate LLM calls, tokens, and cost without calling the LLM
+    #[arg(long, conflicts_with_all = ["vouch", "skip", "copy", "print_metadata", "apply_patches", "post_comments", "post_check", "fail_on"])]
+    dry_run: bool,
+    /// Print only the LLM's short risk summary, not the comment list
+    #[arg(
+        long,
+        conflicts_with_all = ["dry_run", "copy", "apply_patches", "post_comments", "show_filtered", "output"],
+        long_help = "Print only the LLM's short risk summary, for quick PR triage.\n\n\
+            The review runs as usual (so --fail-on still applies) but the self-reflection\n\
+            pass is skipped and no comments are rendered. Supports text, markdown, and json."
+    )]
+    summary_only: bool,
+    /// Skip the extra LLM call that summarizes the findings (overrides [review] summary)
+    #[arg(long, conflicts_with = "summary_only")]
+    no_summary: bool,
+    /// Skip git history mining for hotspot context (overrides [review] use_history_context)
+    #[arg(long)]
+    no_history: bool,
+    /// Sampling seed for providers that support one (overrides [llm] seed)
+    #[arg(
+        long,
+        long_help = "Sampling seed for providers that support one (overrides [llm] seed).\n\n\
+            OpenAI, Gemini, and Ollama honor it; Anthropic has no seed parameter. With\n\
+            [llm] temperature = 0 and a fixed seed, reviews are as repeatable as the\n\
+            provider allows. OpenAI only promises best-effort determinism."
+    )]
+    seed: Option<u64>,
+    /// Add a finding's fingerprint to .argus/ignore-comments and exit (repeatable)
+    #[arg(
+        long,
+        value_name = "FINGERPRINT",
+        long_help = "Add a finding's fingerprint to .argus/ignore-comments and exit.\n\n\
+            Later reviews filter out findings with that fingerprint as \"suppressed by\n\
+            allowlist\" (see --show-filtered). Fingerprints are shown in text output and\n\
+            as \"fingerprint\" in JSON output. They hash the file, the message, and the\n\
+            code around the line, so they survive unrelated edits above the finding.\n\
+            Repeat the flag to add several."
+    )]
+    suppress: Vec<String>,
+    /// Arrange text and markdown findings by severity or per file (JSON and SARIF are unaffected)
+    #[arg(long, default_value = "severity")]
+    group_by: GroupBy,
+    /// Write each extra --format to this file, in order (e.g. --format text --format sarif --output results.sarif)
+    #[arg(long, conflicts_with_all = ["dry_run", "copy"])]
+    output: Vec<PathBuf>,
+    /// Attach the last author of each commented line (via git blame)
+    #[arg(
+        long,
+        requires = "repo",
+        long_help = "Attach the last author of each commented line, found with git blame.\n\n\
+            Opt-in because it puts author names and emails in the output. With\n\
+            --post-comments, authors with a GitHub noreply email are @-mentioned;\n\
+            others are named. Requires --repo."
+    )]
+    blame: bool,
+}
+
 #[derive(Clone, ValueEnum)]
 enum HookAction {
     /// Install pre-commit hook
@@ -309,10 +671,45 @@ enum HistoryAnalysis {
     Coupling,
     /// Analyze knowledge silos and bus factor
     Ownership,
+    /// Rank files by current complexity × churn
+    RefactorCandidates,
     /// Run all analyses
     All,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum GroupBy {
+    /// Most severe findings first
+    Severity,
+    /// One section per file, findings in line order
+    File,
+}
+
+impl From<GroupBy> for argus_review::pipeline::GroupBy {
+    fn from(group_by: GroupBy) -> Self {
+        match group_by {
+            GroupBy::Severity => Self::Severity,
+            GroupBy::File => Self::File,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum QueryExpansion {
+    /// Built-in synonym map
+    Synonyms,
+    /// Terms suggested by the configured LLM
+    Llm,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CommentMode {
+    /// One review comment per finding, on its line
+    Inline,
+    /// One review whose body holds every finding
+    Summary,
+}
+
 #[derive(Clone, PartialEq, Eq, ValueEnum)]
 enum ColorChoice {
     /// Auto-detect based on terminal
@@ -343,6 +740,7 @@ fn print_welcome(use_color: bool) {
         println!("  \x1b[32mmap\x1b[0m       Ranked codebase structure overview");
         println!("  \x1b[32msearch\x1b[0m    Semantic + keyword hybrid search");
         println!("  \x1b[32mhistory\x1b[0m   Hotspot detection, temporal coupling, bus factor");
+        println!("  \x1b[32mcomplexity\x1b[0m Per-file and per-function cyclomatic complexity");
         println!("  \x1b[32mdoctor\x1b[0m    Check your setup and environment");
         println!("  \x1b[32mmcp\x1b[0m       Start MCP server for IDE integration");
         println!("  \x1b[32minit\x1b[0m      Create default configuration\n");
@@ -360,6 +758,7 @@ fn print_welcome(use_color: bool) {
         println!("  map       Ranked codebase structure overview");
         println!("  search    Semantic + keyword hybrid search");
         println!("  history   Hotspot detection, temporal coupling, bus factor");
+        println!("  complexity Per-file and per-function cyclomatic complexity");
         println!("  doctor    Check your setup and environment");
         println!("  mcp       Start MCP server for IDE integration");
         println!("  init      Create default configuration\n");
@@ -368,20 +767,551 @@ fn print_welcome(use_color: bool) {
     println!("Run 'argus <command> --help' for details.");
 }
 
-fn read_diff_input(file: &Option<PathBuf>) -> Result<String> {
-    match file {
-        Some(path) => std::fs::read_to_string(path)
-            .into_diagnostic()
-            .wrap_err(format!("reading {}", path.display())),
-        None => {
-            let mut input = String::new();
-            std::io::stdin()
-                .read_to_string(&mut input)
-                .into_diagnostic()
-                .wrap_err("reading stdin")?;
-            Ok(input)
+fn read_diff_input(file: &Option<PathBuf>) -> Result<String> {
+    match file {
+        Some(path) => std::fs::read(path)
+            .into_diagnostic()
+            .wrap_err(format!("reading {}", path.display()))
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        None => {
+            // Read bytes: a diff touching non-UTF-8 files is still reviewable,
+            // those files are skipped by the diff filter.
+            let mut input = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut input)
+                .into_diagnostic()
+                .wrap_err("reading stdin")?;
+            Ok(String::from_utf8_lossy(&input).into_owned())
+        }
+    }
+}
+
+/// Start a stderr spinner, unless `quiet` is set or stderr is not a terminal.
+fn make_spinner(quiet: bool, message: &str) -> Option<indicatif::ProgressBar> {
+    if quiet || !std::io::stderr().is_terminal() {
+        return None;
+    }
+    let pb = indicatif::ProgressBar::new_spinner();
+    pb.set_style(
+        indicatif::ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})").unwrap(),
+    );
+    pb.set_message(message.to_string());
+    pb.enable_steady_tick(std::time::Duration::from_millis(120));
+    Some(pb)
+}
+
+/// Warn on stderr that a `max_files` cap left the result partial.
+fn warn_files_capped(dropped: usize, kept: usize, verb: &str) {
+    if dropped > 0 {
+        eprintln!(
+            "warning: max_files cap reached: {verb} {kept} files, skipped {dropped}; results are partial"
+        );
+    }
+}
+
+/// Resolve a subcommand's repository: its own `--path`/`--repo` flag, then
+/// the global `--repo`, then the current directory.
+fn resolve_repo(own: &Option<PathBuf>, global: &Option<PathBuf>) -> PathBuf {
+    own.clone()
+        .or_else(|| global.clone())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// The environment variable that holds the API key of LLM `provider`, or
+/// `None` for `ollama`, which runs locally without one.
+fn llm_api_key_var(provider: &str) -> Option<&'static str> {
+    match provider {
+        "anthropic" => Some("ANTHROPIC_API_KEY"),
+        "gemini" => Some("GEMINI_API_KEY"),
+        "ollama" => None,
+        _ => Some("OPENAI_API_KEY"),
+    }
+}
+
+/// Fail with a hint when the configured LLM provider needs an API key and
+/// neither `[llm] api_key` nor the provider's environment variable is set.
+fn require_api_key(config: &argus_core::ArgusConfig) -> Result<()> {
+    let Some(var) = llm_api_key_var(&config.llm.provider) else {
+        return Ok(());
+    };
+    if config.llm.api_key.is_none() && std::env::var(var).is_err() {
+        miette::bail!(miette::miette!(
+            help = format!("Set {var} or add api_key in your .argus.toml under [llm]"),
+            "No API key configured for LLM provider '{}'",
+            config.llm.provider
+        ));
+    }
+    Ok(())
+}
+
+/// Combine the per-scope results of a review with `merge`, leaving the
+/// result of a review without nested configs as it is.
+fn merge_scopes<T>(mut parts: Vec<T>, merge: fn(Vec<T>) -> T) -> T {
+    if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        merge(parts)
+    }
+}
+
+/// With `verbose`, list on stderr the files skipped for exceeding
+/// `[path] max_file_bytes`.
+fn report_oversized(paths: &[PathBuf], limit: u64, verbose: bool) {
+    if !verbose || paths.is_empty() {
+        return;
+    }
+    eprintln!(
+        "skipped {} file(s) larger than {limit} bytes ([path] max_file_bytes):",
+        paths.len()
+    );
+    for path in paths {
+        eprintln!("  {}", path.display());
+    }
+}
+
+/// Warn on stderr about files skipped for not being valid UTF-8, listing
+/// them when `verbose` is set.
+fn warn_non_utf8(paths: &[PathBuf], verbose: bool) {
+    if paths.is_empty() {
+        return;
+    }
+    eprintln!(
+        "warning: skipped {} file(s) that are not valid UTF-8{}",
+        paths.len(),
+        if verbose {
+            ":"
+        } else {
+            " (use --verbose to list them)"
+        }
+    );
+    if verbose {
+        for path in paths {
+            eprintln!("  {}", path.display());
+        }
+    }
+}
+
+/// Warn on stderr about files left out of a repo map because they could
+/// not be read or parsed, listing them with the reason when `verbose` is set.
+fn warn_unmapped(map: &argus_repomap::RepoMap, verbose: bool) {
+    let count = map.unreadable_files.len() + map.unparsed_files.len();
+    if count == 0 {
+        return;
+    }
+    eprintln!(
+        "warning: skipped {count} file(s) that could not be read or parsed{}",
+        if verbose {
+            ":"
+        } else {
+            " (use --verbose to list them)"
+        }
+    );
+    if verbose {
+        for path in &map.unreadable_files {
+            eprintln!("  {} (unreadable)", path.display());
+        }
+        for file in &map.unparsed_files {
+            eprintln!("  {} ({})", file.path.display(), file.reason);
+        }
+    }
+}
+
+/// Run `git diff <base>` in the repository containing `path`.
+///
+/// The ref is validated with git2 first so a typo produces a clear error
+/// instead of raw git stderr.
+fn git_diff_against(path: &std::path::Path, base: &str) -> Result<String> {
+    git_diff_refs(path, &[base], base)
+}
+
+/// Run `git diff <spec>` in the repository containing `path`, after checking
+/// that every ref in `refs` exists.
+fn git_diff_refs(path: &std::path::Path, refs: &[&str], spec: &str) -> Result<String> {
+    let repo = git2::Repository::discover(path).map_err(|_| {
+        miette::miette!(
+            help = "Run argus from inside a git repository, or pipe a diff on stdin",
+            "Not a git repository: {}",
+            path.display()
+        )
+    })?;
+    for name in refs {
+        if repo.revparse_single(name).is_err() {
+            miette::bail!(miette::miette!(
+                help = "Check the branch, tag, or commit name (e.g. `git branch -a`)",
+                "Unknown git ref: {name}"
+            ));
+        }
+    }
+    let workdir = repo.workdir().unwrap_or_else(|| repo.path());
+
+    let output = std::process::Command::new("git")
+        .args(["-C", &workdir.to_string_lossy(), "diff", spec])
+        .output()
+        .into_diagnostic()
+        .wrap_err(format!("Failed to run git diff {spec}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        miette::bail!("git diff failed: {}", stderr.trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Resolve the `--diff` range of `map` to its two revisions: `base..head`,
+/// `base...head` (from the merge base of the two), or a lone `base`
+/// compared with `HEAD`.
+fn map_diff_revisions(repo: &git2::Repository, range: &str) -> Result<(String, String)> {
+    let head_or_default = |head: &str| if head.is_empty() { "HEAD" } else { head }.to_string();
+    // `...` first: splitting it on `..` would leave a `.` on the head
+    if let Some((base, head)) = range.split_once("...") {
+        let head = head_or_default(head);
+        let oid = |rev: &str| {
+            repo.revparse_single(rev)
+                .and_then(|object| object.peel_to_commit())
+                .map(|commit| commit.id())
+                .map_err(|_| {
+                    miette::miette!(
+                        help = "Check the branch, tag, or commit name (e.g. `git branch -a`)",
+                        "Unknown git ref: {rev}"
+                    )
+                })
+        };
+        let merge_base = repo
+            .merge_base(oid(base)?, oid(&head)?)
+            .into_diagnostic()
+            .wrap_err(format!("No merge base between {base} and {head}"))?;
+        return Ok((merge_base.to_string(), head));
+    }
+    Ok(match range.split_once("..") {
+        Some((base, head)) => (base.to_string(), head_or_default(head)),
+        None => (range.to_string(), "HEAD".to_string()),
+    })
+}
+
+/// Read the mappable source files under `subdir` of the tree at `rev`
+/// from git, with paths relative to `subdir`.
+///
+/// Used by `map --diff`, so nothing is checked out. The same files are
+/// skipped as by the repo map's walker: those matched by the tree's
+/// `.gitignore`, `.ignore`, and `.argusignore` files, hidden ones, those in
+/// languages without a grammar, and those larger than `max_file_bytes` or
+/// not valid UTF-8.
+fn source_files_at(
+    repo: &git2::Repository,
+    rev: &str,
+    subdir: &std::path::Path,
+    max_file_bytes: u64,
+) -> Result<Vec<argus_repomap::walker::SourceFile>> {
+    use argus_repomap::walker::{IgnoreRules, Language, SourceFile};
+
+    let tree = repo
+        .revparse_single(rev)
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|_| {
+            miette::miette!(
+                help = "Check the branch, tag, or commit name (e.g. `git branch -a`)",
+                "Unknown git ref: {rev}"
+            )
+        })?;
+
+    // Ignore files can sit below the files they cover in walk order, so
+    // collect them before deciding anything
+    let mut rules = IgnoreRules::default();
+    let mut blobs = Vec::new();
+    tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let name = entry.name().unwrap_or_default();
+        if IgnoreRules::FILE_NAMES.contains(&name) {
+            if let Ok(blob) = repo.find_blob(entry.id()) {
+                rules.add(
+                    std::path::Path::new(dir),
+                    &String::from_utf8_lossy(blob.content()),
+                );
+            }
+        }
+        blobs.push((PathBuf::from(format!("{dir}{name}")), entry.id()));
+        git2::TreeWalkResult::Ok
+    })
+    .into_diagnostic()
+    .wrap_err(format!("Failed to read the tree at {rev}"))?;
+
+    let mut files = Vec::new();
+    for (path, id) in blobs {
+        let Ok(relative) = path.strip_prefix(subdir) else {
+            continue;
+        };
+        if rules.is_ignored(&path) {
+            continue;
+        }
+        let language = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(Language::Unknown, Language::from_extension);
+        if language == Language::Unknown {
+            continue;
+        }
+        let Ok(blob) = repo.find_blob(id) else {
+            continue;
+        };
+        if blob.size() as u64 > max_file_bytes || blob.is_binary() {
+            continue;
+        }
+        if let Ok(content) = std::str::from_utf8(blob.content()) {
+            files.push(SourceFile {
+                path: relative.to_path_buf(),
+                language,
+                content: content.to_string(),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Run `git diff <args>` against the working tree of the repository at `path`.
+///
+/// Used by `review --staged`/`--unstaged`/`--all`. Untracked files are not
+/// part of the diff.
+fn git_working_diff(path: &std::path::Path, args: &[&str]) -> Result<String> {
+    let repo = git2::Repository::discover(path).map_err(|_| {
+        miette::miette!(
+            help = "Run argus from inside a git repository, or pipe a diff on stdin",
+            "Not a git repository: {}",
+            path.display()
+        )
+    })?;
+    let workdir = repo.workdir().unwrap_or_else(|| repo.path());
+
+    let output = std::process::Command::new("git")
+        .args(["-C", &workdir.to_string_lossy(), "diff"])
+        .args(args)
+        .output()
+        .into_diagnostic()
+        .wrap_err(format!("Failed to run git diff {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        miette::bail!("git diff failed: {}", stderr.trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Unified diff from `before` to `after`, for `review --before/--after`.
+///
+/// Both sides are labeled with the path of `after` (see [`diff_label`]), so
+/// the change reads as an edit of one file. Warns on stderr when the two
+/// files have different extensions, as they are likely not the same file.
+fn diff_files(
+    before: &std::path::Path,
+    after: &std::path::Path,
+    repo_root: &std::path::Path,
+) -> Result<String> {
+    let read = |path: &std::path::Path| {
+        std::fs::read(path)
+            .into_diagnostic()
+            .wrap_err(format!("reading {}", path.display()))
+    };
+    let (old, new) = (read(before)?, read(after)?);
+    if before.extension() != after.extension() {
+        eprintln!(
+            "warning: {} and {} have different extensions; reviewing them as one file",
+            before.display(),
+            after.display()
+        );
+    }
+
+    let label = diff_label(after, repo_root);
+    let mut patch = git2::Patch::from_buffers(&old, Some(&label), &new, Some(&label), None)
+        .into_diagnostic()
+        .wrap_err("Failed to diff --before and --after")?;
+    let buf = patch.to_buf().into_diagnostic()?;
+    if buf.is_empty() {
+        miette::bail!(
+            "{} and {} are identical; nothing to review",
+            before.display(),
+            after.display()
+        );
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Path to show for `file` in a generated diff: relative to `repo_root`
+/// when inside it, else the path as given if relative, else the file name.
+fn diff_label(file: &std::path::Path, repo_root: &std::path::Path) -> PathBuf {
+    let inside_repo = file.canonicalize().ok().and_then(|file| {
+        let root = repo_root.canonicalize().ok()?;
+        file.strip_prefix(root)
+            .ok()
+            .map(std::path::Path::to_path_buf)
+    });
+    if let Some(relative) = inside_repo {
+        return relative;
+    }
+    if file.is_relative() {
+        return file.strip_prefix(".").unwrap_or(file).to_path_buf();
+    }
+    file.file_name()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| file.to_path_buf())
+}
+
+/// Render a review result in `format`, exactly as printed to stdout.
+///
+/// Text and markdown cut messages longer than `max_message_chars`; the
+/// machine-readable formats keep them whole.
+fn render_review(
+    result: &argus_review::pipeline::ReviewResult,
+    format: OutputFormat,
+    group_by: GroupBy,
+    max_message_chars: usize,
+) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Json => {
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(result).into_diagnostic()?
+            )
+        }
+        OutputFormat::Markdown => result
+            .with_truncated_messages(max_message_chars)
+            .to_markdown_grouped(group_by.into()),
+        OutputFormat::Sarif => {
+            let sarif = argus_review::sarif::to_sarif(result);
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&sarif).into_diagnostic()?
+            )
+        }
+        OutputFormat::Text => result
+            .with_truncated_messages(max_message_chars)
+            .to_text(group_by.into()),
+        OutputFormat::Junit => argus_review::junit::to_junit(result),
+        OutputFormat::Csv => unreachable!(),
+    })
+}
+
+/// Render only a review's summary, for `review --summary-only`.
+fn render_review_summary(
+    result: &argus_review::pipeline::ReviewResult,
+    format: OutputFormat,
+) -> Result<String> {
+    let summary = result.summary.as_deref();
+    // Findings without a summary mean the summary call failed, not a clean review
+    let count = result.comments.len();
+    let missing = if count == 0 {
+        "No issues found.".to_string()
+    } else {
+        format!(
+            "Summary unavailable ({count} finding{}).",
+            if count == 1 { "" } else { "s" }
+        )
+    };
+    Ok(match format {
+        OutputFormat::Json => {
+            let value = serde_json::json!({
+                "summary": summary,
+                "commentCount": count,
+            });
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&value).into_diagnostic()?
+            )
+        }
+        OutputFormat::Markdown => match summary {
+            Some(summary) => format!("# Review Summary\n\n> {summary}\n"),
+            None => format!("# Review Summary\n\n{missing}\n"),
+        },
+        OutputFormat::Text => match summary {
+            Some(summary) => format!("{summary}\n"),
+            None => format!("{missing}\n"),
+        },
+        OutputFormat::Sarif | OutputFormat::Csv | OutputFormat::Junit => unreachable!(),
+    })
+}
+
+fn format_rules_report(matches: &[argus_review::ruletest::RuleMatches]) -> String {
+    if matches.is_empty() {
+        return "\n--- Rules Report ---\nNo custom rules configured\n".to_string();
+    }
+    let mut out = String::from("\n--- Rules Report ---\n");
+    for rule in matches {
+        out.push_str(&format!(
+            "{} → {} comment{}\n",
+            rule.rule,
+            rule.count(),
+            if rule.count() == 1 { "" } else { "s" }
+        ));
+        for (path, line) in &rule.locations {
+            out.push_str(&format!("  {}:{line}\n", path.display()));
+        }
+    }
+    out.push_str("--------------------\n");
+    out
+}
+
+fn format_rule_test_text(report: &argus_review::ruletest::RuleTestReport) -> String {
+    let mut out = String::new();
+    out.push_str("Fixtures:\n");
+    for fixture in &report.fixtures {
+        let status = if fixture.passed() { "PASS" } else { "FAIL" };
+        out.push_str(&format!("  {status}  {}", fixture.name));
+        if !fixture.missed.is_empty() {
+            out.push_str(&format!("  missed: {}", fixture.missed.join(", ")));
         }
+        if !fixture.false_fired.is_empty() {
+            out.push_str(&format!(
+                "  false-fired: {}",
+                fixture.false_fired.join(", ")
+            ));
+        }
+        out.push('\n');
+    }
+    out.push_str("\nRules:\n");
+    for rule in &report.rules {
+        let status = if rule.ok() { "PASS" } else { "FAIL" };
+        out.push_str(&format!(
+            "  {status}  {}  ({} passed, {} missed, {} false-fired)\n",
+            rule.rule, rule.passed, rule.missed, rule.false_fired
+        ));
+    }
+    let failed = report.fixtures.iter().filter(|f| !f.passed()).count();
+    out.push_str(&format!(
+        "\n{} of {} fixtures passed\n",
+        report.fixtures.len() - failed,
+        report.fixtures.len()
+    ));
+    out
+}
+
+fn format_rule_test_markdown(report: &argus_review::ruletest::RuleTestReport) -> String {
+    let mut out = String::from("# Rule Test\n\n");
+    out.push_str("| Rule | Status | Passed | Missed | False-fired |\n");
+    out.push_str("|------|--------|--------|--------|-------------|\n");
+    for rule in &report.rules {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} |\n",
+            rule.rule,
+            if rule.ok() { "pass" } else { "fail" },
+            rule.passed,
+            rule.missed,
+            rule.false_fired
+        ));
     }
+    out.push_str("\n| Fixture | Status | Missed | False-fired |\n");
+    out.push_str("|---------|--------|--------|-------------|\n");
+    for fixture in &report.fixtures {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            fixture.name,
+            if fixture.passed() { "pass" } else { "fail" },
+            fixture.missed.join(", "),
+            fixture.false_fired.join(", ")
+        ));
+    }
+    out
 }
 
 fn format_issues_for_copy(comments: &[ReviewComment]) -> String {
@@ -567,6 +1497,7 @@ impl CheckResult {
 
 fn run_doctor(
     config: &argus_core::ArgusConfig,
+    config_path: Option<&std::path::Path>,
     format: OutputFormat,
     use_color: bool,
 ) -> Result<()> {
@@ -599,13 +1530,15 @@ fn run_doctor(
     }
 
     // 2. Config file
-    let config_path = std::path::Path::new(".argus.toml");
-    if config_path.exists() {
+    if let Some(config_path) = config_path {
         let rule_count = config.rules.len();
         let detail = if rule_count > 0 {
-            format!(".argus.toml found ({rule_count} custom rules)")
+            format!(
+                "using {} ({rule_count} custom rules)",
+                config_path.display()
+            )
         } else {
-            ".argus.toml found".into()
+            format!("using {}", config_path.display())
         };
         checks.push(CheckResult::pass("config_file", detail));
     } else {
@@ -619,26 +1552,25 @@ fn run_doctor(
     // 3. LLM provider + API key
     let llm_provider = &config.llm.provider;
     let llm_model = &config.llm.model;
-    let llm_env_var = match llm_provider.as_str() {
-        "anthropic" => "ANTHROPIC_API_KEY",
-        "gemini" => "GEMINI_API_KEY",
-        _ => "OPENAI_API_KEY",
-    };
     checks.push(CheckResult::pass(
         "llm_provider",
         format!("{llm_provider} (model: {llm_model})"),
     ));
-    if config.llm.api_key.is_some() || std::env::var(llm_env_var).is_ok() {
-        checks.push(CheckResult::pass(
-            "llm_api_key",
-            format!("{llm_env_var} set"),
-        ));
+    if let Some(llm_env_var) = llm_api_key_var(llm_provider) {
+        if config.llm.api_key.is_some() || std::env::var(llm_env_var).is_ok() {
+            checks.push(CheckResult::pass(
+                "llm_api_key",
+                format!("{llm_env_var} set"),
+            ));
+        } else {
+            checks.push(CheckResult::fail(
+                "llm_api_key",
+                format!("{llm_env_var} not set"),
+                format!("export {llm_env_var}=... or set api_key in .argus.toml"),
+            ));
+        }
     } else {
-        checks.push(CheckResult::fail(
-            "llm_api_key",
-            format!("{llm_env_var} not set"),
-            format!("export {llm_env_var}=... or set api_key in .argus.toml"),
-        ));
+        checks.push(CheckResult::pass("llm_api_key", "not needed for ollama"));
     }
 
     // 4. Embedding provider + API key
@@ -770,6 +1702,86 @@ fn run_doctor(
     Ok(())
 }
 
+/// Print the grammar self-test for `doctor --parsers`. Returns whether every
+/// language passed.
+fn run_parser_doctor(format: OutputFormat, use_color: bool) -> Result<bool> {
+    let checks = argus_repomap::selftest::check_parsers();
+    let passed = checks.iter().filter(|c| c.passed).count();
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "treeSitterAbi": argus_repomap::selftest::supported_abi_range(),
+                "parsers": checks,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).into_diagnostic()?);
+        }
+        _ => {
+            let version = env!("CARGO_PKG_VERSION");
+            println!("Argus v{version} — Parser Self-Test\n");
+            let (min, max) = argus_repomap::selftest::supported_abi_range();
+            println!("tree-sitter supports grammar ABI {min}-{max}\n");
+
+            for check in &checks {
+                let result = if check.passed {
+                    CheckResult::pass("", format!("found `{}`", check.expected_symbol))
+                } else if !(min..=max).contains(&check.abi_version) {
+                    CheckResult::fail(
+                        "",
+                        check.error.clone().unwrap_or_default(),
+                        format!(
+                            "{} is built for ABI {}; pin a release built for ABI {min}-{max}",
+                            check.grammar, check.abi_version
+                        ),
+                    )
+                } else {
+                    CheckResult::fail(
+                        "",
+                        check.error.clone().unwrap_or_default(),
+                        "a grammar upgrade likely renamed node kinds the extractor relies on",
+                    )
+                };
+                let sym = if use_color {
+                    result.colored_symbol()
+                } else {
+                    result.symbol().to_string()
+                };
+                println!(
+                    "  {sym} {:<12} {:<30} ABI {:<3} {}",
+                    check.language,
+                    format!("{} {}", check.grammar, check.grammar_version),
+                    check.abi_version,
+                    result.detail
+                );
+                if let Some(hint) = &result.hint {
+                    println!("    hint: {hint}");
+                }
+            }
+            println!("\n{passed} of {} grammars passed", checks.len());
+        }
+    }
+
+    Ok(passed == checks.len())
+}
+
+/// Ask the configured LLM for terms related to a search query.
+async fn expand_query_with_llm(
+    config: &argus_core::ArgusConfig,
+    query: &str,
+) -> Result<Vec<String>> {
+    let client = argus_review::llm::LlmClient::new(&config.llm)?.with_network(&config.network)?;
+    let response = client
+        .chat(vec![argus_review::llm::ChatMessage {
+            role: argus_review::llm::Role::User,
+            content: argus_review::prompt::build_query_expansion_prompt(query),
+        }])
+        .await?;
+    Ok(argus_review::prompt::parse_query_expansion_response(
+        &response, query,
+    ))
+}
+
 fn chrono_days_ago(days: i64) -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     let now = SystemTime::now()
@@ -787,21 +1799,59 @@ const DEFAULT_CONFIG: &str = r#"# Argus Configuration
 # api_base = "https://api.openai.com/v1"
 # model = "gpt-4o"
 # max_findings = 5
-
-[review.noise]
+# max_comments_per_file = 3  # cap each file before max_comments (default: unlimited)
+# context_lines = 0  # unchanged lines shown around each hunk (needs --repo)
+# expand_to_function = false  # show each changed function whole (needs --repo)
+# flag_todos = false  # report added TODO/FIXME/XXX/HACK lines without the LLM
+# flag_breaking_changes = false  # report removed/changed public API without the LLM
+# flag_new_dependencies = true  # list packages added to Cargo.lock/package-lock.json
+# focus = "general"  # or "security", "performance", "correctness"
+# tone = "terse"  # "explanatory" adds a teaching rationale to each finding
+# summary = true  # false skips the extra summary LLM call
+# use_history_context = true  # false skips git history mining (faster on large repos)
+# use_search_context = true  # false skips related code from the search index
+# max_message_chars = 2000  # longer messages are cut in text/markdown output
+# repo_map_languages = []  # e.g. ["python"]; empty maps the languages the diff touches
+# include_intent = true  # add commit messages / the PR description to the prompt
+# recalibrate_confidence = false  # blend self-reflection scores into confidence
+# comments_on_changed_only = false  # drop comments on unchanged context lines
+# test_presence_context = false  # list changed functions no test mentions (needs --repo)
 # skip_patterns = ["*.lock", "*.min.js", "vendor/**"]
 # min_confidence = 90
 # include_suggestions = false
 # self_reflection = true
 # self_reflection_score_threshold = 7
+# self_reflection_mode = "filter"  # "filter_and_augment" also adds missed issues
+
+[review.noise]
+# min_hunk_lines = 3  # skip hunks with fewer added+removed lines
+# exact_dedup = false # true: only drop duplicates with identical messages
 
 [embedding]
 # provider = "voyage"
 # model = "voyage-code-3"
+# fallback_chunking = false   # also index docs/YAML/SQL as 40-line windows
+# strip_comments = false      # embed code without comments/license headers
+# fts_tokenizer = "porter"    # keyword search: porter (stemmed), trigram (substrings), unicode61
+# fts_stop_words = ["get", "set"]  # noise words left out of keyword search
+
+[embedding.chunking]
+# rust = "by_class"           # by_function (default), by_class, or by_block, per language
+# md = "by_block"             # or per extension for files without a grammar (needs fallback_chunking)
+
+[path]
+# max_files = 20000           # cap files processed by map/search/history
+# max_file_bytes = 1048576    # skip larger files (generated bundles) in repo maps and search
+
+[map]
+# ignored_references = ["new", "get", "build"]  # names too common to link symbols (replaces the default list)
+# leaf_pruning_threshold = 50000  # above this many symbols, unreferenced ones skip PageRank
+# focus_hops = 2              # reference hops around changed files in review/describe maps
 
 [history]
 # since_days = 180
 # max_files_per_commit = 25
+# min_commits = 10           # fewer mined commits mark results low-confidence
 
 # Custom review rules (injected into LLM prompt)
 # [[rules]]
@@ -824,16 +1874,35 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
-    let config = match &cli.config {
+    // The first --format goes to stdout; only review writes extra formats to --output files
+    let format = cli.formats[0];
+    if cli.formats.len() > 1 && !matches!(cli.command, Some(Command::Review(_))) {
+        miette::bail!(miette::miette!(
+            help = "Run the command once per format",
+            "--format can only be given more than once for the review subcommand"
+        ));
+    }
+    if cli.formats.contains(&OutputFormat::Csv)
+        && !matches!(cli.command, Some(Command::History { .. }))
+    {
+        miette::bail!("CSV output is only supported for the history subcommand.");
+    }
+    if cli.formats.contains(&OutputFormat::Junit)
+        && !matches!(cli.command, Some(Command::Review(_)))
+    {
+        miette::bail!("JUnit output is only supported for the review subcommand.");
+    }
+
+    // An explicit --config wins; otherwise use the nearest .argus.toml up to the git root
+    let config_path = match &cli.config {
+        Some(path) => Some(path.clone()),
+        None => std::env::current_dir()
+            .ok()
+            .and_then(|cwd| argus_core::ArgusConfig::discover(&cwd)),
+    };
+    let config = match &config_path {
         Some(path) => argus_core::ArgusConfig::from_file(path)?,
-        None => {
-            let default_path = std::path::Path::new(".argus.toml");
-            if default_path.exists() {
-                argus_core::ArgusConfig::from_file(default_path)?
-            } else {
-                argus_core::ArgusConfig::default()
-            }
-        }
+        None => argus_core::ArgusConfig::default(),
     };
 
     let use_color = match cli.color {
@@ -843,7 +1912,7 @@ async fn main() -> Result<()> {
     };
 
     if cli.verbose {
-        eprintln!("format: {}", cli.format);
+        eprintln!("format: {}", format);
         if !config.rules.is_empty() {
             let bugs = config.rules.iter().filter(|r| r.severity == "bug").count();
             let warnings = config
@@ -875,19 +1944,93 @@ async fn main() -> Result<()> {
             ref path,
             max_tokens,
             ref focus,
+            max_files,
+            ref diff,
         }) => {
-            let output = argus_repomap::generate_map(path, max_tokens, focus, cli.format)?;
-            print!("{output}");
-        }
-        Some(Command::Diff { ref file }) => {
-            if cli.format == OutputFormat::Sarif {
-                miette::bail!("SARIF output is only supported for the review subcommand.");
+            let path = &resolve_repo(path, &cli.repo);
+            if let Some(range) = diff {
+                let repo = git2::Repository::discover(path).map_err(|_| {
+                    miette::miette!(
+                        help = "--diff reads both revisions from git",
+                        "Not a git repository: {}",
+                        path.display()
+                    )
+                })?;
+                let (base, head) = map_diff_revisions(&repo, range)?;
+                // Map only the --path subdirectory, as the walker does
+                let subdir = repo
+                    .workdir()
+                    .and_then(|workdir| {
+                        let workdir = workdir.canonicalize().ok()?;
+                        let path = path.canonicalize().ok()?;
+                        Some(path.strip_prefix(workdir).ok()?.to_path_buf())
+                    })
+                    .unwrap_or_default();
+                let max_file_bytes = config.path.max_file_bytes;
+                let before = source_files_at(&repo, &base, &subdir, max_file_bytes)?;
+                let after = source_files_at(&repo, &head, &subdir, max_file_bytes)?;
+                let delta = argus_repomap::structure::diff_structure(
+                    &before,
+                    &after,
+                    &config.map.ignored_references,
+                )?;
+                match format {
+                    OutputFormat::Json => {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&delta).into_diagnostic()?
+                        );
+                    }
+                    OutputFormat::Markdown => print!("{}", delta.to_markdown()),
+                    OutputFormat::Text => print!("{}", delta.to_text()),
+                    _ => miette::bail!("map --diff supports text, markdown, and json output."),
+                }
+                return Ok(());
             }
-            let input = read_diff_input(file)?;
+            let options = argus_repomap::MapOptions {
+                max_tokens,
+                focus_files: focus.clone(),
+                format,
+                max_files: max_files.or(config.path.max_files),
+                max_file_bytes: config.path.max_file_bytes,
+                ignored_references: config.map.ignored_references.clone(),
+                leaf_pruning_threshold: config.map.leaf_pruning_threshold,
+                ..argus_repomap::MapOptions::default()
+            };
+            let map = argus_repomap::generate_map_with(path, &options)?;
+            warn_files_capped(map.files_capped, map.files_mapped, "mapped");
+            warn_non_utf8(&map.non_utf8_files, cli.verbose);
+            warn_unmapped(&map, cli.verbose);
+            report_oversized(&map.oversized_files, options.max_file_bytes, cli.verbose);
+            print!("{}", map.output);
+        }
+        Some(Command::Diff {
+            ref file,
+            ref base,
+            ref baseline,
+        }) => {
+            let input = match base {
+                Some(base) => git_diff_against(&resolve_repo(&None, &cli.repo), base)?,
+                None => read_diff_input(file)?,
+            };
             let diffs = argus_difflens::parser::parse_unified_diff(&input)?;
-            let report = argus_difflens::risk::compute_risk(&diffs);
+            let mut report = argus_difflens::risk::compute_risk(&diffs);
+            if let Some(baseline) = baseline {
+                let saved = std::fs::read_to_string(baseline)
+                    .into_diagnostic()
+                    .wrap_err(format!("Failed to read baseline {}", baseline.display()))?;
+                let previous: argus_difflens::risk::RiskReport = serde_json::from_str(&saved)
+                    .map_err(|e| {
+                        miette::miette!(
+                            help = "Save a baseline with: argus --format json diff > risk.json",
+                            "{} is not a JSON risk report: {e}",
+                            baseline.display()
+                        )
+                    })?;
+                report = report.with_baseline(&previous);
+            }
 
-            match cli.format {
+            match format {
                 OutputFormat::Json => {
                     println!(
                         "{}",
@@ -900,7 +2043,14 @@ async fn main() -> Result<()> {
                 OutputFormat::Text => {
                     print!("{report}");
                 }
-                OutputFormat::Sarif => unreachable!(),
+                OutputFormat::Sarif => {
+                    let sarif = argus_difflens::risk::to_sarif(&report);
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&sarif).into_diagnostic()?
+                    );
+                }
+                OutputFormat::Csv | OutputFormat::Junit => unreachable!(),
             }
         }
         Some(Command::Search {
@@ -909,12 +2059,51 @@ async fn main() -> Result<()> {
             limit,
             index,
             reindex,
+            prune,
+            auto_reindex,
+            max_files,
+            expand,
+            stats,
         }) => {
-            if cli.format == OutputFormat::Sarif {
-                miette::bail!("SARIF output is only supported for the review subcommand.");
+            if format == OutputFormat::Sarif {
+                miette::bail!(
+                    "SARIF output is only supported for the review and diff subcommands."
+                );
             }
+            let path = &resolve_repo(path, &cli.repo);
             let index_path = path.join(".argus/index.db");
 
+            if stats {
+                if !index_path.exists() {
+                    miette::bail!(miette::miette!(
+                        help = "Build it with `argus search --index`",
+                        "No search index at {}",
+                        index_path.display()
+                    ));
+                }
+                let code_index = argus_codelens::store::CodeIndex::open_with_tokenizer(
+                    &index_path,
+                    config.embedding.fts_tokenizer,
+                )?;
+                let walk = argus_repomap::walker::walk_repo_detailed(
+                    path,
+                    config.embedding.fallback_chunking,
+                    config.path.max_file_bytes,
+                )?;
+                let repo_files: Vec<PathBuf> = walk.files.into_iter().map(|f| f.path).collect();
+                let breakdown = code_index.breakdown(path, &repo_files)?;
+                match format {
+                    OutputFormat::Json => {
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&breakdown).into_diagnostic()?
+                        );
+                    }
+                    _ => print!("{breakdown}"),
+                }
+                return Ok(());
+            }
+
             // Hint: missing embedding API key
             let emb_env_var = match config.embedding.provider.as_str() {
                 "gemini" => "GEMINI_API_KEY",
@@ -930,33 +2119,164 @@ async fn main() -> Result<()> {
             }
 
             let embedding_client =
-                argus_codelens::embedding::EmbeddingClient::with_config(&config.embedding)?;
+                argus_codelens::embedding::EmbeddingClient::with_config(&config.embedding)?
+                    .with_network(&config.network)?;
 
-            let code_index = argus_codelens::store::CodeIndex::open(&index_path)?;
-            let search = argus_codelens::search::HybridSearch::new(code_index, embedding_client);
+            // Held until the command ends so concurrent indexers can't interleave writes
+            let _index_lock = if index || reindex || prune {
+                Some(argus_codelens::store::IndexLock::acquire(&index_path)?)
+            } else {
+                None
+            };
+            let code_index = argus_codelens::store::CodeIndex::open_with_tokenizer(
+                &index_path,
+                config.embedding.fts_tokenizer,
+            )?
+            .with_stop_words(config.embedding.fts_stop_words.clone());
+            let mut search =
+                argus_codelens::search::HybridSearch::new(code_index, embedding_client);
+            if config.embedding.fallback_chunking {
+                search = search.with_fallback_chunking(argus_codelens::chunker::FixedWindow {
+                    lines: config.embedding.fallback_window_lines,
+                    overlap: config.embedding.fallback_overlap_lines,
+                });
+            }
+            if let Some(max) = max_files.or(config.path.max_files) {
+                search = search.with_max_files(max);
+            }
+            search = search.with_max_file_bytes(config.path.max_file_bytes);
+            if config.embedding.strip_comments {
+                search = search.with_comment_stripping();
+            }
+            if !config.embedding.chunking.is_empty() {
+                search = search.with_chunking(config.embedding.chunking.clone());
+            }
 
             if index {
-                eprintln!("Indexing repository at {} ...", path.display());
+                if !cli.quiet {
+                    eprintln!("Indexing repository at {} ...", path.display());
+                }
                 let stats = search.index_repo(path).await?;
-                eprintln!(
-                    "Indexed {} chunks from {} files ({} bytes)",
-                    stats.total_chunks, stats.total_files, stats.index_size_bytes,
+                warn_files_capped(search.files_capped(), stats.total_files, "indexed");
+                warn_non_utf8(&search.non_utf8_files(), cli.verbose);
+                report_oversized(
+                    &search.oversized_files(),
+                    config.path.max_file_bytes,
+                    cli.verbose,
                 );
+                if !cli.quiet {
+                    if let Some(resumed) = search.resume_stats() {
+                        eprintln!(
+                            "Resumed: {} chunks already present, {} remaining",
+                            resumed.present, resumed.remaining,
+                        );
+                    }
+                    eprintln!(
+                        "Indexed {} chunks from {} files ({} bytes)",
+                        stats.total_chunks, stats.total_files, stats.index_size_bytes,
+                    );
+                    if let Some(rate) = search.throughput() {
+                        eprintln!("Embedded {rate:.1} chunks/sec");
+                    }
+                }
             }
 
             if reindex {
-                eprintln!("Re-indexing changed files at {} ...", path.display());
+                if !cli.quiet {
+                    eprintln!("Re-indexing changed files at {} ...", path.display());
+                }
                 let stats = search.reindex_repo(path).await?;
-                eprintln!(
-                    "Index now has {} chunks from {} files ({} bytes)",
-                    stats.total_chunks, stats.total_files, stats.index_size_bytes,
+                warn_files_capped(search.files_capped(), stats.total_files, "indexed");
+                warn_non_utf8(&search.non_utf8_files(), cli.verbose);
+                report_oversized(
+                    &search.oversized_files(),
+                    config.path.max_file_bytes,
+                    cli.verbose,
                 );
+                if !cli.quiet {
+                    if let Some(resumed) = search.resume_stats() {
+                        eprintln!(
+                            "Resumed: {} chunks already present, {} remaining",
+                            resumed.present, resumed.remaining,
+                        );
+                    }
+                    eprintln!(
+                        "Index now has {} chunks from {} files ({} bytes)",
+                        stats.total_chunks, stats.total_files, stats.index_size_bytes,
+                    );
+                    if let Some(rate) = search.throughput() {
+                        eprintln!("Embedded {rate:.1} chunks/sec");
+                    }
+                }
+            }
+
+            if prune {
+                let pruned = search.prune_repo(path)?;
+                if !cli.quiet {
+                    eprintln!(
+                        "Pruned {} chunks from {} files no longer in the repository walk",
+                        pruned.chunks, pruned.files,
+                    );
+                }
             }
 
             if let Some(q) = query {
-                let results = search.search(q, limit).await?;
+                let terms = match expand {
+                    Some(QueryExpansion::Llm) => match expand_query_with_llm(&config, q).await {
+                        Ok(terms) => terms,
+                        Err(e) => {
+                            eprintln!("warning: LLM query expansion failed ({e}); using synonyms");
+                            argus_codelens::search::synonym_terms(q)
+                        }
+                    },
+                    Some(QueryExpansion::Synonyms) => argus_codelens::search::synonym_terms(q),
+                    None => Vec::new(),
+                };
+                if expand.is_some() && !cli.quiet {
+                    if terms.is_empty() {
+                        eprintln!("No related terms found for \"{q}\"");
+                    } else {
+                        eprintln!("Also searching: {}", terms.join(", "));
+                    }
+                }
+
+                let results = match search.search_expanded(q, &terms, limit).await {
+                    Err(argus_core::ArgusError::DimensionMismatch {
+                        index: stored,
+                        query: dims,
+                    }) if auto_reindex => {
+                        if !cli.quiet {
+                            eprintln!(
+                                "Index has {stored}-dimensional embeddings but the query has {dims}; rebuilding {} ...",
+                                path.display()
+                            );
+                        }
+                        let _rebuild_lock = if _index_lock.is_none() {
+                            Some(argus_codelens::store::IndexLock::acquire(&index_path)?)
+                        } else {
+                            None
+                        };
+                        search.index().clear()?;
+                        let stats = search.index_repo(path).await?;
+                        warn_files_capped(search.files_capped(), stats.total_files, "indexed");
+                        warn_non_utf8(&search.non_utf8_files(), cli.verbose);
+                        report_oversized(
+                            &search.oversized_files(),
+                            config.path.max_file_bytes,
+                            cli.verbose,
+                        );
+                        if !cli.quiet {
+                            eprintln!(
+                                "Indexed {} chunks from {} files ({} bytes)",
+                                stats.total_chunks, stats.total_files, stats.index_size_bytes,
+                            );
+                        }
+                        search.search_expanded(q, &terms, limit).await?
+                    }
+                    results => results?,
+                };
 
-                match cli.format {
+                match format {
                     OutputFormat::Json => {
                         println!(
                             "{}",
@@ -1007,10 +2327,10 @@ async fn main() -> Result<()> {
                             }
                         }
                     }
-                    OutputFormat::Sarif => unreachable!(),
+                    OutputFormat::Sarif | OutputFormat::Csv | OutputFormat::Junit => unreachable!(),
                 }
-            } else if !index && !reindex {
-                miette::bail!("provide a search query, or use --index / --reindex");
+            } else if !index && !reindex && !prune {
+                miette::bail!("provide a search query, or use --index / --reindex / --prune");
             }
         }
         Some(Command::History {
@@ -1019,9 +2339,18 @@ async fn main() -> Result<()> {
             since,
             limit,
             min_coupling,
+            timeline,
+            max_files,
+            min_commits,
         }) => {
-            if cli.format == OutputFormat::Sarif {
-                miette::bail!("SARIF output is only supported for the review subcommand.");
+            let path = &resolve_repo(path, &cli.repo);
+            if format == OutputFormat::Sarif {
+                miette::bail!(
+                    "SARIF output is only supported for the review and diff subcommands."
+                );
+            }
+            if timeline && !matches!(analysis, HistoryAnalysis::All | HistoryAnalysis::Ownership) {
+                miette::bail!("--timeline requires --analysis ownership or --analysis all.");
             }
 
             // Hint: not a git repository
@@ -1038,13 +2367,45 @@ async fn main() -> Result<()> {
                 ..argus_gitpulse::mining::MiningOptions::default()
             };
 
-            eprintln!(
-                "Mining git history at {} (last {} days)...",
-                path.display(),
-                since
-            );
-            let commits = argus_gitpulse::mining::mine_history(path, &options)?;
-            eprintln!("Analyzed {} commits.", commits.len());
+            if !cli.quiet {
+                eprintln!(
+                    "Mining git history at {} (last {} days)...",
+                    path.display(),
+                    since
+                );
+            }
+            let cache_path = path.join(".argus/history-cache.db");
+            let (mut commits, reused) =
+                match argus_gitpulse::cache::mine_history_cached(path, &options, &cache_path) {
+                    Ok(history) => (history.commits, history.reused),
+                    Err(e) => {
+                        eprintln!("warning: history cache unavailable ({e}), mining without it");
+                        (argus_gitpulse::mining::mine_history(path, &options)?, 0)
+                    }
+                };
+            if !cli.quiet {
+                if reused > 0 {
+                    eprintln!("Analyzed {} commits ({reused} from cache).", commits.len());
+                } else {
+                    eprintln!("Analyzed {} commits.", commits.len());
+                }
+            }
+            let depth = argus_gitpulse::mining::HistoryDepth::of(
+                path,
+                commits.len(),
+                min_commits.unwrap_or(config.history.min_commits),
+            )?;
+            let depth_warning = depth.warning();
+            if let Some(warning) = &depth_warning {
+                eprintln!("warning: {warning}");
+            }
+            let low_confidence = depth_warning
+                .as_deref()
+                .filter(|_| depth.is_low_confidence());
+            if let Some(max) = max_files.or(config.path.max_files) {
+                let dropped = argus_gitpulse::mining::cap_files(&mut commits, max);
+                warn_files_capped(dropped, max, "analyzed");
+            }
 
             let show_hotspots =
                 matches!(analysis, HistoryAnalysis::All | HistoryAnalysis::Hotspots);
@@ -1052,14 +2413,37 @@ async fn main() -> Result<()> {
                 matches!(analysis, HistoryAnalysis::All | HistoryAnalysis::Coupling);
             let show_ownership =
                 matches!(analysis, HistoryAnalysis::All | HistoryAnalysis::Ownership);
+            let refactor_candidates = if matches!(
+                analysis,
+                HistoryAnalysis::All | HistoryAnalysis::RefactorCandidates
+            ) {
+                let complexity: std::collections::HashMap<String, u32> =
+                    argus_repomap::complexity::repo_complexity(path)?
+                        .into_iter()
+                        .map(|c| (c.path.to_string_lossy().replace('\\', "/"), c.complexity))
+                        .collect();
+                let mut ranked =
+                    argus_gitpulse::refactor::rank_refactor_candidates(&commits, &complexity);
+                ranked.truncate(limit);
+                Some(ranked)
+            } else {
+                None
+            };
 
-            match cli.format {
+            match format {
                 OutputFormat::Json => {
                     let mut json = serde_json::Map::new();
                     json.insert(
                         "commits_analyzed".into(),
                         serde_json::Value::from(commits.len()),
                     );
+                    json.insert(
+                        "low_confidence".into(),
+                        serde_json::Value::from(depth.is_low_confidence()),
+                    );
+                    if let Some(warning) = &depth_warning {
+                        json.insert("warning".into(), serde_json::Value::from(warning.as_str()));
+                    }
 
                     if show_hotspots {
                         let hotspots = argus_gitpulse::hotspots::detect_hotspots(path, &commits)?;
@@ -1085,6 +2469,21 @@ async fn main() -> Result<()> {
                             serde_json::to_value(&ownership).into_diagnostic()?,
                         );
                     }
+                    if timeline {
+                        let timelines =
+                            argus_gitpulse::ownership::analyze_ownership_timeline(&commits)?;
+                        let top: Vec<_> = timelines.into_iter().take(limit).collect();
+                        json.insert(
+                            "ownership_timeline".into(),
+                            serde_json::to_value(&top).into_diagnostic()?,
+                        );
+                    }
+                    if let Some(candidates) = &refactor_candidates {
+                        json.insert(
+                            "refactor_candidates".into(),
+                            serde_json::to_value(candidates).into_diagnostic()?,
+                        );
+                    }
 
                     println!(
                         "{}",
@@ -1095,6 +2494,9 @@ async fn main() -> Result<()> {
                 OutputFormat::Markdown => {
                     println!("# Git History Analysis\n");
                     println!("**Commits analyzed:** {}\n", commits.len());
+                    if let Some(warning) = low_confidence {
+                        println!("> **Low confidence:** {warning}\n");
+                    }
 
                     if show_hotspots {
                         let hotspots = argus_gitpulse::hotspots::detect_hotspots(path, &commits)?;
@@ -1171,8 +2573,59 @@ async fn main() -> Result<()> {
                             println!();
                         }
                     }
+
+                    if timeline {
+                        let timelines =
+                            argus_gitpulse::ownership::analyze_ownership_timeline(&commits)?;
+                        println!("## Ownership Timeline\n");
+                        if timelines.is_empty() {
+                            println!("No ownership history.\n");
+                        } else {
+                            println!("| File | Handoffs | Dominant author by month |");
+                            println!("|------|----------|--------------------------|");
+                            for t in timelines.iter().take(limit) {
+                                let months: Vec<String> = t
+                                    .buckets
+                                    .iter()
+                                    .map(|b| format!("{}: {}", b.month, b.dominant_author))
+                                    .collect();
+                                println!(
+                                    "| `{}` | {} | {} |",
+                                    t.path,
+                                    t.handoffs,
+                                    months.join(", ")
+                                );
+                            }
+                            println!();
+                        }
+                    }
+
+                    if let Some(candidates) = &refactor_candidates {
+                        println!("## Refactoring Candidates\n");
+                        if candidates.is_empty() {
+                            println!("No refactoring candidates.\n");
+                        } else {
+                            println!("| Rank | File | Score | Complexity | Churn | Revisions |");
+                            println!("|------|------|-------|------------|-------|-----------|");
+                            for (i, c) in candidates.iter().enumerate() {
+                                println!(
+                                    "| {} | `{}` | {:.0} | {} | {} | {} |",
+                                    i + 1,
+                                    c.path,
+                                    c.score,
+                                    c.complexity,
+                                    c.total_churn,
+                                    c.revisions,
+                                );
+                            }
+                            println!();
+                        }
+                    }
                 }
                 OutputFormat::Text => {
+                    if let Some(warning) = low_confidence {
+                        println!("LOW CONFIDENCE: {warning}\n");
+                    }
                     if show_hotspots {
                         let hotspots = argus_gitpulse::hotspots::detect_hotspots(path, &commits)?;
                         println!("Hotspots (top {limit}):");
@@ -1237,31 +2690,212 @@ async fn main() -> Result<()> {
                         }
                         println!();
                     }
+
+                    if timeline {
+                        let timelines =
+                            argus_gitpulse::ownership::analyze_ownership_timeline(&commits)?;
+                        println!("Ownership Timeline (top {limit}):");
+                        println!("{:-<72}", "");
+                        if timelines.is_empty() {
+                            println!("  No ownership history.");
+                        }
+                        for t in timelines.iter().take(limit) {
+                            println!("  {} (handoffs={})", t.path, t.handoffs);
+                            let mut previous: Option<&str> = None;
+                            for b in &t.buckets {
+                                let marker = match previous {
+                                    Some(p) if p != b.dominant_author => "  <- handoff",
+                                    _ => "",
+                                };
+                                println!(
+                                    "    {}  {} ({}/{} commits){marker}",
+                                    b.month, b.dominant_author, b.dominant_commits, b.total_commits,
+                                );
+                                previous = Some(&b.dominant_author);
+                            }
+                        }
+                        println!();
+                    }
+
+                    if let Some(candidates) = &refactor_candidates {
+                        println!("Refactoring Candidates (complexity × churn, top {limit}):");
+                        println!("{:-<72}", "");
+                        if candidates.is_empty() {
+                            println!("  No refactoring candidates.");
+                        }
+                        for (i, c) in candidates.iter().enumerate() {
+                            println!(
+                                "{:>2}. {:<40} score={:.0}  complexity={}  churn={}  rev={}",
+                                i + 1,
+                                c.path,
+                                c.score,
+                                c.complexity,
+                                c.total_churn,
+                                c.revisions,
+                            );
+                        }
+                        println!();
+                    }
+                }
+                OutputFormat::Sarif | OutputFormat::Junit => unreachable!(),
+                OutputFormat::Csv => {
+                    // One clean table per analysis; several analyses become titled blocks
+                    let mut sections: Vec<(&str, String)> = Vec::new();
+                    if show_hotspots {
+                        let hotspots = argus_gitpulse::hotspots::detect_hotspots(path, &commits)?;
+                        let top: Vec<_> = hotspots.into_iter().take(limit).collect();
+                        sections.push(("hotspots", argus_gitpulse::csv::hotspots_csv(&top)));
+                    }
+                    if show_coupling {
+                        let coupling =
+                            argus_gitpulse::coupling::detect_coupling(&commits, min_coupling, 3)?;
+                        let top: Vec<_> = coupling.into_iter().take(limit).collect();
+                        sections.push(("coupling", argus_gitpulse::csv::coupling_csv(&top)));
+                    }
+                    if show_ownership {
+                        let ownership = argus_gitpulse::ownership::analyze_ownership(&commits)?;
+                        sections.push((
+                            "ownership",
+                            argus_gitpulse::csv::ownership_csv(&ownership.files),
+                        ));
+                    }
+                    if timeline {
+                        let timelines =
+                            argus_gitpulse::ownership::analyze_ownership_timeline(&commits)?;
+                        let top: Vec<_> = timelines.into_iter().take(limit).collect();
+                        sections.push((
+                            "ownership_timeline",
+                            argus_gitpulse::csv::timeline_csv(&top),
+                        ));
+                    }
+                    if let Some(candidates) = &refactor_candidates {
+                        sections.push((
+                            "refactor_candidates",
+                            argus_gitpulse::csv::refactor_csv(candidates),
+                        ));
+                    }
+
+                    if let [(_, table)] = sections.as_slice() {
+                        print!("{table}");
+                    } else {
+                        let blocks: Vec<String> = sections
+                            .iter()
+                            .map(|(name, table)| format!("# {name}\n{table}"))
+                            .collect();
+                        print!("{}", blocks.join("\n"));
+                    }
+                }
+            }
+        }
+        Some(Command::Complexity {
+            ref path,
+            threshold,
+            limit,
+        }) => {
+            let path = &resolve_repo(path, &cli.repo);
+            if format == OutputFormat::Sarif {
+                miette::bail!(
+                    "SARIF output is only supported for the review and diff subcommands."
+                );
+            }
+
+            let mut report =
+                argus_repomap::complexity::complexity_report(path, config.path.max_file_bytes)?;
+            match threshold {
+                Some(threshold) => report = report.with_threshold(threshold),
+                None => report.truncate(limit),
+            }
+
+            match format {
+                OutputFormat::Json => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&report).into_diagnostic()?
+                    );
                 }
-                OutputFormat::Sarif => unreachable!(),
+                OutputFormat::Markdown => print!("{}", report.to_markdown()),
+                OutputFormat::Text => print!("{report}"),
+                OutputFormat::Sarif | OutputFormat::Csv | OutputFormat::Junit => unreachable!(),
+            }
+
+            if report.exceeds_threshold() {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Review(args)) => {
+            let ReviewArgs {
+                ref pr,
+                ref file,
+                post_comments,
+                mode,
+                post_only_if_findings,
+                post_check,
+                ref repo,
+                ref skip_pattern,
+                include_suggestions,
+                focus,
+                ref instructions,
+                fail_on,
+                show_filtered,
+                rules_report,
+                apply_patches,
+                interactive,
+                no_self_reflection,
+                incremental,
+                ref base_sha,
+                copy,
+                ref commit,
+                ref compare,
+                include_preexisting,
+                ref before,
+                ref after,
+                staged,
+                unstaged,
+                all,
+                full,
+                full_limit,
+                print_metadata,
+                vouch,
+                skip,
+                offline,
+                dry_run,
+                ref output,
+                blame,
+                summary_only,
+                no_summary,
+                no_history,
+                seed,
+                ref suppress,
+                group_by,
+            } = *args;
+            let repo = &repo
+                .clone()
+                .or_else(|| cli.repo.clone())
+                .or_else(|| full.then(|| PathBuf::from(".")));
+            if interactive && !std::io::stdout().is_terminal() {
+                miette::bail!(miette::miette!(
+                    help = "Run it directly in a terminal, without redirecting stdout",
+                    "--interactive needs a terminal on stdout"
+                ));
             }
-        }
-        Some(Command::Review {
-            ref pr,
-            ref file,
-            post_comments,
-            ref repo,
-            ref skip_pattern,
-            include_suggestions,
-            fail_on,
-            show_filtered,
-            apply_patches,
-            no_self_reflection,
-            incremental,
-            ref base_sha,
-            copy,
-            ref commit,
-            print_metadata,
-            vouch,
-            skip,
-        }) => {
+            if summary_only && matches!(format, OutputFormat::Sarif | OutputFormat::Junit) {
+                miette::bail!(
+                    "--summary-only supports text, markdown, and json output, not {format}."
+                );
+            }
+            // Each extra --format needs its own --output file; check before any LLM work
+            let extra_formats = &cli.formats[1..];
+            if extra_formats.len() != output.len() {
+                miette::bail!(miette::miette!(
+                    help = "Pair every --format after the first with an --output file, e.g.\n       argus review --format text --format sarif --output results.sarif",
+                    "{} extra --format value(s) but {} --output file(s)",
+                    extra_formats.len(),
+                    output.len()
+                ));
+            }
+
             // Warn when no config file exists (config will use defaults)
-            if cli.config.is_none() && !std::path::Path::new(".argus.toml").exists() {
+            if config_path.is_none() && !cli.quiet {
                 eprintln!(
                     "hint: no .argus.toml found, using defaults. Run 'argus init' to create one."
                 );
@@ -1283,6 +2917,21 @@ async fn main() -> Result<()> {
                 return Ok(());
             }
 
+            // Handle --suppress early: it only edits the allowlist
+            if !suppress.is_empty() {
+                for fingerprint in suppress {
+                    if argus_review::suppress::Allowlist::append(&repo_root, fingerprint)? {
+                        eprintln!(
+                            "Suppressed {fingerprint} in {}",
+                            argus_review::suppress::ALLOWLIST_PATH
+                        );
+                    } else {
+                        eprintln!("{fingerprint} is already suppressed");
+                    }
+                }
+                return Ok(());
+            }
+
             // Determine diff input and current HEAD (for state saving)
             let (diff_input, current_head_sha) = if let Some(pr_ref) = pr {
                 let (owner, repo, pr_number) = argus_review::github::parse_pr_reference(pr_ref)?;
@@ -1290,6 +2939,8 @@ async fn main() -> Result<()> {
                 (github.get_pr_diff(&owner, &repo, pr_number).await?, None)
             } else if let Some(file_path) = file {
                 (read_diff_input(&Some(file_path.clone()))?, None)
+            } else if let (Some(before), Some(after)) = (before, after) {
+                (diff_files(before, after, &repo_root)?, None)
             } else if let Some(commit_ref) = commit {
                 // Review already-committed changes
                 // Check if commit_ref is a range (contains "..") or a single commit
@@ -1392,9 +3043,11 @@ async fn main() -> Result<()> {
                     if let Some(s) = state {
                         s.last_reviewed_sha
                     } else {
-                        eprintln!(
-                            "No previous review state found. Reviewing uncommitted changes (HEAD)."
-                        );
+                        if !cli.quiet {
+                            eprintln!(
+                                "No previous review state found. Reviewing uncommitted changes (HEAD)."
+                            );
+                        }
                         "HEAD".to_string()
                     }
                 };
@@ -1414,63 +3067,321 @@ async fn main() -> Result<()> {
                     String::from_utf8_lossy(&diff_output.stdout).to_string(),
                     Some(current_head),
                 )
+            } else if let Some(range) = compare {
+                let Some((base, head)) = argus_review::compare::parse_compare_range(range) else {
+                    miette::bail!(miette::miette!(
+                        help = "Pass a range such as main..feature",
+                        "Invalid --compare range: {range}"
+                    ));
+                };
+                (
+                    git_diff_refs(&repo_root, &[&base, &head], &format!("{base}...{head}"))?,
+                    None,
+                )
+            } else if staged || unstaged || all {
+                let args: &[&str] = if staged {
+                    &["--cached"]
+                } else if unstaged {
+                    &[]
+                } else {
+                    &["HEAD"]
+                };
+                (git_working_diff(&repo_root, args)?, None)
+            } else if full {
+                let review = argus_review::full::build_full_review(
+                    &repo_root,
+                    full_limit,
+                    config.review.max_diff_tokens,
+                    config.path.max_file_bytes,
+                )?;
+                if review.files.is_empty() {
+                    miette::bail!(
+                        "No source files with a supported language under {}",
+                        repo_root.display()
+                    );
+                }
+                if !cli.quiet {
+                    eprintln!(
+                        "Full review: {} of {} files, riskiest first (change with --full-limit)",
+                        review.files.len(),
+                        review.total_files
+                    );
+                }
+                if cli.verbose {
+                    for file in &review.files {
+                        eprintln!(
+                            "  {}  complexity={} revisions={}",
+                            file.path.display(),
+                            file.complexity,
+                            file.revisions
+                        );
+                    }
+                }
+                (review.diff, None)
             } else {
                 (read_diff_input(&None)?, None)
             };
 
+            // What the author says the change is for, as prompt context; a dry
+            // run or an offline review never sends a prompt, so fetch nothing
+            let intent = if !config.review.include_intent || offline || dry_run {
+                None
+            } else if let Some(pr_ref) = pr {
+                let (owner, repo, pr_number) = argus_review::github::parse_pr_reference(pr_ref)?;
+                let github = argus_review::github::GitHubClient::new(None)?;
+                match github.get_pr_description(&owner, &repo, pr_number).await {
+                    Ok(description) => Some(description),
+                    Err(e) => {
+                        if !cli.quiet {
+                            eprintln!("warning: could not fetch the PR description: {e}");
+                        }
+                        None
+                    }
+                }
+            } else {
+                let range = commit.clone().or_else(|| {
+                    compare
+                        .as_deref()
+                        .and_then(argus_review::compare::parse_compare_range)
+                        .map(|(base, head)| format!("{base}..{head}"))
+                });
+                match range.map(|range| argus_review::intent::commit_messages(&repo_root, &range)) {
+                    Some(Ok(messages)) => Some(messages),
+                    Some(Err(e)) => {
+                        if !cli.quiet {
+                            eprintln!("warning: could not read commit messages: {e}");
+                        }
+                        None
+                    }
+                    None => None,
+                }
+            }
+            .filter(|intent| !intent.trim().is_empty());
+
             // Hint: empty diff input from stdin/git
             if diff_input.trim().is_empty() && pr.is_none() {
                 miette::bail!(miette::miette!(
-                    help = "Pipe a diff to argus, e.g.: git diff | argus review --repo .\n       Or use --file <path>, --pr owner/repo#123, --commit <ref>, or --incremental",
+                    help = "Pipe a diff to argus, e.g.: git diff | argus review --repo .\n       Or use --all, --staged, --unstaged, --file <path>, --before <old> --after <new>, --pr owner/repo#123, --commit <ref>, or --incremental",
                     "Empty diff input"
                 ));
             }
 
-            let diffs = argus_difflens::parser::parse_unified_diff(&diff_input)?;
+            // A `git format-patch` series is reviewed patch by patch, each with
+            // its commit message as context
+            let mut patches = Vec::new();
+            if argus_difflens::mbox::is_mbox(&diff_input) {
+                for patch in argus_difflens::mbox::parse_mbox(&diff_input) {
+                    let patch_diffs = argus_difflens::parser::parse_unified_diff(&patch.diff)?;
+                    patches.push((patch, patch_diffs));
+                }
+                if patches.is_empty() {
+                    miette::bail!("No patches with a diff found in the mbox input");
+                }
+            }
+            let diffs = if patches.is_empty() {
+                argus_difflens::parser::parse_unified_diff(&diff_input)?
+            } else {
+                patches
+                    .iter()
+                    .flat_map(|(_, patch_diffs)| patch_diffs.clone())
+                    .collect()
+            };
 
-            // Apply CLI overrides to review config
-            let mut review_config = config.review.clone();
-            if !skip_pattern.is_empty() {
-                review_config
-                    .skip_patterns
-                    .extend(skip_pattern.iter().cloned());
+            // Files under a directory with its own .argus.toml are reviewed with
+            // that config laid over the root one
+            let scopes = argus_review::scopes::discover_scopes(&diffs, &repo_root, &config)?;
+            if cli.verbose && scopes.len() > 1 {
+                let dirs: Vec<String> = scopes[1..]
+                    .iter()
+                    .map(|scope| scope.dir.display().to_string())
+                    .collect();
+                eprintln!("Config scopes: {}", dirs.join(", "));
             }
-            if include_suggestions {
-                review_config.include_suggestions = true;
-                if !review_config
-                    .severity_filter
-                    .contains(&argus_core::Severity::Suggestion)
-                {
+
+            // Apply CLI overrides to each scope's review config
+            let make_pipeline = |config: &argus_core::ArgusConfig| -> miette::Result<_> {
+                let mut review_config = config.review.clone();
+                if !skip_pattern.is_empty() {
                     review_config
+                        .skip_patterns
+                        .extend(skip_pattern.iter().cloned());
+                }
+                if include_suggestions {
+                    review_config.include_suggestions = true;
+                    if !review_config
                         .severity_filter
-                        .push(argus_core::Severity::Suggestion);
+                        .contains(&argus_core::Severity::Suggestion)
+                    {
+                        review_config
+                            .severity_filter
+                            .push(argus_core::Severity::Suggestion);
+                    }
+                }
+                if let Some(focus) = focus {
+                    review_config.focus = focus;
                 }
+                if no_summary {
+                    review_config.summary = false;
+                }
+                if no_history {
+                    review_config.use_history_context = false;
+                }
+                if summary_only {
+                    review_config.summary = true;
+                }
+                if no_self_reflection || summary_only {
+                    review_config.self_reflection = false;
+                }
+
+                // Hint: missing API key — check before creating the LLM client
+                if !dry_run && !offline {
+                    require_api_key(config)?;
+                }
+
+                let mut llm_config = config.llm.clone();
+                if seed.is_some() {
+                    llm_config.seed = seed;
+                }
+                let llm_client = argus_review::llm::LlmClient::new(&llm_config)?
+                    .with_network(&config.network)?;
+                let fallbacks = argus_review::llm::LlmClient::fallbacks(&llm_config)?
+                    .into_iter()
+                    .map(|client| client.with_network(&config.network))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut pipeline = argus_review::pipeline::ReviewPipeline::new(
+                    llm_client,
+                    review_config,
+                    config.rules.clone(),
+                )
+                .with_fallbacks(fallbacks)
+                .with_suppressions(argus_review::suppress::Allowlist::load(&repo_root)?)
+                .with_ignored_references(config.map.ignored_references.clone())
+                .with_leaf_pruning_threshold(config.map.leaf_pruning_threshold)
+                .with_focus_hops(config.map.focus_hops)
+                .with_max_file_bytes(config.path.max_file_bytes)
+                .with_fts_stop_words(config.embedding.fts_stop_words.clone())
+                .with_instructions(instructions.clone())
+                .with_reporter(std::sync::Arc::new(if cli.quiet {
+                    argus_review::progress::TerminalReporter::quiet()
+                } else {
+                    argus_review::progress::TerminalReporter::new()
+                }));
+                if blame {
+                    pipeline = pipeline.with_blame();
+                }
+                Ok(pipeline)
+            };
+            let pipelines = scopes
+                .iter()
+                .map(|scope| make_pipeline(&scope.config))
+                .collect::<miette::Result<Vec<_>>>()?;
+            // An empty diff still gets one (empty) review from the root scope
+            let by_scope = |diffs: Vec<argus_difflens::parser::FileDiff>| {
+                if diffs.is_empty() {
+                    vec![(0, diffs)]
+                } else {
+                    argus_review::scopes::split_by_scope(&scopes, diffs, &repo_root)
+                }
+            };
+
+            if dry_run {
+                let plans = by_scope(diffs)
+                    .into_iter()
+                    .map(|(i, scope_diffs)| pipelines[i].plan(scope_diffs, repo.as_deref()))
+                    .collect();
+                let plan = merge_scopes(plans, argus_review::pipeline::ReviewPlan::merge);
+                match format {
+                    OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(&plan).into_diagnostic()?);
+                    }
+                    _ => print!("{plan}"),
+                }
+                return Ok(());
             }
-            if no_self_reflection {
-                review_config.self_reflection = false;
+
+            // Lines added by the branch, to tell introduced findings from pre-existing ones
+            let compare_lines = compare
+                .is_some()
+                .then(|| argus_review::compare::AddedLines::from_diffs(&diffs));
+            // The pipeline consumes the diffs; the TUI shows hunks from them,
+            // and posted comments need them to place suggestion blocks
+            let interactive_diffs = interactive.then(|| diffs.clone());
+            let posted_diffs = post_comments.then(|| diffs.clone());
+            if post_check && pr.is_none() {
+                miette::bail!("--post-check requires --pr");
             }
 
-            // Hint: missing API key — check before creating the LLM client
-            let llm_env_var = match config.llm.provider.as_str() {
-                "anthropic" => "ANTHROPIC_API_KEY",
-                "gemini" => "GEMINI_API_KEY",
-                _ => "OPENAI_API_KEY",
+            let mut result = if offline {
+                let parts = if patches.is_empty() {
+                    vec![diffs]
+                } else {
+                    patches
+                        .into_iter()
+                        .map(|(_, patch_diffs)| patch_diffs)
+                        .collect()
+                };
+                let results = parts
+                    .into_iter()
+                    .flat_map(by_scope)
+                    .map(|(i, scope_diffs)| {
+                        pipelines[i].review_offline(scope_diffs, repo.as_deref())
+                    })
+                    .collect();
+                argus_review::pipeline::ReviewResult::merge(results)
+            } else if patches.is_empty() {
+                let mut results = Vec::with_capacity(pipelines.len());
+                for (i, scope_diffs) in by_scope(diffs) {
+                    let pipeline = &pipelines[i];
+                    results.push(match &intent {
+                        Some(intent) => {
+                            pipeline
+                                .review_described(scope_diffs, repo.as_deref(), intent)
+                                .await?
+                        }
+                        None => pipeline.review(scope_diffs, repo.as_deref()).await?,
+                    });
+                }
+                merge_scopes(results, argus_review::pipeline::ReviewResult::merge)
+            } else {
+                let total = patches.len();
+                let mut results = Vec::with_capacity(total);
+                for (n, (patch, patch_diffs)) in patches.into_iter().enumerate() {
+                    if !cli.quiet {
+                        eprintln!("Patch {}/{total}: {}", n + 1, patch.subject);
+                    }
+                    for (i, scope_diffs) in by_scope(patch_diffs) {
+                        results.push(
+                            pipelines[i]
+                                .review_described(
+                                    scope_diffs,
+                                    repo.as_deref(),
+                                    &patch.commit_message(),
+                                )
+                                .await?,
+                        );
+                    }
+                }
+                argus_review::pipeline::ReviewResult::merge(results)
             };
-            if config.llm.api_key.is_none() && std::env::var(llm_env_var).is_err() {
-                miette::bail!(miette::miette!(
-                    help = "Set {llm_env_var} or add api_key in your .argus.toml under [llm]",
-                    "No API key configured for LLM provider '{}'",
-                    config.llm.provider
-                ));
-            }
 
-            let llm_client = argus_review::llm::LlmClient::new(&config.llm)?;
-            let pipeline = argus_review::pipeline::ReviewPipeline::new(
-                llm_client,
-                review_config,
-                config.rules.clone(),
-            );
-            let result = pipeline.review(diffs, repo.as_deref()).await?;
+            if let Some(added) = &compare_lines {
+                let preexisting = argus_review::compare::mark_origins(&mut result, added);
+                if include_preexisting {
+                    if !cli.quiet && preexisting > 0 {
+                        eprintln!(
+                            "compare: {preexisting} of {} finding(s) are pre-existing",
+                            result.comments.len()
+                        );
+                    }
+                } else {
+                    let moved = argus_review::compare::drop_preexisting(&mut result, added);
+                    if !cli.quiet && moved > 0 {
+                        eprintln!(
+                            "compare: hid {moved} pre-existing finding(s) (use --include-preexisting to show them)"
+                        );
+                    }
+                }
+            }
 
             // Track iteration count for this commit
             let iteration = if let Some(ref commit_sha) = current_head_sha {
@@ -1527,6 +3438,19 @@ async fn main() -> Result<()> {
                     result.stats.comments_reflected_out,
                     result.comments.len(),
                 );
+                let timings = &result.stats.timings;
+                eprintln!("Timings ({}ms total):", timings.total_ms());
+                eprintln!("  Repo map:        {}ms", timings.repo_map_ms);
+                eprintln!("  Git history:     {}ms", timings.history_ms);
+                eprintln!("  Related code:    {}ms", timings.related_code_ms);
+                eprintln!("  LLM review:      {}ms", timings.llm_ms);
+                if timings.llm_group_ms.len() > 1 {
+                    for (i, ms) in timings.llm_group_ms.iter().enumerate() {
+                        eprintln!("    Group {}: {ms}ms", i + 1);
+                    }
+                }
+                eprintln!("  Self-reflection: {}ms", timings.self_reflection_ms);
+                eprintln!("  Summary:         {}ms", timings.summary_ms);
                 eprintln!("--------------------");
             }
 
@@ -1543,26 +3467,53 @@ async fn main() -> Result<()> {
                 eprintln!("{metadata}");
             }
 
-            match cli.format {
-                OutputFormat::Json => {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&result).into_diagnostic()?
-                    );
+            if let Some(diffs) = &interactive_diffs {
+                let outcome = argus_review::interactive::run(&result.comments, diffs)?;
+                let repo_root = repo.as_deref().unwrap_or(std::path::Path::new("."));
+                let store = argus_review::feedback::FeedbackStore::open(repo_root)?;
+                for c in &outcome.accepted {
+                    store.add_feedback(c, "positive")?;
                 }
-                OutputFormat::Markdown => {
-                    print!("{}", result.to_markdown());
+                for c in &outcome.dismissed {
+                    store.add_feedback(c, "negative")?;
                 }
-                OutputFormat::Sarif => {
-                    let sarif = argus_review::sarif::to_sarif(&result);
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&sarif).into_diagnostic()?
+                let patch_result =
+                    argus_review::patch::apply_patches(&outcome.accepted, repo_root)?;
+                eprintln!(
+                    "{} accepted ({} patches applied), {} dismissed, {} skipped",
+                    outcome.accepted.len(),
+                    patch_result.applied.len(),
+                    outcome.dismissed.len(),
+                    outcome.skipped.len(),
+                );
+                for sp in &patch_result.skipped {
+                    eprintln!(
+                        "  patch skipped: {}:{} — {}",
+                        sp.file_path, sp.line, sp.reason
                     );
                 }
-                OutputFormat::Text => {
-                    print!("{result}");
-                }
+                let dismissed: std::collections::HashSet<String> = outcome
+                    .dismissed
+                    .iter()
+                    .map(argus_review::feedback::compute_comment_hash)
+                    .collect();
+                result.comments.retain(|c| {
+                    !dismissed.contains(&argus_review::feedback::compute_comment_hash(c))
+                });
+            } else if summary_only {
+                print!("{}", render_review_summary(&result, format)?);
+            } else {
+                print!(
+                    "{}",
+                    render_review(&result, format, group_by, config.review.max_message_chars)?
+                );
+            }
+            for (extra, path) in extra_formats.iter().zip(output) {
+                let rendered =
+                    render_review(&result, *extra, group_by, config.review.max_message_chars)?;
+                std::fs::write(path, rendered)
+                    .into_diagnostic()
+                    .wrap_err(format!("Failed to write {}", path.display()))?;
             }
 
             if show_filtered && !result.filtered_comments.is_empty() {
@@ -1576,7 +3527,7 @@ async fn main() -> Result<()> {
                     };
                     eprintln!(
                         "FILTERED: {} | [{label}] {}:{} (confidence: {:.0}%)",
-                        fc.reason,
+                        fc.reason_text(),
                         fc.comment.file_path.display(),
                         fc.comment.line,
                         fc.comment.confidence,
@@ -1586,6 +3537,11 @@ async fn main() -> Result<()> {
                 eprintln!("-------------------------");
             }
 
+            if rules_report {
+                let matches = argus_review::ruletest::rule_matches(&result.comments, &config.rules);
+                eprint!("{}", format_rules_report(&matches));
+            }
+
             if apply_patches {
                 let repo_root = repo.as_deref().unwrap_or(std::path::Path::new("."));
                 let patch_result = argus_review::patch::apply_patches(&result.comments, repo_root)?;
@@ -1608,15 +3564,70 @@ async fn main() -> Result<()> {
                 };
                 let (owner, repo, pr_number) = argus_review::github::parse_pr_reference(pr_ref)?;
                 let github = argus_review::github::GitHubClient::new(None)?;
-                let summary = format!(
-                    "Argus Code Review: {} comments ({} files reviewed)",
-                    result.comments.len(),
-                    result.stats.files_reviewed,
-                );
-                github
-                    .post_review(&owner, &repo, pr_number, &result.comments, &summary)
+                let posted = result.with_truncated_messages(config.review.max_message_chars);
+                match argus_review::github::plan_review_post(
+                    &posted,
+                    post_only_if_findings,
+                    mode == CommentMode::Inline,
+                    group_by.into(),
+                ) {
+                    argus_review::github::ReviewPost::Inline { summary } => {
+                        github
+                            .post_review(
+                                &owner,
+                                &repo,
+                                pr_number,
+                                &posted.comments,
+                                posted_diffs.as_deref().unwrap_or_default(),
+                                &summary,
+                            )
+                            .await?;
+                        if !cli.quiet {
+                            eprintln!("Posted {} comments to {pr_ref}", result.comments.len());
+                        }
+                    }
+                    argus_review::github::ReviewPost::Summary { body } => {
+                        github
+                            .post_summary_review(&owner, &repo, pr_number, &posted.comments, &body)
+                            .await?;
+                        if !cli.quiet {
+                            eprintln!(
+                                "Posted a summary of {} comments to {pr_ref}",
+                                result.comments.len()
+                            );
+                        }
+                    }
+                    argus_review::github::ReviewPost::Nothing => {
+                        if !cli.quiet {
+                            eprintln!("No findings; nothing posted to {pr_ref}");
+                        }
+                    }
+                }
+            }
+
+            if post_check {
+                let Some(pr_ref) = pr else {
+                    miette::bail!("--post-check requires --pr");
+                };
+                let (owner, repo, pr_number) = argus_review::github::parse_pr_reference(pr_ref)?;
+                let github = argus_review::github::GitHubClient::new(None)?;
+                let posted = result.with_truncated_messages(config.review.max_message_chars);
+                let url = github
+                    .post_check_run(
+                        &owner,
+                        &repo,
+                        pr_number,
+                        argus_review::github::check_conclusion(&posted.comments, fail_on),
+                        &posted.to_markdown_collapsible(group_by.into()),
+                        &posted.comments,
+                    )
                     .await?;
-                eprintln!("Posted {} comments to {pr_ref}", result.comments.len());
+                if !cli.quiet {
+                    eprintln!(
+                        "Posted a check run with {} annotations: {url}",
+                        posted.comments.len()
+                    );
+                }
             }
 
             if let Some(head) = current_head_sha {
@@ -1651,14 +3662,16 @@ async fn main() -> Result<()> {
             }
         }
         Some(Command::Mcp { ref path }) => {
-            argus_mcp::server::run_server(path.clone()).await?;
+            argus_mcp::server::run_server(resolve_repo(path, &cli.repo), cli.quiet).await?;
         }
         Some(Command::Describe {
             ref pr,
             ref file,
+            ref base,
             ref repo,
         }) => {
-            if cli.format == OutputFormat::Sarif {
+            let repo = &repo.clone().or_else(|| cli.repo.clone());
+            if format == OutputFormat::Sarif {
                 miette::bail!("SARIF output is not supported for the describe subcommand.");
             }
 
@@ -1666,59 +3679,47 @@ async fn main() -> Result<()> {
                 let (owner, repo, pr_number) = argus_review::github::parse_pr_reference(pr_ref)?;
                 let github = argus_review::github::GitHubClient::new(None)?;
                 github.get_pr_diff(&owner, &repo, pr_number).await?
+            } else if let Some(base) = base {
+                git_diff_against(repo.as_deref().unwrap_or(std::path::Path::new(".")), base)?
             } else {
                 read_diff_input(file)?
             };
 
             if diff_input.trim().is_empty() && pr.is_none() {
                 miette::bail!(miette::miette!(
-                    help = "Pipe a diff to argus, e.g.: git diff main | argus describe\n       Or use --file <path> or --pr owner/repo#123",
+                    help = "Pipe a diff to argus, e.g.: git diff main | argus describe\n       Or use --base <ref>, --file <path>, or --pr owner/repo#123",
                     "Empty diff input"
                 ));
             }
 
             // Hint: missing API key
-            let llm_env_var = match config.llm.provider.as_str() {
-                "anthropic" => "ANTHROPIC_API_KEY",
-                "gemini" => "GEMINI_API_KEY",
-                _ => "OPENAI_API_KEY",
-            };
-            if config.llm.api_key.is_none() && std::env::var(llm_env_var).is_err() {
-                miette::bail!(miette::miette!(
-                    help = "Set {llm_env_var} or add api_key in your .argus.toml under [llm]",
-                    "No API key configured for LLM provider '{}'",
-                    config.llm.provider
-                ));
-            }
+            require_api_key(&config)?;
 
             // Generate repo map if a repo path is provided
             let repo_map = if let Some(root) = repo {
                 let diffs = argus_difflens::parser::parse_unified_diff(&diff_input)?;
                 let focus_files: Vec<std::path::PathBuf> =
                     diffs.iter().map(|d| d.new_path.clone()).collect();
-                match argus_repomap::generate_map(root, 1024, &focus_files, OutputFormat::Text) {
-                    Ok(map) if !map.is_empty() => Some(map),
+                let options = argus_repomap::MapOptions {
+                    focus_files,
+                    focus_hops: Some(config.map.focus_hops),
+                    max_file_bytes: config.path.max_file_bytes,
+                    ignored_references: config.map.ignored_references.clone(),
+                    leaf_pruning_threshold: config.map.leaf_pruning_threshold,
+                    ..argus_repomap::MapOptions::default()
+                };
+                match argus_repomap::generate_map_with(root, &options) {
+                    Ok(map) if !map.output.is_empty() => Some(map.output),
                     _ => None,
                 }
             } else {
                 None
             };
 
-            let llm_client = argus_review::llm::LlmClient::new(&config.llm)?;
+            let llm_client =
+                argus_review::llm::LlmClient::new(&config.llm)?.with_network(&config.network)?;
 
-            let is_tty = std::io::stderr().is_terminal();
-            let spinner = if is_tty {
-                let pb = indicatif::ProgressBar::new_spinner();
-                pb.set_style(
-                    indicatif::ProgressStyle::with_template("{spinner:.cyan} {msg} ({elapsed})")
-                        .unwrap(),
-                );
-                pb.set_message("Generating PR description...");
-                pb.enable_steady_tick(std::time::Duration::from_millis(120));
-                Some(pb)
-            } else {
-                None
-            };
+            let spinner = make_spinner(cli.quiet, "Generating PR description...");
 
             let system = argus_review::prompt::build_describe_system_prompt();
             let user =
@@ -1735,11 +3736,14 @@ async fn main() -> Result<()> {
                 },
             ];
 
-            let response = llm_client.chat(messages).await.inspect_err(|_e| {
-                if let Some(pb) = &spinner {
-                    pb.finish_with_message("Failed");
-                }
-            })?;
+            let response = llm_client
+                .chat_with_format(messages, &argus_review::llm::ResponseFormat::Json(None))
+                .await
+                .inspect_err(|_e| {
+                    if let Some(pb) = &spinner {
+                        pb.finish_with_message("Failed");
+                    }
+                })?;
 
             let desc =
                 argus_review::prompt::parse_describe_response(&response).inspect_err(|_e| {
@@ -1752,7 +3756,7 @@ async fn main() -> Result<()> {
                 pb.finish_with_message("Done");
             }
 
-            match cli.format {
+            match format {
                 OutputFormat::Json => {
                     println!("{}", serde_json::to_string_pretty(&desc).into_diagnostic()?);
                 }
@@ -1772,10 +3776,68 @@ async fn main() -> Result<()> {
                         println!("Labels: {}", desc.labels.join(", "));
                     }
                 }
-                OutputFormat::Sarif => unreachable!(),
+                OutputFormat::Sarif | OutputFormat::Csv | OutputFormat::Junit => unreachable!(),
+            }
+        }
+        Some(Command::Explain {
+            ref file,
+            line,
+            ref repo,
+        }) => {
+            let repo = resolve_repo(repo, &cli.repo);
+            if !matches!(format, OutputFormat::Text | OutputFormat::Markdown) {
+                miette::bail!("explain only supports text and markdown output.");
+            }
+            let file = argus_difflens::parser::normalize_path(file, Some(&repo));
+            let context = argus_review::explain::finding_context(&repo, &file, line)?;
+            let comments = ReviewState::load(&repo)?
+                .map(|s| s.comments)
+                .unwrap_or_default();
+            let finding = argus_review::explain::find_finding(&comments, &file, line);
+
+            require_api_key(&config)?;
+
+            let llm_client =
+                argus_review::llm::LlmClient::new(&config.llm)?.with_network(&config.network)?;
+            let spinner = make_spinner(cli.quiet, "Explaining finding...");
+            let messages = vec![
+                argus_review::llm::ChatMessage {
+                    role: argus_review::llm::Role::System,
+                    content: argus_review::prompt::build_explain_system_prompt(),
+                },
+                argus_review::llm::ChatMessage {
+                    role: argus_review::llm::Role::User,
+                    content: argus_review::prompt::build_explain_prompt(
+                        &file, line, &context, finding,
+                    ),
+                },
+            ];
+            let explanation = llm_client.chat(messages).await.inspect_err(|_e| {
+                if let Some(pb) = &spinner {
+                    pb.finish_with_message("Failed");
+                }
+            })?;
+            if let Some(pb) = spinner {
+                pb.finish_with_message("Done");
+            }
+
+            let location = format!("{}:{line}", file.display());
+            if format == OutputFormat::Markdown {
+                println!("# {location}\n");
+                if let Some(c) = finding {
+                    println!("> **{}:** {}\n", c.severity, c.message);
+                }
+            } else {
+                println!("{location}");
+                if let Some(c) = finding {
+                    println!("[{}] {}", c.severity, c.message);
+                }
+                println!();
             }
+            println!("{}", explanation.trim());
         }
         Some(Command::Feedback { ref path }) => {
+            let path = &resolve_repo(path, &cli.repo);
             let state = ReviewState::load(path)?;
             let comments = state.map(|s| s.comments).unwrap_or_default();
 
@@ -1835,22 +3897,24 @@ async fn main() -> Result<()> {
             }
             println!("Feedback session complete. Thank you!");
         }
-        Some(Command::Hook { action, path }) => match action {
-            HookAction::Install => {
-                // Check that .git/hooks directory exists
-                let hooks_dir = path.join(".git/hooks");
-                if !hooks_dir.is_dir() {
-                    miette::bail!(
-                        "Git hooks directory not found at {}. Is this a git repository?",
-                        hooks_dir.display()
-                    );
-                }
+        Some(Command::Hook { action, ref path }) => {
+            let path = resolve_repo(path, &cli.repo);
+            match action {
+                HookAction::Install => {
+                    // Check that .git/hooks directory exists
+                    let hooks_dir = path.join(".git/hooks");
+                    if !hooks_dir.is_dir() {
+                        miette::bail!(
+                            "Git hooks directory not found at {}. Is this a git repository?",
+                            hooks_dir.display()
+                        );
+                    }
 
-                // Sentinel to identify Argus-managed hooks
-                const SENTINEL: &str = "# ARGUS_MANAGED_HOOK";
+                    // Sentinel to identify Argus-managed hooks
+                    const SENTINEL: &str = "# ARGUS_MANAGED_HOOK";
 
-                let hook_content = format!(
-                    r#"#!/bin/sh
+                    let hook_content = format!(
+                        r#"#!/bin/sh
 # {SENTINEL}
 # Argus pre-commit hook
 # Runs Argus review on staged changes before commit
@@ -1890,58 +3954,59 @@ else
     exit 1
 fi
 "#,
-                    SENTINEL = SENTINEL
-                );
+                        SENTINEL = SENTINEL
+                    );
 
-                let hook_path = path.join(".git/hooks/pre-commit");
-                if hook_path.exists() {
-                    // Check if it's an Argus-managed hook
-                    if let Ok(content) = std::fs::read_to_string(&hook_path) {
-                        if content.contains(SENTINEL) {
-                            // Overwrite existing Argus hook
-                            std::fs::remove_file(&hook_path).into_diagnostic()?;
-                        } else {
-                            miette::bail!(
+                    let hook_path = path.join(".git/hooks/pre-commit");
+                    if hook_path.exists() {
+                        // Check if it's an Argus-managed hook
+                        if let Ok(content) = std::fs::read_to_string(&hook_path) {
+                            if content.contains(SENTINEL) {
+                                // Overwrite existing Argus hook
+                                std::fs::remove_file(&hook_path).into_diagnostic()?;
+                            } else {
+                                miette::bail!(
                                 "pre-commit hook already exists at {}. Remove it first or run 'argus hook uninstall' first.",
                                 hook_path.display()
                             );
-                        }
-                    } else {
-                        miette::bail!(
+                            }
+                        } else {
+                            miette::bail!(
                             "pre-commit hook already exists at {}. Remove it first or run 'argus hook uninstall' first.",
                             hook_path.display()
                         );
+                        }
                     }
+                    std::fs::write(&hook_path, hook_content).into_diagnostic()?;
+                    #[cfg(unix)]
+                    {
+                        use std::os::unix::fs::PermissionsExt;
+                        let perms = std::fs::Permissions::from_mode(0o755);
+                        std::fs::set_permissions(&hook_path, perms).into_diagnostic()?;
+                    }
+                    println!("Installed pre-commit hook at {}", hook_path.display());
                 }
-                std::fs::write(&hook_path, hook_content).into_diagnostic()?;
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let perms = std::fs::Permissions::from_mode(0o755);
-                    std::fs::set_permissions(&hook_path, perms).into_diagnostic()?;
-                }
-                println!("Installed pre-commit hook at {}", hook_path.display());
-            }
-            HookAction::Uninstall => {
-                let hook_path = path.join(".git/hooks/pre-commit");
-                if !hook_path.exists() {
-                    miette::bail!("pre-commit hook not found at {}", hook_path.display());
-                }
+                HookAction::Uninstall => {
+                    let hook_path = path.join(".git/hooks/pre-commit");
+                    if !hook_path.exists() {
+                        miette::bail!("pre-commit hook not found at {}", hook_path.display());
+                    }
 
-                // Check if it's an Argus-managed hook
-                const SENTINEL: &str = "# ARGUS_MANAGED_HOOK";
-                let content = std::fs::read_to_string(&hook_path).into_diagnostic()?;
-                if !content.contains(SENTINEL) {
-                    miette::bail!(
+                    // Check if it's an Argus-managed hook
+                    const SENTINEL: &str = "# ARGUS_MANAGED_HOOK";
+                    let content = std::fs::read_to_string(&hook_path).into_diagnostic()?;
+                    if !content.contains(SENTINEL) {
+                        miette::bail!(
                         "pre-commit hook at {} was not installed by Argus. Remove it manually if needed.",
                         hook_path.display()
                     );
-                }
+                    }
 
-                std::fs::remove_file(&hook_path).into_diagnostic()?;
-                println!("Removed pre-commit hook from {}", hook_path.display());
+                    std::fs::remove_file(&hook_path).into_diagnostic()?;
+                    println!("Removed pre-commit hook from {}", hook_path.display());
+                }
             }
-        },
+        }
         Some(Command::Init) => {
             let path = std::path::Path::new(".argus.toml");
             if path.exists() {
@@ -1950,8 +4015,75 @@ fi
             std::fs::write(path, DEFAULT_CONFIG).into_diagnostic()?;
             println!("Created .argus.toml with default configuration");
         }
-        Some(Command::Doctor) => {
-            run_doctor(&config, cli.format, use_color)?;
+        Some(Command::Doctor { parsers: true }) => {
+            if !run_parser_doctor(format, use_color)? {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Doctor { parsers: false }) => {
+            run_doctor(&config, config_path.as_deref(), format, use_color)?;
+        }
+        Some(Command::RuleTest {
+            ref fixtures,
+            ref cache_dir,
+            no_cache,
+        }) => {
+            if format == OutputFormat::Sarif {
+                miette::bail!("SARIF output is not supported for the rule-test subcommand.");
+            }
+            if config.rules.is_empty() {
+                miette::bail!(miette::miette!(
+                    help = "Add [[rules]] entries to .argus.toml, or pass --config",
+                    "No custom rules configured"
+                ));
+            }
+            let fixture_list = argus_review::ruletest::load_fixtures(fixtures)?;
+
+            let mut llm_client =
+                argus_review::llm::LlmClient::new(&config.llm)?.with_network(&config.network)?;
+            if !no_cache {
+                let dir = cache_dir
+                    .clone()
+                    .unwrap_or_else(|| fixtures.join("llm-cache"));
+                llm_client = llm_client.with_response_cache(dir);
+            }
+            let pipeline = argus_review::pipeline::ReviewPipeline::new(
+                llm_client,
+                config.review.clone(),
+                config.rules.clone(),
+            )
+            .with_reporter(std::sync::Arc::new(if cli.quiet {
+                argus_review::progress::TerminalReporter::quiet()
+            } else {
+                argus_review::progress::TerminalReporter::new()
+            }));
+
+            let mut results = Vec::new();
+            for fixture in &fixture_list {
+                let diffs = argus_difflens::parser::parse_unified_diff(&fixture.diff)?;
+                let review = pipeline.review(diffs, None).await?;
+                results.push(argus_review::ruletest::evaluate(
+                    fixture,
+                    &review.comments,
+                    &config.rules,
+                ));
+            }
+            let report = argus_review::ruletest::RuleTestReport::new(results, &config.rules);
+
+            match format {
+                OutputFormat::Json => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&report).into_diagnostic()?
+                    );
+                }
+                OutputFormat::Markdown => print!("{}", format_rule_test_markdown(&report)),
+                _ => print!("{}", format_rule_test_text(&report)),
+            }
+
+            if !report.passed() {
+                std::process::exit(1);
+            }
         }
         Some(Command::Completions { shell }) => {
             let mut cmd = Cli::command();
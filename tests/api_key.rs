@@ -0,0 +1,48 @@
+use std::process::Command;
+
+const DIFF: &str = "\
+diff --git a/lib.rs b/lib.rs
+--- a/lib.rs
++++ b/lib.rs
+@@ -1 +1 @@
+-fn a() {}
++fn b() {}
+";
+
+fn describe(dir: &std::path::Path, config: &str) -> String {
+    std::fs::write(dir.join(".argus.toml"), config).unwrap();
+    std::fs::write(dir.join("change.diff"), DIFF).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args([
+            "--config",
+            ".argus.toml",
+            "describe",
+            "--file",
+            "change.diff",
+        ])
+        .env_remove("OPENAI_API_KEY")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn missing_api_key_is_reported_for_hosted_providers_only() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let stderr = describe(dir.path(), "[llm]\nprovider = \"openai\"\n");
+    assert!(stderr.contains("No API key configured"), "stderr: {stderr}");
+    assert!(stderr.contains("OPENAI_API_KEY"), "stderr: {stderr}");
+
+    // Nothing listens on the discard port, so the request itself fails
+    let stderr = describe(
+        dir.path(),
+        "[llm]\nprovider = \"ollama\"\nbase_url = \"http://127.0.0.1:9\"\n",
+    );
+    assert!(
+        !stderr.contains("No API key configured"),
+        "stderr: {stderr}"
+    );
+}
@@ -0,0 +1,32 @@
+use std::process::Command;
+
+#[test]
+fn doctor_reports_config_found_in_parent_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("packages/app");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+    std::fs::write(
+        dir.path().join(".argus.toml"),
+        "[[rules]]\nname = \"no-unwrap\"\nseverity = \"warning\"\ndescription = \"No unwrap\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--format", "json", "doctor"])
+        .current_dir(&nested)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let check = json["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "config_file")
+        .unwrap();
+    assert_eq!(check["status"], "pass");
+    let detail = check["detail"].as_str().unwrap();
+    assert!(detail.contains(".argus.toml"), "detail: {detail}");
+    assert!(detail.contains("1 custom rules"), "detail: {detail}");
+}
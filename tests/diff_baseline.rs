@@ -0,0 +1,58 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+const ONE_FILE: &str = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1,2 @@\n a\n+b\n";
+
+fn argus(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn diff_baseline_shows_factor_trend() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("old.patch"), ONE_FILE).unwrap();
+    let two_files = format!("{ONE_FILE}{}", ONE_FILE.replace("a.rs", "b.rs"));
+    std::fs::write(dir.path().join("new.patch"), two_files).unwrap();
+
+    let saved = argus(
+        dir.path(),
+        &["--format", "json", "diff", "--file", "old.patch"],
+    );
+    assert!(saved.status.success());
+    std::fs::write(dir.path().join("risk.json"), &saved.stdout).unwrap();
+
+    let output = argus(
+        dir.path(),
+        &["diff", "--file", "new.patch", "--baseline", "risk.json"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Trend vs baseline:"), "{stdout}");
+    assert!(
+        stdout.contains("diffusion ↑ 100% (20.0 → 40.0)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn diff_baseline_rejects_non_reports() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("old.patch"), ONE_FILE).unwrap();
+    std::fs::write(dir.path().join("risk.json"), "{\"hello\": 1}").unwrap();
+
+    let output = argus(
+        dir.path(),
+        &["diff", "--file", "old.patch", "--baseline", "risk.json"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is not a JSON risk report"), "{stderr}");
+}
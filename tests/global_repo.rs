@@ -0,0 +1,43 @@
+use std::process::Command;
+
+fn map(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(args)
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "argus map failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn global_repo_defaults_subcommand_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub fn alpha_marker() {}\n").unwrap();
+    let repo = dir.path().to_str().unwrap();
+
+    assert!(map(&["--repo", repo, "map"]).contains("alpha_marker"));
+    assert!(map(&["--path", repo, "map"]).contains("alpha_marker"));
+}
+
+#[test]
+fn subcommand_path_overrides_global_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub fn alpha_marker() {}\n").unwrap();
+    let other = tempfile::tempdir().unwrap();
+    std::fs::write(other.path().join("lib.rs"), "pub fn beta_marker() {}\n").unwrap();
+
+    let output = map(&[
+        "--repo",
+        other.path().to_str().unwrap(),
+        "map",
+        "--path",
+        dir.path().to_str().unwrap(),
+    ]);
+    assert!(output.contains("alpha_marker"), "{output}");
+    assert!(!output.contains("beta_marker"), "{output}");
+}
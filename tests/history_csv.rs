@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn history_csv(dir: &Path, analysis: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args([
+            "--quiet",
+            "--format",
+            "csv",
+            "history",
+            "--analysis",
+            analysis,
+        ])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "argus history failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn history_csv_emits_one_table_per_analysis() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+    std::fs::write(dir.path().join("b.rs"), "fn b() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-qm", "init"]);
+
+    let hotspots = history_csv(dir.path(), "hotspots");
+    let mut lines = hotspots.lines();
+    assert_eq!(
+        lines.next(),
+        Some("path,score,revisions,total_churn,relative_churn,current_loc,authors,last_modified")
+    );
+    assert_eq!(lines.count(), 2);
+    assert!(!hotspots.contains('#'));
+
+    let all = history_csv(dir.path(), "all");
+    assert!(all.starts_with("# hotspots\npath,"), "{all}");
+    assert!(all.contains("\n\n# coupling\nfile_a,"), "{all}");
+    assert!(
+        all.contains("\n\n# ownership\npath,total_commits,"),
+        "{all}"
+    );
+}
+
+#[test]
+fn csv_is_rejected_outside_history() {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--format", "csv", "diff"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("only supported for the history subcommand"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,14 @@
+use std::process::Command;
+
+#[test]
+fn timeline_without_ownership_analysis_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["history", "--analysis", "hotspots", "--timeline"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--timeline requires"), "stderr: {stderr}");
+}
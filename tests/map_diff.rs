@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn map_diff(dir: &Path, args: &[&str]) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--format", "json", "map"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "map --diff failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn added_names(delta: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = delta["added"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| format!("{}:{}", symbol["file"].as_str().unwrap(), symbol["name"]))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn map_diff_applies_the_walkers_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    git(root, &["init", "-q", "-b", "main"]);
+    std::fs::create_dir_all(root.join("app")).unwrap();
+    std::fs::write(root.join("app/core.rs"), "fn core() {}\n").unwrap();
+    std::fs::write(root.join(".argusignore"), "gen/\n").unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "init"]);
+
+    git(root, &["checkout", "-q", "-b", "feature"]);
+    std::fs::create_dir_all(root.join("gen")).unwrap();
+    std::fs::create_dir_all(root.join(".github")).unwrap();
+    std::fs::write(root.join("app/new.rs"), "fn fresh() {}\n").unwrap();
+    std::fs::write(root.join("top.rs"), "fn top() {}\n").unwrap();
+    std::fs::write(root.join("gen/out.rs"), "fn generated() {}\n").unwrap();
+    std::fs::write(root.join(".github/hook.rs"), "fn hidden() {}\n").unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "feature"]);
+
+    // main moves on; `...` compares from the merge base, so this is not added
+    git(root, &["checkout", "-q", "main"]);
+    std::fs::write(root.join("later.rs"), "fn later() {}\n").unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "-qm", "main work"]);
+
+    let delta = map_diff(root, &["--diff", "main...feature"]);
+    let added = added_names(&delta);
+    assert_eq!(added, ["app/new.rs:\"fresh\"", "top.rs:\"top\""], "{delta}");
+
+    // --path limits the map to a subdirectory, with paths relative to it
+    let delta = map_diff(root, &["--path", "app", "--diff", "main...feature"]);
+    assert_eq!(added_names(&delta), ["new.rs:\"fresh\""], "{delta}");
+}
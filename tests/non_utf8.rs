@@ -0,0 +1,65 @@
+use std::process::Command;
+
+#[test]
+fn review_skips_files_with_invalid_utf8_in_the_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut diff = b"diff --git a/latin1.rs b/latin1.rs\n\
+--- a/latin1.rs\n\
++++ b/latin1.rs\n\
+@@ -1,0 +1,1 @@\n\
++let s = \"caf\xe9\";\n"
+        .to_vec();
+    diff.extend_from_slice(
+        b"diff --git a/ok.rs b/ok.rs\n\
+--- a/ok.rs\n\
++++ b/ok.rs\n\
+@@ -1,0 +1,1 @@\n\
++let s = \"cafe\";\n",
+    );
+    let path = dir.path().join("change.diff");
+    std::fs::write(&path, diff).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args([
+            "--quiet",
+            "--format",
+            "json",
+            "review",
+            "--dry-run",
+            "--file",
+        ])
+        .arg(&path)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "review failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["filesReviewed"], 1);
+    assert_eq!(plan["filesSkipped"], 1);
+    assert_eq!(plan["groups"][0]["files"][0], "ok.rs");
+}
+
+#[test]
+fn map_warns_about_non_utf8_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("latin1.rs"), b"fn caf\xe9() {}\n").unwrap();
+    std::fs::write(dir.path().join("ok.rs"), "fn ok() {}\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--verbose", "map", "--path", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("skipped 1 file(s) that are not valid UTF-8"),
+        "unexpected stderr: {stderr}"
+    );
+    assert!(stderr.contains("latin1.rs"), "unexpected stderr: {stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ok"));
+}
@@ -0,0 +1,73 @@
+use std::process::Command;
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn quiet_suppresses_status_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("lib.rs"), "fn a() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(
+        dir.path(),
+        &[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-qm",
+            "init",
+        ],
+    );
+
+    let run = |quiet: bool| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_argus"));
+        if quiet {
+            cmd.arg("--quiet");
+        }
+        // One commit is enough history: no low-confidence warning on stderr
+        cmd.args([
+            "history",
+            "--path",
+            ".",
+            "--analysis",
+            "hotspots",
+            "--min-commits",
+            "1",
+        ])
+        .current_dir(dir.path())
+        .output()
+        .unwrap()
+    };
+
+    let loud = run(false);
+    assert!(loud.status.success());
+    let stderr = String::from_utf8_lossy(&loud.stderr);
+    assert!(stderr.contains("Mining git history"), "stderr: {stderr}");
+
+    let quiet = run(true);
+    assert!(quiet.status.success());
+    assert!(
+        quiet.stderr.is_empty(),
+        "stderr: {}",
+        String::from_utf8_lossy(&quiet.stderr)
+    );
+    assert_eq!(quiet.stdout, loud.stdout);
+}
+
+#[test]
+fn quiet_conflicts_with_verbose() {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--quiet", "--verbose", "doctor"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
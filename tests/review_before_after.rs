@@ -0,0 +1,62 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Run `argus review --before <old> --after <new> --dry-run` in `dir`.
+fn dry_run(dir: &Path, before: &str, after: &str) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--format", "json", "review", "--dry-run"])
+        .args(["--before", before, "--after", after])
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn before_and_after_are_diffed_under_the_after_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("backup")).unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("backup/lib.rs"), "fn a() {}\n").unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "fn a() {}\nfn b() { let x = 1; }\n",
+    )
+    .unwrap();
+
+    let output = dry_run(dir.path(), "backup/lib.rs", "./src/lib.rs");
+    assert!(
+        output.status.success(),
+        "argus review failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: Vec<&str> = plan["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|g| g["files"].as_array().unwrap())
+        .map(|f| f.as_str().unwrap())
+        .collect();
+    assert_eq!(files, ["src/lib.rs"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("different extensions"));
+}
+
+#[test]
+fn mismatched_extensions_warn_and_identical_files_fail() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("old.txt"), "one\n").unwrap();
+    std::fs::write(dir.path().join("new.rs"), "one\ntwo\n").unwrap();
+    std::fs::write(dir.path().join("same.rs"), "one\ntwo\n").unwrap();
+
+    let output = dry_run(dir.path(), "old.txt", "new.rs");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("different extensions"));
+
+    let output = dry_run(dir.path(), "new.rs", "same.rs");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("identical"));
+
+    let output = dry_run(dir.path(), "missing.rs", "new.rs");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing.rs"));
+}
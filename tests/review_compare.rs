@@ -0,0 +1,82 @@
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn argus(dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn compare_reviews_only_what_the_branch_changed() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q", "-b", "main"]);
+    std::fs::write(dir.path().join("shared.rs"), "fn a() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-qm", "init"]);
+
+    git(dir.path(), &["checkout", "-q", "-b", "feature"]);
+    std::fs::write(dir.path().join("feature.rs"), "fn f() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-qm", "feature work"]);
+
+    // Moves main ahead; these changes are not the branch's
+    git(dir.path(), &["checkout", "-q", "main"]);
+    std::fs::write(dir.path().join("shared.rs"), "fn a() { 1 }\n").unwrap();
+    git(dir.path(), &["commit", "-qam", "main work"]);
+
+    let output = argus(
+        dir.path(),
+        &[
+            "--quiet",
+            "--format",
+            "json",
+            "review",
+            "--compare",
+            "main..feature",
+            "--dry-run",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "review failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["filesReviewed"], 1);
+    assert_eq!(plan["groups"][0]["files"][0], "feature.rs");
+}
+
+#[test]
+fn compare_rejects_malformed_ranges_and_unknown_refs() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+
+    let output = argus(dir.path(), &["review", "--compare", "main", "--dry-run"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid --compare range: main"), "{stderr}");
+
+    let output = argus(
+        dir.path(),
+        &["review", "--compare", "nope..HEAD", "--dry-run"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown git ref: nope"), "{stderr}");
+
+    let output = argus(dir.path(), &["review", "--include-preexisting"]);
+    assert!(!output.status.success());
+}
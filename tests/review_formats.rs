@@ -0,0 +1,49 @@
+use std::process::Command;
+
+fn argus(args: &[&str]) -> std::process::Output {
+    let dir = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(args)
+        .current_dir(dir.path())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn extra_formats_need_a_matching_output_file() {
+    let output = argus(&["--format", "text", "--format", "sarif", "review"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 extra --format value(s) but 0 --output file(s)"),
+        "unexpected stderr: {stderr}"
+    );
+
+    let output = argus(&["review", "--output", "results.sarif"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn repeated_format_is_only_accepted_by_review() {
+    let output = argus(&["--format", "text", "--format", "json", "doctor"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("more than once for the review subcommand"),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn summary_only_rejects_sarif_and_comment_flags() {
+    let output = argus(&["--format", "sarif", "review", "--summary-only"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--summary-only supports text, markdown, and json"),
+        "unexpected stderr: {stderr}"
+    );
+
+    let output = argus(&["review", "--summary-only", "--copy"]);
+    assert!(!output.status.success());
+}
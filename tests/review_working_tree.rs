@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Files covered by `argus review <scope> --dry-run`.
+fn planned_files(dir: &Path, scope: &str) -> Vec<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--quiet", "--format", "json", "review", scope, "--dry-run"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "argus review {scope} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut files: Vec<String> = plan["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|g| g["files"].as_array().unwrap().clone())
+        .map(|f| f.as_str().unwrap().to_string())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn review_scopes_select_staged_unstaged_or_all_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("staged.rs"), "fn a() {}\n").unwrap();
+    std::fs::write(dir.path().join("unstaged.rs"), "fn b() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-qm", "init"]);
+
+    std::fs::write(dir.path().join("staged.rs"), "fn a() { let x = 1; }\n").unwrap();
+    git(dir.path(), &["add", "staged.rs"]);
+    std::fs::write(dir.path().join("unstaged.rs"), "fn b() { let y = 2; }\n").unwrap();
+
+    assert_eq!(planned_files(dir.path(), "--staged"), ["staged.rs"]);
+    assert_eq!(planned_files(dir.path(), "--unstaged"), ["unstaged.rs"]);
+    assert_eq!(
+        planned_files(dir.path(), "--all"),
+        ["staged.rs", "unstaged.rs"]
+    );
+}
+
+#[test]
+fn review_scopes_are_mutually_exclusive() {
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["review", "--staged", "--all"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
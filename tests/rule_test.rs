@@ -0,0 +1,57 @@
+use std::process::Command;
+
+#[test]
+fn rule_test_requires_configured_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["rule-test", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No custom rules configured"), "{stderr}");
+}
+
+#[test]
+fn rule_test_rejects_sarif_output() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["--format", "sarif", "rule-test", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("SARIF output is not supported"), "{stderr}");
+}
+
+#[test]
+fn rule_test_reports_missing_spec() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+    std::fs::write(
+        dir.path().join(".argus.toml"),
+        "[[rules]]\nname = \"no-unwrap\"\nseverity = \"warning\"\ndescription = \"No unwrap\"\n",
+    )
+    .unwrap();
+    let fixtures = dir.path().join("fixtures");
+    std::fs::create_dir_all(&fixtures).unwrap();
+    std::fs::write(fixtures.join("unwrap.diff"), "").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_argus"))
+        .args(["rule-test", "fixtures"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unwrap.json"), "{stderr}");
+}